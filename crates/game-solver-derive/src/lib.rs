@@ -0,0 +1,365 @@
+//! Macros that write the boilerplate of [`game-solver`](https://docs.rs/game-solver) games.
+//!
+//! [`placement_game`] is an attribute on the `impl Game` block rather than a derive on the game's struct:
+//! the methods it writes belong in that block, which the game writes itself (for its moves),
+//! and a derive can only add items next to the struct, not methods to another impl.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{meta::ParseNestedMeta, parse::Parser, parse_quote, Expr, Ident, ImplItem, ItemImpl};
+
+/// Writes the methods of `Game` that every game of placing pieces on a grid shares:
+/// two players (`PartizanPlayer::Left` first) alternately fill one cell each,
+/// until one wins or the grid is full.
+///
+/// Put it on the `impl Game for ...` block, naming:
+///
+/// - `grid = <field>`: the grid, whose `len()` is its number of cells,
+///   or `cells = <expression>`: the number of cells, where `len()` isn't available,
+/// - `winner = <method>`: a method of the game returning `Option<PartizanPlayer>`,
+///   the player who has won by their placements, if any,
+/// - optionally, `move_count = <field>`: the moves made so far (by default, the `move_count` field),
+/// - optionally, `full_board = Left` or `full_board = Right`: who wins once the grid is full
+///   without a winner (by default, it's a tie).
+///
+/// Then `move_count`, `max_moves` (the number of cells), `player`, and `status` are written,
+/// unless the block already has them.
+///
+/// ```
+/// use game_solver::{
+///     game::{Game, GameState},
+///     player::PartizanPlayer,
+/// };
+/// use game_solver_derive::placement_game;
+///
+/// #[derive(Clone)]
+/// struct Line {
+///     cells: Vec<Option<PartizanPlayer>>,
+///     move_count: usize,
+/// }
+///
+/// impl Line {
+///     // whoever takes two cells next to each other wins
+///     fn pair_owner(&self) -> Option<PartizanPlayer> {
+///         self.cells
+///             .windows(2)
+///             .find_map(|pair| pair[0].filter(|_| pair[0] == pair[1]))
+///     }
+/// }
+///
+/// #[placement_game(grid = cells, winner = pair_owner)]
+/// impl Game for Line {
+///     type Move = usize;
+///     type Iter<'a> = std::vec::IntoIter<usize>;
+///     type MoveError = std::fmt::Error;
+///     type Player = PartizanPlayer;
+///
+///     fn make_move(&mut self, m: &usize) -> Result<(), Self::MoveError> {
+///         self.cells[*m] = Some(self.player());
+///         self.move_count += 1;
+///         Ok(())
+///     }
+///
+///     fn possible_moves(&self) -> Self::Iter<'_> {
+///         (0..self.cells.len())
+///             .filter(|&cell| self.cells[cell].is_none())
+///             .collect::<Vec<_>>()
+///             .into_iter()
+///     }
+/// }
+///
+/// let mut line = Line { cells: vec![None; 3], move_count: 0 };
+/// assert_eq!(line.max_moves(), Some(3));
+/// line.make_move(&0).unwrap();
+/// assert_eq!(line.player(), PartizanPlayer::Right);
+/// line.make_move(&2).unwrap();
+/// line.make_move(&1).unwrap();
+/// assert_eq!(line.state(), GameState::Win(PartizanPlayer::Left));
+/// assert_eq!(line.winner(), Some(PartizanPlayer::Left));
+/// ```
+///
+/// The grid, and how to find the winner, have to be named:
+///
+/// ```compile_fail
+/// # use game_solver::{game::Game, player::PartizanPlayer};
+/// # use game_solver_derive::placement_game;
+/// # #[derive(Clone)]
+/// # struct Line { cells: Vec<Option<PartizanPlayer>>, move_count: usize }
+/// #[placement_game(grid = cells)]
+/// impl Game for Line {
+///     // ...
+/// #     type Move = usize;
+/// #     type Iter<'a> = std::vec::IntoIter<usize>;
+/// #     type MoveError = std::fmt::Error;
+/// #     type Player = PartizanPlayer;
+/// #     fn make_move(&mut self, m: &usize) -> Result<(), Self::MoveError> { Ok(()) }
+/// #     fn possible_moves(&self) -> Self::Iter<'_> { Vec::new().into_iter() }
+/// }
+/// ```
+///
+/// ```compile_fail
+/// # use game_solver::{game::Game, player::PartizanPlayer};
+/// # use game_solver_derive::placement_game;
+/// # #[derive(Clone)]
+/// # struct Line { cells: Vec<Option<PartizanPlayer>>, move_count: usize }
+/// # impl Line { fn pair_owner(&self) -> Option<PartizanPlayer> { None } }
+/// #[placement_game(winner = pair_owner)]
+/// impl Game for Line {
+///     // ...
+/// #     type Move = usize;
+/// #     type Iter<'a> = std::vec::IntoIter<usize>;
+/// #     type MoveError = std::fmt::Error;
+/// #     type Player = PartizanPlayer;
+/// #     fn make_move(&mut self, m: &usize) -> Result<(), Self::MoveError> { Ok(()) }
+/// #     fn possible_moves(&self) -> Self::Iter<'_> { Vec::new().into_iter() }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn placement_game(args: TokenStream, item: TokenStream) -> TokenStream {
+    expand(args.into(), item.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// How many cells the grid has.
+enum Cells {
+    /// The field holding the grid.
+    Grid(Ident),
+    Expression(Expr),
+}
+
+#[derive(Default)]
+struct Args {
+    cells: Option<Cells>,
+    winner: Option<Ident>,
+    move_count: Option<Ident>,
+    full_board: Option<Ident>,
+}
+
+impl Args {
+    fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        let key = meta
+            .path
+            .get_ident()
+            .map(Ident::to_string)
+            .unwrap_or_default();
+
+        match key.as_str() {
+            "grid" | "cells" => {
+                if self.cells.is_some() {
+                    return Err(meta.error("only one of `grid` and `cells` can be given"));
+                }
+
+                self.cells = Some(if key == "grid" {
+                    Cells::Grid(meta.value()?.parse()?)
+                } else {
+                    Cells::Expression(meta.value()?.parse()?)
+                });
+            }
+            "winner" => self.winner = Some(meta.value()?.parse()?),
+            "move_count" => self.move_count = Some(meta.value()?.parse()?),
+            "full_board" => {
+                let player: Ident = meta.value()?.parse()?;
+                if player != "Left" && player != "Right" {
+                    return Err(syn::Error::new(
+                        player.span(),
+                        "expected `Left` or `Right`, the player who wins once the grid is full",
+                    ));
+                }
+
+                self.full_board = Some(player);
+            }
+            _ => {
+                return Err(meta.error(
+                    "unknown argument; expected `grid`, `cells`, `winner`, `move_count`, or `full_board`",
+                ))
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn expand(args: TokenStream2, item: TokenStream2) -> syn::Result<TokenStream2> {
+    let mut parsed = Args::default();
+    syn::meta::parser(|meta| parsed.parse(meta)).parse2(args)?;
+
+    let mut item: ItemImpl = syn::parse2(item)?;
+
+    let is_game = matches!(
+        &item.trait_,
+        Some((None, path, _)) if path.segments.last().is_some_and(|segment| segment.ident == "Game")
+    );
+    if !is_game {
+        return Err(syn::Error::new_spanned(
+            &item.self_ty,
+            "#[placement_game] goes on an `impl Game for ...` block",
+        ));
+    }
+
+    let cells =
+        match parsed.cells {
+            Some(Cells::Grid(grid)) => quote!(self.#grid.len()),
+            Some(Cells::Expression(expression)) => quote!(#expression),
+            None => return Err(syn::Error::new(
+                Span::call_site(),
+                "missing the grid: add `grid = <field>` (whose `len()` is the number of cells) \
+                 or `cells = <expression>`",
+            )),
+        };
+    let Some(winner) = parsed.winner else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "missing `winner = <method>`: a method returning the `Option<PartizanPlayer>` who has won",
+        ));
+    };
+    let move_count = parsed
+        .move_count
+        .unwrap_or_else(|| Ident::new("move_count", Span::call_site()));
+    let full_board = match parsed.full_board {
+        Some(player) => quote!(::game_solver::game::GameStatus::won_by(
+            self,
+            ::game_solver::player::PartizanPlayer::#player
+        )),
+        None => quote!(::game_solver::game::GameStatus::TIE),
+    };
+
+    let defined = item
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            ImplItem::Fn(function) => Some(function.sig.ident.to_string()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    let methods: [(&str, ImplItem); 4] = [
+        (
+            "move_count",
+            parse_quote! {
+                fn move_count(&self) -> usize {
+                    self.#move_count
+                }
+            },
+        ),
+        (
+            "max_moves",
+            parse_quote! {
+                fn max_moves(&self) -> Option<usize> {
+                    Some(#cells)
+                }
+            },
+        ),
+        (
+            "player",
+            parse_quote! {
+                fn player(&self) -> Self::Player {
+                    ::game_solver::player::PartizanPlayer::from_move_count(self.#move_count)
+                }
+            },
+        ),
+        (
+            "status",
+            parse_quote! {
+                fn status(&self) -> ::game_solver::game::GameStatus {
+                    if let Some(winner) = self.#winner() {
+                        ::game_solver::game::GameStatus::won_by(self, winner)
+                    } else if self.#move_count == #cells {
+                        #full_board
+                    } else {
+                        ::game_solver::game::GameStatus::InProgress
+                    }
+                }
+            },
+        ),
+    ];
+
+    for (name, method) in methods {
+        if !defined.iter().any(|defined| defined == name) {
+            item.items.push(method);
+        }
+    }
+
+    Ok(quote!(#item))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(args: TokenStream2) -> String {
+        let item = quote! {
+            impl Game for Board {
+                type Player = PartizanPlayer;
+            }
+        };
+
+        match expand(args, item) {
+            Ok(_) => panic!("expected an error"),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn reports_missing_arguments() {
+        assert!(error(quote!(winner = winner)).starts_with("missing the grid"));
+        assert!(error(quote!(grid = board)).starts_with("missing `winner = <method>`"));
+    }
+
+    #[test]
+    fn reports_invalid_arguments() {
+        assert_eq!(
+            error(quote!(grid = board, cells = 9, winner = winner)),
+            "only one of `grid` and `cells` can be given"
+        );
+        assert!(
+            error(quote!(grid = board, winner = winner, size = 3)).starts_with("unknown argument")
+        );
+        assert!(
+            error(quote!(grid = board, winner = winner, full_board = Up))
+                .starts_with("expected `Left` or `Right`")
+        );
+    }
+
+    #[test]
+    fn only_goes_on_game_impls() {
+        let err = expand(
+            quote!(grid = board, winner = winner),
+            quote!(impl Display for Board {}),
+        )
+        .err()
+        .unwrap();
+
+        assert_eq!(
+            err.to_string(),
+            "#[placement_game] goes on an `impl Game for ...` block"
+        );
+    }
+
+    #[test]
+    fn keeps_methods_that_are_already_written() {
+        let expanded = expand(
+            quote!(cells = 9, winner = winner, full_board = Right),
+            quote! {
+                impl Game for Board {
+                    fn player(&self) -> Self::Player {
+                        PartizanPlayer::Right
+                    }
+                }
+            },
+        )
+        .unwrap();
+        let item: ItemImpl = syn::parse2(expanded).unwrap();
+
+        let methods = item
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                ImplItem::Fn(function) => Some(function.sig.ident.to_string()),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(methods, ["player", "move_count", "max_moves", "status"]);
+    }
+}
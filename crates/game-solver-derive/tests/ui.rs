@@ -0,0 +1,6 @@
+//! The compile errors of `#[placement_game]`, for arguments that are missing or invalid.
+
+#[test]
+fn ui() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
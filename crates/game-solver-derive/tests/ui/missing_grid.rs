@@ -0,0 +1,32 @@
+use game_solver::player::PartizanPlayer;
+use game_solver_derive::placement_game;
+
+#[derive(Clone)]
+struct Line {
+    cells: Vec<Option<PartizanPlayer>>,
+    move_count: usize,
+}
+
+impl Line {
+    fn pair_owner(&self) -> Option<PartizanPlayer> {
+        None
+    }
+}
+
+#[placement_game(winner = pair_owner)]
+impl game_solver::game::Game for Line {
+    type Move = usize;
+    type Iter<'a> = std::vec::IntoIter<usize>;
+    type MoveError = std::fmt::Error;
+    type Player = PartizanPlayer;
+
+    fn make_move(&mut self, _: &usize) -> Result<(), Self::MoveError> {
+        Ok(())
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        Vec::new().into_iter()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use game_solver::player::PartizanPlayer;
+use game_solver_derive::placement_game;
+
+struct Line {
+    cells: Vec<Option<PartizanPlayer>>,
+    move_count: usize,
+}
+
+#[placement_game(grid = cells, winner = pair_owner)]
+impl Line {
+    fn pair_owner(&self) -> Option<PartizanPlayer> {
+        None
+    }
+}
+
+fn main() {}
@@ -0,0 +1,154 @@
+#ifndef GAME_SOLVER_H
+#define GAME_SOLVER_H
+
+/* This file is generated by cbindgen. Don't edit it by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The size of [`GsMoveScore::game_move`], including the NUL terminator.
+ */
+#define GS_MOVE_LEN 32
+
+/**
+ * The result of every function.
+ */
+typedef enum GsStatus {
+  GS_STATUS_OK = 0,
+  /**
+   * A pointer that must be set was null.
+   */
+  GS_STATUS_NULL_POINTER,
+  /**
+   * An argument was out of range, or a string wasn't valid UTF-8.
+   */
+  GS_STATUS_INVALID_ARGUMENT,
+  /**
+   * The move couldn't be parsed, or isn't legal in the current position.
+   */
+  GS_STATUS_INVALID_MOVE,
+  /**
+   * The game is over, so there is nothing to solve.
+   */
+  GS_STATUS_GAME_OVER,
+  /**
+   * The buffer can't hold every result; the needed size was still written.
+   */
+  GS_STATUS_BUFFER_TOO_SMALL,
+  /**
+   * The solver panicked. The game may no longer be usable, and should be freed.
+   */
+  GS_STATUS_PANIC,
+  /**
+   * The solver couldn't make a move the game offered, which is a bug in the game.
+   */
+  GS_STATUS_SOLVE_MOVE_ERROR,
+  /**
+   * The search reached a position after more moves than the game can have, which scores are measured from.
+   */
+  GS_STATUS_MAX_MOVES_EXCEEDED,
+  /**
+   * A finished game scored further from a tie than winning right away, which the solver relies on.
+   */
+  GS_STATUS_SCORE_OUT_OF_BOUNDS,
+  /**
+   * The solver failed in another way.
+   */
+  GS_STATUS_SOLVE_ERROR,
+} GsStatus;
+
+/**
+ * An opaque handle to a game.
+ */
+typedef struct GsGame GsGame;
+
+/**
+ * A move and its score, from the perspective of the player making it.
+ */
+typedef struct GsMoveScore {
+  /**
+   * The move in CLI notation, NUL-terminated.
+   */
+  char game_move[GS_MOVE_LEN];
+  int64_t score;
+} GsMoveScore;
+
+/**
+ * Creates a game of Tic-Tac-Toe with `dimensions` dimensions, each `size` squares long.
+ *
+ * # Safety
+ *
+ * `out` must be null or valid for writes. On success, it points to a game
+ * that must be released with [`gs_free`].
+ */
+enum GsStatus gs_game_new_tictactoe(uintptr_t dimensions, uintptr_t size, struct GsGame **out);
+
+/**
+ * Creates a game of Nim with `heap_count` heaps, the `i`th of which has `heaps[i]` objects.
+ *
+ * # Safety
+ *
+ * `heaps` must be valid for `heap_count` reads, and `out` must be null or valid for writes.
+ * On success, `out` points to a game that must be released with [`gs_free`].
+ */
+enum GsStatus gs_game_new_nim(const uintptr_t *heaps, uintptr_t heap_count, struct GsGame **out);
+
+/**
+ * Plays a move, given in CLI notation. The game is unchanged if the move fails.
+ *
+ * # Safety
+ *
+ * `game` must be null or a game from a `gs_game_new_*` function that wasn't freed,
+ * and `game_move` must be null or a NUL-terminated string.
+ */
+enum GsStatus gs_game_make_move(struct GsGame *game, const char *game_move);
+
+/**
+ * Scores the position from the perspective of the player to move,
+ * writing it to `score`: positive if they win, negative if they lose, and 0 for a tie.
+ *
+ * # Safety
+ *
+ * `game` must be null or a game from a `gs_game_new_*` function that wasn't freed,
+ * and `score` must be null or valid for writes.
+ */
+enum GsStatus gs_solve(const struct GsGame *game, int64_t *score);
+
+/**
+ * Scores every legal move, writing them from best to worst to `scores`,
+ * and how many there are to `count`.
+ *
+ * If `capacity` is too small for every move, nothing is written to `scores`,
+ * [`GsStatus::BufferTooSmall`] is returned, and `count` is still set,
+ * so the call can be retried with a large enough buffer.
+ * It's also returned if a move doesn't fit in [`GS_MOVE_LEN`] bytes.
+ *
+ * # Safety
+ *
+ * `game` must be null or a game from a `gs_game_new_*` function that wasn't freed,
+ * `scores` must be valid for `capacity` writes (it can be null if `capacity` is 0),
+ * and `count` must be null or valid for writes.
+ */
+enum GsStatus gs_move_scores(const struct GsGame *game,
+                             struct GsMoveScore *scores,
+                             uintptr_t capacity,
+                             uintptr_t *count);
+
+/**
+ * Releases a game. Null is ignored.
+ *
+ * # Safety
+ *
+ * `game` must be null or a game from a `gs_game_new_*` function that wasn't freed yet.
+ */
+void gs_free(struct GsGame *game);
+
+/**
+ * A static, NUL-terminated description of `status`.
+ */
+const char *gs_status_message(enum GsStatus status);
+
+#endif  /* GAME_SOLVER_H */
@@ -0,0 +1,331 @@
+//! A C API for the solver and the bundled games, for embedding them in other engines.
+//!
+//! Games are opaque handles created by a `gs_game_new_*` function and released with [`gs_free`].
+//! Moves are passed as NUL-terminated strings in the same notation as the CLI (e.g. `"1-1"`).
+//!
+//! Every function returns a [`GsStatus`] instead of aborting: panics are caught at the boundary,
+//! and reported as [`GsStatus::Panic`]. This relies on panics unwinding, which the workspace's
+//! release profile (`panic = "abort"`) turns off, so release builds of the library use the `release-ffi` profile.
+//!
+//! The header, `include/game_solver.h`, is generated by cbindgen (see `cbindgen.toml`).
+
+use std::{
+    collections::HashMap,
+    ffi::{c_char, CStr},
+    fmt::Display,
+    hash::Hash,
+    panic::{self, AssertUnwindSafe},
+    ptr,
+};
+
+use game_solver::{
+    game::{Game, GameState},
+    move_scores,
+    player::TwoPlayer,
+    solve, GameSolveError,
+};
+use games::{naive_nim::Nim, tic_tac_toe::TicTacToe, util::parse::ParseMove};
+
+/// The size of [`GsMoveScore::game_move`], including the NUL terminator.
+pub const GS_MOVE_LEN: usize = 32;
+
+/// The result of every function.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GsStatus {
+    Ok = 0,
+    /// A pointer that must be set was null.
+    NullPointer,
+    /// An argument was out of range, or a string wasn't valid UTF-8.
+    InvalidArgument,
+    /// The move couldn't be parsed, or isn't legal in the current position.
+    InvalidMove,
+    /// The game is over, so there is nothing to solve.
+    GameOver,
+    /// The buffer can't hold every result; the needed size was still written.
+    BufferTooSmall,
+    /// The solver panicked. The game may no longer be usable, and should be freed.
+    Panic,
+    /// The solver couldn't make a move the game offered, which is a bug in the game.
+    SolveMoveError,
+    /// The search reached a position after more moves than the game can have, which scores are measured from.
+    MaxMovesExceeded,
+    /// A finished game scored further from a tie than winning right away, which the solver relies on.
+    ScoreOutOfBounds,
+    /// The solver failed in another way.
+    SolveError,
+}
+
+impl<T: Game> From<GameSolveError<T>> for GsStatus {
+    fn from(err: GameSolveError<T>) -> Self {
+        match err {
+            GameSolveError::MoveError(_) => Self::SolveMoveError,
+            GameSolveError::MaxMovesExceeded { .. } => Self::MaxMovesExceeded,
+            GameSolveError::ScoreOutOfBounds { .. } => Self::ScoreOutOfBounds,
+            // searches here have no cancellation token, and search every score
+            GameSolveError::CancellationTokenError | GameSolveError::InvalidWindow { .. } => {
+                Self::SolveError
+            }
+        }
+    }
+}
+
+/// A move and its score, from the perspective of the player making it.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct GsMoveScore {
+    /// The move in CLI notation, NUL-terminated.
+    pub game_move: [c_char; GS_MOVE_LEN],
+    pub score: i64,
+}
+
+/// The operations of the C API, for any bundled game.
+trait FfiGame {
+    fn make_move(&mut self, token: &str) -> Result<(), GsStatus>;
+    fn solve(&self) -> Result<isize, GsStatus>;
+    fn move_scores(&self) -> Result<Vec<(String, isize)>, GsStatus>;
+}
+
+impl<T, P> FfiGame for T
+where
+    T: Game<Player = P> + ParseMove + Eq + Hash,
+    P: TwoPlayer + 'static,
+    T::Move: Display,
+{
+    fn make_move(&mut self, token: &str) -> Result<(), GsStatus> {
+        if self.state() != GameState::Playable {
+            return Err(GsStatus::GameOver);
+        }
+
+        let m = self.parse_move(token).map_err(|_| GsStatus::InvalidMove)?;
+
+        // moves can fail halfway through, so they're made on a copy
+        let mut board = self.clone();
+        Game::make_move(&mut board, &m).map_err(|_| GsStatus::InvalidMove)?;
+        *self = board;
+
+        Ok(())
+    }
+
+    fn solve(&self) -> Result<isize, GsStatus> {
+        if self.state() != GameState::Playable {
+            return Err(GsStatus::GameOver);
+        }
+
+        solve(self, &mut HashMap::new(), None, &None).map_err(GsStatus::from)
+    }
+
+    fn move_scores(&self) -> Result<Vec<(String, isize)>, GsStatus> {
+        if self.state() != GameState::Playable {
+            return Err(GsStatus::GameOver);
+        }
+
+        let mut scores = move_scores(self, &mut HashMap::new(), None, &None)
+            .map(|result| result.map(|(m, score)| (m.to_string(), score)))
+            .collect::<Result<Vec<_>, _>>()?;
+        scores.sort_by_key(|(_, score)| -score);
+
+        Ok(scores)
+    }
+}
+
+/// An opaque handle to a game.
+pub struct GsGame(Box<dyn FfiGame>);
+
+/// Runs `f`, turning a panic into [`GsStatus::Panic`].
+fn guard(f: impl FnOnce() -> Result<(), GsStatus>) -> GsStatus {
+    match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => GsStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => GsStatus::Panic,
+    }
+}
+
+/// Writes `game` to `out`, as a handle the caller owns.
+unsafe fn write_game(out: *mut *mut GsGame, game: impl FfiGame + 'static) -> Result<(), GsStatus> {
+    let out = out.as_mut().ok_or(GsStatus::NullPointer)?;
+    *out = Box::into_raw(Box::new(GsGame(Box::new(game))));
+
+    Ok(())
+}
+
+/// Creates a game of Tic-Tac-Toe with `dimensions` dimensions, each `size` squares long.
+///
+/// # Safety
+///
+/// `out` must be null or valid for writes. On success, it points to a game
+/// that must be released with [`gs_free`].
+#[no_mangle]
+pub unsafe extern "C" fn gs_game_new_tictactoe(
+    dimensions: usize,
+    size: usize,
+    out: *mut *mut GsGame,
+) -> GsStatus {
+    guard(|| {
+        if dimensions == 0 || size == 0 {
+            return Err(GsStatus::InvalidArgument);
+        }
+
+        write_game(out, TicTacToe::new(dimensions, size))
+    })
+}
+
+/// Creates a game of Nim with `heap_count` heaps, the `i`th of which has `heaps[i]` objects.
+///
+/// # Safety
+///
+/// `heaps` must be valid for `heap_count` reads, and `out` must be null or valid for writes.
+/// On success, `out` points to a game that must be released with [`gs_free`].
+#[no_mangle]
+pub unsafe extern "C" fn gs_game_new_nim(
+    heaps: *const usize,
+    heap_count: usize,
+    out: *mut *mut GsGame,
+) -> GsStatus {
+    guard(|| {
+        if heaps.is_null() {
+            return Err(GsStatus::NullPointer);
+        }
+
+        let heaps = std::slice::from_raw_parts(heaps, heap_count);
+        write_game(out, Nim::new(heaps.to_vec()))
+    })
+}
+
+/// Plays a move, given in CLI notation. The game is unchanged if the move fails.
+///
+/// # Safety
+///
+/// `game` must be null or a game from a `gs_game_new_*` function that wasn't freed,
+/// and `game_move` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn gs_game_make_move(
+    game: *mut GsGame,
+    game_move: *const c_char,
+) -> GsStatus {
+    guard(|| {
+        let game = game.as_mut().ok_or(GsStatus::NullPointer)?;
+        if game_move.is_null() {
+            return Err(GsStatus::NullPointer);
+        }
+
+        let token = CStr::from_ptr(game_move)
+            .to_str()
+            .map_err(|_| GsStatus::InvalidArgument)?;
+
+        game.0.make_move(token)
+    })
+}
+
+/// Scores the position from the perspective of the player to move,
+/// writing it to `score`: positive if they win, negative if they lose, and 0 for a tie.
+///
+/// # Safety
+///
+/// `game` must be null or a game from a `gs_game_new_*` function that wasn't freed,
+/// and `score` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn gs_solve(game: *const GsGame, score: *mut i64) -> GsStatus {
+    guard(|| {
+        let game = game.as_ref().ok_or(GsStatus::NullPointer)?;
+        let score = score.as_mut().ok_or(GsStatus::NullPointer)?;
+
+        *score = game.0.solve()? as i64;
+
+        Ok(())
+    })
+}
+
+/// Scores every legal move, writing them from best to worst to `scores`,
+/// and how many there are to `count`.
+///
+/// If `capacity` is too small for every move, nothing is written to `scores`,
+/// [`GsStatus::BufferTooSmall`] is returned, and `count` is still set,
+/// so the call can be retried with a large enough buffer.
+/// It's also returned if a move doesn't fit in [`GS_MOVE_LEN`] bytes.
+///
+/// # Safety
+///
+/// `game` must be null or a game from a `gs_game_new_*` function that wasn't freed,
+/// `scores` must be valid for `capacity` writes (it can be null if `capacity` is 0),
+/// and `count` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn gs_move_scores(
+    game: *const GsGame,
+    scores: *mut GsMoveScore,
+    capacity: usize,
+    count: *mut usize,
+) -> GsStatus {
+    guard(|| {
+        let game = game.as_ref().ok_or(GsStatus::NullPointer)?;
+        let count = count.as_mut().ok_or(GsStatus::NullPointer)?;
+
+        let move_scores = game.0.move_scores()?;
+        *count = move_scores.len();
+
+        if capacity < move_scores.len() {
+            return Err(GsStatus::BufferTooSmall);
+        }
+
+        if scores.is_null() && !move_scores.is_empty() {
+            return Err(GsStatus::NullPointer);
+        }
+
+        // leave room for the NUL terminator
+        if move_scores
+            .iter()
+            .any(|(game_move, _)| game_move.len() >= GS_MOVE_LEN)
+        {
+            return Err(GsStatus::BufferTooSmall);
+        }
+
+        for (index, (game_move, score)) in move_scores.into_iter().enumerate() {
+            let mut entry = GsMoveScore {
+                game_move: [0; GS_MOVE_LEN],
+                score: score as i64,
+            };
+            for (dest, byte) in entry.game_move.iter_mut().zip(game_move.bytes()) {
+                *dest = byte as c_char;
+            }
+
+            ptr::write(scores.add(index), entry);
+        }
+
+        Ok(())
+    })
+}
+
+/// Releases a game. Null is ignored.
+///
+/// # Safety
+///
+/// `game` must be null or a game from a `gs_game_new_*` function that wasn't freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn gs_free(game: *mut GsGame) {
+    if !game.is_null() {
+        // dropping a game doesn't panic, but unwinding into C is undefined behavior
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| drop(Box::from_raw(game))));
+    }
+}
+
+/// A static, NUL-terminated description of `status`.
+#[no_mangle]
+pub extern "C" fn gs_status_message(status: GsStatus) -> *const c_char {
+    let message: &'static CStr = match status {
+        GsStatus::Ok => c"ok",
+        GsStatus::NullPointer => c"a required pointer was null",
+        GsStatus::InvalidArgument => c"an argument was invalid",
+        GsStatus::InvalidMove => c"the move is invalid",
+        GsStatus::GameOver => c"the game is over",
+        GsStatus::BufferTooSmall => c"the buffer is too small",
+        GsStatus::Panic => c"the solver panicked",
+        GsStatus::SolveMoveError => c"the solver couldn't make a move the game offered",
+        GsStatus::MaxMovesExceeded => c"the game went past the most moves it can have",
+        GsStatus::ScoreOutOfBounds => {
+            c"a finished game scored further from a tie than winning right away"
+        }
+        GsStatus::SolveError => c"the solver failed",
+    };
+
+    message.as_ptr()
+}
@@ -0,0 +1,98 @@
+/* Plays and solves Tic-Tac-Toe and Nim through the C API. */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "game_solver.h"
+
+#define CHECK(expr)                                                           \
+  do {                                                                        \
+    if (!(expr)) {                                                            \
+      fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #expr); \
+      return 1;                                                               \
+    }                                                                         \
+  } while (0)
+
+static int tic_tac_toe(void) {
+  GsGame *game = NULL;
+  CHECK(gs_game_new_tictactoe(2, 3, &game) == GS_STATUS_OK);
+  CHECK(game != NULL);
+
+  int64_t score = -1;
+  CHECK(gs_solve(game, &score) == GS_STATUS_OK);
+  CHECK(score == 0);
+
+  CHECK(gs_game_make_move(game, "1-1") == GS_STATUS_OK);
+  CHECK(gs_game_make_move(game, "1-1") == GS_STATUS_INVALID_MOVE);
+  CHECK(gs_game_make_move(game, "not a move") == GS_STATUS_INVALID_MOVE);
+  CHECK(gs_game_make_move(game, NULL) == GS_STATUS_NULL_POINTER);
+
+  /* ask for the size first, then fill a buffer of exactly that size */
+  size_t count = 0;
+  CHECK(gs_move_scores(game, NULL, 0, &count) == GS_STATUS_BUFFER_TOO_SMALL);
+  CHECK(count == 8);
+
+  GsMoveScore scores[8];
+  CHECK(gs_move_scores(game, scores, count, &count) == GS_STATUS_OK);
+  CHECK(count == 8);
+  /* the corner replies to the center hold the tie, and come first */
+  CHECK(scores[0].score == 0);
+  CHECK(strlen(scores[0].game_move) == 3);
+  for (size_t i = 1; i < count; i++) {
+    CHECK(scores[i - 1].score >= scores[i].score);
+  }
+
+  gs_free(game);
+  return 0;
+}
+
+static int nim(void) {
+  const size_t heaps[] = {3, 5, 7};
+  GsGame *game = NULL;
+  CHECK(gs_game_new_nim(heaps, 3, &game) == GS_STATUS_OK);
+
+  int64_t score = 0;
+  CHECK(gs_solve(game, &score) == GS_STATUS_OK);
+  CHECK(score > 0);
+
+  /* 3 ^ 5 ^ 6 is 0, so this leaves the opponent losing */
+  CHECK(gs_game_make_move(game, "2-1") == GS_STATUS_OK);
+  CHECK(gs_solve(game, &score) == GS_STATUS_OK);
+  CHECK(score < 0);
+
+  gs_free(game);
+  return 0;
+}
+
+static int errors(void) {
+  GsGame *game = NULL;
+  CHECK(gs_game_new_tictactoe(0, 3, &game) == GS_STATUS_INVALID_ARGUMENT);
+  CHECK(game == NULL);
+  CHECK(gs_game_new_tictactoe(2, 3, NULL) == GS_STATUS_NULL_POINTER);
+  CHECK(gs_solve(NULL, NULL) == GS_STATUS_NULL_POINTER);
+  CHECK(strcmp(gs_status_message(GS_STATUS_GAME_OVER), "the game is over") == 0);
+  CHECK(strcmp(gs_status_message(GS_STATUS_SOLVE_ERROR), "the solver failed") == 0);
+
+  CHECK(gs_game_new_tictactoe(2, 3, &game) == GS_STATUS_OK);
+  const char *moves[] = {"0-0", "1-0", "0-1", "1-1", "0-2"};
+  for (size_t i = 0; i < 5; i++) {
+    CHECK(gs_game_make_move(game, moves[i]) == GS_STATUS_OK);
+  }
+
+  int64_t score = 0;
+  CHECK(gs_solve(game, &score) == GS_STATUS_GAME_OVER);
+  CHECK(gs_game_make_move(game, "2-2") == GS_STATUS_GAME_OVER);
+
+  gs_free(game);
+  gs_free(NULL);
+  return 0;
+}
+
+int main(void) {
+  if (tic_tac_toe() || nim() || errors()) {
+    return 1;
+  }
+
+  puts("ok");
+  return 0;
+}
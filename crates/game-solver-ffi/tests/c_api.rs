@@ -0,0 +1,55 @@
+//! Compiles and runs a C program against the static library, to check the header matches the ABI.
+
+use std::{env, path::PathBuf, process::Command};
+
+#[cfg(unix)]
+#[test]
+fn c_program() {
+    // the test binary lives in target/<profile>/deps, next to the library one directory up
+    let profile_dir = env::current_exe()
+        .unwrap()
+        .parent()
+        .and_then(|deps| deps.parent())
+        .unwrap()
+        .to_path_buf();
+
+    // `cargo test` only builds the rlib, so the static library is built on its own, with the same profile
+    let profile = match profile_dir.file_name().unwrap().to_str().unwrap() {
+        "debug" => "dev",
+        profile => profile,
+    };
+    let status = Command::new(env!("CARGO"))
+        .args(["build", "--quiet", "--package", "game-solver-ffi", "--lib"])
+        .args(["--profile", profile])
+        .status()
+        .unwrap();
+    assert!(status.success(), "the library didn't build");
+
+    let library = profile_dir.join("libgame_solver_ffi.a");
+
+    let program = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("game_solver_ffi_smoke");
+
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(compiler)
+        .args([
+            "-std=c99",
+            "-Wall",
+            "-Werror",
+            "-Iinclude",
+            "tests/c/smoke.c",
+        ])
+        .arg(&library)
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&program)
+        .status()
+        .unwrap();
+    assert!(status.success(), "the C program didn't compile");
+
+    let output = Command::new(&program).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "ok\n");
+}
@@ -0,0 +1,28 @@
+//! Checks that the checked-in header is the one cbindgen generates, so it can't drift from the Rust signatures.
+
+use std::{fs, path::PathBuf};
+
+#[test]
+fn header_is_generated_by_cbindgen() {
+    let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap();
+
+    let mut generated = Vec::new();
+    cbindgen::generate_with_config(&crate_dir, config)
+        .unwrap()
+        .write(&mut generated);
+    let generated = String::from_utf8(generated).unwrap();
+
+    let header = fs::read_to_string(crate_dir.join("include/game_solver.h")).unwrap();
+    if header != generated {
+        // left where it can be diffed against the checked-in one
+        let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("game_solver.h");
+        fs::write(&path, &generated).unwrap();
+
+        panic!(
+            "include/game_solver.h is out of date (cbindgen generated {}); regenerate it with \
+             `cbindgen --config cbindgen.toml --output include/game_solver.h`",
+            path.display()
+        );
+    }
+}
@@ -0,0 +1,198 @@
+//! Small games that only need `core` and `alloc`, used to test the solver's core
+//! without its `std` feature: run `cargo test -p game-solver-no-std`.
+//!
+//! These tests live in their own crate because `game-solver`'s benchmarks depend on `games`,
+//! which turns `std` back on for every target of `game-solver` itself.
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::{
+    error::Error,
+    fmt,
+    iter::{repeat, FilterMap, Repeat, Zip},
+    ops::Range,
+};
+
+use game_solver::{
+    game::{Game, GameStatus, Normal},
+    player::{ImpartialPlayer, PartizanPlayer, Player},
+};
+
+/// A move that isn't possible in the position it was made in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IllegalMove;
+
+impl fmt::Display for IllegalMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the move isn't possible in this position")
+    }
+}
+
+impl Error for IllegalMove {}
+
+/// Nim with a fixed number of heaps, where a move takes objects from one heap,
+/// and whoever takes the last object wins.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Nim<const N: usize> {
+    heaps: [usize; N],
+    move_count: usize,
+    max_moves: usize,
+}
+
+impl<const N: usize> Nim<N> {
+    pub fn new(heaps: [usize; N]) -> Self {
+        Self {
+            heaps,
+            move_count: 0,
+            max_moves: heaps.iter().sum(),
+        }
+    }
+
+    /// The xor of the heaps, which is non-zero exactly when the player to move can win.
+    pub fn nim_sum(&self) -> usize {
+        self.heaps.iter().fold(0, |sum, heap| sum ^ heap)
+    }
+}
+
+impl<const N: usize> Normal for Nim<N> {}
+
+impl<const N: usize> Game for Nim<N> {
+    /// The heap, and how many objects to take from it.
+    type Move = (usize, usize);
+    type Iter<'a> = alloc::vec::IntoIter<Self::Move>;
+    type MoveError = IllegalMove;
+    type Player = ImpartialPlayer;
+
+    const IMPARTIAL: bool = true;
+
+    fn move_count(&self) -> usize {
+        self.move_count
+    }
+
+    fn max_moves(&self) -> Option<usize> {
+        Some(self.max_moves)
+    }
+
+    fn make_move(&mut self, &(heap, amount): &Self::Move) -> Result<(), Self::MoveError> {
+        match self.heaps.get_mut(heap) {
+            Some(objects) if (1..=*objects).contains(&amount) => *objects -= amount,
+            _ => return Err(IllegalMove),
+        }
+
+        self.move_count += 1;
+        Ok(())
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        self.heaps
+            .iter()
+            .enumerate()
+            .flat_map(|(heap, &objects)| (1..=objects).map(move |amount| (heap, amount)))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn move_count_hint(&self) -> (usize, Option<usize>) {
+        let count = self.heaps.iter().sum();
+        (count, Some(count))
+    }
+
+    fn status(&self) -> GameStatus {
+        <Self as Normal>::status(self)
+    }
+
+    fn player(&self) -> Self::Player {
+        ImpartialPlayer::Next
+    }
+}
+
+/// The lines of three cells that win Tic-Tac-Toe.
+const LINES: [[usize; 3]; 8] = [
+    [0, 1, 2],
+    [3, 4, 5],
+    [6, 7, 8],
+    [0, 3, 6],
+    [1, 4, 7],
+    [2, 5, 8],
+    [0, 4, 8],
+    [2, 4, 6],
+];
+
+/// Tic-Tac-Toe, where [`PartizanPlayer::Left`] plays first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TicTacToe {
+    /// The cells, row by row: 0 if empty, or 1 + the index of the player who marked it.
+    cells: [u8; 9],
+    move_count: usize,
+}
+
+impl TicTacToe {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Game for TicTacToe {
+    /// The index of the cell to mark.
+    type Move = usize;
+    type Iter<'a> =
+        FilterMap<Zip<Range<usize>, Repeat<[u8; 9]>>, fn((usize, [u8; 9])) -> Option<usize>>;
+    type MoveError = IllegalMove;
+    type Player = PartizanPlayer;
+
+    fn move_count(&self) -> usize {
+        self.move_count
+    }
+
+    fn max_moves(&self) -> Option<usize> {
+        Some(9)
+    }
+
+    fn make_move(&mut self, &m: &Self::Move) -> Result<(), Self::MoveError> {
+        let mark = 1 + self.player().idx() as u8;
+        match self.cells.get_mut(m) {
+            Some(cell) if *cell == 0 => *cell = mark,
+            _ => return Err(IllegalMove),
+        }
+
+        self.move_count += 1;
+        Ok(())
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        (0..9)
+            .zip(repeat(self.cells))
+            .filter_map(|(cell, cells)| (cells[cell] == 0).then_some(cell))
+    }
+
+    fn move_count_hint(&self) -> (usize, Option<usize>) {
+        (9 - self.move_count, Some(9 - self.move_count))
+    }
+
+    fn status(&self) -> GameStatus {
+        for line in LINES {
+            let [a, b, c] = line.map(|cell| self.cells[cell]);
+            if a != 0 && a == b && b == c {
+                let winner = if a == 1 {
+                    PartizanPlayer::Left
+                } else {
+                    PartizanPlayer::Right
+                };
+                return GameStatus::won_by(self, winner);
+            }
+        }
+
+        if self.move_count == 9 {
+            GameStatus::TIE
+        } else {
+            GameStatus::InProgress
+        }
+    }
+
+    fn player(&self) -> Self::Player {
+        PartizanPlayer::from_move_count(self.move_count)
+    }
+}
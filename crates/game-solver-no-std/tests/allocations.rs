@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use counting_allocator::{allocations, CountingAllocator};
+
+use game_solver::{
+    game::{Game, GameStatus, Normal},
+    ordering::MoveOrdering,
+    solve, solve_ordered,
+    stats::Stats,
+};
+use game_solver_no_std::{Nim, TicTacToe};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// A normal play game that hides the [`Game::move_count_hint`] of the game it wraps.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Unhinted<T>(T);
+
+impl<T: Game> Normal for Unhinted<T> {}
+
+impl<T: Game> Game for Unhinted<T> {
+    type Move = T::Move;
+    type Iter<'a>
+        = T::Iter<'a>
+    where
+        T: 'a;
+    type MoveError = T::MoveError;
+    type Player = T::Player;
+
+    fn move_count(&self) -> usize {
+        self.0.move_count()
+    }
+
+    fn max_moves(&self) -> Option<usize> {
+        self.0.max_moves()
+    }
+
+    fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        self.0.make_move(m)
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        self.0.possible_moves()
+    }
+
+    fn status(&self) -> GameStatus {
+        <Self as Normal>::status(self)
+    }
+
+    fn player(&self) -> Self::Player {
+        self.0.player()
+    }
+}
+
+#[test]
+#[cfg_attr(
+    game_solver_check_dominance,
+    ignore = "every solve also searches the dominated moves, twice"
+)]
+fn move_count_hints_save_allocations() {
+    let game = Nim::new([3, 4, 5]);
+    assert_eq!(game.move_count_hint(), (12, Some(12)));
+    assert!(game.has_moves());
+    assert!(!Nim::new([0, 0]).has_moves());
+    assert_eq!(Unhinted(game.clone()).move_count_hint(), (0, None));
+
+    let (hinted, with_hint) = allocations(|| solve(&game, &mut BTreeMap::new(), None, &None));
+    let (unhinted, without_hint) =
+        allocations(|| solve(&Unhinted(game.clone()), &mut BTreeMap::new(), None, &None));
+
+    assert_eq!(hinted.unwrap(), unhinted.unwrap());
+    // finding whether a position is over no longer collects its moves
+    assert!(
+        with_hint < without_hint,
+        "{with_hint} allocations with the hint, {without_hint} without"
+    );
+}
+
+#[test]
+#[cfg_attr(
+    game_solver_check_dominance,
+    ignore = "every solve also searches the dominated moves, twice"
+)]
+fn sorting_a_few_moves_does_not_allocate() {
+    let game = TicTacToe::new();
+    let central = MoveOrdering::Heuristic(Box::new(|_: &TicTacToe, &cell: &usize| {
+        // the center, then the corners
+        [1, 0, 1, 0, 2, 0, 1, 0, 1][cell]
+    }));
+
+    let (unsorted, table) = allocations(|| {
+        solve_ordered(
+            &game,
+            &mut BTreeMap::new(),
+            None,
+            &None,
+            &MoveOrdering::GameOrder,
+        )
+    });
+
+    for ordering in [MoveOrdering::TtFirst, central] {
+        let stats = Stats::new(game.player(), game.move_count());
+        let (sorted, count) = allocations(|| {
+            solve_ordered(&game, &mut BTreeMap::new(), Some(&stats), &None, &ordering)
+        });
+        let nodes = stats.states_explored.into_inner();
+
+        assert_eq!(sorted.unwrap(), unsorted.as_ref().copied().unwrap());
+        // only the table allocates, not the moves sorted at every node
+        assert!(
+            count < nodes as usize / 10,
+            "{count} allocations for {nodes} nodes, and {table} without sorting"
+        );
+    }
+}
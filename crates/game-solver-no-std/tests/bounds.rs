@@ -0,0 +1,115 @@
+use std::collections::BTreeMap;
+
+use game_solver::{
+    game::{Game, GameStatus, Normal},
+    player::ImpartialPlayer,
+    solve, solve_depth_limited, GameSolveError,
+};
+use game_solver_no_std::IllegalMove;
+
+/// A pile of stones where a move takes one or two, and whoever takes the last stone wins,
+/// which claims whatever `max_moves` it's given.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Countdown {
+    stones: usize,
+    move_count: usize,
+    max_moves: Option<usize>,
+}
+
+impl Countdown {
+    fn new(stones: usize, max_moves: Option<usize>) -> Self {
+        Self {
+            stones,
+            move_count: 0,
+            max_moves,
+        }
+    }
+}
+
+impl Normal for Countdown {}
+
+impl Game for Countdown {
+    type Move = usize;
+    type Iter<'a> = std::vec::IntoIter<usize>;
+    type MoveError = IllegalMove;
+    type Player = ImpartialPlayer;
+
+    fn move_count(&self) -> usize {
+        self.move_count
+    }
+
+    fn max_moves(&self) -> Option<usize> {
+        self.max_moves
+    }
+
+    fn make_move(&mut self, &m: &usize) -> Result<(), IllegalMove> {
+        if !(1..=2).contains(&m) || m > self.stones {
+            return Err(IllegalMove);
+        }
+
+        self.stones -= m;
+        self.move_count += 1;
+        Ok(())
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        (1..=self.stones.min(2)).collect::<Vec<_>>().into_iter()
+    }
+
+    fn status(&self) -> GameStatus {
+        <Self as Normal>::status(self)
+    }
+
+    fn player(&self) -> ImpartialPlayer {
+        ImpartialPlayer::Next
+    }
+}
+
+#[test]
+fn unbounded_games_are_solved() {
+    // these used to overflow when computing the search window
+    for max_moves in [None, Some(usize::MAX), Some(isize::MAX as usize)] {
+        for stones in 1..8 {
+            let game = Countdown::new(stones, max_moves);
+            let score = solve(&game, &mut BTreeMap::new(), None, &None).unwrap();
+
+            // the player to move loses exactly when the stones are a multiple of 3
+            assert_eq!(score > 0, stones % 3 != 0, "{stones} {max_moves:?}");
+            assert_eq!(
+                score,
+                solve_depth_limited(&game, stones, &None).unwrap(),
+                "{stones} {max_moves:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn exceeding_max_moves_is_an_error() {
+    // 7 stones take at least 4 moves
+    let game = Countdown::new(7, Some(2));
+
+    assert!(matches!(
+        solve(&game, &mut BTreeMap::new(), None, &None),
+        Err(GameSolveError::MaxMovesExceeded { max_moves: 2, .. })
+    ));
+    assert!(matches!(
+        solve_depth_limited(&game, 7, &None),
+        Err(GameSolveError::MaxMovesExceeded { max_moves: 2, .. })
+    ));
+}
+
+#[test]
+fn starting_past_max_moves_is_an_error() {
+    let mut game = Countdown::new(5, Some(1));
+    game.make_move(&1).unwrap();
+    game.make_move(&1).unwrap();
+
+    assert!(matches!(
+        solve(&game, &mut BTreeMap::new(), None, &None),
+        Err(GameSolveError::MaxMovesExceeded {
+            move_count: 2,
+            max_moves: 1
+        })
+    ));
+}
@@ -0,0 +1,263 @@
+use std::{
+    collections::BTreeMap,
+    sync::{atomic::AtomicBool, Arc},
+};
+
+use game_solver::{
+    game::{Game, GameStatus},
+    iterative_deepening, move_scores, perft,
+    proof_number::ProofNumber,
+    solve, solve_depth_limited, solve_depth_limited_with_table,
+    stats::Stats,
+    strategy::{Negamax, Outcome, Strategy, Value},
+    transposition::{DepthAwareTable, DepthEntry, Score},
+    GameSolveError,
+};
+use game_solver_no_std::{IllegalMove, Nim, TicTacToe};
+
+#[test]
+fn nim_is_won_exactly_when_the_nim_sum_is_non_zero() {
+    for a in 0..4 {
+        for b in 0..4 {
+            for c in 0..4 {
+                let game = Nim::new([a, b, c]);
+                let score = solve(&game, &mut BTreeMap::new(), None, &None).unwrap();
+
+                assert_eq!(score > 0, game.nim_sum() != 0, "{a}, {b}, {c}");
+            }
+        }
+    }
+}
+
+#[test]
+fn hashbrown_and_btree_tables_agree() {
+    let game = Nim::new([2, 3, 4]);
+
+    let btree = move_scores(&game, &mut BTreeMap::new(), None, &None)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let hashbrown = move_scores(&game, &mut hashbrown::HashMap::new(), None, &None)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    assert_eq!(btree, hashbrown);
+    // taking 3 from the last heap leaves heaps whose nim sum is 0, and is the only winning move
+    assert_eq!(
+        btree
+            .iter()
+            .filter(|(_, score)| *score > 0)
+            .map(|(m, _)| *m)
+            .collect::<Vec<_>>(),
+        [(2, 3)]
+    );
+}
+
+#[test]
+fn tic_tac_toe_is_a_tie() {
+    let game = TicTacToe::new();
+    let stats = Stats::new(game.player(), game.move_count());
+
+    assert_eq!(
+        solve(&game, &mut BTreeMap::new(), Some(&stats), &None).unwrap(),
+        0
+    );
+    assert!(stats.states_explored.into_inner() > 0);
+
+    let scores = move_scores(&game, &mut hashbrown::HashMap::new(), None, &None)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(scores.len(), 9);
+    assert!(scores.iter().all(|&(_, score)| score <= 0));
+    assert!(scores.contains(&(4, 0)));
+}
+
+#[test]
+fn depth_limited_solving_finds_short_wins() {
+    // X has two in a row on the top and left, and plays next
+    let mut game = TicTacToe::new();
+    for m in [0, 4, 1, 8, 3, 5] {
+        game.make_move(&m).unwrap();
+    }
+
+    assert!(solve_depth_limited(&game, 1, &None).unwrap() > 0);
+    assert_eq!(
+        solve_depth_limited(&game, 1, &None).unwrap(),
+        solve(&game, &mut BTreeMap::new(), None, &None).unwrap()
+    );
+}
+
+#[test]
+fn shallow_scores_dont_answer_deeper_searches() {
+    let game = TicTacToe::new();
+    let mut table = BTreeMap::new();
+
+    // a depth-1 search can't see any win, so it scores the empty board as a tie
+    assert_eq!(
+        solve_depth_limited_with_table(&game, 1, &mut table, &None).unwrap(),
+        0
+    );
+    assert_eq!(DepthAwareTable::get(&table, &game).unwrap().depth, 1);
+    assert_eq!(
+        DepthAwareTable::get(&table, &game).unwrap().score_at(2),
+        None
+    );
+
+    // pretend the shallow search proved a win, which a deeper search must not believe
+    table.insert(
+        game.clone(),
+        DepthEntry {
+            score: Score::Exact(5),
+            depth: 1,
+            best_move: Some(4),
+        },
+    );
+    assert_eq!(
+        solve_depth_limited_with_table(&game, 9, &mut table, &None).unwrap(),
+        0
+    );
+
+    let mut position = TicTacToe::new();
+    for m in [0, 4, 1, 8] {
+        position.make_move(&m).unwrap();
+    }
+    assert_eq!(
+        iterative_deepening(&position, 5, &mut hashbrown::HashMap::new(), &None).unwrap(),
+        solve_depth_limited(&position, 5, &None).unwrap()
+    );
+}
+
+#[test]
+fn deeper_scores_answer_shallower_searches() {
+    // O answered X's corner with an edge next to it, which X wins
+    let mut position = TicTacToe::new();
+    for m in [0, 1] {
+        position.make_move(&m).unwrap();
+    }
+    let score = solve(&position, &mut BTreeMap::new(), None, &None).unwrap();
+    assert!(score > 0);
+    assert_eq!(solve_depth_limited(&position, 1, &None).unwrap(), 0);
+
+    let mut table = BTreeMap::new();
+    assert_eq!(
+        solve_depth_limited_with_table(&position, 7, &mut table, &None).unwrap(),
+        score
+    );
+    // the deeper search proved the win, so the shallower one is decided too
+    assert_eq!(
+        solve_depth_limited_with_table(&position, 1, &mut table, &None).unwrap(),
+        score
+    );
+    assert_eq!(DepthAwareTable::get(&table, &position).unwrap().depth, 7);
+}
+
+#[test]
+fn shallower_entries_dont_replace_deeper_ones() {
+    let game = TicTacToe::new();
+    let entry = |depth| DepthEntry {
+        score: Score::Exact(0),
+        depth,
+        best_move: Some(depth),
+    };
+
+    let mut table = hashbrown::HashMap::new();
+    DepthAwareTable::insert(&mut table, game.clone(), entry(3));
+    DepthAwareTable::insert(&mut table, game.clone(), entry(1));
+    assert_eq!(DepthAwareTable::get(&table, &game), Some(entry(3)));
+
+    DepthAwareTable::insert(&mut table, game.clone(), entry(5));
+    assert_eq!(DepthAwareTable::get(&table, &game), Some(entry(5)));
+}
+
+#[test]
+fn tic_tac_toe_perft() {
+    let game = TicTacToe::new();
+
+    for (depth, count) in [1, 9, 72, 504, 3024, 15120, 54720].into_iter().enumerate() {
+        assert_eq!(perft(&game, depth), count, "depth {depth}");
+    }
+}
+
+#[test]
+fn cancelled_searches_stop() {
+    let token = Some(Arc::new(AtomicBool::new(true)));
+
+    assert!(matches!(
+        solve(&TicTacToe::new(), &mut BTreeMap::new(), None, &token),
+        Err(GameSolveError::CancellationTokenError)
+    ));
+}
+
+#[test]
+fn proof_number_search_agrees_with_negamax() {
+    let mut negamax = Negamax::new(BTreeMap::new());
+    let mut proof_number = ProofNumber::new();
+
+    for heaps in [[1, 2, 3], [2, 3, 4], [1, 1, 0]] {
+        let game = Nim::new(heaps);
+
+        assert_eq!(
+            proof_number.evaluate(&game).unwrap().value.outcome(),
+            negamax.evaluate(&game).unwrap().value.outcome(),
+            "{heaps:?}"
+        );
+    }
+
+    assert_eq!(
+        proof_number.evaluate(&TicTacToe::new()).unwrap().value,
+        Value::Proven(Outcome::Tie)
+    );
+}
+
+/// Nim, but with a bug: it offers a move on a heap that doesn't exist.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct BrokenNim(Nim<2>);
+
+impl Game for BrokenNim {
+    type Move = (usize, usize);
+    type Iter<'a> = std::vec::IntoIter<Self::Move>;
+    type MoveError = IllegalMove;
+    type Player = <Nim<2> as Game>::Player;
+
+    fn move_count(&self) -> usize {
+        self.0.move_count()
+    }
+
+    fn max_moves(&self) -> Option<usize> {
+        self.0.max_moves()
+    }
+
+    fn make_move(&mut self, m: &Self::Move) -> Result<(), IllegalMove> {
+        self.0.make_move(m)
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        let mut moves = self.0.possible_moves().collect::<Vec<_>>();
+        moves.push((2, 1));
+        moves.into_iter()
+    }
+
+    fn status(&self) -> GameStatus {
+        self.0.status()
+    }
+
+    fn player(&self) -> Self::Player {
+        self.0.player()
+    }
+}
+
+#[test]
+fn impossible_moves_are_reported() {
+    let game = BrokenNim(Nim::new([1, 2]));
+
+    let scores = move_scores(&game, &mut BTreeMap::new(), None, &None).collect::<Vec<_>>();
+    assert_eq!(scores.len(), 4);
+    assert!(matches!(
+        scores.last(),
+        Some(Err(GameSolveError::MoveError(IllegalMove)))
+    ));
+
+    assert!(matches!(
+        solve(&game, &mut BTreeMap::new(), None, &None),
+        Err(GameSolveError::MoveError(IllegalMove))
+    ));
+}
@@ -0,0 +1,98 @@
+use game_solver::nimber::Nimber;
+
+fn nim(n: usize) -> Nimber {
+    Nimber(n)
+}
+
+#[test]
+fn nim_addition_is_xor() {
+    assert_eq!(nim(3) + nim(5), nim(6));
+    assert_eq!(nim(7) + nim(7), Nimber::ZERO);
+    assert_eq!([1, 2, 3].map(nim).into_iter().sum::<Nimber>(), Nimber::ZERO);
+
+    let mut sum = nim(4);
+    sum += nim(1);
+    assert_eq!(sum, nim(5));
+}
+
+#[test]
+fn mex_is_the_smallest_missing_nimber() {
+    assert_eq!(Nimber::mex([]), Nimber::ZERO);
+    assert_eq!(Nimber::mex([0, 1, 3].map(nim)), nim(2));
+    assert_eq!(Nimber::mex([5, 0, 0, 1].map(nim)), nim(2));
+    assert_eq!(Nimber::mex((0..10).map(nim)), nim(10));
+}
+
+#[test]
+fn nim_multiplication_table() {
+    let table = [[0, 0, 0, 0], [0, 1, 2, 3], [0, 2, 3, 1], [0, 3, 1, 2]];
+    for (a, row) in table.iter().enumerate() {
+        for (b, &product) in row.iter().enumerate() {
+            assert_eq!(nim(a) * nim(b), nim(product), "{a} * {b}");
+        }
+    }
+
+    // the square of a Fermat 2-power F is F + F / 2
+    assert_eq!(nim(4) * nim(4), nim(6));
+    assert_eq!(nim(16) * nim(16), nim(24));
+    assert_eq!(nim(256) * nim(256), nim(384));
+    assert_eq!(nim(8) * nim(8), nim(13));
+}
+
+#[test]
+fn nim_multiplication_matches_its_definition() {
+    // a * b = mex { a' * b + a * b' + a' * b' : a' < a, b' < b }
+    const SIZE: usize = 16;
+    let mut table = [[Nimber::ZERO; SIZE]; SIZE];
+    for a in 0..SIZE {
+        for b in 0..SIZE {
+            table[a][b] = Nimber::mex(
+                (0..a)
+                    .flat_map(|x| (0..b).map(move |y| (x, y)))
+                    .map(|(x, y)| table[x][b] + table[a][y] + table[x][y]),
+            );
+        }
+    }
+
+    for (a, row) in table.iter().enumerate() {
+        for (b, &product) in row.iter().enumerate() {
+            assert_eq!(nim(a) * nim(b), product, "{a} * {b}");
+        }
+    }
+}
+
+#[test]
+fn nimbers_below_fermat_two_powers_are_fields() {
+    for a in 1..256 {
+        let products = (0..256).map(|b| nim(a) * nim(b)).collect::<Vec<_>>();
+
+        assert!(products.iter().all(|product| product.0 < 256), "{a}");
+        assert!(products.contains(&Nimber::STAR), "{a} has no inverse");
+    }
+
+    for a in 0..16 {
+        for b in 0..16 {
+            for c in 0..16 {
+                let (a, b, c) = (nim(a), nim(b), nim(c));
+                assert_eq!((a * b) * c, a * (b * c));
+                assert_eq!(a * (b + c), a * b + a * c);
+            }
+        }
+    }
+
+    // products of large nimbers stay below the same Fermat 2-power
+    let (a, b) = (nim(0xdead_beef), nim(0x1234_5678));
+    assert!((a * b).0 < 1 << 32);
+    assert_eq!(a * b, b * a);
+    assert_eq!([a, b].into_iter().product::<Nimber>(), a * b);
+}
+
+#[test]
+fn conversions_and_display() {
+    assert_eq!(Nimber::from(5), nim(5));
+    assert_eq!(usize::from(nim(5)), 5);
+
+    assert_eq!(Nimber::ZERO.to_string(), "0");
+    assert_eq!(Nimber::STAR.to_string(), "*");
+    assert_eq!(nim(12).to_string(), "*12");
+}
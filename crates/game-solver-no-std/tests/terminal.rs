@@ -0,0 +1,354 @@
+use std::collections::BTreeMap;
+
+use game_solver::{
+    dominance::Undominated,
+    game::{Game, GameState, GameStatus},
+    move_scores,
+    player::PartizanPlayer,
+    solve, GameSolveError,
+};
+use game_solver_no_std::IllegalMove;
+
+/// A game where Left can end the game in a tie right away, or play on to a position
+/// where Right's only move hands Left the win.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum TieOrWin {
+    Start,
+    Tied,
+    PlayedOn,
+    LeftWon,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Choice {
+    Tie,
+    PlayOn,
+    Resign,
+}
+
+impl Game for TieOrWin {
+    type Move = Choice;
+    type Iter<'a> = std::vec::IntoIter<Choice>;
+    type MoveError = IllegalMove;
+    type Player = PartizanPlayer;
+
+    fn move_count(&self) -> usize {
+        match self {
+            Self::Start => 0,
+            Self::Tied | Self::PlayedOn => 1,
+            Self::LeftWon => 2,
+        }
+    }
+
+    fn max_moves(&self) -> Option<usize> {
+        Some(2)
+    }
+
+    fn make_move(&mut self, m: &Choice) -> Result<(), IllegalMove> {
+        *self = match (*self, m) {
+            (Self::Start, Choice::Tie) => Self::Tied,
+            (Self::Start, Choice::PlayOn) => Self::PlayedOn,
+            (Self::PlayedOn, Choice::Resign) => Self::LeftWon,
+            _ => return Err(IllegalMove),
+        };
+
+        Ok(())
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        match self {
+            Self::Start => vec![Choice::Tie, Choice::PlayOn],
+            Self::PlayedOn => vec![Choice::Resign],
+            Self::Tied | Self::LeftWon => Vec::new(),
+        }
+        .into_iter()
+    }
+
+    fn status(&self) -> GameStatus {
+        match self {
+            Self::Start | Self::PlayedOn => GameStatus::InProgress,
+            Self::Tied => GameStatus::TIE,
+            Self::LeftWon => GameStatus::won_by(self, PartizanPlayer::Left),
+        }
+    }
+
+    fn player(&self) -> PartizanPlayer {
+        PartizanPlayer::from_move_count(self.move_count())
+    }
+}
+
+#[test]
+fn a_tie_in_one_move_does_not_end_the_search() {
+    let game = TieOrWin::Start;
+
+    // the default `find_immediately_resolvable_game` finds the tie, but playing on wins
+    assert_eq!(
+        game.find_immediately_resolvable_game().unwrap(),
+        Some(TieOrWin::Tied)
+    );
+
+    let score = solve(&game, &mut BTreeMap::new(), None, &None).unwrap();
+    assert!(score > 0, "{score}");
+
+    let scores = move_scores(&game, &mut BTreeMap::new(), None, &None)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(scores, vec![(Choice::Tie, 0), (Choice::PlayOn, score)]);
+}
+
+/// A game scored by how many points Left wins by: Left can take 1 point right away,
+/// or play on, after which Right has to give up 2 or 3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Margin {
+    Start,
+    PlayedOn,
+    /// Left won by this many points.
+    LeftWonBy(isize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Points {
+    TakeOne,
+    PlayOn,
+    GiveUp(isize),
+}
+
+impl Game for Margin {
+    type Move = Points;
+    type Iter<'a> = std::vec::IntoIter<Points>;
+    type MoveError = IllegalMove;
+    type Player = PartizanPlayer;
+
+    fn move_count(&self) -> usize {
+        match self {
+            Self::Start => 0,
+            Self::PlayedOn | Self::LeftWonBy(1) => 1,
+            Self::LeftWonBy(_) => 2,
+        }
+    }
+
+    // the game lasts at most 2 moves, but winning by 3 points after them has to be no better than winning right away
+    fn max_moves(&self) -> Option<usize> {
+        Some(4)
+    }
+
+    fn make_move(&mut self, m: &Points) -> Result<(), IllegalMove> {
+        *self = match (*self, m) {
+            (Self::Start, Points::TakeOne) => Self::LeftWonBy(1),
+            (Self::Start, Points::PlayOn) => Self::PlayedOn,
+            (Self::PlayedOn, Points::GiveUp(points @ (2 | 3))) => Self::LeftWonBy(*points),
+            _ => return Err(IllegalMove),
+        };
+
+        Ok(())
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        match self {
+            Self::Start => vec![Points::TakeOne, Points::PlayOn],
+            Self::PlayedOn => vec![Points::GiveUp(3), Points::GiveUp(2)],
+            Self::LeftWonBy(_) => Vec::new(),
+        }
+        .into_iter()
+    }
+
+    fn status(&self) -> GameStatus {
+        match self {
+            Self::Start | Self::PlayedOn => GameStatus::InProgress,
+            Self::LeftWonBy(points) if self.player() == PartizanPlayer::Left => {
+                GameStatus::Terminal(*points)
+            }
+            Self::LeftWonBy(points) => GameStatus::Terminal(-points),
+        }
+    }
+
+    // so the solver looks for a win right away first, which taking the point is, though not the best one
+    fn cheap_win_check(&self) -> bool {
+        true
+    }
+
+    fn player(&self) -> PartizanPlayer {
+        PartizanPlayer::from_move_count(self.move_count())
+    }
+}
+
+#[test]
+fn terminal_scores_are_the_games_own() {
+    let game = Margin::Start;
+
+    // scored by how fast it's won, taking the point right away would be best
+    let score = solve(&game, &mut BTreeMap::new(), None, &None).unwrap();
+    assert_eq!(score, 2);
+
+    let scores = move_scores(&game, &mut BTreeMap::new(), None, &None)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(scores, vec![(Points::TakeOne, 1), (Points::PlayOn, 2)]);
+
+    // whoever scored more won
+    assert_eq!(
+        Margin::LeftWonBy(3).state(),
+        GameState::Win(PartizanPlayer::Left)
+    );
+}
+
+/// Four boxes worth 1, 2, 3, and 4, handed out in that order: each move, the player to move
+/// takes the next box, or gives it to the other player. Each player ends up with 0 to 10,
+/// and the status is what the player to move has, so a tie is 5 each.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Boxes {
+    handed_out: usize,
+    /// What Left has.
+    left: isize,
+    /// The score of a tie, which is 5 for the game to be scored right.
+    draw: isize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Hand {
+    Take,
+    Give,
+}
+
+impl Boxes {
+    fn new(draw: isize) -> Self {
+        Self {
+            handed_out: 0,
+            left: 0,
+            draw,
+        }
+    }
+
+    fn play(mut self, moves: &[Hand]) -> Self {
+        for m in moves {
+            self.make_move(m).unwrap();
+        }
+        self
+    }
+}
+
+impl Game for Boxes {
+    type Move = Hand;
+    type Iter<'a> = std::vec::IntoIter<Hand>;
+    type MoveError = IllegalMove;
+    type Player = PartizanPlayer;
+
+    fn move_count(&self) -> usize {
+        self.handed_out
+    }
+
+    // the game lasts 4 moves, and leaves room for winning by 5 after them
+    fn max_moves(&self) -> Option<usize> {
+        Some(8)
+    }
+
+    fn make_move(&mut self, m: &Hand) -> Result<(), IllegalMove> {
+        if self.handed_out == 4 {
+            return Err(IllegalMove);
+        }
+
+        let worth = self.handed_out as isize + 1;
+        if (self.player() == PartizanPlayer::Left) == (*m == Hand::Take) {
+            self.left += worth;
+        }
+        self.handed_out += 1;
+
+        Ok(())
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        if self.handed_out == 4 {
+            Vec::new()
+        } else {
+            vec![Hand::Take, Hand::Give]
+        }
+        .into_iter()
+    }
+
+    fn status(&self) -> GameStatus {
+        if self.handed_out < 4 {
+            GameStatus::InProgress
+        } else if self.player() == PartizanPlayer::Left {
+            GameStatus::Terminal(self.left)
+        } else {
+            GameStatus::Terminal(10 - self.left)
+        }
+    }
+
+    fn draw_score(&self) -> isize {
+        self.draw
+    }
+
+    fn player(&self) -> PartizanPlayer {
+        PartizanPlayer::from_move_count(self.move_count())
+    }
+}
+
+/// Scores that don't straddle 0 are measured from the draw score: the solver's score is
+/// what the player to move ends up with, less 5.
+#[test]
+fn scores_are_measured_from_the_draw_score() {
+    let game = Boxes::new(5);
+
+    // each player takes their own boxes, so Right gets 2 and 4, and Left loses 4 to 6
+    let score = solve(&game, &mut BTreeMap::new(), None, &None).unwrap();
+    assert_eq!(score, 4 - 5);
+
+    let scores = move_scores(&game, &mut BTreeMap::new(), None, &None)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(scores, vec![(Hand::Take, -1), (Hand::Give, 3 - 5)]);
+
+    // a tie is the draw score, and a win is above it
+    let tied = game.play(&[Hand::Take, Hand::Take, Hand::Give, Hand::Give]);
+    assert_eq!(tied.status(), GameStatus::Terminal(5));
+    assert_eq!(tied.state(), GameState::Tie);
+
+    let everything = game.play(&[Hand::Take, Hand::Give, Hand::Take, Hand::Give]);
+    assert_eq!(everything.status(), GameStatus::Terminal(10));
+    assert_eq!(everything.state(), GameState::Win(PartizanPlayer::Left));
+    assert_eq!(
+        solve(&everything, &mut BTreeMap::new(), None, &None).unwrap(),
+        5
+    );
+}
+
+/// Searching every move doesn't change the draw score the scores are measured from.
+#[test]
+fn undominated_games_keep_the_draw_score() {
+    let game = Boxes::new(5);
+    assert_eq!(Undominated(game).draw_score(), 5);
+
+    assert_eq!(
+        solve(&Undominated(game), &mut BTreeMap::new(), None, &None).unwrap(),
+        solve(&game, &mut BTreeMap::new(), None, &None).unwrap()
+    );
+
+    let everything = game.play(&[Hand::Take, Hand::Give, Hand::Take, Hand::Give]);
+    assert_eq!(
+        solve(&Undominated(everything), &mut BTreeMap::new(), None, &None).unwrap(),
+        5
+    );
+}
+
+/// Without the draw score, taking every box scores 10, past the 5 that winning right away scores
+/// with the moves left, so solving fails instead of scoring it wrong.
+#[test]
+fn scores_past_winning_right_away_are_an_error() {
+    let everything = Boxes::new(0).play(&[Hand::Take, Hand::Give, Hand::Take, Hand::Give]);
+
+    assert!(matches!(
+        solve(&everything, &mut BTreeMap::new(), None, &None),
+        Err(GameSolveError::ScoreOutOfBounds {
+            score: 10,
+            bound: 5
+        })
+    ));
+
+    // and so does a search that reaches it, as Right can give Left the last box
+    let last_box = Boxes::new(0).play(&[Hand::Take, Hand::Give, Hand::Take]);
+    assert!(matches!(
+        solve(&last_box, &mut BTreeMap::new(), None, &None),
+        Err(GameSolveError::ScoreOutOfBounds { .. })
+    ));
+}
@@ -0,0 +1,103 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use game_solver::{
+    game::{Game, GameState},
+    strategy::{Negamax, Outcome, Strategy},
+    tie_break::{TieBreak, TieBreaker},
+};
+use game_solver_no_std::TicTacToe;
+
+/// The indices `tie_break` picks out of ties of 1 to 10 moves.
+fn picks(tie_break: TieBreak<usize>) -> Vec<usize> {
+    let mut tie_break = TieBreaker::new(tie_break);
+
+    (1..=10)
+        .map(|len| {
+            let tied = (0..len).collect::<Vec<_>>();
+            *tie_break.choose(&tied).unwrap()
+        })
+        .collect()
+}
+
+/// Plays tic-tac-toe with negamax on both sides, returning the moves it played.
+fn self_play(tie_break: TieBreak<usize>) -> Vec<usize> {
+    let mut negamax = Negamax::new(BTreeMap::new());
+    negamax.tie_break = TieBreaker::new(tie_break);
+
+    let mut game = TicTacToe::new();
+    let mut moves = Vec::new();
+
+    while game.state() == GameState::Playable {
+        let evaluation = negamax.evaluate(&game).unwrap();
+        // every move played keeps the tie of the start
+        assert_eq!(evaluation.value.outcome(), Some(Outcome::Tie), "{moves:?}");
+
+        let m = evaluation.best_move.unwrap();
+        game.make_move(&m).unwrap();
+        moves.push(m);
+    }
+
+    assert_eq!(game.state(), GameState::Tie);
+    moves
+}
+
+#[test]
+fn seeded_picks_are_the_same_everywhere() {
+    assert_eq!(picks(TieBreak::Random(42)), [0, 1, 0, 1, 1, 0, 6, 1, 7, 3]);
+    assert_eq!(picks(TieBreak::Random(42)), picks(TieBreak::Random(42)));
+    assert_ne!(picks(TieBreak::Random(42)), picks(TieBreak::Random(43)));
+}
+
+#[test]
+fn first_and_custom_picks() {
+    assert_eq!(picks(TieBreak::First), [0; 10]);
+    assert_eq!(
+        picks(TieBreak::Custom(|tied| tied.len() / 2)),
+        [0, 1, 1, 2, 2, 3, 3, 4, 4, 5]
+    );
+    // an index past the end picks the last move
+    assert_eq!(
+        picks(TieBreak::Custom(|_| usize::MAX)),
+        [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+    );
+    assert_eq!(TieBreaker::<usize>::default().choose(&[]), None);
+}
+
+#[test]
+fn only_the_best_moves_are_chosen_between() {
+    let scored = [('a', 1), ('b', 3), ('c', 2), ('d', 3), ('e', 3)];
+
+    assert_eq!(TieBreaker::default().best(scored), Some(('b', 3)));
+    assert_eq!(
+        TieBreaker::new(TieBreak::Custom(|_| 2)).best(scored),
+        Some(('e', 3))
+    );
+
+    for seed in 0..20 {
+        let (m, score) = TieBreaker::new(TieBreak::Random(seed))
+            .best(scored)
+            .unwrap();
+        assert!(['b', 'd', 'e'].contains(&m));
+        assert_eq!(score, 3);
+    }
+
+    assert_eq!(TieBreaker::<char>::default().best::<isize>([]), None);
+}
+
+#[test]
+fn a_seed_always_plays_the_same_optimal_line() {
+    assert_eq!(
+        self_play(TieBreak::Random(7)),
+        self_play(TieBreak::Random(7))
+    );
+    assert_eq!(self_play(TieBreak::First), self_play(TieBreak::First));
+}
+
+#[test]
+fn different_seeds_play_different_optimal_lines() {
+    let lines = (0..10)
+        .map(|seed| self_play(TieBreak::Random(seed)))
+        .collect::<BTreeSet<_>>();
+
+    assert!(lines.len() > 1, "{lines:?}");
+}
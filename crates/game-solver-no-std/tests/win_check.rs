@@ -0,0 +1,100 @@
+use std::{cell::Cell, collections::BTreeMap};
+
+use game_solver::{
+    game::{Game, GameStatus, Normal},
+    solve,
+};
+use game_solver_no_std::Nim;
+
+thread_local! {
+    static POSSIBLE_MOVES: Cell<usize> = const { Cell::new(0) };
+    static MOVES_MADE: Cell<usize> = const { Cell::new(0) };
+}
+
+/// A game that counts how often its moves are asked for and made, and checks for a win
+/// before searching any move if `PRE_PASS` is set.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Counted<T, const PRE_PASS: bool>(T);
+
+impl<T: Game, const PRE_PASS: bool> Normal for Counted<T, PRE_PASS> {}
+
+impl<T: Game, const PRE_PASS: bool> Game for Counted<T, PRE_PASS> {
+    type Move = T::Move;
+    type Iter<'a>
+        = T::Iter<'a>
+    where
+        T: 'a;
+    type MoveError = T::MoveError;
+    type Player = T::Player;
+
+    fn move_count(&self) -> usize {
+        self.0.move_count()
+    }
+
+    fn max_moves(&self) -> Option<usize> {
+        self.0.max_moves()
+    }
+
+    fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        MOVES_MADE.with(|count| count.set(count.get() + 1));
+        self.0.make_move(m)
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        POSSIBLE_MOVES.with(|count| count.set(count.get() + 1));
+        self.0.possible_moves()
+    }
+
+    fn move_count_hint(&self) -> (usize, Option<usize>) {
+        self.0.move_count_hint()
+    }
+
+    fn cheap_win_check(&self) -> bool {
+        PRE_PASS
+    }
+
+    fn status(&self) -> GameStatus {
+        <Self as Normal>::status(self)
+    }
+
+    fn player(&self) -> Self::Player {
+        self.0.player()
+    }
+}
+
+/// The score of `game`, and how many times its moves were asked for.
+fn counted<const N: usize, const PRE_PASS: bool>(
+    game: &Counted<Nim<N>, PRE_PASS>,
+) -> (isize, usize) {
+    let before = POSSIBLE_MOVES.with(Cell::get);
+    let score = solve(game, &mut BTreeMap::new(), None, &None).unwrap();
+    (score, POSSIBLE_MOVES.with(Cell::get) - before)
+}
+
+#[test]
+fn winning_moves_are_found_without_a_pre_pass() {
+    for heaps in [[1, 2, 3], [3, 4, 5], [6, 7, 8], [9, 9, 1]] {
+        let (score, searched) = counted(&Counted::<_, false>(Nim::new(heaps)));
+        let (pre_pass_score, pre_passed) = counted(&Counted::<_, true>(Nim::new(heaps)));
+
+        assert_eq!(score, pre_pass_score, "{heaps:?}");
+        // the pre-pass asks every position for its moves once more, and as a win it finds isn't stored
+        // in the table, it's found again every time the position is reached
+        assert!(
+            searched < pre_passed,
+            "{heaps:?}: moves were asked for {searched} times, and {pre_passed} times with the pre-pass"
+        );
+    }
+}
+
+#[test]
+fn a_winning_move_found_first_is_not_made_again() {
+    // the pre-pass takes 1 and then 2 from the heap before it finds that taking all 3 wins
+    let game = Counted::<_, true>(Nim::new([3]));
+
+    let before = MOVES_MADE.with(Cell::get);
+    assert_eq!(counted(&game).0, 3);
+
+    // the position it returns is scored as it is, without making its move once more
+    assert_eq!(MOVES_MADE.with(Cell::get) - before, 3);
+}
@@ -0,0 +1,234 @@
+//! Python bindings for the solver and the bundled games.
+//!
+//! Every game is a class built from a list of moves, and moves cross the boundary
+//! as strings in the same notation as the CLI (e.g. `"1-1"` in Tic-Tac-Toe).
+//! The solver releases the GIL while it searches.
+
+// the wrappers `#[pyfunction]` generates in pyo3 0.22 convert `PyResult`s into themselves
+#![allow(clippy::useless_conversion)]
+
+use std::{collections::HashMap, fmt::Display, hash::Hash, time::Duration};
+
+use game_solver::{
+    game::{Game, GameState},
+    player::TwoPlayer,
+    GameSolveError,
+};
+use games::{
+    chomp::{Chomp, ChompArgs},
+    domineering::{Domineering, DomineeringArgs},
+    naive_nim::{Nim, NimArgs},
+    order_and_chaos::{OrderAndChaos, OrderAndChaosArgs},
+    reversi::{Reversi, ReversiArgs},
+    sprouts::{Sprouts, SproutsArgs},
+    takeaway::{Takeaway, TakeawayArgs},
+    tic_tac_toe::{TicTacToe, TicTacToeArgs},
+    util::{
+        cli::{interactive::start_timer, move_failable},
+        parse::ParseMove,
+    },
+};
+use pyo3::{
+    exceptions::{PyRuntimeError, PyTimeoutError, PyValueError},
+    prelude::*,
+};
+
+/// Why a search didn't finish, without borrowing the game,
+/// so it can leave the thread that ran the search.
+enum SearchError {
+    Timeout,
+    Failed(String),
+}
+
+impl From<SearchError> for PyErr {
+    fn from(err: SearchError) -> Self {
+        match err {
+            SearchError::Timeout => PyTimeoutError::new_err("the search ran out of time"),
+            SearchError::Failed(message) => PyRuntimeError::new_err(message),
+        }
+    }
+}
+
+impl<T: Game> From<GameSolveError<T>> for SearchError
+where
+    T::MoveError: Display,
+{
+    fn from(err: GameSolveError<T>) -> Self {
+        match err {
+            GameSolveError::CancellationTokenError => Self::Timeout,
+            GameSolveError::MoveError(err) => Self::Failed(format!("could not make a move: {err}")),
+            err @ (GameSolveError::MaxMovesExceeded { .. }
+            | GameSolveError::InvalidWindow { .. }
+            | GameSolveError::ScoreOutOfBounds { .. }) => Self::Failed(err.to_string()),
+        }
+    }
+}
+
+fn search_score<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    game: &T,
+    timeout: Option<Duration>,
+) -> Result<isize, SearchError>
+where
+    T::MoveError: Display,
+{
+    let (cancellation_token, _timer) = start_timer(timeout);
+
+    Ok(game_solver::solve(
+        game,
+        &mut HashMap::new(),
+        None,
+        &cancellation_token,
+    )?)
+}
+
+fn search_move_scores<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    game: &T,
+) -> Result<Vec<(String, isize)>, SearchError>
+where
+    T::Move: Display,
+    T::MoveError: Display,
+{
+    let mut scores = game_solver::move_scores(game, &mut HashMap::new(), None, &None)
+        .map(|result| result.map(|(m, score)| (m.to_string(), score)))
+        .collect::<Result<Vec<_>, _>>()?;
+    scores.sort_by_key(|(_, score)| -score);
+
+    Ok(scores)
+}
+
+/// Defines a Python class for a bundled game, built from its default arguments.
+macro_rules! py_game {
+    ($name:ident, $py_name:literal, $game:ty, $args:ty) => {
+        #[pyclass(name = $py_name, module = "game_solver_py")]
+        #[derive(Clone)]
+        pub struct $name($game);
+
+        #[pymethods]
+        impl $name {
+            /// Starts a game with its default arguments, then plays `moves`.
+            #[new]
+            #[pyo3(signature = (moves = Vec::new()))]
+            fn new(moves: Vec<String>) -> PyResult<Self> {
+                let game: $game = <$args>::default()
+                    .try_into()
+                    .map_err(|err: anyhow::Error| PyValueError::new_err(err.to_string()))?;
+
+                let mut game = Self(game);
+                for m in moves {
+                    game.push(&m)?;
+                }
+
+                Ok(game)
+            }
+
+            /// Every legal move, in CLI notation. Empty once the game is over.
+            fn legal_moves(&self) -> Vec<String> {
+                if self.is_terminal() {
+                    return Vec::new();
+                }
+
+                self.0.possible_moves().map(|m| m.to_string()).collect()
+            }
+
+            /// Plays a move, given in CLI notation.
+            fn push(&mut self, m: &str) -> PyResult<()> {
+                let m = self
+                    .0
+                    .parse_move(m)
+                    .map_err(|err| PyValueError::new_err(err.to_string()))?;
+
+                move_failable(&mut self.0, &m).map_err(|err| PyValueError::new_err(err.to_string()))
+            }
+
+            fn is_terminal(&self) -> bool {
+                self.0.state() != GameState::Playable
+            }
+
+            fn __str__(&self) -> String {
+                self.0.to_string()
+            }
+        }
+    };
+}
+
+py_game!(PyReversi, "Reversi", Reversi, ReversiArgs);
+py_game!(PyTicTacToe, "TicTacToe", TicTacToe, TicTacToeArgs);
+py_game!(
+    PyOrderAndChaos,
+    "OrderAndChaos",
+    OrderAndChaos<6, 6, 5, 6>,
+    OrderAndChaosArgs
+);
+py_game!(PyNim, "Nim", Nim, NimArgs);
+py_game!(PyDomineering, "Domineering", Domineering<5, 5>, DomineeringArgs);
+py_game!(PyChomp, "Chomp", Chomp, ChompArgs);
+py_game!(PySprouts, "Sprouts", Sprouts, SproutsArgs);
+py_game!(PyTakeaway, "Takeaway", Takeaway, TakeawayArgs);
+
+/// Any of the game classes, copied out of Python so it can be searched without the GIL.
+#[derive(FromPyObject)]
+enum AnyGame {
+    Reversi(PyReversi),
+    TicTacToe(PyTicTacToe),
+    OrderAndChaos(PyOrderAndChaos),
+    Nim(PyNim),
+    Domineering(PyDomineering),
+    Chomp(PyChomp),
+    Sprouts(PySprouts),
+    Takeaway(PyTakeaway),
+}
+
+/// Evaluates `$body` with `$game` bound to the concrete game inside an [`AnyGame`].
+macro_rules! with_any_game {
+    ($any:expr, $game:ident => $body:expr) => {
+        match $any {
+            AnyGame::Reversi(PyReversi($game)) => $body,
+            AnyGame::TicTacToe(PyTicTacToe($game)) => $body,
+            AnyGame::OrderAndChaos(PyOrderAndChaos($game)) => $body,
+            AnyGame::Nim(PyNim($game)) => $body,
+            AnyGame::Domineering(PyDomineering($game)) => $body,
+            AnyGame::Chomp(PyChomp($game)) => $body,
+            AnyGame::Sprouts(PySprouts($game)) => $body,
+            AnyGame::Takeaway(PyTakeaway($game)) => $body,
+        }
+    };
+}
+
+/// Scores the position from the perspective of the player to move:
+/// positive if they win, negative if they lose, and 0 for a tie.
+///
+/// Raises `TimeoutError` if `timeout` (in seconds) elapses before the search finishes.
+#[pyfunction]
+#[pyo3(signature = (game, timeout = None))]
+fn solve(py: Python<'_>, game: AnyGame, timeout: Option<f64>) -> PyResult<isize> {
+    let timeout = timeout
+        .map(Duration::try_from_secs_f64)
+        .transpose()
+        .map_err(|err| PyValueError::new_err(err.to_string()))?;
+
+    Ok(py.allow_threads(|| with_any_game!(game, game => search_score(&game, timeout)))?)
+}
+
+/// Scores every legal move from the perspective of the player making it,
+/// as `(move, score)` pairs from best to worst.
+#[pyfunction]
+fn move_scores(py: Python<'_>, game: AnyGame) -> PyResult<Vec<(String, isize)>> {
+    Ok(py.allow_threads(|| with_any_game!(game, game => search_move_scores(&game)))?)
+}
+
+#[pymodule]
+fn game_solver_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyReversi>()?;
+    m.add_class::<PyTicTacToe>()?;
+    m.add_class::<PyOrderAndChaos>()?;
+    m.add_class::<PyNim>()?;
+    m.add_class::<PyDomineering>()?;
+    m.add_class::<PyChomp>()?;
+    m.add_class::<PySprouts>()?;
+    m.add_class::<PyTakeaway>()?;
+
+    m.add_function(wrap_pyfunction!(solve, m)?)?;
+    m.add_function(wrap_pyfunction!(move_scores, m)?)?;
+
+    Ok(())
+}
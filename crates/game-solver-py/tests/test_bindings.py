@@ -0,0 +1,56 @@
+import pytest
+
+from game_solver_py import Nim, Reversi, TicTacToe, move_scores, solve
+
+
+def test_nim_moves():
+    game = Nim()
+    assert "2-1" in game.legal_moves()
+    assert not game.is_terminal()
+
+    game.push("2-1")
+    assert "2-7" not in game.legal_moves()
+
+    with pytest.raises(ValueError):
+        game.push("2-7")
+
+
+def test_nim_solve():
+    # 3 ^ 5 ^ 7 is not 0, so the first player wins
+    assert solve(Nim()) > 0
+    # taking one from the last heap leaves 3 ^ 5 ^ 6 = 0 for the opponent
+    assert solve(Nim(["2-1"])) < 0
+
+    # taking one from any heap leaves a nim-sum of 0, and those are the only winning moves
+    scores = move_scores(Nim())
+    assert {m for m, score in scores if score > 0} == {"0-1", "1-1", "2-1"}
+    assert scores[0][1] > 0
+
+
+def test_tic_tac_toe_is_a_tie():
+    assert solve(TicTacToe()) == 0
+
+    scores = move_scores(TicTacToe(["1-1"]))
+    assert len(scores) == 8
+    # scores are sorted from best to worst, and the best reply to the center holds the tie
+    assert scores[0][1] == 0
+    assert [score for _, score in scores] == sorted((score for _, score in scores), reverse=True)
+
+
+def test_tic_tac_toe_game_over():
+    game = TicTacToe(["0-0", "1-0", "0-1", "1-1", "0-2"])
+    assert game.is_terminal()
+    assert game.legal_moves() == []
+
+    with pytest.raises(ValueError):
+        TicTacToe(["9-9"])
+
+
+def test_timeout():
+    with pytest.raises(TimeoutError):
+        solve(Reversi(), timeout=0.05)
+
+
+def test_invalid_timeout():
+    with pytest.raises(ValueError):
+        solve(Nim(), timeout=-1.0)
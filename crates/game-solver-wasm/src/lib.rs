@@ -0,0 +1,327 @@
+//! WebAssembly bindings for the solver and the bundled games,
+//! for running them in the browser (such as in the book's interactive examples).
+//!
+//! Everything is exchanged as JSON strings, and nothing uses threads:
+//! searches are bounded by a node or time budget instead.
+//! The `games` crate is built without its `rayon` feature (and its `tui` feature), so neither rayon
+//! nor the terminal's crates are compiled in, and this builds for `wasm32-unknown-unknown`.
+
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    fmt::{Debug, Display},
+    hash::Hash,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use game_solver::{
+    game::{Game, GameState},
+    move_scores,
+    player::TwoPlayer,
+    transposition::{Score, TranspositionTable},
+    GameSolveError,
+};
+use games::{
+    util::{cli::move_failable, parse::ParseMove},
+    with_game, GameKind, Games,
+};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// The current time in milliseconds.
+#[cfg(target_arch = "wasm32")]
+fn now_ms() -> f64 {
+    js_sys::Date::now()
+}
+
+/// The current time in milliseconds.
+#[cfg(not(target_arch = "wasm32"))]
+fn now_ms() -> f64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0.0, |elapsed| elapsed.as_secs_f64() * 1000.0)
+}
+
+/// Limits on how much a search can do, as there's no thread to stop it from the outside.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Budget {
+    /// How many positions the solver can visit.
+    pub max_nodes: Option<u64>,
+    /// How many milliseconds the solver can search for.
+    pub max_ms: Option<f64>,
+}
+
+/// A transposition table that counts the positions the solver visits,
+/// cancelling the search once the budget runs out.
+struct BudgetTable<T: Game> {
+    table: HashMap<T, Score>,
+    nodes: Cell<u64>,
+    max_nodes: Option<u64>,
+    deadline: Option<f64>,
+    cancellation_token: Arc<AtomicBool>,
+}
+
+impl<T: Game> BudgetTable<T> {
+    fn new(budget: Budget) -> Self {
+        Self {
+            table: HashMap::new(),
+            nodes: Cell::new(0),
+            max_nodes: budget.max_nodes,
+            deadline: budget.max_ms.map(|ms| now_ms() + ms),
+            cancellation_token: Arc::new(AtomicBool::new(false)),
+        }
+    }
+}
+
+impl<T: Game + Eq + Hash> TranspositionTable<T> for BudgetTable<T> {
+    fn get(&self, board: &T) -> Option<Score> {
+        // the solver looks up every position it visits
+        let nodes = self.nodes.get() + 1;
+        self.nodes.set(nodes);
+
+        let out_of_nodes = self.max_nodes.is_some_and(|max| nodes > max);
+        // reading the clock is slow, so it's only checked every so often
+        let out_of_time = nodes % 1024 == 0 && self.deadline.is_some_and(|end| now_ms() > end);
+
+        if out_of_nodes || out_of_time {
+            self.cancellation_token.store(true, Ordering::Relaxed);
+        }
+
+        self.table.get(board).copied()
+    }
+
+    fn insert(&mut self, board: T, score: Score) {
+        self.table.insert(board, score);
+    }
+
+    fn has(&self, board: &T) -> bool {
+        self.table.contains_key(board)
+    }
+}
+
+#[derive(Serialize)]
+struct MoveScore {
+    #[serde(rename = "move")]
+    game_move: String,
+    score: isize,
+}
+
+#[derive(Serialize)]
+struct Solution {
+    /// False if the budget ran out before every move was scored.
+    complete: bool,
+    /// The best of the scored moves.
+    best: Option<String>,
+    /// The scored moves, from best to worst.
+    moves: Vec<MoveScore>,
+    /// How many positions the solver visited.
+    nodes: u64,
+}
+
+/// The operations exposed to JavaScript, for any bundled game.
+trait AnyGame {
+    fn position(&self) -> Result<String>;
+    fn legal_moves(&self) -> Result<String>;
+    fn make_move(&mut self, token: &str) -> Result<()>;
+    fn solve(&self, budget: Budget) -> Result<String>;
+}
+
+impl<T, P> AnyGame for T
+where
+    T: Game<Player = P> + ParseMove + Eq + Hash + Serialize,
+    P: TwoPlayer + Debug + 'static,
+    T::Move: Display,
+    T::MoveError: Display,
+{
+    fn position(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    fn legal_moves(&self) -> Result<String> {
+        let moves = if self.state() == GameState::Playable {
+            self.possible_moves().map(|m| m.to_string()).collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(serde_json::to_string(&moves)?)
+    }
+
+    fn make_move(&mut self, token: &str) -> Result<()> {
+        let m = self.parse_move(token)?;
+        move_failable(self, &m)
+    }
+
+    fn solve(&self, budget: Budget) -> Result<String> {
+        if self.state() != GameState::Playable {
+            return Err(anyhow!("The game is already over."));
+        }
+
+        let mut table = BudgetTable::new(budget);
+        let cancellation_token = Some(table.cancellation_token.clone());
+
+        let mut complete = true;
+        let mut moves = Vec::new();
+
+        for result in move_scores(self, &mut table, None, &cancellation_token) {
+            match result {
+                Ok((m, score)) => moves.push(MoveScore {
+                    game_move: m.to_string(),
+                    score,
+                }),
+                Err(GameSolveError::CancellationTokenError) => {
+                    complete = false;
+                    break;
+                }
+                Err(err) => return Err(anyhow!("The solver failed: {err}")),
+            }
+        }
+
+        moves.sort_by_key(|m| -m.score);
+
+        Ok(serde_json::to_string(&Solution {
+            complete,
+            best: moves.first().map(|m| m.game_move.clone()),
+            moves,
+            nodes: table.nodes.get(),
+        })?)
+    }
+}
+
+fn build(games: Games) -> Result<Box<dyn AnyGame>> {
+    with_game!(games, game => Ok(Box::new(game)))
+}
+
+fn from_json(json: &str) -> Result<Box<dyn AnyGame>> {
+    build(serde_json::from_str(json)?)
+}
+
+fn from_moves(game: &str, moves: &str) -> Result<Box<dyn AnyGame>> {
+    let kind = GameKind::from_str(game, true).map_err(|err| anyhow!(err))?;
+
+    let mut game = build(kind.default_game())?;
+    for token in moves.split_whitespace() {
+        game.make_move(token)?;
+    }
+
+    Ok(game)
+}
+
+fn to_js_error(err: anyhow::Error) -> JsError {
+    JsError::new(&err.to_string())
+}
+
+/// A bundled game, playable from JavaScript.
+#[wasm_bindgen]
+pub struct WasmGame(Box<dyn AnyGame>);
+
+#[wasm_bindgen]
+impl WasmGame {
+    /// Builds a game from the JSON form of its CLI arguments,
+    /// e.g. `{"TicTacToe": {"dimensions": 2, "size": 3, "moves": ["1-1"]}}`.
+    #[wasm_bindgen(js_name = fromJson)]
+    pub fn from_json(json: &str) -> Result<WasmGame, JsError> {
+        from_json(json).map(Self).map_err(to_js_error)
+    }
+
+    /// Builds a game (by its CLI name, e.g. `tic-tac-toe`) from its default position,
+    /// then plays a whitespace-separated list of moves.
+    #[wasm_bindgen(js_name = fromMoves)]
+    pub fn from_moves(game: &str, moves: &str) -> Result<WasmGame, JsError> {
+        from_moves(game, moves).map(Self).map_err(to_js_error)
+    }
+
+    /// The current position, as JSON.
+    pub fn position(&self) -> Result<String, JsError> {
+        self.0.position().map_err(to_js_error)
+    }
+
+    /// Every legal move, as a JSON array of strings.
+    #[wasm_bindgen(js_name = legalMoves)]
+    pub fn legal_moves(&self) -> Result<String, JsError> {
+        self.0.legal_moves().map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = makeMove)]
+    pub fn make_move(&mut self, game_move: &str) -> Result<(), JsError> {
+        self.0.make_move(game_move).map_err(to_js_error)
+    }
+
+    /// Scores every move, stopping early once the solver visited `max_nodes` positions
+    /// or searched for `max_ms` milliseconds.
+    ///
+    /// Returns JSON such as `{"complete": true, "best": "1-1", "moves": [{"move": "1-1", "score": 0}], "nodes": 100}`.
+    pub fn solve(&self, max_nodes: Option<u32>, max_ms: Option<f64>) -> Result<String, JsError> {
+        self.0
+            .solve(Budget {
+                max_nodes: max_nodes.map(u64::from),
+                max_ms,
+            })
+            .map_err(to_js_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tic_tac_toe_round_trip() {
+        let mut game = from_moves("tic-tac-toe", "1-1").unwrap();
+        assert_eq!(
+            game.position().unwrap(),
+            r#"{"dimensions":2,"size":3,"board":["---","-X-","---"]}"#
+        );
+
+        game.make_move("0-0").unwrap();
+        assert!(game.make_move("0-0").is_err());
+        assert_eq!(
+            game.legal_moves().unwrap(),
+            r#"["0-1","0-2","1-0","1-2","2-0","2-1","2-2"]"#
+        );
+
+        let solution: serde_json::Value =
+            serde_json::from_str(&game.solve(Budget::default()).unwrap()).unwrap();
+        assert_eq!(solution["complete"], true);
+        assert_eq!(solution["moves"].as_array().unwrap().len(), 7);
+        // the corner reply to the center holds the tie
+        assert_eq!(solution["moves"][0]["score"], 0);
+    }
+
+    #[test]
+    fn from_json_matches_moves() {
+        let json = r#"{"TicTacToe": {"dimensions": 2, "size": 3, "moves": ["1-1", "0-0"]}}"#;
+
+        assert_eq!(
+            from_json(json).unwrap().position().unwrap(),
+            from_moves("tic-tac-toe", "1-1 0-0")
+                .unwrap()
+                .position()
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn budget_stops_the_search() {
+        let game = from_moves("tic-tac-toe", "").unwrap();
+        let solution: serde_json::Value = serde_json::from_str(
+            &game
+                .solve(Budget {
+                    max_nodes: Some(50),
+                    max_ms: None,
+                })
+                .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(solution["complete"], false);
+        assert!(solution["nodes"].as_u64().unwrap() <= 51);
+    }
+}
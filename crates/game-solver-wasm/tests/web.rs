@@ -0,0 +1,56 @@
+//! The bindings as JavaScript calls them, compiled to WebAssembly.
+
+#![cfg(target_arch = "wasm32")]
+
+use game_solver_wasm::WasmGame;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn tic_tac_toe_round_trip() {
+    let mut game = WasmGame::from_moves("tic-tac-toe", "1-1").unwrap();
+    assert_eq!(
+        game.position().unwrap(),
+        r#"{"dimensions":2,"size":3,"board":["---","-X-","---"]}"#
+    );
+
+    game.make_move("0-0").unwrap();
+    assert!(game.make_move("0-0").is_err());
+    assert_eq!(
+        game.legal_moves().unwrap(),
+        r#"["0-1","0-2","1-0","1-2","2-0","2-1","2-2"]"#
+    );
+
+    let solution: serde_json::Value =
+        serde_json::from_str(&game.solve(None, None).unwrap()).unwrap();
+    assert_eq!(solution["complete"], true);
+    assert_eq!(solution["moves"].as_array().unwrap().len(), 7);
+    assert_eq!(solution["moves"][0]["score"], 0);
+
+    // the position it reached builds the same game again
+    let json = r#"{"TicTacToe": {"dimensions": 2, "size": 3, "moves": ["1-1", "0-0"]}}"#;
+    assert_eq!(
+        WasmGame::from_json(json).unwrap().position().unwrap(),
+        game.position().unwrap()
+    );
+}
+
+#[wasm_bindgen_test]
+fn budgets_stop_the_search_without_threads() {
+    let game = WasmGame::from_moves("tic-tac-toe", "").unwrap();
+    let solution: serde_json::Value =
+        serde_json::from_str(&game.solve(Some(50), None).unwrap()).unwrap();
+
+    assert_eq!(solution["complete"], false);
+    assert!(solution["nodes"].as_u64().unwrap() <= 51);
+
+    // a time budget reads the clock from JavaScript
+    let solution: serde_json::Value =
+        serde_json::from_str(&game.solve(None, Some(60_000.0)).unwrap()).unwrap();
+    assert_eq!(solution["complete"], true);
+}
+
+#[wasm_bindgen_test]
+fn illegal_input_is_a_js_error() {
+    assert!(WasmGame::from_moves("no-such-game", "").is_err());
+    assert!(WasmGame::from_moves("tic-tac-toe", "9-9").is_err());
+}
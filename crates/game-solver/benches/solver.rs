@@ -0,0 +1,645 @@
+//! Compares solver configurations on a small position of every bundled game with [criterion],
+//! so changes to the solver can be measured against the same yardstick.
+//!
+//! Run with `cargo bench -p game-solver`, optionally followed by `-- <filter>`
+//! to only run the benchmarks whose names match the filter. Criterion compares every run to the one before it,
+//! and `-- --save-baseline <name>` and `-- --baseline <name>` compare against a named one instead;
+//! `target/criterion/report/index.html` compares the configurations of each group side by side.
+//!
+//! Every configuration scores every move of the position, as the parallel solver does,
+//! and has to agree with the plain `HashMap` table.
+//!
+//! Then the move orderings of [`MoveOrdering`] are compared on 4x4 Tic-Tac-Toe, and so is Domineering's
+//! hottest-regions-first ordering on empty 5x5 and 4x6 boards. On the same 4x4 positions,
+//! leaving out the moves [`Game::dominated_moves`] gives is compared to searching every move.
+//! Besides their times, these print how many positions each searches, which unlike time doesn't depend on the machine.
+//!
+//! Then the games that implement [`IncrementalHash`] are timed with their hash kept up to date by their moves,
+//! compared to hashing their whole board at every probe.
+//!
+//! Last, the games that find winning moves without making them are timed against making every move
+//! to find them, as [`Game::find_immediately_resolvable_game`] does by default.
+
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    fmt::{Debug, Display},
+    hash::{Hash, Hasher},
+    sync::Arc,
+    time::Duration,
+};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use game_solver::{
+    dominance::Undominated,
+    game::{Game, GameState, GameStatus},
+    hash::{BuildIncrementalHasher, IncrementalHash},
+    move_scores,
+    ordering::MoveOrdering,
+    par_move_scores,
+    player::{PartizanPlayer, TwoPlayer},
+    solve, solve_ordered,
+    stats::Stats,
+    transposition::TranspositionCache,
+};
+use games::{
+    chomp::{self, Chomp},
+    domineering::{self, Domineering},
+    naive_nim::{self, Nim},
+    order_and_chaos::{self, OrderAndChaos},
+    reversi::{self, Reversi},
+    sprouts::{self, Sprouts},
+    tic_tac_toe::{self, TicTacToe},
+    util::{bench::BenchPosition, notation::NotatedGame},
+};
+
+/// The entries the bounded table can hold, which is far less than most of these positions need.
+const BOUNDED_CAPACITY: u64 = 1 << 12;
+
+/// Orders the moves of a game by how few replies they leave, which is cheap to compute for any game
+/// and tends to find winning (and forcing) moves first.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct MobilityOrdered<T>(T);
+
+impl<T: Game> Game for MobilityOrdered<T> {
+    type Move = T::Move;
+    type Iter<'a> = std::vec::IntoIter<T::Move> where T: 'a;
+    type MoveError = T::MoveError;
+    type Player = T::Player;
+
+    fn move_count(&self) -> usize {
+        self.0.move_count()
+    }
+
+    fn max_moves(&self) -> Option<usize> {
+        self.0.max_moves()
+    }
+
+    fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        self.0.make_move(m)
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        let mut moves = self
+            .0
+            .possible_moves()
+            .map(|m| {
+                let mut board = self.0.clone();
+                let replies = match board.make_move(&m) {
+                    Ok(()) if board.state() == GameState::Playable => {
+                        board.possible_moves().count()
+                    }
+                    _ => 0,
+                };
+                (replies, m)
+            })
+            .collect::<Vec<_>>();
+
+        // stable, so the game's own order breaks ties
+        moves.sort_by_key(|(replies, _)| *replies);
+        moves
+            .into_iter()
+            .map(|(_, m)| m)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn find_immediately_resolvable_game(&self) -> Result<Option<Self>, Self::MoveError> {
+        Ok(self.0.find_immediately_resolvable_game()?.map(Self))
+    }
+
+    fn cheap_win_check(&self) -> bool {
+        self.0.cheap_win_check()
+    }
+
+    fn status(&self) -> GameStatus {
+        self.0.status()
+    }
+
+    fn player(&self) -> Self::Player {
+        self.0.player()
+    }
+}
+
+/// Hashes its whole board at every probe, as a game that doesn't implement [`IncrementalHash`] would.
+#[derive(Clone, PartialEq, Eq)]
+struct Rehashed<T>(T);
+
+impl<T: IncrementalHash> Hash for Rehashed<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.0.full_hash());
+    }
+}
+
+impl<T: Game> Game for Rehashed<T> {
+    type Move = T::Move;
+    type Iter<'a> = T::Iter<'a> where T: 'a;
+    type MoveError = T::MoveError;
+    type Player = T::Player;
+
+    fn move_count(&self) -> usize {
+        self.0.move_count()
+    }
+
+    fn max_moves(&self) -> Option<usize> {
+        self.0.max_moves()
+    }
+
+    fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        self.0.make_move(m)
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        self.0.possible_moves()
+    }
+
+    fn move_count_hint(&self) -> (usize, Option<usize>) {
+        self.0.move_count_hint()
+    }
+
+    fn find_immediately_resolvable_game(&self) -> Result<Option<Self>, Self::MoveError> {
+        Ok(self.0.find_immediately_resolvable_game()?.map(Self))
+    }
+
+    fn cheap_win_check(&self) -> bool {
+        self.0.cheap_win_check()
+    }
+
+    fn status(&self) -> GameStatus {
+        self.0.status()
+    }
+
+    fn player(&self) -> Self::Player {
+        self.0.player()
+    }
+}
+
+/// Makes every move to find one that wins right away, as [`Game::find_immediately_resolvable_game`] does by default,
+/// but asks for it wherever the game does.
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct MadeEveryMove<T>(T);
+
+impl<T: Game> Game for MadeEveryMove<T> {
+    type Move = T::Move;
+    type Iter<'a> = T::Iter<'a> where T: 'a;
+    type MoveError = T::MoveError;
+    type Player = T::Player;
+
+    fn move_count(&self) -> usize {
+        self.0.move_count()
+    }
+
+    fn max_moves(&self) -> Option<usize> {
+        self.0.max_moves()
+    }
+
+    fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        self.0.make_move(m)
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        self.0.possible_moves()
+    }
+
+    fn move_count_hint(&self) -> (usize, Option<usize>) {
+        self.0.move_count_hint()
+    }
+
+    fn cheap_win_check(&self) -> bool {
+        self.0.cheap_win_check()
+    }
+
+    fn status(&self) -> GameStatus {
+        self.0.status()
+    }
+
+    fn player(&self) -> Self::Player {
+        self.0.player()
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Configuration {
+    /// `move_scores` with a plain `HashMap`, which every other configuration is compared to.
+    HashMap,
+    /// `move_scores` with a bounded, concurrent cache that has to evict entries.
+    Bounded,
+    /// `move_scores` with a plain `HashMap`, with moves ordered by [`MobilityOrdered`].
+    Ordered,
+    /// `par_move_scores`, which scores every move on its own thread.
+    Parallel,
+}
+
+impl Configuration {
+    const ALL: [Self; 4] = [Self::HashMap, Self::Bounded, Self::Ordered, Self::Parallel];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::HashMap => "hashmap",
+            Self::Bounded => "bounded",
+            Self::Ordered => "mobility",
+            Self::Parallel => "parallel",
+        }
+    }
+}
+
+/// The sum of the scores of every move, so configurations can be checked to agree.
+fn total<M, E>(scores: impl IntoIterator<Item = Result<(M, isize), E>>) -> isize {
+    scores
+        .into_iter()
+        .map(|result| match result {
+            Ok((_, score)) => score,
+            Err(_) => panic!("the solver failed"),
+        })
+        .sum()
+}
+
+/// Scores every move of `game` once in `configuration`.
+fn score<T, P>(game: &T, configuration: Configuration) -> isize
+where
+    P: TwoPlayer + Sync + 'static,
+    T: Game<Player = P> + Eq + Hash + Sync + Send + 'static,
+    T::Move: Sync + Send,
+    T::MoveError: Sync + Send,
+{
+    match configuration {
+        Configuration::HashMap => total(move_scores(game, &mut HashMap::new(), None, &None)),
+        Configuration::Bounded => {
+            let mut table = Arc::new(TranspositionCache::<T, RandomState>::with_capacity(
+                BOUNDED_CAPACITY,
+            ));
+            total(move_scores(game, &mut table, None, &None))
+        }
+        Configuration::Ordered => {
+            let game = MobilityOrdered(game.clone());
+            total(move_scores(&game, &mut HashMap::new(), None, &None))
+        }
+        Configuration::Parallel => total(par_move_scores(game, None, &None)),
+    }
+}
+
+/// Checks that `score` is the same as the first one checked against `baseline`.
+fn check_agrees(baseline: &mut Option<isize>, score: isize, description: impl Display) {
+    let expected = *baseline.get_or_insert(score);
+    assert_eq!(score, expected, "{description}");
+}
+
+/// The quick position of a game, which every game has.
+fn quick_position<T, A>(positions: Vec<BenchPosition<A>>) -> (&'static str, T)
+where
+    A: TryInto<T>,
+    A::Error: Debug,
+{
+    let position = positions
+        .into_iter()
+        .find(|position| position.quick)
+        .expect("every game has a quick position");
+
+    (
+        position.name,
+        position
+            .args
+            .try_into()
+            .expect("benchmark positions are valid"),
+    )
+}
+
+fn bench_game<T, P, A>(c: &mut Criterion, game: &str, positions: Vec<BenchPosition<A>>)
+where
+    P: TwoPlayer + Sync + 'static,
+    T: Game<Player = P> + Eq + Hash + Sync + Send + 'static,
+    T::Move: Sync + Send,
+    T::MoveError: Sync + Send,
+    A: TryInto<T>,
+    A::Error: Debug,
+{
+    let (position, board) = quick_position::<T, A>(positions);
+
+    let mut group = c.benchmark_group(game);
+    let mut baseline = None;
+
+    for configuration in Configuration::ALL {
+        let mut checked = false;
+        group.bench_function(BenchmarkId::new(configuration.name(), position), |b| {
+            if !checked {
+                check_agrees(
+                    &mut baseline,
+                    score(&board, configuration),
+                    format!("{game}: {} disagrees with the others", configuration.name()),
+                );
+                checked = true;
+            }
+
+            b.iter(|| score(&board, configuration));
+        });
+    }
+
+    group.finish();
+}
+
+fn configurations(c: &mut Criterion) {
+    bench_game::<Nim, _, _>(c, "naive-nim", naive_nim::bench_positions());
+    bench_game::<TicTacToe, _, _>(c, "tic-tac-toe", tic_tac_toe::bench_positions());
+    bench_game::<Chomp, _, _>(c, "chomp", chomp::bench_positions());
+    bench_game::<Domineering<5, 5>, _, _>(c, "domineering", domineering::bench_positions());
+    bench_game::<Sprouts, _, _>(c, "sprouts", sprouts::bench_positions());
+    bench_game::<OrderAndChaos<6, 6, 5, 6>, _, _>(
+        c,
+        "order-and-chaos",
+        order_and_chaos::bench_positions(),
+    );
+    bench_game::<Reversi, _, _>(c, "reversi", reversi::bench_positions());
+}
+
+/// The 4x4 Tic-Tac-Toe positions the move orderings are compared on.
+const ORDERING_POSITIONS: [&str; 3] = [
+    "X.../.O../..X./.... O",
+    "XO../..X./..../.... O",
+    "X..O/..../..../.... X",
+];
+
+/// Every ordering compared, named, starting with the game's own order.
+fn orderings() -> Vec<(&'static str, MoveOrdering<TicTacToe>)> {
+    vec![
+        ("game order", MoveOrdering::GameOrder),
+        ("table first", MoveOrdering::TtFirst),
+        ("central cells", MoveOrdering::central_cells_first()),
+        (
+            "table, central",
+            MoveOrdering::Chain(vec![
+                MoveOrdering::TtFirst,
+                MoveOrdering::central_cells_first(),
+            ]),
+        ),
+    ]
+}
+
+/// The score of solving `game` with `ordering`, and how many positions that searches.
+fn solve_counted<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    game: &T,
+    ordering: &MoveOrdering<T>,
+) -> (isize, u64) {
+    let stats = Stats::new(game.player(), game.move_count());
+    let score = solve_ordered(game, &mut HashMap::new(), Some(&stats), &None, ordering)
+        .unwrap_or_else(|_| panic!("the solver failed"));
+
+    (score, stats.states_explored.into_inner())
+}
+
+/// Prints how many positions a search of `position` searched, compared to the first search of it.
+fn report_nodes(position: &str, name: &str, nodes: u64, baseline_nodes: &mut Option<u64>) {
+    let baseline_nodes = *baseline_nodes.get_or_insert(nodes);
+
+    println!(
+        "{position}, {name}: {nodes} positions searched, {:.2}x the first",
+        nodes as f64 / baseline_nodes as f64
+    );
+}
+
+fn ordering(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tic-tac-toe ordering");
+
+    for notation in ORDERING_POSITIONS {
+        let game = TicTacToe::from_notation(notation).expect("ordering positions are valid");
+        let mut baseline = None;
+        let mut baseline_nodes = None;
+
+        for (name, ordering) in orderings() {
+            let mut checked = false;
+            group.bench_function(BenchmarkId::new(name, notation), |b| {
+                if !checked {
+                    let (score, nodes) = solve_counted(&game, &ordering);
+                    check_agrees(
+                        &mut baseline,
+                        score,
+                        format!("{notation}: {name} disagrees with the others"),
+                    );
+                    report_nodes(notation, name, nodes, &mut baseline_nodes);
+                    checked = true;
+                }
+
+                b.iter(|| {
+                    solve_ordered(&game, &mut HashMap::new(), None, &None, &ordering)
+                        .expect("the solver failed")
+                });
+            });
+        }
+    }
+
+    group.finish();
+}
+
+/// Compares Domineering's row-major moves to those of its hottest regions first,
+/// on an empty board of each size.
+fn bench_region_ordering<const WIDTH: usize, const HEIGHT: usize>(c: &mut Criterion) {
+    let mut group = c.benchmark_group(format!("domineering {WIDTH}x{HEIGHT} ordering"));
+    let position = format!("empty {WIDTH}x{HEIGHT}");
+
+    let game = Domineering::<WIDTH, HEIGHT>::new();
+    let mut baseline = None;
+    let mut baseline_nodes = None;
+
+    for (name, ordering) in [
+        ("row-major", MoveOrdering::GameOrder),
+        ("hottest regions", Domineering::hottest_regions_first()),
+        (
+            "table, hottest",
+            MoveOrdering::Chain(vec![
+                MoveOrdering::TtFirst,
+                Domineering::hottest_regions_first(),
+            ]),
+        ),
+    ] {
+        let mut checked = false;
+        group.bench_function(name, |b| {
+            if !checked {
+                let (score, nodes) = solve_counted(&game, &ordering);
+                check_agrees(
+                    &mut baseline,
+                    score,
+                    format!("{position}: {name} disagrees with the others"),
+                );
+                report_nodes(&position, name, nodes, &mut baseline_nodes);
+                checked = true;
+            }
+
+            b.iter(|| {
+                solve_ordered(&game, &mut HashMap::new(), None, &None, &ordering)
+                    .expect("the solver failed")
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn region_ordering(c: &mut Criterion) {
+    bench_region_ordering::<5, 5>(c);
+    bench_region_ordering::<4, 6>(c);
+}
+
+/// Compares solving 4x4 Tic-Tac-Toe while leaving out its dominated moves to searching every move.
+fn dominance(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tic-tac-toe dominated moves");
+
+    for notation in ORDERING_POSITIONS {
+        let game = TicTacToe::from_notation(notation).expect("ordering positions are valid");
+        let undominated = Undominated(game.clone());
+
+        let run = |search_all: bool, stats: Option<&Stats<PartizanPlayer>>| {
+            let score = if search_all {
+                solve(&undominated, &mut HashMap::new(), stats, &None).ok()
+            } else {
+                solve(&game, &mut HashMap::new(), stats, &None).ok()
+            };
+            score.expect("the solver failed")
+        };
+
+        let mut baseline = None;
+        let mut baseline_nodes = None;
+        for (name, search_all) in [("searched", true), ("left out", false)] {
+            let mut checked = false;
+            group.bench_function(BenchmarkId::new(name, notation), |b| {
+                if !checked {
+                    let stats = Stats::new(game.player(), game.move_count());
+                    check_agrees(
+                        &mut baseline,
+                        run(search_all, Some(&stats)),
+                        format!("{notation}: leaving out dominated moves changed the score"),
+                    );
+                    report_nodes(
+                        notation,
+                        &format!("dominated moves {name}"),
+                        stats.states_explored.into_inner(),
+                        &mut baseline_nodes,
+                    );
+                    checked = true;
+                }
+
+                b.iter(|| run(search_all, None));
+            });
+        }
+    }
+
+    group.finish();
+}
+
+fn bench_hashing<T, P, A>(c: &mut Criterion, game: &str, positions: Vec<BenchPosition<A>>)
+where
+    P: TwoPlayer + 'static,
+    T: IncrementalHash<Player = P> + Eq + Hash,
+    A: TryInto<T>,
+    A::Error: Debug,
+{
+    let (position, board) = quick_position::<T, A>(positions);
+
+    let mut group = c.benchmark_group(format!("{game} hashing"));
+    let mut baseline = None;
+
+    let rehashed = || {
+        total(move_scores(
+            &Rehashed(board.clone()),
+            &mut HashMap::new(),
+            None,
+            &None,
+        ))
+    };
+    let incremental = || total(move_scores(&board, &mut HashMap::new(), None, &None));
+    let pass_through = || {
+        total(move_scores(
+            &board,
+            &mut HashMap::with_hasher(BuildIncrementalHasher::default()),
+            None,
+            &None,
+        ))
+    };
+    let hashings: [(&str, &dyn Fn() -> isize); 3] = [
+        ("rehashed", &rehashed),
+        ("incremental", &incremental),
+        ("pass-through", &pass_through),
+    ];
+
+    for (name, run) in hashings {
+        let mut checked = false;
+        group.bench_function(BenchmarkId::new(name, position), |b| {
+            if !checked {
+                check_agrees(
+                    &mut baseline,
+                    run(),
+                    format!("{game}: {name} disagrees with the others"),
+                );
+                checked = true;
+            }
+
+            b.iter(run);
+        });
+    }
+
+    group.finish();
+}
+
+fn hashing(c: &mut Criterion) {
+    bench_hashing::<TicTacToe, _, _>(c, "tic-tac-toe", tic_tac_toe::bench_positions());
+    bench_hashing::<Domineering<5, 5>, _, _>(c, "domineering", domineering::bench_positions());
+}
+
+fn bench_win_check<T, P, A>(c: &mut Criterion, game: &str, positions: Vec<BenchPosition<A>>)
+where
+    P: TwoPlayer + 'static,
+    T: Game<Player = P> + Eq + Hash,
+    A: TryInto<T>,
+    A::Error: Debug,
+{
+    let (position, board) = quick_position::<T, A>(positions);
+
+    let mut group = c.benchmark_group(format!("{game} winning moves"));
+    let mut baseline = None;
+
+    let made = || {
+        total(move_scores(
+            &MadeEveryMove(board.clone()),
+            &mut HashMap::new(),
+            None,
+            &None,
+        ))
+    };
+    let direct = || total(move_scores(&board, &mut HashMap::new(), None, &None));
+    let checks: [(&str, &dyn Fn() -> isize); 2] = [("every move", &made), ("direct", &direct)];
+
+    for (name, run) in checks {
+        let mut checked = false;
+        group.bench_function(BenchmarkId::new(name, position), |b| {
+            if !checked {
+                check_agrees(
+                    &mut baseline,
+                    run(),
+                    format!("{game}: finding winning moves {name} disagrees with the others"),
+                );
+                checked = true;
+            }
+
+            b.iter(run);
+        });
+    }
+
+    group.finish();
+}
+
+fn winning_moves(c: &mut Criterion) {
+    bench_win_check::<TicTacToe, _, _>(c, "tic-tac-toe", tic_tac_toe::bench_positions());
+    bench_win_check::<OrderAndChaos<6, 6, 5, 6>, _, _>(
+        c,
+        "order-and-chaos",
+        order_and_chaos::bench_positions(),
+    );
+}
+
+criterion_group! {
+    name = benches;
+    // some positions take a good fraction of a second to solve, so fewer samples are taken than usual
+    config = Criterion::default()
+        .sample_size(10)
+        .warm_up_time(Duration::from_secs(1))
+        .measurement_time(Duration::from_secs(2));
+    targets = configurations, ordering, region_ordering, dominance, hashing, winning_moves
+}
+criterion_main!(benches);
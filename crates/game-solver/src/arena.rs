@@ -0,0 +1,234 @@
+//! Pits two agents against each other over many games, to compare them.
+//! This requires the `std` feature to be enabled.
+//!
+//! Agents are built anew for every game from a seed, so that random agents play
+//! different games, while the same [`Arena`] seed always plays the same games.
+
+use std::ops::Add;
+
+use crate::{
+    game::{Game, GameState},
+    policy::Policy,
+    strategy::Strategy,
+    GameSolveError,
+};
+
+/// Something that plays a game: a [`Policy`] (through [`PolicyAgent`])
+/// or a [`Strategy`] (through [`StrategyAgent`]).
+pub trait Agent<T: Game> {
+    /// The move to play in `game`, which is still playable.
+    fn choose(&mut self, game: &T) -> Result<T::Move, GameSolveError<T>>;
+}
+
+impl<T: Game, A: Agent<T> + ?Sized> Agent<T> for Box<A> {
+    fn choose(&mut self, game: &T) -> Result<T::Move, GameSolveError<T>> {
+        (**self).choose(game)
+    }
+}
+
+/// Plays the moves of a [`Policy`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PolicyAgent<P>(pub P);
+
+impl<T: Game, P: Policy<T>> Agent<T> for PolicyAgent<P> {
+    fn choose(&mut self, game: &T) -> Result<T::Move, GameSolveError<T>> {
+        Ok(self.0.choose(game))
+    }
+}
+
+/// Plays the best move of a [`Strategy`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StrategyAgent<S>(pub S);
+
+impl<T: Game, S: Strategy<T>> Agent<T> for StrategyAgent<S> {
+    fn choose(&mut self, game: &T) -> Result<T::Move, GameSolveError<T>> {
+        Ok(self
+            .0
+            .evaluate(game)?
+            .best_move
+            .expect("a playable game has a best move"))
+    }
+}
+
+/// The results of the games of an [`Arena`], from the point of view of its first agent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ArenaResults {
+    pub wins: u64,
+    pub draws: u64,
+    pub losses: u64,
+    /// The amount of moves played over every game.
+    pub moves: u64,
+}
+
+impl ArenaResults {
+    pub fn games(&self) -> u64 {
+        self.wins + self.draws + self.losses
+    }
+
+    /// The share of games won, from 0 to 1.
+    pub fn win_rate(&self) -> f64 {
+        self.wins as f64 / self.games() as f64
+    }
+
+    /// The average amount of moves in a game.
+    pub fn average_length(&self) -> f64 {
+        self.moves as f64 / self.games() as f64
+    }
+
+    /// The [Wilson score interval](https://en.wikipedia.org/wiki/Binomial_proportion_confidence_interval#Wilson_score_interval)
+    /// of the win rate, which holds the true win rate with 95% confidence.
+    pub fn win_rate_interval(&self) -> (f64, f64) {
+        // the 97.5th percentile of the standard normal distribution
+        const Z: f64 = 1.96;
+
+        let games = self.games() as f64;
+        if games == 0.0 {
+            return (0.0, 1.0);
+        }
+
+        let rate = self.win_rate();
+        let center = rate + Z * Z / (2.0 * games);
+        let spread = Z * (rate * (1.0 - rate) / games + Z * Z / (4.0 * games * games)).sqrt();
+        let denominator = 1.0 + Z * Z / games;
+
+        (
+            ((center - spread) / denominator).max(0.0),
+            ((center + spread) / denominator).min(1.0),
+        )
+    }
+}
+
+impl Add for ArenaResults {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            wins: self.wins + other.wins,
+            draws: self.draws + other.draws,
+            losses: self.losses + other.losses,
+            moves: self.moves + other.moves,
+        }
+    }
+}
+
+/// Plays games between two agents, alternating which one moves first.
+#[derive(Debug, Clone, Copy)]
+pub struct Arena {
+    pub games: u64,
+    /// The seed the agents of every game are built from.
+    pub seed: u64,
+}
+
+impl Arena {
+    pub fn new(games: u64, seed: u64) -> Self {
+        Self { games, seed }
+    }
+
+    /// The seed of the `index`th game, spread out so that neighbouring games are unrelated.
+    fn game_seed(&self, index: u64) -> u64 {
+        // the finalizer of [SplitMix64](https://prng.di.unimi.it/splitmix64.c)
+        let mut z = self
+            .seed
+            .wrapping_add(index.wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Plays the `index`th game, where the first agent moves first in even games.
+    fn play_game<T: Game, A: Agent<T>, B: Agent<T>>(
+        &self,
+        start: &T,
+        index: u64,
+        agent_a: &impl Fn(u64) -> A,
+        agent_b: &impl Fn(u64) -> B,
+    ) -> Result<ArenaResults, GameSolveError<T>> {
+        let seed = self.game_seed(index);
+        let mut a = agent_a(seed);
+        let mut b = agent_b(!seed);
+
+        let mut game = start.clone();
+        // whether the first agent is to move
+        let mut a_to_move = index % 2 == 0;
+
+        loop {
+            match game.state() {
+                GameState::Playable => (),
+                GameState::Tie => {
+                    return Ok(ArenaResults {
+                        draws: 1,
+                        moves: (game.move_count() - start.move_count()) as u64,
+                        ..Default::default()
+                    });
+                }
+                GameState::Win(winner) => {
+                    // the winner is either the player to move, or the player that just moved.
+                    let a_won = if winner == game.player() {
+                        a_to_move
+                    } else {
+                        !a_to_move
+                    };
+
+                    return Ok(ArenaResults {
+                        wins: a_won as u64,
+                        losses: !a_won as u64,
+                        moves: (game.move_count() - start.move_count()) as u64,
+                        ..Default::default()
+                    });
+                }
+            }
+
+            let m = if a_to_move {
+                a.choose(&game)?
+            } else {
+                b.choose(&game)?
+            };
+            game.make_move(&m).map_err(GameSolveError::MoveError)?;
+            a_to_move = !a_to_move;
+        }
+    }
+
+    /// Plays every game from `start`, one after the other,
+    /// building the agents of each game from its seed.
+    pub fn run<T: Game, A: Agent<T>, B: Agent<T>>(
+        &self,
+        start: &T,
+        agent_a: impl Fn(u64) -> A,
+        agent_b: impl Fn(u64) -> B,
+    ) -> Result<ArenaResults, GameSolveError<T>> {
+        (0..self.games).try_fold(ArenaResults::default(), |results, index| {
+            Ok(results + self.play_game(start, index, &agent_a, &agent_b)?)
+        })
+    }
+
+    /// Parallelized version of [`Arena::run`], playing many games at once.
+    /// This requires the `rayon` feature to be enabled.
+    ///
+    /// The games, and so the results, are the same as those of [`Arena::run`].
+    /// They're played on the rayon thread pool this is called in (see [`rayon::ThreadPool::install`]),
+    /// or one after the other if it has a single thread.
+    #[cfg(feature = "rayon")]
+    pub fn par_run<T, A, B>(
+        &self,
+        start: &T,
+        agent_a: impl Fn(u64) -> A + Sync,
+        agent_b: impl Fn(u64) -> B + Sync,
+    ) -> Result<ArenaResults, GameSolveError<T>>
+    where
+        T: Game + Sync,
+        T::MoveError: Send,
+        A: Agent<T>,
+        B: Agent<T>,
+    {
+        use rayon::prelude::*;
+
+        if rayon::current_num_threads() == 1 {
+            return self.run(start, agent_a, agent_b);
+        }
+
+        (0..self.games)
+            .into_par_iter()
+            .map(|index| self.play_game(start, index, &agent_a, &agent_b))
+            .try_reduce(ArenaResults::default, |a, b| Ok(a + b))
+    }
+}
@@ -0,0 +1,95 @@
+//! Solving many independent positions at once, such as the lines of a batch file,
+//! the positions of an opening book, or those of a tablebase to verify.
+//! This requires the `rayon` feature to be enabled.
+//!
+//! Unlike [`par_move_scores`](crate::par_move_scores), which splits the search of one position,
+//! every position here is searched on its own thread, sharing one transposition table.
+
+use std::{
+    hash::{BuildHasher, Hash},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use rayon::prelude::*;
+
+use crate::{
+    game::Game, player::TwoPlayer, session::deadline_token, solve, stats::Stats,
+    transposition::TranspositionCache, GameSolveError,
+};
+
+/// How the search of one position of [`par_solve_many`] ended.
+#[derive(Debug)]
+pub enum BatchScore<T: Game> {
+    /// The position was solved, with this score.
+    Solved(isize),
+    /// The deadline passed before the position was solved.
+    Unfinished,
+    /// The search failed.
+    Failed(GameSolveError<T>),
+}
+
+/// The search of one position of [`par_solve_many`].
+#[derive(Debug)]
+pub struct BatchResult<T: Game> {
+    pub score: BatchScore<T>,
+    /// What the search of this position went through, even if it didn't finish.
+    pub stats: Stats<T::Player>,
+    /// How long the position was searched for.
+    pub elapsed: Duration,
+}
+
+/// Solves every position in `positions` in parallel, sharing `table`,
+/// returning their results in the same order.
+///
+/// Each position is a task of its own on the rayon thread pool this is called in,
+/// so threads that are done with the easy positions take the ones still waiting,
+/// and a few hard positions don't hold up the rest.
+///
+/// Once `deadline` passes, positions still being searched are stopped, and those not yet started are skipped,
+/// all [`BatchScore::Unfinished`]. A stopped search never stores the score of a position it didn't finish,
+/// so the table stays valid for the next batch.
+pub fn par_solve_many<T, P, S>(
+    positions: &[T],
+    table: &Arc<TranspositionCache<T, S>>,
+    deadline: Option<Instant>,
+) -> Vec<BatchResult<T>>
+where
+    P: TwoPlayer + Send + Sync + 'static,
+    T: Game<Player = P> + Eq + Hash + Send + Sync + 'static,
+    T::MoveError: Send,
+    S: BuildHasher + Default + Send + Sync + Clone + 'static,
+{
+    let (token, _timer) = match deadline {
+        Some(deadline) => {
+            let (token, timer) = deadline_token(deadline);
+            (Some(token), Some(timer))
+        }
+        None => (None, None),
+    };
+
+    positions
+        .par_iter()
+        .with_max_len(1)
+        .map(|game| {
+            let stats = Stats::new(game.player(), game.move_count());
+            let started = Instant::now();
+
+            let score = if deadline.is_some_and(|deadline| started >= deadline) {
+                BatchScore::Unfinished
+            } else {
+                match solve(game, &mut Arc::clone(table), Some(&stats), &token) {
+                    Ok(score) => BatchScore::Solved(score),
+                    Err(GameSolveError::CancellationTokenError) => BatchScore::Unfinished,
+                    Err(err) => BatchScore::Failed(err),
+                }
+            };
+
+            BatchResult {
+                score,
+                stats,
+                elapsed: started.elapsed(),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,313 @@
+//! Counts what a game tree is made of: how many positions it has, how many of them are distinct,
+//! how its games end, and how many moves there are at each ply.
+//!
+//! The tree is walked depth first without being kept, so only the distinct positions take up memory,
+//! and with the `hyperloglog` feature, [`HyperLogLog`] estimates how many there are in a fixed amount of it.
+
+use std::{collections::HashSet, hash::Hash};
+
+use crate::game::{Game, GameState};
+
+/// Counts distinct positions, for [`census_with`].
+pub trait DistinctCounter<K> {
+    /// Counts `key`, returning false if it was certainly counted before.
+    fn insert(&mut self, key: K) -> bool;
+
+    /// How many distinct keys were counted, which may be an estimate.
+    fn count(&self) -> u64;
+}
+
+impl<K: Eq + Hash> DistinctCounter<K> for HashSet<K> {
+    fn insert(&mut self, key: K) -> bool {
+        HashSet::insert(self, key)
+    }
+
+    fn count(&self) -> u64 {
+        self.len() as u64
+    }
+}
+
+/// Estimates how many distinct keys it was given, within about `1.04 / sqrt(2^precision)`,
+/// with `2^precision` bytes whatever the count.
+///
+/// Keys are hashed with the standard library's [`DefaultHasher`](std::hash::DefaultHasher),
+/// whose keys are fixed, so the same keys always give the same estimate.
+///
+/// ```
+/// use game_solver::census::{DistinctCounter, HyperLogLog};
+///
+/// let mut counter = HyperLogLog::new(12);
+/// for key in 0..100_000u64 {
+///     // counting a key again doesn't change the estimate
+///     counter.insert(key);
+///     counter.insert(key);
+/// }
+///
+/// // the standard error is 1.6%, so this is over 3 of them
+/// let error = (counter.estimate() - 100_000.0).abs() / 100_000.0;
+/// assert!(error < 0.05, "{}", counter.estimate());
+/// ```
+#[cfg(feature = "hyperloglog")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HyperLogLog {
+    precision: u32,
+    /// The most leading zeros (plus one) seen among the hashes that fall in each register.
+    registers: Vec<u8>,
+}
+
+#[cfg(feature = "hyperloglog")]
+impl HyperLogLog {
+    /// # Panics
+    ///
+    /// If `precision` isn't between 4 and 18.
+    pub fn new(precision: u32) -> Self {
+        assert!(
+            (4..=18).contains(&precision),
+            "the precision of a HyperLogLog is between 4 and 18, not {precision}"
+        );
+
+        Self {
+            precision,
+            registers: vec![0; 1 << precision],
+        }
+    }
+
+    fn insert_hash(&mut self, hash: u64) {
+        let index = (hash >> (64 - self.precision)) as usize;
+        // the bit past the register's can't be shifted out, so the rank is at most 64 - precision + 1
+        let rest = (hash << self.precision) | (1 << (self.precision - 1));
+        let rank = rest.leading_zeros() as u8 + 1;
+
+        self.registers[index] = self.registers[index].max(rank);
+    }
+
+    /// The estimated number of distinct keys.
+    pub fn estimate(&self) -> f64 {
+        let m = self.registers.len() as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum = self
+            .registers
+            .iter()
+            .map(|&rank| 2f64.powi(-i32::from(rank)))
+            .sum::<f64>();
+        let estimate = alpha * m * m / sum;
+
+        // small counts leave registers empty, which linear counting uses instead
+        let empty = self.registers.iter().filter(|&&rank| rank == 0).count();
+        if estimate <= 2.5 * m && empty > 0 {
+            m * (m / empty as f64).ln()
+        } else {
+            estimate
+        }
+    }
+}
+
+#[cfg(feature = "hyperloglog")]
+impl<K: Hash> DistinctCounter<K> for HyperLogLog {
+    /// Always true, as a HyperLogLog can't tell whether it saw a key before.
+    fn insert(&mut self, key: K) -> bool {
+        use std::hash::{BuildHasher, BuildHasherDefault, DefaultHasher};
+
+        self.insert_hash(BuildHasherDefault::<DefaultHasher>::default().hash_one(key));
+        true
+    }
+
+    fn count(&self) -> u64 {
+        self.estimate().round() as u64
+    }
+}
+
+/// The positions [`census`] found `ply` moves into the game.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PlyCensus {
+    /// Every position, once for each sequence of moves that reaches it.
+    pub nodes: u64,
+    /// The positions where the game is over.
+    pub terminal: u64,
+    /// The positions whose moves were counted: those where the game goes on, short of the depth limit.
+    pub expanded: u64,
+    /// How many moves the expanded positions have altogether.
+    pub moves: u64,
+    /// The fewest moves of an expanded position, or 0 if there are none.
+    pub min_moves: usize,
+    /// The most moves of an expanded position.
+    pub max_moves: usize,
+}
+
+impl PlyCensus {
+    /// The average number of moves of the expanded positions, or None if there are none.
+    pub fn branching_factor(&self) -> Option<f64> {
+        (self.expanded > 0).then(|| self.moves as f64 / self.expanded as f64)
+    }
+
+    fn expand(&mut self, moves: usize) {
+        self.min_moves = if self.expanded == 0 {
+            moves
+        } else {
+            self.min_moves.min(moves)
+        };
+        self.max_moves = self.max_moves.max(moves);
+        self.expanded += 1;
+        self.moves += moves as u64;
+    }
+}
+
+/// What a game tree is made of, up to a depth.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Census<P> {
+    /// Every position, including the one the walk started from,
+    /// once for each sequence of moves that reaches it.
+    pub nodes: u64,
+    /// The distinct positions.
+    pub unique: u64,
+    /// The distinct positions, counting symmetric ones (with the same canonical position) once.
+    pub canonical: u64,
+    /// The finished games, once for each sequence of moves that ends them.
+    pub terminal: u64,
+    /// How many of the finished games each player won, in the order they first won one.
+    pub wins: Vec<(P, u64)>,
+    /// How many of the finished games were tied.
+    pub ties: u64,
+    /// The positions found at each ply, starting with the one the walk started from.
+    pub plies: Vec<PlyCensus>,
+}
+
+/// Walks the game tree of `game` up to `max_depth` moves deep, counting its positions.
+///
+/// Like [`census_with`], but every distinct position is kept in a [`HashSet`],
+/// and positions are only symmetric to themselves.
+///
+/// ```
+/// use game_solver::{census::census, perft};
+/// use games::naive_nim::Nim;
+///
+/// let game = Nim::new(vec![3, 5, 7]);
+/// let census = census(&game, 2);
+///
+/// // every ply has as many positions as there are sequences of moves that long
+/// for (depth, ply) in census.plies.iter().enumerate() {
+///     assert_eq!(ply.nodes, perft(&game, depth));
+/// }
+/// assert_eq!(census.plies[0].branching_factor(), Some(15.0));
+///
+/// // the depth limit stops before any game ends, and positions repeat after two moves
+/// assert_eq!(census.terminal, 0);
+/// assert!(census.unique < census.nodes);
+/// ```
+///
+/// # Panics
+///
+/// If the game rejects one of its own possible moves.
+pub fn census<T: Game + Eq + Hash>(game: &T, max_depth: usize) -> Census<T::Player> {
+    census_with(
+        game,
+        max_depth,
+        T::clone,
+        &mut HashSet::new(),
+        &mut HashSet::new(),
+    )
+}
+
+/// Walks the game tree of `game` up to `max_depth` moves deep, counting its positions,
+/// and its distinct positions with `unique`, and their `canonical` positions with `canonical_unique`.
+///
+/// A position's canonical position is only found when `unique` may not have seen it before.
+///
+/// # Panics
+///
+/// If the game rejects one of its own possible moves.
+pub fn census_with<T: Game>(
+    game: &T,
+    max_depth: usize,
+    canonical: impl Fn(&T) -> T,
+    unique: &mut dyn DistinctCounter<T>,
+    canonical_unique: &mut dyn DistinctCounter<T>,
+) -> Census<T::Player> {
+    let mut census = Census {
+        nodes: 0,
+        unique: 0,
+        canonical: 0,
+        terminal: 0,
+        wins: Vec::new(),
+        ties: 0,
+        plies: Vec::new(),
+    };
+
+    walk(
+        game,
+        0,
+        max_depth,
+        &canonical,
+        unique,
+        canonical_unique,
+        &mut census,
+    );
+
+    census.nodes = census.plies.iter().map(|ply| ply.nodes).sum();
+    census.unique = unique.count();
+    census.canonical = canonical_unique.count();
+    census
+}
+
+fn walk<T: Game>(
+    game: &T,
+    ply: usize,
+    max_depth: usize,
+    canonical: &impl Fn(&T) -> T,
+    unique: &mut dyn DistinctCounter<T>,
+    canonical_unique: &mut dyn DistinctCounter<T>,
+    census: &mut Census<T::Player>,
+) {
+    if census.plies.len() <= ply {
+        census.plies.push(PlyCensus::default());
+    }
+    census.plies[ply].nodes += 1;
+
+    if unique.insert(game.clone()) {
+        canonical_unique.insert(canonical(game));
+    }
+
+    match game.state() {
+        GameState::Playable => (),
+        GameState::Tie => {
+            census.plies[ply].terminal += 1;
+            census.terminal += 1;
+            census.ties += 1;
+            return;
+        }
+        GameState::Win(player) => {
+            census.plies[ply].terminal += 1;
+            census.terminal += 1;
+            match census.wins.iter_mut().find(|(winner, _)| *winner == player) {
+                Some((_, wins)) => *wins += 1,
+                None => census.wins.push((player, 1)),
+            }
+            return;
+        }
+    }
+
+    if ply == max_depth {
+        return;
+    }
+
+    let mut moves = 0;
+    for m in game.possible_moves() {
+        let mut board = game.clone();
+        if let Err(err) = board.make_move(&m) {
+            panic!("a possible move could not be made: {err}");
+        }
+
+        walk(
+            &board,
+            ply + 1,
+            max_depth,
+            canonical,
+            unique,
+            canonical_unique,
+            census,
+        );
+        moves += 1;
+    }
+
+    census.plies[ply].expand(moves);
+}
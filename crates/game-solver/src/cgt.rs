@@ -0,0 +1,876 @@
+//! Values of short partizan games from combinatorial game theory,
+//! which tell not just who wins a position, but how it adds up with others.
+//!
+//! Every value has a unique [`CanonicalGame`] form, with no dominated or reversible options,
+//! so two positions have the same value exactly when their canonical forms are equal.
+
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt,
+    hash::Hash,
+    ops::{Add, Neg, Sub},
+};
+
+use crate::{
+    game::{Game, Normal, PartizanGame},
+    nimber::Nimber,
+    player::{ImpartialPlayer, PartizanPlayer, TwoPlayer},
+    solve_outcome,
+    strategy::Outcome,
+    GameSolveError,
+};
+
+/// A game in canonical form: `{ left options | right options }`, as simple as its value allows.
+///
+/// Games are partially ordered by how good they are for Left:
+/// a game is greater than 0 if Left wins it whoever starts, less than 0 if Right does,
+/// equal to 0 if the second player does, and confused with 0 (incomparable) if the first player does.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct CanonicalGame {
+    left: Vec<CanonicalGame>,
+    right: Vec<CanonicalGame>,
+}
+
+/// A total order on the forms of games, only used to keep options in a unique order.
+fn form_cmp(a: &CanonicalGame, b: &CanonicalGame) -> Ordering {
+    fn options_cmp(a: &[CanonicalGame], b: &[CanonicalGame]) -> Ordering {
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| form_cmp(a, b))
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or_else(|| a.len().cmp(&b.len()))
+    }
+
+    options_cmp(&a.left, &b.left).then_with(|| options_cmp(&a.right, &b.right))
+}
+
+impl CanonicalGame {
+    /// The game with the given options, which have to be canonical, simplified to its canonical form.
+    pub fn new(left: Vec<CanonicalGame>, right: Vec<CanonicalGame>) -> Self {
+        let mut game = Self { left, right };
+
+        loop {
+            game.sort_options();
+            let simplified = game
+                .without_dominated_options()
+                .bypassing_reversible_options();
+            if simplified == game {
+                return game;
+            }
+
+            game = simplified;
+        }
+    }
+
+    /// 0, the game where neither player can move, which the second player wins.
+    pub fn zero() -> Self {
+        Self {
+            left: Vec::new(),
+            right: Vec::new(),
+        }
+    }
+
+    /// The integer `n`: `n` free moves for Left if it's positive, or `-n` for Right if it's negative.
+    pub fn integer(n: isize) -> Self {
+        (0..n.unsigned_abs()).fold(Self::zero(), |game, _| {
+            if n > 0 {
+                Self {
+                    left: vec![game],
+                    right: Vec::new(),
+                }
+            } else {
+                Self {
+                    left: Vec::new(),
+                    right: vec![game],
+                }
+            }
+        })
+    }
+
+    /// The nimber `*n`, a heap of `n` in Nim, where both players can move to any smaller nimber.
+    pub fn nimber(n: usize) -> Self {
+        let smaller = (0..n).map(Self::nimber).collect::<Vec<_>>();
+
+        Self {
+            left: smaller.clone(),
+            right: smaller,
+        }
+    }
+
+    pub fn left_options(&self) -> &[CanonicalGame] {
+        &self.left
+    }
+
+    pub fn right_options(&self) -> &[CanonicalGame] {
+        &self.right
+    }
+
+    /// The thermograph of `self`, which tells how hot it is, and its mean value.
+    pub fn thermograph(&self) -> Thermograph {
+        Thermograph::of(self, &mut HashMap::new())
+    }
+
+    /// The temperature of `self`, from its [`thermograph`](Self::thermograph).
+    pub fn temperature(&self) -> Dyadic {
+        self.thermograph().temperature()
+    }
+
+    /// The mean value of `self`, from its [`thermograph`](Self::thermograph).
+    pub fn mean(&self) -> Dyadic {
+        self.thermograph().mast()
+    }
+
+    /// Whether `self` is at most `other` (for Left): `self` has no left option at least `other`,
+    /// and `other` has no right option at most `self`.
+    fn less_or_equal(&self, other: &Self) -> bool {
+        !self.left.iter().any(|left| other.less_or_equal(left))
+            && !other.right.iter().any(|right| right.less_or_equal(self))
+    }
+
+    fn sort_options(&mut self) {
+        for options in [&mut self.left, &mut self.right] {
+            options.sort_by(form_cmp);
+            options.dedup();
+        }
+    }
+
+    /// Removes the left options that are at most another left option,
+    /// and the right options that are at least another right option.
+    /// The options have to be sorted, so that equal ones have already been removed.
+    fn without_dominated_options(&self) -> Self {
+        let undominated = |options: &[Self], dominates: fn(&Self, &Self) -> bool| {
+            options
+                .iter()
+                .enumerate()
+                .filter(|(i, option)| {
+                    !options
+                        .iter()
+                        .enumerate()
+                        .any(|(j, other)| *i != j && dominates(other, option))
+                })
+                .map(|(_, option)| option.clone())
+                .collect()
+        };
+
+        Self {
+            left: undominated(&self.left, |other, option| option.less_or_equal(other)),
+            right: undominated(&self.right, |other, option| other.less_or_equal(option)),
+        }
+    }
+
+    /// Replaces each left option with a right option at most `self` by that right option's left options,
+    /// and each right option with a left option at least `self` by that left option's right options.
+    fn bypassing_reversible_options(&self) -> Self {
+        let left = self
+            .left
+            .iter()
+            .flat_map(
+                |left| match left.right.iter().find(|reply| reply.less_or_equal(self)) {
+                    Some(reply) => reply.left.clone(),
+                    None => vec![left.clone()],
+                },
+            )
+            .collect();
+
+        let right = self
+            .right
+            .iter()
+            .flat_map(
+                |right| match right.left.iter().find(|reply| self.less_or_equal(reply)) {
+                    Some(reply) => reply.right.clone(),
+                    None => vec![right.clone()],
+                },
+            )
+            .collect();
+
+        Self { left, right }
+    }
+
+    /// The value of `self` as a number, if it is one.
+    fn number(&self) -> Option<Dyadic> {
+        match (&self.left[..], &self.right[..]) {
+            ([], []) => Some(Dyadic::integer(0)),
+            ([left], []) => Some(left.number()?.add_integer(1)),
+            ([], [right]) => Some(right.number()?.add_integer(-1)),
+            ([left], [right]) => {
+                let (left, right) = (left.number()?, right.number()?);
+                (left < right).then(|| left.midpoint(right))
+            }
+            _ => None,
+        }
+    }
+
+    /// The value of `self` as a number plus a nimber, if it is one.
+    fn number_and_nimber(&self) -> Option<(Dyadic, usize)> {
+        if let Some(number) = self.number() {
+            return Some((number, 0));
+        }
+
+        // x + *n is { x, x + *, ..., x + *(n - 1) | the same }
+        if self.left != self.right {
+            return None;
+        }
+
+        let parts = self
+            .left
+            .iter()
+            .map(Self::number_and_nimber)
+            .collect::<Option<Vec<_>>>()?;
+        let number = parts.first()?.0;
+
+        let mut nimbers = parts
+            .iter()
+            .map(|&(option_number, nimber)| (option_number == number).then_some(nimber))
+            .collect::<Option<Vec<_>>>()?;
+        nimbers.sort_unstable();
+
+        nimbers
+            .iter()
+            .enumerate()
+            .all(|(i, &nimber)| i == nimber)
+            .then_some((number, nimbers.len()))
+    }
+}
+
+impl From<Nimber> for CanonicalGame {
+    fn from(nimber: Nimber) -> Self {
+        Self::nimber(nimber.into())
+    }
+}
+
+impl PartialOrd for CanonicalGame {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self.less_or_equal(other), other.less_or_equal(self)) {
+            (true, true) => Some(Ordering::Equal),
+            (true, false) => Some(Ordering::Less),
+            (false, true) => Some(Ordering::Greater),
+            (false, false) => None,
+        }
+    }
+}
+
+impl Neg for &CanonicalGame {
+    type Output = CanonicalGame;
+
+    /// The game with the players' roles swapped.
+    fn neg(self) -> CanonicalGame {
+        let mut game = CanonicalGame {
+            left: self.right.iter().map(Neg::neg).collect(),
+            right: self.left.iter().map(Neg::neg).collect(),
+        };
+        game.sort_options();
+
+        game
+    }
+}
+
+impl Neg for CanonicalGame {
+    type Output = CanonicalGame;
+
+    fn neg(self) -> CanonicalGame {
+        -&self
+    }
+}
+
+impl Add for &CanonicalGame {
+    type Output = CanonicalGame;
+
+    /// The disjunctive sum: both games side by side, where each move is made in one of them.
+    fn add(self, other: &CanonicalGame) -> CanonicalGame {
+        let left = self
+            .left
+            .iter()
+            .map(|left| left + other)
+            .chain(other.left.iter().map(|left| self + left))
+            .collect();
+        let right = self
+            .right
+            .iter()
+            .map(|right| right + other)
+            .chain(other.right.iter().map(|right| self + right))
+            .collect();
+
+        CanonicalGame::new(left, right)
+    }
+}
+
+impl Add for CanonicalGame {
+    type Output = CanonicalGame;
+
+    fn add(self, other: CanonicalGame) -> CanonicalGame {
+        &self + &other
+    }
+}
+
+/// Written as a number (such as `-2` or `3/4`), a nimber (such as `*` or `*2`), both (such as `1*`),
+/// a switch `±x`, or otherwise as `{left options | right options}`.
+impl fmt::Display for CanonicalGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some((number, nimber)) = self.number_and_nimber() {
+            if number != Dyadic::integer(0) || nimber == 0 {
+                write!(f, "{number}")?;
+            }
+
+            return match nimber {
+                0 => Ok(()),
+                1 => write!(f, "*"),
+                nimber => write!(f, "*{nimber}"),
+            };
+        }
+
+        if let ([left], [right]) = (&self.left[..], &self.right[..]) {
+            if let (Some(left), Some(right)) = (left.number(), right.number()) {
+                if left == -right && right < left {
+                    return write!(f, "±{left}");
+                }
+            }
+        }
+
+        let options = |options: &[Self]| {
+            options
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+
+        write!(f, "{{{} | {}}}", options(&self.left), options(&self.right))
+    }
+}
+
+impl fmt::Debug for CanonicalGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// A number `numerator / 2^exponent`, in lowest terms: the only numbers short games can be,
+/// and the temperatures and mean values of their thermographs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Dyadic {
+    numerator: isize,
+    exponent: u32,
+}
+
+impl Dyadic {
+    /// `numerator / 2^exponent`, in lowest terms.
+    pub fn new(numerator: isize, exponent: u32) -> Self {
+        Self {
+            numerator,
+            exponent,
+        }
+        .reduced()
+    }
+
+    pub fn integer(n: isize) -> Self {
+        Self {
+            numerator: n,
+            exponent: 0,
+        }
+    }
+
+    pub fn numerator(self) -> isize {
+        self.numerator
+    }
+
+    pub fn denominator(self) -> usize {
+        1 << self.exponent
+    }
+
+    fn reduced(mut self) -> Self {
+        while self.exponent > 0 && self.numerator % 2 == 0 {
+            self.numerator /= 2;
+            self.exponent -= 1;
+        }
+
+        self
+    }
+
+    /// The numerators of `self` and `other` over their common denominator, and its exponent.
+    fn common(self, other: Self) -> (isize, isize, u32) {
+        let exponent = self.exponent.max(other.exponent);
+
+        (
+            self.numerator << (exponent - self.exponent),
+            other.numerator << (exponent - other.exponent),
+            exponent,
+        )
+    }
+
+    fn add_integer(self, n: isize) -> Self {
+        self + Self::integer(n)
+    }
+
+    fn midpoint(self, other: Self) -> Self {
+        let (a, b, exponent) = self.common(other);
+
+        Self::new(a + b, exponent + 1)
+    }
+
+    fn times(self, n: isize) -> Self {
+        Self::new(self.numerator * n, self.exponent)
+    }
+
+    /// `self / divisor`, where `divisor` is plus or minus a power of two.
+    fn divided_by(self, divisor: isize) -> Self {
+        assert!(
+            divisor.unsigned_abs().is_power_of_two(),
+            "{divisor} is not a power of two"
+        );
+
+        Self::new(
+            self.numerator * divisor.signum(),
+            self.exponent + divisor.unsigned_abs().trailing_zeros(),
+        )
+    }
+}
+
+impl Add for Dyadic {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let (a, b, exponent) = self.common(other);
+
+        Self::new(a + b, exponent)
+    }
+}
+
+impl Sub for Dyadic {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Neg for Dyadic {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            numerator: -self.numerator,
+            exponent: self.exponent,
+        }
+    }
+}
+
+impl Ord for Dyadic {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b, _) = self.common(*other);
+        a.cmp(&b)
+    }
+}
+
+impl PartialOrd for Dyadic {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Dyadic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.exponent == 0 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator())
+        }
+    }
+}
+
+/// A continuous piecewise linear function of the temperature `t`, for `t >= -1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Trajectory {
+    /// The temperature each piece starts at, its value there, and its slope, in order,
+    /// where the first piece starts at -1 and the last one goes on forever.
+    pieces: Vec<(Dyadic, Dyadic, isize)>,
+}
+
+impl Trajectory {
+    fn constant(value: Dyadic) -> Self {
+        Self {
+            pieces: vec![(Dyadic::integer(-1), value, 0)],
+        }
+    }
+
+    /// The piece `t` is in.
+    fn piece(&self, t: Dyadic) -> (Dyadic, Dyadic, isize) {
+        self.pieces
+            .iter()
+            .rev()
+            .find(|&&(start, _, _)| start <= t)
+            .copied()
+            .unwrap_or(self.pieces[0])
+    }
+
+    fn at(&self, t: Dyadic) -> Dyadic {
+        let (start, value, slope) = self.piece(t);
+        value + (t - start).times(slope)
+    }
+
+    fn slope_at(&self, t: Dyadic) -> isize {
+        self.piece(t).2
+    }
+
+    /// Adds a piece, unless it continues the last one.
+    fn push(&mut self, start: Dyadic, value: Dyadic, slope: isize) {
+        if let Some(&(last_start, last_value, last_slope)) = self.pieces.last() {
+            if last_slope == slope && last_value + (start - last_start).times(slope) == value {
+                return;
+            }
+        }
+
+        self.pieces.push((start, value, slope));
+    }
+
+    /// The function plus `slope * t`.
+    fn tilted(&self, slope: isize) -> Self {
+        Self {
+            pieces: self
+                .pieces
+                .iter()
+                .map(|&(start, value, old)| (start, value + start.times(slope), old + slope))
+                .collect(),
+        }
+    }
+
+    /// The temperatures where `self` or `other` change slope, in order.
+    fn breakpoints(&self, other: &Self) -> Vec<Dyadic> {
+        let mut points = self
+            .pieces
+            .iter()
+            .chain(&other.pieces)
+            .map(|&(start, _, _)| start)
+            .collect::<Vec<_>>();
+        points.sort_unstable();
+        points.dedup();
+
+        points
+    }
+
+    /// The pointwise maximum of `self` and `other`, or their minimum if `pick` is [`Ordering::Less`].
+    fn pointwise(&self, other: &Self, pick: Ordering) -> Self {
+        let points = self.breakpoints(other);
+        let mut combined = Self { pieces: Vec::new() };
+
+        for (i, &start) in points.iter().enumerate() {
+            let end = points.get(i + 1).copied();
+            let lines =
+                [self, other].map(|trajectory| (trajectory.at(start), trajectory.slope_at(start)));
+
+            // whichever is picked just after the start, then the other if they cross before the end
+            let first = match lines[0].cmp(&lines[1]) {
+                ordering if ordering == pick.reverse() => 1,
+                _ => 0,
+            };
+            let (value, slope) = lines[first];
+            combined.push(start, value, slope);
+
+            let (other_value, other_slope) = lines[1 - first];
+            if slope != other_slope {
+                let crossing = start + (value - other_value).divided_by(other_slope - slope);
+                if crossing > start && end.map_or(true, |end| crossing < end) {
+                    combined.push(
+                        crossing,
+                        value + (crossing - start).times(slope),
+                        other_slope,
+                    );
+                }
+            }
+        }
+
+        combined
+    }
+
+    /// The function up to `temperature`, and constant from there on.
+    fn capped(&self, temperature: Dyadic) -> Self {
+        let mut capped = Self { pieces: Vec::new() };
+        for &(start, value, slope) in &self.pieces {
+            if start < temperature {
+                capped.push(start, value, slope);
+            }
+        }
+        capped.push(temperature, self.at(temperature), 0);
+
+        capped
+    }
+}
+
+/// The thermograph of a game: the values it settles at (its walls) when every move costs a tax of `t`,
+/// if Left moves first and if Right does, for every temperature `t` at least -1.
+///
+/// Once `t` reaches the game's temperature, moving isn't worth the tax for either player,
+/// and both walls meet at its mast, the mean value of the game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Thermograph {
+    left_wall: Trajectory,
+    right_wall: Trajectory,
+    temperature: Dyadic,
+}
+
+impl Thermograph {
+    /// How urgent moving in the game is. Switches `{a | b}` are hot, with a temperature of `(a - b) / 2`,
+    /// infinitesimals like `*` have a temperature of 0, and numbers are cold:
+    /// moving in `m / 2^k` (in lowest terms) is a loss of `1 / 2^k`, so its temperature is `-1 / 2^k`,
+    /// and the temperature of integers is -1.
+    pub fn temperature(&self) -> Dyadic {
+        self.temperature
+    }
+
+    /// The value the walls meet at, which is the mean value of the game:
+    /// the value of `n` copies of it is within a constant of `n` times its mean.
+    pub fn mast(&self) -> Dyadic {
+        self.left_wall.at(self.temperature)
+    }
+
+    /// The value of the game at temperature `t` if Left moves first.
+    pub fn left_wall(&self, t: Dyadic) -> Dyadic {
+        self.left_wall.at(t)
+    }
+
+    /// The value of the game at temperature `t` if Right moves first.
+    pub fn right_wall(&self, t: Dyadic) -> Dyadic {
+        self.right_wall.at(t)
+    }
+
+    /// The lowest temperature where the left scaffold is no further left than the right one.
+    fn crossing(left: &Trajectory, right: &Trajectory) -> Dyadic {
+        let points = left.breakpoints(right);
+
+        for (i, &start) in points.iter().enumerate() {
+            let gap = left.at(start) - right.at(start);
+            if gap <= Dyadic::integer(0) {
+                return start;
+            }
+
+            let closing = right.slope_at(start) - left.slope_at(start);
+            if closing > 0 {
+                let crossing = start + gap.divided_by(closing);
+                if points.get(i + 1).map_or(true, |&end| crossing < end) {
+                    return crossing;
+                }
+            }
+        }
+
+        unreachable!("the left scaffold ends up falling, and the right one rising")
+    }
+
+    fn of(game: &CanonicalGame, known: &mut HashMap<CanonicalGame, Thermograph>) -> Thermograph {
+        if let Some(thermograph) = known.get(game) {
+            return thermograph.clone();
+        }
+
+        let thermograph = if let Some(number) = game.number() {
+            Thermograph {
+                left_wall: Trajectory::constant(number),
+                right_wall: Trajectory::constant(number),
+                temperature: Dyadic::new(-1, number.exponent),
+            }
+        } else {
+            // Left moves to the option whose right wall, minus the tax, is best for them,
+            // and Right to the option whose left wall, plus the tax, is best for them
+            let scaffold = |options: &[CanonicalGame],
+                            known: &mut HashMap<CanonicalGame, Thermograph>,
+                            pick: Ordering| {
+                options
+                    .iter()
+                    .map(|option| {
+                        let thermograph = Self::of(option, known);
+                        match pick {
+                            Ordering::Greater => thermograph.right_wall.tilted(-1),
+                            _ => thermograph.left_wall.tilted(1),
+                        }
+                    })
+                    .reduce(|a, b| a.pointwise(&b, pick))
+                    .expect("games that aren't numbers have options for both players")
+            };
+            let left = scaffold(&game.left, known, Ordering::Greater);
+            let right = scaffold(&game.right, known, Ordering::Less);
+
+            let temperature = Self::crossing(&left, &right);
+            let (left_mast, right_mast) = (left.at(temperature), right.at(temperature));
+            if left_mast == right_mast {
+                Thermograph {
+                    left_wall: left.capped(temperature),
+                    right_wall: right.capped(temperature),
+                    temperature,
+                }
+            } else {
+                // the scaffolds had already crossed at -1
+                let mast = Trajectory::constant(left_mast.midpoint(right_mast));
+                Thermograph {
+                    left_wall: mast.clone(),
+                    right_wall: mast,
+                    temperature,
+                }
+            }
+        };
+
+        known.insert(game.clone(), thermograph.clone());
+
+        thermograph
+    }
+}
+
+/// The canonical value of `game`, found from the values of both players' options.
+///
+/// Positions are remembered, so that the values of positions reached by different orders of moves
+/// are only found once.
+pub fn canonical_value<G: PartizanGame + Eq + Hash>(game: &G) -> CanonicalGame {
+    value_of(game, &mut HashMap::new())
+}
+
+fn value_of<G: PartizanGame + Eq + Hash>(
+    game: &G,
+    known: &mut HashMap<G, CanonicalGame>,
+) -> CanonicalGame {
+    if let Some(value) = known.get(game) {
+        return value.clone();
+    }
+
+    let [left, right] = [PartizanPlayer::Left, PartizanPlayer::Right].map(|player| {
+        game.options(player)
+            .iter()
+            .map(|option| value_of(option, known))
+            .collect::<Vec<_>>()
+    });
+
+    let value = CanonicalGame::new(left, right);
+    known.insert(game.clone(), value.clone());
+
+    value
+}
+
+/// Who wins a game under normal play, depending on who moves first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutcomeClass {
+    /// Left wins, whoever moves first: the game is greater than 0.
+    Left,
+    /// Right wins, whoever moves first: the game is less than 0.
+    Right,
+    /// Whoever moves first (the next player) wins: the game is confused with 0.
+    Next,
+    /// Whoever moves second (the previous player) wins: the game is equal to 0.
+    Previous,
+}
+
+impl OutcomeClass {
+    /// The outcome class of a game, from whether Left wins it moving first, and whether Right does.
+    pub fn from_first_player_wins(left_wins_first: bool, right_wins_first: bool) -> Self {
+        match (left_wins_first, right_wins_first) {
+            (true, true) => Self::Next,
+            (true, false) => Self::Left,
+            (false, true) => Self::Right,
+            (false, false) => Self::Previous,
+        }
+    }
+}
+
+/// Written as the letter of the class: `L`, `R`, `N`, or `P`.
+impl fmt::Display for OutcomeClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let letter = match self {
+            Self::Left => "L",
+            Self::Right => "R",
+            Self::Next => "N",
+            Self::Previous => "P",
+        };
+
+        write!(f, "{letter}")
+    }
+}
+
+/// The outcome class of `game`, found by solving it once with each player moving first,
+/// remembering the positions both searches reach.
+///
+/// Unlike [`canonical_value`], this only finds who wins, so it can cut a search short
+/// as soon as it finds a winning move.
+pub fn outcome_class<G: PartizanGame + Eq + Hash>(game: &G) -> OutcomeClass {
+    fn wins_first<G: PartizanGame + Eq + Hash>(
+        game: &G,
+        player: PartizanPlayer,
+        known: &mut HashMap<(G, PartizanPlayer), bool>,
+    ) -> bool {
+        if let Some(&wins) = known.get(&(game.clone(), player)) {
+            return wins;
+        }
+
+        // a player wins moving first if they can move to a position the opponent loses moving first
+        let wins = game
+            .options(player)
+            .iter()
+            .any(|option| !wins_first(option, player.other(), known));
+        known.insert((game.clone(), player), wins);
+
+        wins
+    }
+
+    let mut known = HashMap::new();
+    OutcomeClass::from_first_player_wins(
+        wins_first(game, PartizanPlayer::Left, &mut known),
+        wins_first(game, PartizanPlayer::Right, &mut known),
+    )
+}
+
+/// The outcome class of an impartial game, where both players have the same moves:
+/// [`OutcomeClass::Next`] if the player to move wins, or [`OutcomeClass::Previous`] otherwise.
+pub fn impartial_outcome_class<T: Game<Player = ImpartialPlayer> + Normal + Eq + Hash>(
+    game: &T,
+) -> Result<OutcomeClass, GameSolveError<T>> {
+    let wins = solve_outcome(game, &mut HashMap::new(), None, &None)? == Outcome::Win;
+
+    Ok(OutcomeClass::from_first_player_wins(wins, wins))
+}
+
+/// A move in one component of a sum of games, along with how hot that component is.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HotMove<M> {
+    /// The index of the component the move is made in.
+    pub component: usize,
+    pub m: M,
+    /// The temperature of the component before the move.
+    pub temperature: Dyadic,
+    /// The mean value of the component after the move.
+    pub mean: Dyadic,
+}
+
+/// Every move of the player to move in each of `components`, which are played side by side,
+/// from the hottest component to the coldest (the "hotstrat" heuristic: move where it's most urgent).
+/// Moves in equally hot components are ordered by the mean value they leave, best for the player to move first.
+///
+/// The player to move has to be the same in every component.
+pub fn hot_moves<G: PartizanGame + Eq + Hash>(
+    components: &[G],
+) -> Result<Vec<HotMove<G::Move>>, G::MoveError> {
+    let mut known = HashMap::new();
+    let mut moves = Vec::new();
+
+    for (component, game) in components.iter().enumerate() {
+        let temperature = value_of(game, &mut known).temperature();
+
+        for m in game.possible_moves() {
+            let mut option = game.clone();
+            option.make_move(&m)?;
+
+            moves.push(HotMove {
+                component,
+                m,
+                temperature,
+                mean: value_of(&option, &mut known).mean(),
+            });
+        }
+    }
+
+    let player = components.first().map(Game::player);
+    moves.sort_by(|a, b| {
+        let by_mean = if player.is_some_and(TwoPlayer::is_maximizing) {
+            b.mean.cmp(&a.mean)
+        } else {
+            a.mean.cmp(&b.mean)
+        };
+
+        b.temperature.cmp(&a.temperature).then(by_mean)
+    });
+
+    Ok(moves)
+}
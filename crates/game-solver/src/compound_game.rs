@@ -0,0 +1,263 @@
+//! Compounds of impartial games besides the disjunctive sum of [`disjoint_game`](crate::disjoint_game),
+//! as described in chapter 14 of Conway's On Numbers and Games.
+//!
+//! - In a [`ConjunctiveCompound`], every move is made in every component,
+//!   so it ends as soon as any component does. Its outcome follows from [`remoteness`].
+//! - In a [`SelectiveCompound`], every move is made in any nonempty set of components,
+//!   so it only ends once every component has.
+
+use std::{collections::HashMap, hash::Hash};
+
+use itertools::Itertools;
+use thiserror::Error;
+
+use crate::{
+    game::{Game, GameStatus, Normal, NormalImpartial},
+    player::ImpartialPlayer,
+};
+
+#[derive(Debug, Error, Clone)]
+pub enum CompoundMoveError<E> {
+    #[error("Could not make the move in component {index}: {error}")]
+    Component { index: usize, error: E },
+    #[error("The move has {found} parts, but there are {expected} components")]
+    WrongLength { expected: usize, found: usize },
+    #[error("The move doesn't move in any component")]
+    NoComponentMoved,
+}
+
+/// Makes `moves[i]` in the `i`th component, if there is one,
+/// leaving every component as it was if any of them can't be moved.
+fn move_components<'a, G: Game + 'a>(
+    components: &mut Vec<G>,
+    moves: impl ExactSizeIterator<Item = Option<&'a G::Move>>,
+) -> Result<(), CompoundMoveError<G::MoveError>> {
+    if moves.len() != components.len() {
+        return Err(CompoundMoveError::WrongLength {
+            expected: components.len(),
+            found: moves.len(),
+        });
+    }
+
+    let mut moved = components.clone();
+    for (index, (component, m)) in moved.iter_mut().zip(moves).enumerate() {
+        if let Some(m) = m {
+            component
+                .make_move(m)
+                .map_err(|error| CompoundMoveError::Component { index, error })?;
+        }
+    }
+
+    *components = moved;
+
+    Ok(())
+}
+
+/// The conjunctive compound of impartial games under normal play:
+/// every move is made in every component, and whoever can't move in some component loses.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ConjunctiveCompound<G: Game + NormalImpartial> {
+    components: Vec<G>,
+    move_count: usize,
+}
+
+impl<G: Game + NormalImpartial> ConjunctiveCompound<G> {
+    pub fn new(components: Vec<G>) -> Self {
+        Self {
+            components,
+            move_count: 0,
+        }
+    }
+
+    pub fn components(&self) -> &[G] {
+        &self.components
+    }
+}
+
+impl<G: Game<Player = ImpartialPlayer> + NormalImpartial> Normal for ConjunctiveCompound<G> {}
+impl<G: Game<Player = ImpartialPlayer> + NormalImpartial> NormalImpartial
+    for ConjunctiveCompound<G>
+{
+}
+
+impl<G: Game<Player = ImpartialPlayer> + NormalImpartial> Game for ConjunctiveCompound<G> {
+    /// The move of each component, in order.
+    type Move = Vec<G::Move>;
+    type Iter<'a> = std::vec::IntoIter<Self::Move> where G: 'a;
+    type Player = ImpartialPlayer;
+
+    const IMPARTIAL: bool = true;
+    type MoveError = CompoundMoveError<G::MoveError>;
+
+    fn move_count(&self) -> usize {
+        self.move_count
+    }
+
+    /// The compound ends with its shortest component.
+    fn max_moves(&self) -> Option<usize> {
+        let remaining = self
+            .components
+            .iter()
+            .filter_map(|component| Some(component.max_moves()? - component.move_count()))
+            .min()?;
+
+        Some(self.move_count + remaining)
+    }
+
+    fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        move_components(&mut self.components, m.iter().map(Some))?;
+        self.move_count += 1;
+
+        Ok(())
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        if self.components.is_empty() {
+            return Vec::new().into_iter();
+        }
+
+        self.components
+            .iter()
+            .map(|component| component.possible_moves().collect::<Vec<_>>())
+            .multi_cartesian_product()
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn status(&self) -> GameStatus {
+        <Self as Normal>::status(self)
+    }
+
+    fn player(&self) -> Self::Player {
+        ImpartialPlayer::Next
+    }
+}
+
+/// The selective compound of impartial games under normal play:
+/// every move is made in any nonempty set of components, and whoever can't move in any of them loses.
+///
+/// The player to move wins exactly when some component is won by the player to move,
+/// as they can move to a position lost for the player to move in every component at once.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SelectiveCompound<G: Game + NormalImpartial> {
+    components: Vec<G>,
+    move_count: usize,
+}
+
+impl<G: Game + NormalImpartial> SelectiveCompound<G> {
+    pub fn new(components: Vec<G>) -> Self {
+        Self {
+            components,
+            move_count: 0,
+        }
+    }
+
+    pub fn components(&self) -> &[G] {
+        &self.components
+    }
+}
+
+impl<G: Game<Player = ImpartialPlayer> + NormalImpartial> Normal for SelectiveCompound<G> {}
+impl<G: Game<Player = ImpartialPlayer> + NormalImpartial> NormalImpartial for SelectiveCompound<G> {}
+
+impl<G: Game<Player = ImpartialPlayer> + NormalImpartial> Game for SelectiveCompound<G> {
+    /// The move of each component, in order, or None for the components that aren't moved.
+    type Move = Vec<Option<G::Move>>;
+    type Iter<'a> = std::vec::IntoIter<Self::Move> where G: 'a;
+    type Player = ImpartialPlayer;
+
+    const IMPARTIAL: bool = true;
+    type MoveError = CompoundMoveError<G::MoveError>;
+
+    fn move_count(&self) -> usize {
+        self.move_count
+    }
+
+    /// Every move advances at least one component.
+    fn max_moves(&self) -> Option<usize> {
+        self.components
+            .iter()
+            .map(|component| Some(component.max_moves()? - component.move_count()))
+            .sum::<Option<usize>>()
+            .map(|remaining| self.move_count + remaining)
+    }
+
+    fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        if m.iter().all(Option::is_none) {
+            return Err(CompoundMoveError::NoComponentMoved);
+        }
+
+        move_components(&mut self.components, m.iter().map(Option::as_ref))?;
+        self.move_count += 1;
+
+        Ok(())
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        if self.components.is_empty() {
+            return Vec::new().into_iter();
+        }
+
+        self.components
+            .iter()
+            .map(|component| {
+                std::iter::once(None)
+                    .chain(component.possible_moves().map(Some))
+                    .collect::<Vec<_>>()
+            })
+            .multi_cartesian_product()
+            .filter(|m| m.iter().any(Option::is_some))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn status(&self) -> GameStatus {
+        <Self as Normal>::status(self)
+    }
+
+    fn player(&self) -> Self::Player {
+        ImpartialPlayer::Next
+    }
+}
+
+/// The remoteness of an impartial game under normal play: how many moves it lasts
+/// when the winner wins as quickly as they can, and the loser loses as slowly as they can.
+///
+/// A position is lost for the player to move exactly when its remoteness is even,
+/// and the remoteness of a [`ConjunctiveCompound`] is the smallest remoteness of its components
+/// (Steinhaus' theorem), as the winner of the shortest component can end it first.
+pub fn remoteness<T: Game + Normal + Eq + Hash>(game: &T) -> Result<usize, T::MoveError> {
+    fn search<T: Game + Normal + Eq + Hash>(
+        game: &T,
+        known: &mut HashMap<T, usize>,
+    ) -> Result<usize, T::MoveError> {
+        if let Some(&remoteness) = known.get(game) {
+            return Ok(remoteness);
+        }
+
+        // the quickest move to a position lost for the opponent, and the slowest move overall
+        let mut quickest_win: Option<usize> = None;
+        let mut slowest: Option<usize> = None;
+        for m in game.possible_moves() {
+            let mut board = game.clone();
+            board.make_move(&m)?;
+
+            let remoteness = search(&board, known)?;
+            if remoteness % 2 == 0 {
+                quickest_win = Some(quickest_win.map_or(remoteness, |r| r.min(remoteness)));
+            }
+            slowest = Some(slowest.map_or(remoteness, |r| r.max(remoteness)));
+        }
+
+        let remoteness = match (quickest_win, slowest) {
+            (Some(win), _) => win + 1,
+            (None, Some(loss)) => loss + 1,
+            (None, None) => 0,
+        };
+        known.insert(game.clone(), remoteness);
+
+        Ok(remoteness)
+    }
+
+    search(game, &mut HashMap::new())
+}
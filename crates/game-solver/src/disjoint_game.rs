@@ -22,6 +22,17 @@ pub enum DisjointMove<L: Game, R: Game> {
     RightMove(R::Move)
 }
 
+// not derived, as that would require the games themselves to be comparable
+impl<L: Game, R: Game> PartialEq for DisjointMove<L, R> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::LeftMove(a), Self::LeftMove(b)) => a == b,
+            (Self::RightMove(a), Self::RightMove(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
 #[derive(Debug, Error, Clone)]
 pub enum DisjointMoveError<L: Game, R: Game> {
     #[error("Could not make the move on left: {0}")]
@@ -54,6 +65,8 @@ impl<
     > where L: 'a, R: 'a, L::Move: 'a, R::Move: 'a;
 
     type Player = ImpartialPlayer;
+
+    const IMPARTIAL: bool = true;
     type MoveError = DisjointMoveError<L, R>;
 
     fn move_count(&self) -> usize {
@@ -62,10 +75,10 @@ impl<
 
     fn max_moves(&self) -> Option<usize> {
         self.left.max_moves()
-            .map(
+            .and_then(
                 |l| self.right.max_moves()
                     .map(|r| l + r)
-            ).flatten()
+            )
     }
 
     fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
@@ -94,8 +107,8 @@ impl<
             )
     }
 
-    fn state(&self) -> crate::game::GameState<Self::Player> {
-        <Self as Normal>::state(&self)
+    fn status(&self) -> crate::game::GameStatus {
+        <Self as Normal>::status(self)
     }
 
     fn player(&self) -> Self::Player {
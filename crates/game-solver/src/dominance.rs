@@ -0,0 +1,149 @@
+//! Checks of [`Game::dominated_moves`], which the solver trusts to leave moves out of its search.
+//!
+//! [`Undominated`] wraps a game so that none of its moves are left out,
+//! and [`check_dominated_moves`] solves a position with and without them to compare the scores.
+//! Built with `--cfg game_solver_check_dominance`, debug builds do this for every position [`solve`](crate::solve) is given.
+
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use core::hash::Hash;
+
+use crate::game::{Game, GameState, GameStatus};
+#[cfg(feature = "std")]
+use crate::{ordering::MoveOrdering, player::TwoPlayer, solve_with_guess, GameSolveError};
+
+/// A game whose [dominated moves](Game::dominated_moves) are all searched,
+/// as it's otherwise the game it wraps.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use game_solver::{dominance::Undominated, solve};
+/// use games::tic_tac_toe::TicTacToe;
+///
+/// let game = TicTacToe::from_board("XO./.X./..O", None).unwrap();
+/// assert_eq!(
+///     solve(&game, &mut HashMap::new(), None, &None).unwrap(),
+///     solve(&Undominated(game), &mut HashMap::new(), None, &None).unwrap()
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Undominated<T>(pub T);
+
+impl<T: Game> Game for Undominated<T> {
+    type Move = T::Move;
+    type Iter<'a> = T::Iter<'a> where T: 'a;
+    type MoveError = T::MoveError;
+    type Player = T::Player;
+
+    const IMPARTIAL: bool = T::IMPARTIAL;
+
+    fn move_count(&self) -> usize {
+        self.0.move_count()
+    }
+
+    fn max_moves(&self) -> Option<usize> {
+        self.0.max_moves()
+    }
+
+    fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        self.0.make_move(m)
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        self.0.possible_moves()
+    }
+
+    fn move_count_hint(&self) -> (usize, Option<usize>) {
+        self.0.move_count_hint()
+    }
+
+    fn has_moves(&self) -> bool {
+        self.0.has_moves()
+    }
+
+    fn noisy_moves(&self) -> alloc::vec::Vec<Self::Move> {
+        self.0.noisy_moves()
+    }
+
+    fn find_immediately_resolvable_game(&self) -> Result<Option<Self>, Self::MoveError> {
+        Ok(self.0.find_immediately_resolvable_game()?.map(Self))
+    }
+
+    fn cheap_win_check(&self) -> bool {
+        self.0.cheap_win_check()
+    }
+
+    fn status(&self) -> GameStatus {
+        self.0.status()
+    }
+
+    fn draw_score(&self) -> isize {
+        self.0.draw_score()
+    }
+
+    fn state(&self) -> GameState<Self::Player> {
+        self.0.state()
+    }
+
+    fn player(&self) -> Self::Player {
+        self.0.player()
+    }
+}
+
+/// Solves `game` leaving out its [dominated moves](Game::dominated_moves), then searching them,
+/// each with a table of its own, and returns both scores.
+/// They're the same if leaving the moves out was sound in every position searched.
+///
+/// ```
+/// use game_solver::dominance::check_dominated_moves;
+/// use games::tic_tac_toe::TicTacToe;
+///
+/// // on a 4x4 board, most squares soon stop being on a line either player can still fill
+/// let game = TicTacToe::from_board("XO../..X./..O./O..X", None).unwrap();
+/// let (left_out, searched) = check_dominated_moves(&game).unwrap();
+/// assert_eq!(left_out, searched);
+/// ```
+#[cfg(feature = "std")]
+pub fn check_dominated_moves<T, P>(game: &T) -> Result<(isize, isize), GameSolveError<T>>
+where
+    P: TwoPlayer + 'static,
+    T: Game<Player = P> + Eq + Hash,
+{
+    let left_out = solve_with_guess(
+        game,
+        &mut HashMap::new(),
+        None,
+        &None,
+        None,
+        &MoveOrdering::GameOrder,
+    )?;
+    let searched = solve_with_guess(
+        &Undominated(game.clone()),
+        &mut HashMap::new(),
+        None,
+        &None,
+        None,
+        &MoveOrdering::GameOrder,
+    )
+    .map_err(|err| match err {
+        GameSolveError::MoveError(err) => GameSolveError::MoveError(err),
+        GameSolveError::CancellationTokenError => GameSolveError::CancellationTokenError,
+        GameSolveError::MaxMovesExceeded {
+            move_count,
+            max_moves,
+        } => GameSolveError::MaxMovesExceeded {
+            move_count,
+            max_moves,
+        },
+        GameSolveError::InvalidWindow { alpha, beta } => {
+            GameSolveError::InvalidWindow { alpha, beta }
+        }
+        GameSolveError::ScoreOutOfBounds { score, bound } => {
+            GameSolveError::ScoreOutOfBounds { score, bound }
+        }
+    })?;
+
+    Ok((left_out, searched))
+}
@@ -0,0 +1,139 @@
+//! Exports the game tree to [Graphviz DOT](https://graphviz.org/doc/info/lang.html),
+//! for looking at what the solver searches through.
+
+use std::{
+    fmt::Display,
+    io::{self, Write},
+};
+
+use thiserror::Error;
+
+use crate::{
+    game::{upper_bound, Game},
+    player::TwoPlayer,
+};
+
+#[derive(Error, Debug)]
+pub enum ExportTreeError<T: Game> {
+    #[error("could not make a move")]
+    MoveError(T::MoveError),
+    #[error("could not write the tree: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Escapes a string for use inside of a quoted DOT label,
+/// left-aligning every line.
+fn escape_label(label: &str) -> String {
+    label
+        .trim_end_matches('\n')
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\l")
+}
+
+/// Writes the subtree of `game` in post-order, so that nothing but
+/// the current path has to be kept in memory.
+///
+/// Returns the id of the node, and its score if it could be decided within `depth` moves.
+fn export_node<T: Game<Player = impl TwoPlayer + 'static> + Display>(
+    game: &T,
+    depth: usize,
+    next_id: &mut usize,
+    writer: &mut impl Write,
+) -> Result<(usize, Option<isize>), ExportTreeError<T>>
+where
+    T::Move: Display,
+{
+    let id = *next_id;
+    *next_id += 1;
+
+    let score = match game.status().score(game) {
+        Some(score) => Some(score),
+        None if depth == 0 => None,
+        None => {
+            // a proven win is always the best score, as a faster win would have been proven too
+            let mut best_win = None;
+            let mut best_known = None;
+            let mut all_known = true;
+
+            for m in game.possible_moves() {
+                let mut board = game.clone();
+                board.make_move(&m).map_err(ExportTreeError::MoveError)?;
+
+                let (child, child_score) = export_node(&board, depth - 1, next_id, writer)?;
+                writeln!(
+                    writer,
+                    "  n{id} -> n{child} [label=\"{}\"];",
+                    escape_label(&m.to_string())
+                )?;
+
+                match child_score.map(|score| -score) {
+                    Some(score) if score > 0 => best_win = best_win.max(Some(score)),
+                    Some(score) => best_known = best_known.max(Some(score)),
+                    None => all_known = false,
+                }
+            }
+
+            best_win.or(if all_known { best_known } else { None })
+        }
+    };
+
+    let (evaluation, color) = match score {
+        None => ("unknown".to_string(), "white"),
+        Some(0) => ("score 0, tie".to_string(), "white"),
+        Some(score) => {
+            // invert the scoring, to get the amount of moves until the game ends
+            let moves = upper_bound(game) + 1 - score.abs() - game.move_count() as isize;
+            let outcome = match (score > 0, moves) {
+                (true, 0) => "won".to_string(),
+                (false, 0) => "lost".to_string(),
+                (true, moves) => format!("win in {moves}"),
+                (false, moves) => format!("lose in {moves}"),
+            };
+
+            (
+                format!("score {score:+}, {outcome}"),
+                if score > 0 { "palegreen" } else { "lightpink" },
+            )
+        }
+    };
+
+    writeln!(
+        writer,
+        "  n{id} [label=\"{}\\l{}\\l\", fillcolor={color}];",
+        escape_label(&game.to_string()),
+        escape_label(&evaluation)
+    )?;
+
+    Ok((id, score))
+}
+
+/// Writes the game tree of `game`, `depth` moves deep, to `writer` as a DOT graph.
+///
+/// Every node is labeled with its position and its evaluation from the perspective of the player to move,
+/// which is only known if the game could be decided within the exported tree.
+/// Nodes that are won by the player to move are colored green, and nodes that are lost are colored red.
+/// Edges are labeled with the move that was made.
+///
+/// Transpositions aren't merged, so the tree grows exponentially with `depth`.
+/// It is streamed to `writer` as it's explored, so it never has to be held in memory.
+pub fn export_tree<T: Game<Player = impl TwoPlayer + 'static> + Display>(
+    game: &T,
+    depth: usize,
+    mut writer: impl Write,
+) -> Result<(), ExportTreeError<T>>
+where
+    T::Move: Display,
+{
+    writeln!(writer, "digraph tree {{")?;
+    writeln!(
+        writer,
+        "  node [shape=box, style=filled, fontname=\"monospace\"];"
+    )?;
+
+    export_node(game, depth, &mut 0, &mut writer)?;
+
+    writeln!(writer, "}}")?;
+
+    Ok(())
+}
@@ -0,0 +1,216 @@
+//! Sweeps of the end of a game: every position of an [`EnumerableGame`] that ends within a few moves,
+//! however it's played, solved by each of a list of [checks](crate::verify::Check), and compared to
+//! the scores [retrograde analysis](crate::tablebase) finds from the positions after each move.
+//!
+//! Retrograde analysis only asks the game for its moves and [`Game::state`], so a check that disagrees with it
+//! points to a part of the game the solver trusts, such as [`Game::find_immediately_resolvable_game`],
+//! or to the solver itself.
+
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{
+    game::{upper_bound, Game, GameState},
+    player::TwoPlayer,
+    solve,
+    tablebase::{EnumerableGame, Tablebase},
+    verify::{first_disagreement, Check, Disagreement, Found},
+    GameSolveError,
+};
+
+/// What [`endgame_sweep`] found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndgameSweep<T: Game> {
+    /// How many positions end within the plies swept, including those that are already over.
+    pub positions: usize,
+    /// How many of them the player to move wins.
+    pub wins: usize,
+    /// How many of them the player to move loses.
+    pub losses: usize,
+    /// How many of them are tied.
+    pub ties: usize,
+    /// The most moves the player to move can be held to before winning, and the first position (by index) where they are.
+    pub longest_win: Option<(usize, T)>,
+    /// Every position a check disagreed about, with the first score it got wrong.
+    pub disagreements: Vec<(T, Disagreement<T::Move>)>,
+}
+
+/// The [`Check`] of [`Game::find_immediately_resolvable_game`]: a position it finds a win for the player to move in
+/// is scored as a win on the next move, as the solver scores it, and any other position is [solved](solve).
+///
+/// A win it misses isn't caught, as the solver still finds it by searching the moves.
+pub fn immediate_win<T, P>() -> Check<T>
+where
+    P: TwoPlayer + 'static,
+    T: Game<Player = P> + Eq + Hash,
+{
+    Check {
+        name: "find_immediately_resolvable_game",
+        run: |game| {
+            if game.state() == GameState::Playable {
+                let resolvable = game
+                    .find_immediately_resolvable_game()
+                    .map_err(GameSolveError::MoveError)?;
+
+                if let Some(board) = resolvable {
+                    if board.state() == GameState::Win(game.player().turn()) {
+                        return Ok(Found::Score(upper_bound(game) - game.move_count() as isize));
+                    }
+                }
+            }
+
+            Ok(Found::Score(solve(game, &mut HashMap::new(), None, &None)?))
+        },
+    }
+}
+
+/// How many moves the game lasts at most from each position of `positions`, however it's played,
+/// or None for positions with a move that leaves the set, or that aren't in it.
+fn plies_to_end<T: EnumerableGame>(positions: &T) -> Result<Vec<Option<usize>>, T::MoveError> {
+    let mut plies = vec![None; positions.position_count()];
+
+    // like the tablebase, every pass finds the positions whose moves all lead to positions found before
+    loop {
+        let mut changed = false;
+
+        for index in 0..plies.len() {
+            if plies[index].is_some() {
+                continue;
+            }
+
+            let Some(position) = positions.unindex(index) else {
+                continue;
+            };
+
+            let found = if position.state() == GameState::Playable {
+                let mut longest = Some(0);
+
+                for m in position.possible_moves() {
+                    let mut board = position.clone();
+                    board.make_move(&m)?;
+
+                    let child = positions.index(&board).and_then(|child| plies[child]);
+                    longest = longest
+                        .zip(child)
+                        .map(|(longest, child)| longest.max(child + 1));
+                    if longest.is_none() {
+                        break;
+                    }
+                }
+
+                longest
+            } else {
+                Some(0)
+            };
+
+            if found.is_some() {
+                plies[index] = found;
+                changed = true;
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    Ok(plies)
+}
+
+/// Solves every position of `positions` that ends within `max_plies` moves, however it's played,
+/// with each of `checks`, and compares their scores to those of retrograde analysis.
+///
+/// The scores also give how many of the positions the player to move wins, loses, and ties,
+/// and the longest win they can be held to.
+///
+/// ```
+/// use game_solver::{
+///     endgame::{endgame_sweep, immediate_win},
+///     verify::checks,
+/// };
+/// use games::naive_nim::Nim;
+///
+/// // every position with at most 2 objects in each of 3 heaps, which ends within 2 moves
+/// let mut checks = checks();
+/// checks.push(immediate_win());
+/// let sweep = endgame_sweep(&Nim::new(vec![2, 2, 2]), 2, &checks).unwrap();
+///
+/// // 0 or 1 objects left, or 2 in a single heap or heaps of 1
+/// assert_eq!(sweep.positions, 1 + 3 + 3 + 3);
+/// // only the empty heaps and the two heaps of 1 are lost
+/// assert_eq!((sweep.wins, sweep.losses, sweep.ties), (6, 4, 0));
+/// assert_eq!(sweep.longest_win.unwrap().0, 1);
+/// assert!(sweep.disagreements.is_empty());
+/// ```
+pub fn endgame_sweep<T, P>(
+    positions: &T,
+    max_plies: usize,
+    checks: &[Check<T>],
+) -> Result<EndgameSweep<T>, GameSolveError<T>>
+where
+    P: TwoPlayer + 'static,
+    T: EnumerableGame<Player = P> + Eq + Hash,
+{
+    let tablebase = Tablebase::new(positions.clone()).map_err(GameSolveError::MoveError)?;
+    let plies = plies_to_end(positions).map_err(GameSolveError::MoveError)?;
+
+    let mut sweep = EndgameSweep {
+        positions: 0,
+        wins: 0,
+        losses: 0,
+        ties: 0,
+        longest_win: None,
+        disagreements: Vec::new(),
+    };
+
+    let probe = |position: &T| {
+        tablebase
+            .probe(position)
+            .expect("every position that ends within a known amount of moves is in the tablebase")
+    };
+
+    for (index, &ply) in plies.iter().enumerate() {
+        if ply.map_or(true, |ply| ply > max_plies) {
+            continue;
+        }
+        let position = positions
+            .unindex(index)
+            .expect("only positions in the set have plies");
+
+        // a game that's over may still have moves, which aren't scored
+        let mut scores = Vec::new();
+        if position.state() == GameState::Playable {
+            for m in position.possible_moves() {
+                let mut board = position.clone();
+                board.make_move(&m).map_err(GameSolveError::MoveError)?;
+                scores.push((m, -probe(&board)));
+            }
+        }
+        let score = probe(&position);
+
+        sweep.positions += 1;
+        match score {
+            0 => sweep.ties += 1,
+            score if score < 0 => sweep.losses += 1,
+            score => {
+                sweep.wins += 1;
+
+                // see `win_score` for how scores are computed once the game is over
+                let final_move_count = (upper_bound(&position) - score + 1) as usize;
+                let moves = final_move_count - position.move_count();
+                if sweep
+                    .longest_win
+                    .as_ref()
+                    .map_or(true, |(longest, _)| moves > *longest)
+                {
+                    sweep.longest_win = Some((moves, position.clone()));
+                }
+            }
+        }
+
+        if let Some(disagreement) = first_disagreement(&position, checks, &(score, scores))? {
+            sweep.disagreements.push((position, disagreement));
+        }
+    }
+
+    Ok(sweep)
+}
@@ -1,8 +1,9 @@
 //! Game trait and related types.
 
-use std::{cmp::Ordering, error::Error};
+use alloc::vec::Vec;
+use core::{cmp::Ordering, error::Error};
 
-use crate::player::Player;
+use crate::player::{PartizanPlayer, Player};
 
 /// Represents a move outcome
 #[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
@@ -16,19 +17,77 @@ pub enum GameState<P: Player> {
     Win(P),
 }
 
+/// Whether a game is over, and if it is, what it's worth, which the solver reads at every position it reaches.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+pub enum GameStatus {
+    /// The game goes on: the player to move has a possible move.
+    InProgress,
+    /// The game is over, and the player to move scored this: above the game's [draw score](Game::draw_score)
+    /// if they won, below it if they lost, and the draw score itself for a tie.
+    ///
+    /// See [`Game::status`] for the scores a game can give.
+    Terminal(isize),
+}
+
+impl GameStatus {
+    /// The game ended in a tie, for games whose [draw score](Game::draw_score) is 0, as it is by default.
+    /// Others can use [`tied`](Self::tied).
+    pub const TIE: Self = Self::Terminal(0);
+
+    /// `game` ended in a tie, scoring its [draw score](Game::draw_score).
+    pub fn tied<T: Game>(game: &T) -> Self {
+        Self::Terminal(game.draw_score())
+    }
+
+    /// The player to move won `game`, scoring [`win_score`] above its [draw score](Game::draw_score).
+    pub fn won<T: Game>(game: &T) -> Self {
+        Self::Terminal(game.draw_score() + win_score(game))
+    }
+
+    /// The player to move lost `game`, scoring [`win_score`] below its [draw score](Game::draw_score).
+    pub fn lost<T: Game>(game: &T) -> Self {
+        Self::Terminal(game.draw_score() - win_score(game))
+    }
+
+    /// `winner` won `game`, which is [`won`](Self::won) if they're the player to move,
+    /// and [`lost`](Self::lost) otherwise.
+    pub fn won_by<T: Game>(game: &T, winner: T::Player) -> Self {
+        if winner == game.player() {
+            Self::won(game)
+        } else {
+            Self::lost(game)
+        }
+    }
+
+    /// Returns whether the game is over.
+    pub fn is_over(self) -> bool {
+        matches!(self, Self::Terminal(_))
+    }
+
+    /// The score of the player to move if `game` is over, measured from its [draw score](Game::draw_score),
+    /// as the solver scores it: positive if they won, negative if they lost, and 0 for a tie.
+    pub fn score<T: Game>(self, game: &T) -> Option<isize> {
+        match self {
+            Self::InProgress => None,
+            Self::Terminal(score) => Some(score - game.draw_score()),
+        }
+    }
+}
+
 /// Marks a game as being 'normal' (a game has the 'normal play' convention).
-/// 
+///
 /// Rather, this means that the game is won by whoever plays last.
 /// Under this convention, no ties are possible: there has to exist a strategy
 /// for players to be able to force a win.
 ///
 /// Learn more: <https://en.wikipedia.org/wiki/Normal_play_convention>
 pub trait Normal: Game {
-    fn state(&self) -> GameState<Self::Player> {
-        if self.possible_moves().next().is_none() {
-            GameState::Win(self.player().previous())
+    /// The status of a normal play game: whoever can't move has lost.
+    fn status(&self) -> GameStatus {
+        if !self.has_moves() {
+            GameStatus::lost(self)
         } else {
-            GameState::Playable
+            GameStatus::InProgress
         }
     }
 }
@@ -37,11 +96,11 @@ pub trait Normal: Game {
 /// the disjunctive sum of two games is equal to another normal-play game.
 pub trait NormalImpartial: Normal {
     /// Splits a game into multiple separate games.
-    /// 
+    ///
     /// This function doesn't have to be necessarily optimal, but
     /// it makes normal impartial game analysis much quicker,
     /// using the technique described in [Nimbers Are Inevitable](https://arxiv.org/abs/1011.5841).
-    /// 
+    ///
     /// Returns `Option::None`` if the game currently can not be split.
     fn split(&self) -> Option<Vec<Self>> {
         None
@@ -49,22 +108,31 @@ pub trait NormalImpartial: Normal {
 }
 
 /// Marks a game as being 'misere' (a game has the 'misere play' convention).
-/// 
+///
 /// Rather, this means that the game is lost by whoever plays last.
 /// Under this convention, no ties are possible: there has to exist a strategy
 /// for players to be able to force a win.
 ///
 /// Learn more: <https://en.wikipedia.org/wiki/Mis%C3%A8re#Mis%C3%A8re_game>
 pub trait Misere: Game {
-    fn state<T>(&self) -> GameState<Self::Player> {
-        if self.possible_moves().next().is_none() {
-            GameState::Win(self.player())
+    /// The status of a misere play game: whoever can't move has won.
+    fn status(&self) -> GameStatus {
+        if !self.has_moves() {
+            GameStatus::won(self)
         } else {
-            GameState::Playable
+            GameStatus::InProgress
         }
     }
 }
 
+/// A partizan game where either player can be asked for their options, whoever's turn it is,
+/// as combinatorial game theory looks at both players' options of every position
+/// (see [`canonical_value`](crate::cgt::canonical_value)).
+pub trait PartizanGame: Game<Player = PartizanPlayer> {
+    /// The positions `player` can move to.
+    fn options(&self, player: PartizanPlayer) -> Vec<Self>;
+}
+
 /// Represents a combinatorial game.
 ///
 /// A game has three distinct variants per game:
@@ -74,7 +142,8 @@ pub trait Misere: Game {
 /// - Game player count: >0
 pub trait Game: Clone {
     /// The type of move this game uses.
-    type Move: Clone;
+    /// Moves are compared to leave out the [dominated ones](Self::dominated_moves).
+    type Move: Clone + PartialEq;
 
     /// The iterator type for possible moves.
     type Iter<'a>: Iterator<Item = Self::Move> + 'a
@@ -85,10 +154,21 @@ pub trait Game: Clone {
 
     type Player: Player;
 
+    /// Whether the game is impartial under normal play: from every position, both players have the same moves,
+    /// and whoever can't move loses (see [`NormalImpartial`]). By default, it isn't.
+    ///
+    /// Such a game is equivalent to a Nim heap, so [`solve_outcome`](crate::solve_outcome) finds who wins it
+    /// from its [Grundy value](crate::nimber::grundy_value), rather than searching for the fastest win.
+    const IMPARTIAL: bool = false;
+
     /// Returns the amount of moves that have been played
     fn move_count(&self) -> usize;
 
     /// Get the max number of moves in a game, if any.
+    ///
+    /// Scores are measured from this bound, so it can't be less than the move count
+    /// of any position the game reaches: solving such a position fails
+    /// with [`GameSolveError::MaxMovesExceeded`](crate::GameSolveError::MaxMovesExceeded).
     fn max_moves(&self) -> Option<usize>;
 
     /// Makes a move.
@@ -101,6 +181,52 @@ pub trait Game: Clone {
     /// Since "better" moves would be found first, this permits more alpha/beta cutoffs.
     fn possible_moves(&self) -> Self::Iter<'_>;
 
+    /// Returns bounds on the number of [possible moves](Self::possible_moves),
+    /// like [`Iterator::size_hint`]: at least the first, and at most the second, if any.
+    ///
+    /// The solver uses it to size the vectors it collects moves into, and in [`has_moves`](Self::has_moves),
+    /// so it must be correct, but it needn't be exact. By default, it doesn't know anything.
+    fn move_count_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+
+    /// Returns whether there is any possible move, which is how [`Normal`] and [`Misere`] games end.
+    ///
+    /// By default, this asks [`move_count_hint`](Self::move_count_hint) first,
+    /// and only creates the moves if it can't tell.
+    fn has_moves(&self) -> bool {
+        match self.move_count_hint() {
+            (0, Some(0)) => false,
+            (0, _) => self.possible_moves().next().is_some(),
+            _ => true,
+        }
+    }
+
+    /// Returns the possible moves that change the position too much for a heuristic to judge it before they're played,
+    /// such as captures. By default, there are none.
+    ///
+    /// Heuristic searches keep searching these moves past their depth
+    /// (see [`solve_heuristic`](crate::solve_heuristic)), so that they don't evaluate a position
+    /// in the middle of a tactical sequence.
+    fn noisy_moves(&self) -> Vec<Self::Move> {
+        Vec::new()
+    }
+
+    /// Returns possible moves that are never better than another possible move, which the solver doesn't search.
+    /// By default, there are none.
+    ///
+    /// Leaving them out only keeps the scores right if it's sound: for every dominated move,
+    /// some move that isn't dominated has to score at least as well, counting how many moves it takes to win,
+    /// however the game goes on. So at least one move must be left, and two moves can't be left out
+    /// for each other. A move that's wrongly left out changes the score of its position
+    /// (see [`Undominated`](crate::dominance::Undominated) to check them).
+    ///
+    /// [`move_scores`](crate::move_scores) still scores every move of the position it's given,
+    /// dominated or not, but searches past them without their dominated moves.
+    fn dominated_moves(&self) -> Vec<Self::Move> {
+        Vec::new()
+    }
+
     /// Returns a reachable game in one move.
     ///
     /// Rather, this function asks if there exists some game in the possible games set
@@ -117,19 +243,26 @@ pub trait Game: Clone {
     ///
     /// This function's default implementation is quite slow,
     /// and it's encouraged to use a custom implementation.
+    ///
+    /// It's only a hint to speed up the search: the solver only asks it if [`cheap_win_check`](Self::cheap_win_check) says so,
+    /// and only trusts it when it returns a win for the player to move scored like [`GameStatus::won`],
+    /// as a tie, a loss, or a win the game scores lower (see [`status`](Self::status)) right away
+    /// might not be the best they can do.
+    /// A winning position it returns is scored as it is, so the solver doesn't make its move again.
+    /// [`status`](Self::status) still decides whether any position is over.
     fn find_immediately_resolvable_game(&self) -> Result<Option<Self>, Self::MoveError> {
         let mut best_non_winning_game: Option<Self> = None;
 
         for m in &mut self.possible_moves() {
             let mut new_self = self.clone();
             new_self.make_move(&m)?;
-            match new_self.state() {
-                GameState::Playable => continue,
-                GameState::Tie => best_non_winning_game = Some(new_self),
-                GameState::Win(winning_player) => {
-                    if winning_player == self.player().turn() {
-                        return Ok(Some(new_self));
-                    } else if best_non_winning_game.is_none() {
+            // the score is of the player to move next, so the move won if it's negative
+            match new_self.status().score(&new_self) {
+                None => continue,
+                Some(score) if score < 0 => return Ok(Some(new_self)),
+                Some(0) => best_non_winning_game = Some(new_self),
+                Some(_) => {
+                    if best_non_winning_game.is_none() {
                         best_non_winning_game = Some(new_self)
                     }
                 }
@@ -139,41 +272,97 @@ pub trait Game: Clone {
         Ok(best_non_winning_game)
     }
 
-    /// Returns the current state of the game.
-    /// Used for verifying initialization and is commonly called.
+    /// Returns whether [`find_immediately_resolvable_game`](Self::find_immediately_resolvable_game)
+    /// is cheap enough for the solver to ask it at every position, before searching any move.
+    ///
+    /// By default it isn't, as it makes every move, and the solver makes them again to search them:
+    /// without it, a winning move is still found as soon as it's searched.
+    /// Return true if your implementation can rule out most positions without making their moves.
+    fn cheap_win_check(&self) -> bool {
+        false
+    }
+
+    /// Returns whether the game is over, and if it is, the score of the player to move.
     ///
-    /// the following implementation can be used:
+    /// The solver checks it at every position before anything else, so it alone decides
+    /// whether the game is over and what it's worth. A position in progress must have a possible move.
+    ///
+    /// Most games score a finished game with [`GameStatus::won`], [`GameStatus::lost`], or [`GameStatus::TIE`],
+    /// which prefer the fastest win and the slowest loss. Games that score their endings some other way,
+    /// such as by the pieces each player has, can give any score, as long as it's above the [draw score](Self::draw_score)
+    /// for a win and below it for a loss, and it's no further from the draw score than [`win_score`],
+    /// as the solver stops searching a position once a move wins right away:
+    /// raise [`max_moves`](Self::max_moves) to leave room for the scores.
+    /// Solving a game that scores past that fails with [`GameSolveError::ScoreOutOfBounds`](crate::GameSolveError::ScoreOutOfBounds).
+    ///
+    /// Normal and misere play games can use those conventions' implementations:
     ///
     /// ```ignore
-    /// fn state(&self) -> GameState<Self::Player> {
-    ///     <Self as Normal>::state(&self) // or Misere if misere.
+    /// fn status(&self) -> GameStatus {
+    ///     <Self as Normal>::status(self) // or Misere if misere.
     /// }
     /// ```
-    fn state(&self) -> GameState<Self::Player>;
+    fn status(&self) -> GameStatus;
+
+    /// Returns the score of a tied game in [`status`](Self::status), which the solver measures every score from,
+    /// so that it scores ties as 0. By default, it's 0.
+    ///
+    /// Games scored by a count that doesn't straddle 0, such as the boxes the player to move captured,
+    /// give the count a tie ends with here, so the solver scores the margin from it.
+    /// That count has to be the same for both players: a score `s` for one player is `2 * draw_score - s`
+    /// for the other, as every score is negated for the other player.
+    fn draw_score(&self) -> isize {
+        0
+    }
+
+    /// Returns the current state of the game, as read from [`status`](Self::status):
+    /// a score above the [draw score](Self::draw_score) is a win for the player to move,
+    /// and one below it for the player who moved last.
+    ///
+    /// Games of more than two players, whose winner might not have moved last, have to say who won here.
+    fn state(&self) -> GameState<Self::Player> {
+        match self.status().score(self) {
+            None => GameState::Playable,
+            Some(0) => GameState::Tie,
+            Some(score) if score > 0 => GameState::Win(self.player()),
+            Some(_) => GameState::Win(self.player().previous()),
+        }
+    }
+
+    /// Returns the player who won, or None if the game is tied or still playable.
+    ///
+    /// This is read from [`state`](Self::state), which alone decides who won.
+    fn winner(&self) -> Option<Self::Player> {
+        match self.state() {
+            GameState::Win(player) => Some(player),
+            GameState::Playable | GameState::Tie => None,
+        }
+    }
+
+    /// Returns whether the game ended in a tie, as read from [`state`](Self::state).
+    fn is_tie(&self) -> bool {
+        self.state() == GameState::Tie
+    }
 
     /// Returns the player whose turn it is.
     /// The implementation of this should be
     /// similar to either
     ///
     /// ```ignore
-    /// use game_solver::game::ZeroSumPlayer;
+    /// use game_solver::player::PartizanPlayer;
     ///
     /// fn player(&self) -> Self::Player {
-    ///     if game.move_count % 2 == 0 {
-    ///        ZeroSumPlayer::One
-    ///     } else {
-    ///         ZeroSumPlayer::Two
-    ///     }
+    ///     PartizanPlayer::from_move_count(self.move_count)
     /// }
     /// ```
     ///
     /// or
     ///
     /// ```ignore
-    /// use game_solver::game::NPlayer;
+    /// use game_solver::player::NPlayerPartizanConst;
     ///
     /// fn player(&self) -> Self::Player {
-    ///     NPlayer(game.move_count % game.num_players)
+    ///     NPlayerPartizanConst::new(self.move_count % N)
     /// }
     /// ```
     ///
@@ -184,6 +373,35 @@ pub trait Game: Clone {
     fn player(&self) -> Self::Player;
 }
 
+/// Collects the possible moves of `game`, with room for as many as it hints it has.
+pub(crate) fn collect_moves<T: Game>(game: &T) -> Vec<T::Move> {
+    let mut moves = Vec::with_capacity(game.move_count_hint().0);
+    moves.extend(game.possible_moves());
+    moves
+}
+
+/// How many moves a [`MoveBuf`] holds before it moves them to the heap.
+#[cfg(feature = "smallvec")]
+pub(crate) const INLINE_MOVES: usize = 16;
+
+/// A buffer for the moves of a position, which the solver collects at every node it sorts the moves of.
+///
+/// With the `smallvec` feature, it holds up to `N` moves without allocating.
+#[cfg(feature = "smallvec")]
+pub(crate) type MoveBuf<M, const N: usize = INLINE_MOVES> = smallvec::SmallVec<[M; N]>;
+#[cfg(not(feature = "smallvec"))]
+pub(crate) type MoveBuf<M> = Vec<M>;
+
+/// Collects the possible moves of `game` into a [`MoveBuf`].
+///
+/// If it hints at more moves than fit inline, they go straight to the heap,
+/// so games with many moves don't pay to move them there.
+pub(crate) fn buffer_moves<T: Game>(game: &T) -> MoveBuf<T::Move> {
+    let mut moves = MoveBuf::with_capacity(game.move_count_hint().0);
+    moves.extend(game.possible_moves());
+    moves
+}
+
 /// Utility function to get the upper score bound of a game.
 ///
 /// Essentially, score computation generally gives some max (usually max moves),
@@ -191,10 +409,30 @@ pub trait Game: Clone {
 /// trying to encourage winning in the shortest amount of time - God's algorithm.
 ///
 /// Note: Despite this returning isize, this function will always be positive.
+/// It is at most [`MAX_UPPER_BOUND`], including for games without a max number of moves.
 pub fn upper_bound<T: Game>(game: &T) -> isize {
-    game.max_moves().map_or(isize::MAX, |m| m as isize)
+    game.max_moves()
+        .and_then(|m| isize::try_from(m).ok())
+        .map_or(MAX_UPPER_BOUND, |m| m.min(MAX_UPPER_BOUND))
 }
 
+/// The score of winning `game` right now, which is higher the fewer moves it took:
+/// one more than the moves left until its [upper bound](upper_bound).
+///
+/// The one is added so that a win that uses up every move isn't scored as a tie.
+/// Take Nim with two heaps of one: the second player always wins, once both moves are made,
+/// but with an upper bound of 2, 2 - 2 = 0, which is reserved for ties.
+pub fn win_score<T: Game>(game: &T) -> isize {
+    let move_count = isize::try_from(game.move_count()).unwrap_or(isize::MAX);
+
+    // a game past its bound can't be solved, which the solver reports
+    upper_bound(game).saturating_sub(move_count).max(0) + 1
+}
+
+/// The largest [`upper_bound`] of a game, which leaves room for the winning score
+/// one above it, and for negating every score.
+pub const MAX_UPPER_BOUND: isize = isize::MAX - 1;
+
 /// Represents an outcome of a game derived by a score and a valid instance of a game.
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum GameScoreOutcome {
@@ -0,0 +1,80 @@
+//! Hashing positions as moves are made, instead of all at once.
+//!
+//! Every transposition table probe hashes the position it looks up,
+//! which for a large board can take a good part of the time spent on it.
+//! A game that implements [`IncrementalHash`] keeps a hash of its position up to date in
+//! [`Game::make_move`], usually by XORing in [Zobrist keys](https://en.wikipedia.org/wiki/Zobrist_hashing)
+//! from [`zobrist_key`] for whatever the move changed, and writes only that hash in its [`Hash`](core::hash::Hash) implementation.
+//!
+//! Every hash-keyed table then uses it as is, while games that derive [`Hash`](core::hash::Hash) still hash their whole board.
+//! To skip hashing the hash again, key a table with [`BuildIncrementalHasher`].
+
+use core::hash::{BuildHasherDefault, Hasher};
+
+use crate::game::Game;
+
+/// A game that keeps the hash of its position up to date as moves are made.
+///
+/// Its [`Hash`](core::hash::Hash) implementation should only write [`IncrementalHash::incremental_hash`],
+/// with [`Hasher::write_u64`].
+pub trait IncrementalHash: Game {
+    /// The hash of the position, as kept up to date by every move made to reach it.
+    fn incremental_hash(&self) -> u64;
+
+    /// The hash of the position, computed from scratch.
+    ///
+    /// This always has to equal [`IncrementalHash::incremental_hash`].
+    fn full_hash(&self) -> u64;
+}
+
+/// The Zobrist key of `index`, such as that of a piece on a square.
+///
+/// The keys are fixed, so hashes are the same across runs, and look random enough that
+/// XORing any few of them together rarely collides.
+#[must_use]
+pub const fn zobrist_key(index: usize) -> u64 {
+    splitmix64(index as u64)
+}
+
+/// The increment of the state of SplitMix64 between outputs.
+pub(crate) const SPLITMIX64_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// The output of [SplitMix64](https://prng.di.unimi.it/splitmix64.c) for the state `state`,
+/// before it's incremented.
+pub(crate) const fn splitmix64(state: u64) -> u64 {
+    let mut z = state.wrapping_add(SPLITMIX64_GAMMA);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// A hasher that passes an [`IncrementalHash`] through as it is, as it's already well mixed.
+///
+/// Anything written past a single [`u64`] is still mixed in, so any key can be hashed with it,
+/// but only games that implement [`IncrementalHash`] should be.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IncrementalHasher(u64);
+
+impl Hasher for IncrementalHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.write_u64(u64::from(byte));
+        }
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        // the first write leaves `i` as it is, as the mix of 0 is 0
+        self.0 = self
+            .0
+            .rotate_left(5)
+            .wrapping_mul(0x51_7c_c1_b7_27_22_0a_95)
+            ^ i;
+    }
+}
+
+/// Builds [`IncrementalHasher`]s, to key a table with, like `HashMap<T, Score, BuildIncrementalHasher>`.
+pub type BuildIncrementalHasher = BuildHasherDefault<IncrementalHasher>;
@@ -0,0 +1,263 @@
+//! Suggesting a move with one call, [`hint`], which trades how sure the suggestion is for how long it takes.
+//! This requires the `std` feature to be enabled.
+//!
+//! Each game answers from the cheapest thing it has that knows: its book, then the Grundy values of impartial games,
+//! then a search cut off by a time budget (with the game's evaluator, if it has one), and a full solve last.
+//! Games say what they have by implementing [`Hints`], whose defaults fit any game.
+
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    game::Game,
+    nimber::{grundy_value, Nimber},
+    player::TwoPlayer,
+    policy::Evaluator,
+    session::deadline_token,
+    strategy::{Evaluation, HeuristicSearch, Negamax, Outcome, Strategy, Value},
+    transposition::DepthEntry,
+    GameSolveError,
+};
+
+/// How hard [`hint`] tries to find the best move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Effort {
+    /// Answer within a few milliseconds, from the book if it knows the position, or else a very short search.
+    Instant,
+    /// Answer within a fraction of a second, trying the Grundy values of impartial games before searching.
+    Quick,
+    /// Solve the position, however long that takes.
+    Exact,
+}
+
+impl Effort {
+    /// How long a search can take with this effort, unless the game says otherwise
+    /// (see [`Hints::hint_budget`]), or None to solve the position.
+    pub fn default_budget(self) -> Option<Duration> {
+        match self {
+            Self::Instant => Some(Duration::from_millis(5)),
+            Self::Quick => Some(Duration::from_millis(250)),
+            Self::Exact => None,
+        }
+    }
+}
+
+/// What a game knows that helps [`hint`] answer quickly. Every method has a default,
+/// so `impl Hints for MyGame {}` is enough for hints found by searching alone.
+pub trait Hints: Game + Sized {
+    /// A move that theory says wins, without searching, or None (the default) if there's no such theory
+    /// or the position is lost.
+    fn book_move(&self) -> Option<Self::Move> {
+        None
+    }
+
+    /// The evaluator to search the game with once the budget stops the search short of the end,
+    /// or None (the default) to score the positions it can't see the end of as ties.
+    fn hint_evaluator() -> Option<Box<dyn Evaluator<Self>>> {
+        None
+    }
+
+    /// How long [`hint`] searches the game for with `effort`, or None to solve it,
+    /// [`Effort::default_budget`] by default.
+    fn hint_budget(effort: Effort) -> Option<Duration> {
+        effort.default_budget()
+    }
+}
+
+/// Whether the estimate of a [`Hint`] can be trusted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Honesty {
+    /// The estimate is proven: the move is one of the best, and gets the outcome the estimate says.
+    Exact,
+    /// The estimate is a guess, from a search that couldn't see the end of the game.
+    Heuristic,
+}
+
+/// Where the move of a [`Hint`] came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HintSource {
+    /// The game's theory of winning moves, as [`Hints::book_move`] gives them.
+    Book,
+    /// The Grundy values of the positions after each move, for impartial games.
+    Grundy,
+    /// A search that looked as many moves ahead as it had time for.
+    Search,
+    /// A solve, or a search that saw how the game ends.
+    Solve,
+}
+
+/// A move suggested by [`hint`], with how good it is for the player to move.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hint<M> {
+    pub best_move: M,
+    /// Whether [`estimate`](Self::estimate) is proven, or only a guess.
+    pub honesty: Honesty,
+    /// How good the position is for the player to move, if they play the move.
+    pub estimate: Value,
+    pub source: HintSource,
+}
+
+/// Suggests a move in `game`, trying as hard as `effort` says, or None if the game is over.
+///
+/// - [`Effort::Exact`] solves the position like [`Negamax`], so its estimate is the score of [`solve`](crate::solve).
+/// - Otherwise, the [book move](Hints::book_move) is suggested if there is one,
+///   and impartial games then try their [Grundy values](grundy_value) (with [`Effort::Quick`]).
+///   If those don't know the position, it's searched one move deeper at a time with the game's
+///   [evaluator](Hints::hint_evaluator) until its [budget](Hints::hint_budget) runs out,
+///   or until the search sees the end of every line, or a win or loss, which solves it.
+///
+/// The search always finishes looking one move ahead, so there's a move to suggest even if the budget is spent.
+///
+/// ```
+/// use game_solver::hint::{hint, Effort, HintSource, Honesty};
+/// use games::naive_nim::Nim;
+///
+/// // the heaps' nim-sum is 1, so Bouton's theorem gives the winning move without searching
+/// let game = Nim::new(vec![3, 5, 7]);
+/// let suggestion = hint(&game, Effort::Instant).unwrap().unwrap();
+///
+/// assert_eq!(suggestion.source, HintSource::Book);
+/// assert_eq!(suggestion.honesty, Honesty::Exact);
+/// ```
+pub fn hint<T, P>(game: &T, effort: Effort) -> Result<Option<Hint<T::Move>>, GameSolveError<T>>
+where
+    P: TwoPlayer + 'static,
+    T: Hints<Player = P> + Eq + Hash,
+{
+    if game.status().is_over() {
+        return Ok(None);
+    }
+
+    let Some(budget) = T::hint_budget(effort) else {
+        let evaluation = Negamax::new(HashMap::new()).evaluate(game)?;
+        return Ok(evaluation.best_move.map(|best_move| Hint {
+            best_move,
+            honesty: Honesty::Exact,
+            estimate: evaluation.value,
+            source: HintSource::Solve,
+        }));
+    };
+    let deadline = Instant::now() + budget;
+
+    if let Some(best_move) = game.book_move() {
+        return Ok(Some(Hint {
+            best_move,
+            honesty: Honesty::Exact,
+            estimate: Value::Proven(Outcome::Win),
+            source: HintSource::Book,
+        }));
+    }
+
+    if T::IMPARTIAL && effort != Effort::Instant {
+        match grundy_hint(game, deadline) {
+            Err(GameSolveError::CancellationTokenError) => (),
+            hint => return hint.map(Some),
+        }
+    }
+
+    search_hint(game, deadline).map(Some)
+}
+
+/// The first move of `game` to a position whose Grundy value is 0, which wins,
+/// or its first move if there is none, as every move loses.
+fn grundy_hint<T: Game + Eq + Hash>(
+    game: &T,
+    deadline: Instant,
+) -> Result<Hint<T::Move>, GameSolveError<T>> {
+    let (token, _timer) = deadline_token(deadline);
+    let cancellation_token = Some(token);
+    let mut known = HashMap::new();
+
+    let mut first_move = None;
+    for m in game.possible_moves() {
+        let mut board = game.clone();
+        board.make_move(&m).map_err(GameSolveError::MoveError)?;
+
+        if grundy_value(&board, &mut known, None, &cancellation_token)? == Nimber::ZERO {
+            return Ok(Hint {
+                best_move: m,
+                honesty: Honesty::Exact,
+                estimate: Value::Proven(Outcome::Win),
+                source: HintSource::Grundy,
+            });
+        }
+
+        first_move.get_or_insert(m);
+    }
+
+    Ok(Hint {
+        best_move: first_move.expect("the game is playable, so it has moves"),
+        honesty: Honesty::Exact,
+        estimate: Value::Proven(Outcome::Loss),
+        source: HintSource::Grundy,
+    })
+}
+
+/// The best move of the deepest search of `game` that finished before `deadline`,
+/// searching one move deeper at a time with the game's evaluator.
+fn search_hint<T, P>(game: &T, deadline: Instant) -> Result<Hint<T::Move>, GameSolveError<T>>
+where
+    P: TwoPlayer + 'static,
+    T: Hints<Player = P> + Eq + Hash,
+{
+    let (token, _timer) = deadline_token(deadline);
+    // the positions past the last move can't be played, so looking this far ahead sees the end of every game
+    let moves_left = game
+        .max_moves()
+        .map(|max_moves| max_moves.saturating_sub(game.move_count()));
+    let sees_the_end = |depth: usize| moves_left.is_some_and(|moves_left| depth >= moves_left);
+
+    let evaluator = T::hint_evaluator();
+    let mut search = HeuristicSearch::new(
+        move |game: &T| {
+            evaluator
+                .as_ref()
+                .map_or(0, |evaluator| evaluator.evaluate(game))
+        },
+        1,
+        HashMap::<T, DepthEntry>::new(),
+    );
+    // one move ahead is always searched, so there's a move to suggest
+    let mut deepest = search.evaluate(game)?;
+    search.cancellation_token = Some(token);
+
+    while !matches!(deepest.value, Value::Exact(_)) && !sees_the_end(search.depth) {
+        search.depth += 1;
+        match search.evaluate(game) {
+            Ok(evaluation) => deepest = evaluation,
+            Err(GameSolveError::CancellationTokenError) => {
+                search.depth -= 1;
+                break;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    let Evaluation {
+        value, best_move, ..
+    } = deepest;
+    // a seen win or loss is decided, and a search that saw the end of every line only evaluated ties
+    let value = match value {
+        Value::Heuristic(score) if sees_the_end(search.depth) => Value::Exact(score),
+        value => value,
+    };
+    let solved = matches!(value, Value::Exact(_));
+
+    Ok(Hint {
+        best_move: best_move.expect("the game is playable, so it has moves"),
+        honesty: if solved {
+            Honesty::Exact
+        } else {
+            Honesty::Heuristic
+        },
+        estimate: value,
+        source: if solved {
+            HintSource::Solve
+        } else {
+            HintSource::Search
+        },
+    })
+}
@@ -0,0 +1,114 @@
+//! A game along with the moves that were played to reach it, which can be taken back and replayed.
+//!
+//! [`Game`] itself only moves forward, so [`History`] keeps a snapshot of every position it went through.
+//! Undoing a move goes back to the snapshot from before it, which restores everything the game keeps
+//! (such as an [incremental hash](crate::hash::IncrementalHash)) exactly as it was.
+
+use alloc::vec::Vec;
+
+use crate::game::Game;
+
+/// A game, the moves played to reach it from where it started, and the moves that were undone.
+///
+/// ```
+/// use game_solver::history::History;
+/// use games::{naive_nim::Nim, util::move_natural::NaturalMove};
+///
+/// let start = Nim::new(vec![2, 3]);
+/// let mut history = History::new(start.clone());
+///
+/// // takes 2 from the second heap, then 1 from the first
+/// history.push(NaturalMove([1, 2])).unwrap();
+/// history.push(NaturalMove([0, 1])).unwrap();
+/// assert!(history.push(NaturalMove([0, 5])).is_err());
+/// assert_eq!(history.moves(), [NaturalMove([1, 2]), NaturalMove([0, 1])]);
+///
+/// assert_eq!(history.undo(), Some(&NaturalMove([0, 1])));
+/// assert_eq!(history.undo(), Some(&NaturalMove([1, 2])));
+/// assert_eq!(history.undo(), None);
+/// assert_eq!(history.game(), &start);
+///
+/// assert_eq!(history.redo(), Some(&NaturalMove([1, 2])));
+/// assert_eq!(history.position_at(1), Some(history.game()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct History<G: Game> {
+    /// Every position played through, from the start. The ones after `ply` were undone.
+    positions: Vec<G>,
+    /// The move leading to each position after the start.
+    moves: Vec<G::Move>,
+    /// How many moves were played to reach the current position.
+    ply: usize,
+}
+
+impl<G: Game> History<G> {
+    /// Starts a history from `game`, with no moves played.
+    pub fn new(game: G) -> Self {
+        Self {
+            positions: alloc::vec![game],
+            moves: Vec::new(),
+            ply: 0,
+        }
+    }
+
+    /// The current position.
+    pub fn game(&self) -> &G {
+        &self.positions[self.ply]
+    }
+
+    /// The position the history started from.
+    pub fn start(&self) -> &G {
+        &self.positions[0]
+    }
+
+    /// How many moves were played to reach the current position.
+    pub fn ply(&self) -> usize {
+        self.ply
+    }
+
+    /// Plays a move from the current position, forgetting the moves that were undone.
+    ///
+    /// If the move fails, the history stays as it was.
+    pub fn push(&mut self, m: G::Move) -> Result<(), G::MoveError> {
+        let mut board = self.game().clone();
+        board.make_move(&m)?;
+
+        self.positions.truncate(self.ply + 1);
+        self.moves.truncate(self.ply);
+
+        self.positions.push(board);
+        self.moves.push(m);
+        self.ply += 1;
+
+        Ok(())
+    }
+
+    /// Takes back the last move, returning it, or None if no move was played.
+    ///
+    /// The move can be played again with [`History::redo`], until another move is pushed.
+    pub fn undo(&mut self) -> Option<&G::Move> {
+        self.ply = self.ply.checked_sub(1)?;
+        Some(&self.moves[self.ply])
+    }
+
+    /// Plays the last undone move again, returning it, or None if there is none.
+    pub fn redo(&mut self) -> Option<&G::Move> {
+        let m = self.moves.get(self.ply)?;
+        self.ply += 1;
+        Some(m)
+    }
+
+    /// The moves played to reach the current position, in order, which is the record of the game so far.
+    pub fn moves(&self) -> &[G::Move] {
+        &self.moves[..self.ply]
+    }
+
+    /// The position after the first `ply` moves, or None if fewer moves were played.
+    pub fn position_at(&self, ply: usize) -> Option<&G> {
+        if ply > self.ply {
+            return None;
+        }
+
+        self.positions.get(ply)
+    }
+}
@@ -3,41 +3,188 @@
 //! If you want to read how to properly use this library,
 //! [the book](https://leodog896.github.io/game-solver/book) is
 //! a great place to start.
+//!
+//! Without the default `std` feature, the solver only needs `core` and `alloc`,
+//! so it can run on targets without an operating system.
+//! Use a [`BTreeMap`](alloc::collections::BTreeMap), or a `hashbrown` map with the `hashbrown` feature,
+//! as the transposition table there.
 
-pub mod game;
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod arena;
+#[cfg(feature = "rayon")]
+pub mod batch;
+#[cfg(feature = "std")]
+pub mod census;
+#[cfg(feature = "std")]
+pub mod cgt;
+#[cfg(feature = "std")]
+pub mod compound_game;
+#[cfg(feature = "std")]
 pub mod disjoint_game;
+pub mod dominance;
+#[cfg(feature = "std")]
+pub mod dot;
+#[cfg(feature = "std")]
+pub mod endgame;
+pub mod game;
+pub mod hash;
+pub mod history;
+#[cfg(feature = "std")]
+pub mod hint;
+#[cfg(feature = "rand")]
+pub mod mcts;
+pub mod nimber;
+pub mod ordering;
 pub mod player;
+pub mod policy;
+mod pool;
+pub mod prelude;
+pub mod proof_number;
+#[cfg(feature = "rand")]
+pub mod puzzle;
+#[cfg(feature = "rand")]
+pub mod random;
+pub mod sequential_game;
+#[cfg(feature = "std")]
+pub mod session;
 pub mod stats;
+pub mod strategy;
+pub mod tablebase;
+#[cfg(feature = "rayon")]
+pub mod think;
+pub mod tie_break;
+mod trace;
 // TODO: reinforcement
 // #[cfg(feature = "reinforcement")]
 // pub mod reinforcement;
 pub mod transposition;
+#[cfg(feature = "std")]
+pub mod verify;
 
-use core::panic;
+use alloc::{sync::Arc, vec::Vec};
+use core::fmt;
 #[cfg(feature = "rayon")]
-use std::hash::BuildHasher;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+use core::hash::BuildHasher;
+use core::panic;
+use core::sync::atomic::{AtomicBool, Ordering};
 
-use game::{upper_bound, GameState};
-use player::{ImpartialPlayer, TwoPlayer};
+use game::{upper_bound, MoveBuf};
+use ordering::MoveOrdering;
+use player::TwoPlayer;
+use policy::Evaluator;
+use pool::BoardPool;
 use stats::Stats;
+use strategy::Outcome;
+use trace::{trace_event, trace_span};
 
 use crate::game::Game;
-use crate::transposition::{Score, TranspositionTable};
-use std::hash::Hash;
-use thiserror::Error;
+use crate::transposition::{DepthAwareTable, DepthEntry, Score, TranspositionTable};
+use core::hash::Hash;
 
-#[derive(Error, Debug)]
+#[derive(Debug)]
 pub enum GameSolveError<T: Game> {
-    #[error("could not make a move")]
+    /// A move couldn't be made. When searching, the moves come from [`Game::possible_moves`],
+    /// so this is a bug in the game.
     MoveError(T::MoveError),
-    #[error("the game was cancelled by the token")]
     CancellationTokenError,
+    /// A position was reached after more moves than the game's [`Game::max_moves`],
+    /// which scores are measured from.
+    MaxMovesExceeded {
+        move_count: usize,
+        max_moves: usize,
+    },
+    /// A search was given a window that's empty, or that goes past the scores the game can have
+    /// (see [`evaluate_window`]).
+    InvalidWindow {
+        alpha: isize,
+        beta: isize,
+    },
+    /// A finished game was scored further from its [draw score](Game::draw_score) than winning right away,
+    /// which the solver relies on to stop searching (see [`Game::status`]).
+    ScoreOutOfBounds {
+        /// The score, measured from the draw score.
+        score: isize,
+        /// The [score of winning](game::win_score) the game when it finished.
+        bound: isize,
+    },
+}
+
+// written by hand rather than derived, as `thiserror` needs `std`
+impl<T: Game> fmt::Display for GameSolveError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MoveError(_) => write!(f, "could not make a move"),
+            Self::CancellationTokenError => write!(f, "the game was cancelled by the token"),
+            Self::MaxMovesExceeded {
+                move_count,
+                max_moves,
+            } => write!(
+                f,
+                "a position was reached after {move_count} moves, but the game has at most {max_moves}"
+            ),
+            Self::InvalidWindow { alpha, beta } => write!(
+                f,
+                "the window {alpha}..{beta} is empty or past the scores the game can have"
+            ),
+            Self::ScoreOutOfBounds { score, bound } => write!(
+                f,
+                "a finished game scored {score} from its draw score, further than the {bound} of winning right away"
+            ),
+        }
+    }
+}
+
+impl<T: Game> core::error::Error for GameSolveError<T> where Self: fmt::Debug {}
+
+/// Checks that `game` hasn't made more moves than its upper bound, which scores are measured from
+/// (see [`win_score`](game::win_score)), so they'd stop making sense past it.
+pub(crate) fn check_move_count<T: Game>(game: &T) -> Result<(), GameSolveError<T>> {
+    let bound = upper_bound(game);
+
+    match isize::try_from(game.move_count()) {
+        Ok(move_count) if move_count <= bound => Ok(()),
+        _ => Err(GameSolveError::MaxMovesExceeded {
+            move_count: game.move_count(),
+            max_moves: bound as usize,
+        }),
+    }
+}
+
+/// The score of `game` for the player to move if it's over, as its [status](Game::status) says,
+/// measured from its [draw score](Game::draw_score), or None if it isn't.
+pub(crate) fn terminal_score<T: Game>(game: &T) -> Result<Option<isize>, GameSolveError<T>> {
+    match game.status().score(game) {
+        None => Ok(None),
+        Some(0) => Ok(Some(0)),
+        Some(score) => {
+            check_move_count(game)?;
+
+            let bound = game::win_score(game);
+            if score.unsigned_abs() > bound.unsigned_abs() {
+                return Err(GameSolveError::ScoreOutOfBounds { score, bound });
+            }
+
+            Ok(Some(score))
+        }
+    }
 }
 
 /// Runs the two-player minimax variant on a zero-sum game.
-/// Since it uses alpha-beta pruning, you can specify an alpha beta window.
+/// Since it uses alpha-beta pruning, you can specify an alpha beta window, where `alpha < beta`.
+///
+/// The search fails hard: a score at or below `alpha` only means the position's score is at most that,
+/// and one at or above `beta` that it's at least that, while one strictly between them is the score itself.
+/// What it stores in the transposition table follows the same rule, as an [upper bound](Score::UpperBound),
+/// a [lower bound](Score::LowerBound), or an [exact score](Score::Exact), so every entry holds
+/// whatever the window of the search that stored it, and searches with any windows can share a table.
+///
+/// The positions after each move are copied into the boards of `boards`,
+/// which can be kept from one search to the next.
+#[allow(clippy::too_many_arguments)]
 fn negamax<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
     game: &T,
     transposition_table: &mut dyn TranspositionTable<T>,
@@ -45,7 +192,11 @@ fn negamax<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
     mut beta: isize,
     stats: Option<&Stats<T::Player>>,
     cancellation_token: &Option<Arc<AtomicBool>>,
+    ordering: &MoveOrdering<T>,
+    boards: &mut BoardPool<T>,
 ) -> Result<isize, GameSolveError<T>> {
+    debug_assert!(alpha < beta, "the window {alpha}..{beta} is empty");
+
     if let Some(token) = cancellation_token {
         if token.load(Ordering::Relaxed) {
             return Err(GameSolveError::CancellationTokenError);
@@ -54,8 +205,16 @@ fn negamax<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
 
     if let Some(stats) = stats {
         stats.states_explored.fetch_add(1, Ordering::Relaxed);
+        if let Some(stats) = stats.ply(game.move_count()) {
+            stats.nodes.fetch_add(1, Ordering::Relaxed);
+        }
     }
 
+    trace::count_node();
+
+    // scores above this are within the window, and so exact, unless they're cutoffs
+    let lowest = alpha;
+
     // TODO: debug-based depth counting
     // if let Some(stats) = stats {
     //     stats.max_depth.fetch_max(depth, Ordering::Relaxed);
@@ -63,96 +222,70 @@ fn negamax<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
 
     // TODO(perf): if find_immediately_resolvable_game satisfies its contract,
     // we can ignore this at larger depths.
-    match game.state() {
-        GameState::Playable => (),
-        GameState::Tie => {
-            if let Some(stats) = stats {
-                stats.terminal_ends.tie.fetch_add(1, Ordering::Relaxed);
-            }
-            return Ok(0);
-        }
-        GameState::Win(winning_player) => {
-            // TODO: can we not duplicate this
-            if let Some(stats) = stats {
-                if let Ok(player) = castaway::cast!(winning_player, ImpartialPlayer) {
-                    if ImpartialPlayer::from_move_count(
-                        stats.original_move_count,
-                        game.move_count(),
-                    ) == player
-                    {
-                        stats.terminal_ends.winning.fetch_add(1, Ordering::Relaxed);
-                    } else {
-                        stats.terminal_ends.losing.fetch_add(1, Ordering::Relaxed);
-                    }
-                } else if stats.original_player == winning_player {
-                    stats.terminal_ends.winning.fetch_add(1, Ordering::Relaxed);
-                } else {
-                    stats.terminal_ends.losing.fetch_add(1, Ordering::Relaxed);
+    if let Some(score) = terminal_score(game)? {
+        if let Some(stats) = stats {
+            match score {
+                0 => {
+                    stats.terminal_ends.tie.fetch_add(1, Ordering::Relaxed);
                 }
-            }
-
-            // if the next player is the winning player,
-            // the score should be positive.
-            if game.player() == winning_player {
-                // we add one to make sure games that use up every move
-                // aren't represented by ties.
-                //
-                // take the 2 heap game where each heap has one object in Nim, for example
-                // player 2 will always win since 2 moves will always be used,
-                // but since the upper bound is 2, 2 - 2 = 0,
-                // but we reserve 0 for ties.
-                return Ok(upper_bound(game) - game.move_count() as isize + 1);
-            } else {
-                return Ok(-(upper_bound(game) - game.move_count() as isize + 1));
+                // a positive score is the player to move's
+                1.. => stats.record_win(game.player(), game.move_count()),
+                _ => stats.record_win(game.player().previous(), game.move_count()),
             }
         }
-    };
 
-    // check if this is a winning configuration
-    if let Ok(Some(board)) = game.find_immediately_resolvable_game() {
-        match board.state() {
-            GameState::Playable => panic!("A resolvable game should not be playable."),
-            GameState::Tie => {
-                if let Some(stats) = stats {
-                    stats.terminal_ends.tie.fetch_add(1, Ordering::Relaxed);
-                }
-                return Ok(0);
-            }
-            GameState::Win(winning_player) => {
-                if let Some(stats) = stats {
-                    if let Ok(player) = castaway::cast!(winning_player, ImpartialPlayer) {
-                        if ImpartialPlayer::from_move_count(
-                            stats.original_move_count,
-                            game.move_count(),
-                        ) == player
-                        {
-                            stats.terminal_ends.winning.fetch_add(1, Ordering::Relaxed);
-                        } else {
-                            stats.terminal_ends.losing.fetch_add(1, Ordering::Relaxed);
-                        }
-                    } else if stats.original_player == winning_player {
-                        stats.terminal_ends.winning.fetch_add(1, Ordering::Relaxed);
-                    } else {
-                        stats.terminal_ends.losing.fetch_add(1, Ordering::Relaxed);
-                    }
-                }
+        return Ok(score);
+    }
 
-                if game.player().turn() == winning_player {
-                    return Ok(upper_bound(&board) - board.move_count() as isize + 1);
-                } else {
-                    return Ok(-(upper_bound(&board) - board.move_count() as isize + 1));
-                }
+    // no move can do better than winning right away, so a move that does is a beta cutoff
+    if let Some(best) = isize::try_from(game.move_count())
+        .ok()
+        .filter(|&move_count| move_count < upper_bound(game))
+        .map(|move_count| upper_bound(game) - move_count)
+    {
+        if beta > best {
+            beta = best;
+            if alpha >= beta {
+                return Ok(beta);
             }
         }
     }
 
     // fetch values from the transposition table
     {
-        let score = transposition_table
-            .get(game)
-            .unwrap_or_else(|| Score::UpperBound(upper_bound(game)));
+        let collisions = stats.map(|_| transposition_table.collisions());
+        let entry = transposition_table.get(game);
+
+        if let Some(stats) = stats {
+            stats.transposition_probes.fetch_add(1, Ordering::Relaxed);
+            if entry.is_some() {
+                stats.transposition_hits.fetch_add(1, Ordering::Relaxed);
+            }
+            if collisions.is_some_and(|collisions| transposition_table.collisions() > collisions) {
+                stats
+                    .transposition_collisions
+                    .fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        if let Some(_score) = entry {
+            trace_event!(
+                tracing::Level::TRACE,
+                nodes = trace::nodes(),
+                score = ?_score,
+                "transposition table hit"
+            );
+        }
+
+        let score = entry.unwrap_or_else(|| Score::UpperBound(upper_bound(game)));
 
         match score {
+            Score::Exact(score) => {
+                if let Some(stats) = stats {
+                    stats.cache_hits.fetch_add(1, Ordering::Relaxed);
+                }
+                return Ok(score.clamp(alpha, beta));
+            }
             Score::UpperBound(max) => {
                 if beta > max {
                     beta = max;
@@ -178,16 +311,68 @@ fn negamax<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
         };
     }
 
+    // check if this is a winning configuration: winning right away, scored like `GameStatus::won`,
+    // is the best the player to move can do, as no later ending can score more.
+    // a tie, a loss, or a win the game scores lower right away isn't, as another move might do better.
+    // this makes every move before searching any, so it's only done if the game can do it cheaply:
+    // otherwise, the winning move is a cutoff once it's searched (see below)
+    if let Some(Ok(Some(board))) = game
+        .cheap_win_check()
+        .then(|| game.find_immediately_resolvable_game())
+    {
+        // the score is of the player to move after the winning move, so it's negative
+        match terminal_score(&board)? {
+            None => panic!("A resolvable game should not be playable."),
+            Some(score) if score == -game::win_score(&board) => {
+                if let Some(stats) = stats {
+                    stats.record_win(game.player().turn(), game.move_count());
+                }
+
+                // the position won't be searched, so the score has to be stored for the next time it's reached
+                transposition_table.insert(game.clone(), Score::Exact(-score));
+                return Ok(-score);
+            }
+            Some(_) => (),
+        }
+    }
+
+    // the first move is searched with the full window,
     // for [principal variation search](https://www.chessprogramming.org/Principal_Variation_Search)
-    let mut first_child = true;
+    let mut searched = 0;
 
-    for m in &mut game.possible_moves() {
-        let mut board = game.clone();
+    // only collect the moves if they have to be sorted
+    let mut ordered = (!ordering.is_game_order()).then(|| {
+        ordering
+            .order_buffered(game, &*transposition_table)
+            .into_iter()
+    });
+    let mut unordered = ordered.is_none().then(|| game.possible_moves());
+    let moves = core::iter::from_fn(|| match &mut ordered {
+        Some(moves) => moves.next(),
+        None => unordered.as_mut()?.next(),
+    });
+
+    // moves that are never better than another aren't searched (see `Game::dominated_moves`).
+    // most games have none, which doesn't allocate, and leaves nothing to compare
+    let dominated = game.dominated_moves();
+
+    let ply = game.move_count();
+    let mut board = boards.take(ply, game);
+    let mut made_move = false;
+    for m in moves {
+        if dominated.contains(&m) {
+            continue;
+        }
+
+        if made_move {
+            board.clone_from(game);
+        }
         board
             .make_move(&m)
             .map_err(|err| GameSolveError::MoveError::<T>(err))?;
+        made_move = true;
 
-        let score = if first_child {
+        let score = if searched == 0 {
             -negamax(
                 &board,
                 transposition_table,
@@ -195,6 +380,8 @@ fn negamax<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
                 -alpha,
                 stats,
                 cancellation_token,
+                ordering,
+                boards,
             )?
         } else {
             let score = -negamax(
@@ -204,8 +391,18 @@ fn negamax<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
                 -alpha,
                 stats,
                 cancellation_token,
+                ordering,
+                boards,
             )?;
             if score > alpha {
+                trace_event!(
+                    tracing::Level::DEBUG,
+                    nodes = trace::nodes(),
+                    score,
+                    alpha,
+                    beta,
+                    "re-searching with the full window"
+                );
                 -negamax(
                     &board,
                     transposition_table,
@@ -213,18 +410,42 @@ fn negamax<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
                     -alpha,
                     stats,
                     cancellation_token,
+                    ordering,
+                    boards,
                 )?
             } else {
                 score
             }
         };
 
+        debug_assert!(
+            score.unsigned_abs() <= upper_bound(game).unsigned_abs() + 1,
+            "the score {score} of a move is out of the game's bounds"
+        );
+
         // alpha-beta pruning - we can return early
         if score >= beta {
             if let Some(stats) = stats {
                 stats.pruning_cutoffs.fetch_add(1, Ordering::Relaxed);
+                if let Some(stats) = stats.ply(ply) {
+                    stats.record_cutoff(searched);
+                }
             }
+            trace_event!(
+                tracing::Level::TRACE,
+                nodes = trace::nodes(),
+                score,
+                beta,
+                "beta cutoff"
+            );
+            trace_event!(
+                tracing::Level::TRACE,
+                nodes = trace::nodes(),
+                score = ?Score::LowerBound(score),
+                "transposition table store"
+            );
             transposition_table.insert(game.clone(), Score::LowerBound(score));
+            boards.give(ply, board);
             return Ok(beta);
         }
 
@@ -232,27 +453,456 @@ fn negamax<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
             alpha = score;
         }
 
-        first_child = false;
+        searched += 1;
     }
+    boards.give(ply, board);
 
-    transposition_table.insert(game.clone(), Score::UpperBound(alpha));
+    // no move was a cutoff, so if one raised alpha (or a lower bound did), it's the score:
+    // every other move is at most it. otherwise, every move failed low, which only bounds the score.
+    let score = if alpha > lowest {
+        Score::Exact(alpha)
+    } else {
+        Score::UpperBound(alpha)
+    };
+    trace_event!(
+        tracing::Level::TRACE,
+        nodes = trace::nodes(),
+        score = ?score,
+        "transposition table store"
+    );
+    transposition_table.insert(game.clone(), score);
 
     Ok(alpha)
 }
 
 /// Solves a game, returning the evaluated score.
 ///
+/// Once solved, the exact score of `game` is stored in the transposition table,
+/// so it can be looked up with [`TranspositionTable::entry`] afterwards.
+///
 /// The score of a position is defined by the best possible end result for the player whose turn it is.
 /// In 2 player games, if a score > 0, then the player whose turn it is has a winning strategy.
 /// If a score < 0, then the player whose turn it is has a losing strategy.
 /// Else, the game is a draw (score = 0).
+///
+/// # Scores
+///
+/// Scores are measured from the game's [`upper_bound`] `B`, its [`Game::max_moves`]:
+/// a tie scores 0, and a win after `n` moves scores `B - n + 1` for the winner, and minus that for the loser.
+/// Every score is then in `-(B + 1)..=B + 1`, and 0 only ever stands for a tie, even for a win on the last move a game can have,
+/// which is what the searches for the score (whose windows start from `-B..B + 1`) rely on.
+///
+/// Games can give their own scores in [`Game::status`] instead (such as how many boxes each player captured),
+/// which are measured from the game's [`Game::draw_score`], so that a tie is still 0.
+/// They can't be further from it than a win at the same move would score, or solving fails
+/// with [`GameSolveError::ScoreOutOfBounds`], as the search relies on nothing beating a win right away.
+/// A position after more than `B` moves can't be scored at all, so solving it fails
+/// with [`GameSolveError::MaxMovesExceeded`], rather than giving a score past the bounds.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use game_solver::solve;
+/// use games::takeaway::Takeaway;
+///
+/// // taking all 3 objects wins on the first move, as fast as a 3 move game can be won
+/// assert_eq!(solve(&Takeaway::new(3), &mut HashMap::new(), None, &None).unwrap(), 3);
+///
+/// // 4 is lost, on the second move whatever is taken
+/// assert_eq!(solve(&Takeaway::new(4), &mut HashMap::new(), None, &None).unwrap(), -3);
+///
+/// // 21 is won by taking 1, then the rest of every 4 the opponent starts on,
+/// // which they make last as long as they can by taking 1 at a time: the win is on the 11th move
+/// assert_eq!(solve(&Takeaway::new(21), &mut HashMap::new(), None, &None).unwrap(), 21 - 11 + 1);
+/// ```
 pub fn solve<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
     game: &T,
     transposition_table: &mut dyn TranspositionTable<T>,
     stats: Option<&Stats<T::Player>>,
     cancellation_token: &Option<Arc<AtomicBool>>,
 ) -> Result<isize, GameSolveError<T>> {
+    solve_ordered(
+        game,
+        transposition_table,
+        stats,
+        cancellation_token,
+        &MoveOrdering::GameOrder,
+    )
+}
+
+/// Solves a game like [`solve`], naming whose point of view the score is from:
+/// that of the player to move in `game`.
+///
+/// A position after an odd number of moves has the other player to move,
+/// so its score is for them; use [`evaluate_for`] to score it for a given player instead.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use game_solver::evaluate_for_mover;
+/// use games::naive_nim::Nim;
+///
+/// let game = Nim::new(vec![1, 2]);
+/// let score = evaluate_for_mover(&game, &mut HashMap::new(), None, &None).unwrap();
+/// // the player to move wins by evening out the heaps
+/// assert!(score > 0);
+/// ```
+pub fn evaluate_for_mover<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    game: &T,
+    transposition_table: &mut dyn TranspositionTable<T>,
+    stats: Option<&Stats<T::Player>>,
+    cancellation_token: &Option<Arc<AtomicBool>>,
+) -> Result<isize, GameSolveError<T>> {
+    solve(game, transposition_table, stats, cancellation_token)
+}
+
+/// Solves a game like [`solve`], scoring it from the point of view of `player`,
+/// whether or not it's their turn: positive if they can force a win, negative if they can't avoid a loss.
+///
+/// The players of impartial games are named relative to the position, [`ImpartialPlayer::Next`](player::ImpartialPlayer::Next) being the one to move,
+/// so the same player is named [`Player::turn`](player::Player::turn) after a move.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use game_solver::{evaluate_for, player::ImpartialPlayer};
+/// use games::naive_nim::Nim;
+///
+/// let game = Nim::new(vec![1, 2]);
+/// let mut table = HashMap::new();
+///
+/// let next = evaluate_for(&game, ImpartialPlayer::Next, &mut table, None, &None).unwrap();
+/// let previous = evaluate_for(&game, ImpartialPlayer::Previous, &mut table, None, &None).unwrap();
+/// assert_eq!(next, -previous);
+/// ```
+pub fn evaluate_for<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    game: &T,
+    player: T::Player,
+    transposition_table: &mut dyn TranspositionTable<T>,
+    stats: Option<&Stats<T::Player>>,
+    cancellation_token: &Option<Arc<AtomicBool>>,
+) -> Result<isize, GameSolveError<T>> {
+    let score = solve(game, transposition_table, stats, cancellation_token)?;
+
+    Ok(score_for(game, player, score))
+}
+
+/// Turns `score`, a score of `game` for the player to move (like those of [`solve`] and [`move_scores`]),
+/// into its score for `player`.
+///
+/// ```
+/// use game_solver::{game::Game, player::TwoPlayer, score_for};
+/// use games::domineering::Domineering;
+///
+/// let game = Domineering::<4, 4>::new();
+///
+/// assert_eq!(score_for(&game, game.player(), 3), 3);
+/// assert_eq!(score_for(&game, game.player().other(), 3), -3);
+/// ```
+pub fn score_for<T: Game>(game: &T, player: T::Player, score: isize) -> isize {
+    if player == game.player() {
+        score
+    } else {
+        -score
+    }
+}
+
+/// Solves a game like [`solve`], but only finds its outcome for the player to move, not how many moves it takes.
+///
+/// Games that are [impartial](Game::IMPARTIAL) are won by the player to move if and only if their
+/// [Grundy value](nimber::grundy_value) isn't 0, which is what's found for them (with the `std` feature),
+/// without the transposition table. Every other game is searched with [`evaluate_window`] around a tie,
+/// which stops as soon as it knows whether the position is won, tied, or lost.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use game_solver::{solve, solve_outcome, strategy::Outcome};
+/// use games::naive_nim::Nim;
+///
+/// let game = Nim::new(vec![1, 2, 3]);
+/// let outcome = solve_outcome(&game, &mut HashMap::new(), None, &None).unwrap();
+///
+/// // the heaps' nim-sum is 0, so the player to move loses
+/// assert_eq!(outcome, Outcome::Loss);
+/// assert!(solve(&game, &mut HashMap::new(), None, &None).unwrap() < 0);
+/// ```
+pub fn solve_outcome<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    game: &T,
+    transposition_table: &mut dyn TranspositionTable<T>,
+    stats: Option<&Stats<T::Player>>,
+    cancellation_token: &Option<Arc<AtomicBool>>,
+) -> Result<Outcome, GameSolveError<T>> {
+    #[cfg(feature = "std")]
+    if T::IMPARTIAL {
+        let value = nimber::grundy_value(
+            game,
+            &mut std::collections::HashMap::new(),
+            stats,
+            cancellation_token,
+        )?;
+
+        return Ok(if value == nimber::Nimber::ZERO {
+            Outcome::Loss
+        } else {
+            Outcome::Win
+        });
+    }
+
+    // ties score 0, and wins and losses straddle it, so a window around it tells them apart
+    let result = evaluate_window(game, transposition_table, -1, 1, stats, cancellation_token)?;
+
+    Ok(Outcome::from_score(result.value()))
+}
+
+/// Solves a game like [`solve`], searching the moves of every position in the given order.
+///
+/// The score is the same whatever the order, but good moves searched first prune more of the tree.
+pub fn solve_ordered<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    game: &T,
+    transposition_table: &mut dyn TranspositionTable<T>,
+    stats: Option<&Stats<T::Player>>,
+    cancellation_token: &Option<Arc<AtomicBool>>,
+    ordering: &MoveOrdering<T>,
+) -> Result<isize, GameSolveError<T>> {
+    let score = solve_with_guess(
+        game,
+        transposition_table,
+        stats,
+        cancellation_token,
+        None,
+        ordering,
+    )?;
+
+    // the score is the table's, which can be lossy, so the check compares two searches of its own
+    #[cfg(all(game_solver_check_dominance, feature = "std", debug_assertions))]
+    {
+        let (left_out, searched) = dominance::check_dominated_moves(game)?;
+        assert_eq!(
+            left_out, searched,
+            "the score changed when dominated moves were left out, so one of them wasn't dominated"
+        );
+    }
+
+    Ok(score)
+}
+
+/// Solves a game like [`solve_ordered`], returning the score along with the statistics of the search,
+/// including what it did at each ply (see [`Stats::per_ply`]): how many positions it searched there,
+/// how many of them were cut off, and on which of their moves.
+///
+/// The position itself is searched once for each null window [`solve`] narrows the score down with,
+/// so the first ply can have more than one position.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use game_solver::{ordering::MoveOrdering, solve_detailed};
+/// use games::chomp::Chomp;
+///
+/// let game = Chomp::new(4, 3);
+/// let (score, stats) = solve_detailed(&game, &mut HashMap::new(), &None, &MoveOrdering::GameOrder).unwrap();
+/// assert!(score > 0);
+///
+/// let plies = stats.plies();
+/// assert!(plies[0].nodes >= 1);
+/// for ply in &plies {
+///     // every cutoff was on the first move searched, the second, or a later one
+///     assert_eq!(ply.cutoff_moves.iter().sum::<u64>(), ply.cutoffs);
+/// }
+/// ```
+pub fn solve_detailed<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    game: &T,
+    transposition_table: &mut dyn TranspositionTable<T>,
+    cancellation_token: &Option<Arc<AtomicBool>>,
+    ordering: &MoveOrdering<T>,
+) -> Result<(isize, Stats<T::Player>), GameSolveError<T>> {
+    let stats = Stats::per_ply(game);
+    let score = solve_ordered(
+        game,
+        transposition_table,
+        Some(&stats),
+        cancellation_token,
+        ordering,
+    )?;
+
+    Ok((score, stats))
+}
+
+/// What a search with a window found out about the score of a position, as [`evaluate_window`] returns it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowResult {
+    /// The score itself, which was strictly inside the window (or the position is over).
+    Exact(isize),
+    /// The search failed low: the score is at most this, which is at most the window's `alpha`.
+    AtMost(isize),
+    /// The search failed high: the score is at least this, which is at least the window's `beta`.
+    AtLeast(isize),
+}
+
+impl WindowResult {
+    /// The score, or the bound on it.
+    pub fn value(self) -> isize {
+        match self {
+            Self::Exact(score) | Self::AtMost(score) | Self::AtLeast(score) => score,
+        }
+    }
+}
+
+/// Searches a game once, with the alpha-beta window `alpha..beta`, without the null windows [`solve`] narrows the score down with.
+///
+/// The score is only found if it's strictly inside the window: otherwise, the search stops as soon as it knows
+/// the score is at most `alpha` or at least `beta`, which takes fewer positions the narrower the window is.
+/// Scores are those of [`solve`], so the window `-1..1` asks whether the position is won, tied, or lost.
+///
+/// The window has to have `alpha < beta`, and be within `-(B + 1)..=B + 1` for the game's [`upper_bound`] `B`,
+/// which every score is within: otherwise, this fails with [`GameSolveError::InvalidWindow`].
+/// What the search stores in the transposition table holds whatever the window, so any searches can share a table.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use game_solver::{evaluate_window, solve, WindowResult};
+/// use games::chomp::Chomp;
+///
+/// let game = Chomp::new(5, 4);
+/// let mut table = HashMap::new();
+///
+/// // the first player wins Chomp, which the window finds without finding how fast
+/// let result = evaluate_window(&game, &mut table, -1, 1, None, &None).unwrap();
+/// assert_eq!(result, WindowResult::AtLeast(1));
+///
+/// let score = solve(&game, &mut HashMap::new(), None, &None).unwrap();
+/// let window = evaluate_window(&game, &mut table, score - 1, score + 1, None, &None).unwrap();
+/// assert_eq!(window, WindowResult::Exact(score));
+///
+/// assert!(evaluate_window(&game, &mut table, 1, 1, None, &None).is_err());
+/// ```
+pub fn evaluate_window<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    game: &T,
+    transposition_table: &mut dyn TranspositionTable<T>,
+    alpha: isize,
+    beta: isize,
+    stats: Option<&Stats<T::Player>>,
+    cancellation_token: &Option<Arc<AtomicBool>>,
+) -> Result<WindowResult, GameSolveError<T>> {
+    check_move_count(game)?;
+
+    // this can't overflow, as the upper bound is at most `MAX_UPPER_BOUND`
+    let bound = upper_bound(game) + 1;
+    if alpha >= beta || alpha < -bound || beta > bound {
+        return Err(GameSolveError::InvalidWindow { alpha, beta });
+    }
+
+    let score = negamax(
+        game,
+        transposition_table,
+        alpha,
+        beta,
+        stats,
+        cancellation_token,
+        &MoveOrdering::GameOrder,
+        &mut BoardPool::new(),
+    )?;
+
+    // a game that's over is scored as it is, whatever the window
+    Ok(
+        if game.status().is_over() || (alpha < score && score < beta) {
+            WindowResult::Exact(score)
+        } else if score <= alpha {
+            WindowResult::AtMost(score)
+        } else {
+            WindowResult::AtLeast(score)
+        },
+    )
+}
+
+/// Solves a game like [`solve`], with a single search over every score it could have
+/// instead of a series of null windows around it.
+///
+/// Each position is searched at most once, but with a wider window, which prunes less,
+/// so this is usually slower than [`solve`]; it can share a transposition table with it,
+/// as every search stores bounds that hold whatever its window.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use game_solver::{solve, solve_full_window};
+/// use games::chomp::Chomp;
+///
+/// let game = Chomp::new(4, 3);
+/// let mut table = HashMap::new();
+///
+/// let score = solve_full_window(&game, &mut table, None, &None).unwrap();
+/// assert_eq!(solve(&game, &mut table, None, &None).unwrap(), score);
+/// assert_eq!(solve(&game, &mut HashMap::new(), None, &None).unwrap(), score);
+/// ```
+pub fn solve_full_window<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    game: &T,
+    transposition_table: &mut dyn TranspositionTable<T>,
+    stats: Option<&Stats<T::Player>>,
+    cancellation_token: &Option<Arc<AtomicBool>>,
+) -> Result<isize, GameSolveError<T>> {
+    let bound = upper_bound(game) + 1;
+
+    // no score is past the edges of the window, so one at them is exact too
+    let score = evaluate_window(
+        game,
+        transposition_table,
+        -bound,
+        bound,
+        stats,
+        cancellation_token,
+    )?
+    .value();
+
+    if !game.status().is_over() {
+        transposition_table.insert(game.clone(), Score::Exact(score));
+    }
+
+    Ok(score)
+}
+
+/// [`solve`], but with the first null window placed at `guess`, if there is one.
+/// The closer the guess is to the score, the fewer null windows are needed.
+fn solve_with_guess<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    game: &T,
+    transposition_table: &mut dyn TranspositionTable<T>,
+    stats: Option<&Stats<T::Player>>,
+    cancellation_token: &Option<Arc<AtomicBool>>,
+    mut guess: Option<isize>,
+    ordering: &MoveOrdering<T>,
+) -> Result<isize, GameSolveError<T>> {
+    let _span = trace_span!(
+        tracing::Level::INFO,
+        "solve",
+        move_count = game.move_count()
+    );
+    trace::reset_nodes();
+
+    // every score is measured from the upper bound, so it has to hold this position.
+    check_move_count(game)?;
+
+    // a game that's over is scored as it is, without the null windows below:
+    // one lost before any move scores one less than the lowest of them.
+    // the null window searches below all copy their positions into the same boards
+    let mut boards = BoardPool::new();
+
+    if game.status().is_over() {
+        return negamax(
+            game,
+            transposition_table,
+            -upper_bound(game) - 1,
+            upper_bound(game) + 1,
+            stats,
+            cancellation_token,
+            ordering,
+            &mut boards,
+        );
+    }
+
     let mut alpha = -upper_bound(game);
+    // this can't overflow, as the upper bound is at most `MAX_UPPER_BOUND`
     let mut beta = upper_bound(game) + 1;
 
     // we're trying to guess the score of the board via null windows
@@ -263,7 +913,11 @@ pub fn solve<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
             }
         }
 
-        let med = alpha + (beta - alpha) / 2;
+        let med = match guess.take() {
+            Some(guess) => guess.clamp(alpha, beta - 1),
+            // `beta - alpha` can overflow when the bounds are close to `isize::MAX`
+            None => ((alpha as i128 + beta as i128).div_euclid(2)) as isize,
+        };
 
         // do a [null window search](https://www.chessprogramming.org/Null_Window)
         let evaluation = negamax(
@@ -273,8 +927,18 @@ pub fn solve<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
             med + 1,
             stats,
             cancellation_token,
+            ordering,
+            &mut boards,
         )?;
 
+        trace_event!(
+            tracing::Level::DEBUG,
+            nodes = trace::nodes(),
+            window = med,
+            evaluation,
+            "null window search"
+        );
+
         if evaluation <= med {
             beta = evaluation;
         } else {
@@ -282,9 +946,345 @@ pub fn solve<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
         }
     }
 
+    trace_event!(
+        tracing::Level::INFO,
+        nodes = trace::nodes(),
+        score = alpha,
+        "solved"
+    );
+
+    // the searches only stored bounds on this position's score, but now it's known exactly
+    transposition_table.insert(game.clone(), Score::Exact(alpha));
+
     Ok(alpha)
 }
 
+/// Heuristic searches score the positions they see the end of like [`solve`] does, offset by this,
+/// so that no evaluation (which is kept within it) is mistaken for a win or a loss.
+pub const DECISIVE_SCORE: isize = isize::MAX / 4;
+
+/// The score [`solve`] would give a position, from its score found by [`solve_heuristic`],
+/// or None if the search only evaluated it.
+pub fn decided_score(score: isize) -> Option<isize> {
+    if score >= DECISIVE_SCORE {
+        Some(score - DECISIVE_SCORE)
+    } else if score <= -DECISIVE_SCORE {
+        Some(score + DECISIVE_SCORE)
+    } else {
+        None
+    }
+}
+
+/// A plain alpha-beta search that stops a given number of moves past the position it searches.
+/// Unlike `negamax`, this can't use a [`TranspositionTable`], as its scores aren't exact:
+/// given a [`DepthAwareTable`], it only uses the scores that were searched at least as deep,
+/// which makes its scores those of a search at least `depth` deep, not exactly that deep.
+struct DepthLimitedSearch<'a, T: Game> {
+    table: Option<&'a mut dyn DepthAwareTable<T>>,
+    /// Scores the positions the search can't see the end of, which are otherwise scored as ties.
+    evaluator: Option<&'a dyn Evaluator<T>>,
+    /// How many plies of [noisy moves](Game::noisy_moves) to search past the depth before evaluating.
+    quiescence: usize,
+    stats: Option<&'a Stats<T::Player>>,
+    cancellation_token: &'a Option<Arc<AtomicBool>>,
+}
+
+impl<'a, T: Game<Player = impl TwoPlayer + 'static>> DepthLimitedSearch<'a, T> {
+    /// Counts `game` as explored, returning its score if it's over.
+    fn visit(&self, game: &T) -> Result<Option<isize>, GameSolveError<T>> {
+        if let Some(token) = self.cancellation_token {
+            if token.load(Ordering::Relaxed) {
+                return Err(GameSolveError::CancellationTokenError);
+            }
+        }
+
+        if let Some(stats) = self.stats {
+            stats.states_explored.fetch_add(1, Ordering::Relaxed);
+        }
+
+        // with an evaluator, the scores of decided positions are kept apart from evaluations
+        let decisive = if self.evaluator.is_some() {
+            DECISIVE_SCORE
+        } else {
+            0
+        };
+
+        Ok(terminal_score(game)?.map(|score| match score {
+            0 => 0,
+            1.. => decisive + score,
+            _ => -decisive + score,
+        }))
+    }
+
+    /// Searches only the noisy moves of `game`, up to `plies` deep,
+    /// so that the evaluator doesn't score positions in the middle of a tactical sequence.
+    /// The player to move can always stand pat (play quietly), which is scored by the evaluator.
+    fn quiesce(
+        &mut self,
+        game: &T,
+        evaluator: &dyn Evaluator<T>,
+        plies: usize,
+        mut alpha: isize,
+        beta: isize,
+    ) -> Result<isize, GameSolveError<T>> {
+        if let Some(score) = self.visit(game)? {
+            return Ok(score);
+        }
+
+        let stand_pat = evaluator
+            .evaluate(game)
+            .clamp(-DECISIVE_SCORE + 1, DECISIVE_SCORE - 1);
+        if plies == 0 || stand_pat >= beta {
+            return Ok(stand_pat);
+        }
+        alpha = alpha.max(stand_pat);
+
+        for m in game.noisy_moves() {
+            let mut board = game.clone();
+            board
+                .make_move(&m)
+                .map_err(|err| GameSolveError::MoveError::<T>(err))?;
+
+            let score = -self.quiesce(&board, evaluator, plies - 1, -beta, -alpha)?;
+
+            if score >= beta {
+                return Ok(beta);
+            }
+            alpha = alpha.max(score);
+        }
+
+        Ok(alpha)
+    }
+
+    fn search(
+        &mut self,
+        game: &T,
+        depth: usize,
+        mut alpha: isize,
+        beta: isize,
+    ) -> Result<isize, GameSolveError<T>> {
+        if depth == 0 {
+            return match self.evaluator {
+                Some(evaluator) => self.quiesce(game, evaluator, self.quiescence, alpha, beta),
+                // we don't know anything about this position - treat it like a tie.
+                None => Ok(self.visit(game)?.unwrap_or(0)),
+            };
+        }
+
+        if let Some(score) = self.visit(game)? {
+            return Ok(score);
+        }
+
+        let entry = self.table.as_deref().and_then(|table| table.get(game));
+        match entry.and_then(|entry| entry.score_at(depth)) {
+            Some(Score::Exact(score)) => return Ok(score),
+            Some(Score::LowerBound(score)) if score >= beta => return Ok(beta),
+            Some(Score::UpperBound(score)) if score <= alpha => return Ok(alpha),
+            _ => (),
+        }
+
+        let original_alpha = alpha;
+
+        // a shallower search's best move is still the best guess, so it goes first
+        let mut moves = MoveBuf::<_>::with_capacity(game.move_count_hint().0);
+        moves.extend(game.possible_moves().enumerate());
+        let mut best_move = entry.and_then(|entry| entry.best_move);
+        if let Some(best) = best_move.filter(|best| *best < moves.len()) {
+            moves[..=best].rotate_right(1);
+        }
+
+        for (index, m) in moves {
+            let mut board = game.clone();
+            board
+                .make_move(&m)
+                .map_err(|err| GameSolveError::MoveError::<T>(err))?;
+
+            let score = -self.search(&board, depth - 1, -beta, -alpha)?;
+
+            if score >= beta {
+                self.store(
+                    game,
+                    DepthEntry {
+                        score: Score::LowerBound(beta),
+                        depth,
+                        best_move: Some(index),
+                    },
+                );
+
+                return Ok(beta);
+            }
+
+            if score > alpha {
+                alpha = score;
+                best_move = Some(index);
+            }
+        }
+
+        let score = if alpha > original_alpha {
+            Score::Exact(alpha)
+        } else {
+            Score::UpperBound(alpha)
+        };
+        self.store(
+            game,
+            DepthEntry {
+                score,
+                depth,
+                best_move,
+            },
+        );
+
+        Ok(alpha)
+    }
+
+    fn store(&mut self, game: &T, entry: DepthEntry) {
+        if let Some(table) = self.table.as_deref_mut() {
+            table.insert(game.clone(), entry);
+        }
+    }
+}
+
+/// Solves a game like [`solve`], but only looks `depth` moves ahead.
+///
+/// Positions that are still playable once the depth runs out are scored as ties (0).
+/// This means that non-zero scores are still exact, as the win or loss was proven within `depth` moves,
+/// while a score of 0 is either a tie or a position whose outcome is unknown.
+pub fn solve_depth_limited<T: Game<Player = impl TwoPlayer + 'static>>(
+    game: &T,
+    depth: usize,
+    cancellation_token: &Option<Arc<AtomicBool>>,
+) -> Result<isize, GameSolveError<T>> {
+    DepthLimitedSearch {
+        table: None,
+        evaluator: None,
+        quiescence: 0,
+        stats: None,
+        cancellation_token,
+    }
+    .search(game, depth, -isize::MAX, isize::MAX)
+}
+
+/// Solves a game like [`solve_depth_limited`], remembering what it found in `table`.
+///
+/// Scores in the table are only used when they were searched at least as deep as they're needed.
+/// Those searched deeper can prove a win or loss that `depth` moves don't see,
+/// so the score may be decided where [`solve_depth_limited`] scores a tie (0), but never the other way around:
+/// as without a table, non-zero scores are proven, and 0 is a tie or unknown.
+pub fn solve_depth_limited_with_table<T: Game<Player = impl TwoPlayer + 'static>>(
+    game: &T,
+    depth: usize,
+    table: &mut dyn DepthAwareTable<T>,
+    cancellation_token: &Option<Arc<AtomicBool>>,
+) -> Result<isize, GameSolveError<T>> {
+    DepthLimitedSearch {
+        table: Some(table),
+        evaluator: None,
+        quiescence: 0,
+        stats: None,
+        cancellation_token,
+    }
+    .search(game, depth, -isize::MAX, isize::MAX)
+}
+
+/// Solves a game with [`solve_depth_limited_with_table`] one move deeper at a time,
+/// up to `max_depth` moves, returning the score of the deepest search.
+///
+/// Each search orders its moves by the best moves the shallower ones stored in `table`,
+/// which makes up for most of the work they repeat.
+pub fn iterative_deepening<T: Game<Player = impl TwoPlayer + 'static>>(
+    game: &T,
+    max_depth: usize,
+    table: &mut dyn DepthAwareTable<T>,
+    cancellation_token: &Option<Arc<AtomicBool>>,
+) -> Result<isize, GameSolveError<T>> {
+    (0..=max_depth).try_fold(0, |_, depth| {
+        solve_depth_limited_with_table(game, depth, table, cancellation_token)
+    })
+}
+
+/// Searches a game `depth` moves ahead like [`solve_depth_limited_with_table`],
+/// scoring the positions it can't see the end of with `evaluator`.
+///
+/// The positions whose outcome the search sees are scored beyond [`DECISIVE_SCORE`],
+/// and [`decided_score`] turns their scores back into those of [`solve`].
+/// The table's scores depend on the evaluator, so it shouldn't be shared between evaluators.
+///
+/// Once the depth runs out, up to `quiescence` more plies of [noisy moves](Game::noisy_moves) are searched,
+/// so that a capture just past the horizon isn't missed. Each side may decline them,
+/// and no other moves are searched, so these plies cost little.
+pub fn solve_heuristic<T: Game<Player = impl TwoPlayer + 'static>>(
+    game: &T,
+    depth: usize,
+    quiescence: usize,
+    evaluator: &dyn Evaluator<T>,
+    table: &mut dyn DepthAwareTable<T>,
+    stats: Option<&Stats<T::Player>>,
+    cancellation_token: &Option<Arc<AtomicBool>>,
+) -> Result<isize, GameSolveError<T>> {
+    DepthLimitedSearch {
+        table: Some(table),
+        evaluator: Some(evaluator),
+        quiescence,
+        stats,
+        cancellation_token,
+    }
+    .search(game, depth, -isize::MAX, isize::MAX)
+}
+
+/// Counts the sequences of exactly `depth` moves that can be played from `game`,
+/// like [perft](https://www.chessprogramming.org/Perft) does for chess engines.
+///
+/// Transpositions aren't deduplicated, and sequences that end the game early aren't counted.
+/// Comparing these counts against known-good numbers validates a game's move generation.
+///
+/// # Panics
+///
+/// If the game rejects one of its own possible moves.
+pub fn perft<T: Game>(game: &T, depth: usize) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    if game.status().is_over() {
+        return 0;
+    }
+
+    game.possible_moves()
+        .map(|m| {
+            let mut board = game.clone();
+            if let Err(err) = board.make_move(&m) {
+                panic!("a possible move could not be made: {err}");
+            }
+
+            perft(&board, depth - 1)
+        })
+        .sum()
+}
+
+/// Splits [`perft`] by the first move of each sequence, which helps narrow down
+/// where move generation goes wrong.
+///
+/// # Panics
+///
+/// If the game rejects one of its own possible moves.
+pub fn perft_divide<T: Game>(game: &T, depth: usize) -> Vec<(T::Move, u64)> {
+    if depth == 0 || game.status().is_over() {
+        return Vec::new();
+    }
+
+    game.possible_moves()
+        .map(|m| {
+            let mut board = game.clone();
+            if let Err(err) = board.make_move(&m) {
+                panic!("a possible move could not be made: {err}");
+            }
+
+            let count = perft(&board, depth - 1);
+            (m, count)
+        })
+        .collect()
+}
+
 /// Utility function to get a list of the move scores of a certain game.
 /// Since its evaluating the same game, you can use the same transposition table.
 ///
@@ -293,26 +1293,211 @@ pub fn solve<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
 /// # Returns
 ///
 /// An iterator of tuples of the form `(move, score)`.
+/// A move that can't be made gives a [`GameSolveError::MoveError`] instead of a score,
+/// rather than being skipped.
+///
+/// Every score is from the point of view of the player to move in `game`, who would make the move,
+/// like the score of `game` itself: [`solve`] scores the position after the move for the opponent,
+/// which is negated here. So the best move has the highest score, which is the score of `game`.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use game_solver::{move_scores, solve};
+/// use games::takeaway::Takeaway;
+///
+/// let game = Takeaway::new(21);
+/// let mut table = HashMap::new();
+/// let scores = move_scores(&game, &mut table, None, &None)
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// // every take is scored, from 3 down to 1
+/// assert_eq!(scores.iter().map(|(take, _)| *take).collect::<Vec<_>>(), [3, 2, 1]);
+///
+/// // only taking 1 leaves a multiple of 4, which wins, and scores as much as the game
+/// let (best, score) = *scores.iter().max_by_key(|(_, score)| *score).unwrap();
+/// assert_eq!(best, 1);
+/// assert_eq!(score, solve(&game, &mut table, None, &None).unwrap());
+/// assert!(scores.iter().all(|&(take, score)| (take == 1) == (score > 0)));
+/// ```
+// the index of each move is only used to trace it
+#[cfg_attr(not(feature = "tracing"), allow(clippy::unused_enumerate_index))]
 pub fn move_scores<'a, T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
     game: &'a T,
     transposition_table: &'a mut dyn TranspositionTable<T>,
     stats: Option<&'a Stats<T::Player>>,
     cancellation_token: &'a Option<Arc<AtomicBool>>,
 ) -> impl Iterator<Item = Result<(T::Move, isize), GameSolveError<T>>> + 'a {
-    game.possible_moves().map(move |m| {
-        let mut board = game.clone();
-        board
-            .make_move(&m)
-            .map_err(|err| GameSolveError::MoveError(err))?;
-        // We flip the sign of the score because we want the score from the
-        // perspective of the player playing the move, not the player whose turn it is.
-        Ok((
-            m,
-            -solve(&board, transposition_table, stats, cancellation_token)?,
-        ))
+    game.possible_moves().enumerate().map(move |(_index, m)| {
+        let _span = trace_span!(tracing::Level::INFO, "root_move", index = _index);
+        score_move(game, m, transposition_table, stats, cancellation_token)
+    })
+}
+
+/// Like [`move_scores`], but only scores the given moves, in the given order.
+///
+/// This is much faster when only a few candidates matter.
+/// A move that can't be made gives a [`GameSolveError::MoveError`], as in [`move_scores`].
+// the index of each move is only used to trace it
+#[cfg_attr(not(feature = "tracing"), allow(clippy::unused_enumerate_index))]
+pub fn move_scores_for<'a, T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    game: &'a T,
+    transposition_table: &'a mut dyn TranspositionTable<T>,
+    moves: &'a [T::Move],
+    stats: Option<&'a Stats<T::Player>>,
+    cancellation_token: &'a Option<Arc<AtomicBool>>,
+) -> impl Iterator<Item = Result<(T::Move, isize), GameSolveError<T>>> + 'a {
+    moves.iter().enumerate().map(move |(_index, m)| {
+        let _span = trace_span!(tracing::Level::INFO, "root_move", index = _index);
+        score_move(
+            game,
+            m.clone(),
+            transposition_table,
+            stats,
+            cancellation_token,
+        )
     })
 }
 
+/// Plays `m` in `game`, and solves the position it leads to.
+fn score_move<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    game: &T,
+    m: T::Move,
+    transposition_table: &mut dyn TranspositionTable<T>,
+    stats: Option<&Stats<T::Player>>,
+    cancellation_token: &Option<Arc<AtomicBool>>,
+) -> Result<(T::Move, isize), GameSolveError<T>> {
+    let mut board = game.clone();
+    board
+        .make_move(&m)
+        .map_err(|err| GameSolveError::MoveError(err))?;
+    // We flip the sign of the score because we want the score from the
+    // perspective of the player playing the move, not the player whose turn it is.
+    Ok((
+        m,
+        -solve(&board, transposition_table, stats, cancellation_token)?,
+    ))
+}
+
+/// The moves of `game` that keep its score, in the order of [`Game::possible_moves`].
+///
+/// This is cheaper than filtering [`move_scores`]: once `game` is solved,
+/// each move only needs a null window search to tell whether it's as good as the best one,
+/// which stops as soon as it finds out it isn't.
+pub fn optimal_moves<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    game: &T,
+    transposition_table: &mut dyn TranspositionTable<T>,
+) -> Result<Vec<T::Move>, GameSolveError<T>> {
+    if game.status().is_over() {
+        return Ok(Vec::new());
+    }
+
+    let score = solve(game, transposition_table, None, &None)?;
+    let mut moves = Vec::new();
+
+    for m in game.possible_moves() {
+        let mut board = game.clone();
+        board.make_move(&m).map_err(GameSolveError::MoveError)?;
+
+        // no move scores more than `game`, so the move is optimal
+        // exactly when the opponent's score after it is at most `-score`
+        let opponent =
+            evaluate_window(&board, transposition_table, -score, -score + 1, None, &None)?;
+        if opponent.value() <= -score {
+            moves.push(m);
+        }
+    }
+
+    Ok(moves)
+}
+
+/// The line that punishes `bad_move` in `game`: the opponent's best reply to it,
+/// followed by the best moves of both sides, until the game ends or the line is `max_len` moves long.
+/// `bad_move` itself isn't part of the line.
+///
+/// Every position on the line is solved with `transposition_table`, so after scoring the moves of `game`
+/// with [`move_scores`] and the same table, this only takes a few lookups.
+pub fn refutation_line<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    game: &T,
+    bad_move: &T::Move,
+    transposition_table: &mut dyn TranspositionTable<T>,
+    max_len: usize,
+) -> Result<Vec<T::Move>, GameSolveError<T>> {
+    let mut position = game.clone();
+    position
+        .make_move(bad_move)
+        .map_err(GameSolveError::MoveError)?;
+
+    let mut line = Vec::new();
+
+    while line.len() < max_len && !position.status().is_over() {
+        let mut best: Option<(T::Move, T, isize)> = None;
+
+        for m in position.possible_moves() {
+            let mut board = position.clone();
+            board.make_move(&m).map_err(GameSolveError::MoveError)?;
+
+            let score = -solve(&board, transposition_table, None, &None)?;
+            if best.as_ref().map_or(true, |(_, _, best)| score > *best) {
+                best = Some((m, board, score));
+            }
+        }
+
+        let (m, board, _) = best.expect("a playable game has moves");
+        line.push(m);
+        position = board;
+    }
+
+    Ok(line)
+}
+
+/// Moves along with their score and principal variation, as returned by [`multi_pv`].
+pub type PrincipalVariations<T> = Vec<(<T as Game>::Move, isize, Vec<<T as Game>::Move>)>;
+
+/// The `k` best moves of `game`, best first, each with its score
+/// and its principal variation: the best moves of both sides after it, until the game ends.
+///
+/// Moves with the same score keep the order of [`Game::possible_moves`].
+/// Each move's search starts from the score of the move before it, as neighbouring moves often score alike,
+/// and the principal variations are looked up in the table those searches filled.
+pub fn multi_pv<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    game: &T,
+    transposition_table: &mut dyn TranspositionTable<T>,
+    k: usize,
+) -> Result<PrincipalVariations<T>, GameSolveError<T>> {
+    let mut scores = Vec::new();
+    let mut guess = None;
+
+    for m in game.possible_moves() {
+        let mut board = game.clone();
+        board.make_move(&m).map_err(GameSolveError::MoveError)?;
+
+        let score = -solve_with_guess(
+            &board,
+            transposition_table,
+            None,
+            &None,
+            guess,
+            &MoveOrdering::GameOrder,
+        )?;
+        guess = Some(-score);
+        scores.push((m, score));
+    }
+
+    // a stable sort, so ties stay in move order
+    scores.sort_by_key(|(_, score)| -*score);
+    scores.truncate(k);
+
+    scores
+        .into_iter()
+        .map(|(m, score)| {
+            let line = refutation_line(game, &m, transposition_table, usize::MAX)?;
+            Ok((m, score, line))
+        })
+        .collect()
+}
+
 pub type CollectedMoves<T> = Vec<Result<(<T as Game>::Move, isize), GameSolveError<T>>>;
 
 /// Parallelized version of `move_scores`. (faster by a large margin)
@@ -321,6 +1506,10 @@ pub type CollectedMoves<T> = Vec<Result<(<T as Game>::Move, isize), GameSolveErr
 ///
 /// This also allows you to pass in your own hasher, for transposition table optimization.
 ///
+/// The moves are scored on the rayon thread pool this is called in, which is the global one
+/// unless it's called inside [`rayon::ThreadPool::install`] (see [`par_move_scores_in`]).
+/// If that pool has a single thread, they're scored one after the other instead, as [`move_scores`] does.
+///
 /// # Returns
 ///
 /// A vector of tuples of the form `(move, score)`.
@@ -338,23 +1527,35 @@ where
     T::MoveError: Sync + Send,
     S: BuildHasher + Default + Sync + Send + Clone + 'static,
 {
-    use crate::transposition::TranspositionCache;
+    use crate::{
+        game::buffer_moves,
+        transposition::{Score, TranspositionCache},
+    };
     use rayon::prelude::*;
-    use std::sync::Arc;
+    use std::{collections::HashMap, sync::Arc};
+
+    // with a single thread, the moves are scored one after the other, just as `move_scores` does,
+    // so everything (down to the positions each search counts) is the same from run to run
+    if rayon::current_num_threads() == 1 {
+        let mut table = HashMap::<T, Score, S>::default();
+        return move_scores(game, &mut table, stats, cancellation_token).collect();
+    }
 
     // we need to collect it first as we cant parallelize an already non-parallel iterator
-    let all_moves = game.possible_moves().collect::<Vec<_>>();
+    let all_moves = buffer_moves(game);
     let hashmap = Arc::new(TranspositionCache::<T, S>::new());
 
     all_moves
         .par_iter()
-        .map(move |m| {
+        .enumerate()
+        .map(move |(_index, m)| {
+            let _span = trace_span!(tracing::Level::INFO, "root_move", index = _index);
             let mut board = game.clone();
             board
                 .make_move(m)
                 .map_err(|err| GameSolveError::MoveError::<T>(err))?;
             // We flip the sign of the score because we want the score from the
-            // perspective of the player pla`ying the move, not the player whose turn it is.
+            // perspective of the player playing the move, not the player whose turn it is.
             let mut map = Arc::clone(&hashmap);
             Ok((
                 (*m).clone(),
@@ -369,7 +1570,8 @@ where
 /// It uses rayon's parallel iterators to evaluate the scores of each move in parallel.
 ///
 /// By default, this uses the cryptograpphically unsecure `XxHash64` hasher.
-/// If you want to use your own hasher, use [`par_move_scores_with_hasher`].
+/// If you want to use your own hasher, use [`par_move_scores_with_hasher`],
+/// which also tells which thread pool the moves are scored on.
 ///
 /// # Returns
 ///
@@ -394,3 +1596,23 @@ where
         par_move_scores_with_hasher::<T, RandomState>(game, stats, cancellation_token)
     }
 }
+
+/// [`par_move_scores`] on the given thread pool, instead of the one it's called in,
+/// such as one the application already manages, or one with as many threads as a benchmark needs.
+///
+/// With a single thread, the moves are scored one after the other.
+#[cfg(feature = "rayon")]
+pub fn par_move_scores_in<
+    T: Game<Player = impl TwoPlayer + Sync + 'static> + Eq + Hash + Sync + Send + 'static,
+>(
+    pool: &rayon::ThreadPool,
+    game: &T,
+    stats: Option<&Stats<T::Player>>,
+    cancellation_token: &Option<Arc<AtomicBool>>,
+) -> CollectedMoves<T>
+where
+    T::Move: Sync + Send,
+    T::MoveError: Sync + Send,
+{
+    pool.install(|| par_move_scores(game, stats, cancellation_token))
+}
@@ -0,0 +1,172 @@
+//! [Monte Carlo tree search](https://en.wikipedia.org/wiki/Monte_Carlo_tree_search),
+//! which estimates how good a position is by playing random games from it.
+//! This requires the `rand` feature to be enabled.
+//!
+//! It never proves anything, but it doesn't need to search the whole game tree either,
+//! so it can still pick good moves in games too large to solve.
+
+use std::sync::{atomic::AtomicBool, Arc};
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    game::{collect_moves, Game, GameState},
+    strategy::{check_cancelled, Evaluation, Outcome, Strategy, Value},
+    GameSolveError,
+};
+
+struct Node<T: Game> {
+    game: T,
+    /// The move that led here from the parent, which the root doesn't have.
+    m: Option<T::Move>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    /// The moves that don't have a child yet.
+    untried: Vec<T::Move>,
+    visits: u64,
+    /// The sum of the results of the playouts through this node,
+    /// for the player who moved into it: 1 for a win, 0 for a tie, and -1 for a loss.
+    total: f64,
+}
+
+impl<T: Game> Node<T> {
+    fn new(game: T, m: Option<T::Move>, parent: Option<usize>) -> Self {
+        let untried = if game.state() == GameState::Playable {
+            collect_moves(&game)
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            game,
+            m,
+            parent,
+            children: Vec::new(),
+            untried,
+            visits: 0,
+            total: 0.0,
+        }
+    }
+
+    fn mean(&self) -> f64 {
+        self.total / self.visits as f64
+    }
+}
+
+/// Estimates positions with Monte Carlo tree search,
+/// choosing which moves to explore with [UCT](https://www.chessprogramming.org/UCT).
+pub struct Mcts<R> {
+    /// How many random games are played for each evaluation.
+    pub iterations: u64,
+    /// How much unexplored moves are favored over moves that have done well so far.
+    pub exploration: f64,
+    pub rng: R,
+    pub cancellation_token: Option<Arc<AtomicBool>>,
+}
+
+impl<R: Rng> Mcts<R> {
+    /// Plays 10,000 random games per evaluation, with the usual exploration constant of √2.
+    pub fn new(rng: R) -> Self {
+        Self {
+            iterations: 10_000,
+            exploration: std::f64::consts::SQRT_2,
+            rng,
+            cancellation_token: None,
+        }
+    }
+
+    /// Plays random moves from `game` until it ends, returning the result
+    /// for the player to move in `game`.
+    fn playout<T: Game>(&mut self, game: &T) -> Result<f64, GameSolveError<T>> {
+        let mut game = game.clone();
+        let mut flipped = false;
+
+        while game.state() == GameState::Playable {
+            let moves = collect_moves(&game);
+            let Some(m) = moves.choose(&mut self.rng) else {
+                break;
+            };
+
+            game.make_move(m).map_err(GameSolveError::MoveError)?;
+            flipped = !flipped;
+        }
+
+        let result = match Outcome::of_state(&game) {
+            Some(Outcome::Win) => 1.0,
+            Some(Outcome::Loss) => -1.0,
+            Some(Outcome::Tie) | None => 0.0,
+        };
+
+        Ok(if flipped { -result } else { result })
+    }
+}
+
+impl<T: Game, R: Rng> Strategy<T> for Mcts<R> {
+    fn evaluate(&mut self, game: &T) -> Result<Evaluation<T::Move>, GameSolveError<T>> {
+        if let Some(outcome) = Outcome::of_state(game) {
+            return Ok(Evaluation {
+                value: Value::Proven(outcome),
+                best_move: None,
+                nodes: 1,
+            });
+        }
+
+        let mut nodes = vec![Node::new(game.clone(), None, None)];
+
+        for _ in 0..self.iterations {
+            check_cancelled(&self.cancellation_token)?;
+
+            // select the most promising node that still has moves to try
+            let mut index = 0;
+            while nodes[index].untried.is_empty() && !nodes[index].children.is_empty() {
+                let parent_visits = (nodes[index].visits as f64).ln();
+                let uct = |child: &Node<T>| {
+                    child.mean() + self.exploration * (parent_visits / child.visits as f64).sqrt()
+                };
+
+                index = nodes[index]
+                    .children
+                    .iter()
+                    .copied()
+                    .max_by(|&a, &b| uct(&nodes[a]).total_cmp(&uct(&nodes[b])))
+                    .expect("the node has children");
+            }
+
+            // try one of its moves
+            if !nodes[index].untried.is_empty() {
+                let untried = &mut nodes[index].untried;
+                let m = untried.swap_remove(self.rng.gen_range(0..untried.len()));
+
+                let mut board = nodes[index].game.clone();
+                board.make_move(&m).map_err(GameSolveError::MoveError)?;
+
+                nodes.push(Node::new(board, Some(m), Some(index)));
+                let child = nodes.len() - 1;
+                nodes[index].children.push(child);
+                index = child;
+            }
+
+            // the result is for the player to move, so it's negated for the player who moved here
+            let mut result = -self.playout(&nodes[index].game)?;
+            let mut ancestor = Some(index);
+            while let Some(index) = ancestor {
+                nodes[index].visits += 1;
+                nodes[index].total += result;
+                result = -result;
+                ancestor = nodes[index].parent;
+            }
+        }
+
+        let best = nodes[0]
+            .children
+            .iter()
+            .max_by_key(|&&child| nodes[child].visits)
+            .map(|&child| &nodes[child]);
+
+        Ok(Evaluation {
+            value: Value::Estimate(best.map_or(0.0, Node::mean)),
+            best_move: best.and_then(|node| node.m.clone()),
+            nodes: nodes.len() as u64,
+        })
+    }
+}
@@ -0,0 +1,194 @@
+//! Nimbers, the values of impartial games under normal play.
+//!
+//! By the Sprague-Grundy theorem, every such game is equivalent to a Nim heap `*n`,
+//! and the value of a sum of games is the nim-sum of their values.
+
+use alloc::{vec, vec::Vec};
+use core::{
+    fmt,
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Mul, MulAssign},
+};
+#[cfg(feature = "std")]
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+#[cfg(feature = "std")]
+use crate::{game::Game, stats::Stats, strategy::check_cancelled, GameSolveError};
+
+/// The nimber `*n`, the value of a Nim heap of `n`.
+///
+/// Nimbers form a field: addition is the xor of their binary forms (so every nimber is its own negative),
+/// and [`Mul`] is nim-multiplication, under which the nimbers below every `2^2^k` are closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Nimber(pub usize);
+
+impl Nimber {
+    pub const ZERO: Self = Self(0);
+    /// `*`, the nimber of a single move.
+    pub const STAR: Self = Self(1);
+
+    /// The minimum excludant of `nimbers`: the smallest nimber that isn't one of them,
+    /// which is the value of a game whose options have those values.
+    pub fn mex(nimbers: impl IntoIterator<Item = Nimber>) -> Self {
+        let nimbers = nimbers.into_iter().collect::<Vec<_>>();
+
+        // the mex of n nimbers is at most n, so larger ones can't be it
+        let mut seen = vec![false; nimbers.len() + 1];
+        for Nimber(n) in nimbers {
+            if let Some(seen) = seen.get_mut(n) {
+                *seen = true;
+            }
+        }
+
+        Self(
+            seen.iter()
+                .position(|&seen| !seen)
+                .expect("n nimbers can't cover n + 1 values"),
+        )
+    }
+}
+
+/// The nim-product of `a` and `b`, which are below `2^bits`, where `bits` is a power of two.
+///
+/// With `F = 2^(bits / 2)`, a Fermat 2-power, `a = a1 F + a0` and `b = b1 F + b0`
+/// (where nim-products with `F` are ordinary products, and `F ⊗ F = F + F / 2`), so
+/// `a ⊗ b = (a1 b1 + a1 b0 + a0 b1) F + a0 b0 + a1 b1 ⊗ F / 2`.
+fn nim_product(a: usize, b: usize, bits: u32) -> usize {
+    if bits == 1 {
+        return a & b;
+    }
+
+    let half = bits / 2;
+    let mask = (1 << half) - 1;
+    let (a1, a0) = (a >> half, a & mask);
+    let (b1, b0) = (b >> half, b & mask);
+
+    let high = nim_product(a1, b1, half);
+    let low = nim_product(a0, b0, half);
+    // a1 b1 + a1 b0 + a0 b1, from a single product of the halves' sums
+    let middle = nim_product(a1 ^ a0, b1 ^ b0, half) ^ low;
+
+    (middle << half) | (low ^ nim_product(high, 1 << (half - 1), half))
+}
+
+impl Add for Nimber {
+    type Output = Self;
+
+    /// The nim-sum, the value of playing both games side by side.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn add(self, other: Self) -> Self {
+        Self(self.0 ^ other.0)
+    }
+}
+
+impl AddAssign for Nimber {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Mul for Nimber {
+    type Output = Self;
+
+    /// The nim-product, as in Conway's On Numbers and Games.
+    fn mul(self, other: Self) -> Self {
+        // the smallest field both are in
+        let bits = usize::BITS - (self.0 | other.0).leading_zeros();
+
+        Self(nim_product(self.0, other.0, bits.next_power_of_two()))
+    }
+}
+
+impl MulAssign for Nimber {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl Sum for Nimber {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl Product for Nimber {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::STAR, Mul::mul)
+    }
+}
+
+impl From<usize> for Nimber {
+    fn from(n: usize) -> Self {
+        Self(n)
+    }
+}
+
+impl From<Nimber> for usize {
+    fn from(nimber: Nimber) -> Self {
+        nimber.0
+    }
+}
+
+/// Written as `0`, `*`, or `*n`.
+impl fmt::Display for Nimber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            0 => write!(f, "0"),
+            1 => write!(f, "*"),
+            n => write!(f, "*{n}"),
+        }
+    }
+}
+
+/// The Grundy value of `game`, an impartial game under normal play (see [`Game::IMPARTIAL`]):
+/// the nimber it's equivalent to, which is the mex of those of its options.
+///
+/// The player to move wins if and only if it isn't 0.
+/// `known` remembers the value of every position it reaches, to be used again.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use game_solver::nimber::{grundy_value, Nimber};
+/// use games::naive_nim::Nim;
+///
+/// // a Nim heap of n is *n, and a sum of them is the nim-sum of the heaps
+/// let value = grundy_value(&Nim::new(vec![3, 5, 7]), &mut HashMap::new(), None, &None).unwrap();
+/// assert_eq!(value, Nimber(3 ^ 5 ^ 7));
+/// ```
+#[cfg(feature = "std")]
+pub fn grundy_value<T: Game + Eq + Hash>(
+    game: &T,
+    known: &mut HashMap<T, Nimber>,
+    stats: Option<&Stats<T::Player>>,
+    cancellation_token: &Option<Arc<AtomicBool>>,
+) -> Result<Nimber, GameSolveError<T>> {
+    if let Some(&value) = known.get(game) {
+        return Ok(value);
+    }
+
+    check_cancelled(cancellation_token)?;
+    if let Some(stats) = stats {
+        stats.states_explored.fetch_add(1, Ordering::Relaxed);
+    }
+
+    let mut options = Vec::new();
+    for m in game.possible_moves() {
+        let mut board = game.clone();
+        board.make_move(&m).map_err(GameSolveError::MoveError)?;
+
+        options.push(grundy_value(&board, known, stats, cancellation_token)?);
+    }
+
+    let value = Nimber::mex(options);
+    known.insert(game.clone(), value);
+
+    Ok(value)
+}
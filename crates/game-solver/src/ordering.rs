@@ -0,0 +1,145 @@
+//! The order the solver searches moves in.
+//!
+//! [`Game::possible_moves`] is expected to guess good moves first, but it can only do so from the position.
+//! A [`MoveOrdering`], given to [`solve_ordered`](crate::solve_ordered), reorders the moves at every node,
+//! from what the search has stored so far or from a heuristic, without changing the scores it finds.
+
+use alloc::{boxed::Box, vec::Vec};
+use core::{cmp::Reverse, hash::Hash};
+
+use crate::{
+    game::{buffer_moves, collect_moves, Game, MoveBuf},
+    transposition::TranspositionTable,
+};
+
+/// Values a move of a position, so that moves with higher values are searched first.
+pub type Heuristic<T> = Box<dyn Fn(&T, &<T as Game>::Move) -> isize>;
+
+/// How to order the moves of a position before searching them.
+///
+/// Every ordering is stable: moves it can't tell apart stay in the order of [`Game::possible_moves`].
+pub enum MoveOrdering<T: Game> {
+    /// The order of [`Game::possible_moves`], which doesn't need to collect the moves first.
+    GameOrder,
+    /// Moves leading to positions in the transposition table first, the best for the player to move first.
+    TtFirst,
+    /// Moves with the highest heuristic value first.
+    Heuristic(Heuristic<T>),
+    /// Orders by the first ordering, breaking its ties with the next one, and so on.
+    Chain(Vec<MoveOrdering<T>>),
+}
+
+impl<T: Game> Default for MoveOrdering<T> {
+    fn default() -> Self {
+        Self::GameOrder
+    }
+}
+
+impl<T: Game + Eq + Hash> MoveOrdering<T> {
+    /// Whether this keeps the order of [`Game::possible_moves`], so the moves needn't be collected.
+    pub(crate) fn is_game_order(&self) -> bool {
+        match self {
+            Self::GameOrder => true,
+            Self::Chain(orderings) => orderings.iter().all(Self::is_game_order),
+            Self::TtFirst | Self::Heuristic(_) => false,
+        }
+    }
+
+    /// The moves of `game`, in this order.
+    pub fn order(&self, game: &T, table: &dyn TranspositionTable<T>) -> Vec<T::Move> {
+        let mut moves = collect_moves(game);
+        self.sort(game, table, &mut moves);
+        moves
+    }
+
+    /// [`order`](Self::order), into a buffer that the solver doesn't have to allocate for a few moves.
+    pub(crate) fn order_buffered(
+        &self,
+        game: &T,
+        table: &dyn TranspositionTable<T>,
+    ) -> MoveBuf<T::Move> {
+        let mut moves = buffer_moves(game);
+        self.sort(game, table, &mut moves);
+        moves
+    }
+
+    fn sort(&self, game: &T, table: &dyn TranspositionTable<T>, moves: &mut [T::Move]) {
+        match self {
+            Self::GameOrder => (),
+            Self::TtFirst => sort_by_cached_key(moves, |m| {
+                let mut board = game.clone();
+                // an impossible move is reported when it's searched
+                if board.make_move(m).is_err() {
+                    return Reverse(None);
+                }
+
+                // the table holds the opponent's score after the move
+                Reverse(table.get(&board).map(|score| -score.value()))
+            }),
+            Self::Heuristic(heuristic) => {
+                sort_by_cached_key(moves, |m| Reverse(heuristic(game, m)))
+            }
+            // sorting by the least important ordering first leaves the most important one in charge,
+            // as every sort is stable
+            Self::Chain(orderings) => {
+                for ordering in orderings.iter().rev() {
+                    ordering.sort(game, table, moves);
+                }
+            }
+        }
+    }
+}
+
+/// Sorts `moves` by `key` like [`slice::sort_by_cached_key`], which is stable and computes each key once,
+/// but keeps the keys in a [`MoveBuf`], so a few moves are sorted without allocating.
+fn sort_by_cached_key<M, K: Ord>(moves: &mut [M], mut key: impl FnMut(&M) -> K) {
+    // the indices keep moves with equal keys in order, even with an unstable sort
+    let mut keys = moves
+        .iter()
+        .enumerate()
+        .map(|(index, m)| (key(m), index))
+        .collect::<MoveBuf<_>>();
+    keys.sort_unstable();
+
+    // put every move where its key ended up, following the moves already swapped away
+    for i in 0..moves.len() {
+        let mut index = keys[i].1;
+        while index < i {
+            index = keys[index].1;
+        }
+        keys[i].1 = index;
+        moves.swap(i, index);
+    }
+}
+
+/// A game played by placing pieces on the cells of a grid, such as Tic-Tac-Toe.
+pub trait PlacementGame: Game {
+    /// The length of the grid along each of its dimensions.
+    /// A two-dimensional grid is measured in rows, then columns.
+    fn grid_size(&self) -> Vec<usize>;
+
+    /// The coordinates of the cell `m` places a piece on.
+    fn cell(&self, m: &Self::Move) -> Vec<usize>;
+}
+
+impl<T: PlacementGame + 'static> MoveOrdering<T> {
+    /// Prefers cells close to the center of the grid, which take part in the most lines.
+    pub fn central_cells_first() -> Self {
+        Self::Heuristic(Box::new(|game: &T, m: &T::Move| {
+            let size = game.grid_size();
+
+            // the squared distance to the center, doubled along each dimension to stay whole
+            let distance = game
+                .cell(m)
+                .iter()
+                .zip(&size)
+                .map(|(&coordinate, &length)| {
+                    let offset = 2 * coordinate as isize - (length as isize - 1);
+                    offset * offset
+                })
+                .sum::<isize>();
+
+            -distance
+        }))
+    }
+}
@@ -1,3 +1,8 @@
+use core::{
+    fmt::{self, Debug, Display},
+    hash::Hash,
+};
+
 /// Represents a player.
 pub trait Player: Sized + Eq {
     /// The max player count.
@@ -25,12 +30,19 @@ pub trait Player: Sized + Eq {
 /// Represents a two player player.
 ///
 /// This player should always be representable by a byte.
-pub trait TwoPlayer: Player + Copy {
+/// It can be copied, hashed, and printed, so code written for any two player game can do so
+/// without a helper of its own; with the `serde` feature, both kinds are (de)serialized by name too.
+pub trait TwoPlayer: Player + Copy + Hash + Debug + Display {
     /// Gets the other player
     #[must_use]
     fn other(self) -> Self {
         self.next()
     }
+
+    /// Whether this is the player the values of positions are measured for,
+    /// who is better off the greater they are: Left, as in combinatorial game theory,
+    /// or Next, the player to move in an impartial game.
+    fn is_maximizing(self) -> bool;
 }
 
 /// Represents a player in a zero-sum (2-player) game,
@@ -38,6 +50,7 @@ pub trait TwoPlayer: Player + Copy {
 /// a player can affect the `Game::possible_moves` function,
 /// or players have different winning outcomes.
 #[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PartizanPlayer {
     /// The first player.
     Left,
@@ -73,7 +86,37 @@ impl Player for PartizanPlayer {
     }
 }
 
-impl TwoPlayer for PartizanPlayer {}
+impl PartizanPlayer {
+    /// The player to move after `move_count` moves, when Left moves first.
+    ///
+    /// ```
+    /// use game_solver::player::{PartizanPlayer, TwoPlayer};
+    ///
+    /// assert_eq!(PartizanPlayer::from_move_count(4), PartizanPlayer::Left);
+    /// assert!(!PartizanPlayer::from_move_count(5).is_maximizing());
+    /// assert_eq!(PartizanPlayer::from_move_count(5).to_string(), "Right");
+    /// ```
+    pub fn from_move_count(move_count: usize) -> Self {
+        if move_count % 2 == 0 {
+            Self::Left
+        } else {
+            Self::Right
+        }
+    }
+}
+
+impl TwoPlayer for PartizanPlayer {
+    fn is_maximizing(self) -> bool {
+        self == Self::Left
+    }
+}
+
+/// Written as `Left` or `Right`.
+impl Display for PartizanPlayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
 
 /// Represents a player in a zero-sum (2-player) game,
 /// where the game is impartial. That is,
@@ -81,6 +124,7 @@ impl TwoPlayer for PartizanPlayer {}
 /// and the only thing that defines a game is its 'state':
 /// both players need to have the same winning criteria for this player to be used.
 #[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ImpartialPlayer {
     /// The player that will play on the current game state,
     Next,
@@ -90,6 +134,34 @@ pub enum ImpartialPlayer {
 }
 
 impl ImpartialPlayer {
+    /// Names `player` relative to a position where `to_move` is the player to move,
+    /// such as a player of a partizan game, or an impartial one named relative to another position.
+    ///
+    /// ```
+    /// use game_solver::player::{ImpartialPlayer, PartizanPlayer};
+    ///
+    /// let to_move = PartizanPlayer::Right;
+    /// assert_eq!(ImpartialPlayer::of(PartizanPlayer::Right, to_move), ImpartialPlayer::Next);
+    /// assert_eq!(ImpartialPlayer::of(PartizanPlayer::Left, to_move), ImpartialPlayer::Previous);
+    /// assert_eq!(ImpartialPlayer::Previous.to_partizan(to_move), PartizanPlayer::Left);
+    /// ```
+    pub fn of<P: TwoPlayer>(player: P, to_move: P) -> Self {
+        if player == to_move {
+            Self::Next
+        } else {
+            Self::Previous
+        }
+    }
+
+    /// The partizan player this is in a position where `to_move` is the player to move,
+    /// the inverse of [`of`](Self::of).
+    pub fn to_partizan(self, to_move: PartizanPlayer) -> PartizanPlayer {
+        match self {
+            Self::Next => to_move,
+            Self::Previous => to_move.other(),
+        }
+    }
+
     pub fn from_move_count(initial_move_count: usize, final_move_count: usize) -> ImpartialPlayer {
         if (final_move_count - initial_move_count) % 2 == 0 {
             ImpartialPlayer::Next
@@ -127,7 +199,18 @@ impl Player for ImpartialPlayer {
     }
 }
 
-impl TwoPlayer for ImpartialPlayer {}
+impl TwoPlayer for ImpartialPlayer {
+    fn is_maximizing(self) -> bool {
+        self == Self::Next
+    }
+}
+
+/// Written as `Next` or `Previous`.
+impl Display for ImpartialPlayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Debug::fmt(self, f)
+    }
+}
 
 /// Represents a player in an N-player game.
 #[derive(PartialEq, Eq, PartialOrd, Ord)]
@@ -0,0 +1,161 @@
+//! Fixed opponents, and how well perfect play does against them.
+//!
+//! A [`Policy`] always answers a position with the same move, so the best response to it
+//! only branches on the optimizing player's moves, and [`best_response_value`] can find it exactly.
+//! How much more it scores than [`solve`](crate::solve) is how much the policy gives away.
+
+use core::hash::Hash;
+
+use crate::{
+    game::Game,
+    terminal_score,
+    transposition::{Score, TranspositionTable},
+    GameSolveError,
+};
+
+/// A fixed way of choosing moves, such as a heuristic bot.
+pub trait Policy<T: Game> {
+    /// The move to play in `game`, which is still playable.
+    fn choose(&self, game: &T) -> T::Move;
+}
+
+/// A heuristic score of a position, for the player to move: the higher, the better.
+pub trait Evaluator<T: Game> {
+    fn evaluate(&self, game: &T) -> isize;
+}
+
+impl<T: Game, F: Fn(&T) -> isize> Evaluator<T> for F {
+    fn evaluate(&self, game: &T) -> isize {
+        self(game)
+    }
+}
+
+/// Scores a position by how many moves the player to move has.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Mobility;
+
+impl<T: Game> Evaluator<T> for Mobility {
+    fn evaluate(&self, game: &T) -> isize {
+        game.possible_moves().count() as isize
+    }
+}
+
+/// Plays the move that leaves the opponent with the worst evaluation,
+/// keeping the first of equally good moves.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GreedyPolicy<E>(pub E);
+
+impl<T: Game, E: Evaluator<T>> Policy<T> for GreedyPolicy<E> {
+    fn choose(&self, game: &T) -> T::Move {
+        let mut best: Option<(T::Move, isize)> = None;
+
+        for m in game.possible_moves() {
+            let mut board = game.clone();
+            if board.make_move(&m).is_err() {
+                continue;
+            }
+
+            let score = -self.0.evaluate(&board);
+            if best.as_ref().map_or(true, |(_, best)| score > *best) {
+                best = Some((m, score));
+            }
+        }
+
+        best.expect("the policy was asked to move in a finished game")
+            .0
+    }
+}
+
+/// Plays a random move, picked from the position and the seed.
+///
+/// The same position always gets the same move, as a [`Policy`] has to be fixed,
+/// but the moves of different seeds are unrelated.
+/// This requires the `std` feature to be enabled.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RandomPolicy {
+    pub seed: u64,
+}
+
+#[cfg(feature = "std")]
+impl RandomPolicy {
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Game + Hash> Policy<T> for RandomPolicy {
+    fn choose(&self, game: &T) -> T::Move {
+        use std::hash::{DefaultHasher, Hasher};
+
+        let mut hasher = DefaultHasher::new();
+        hasher.write_u64(self.seed);
+        game.hash(&mut hasher);
+
+        let moves = game.possible_moves().collect::<alloc::vec::Vec<_>>();
+        assert!(
+            !moves.is_empty(),
+            "the policy was asked to move in a finished game"
+        );
+
+        moves[(hasher.finish() % moves.len() as u64) as usize].clone()
+    }
+}
+
+/// Finds the score that the player to move in `game` reaches by playing perfectly
+/// against `policy`, which plays every move of the other player.
+///
+/// Scores are measured like those of [`solve`](crate::solve), so they're at least its score,
+/// and at most `upper_bound(game) - game.move_count()` (winning with the next move).
+///
+/// `table` stores exact scores against `policy`, which are different from the scores `solve` stores,
+/// so it shouldn't be shared with `solve`, or with other policies.
+pub fn best_response_value<T: Game + Eq + Hash>(
+    game: &T,
+    policy: &impl Policy<T>,
+    table: &mut dyn TranspositionTable<T>,
+) -> Result<isize, GameSolveError<T>> {
+    best_response(game, policy, table, true)
+}
+
+/// The score of `game` for the optimizing player, who is to move if `optimizer` is true.
+fn best_response<T: Game + Eq + Hash>(
+    game: &T,
+    policy: &impl Policy<T>,
+    table: &mut dyn TranspositionTable<T>,
+    optimizer: bool,
+) -> Result<isize, GameSolveError<T>> {
+    let score = match terminal_score(game)? {
+        Some(score) => return Ok(if optimizer { score } else { -score }),
+        None => {
+            if let Some(Score::Exact(score)) = table.get(game) {
+                return Ok(score);
+            }
+
+            if optimizer {
+                let mut best = None;
+                for m in game.possible_moves() {
+                    let mut board = game.clone();
+                    board.make_move(&m).map_err(GameSolveError::MoveError)?;
+
+                    let score = best_response(&board, policy, table, false)?;
+                    best = Some(best.map_or(score, |best: isize| best.max(score)));
+                }
+
+                best.expect("a playable game has moves")
+            } else {
+                let mut board = game.clone();
+                board
+                    .make_move(&policy.choose(game))
+                    .map_err(GameSolveError::MoveError)?;
+
+                best_response(&board, policy, table, true)?
+            }
+        }
+    };
+
+    table.insert(game.clone(), Score::Exact(score));
+
+    Ok(score)
+}
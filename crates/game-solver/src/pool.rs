@@ -0,0 +1,38 @@
+//! Reuses the boards the search copies positions into, so that a board holding its position
+//! on the heap doesn't allocate a new one for every position searched.
+
+use alloc::vec::Vec;
+
+/// The boards a search has finished with, one for each ply.
+///
+/// A position `ply` moves into the game copies its moves' positions into the board of that ply,
+/// which none of the positions searched after it (deeper) use, with [`Clone::clone_from`]:
+/// a board whose `clone_from` reuses its allocations then only allocates the first time a ply is reached.
+pub(crate) struct BoardPool<T> {
+    boards: Vec<Option<T>>,
+}
+
+impl<T: Clone> BoardPool<T> {
+    pub(crate) fn new() -> Self {
+        Self { boards: Vec::new() }
+    }
+
+    /// A copy of `game`, in the board of `ply` if it's been given back.
+    pub(crate) fn take(&mut self, ply: usize, game: &T) -> T {
+        match self.boards.get_mut(ply).and_then(Option::take) {
+            Some(mut board) => {
+                board.clone_from(game);
+                board
+            }
+            None => game.clone(),
+        }
+    }
+
+    /// Gives `board` back to be reused at `ply`.
+    pub(crate) fn give(&mut self, ply: usize, board: T) {
+        if self.boards.len() <= ply {
+            self.boards.resize_with(ply + 1, || None);
+        }
+        self.boards[ply] = Some(board);
+    }
+}
@@ -0,0 +1,89 @@
+//! What writing a game, and solving it, usually takes, in one import.
+//!
+//! It has the [`Game`] trait and the players, the solver's entry points with their error,
+//! the transposition tables, and the outcomes searches find. Each of them still lives in (and is documented in)
+//! its own module, which is the place to look for the rest of that module.
+//! [`Normal`](crate::game::Normal) and the other helpers for implementing [`Game::status`] are left out,
+//! as calling `status` with both traits in scope is ambiguous, and so is the
+//! [`TranspositionTable`](crate::transposition::TranspositionTable) trait, whose `entry` would be called
+//! instead of that of a `HashMap`. Any `HashMap` from positions to [`Score`]s is a table without it.
+//!
+//! ```
+//! use std::{collections::HashMap, convert::Infallible};
+//!
+//! use game_solver::prelude::*;
+//!
+//! /// A pile of stones, which the players take 1 or 2 from in turn. Whoever takes the last one wins.
+//! #[derive(Clone, Hash, PartialEq, Eq, Debug)]
+//! struct Pile {
+//!     stones: usize,
+//!     taken: usize,
+//! }
+//!
+//! impl Game for Pile {
+//!     type Move = usize;
+//!     type Iter<'a> = std::ops::RangeInclusive<usize>;
+//!     type MoveError = Infallible;
+//!     type Player = ImpartialPlayer;
+//!
+//!     fn move_count(&self) -> usize {
+//!         self.taken
+//!     }
+//!
+//!     fn max_moves(&self) -> Option<usize> {
+//!         Some(self.stones + self.taken)
+//!     }
+//!
+//!     fn make_move(&mut self, m: &usize) -> Result<(), Infallible> {
+//!         self.stones -= m;
+//!         self.taken += 1;
+//!         Ok(())
+//!     }
+//!
+//!     fn possible_moves(&self) -> Self::Iter<'_> {
+//!         1..=self.stones.min(2)
+//!     }
+//!
+//!     fn status(&self) -> GameStatus {
+//!         // whoever took the last stone won, so the player to move lost
+//!         if self.stones == 0 {
+//!             GameStatus::lost(self)
+//!         } else {
+//!             GameStatus::InProgress
+//!         }
+//!     }
+//!
+//!     fn player(&self) -> ImpartialPlayer {
+//!         ImpartialPlayer::Next
+//!     }
+//! }
+//!
+//! // piles of a multiple of 3 are lost: whatever is taken, the other player takes the rest of the 3
+//! let pile = Pile { stones: 7, taken: 0 };
+//! assert_eq!(solve_outcome(&pile, &mut HashMap::new(), None, &None).unwrap(), Outcome::Win);
+//! assert!(solve(&pile, &mut HashMap::new(), None, &None).unwrap() > 0);
+//!
+//! let scores = move_scores(&pile, &mut HashMap::new(), None, &None)
+//!     .collect::<Result<Vec<_>, GameSolveError<Pile>>>()
+//!     .unwrap();
+//! assert_eq!(scores.iter().filter(|(_, score)| *score > 0).count(), 1);
+//! ```
+
+pub use crate::{
+    evaluate_for, evaluate_for_mover,
+    game::{score_to_outcome, Game, GameScoreOutcome, GameState, GameStatus},
+    move_scores, move_scores_for, optimal_moves,
+    ordering::MoveOrdering,
+    player::{ImpartialPlayer, PartizanPlayer, Player, TwoPlayer},
+    solve, solve_ordered, solve_outcome,
+    stats::Stats,
+    strategy::Outcome,
+    transposition::Score,
+    CollectedMoves, GameSolveError,
+};
+
+#[cfg(feature = "std")]
+pub use crate::transposition::CompactTable;
+
+#[cfg(feature = "rayon")]
+pub use crate::{par_move_scores, transposition::TranspositionCache};
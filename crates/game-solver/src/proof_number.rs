@@ -0,0 +1,241 @@
+//! [Proof-number search](https://www.chessprogramming.org/Proof-Number_Search),
+//! a best-first search that proves the outcome of a position without scoring it.
+//!
+//! It grows the game tree towards the positions that take the fewest others to settle,
+//! which often proves an outcome with far fewer nodes than alpha-beta,
+//! but keeps the whole tree it searched in memory.
+
+use alloc::{sync::Arc, vec::Vec};
+use core::sync::atomic::AtomicBool;
+
+use crate::{
+    game::Game,
+    strategy::{check_cancelled, Evaluation, Outcome, Strategy, Value},
+    GameSolveError,
+};
+
+/// A proof or disproof number of a position that can't be proven (or disproven).
+const INFINITY: u64 = u64::MAX;
+
+fn saturating_sum(numbers: impl Iterator<Item = u64>) -> u64 {
+    numbers.fold(0, u64::saturating_add)
+}
+
+struct Node<T: Game> {
+    game: T,
+    /// The move that led here from the parent, which the root doesn't have.
+    m: Option<T::Move>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    /// Whether the player to move at the root is to move here.
+    attacker: bool,
+    proof: u64,
+    disproof: u64,
+}
+
+/// Proves outcomes with proof-number search.
+///
+/// A position is searched once to prove a win, and if that fails,
+/// again to prove that it isn't lost.
+#[derive(Default)]
+pub struct ProofNumber {
+    pub cancellation_token: Option<Arc<AtomicBool>>,
+}
+
+impl ProofNumber {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// What one proof-number search found.
+struct SearchResult<M> {
+    proven: bool,
+    /// The move that proves the root, if it was proven.
+    best_move: Option<M>,
+    nodes: u64,
+}
+
+/// One proof-number search, trying to prove that the player to move at the root
+/// reaches at least `target`.
+struct Search<'a, T: Game> {
+    nodes: Vec<Node<T>>,
+    target: Outcome,
+    cancellation_token: &'a Option<Arc<AtomicBool>>,
+}
+
+impl<T: Game> Search<'_, T> {
+    /// Sets the proof and disproof numbers of a position that hasn't been expanded.
+    fn initialize(&mut self, index: usize) {
+        let node = &mut self.nodes[index];
+
+        (node.proof, node.disproof) = match Outcome::of_state(&node.game) {
+            None => (1, 1),
+            Some(outcome) => {
+                let outcome = if node.attacker {
+                    outcome
+                } else {
+                    outcome.flip()
+                };
+                if outcome >= self.target {
+                    (0, INFINITY)
+                } else {
+                    (INFINITY, 0)
+                }
+            }
+        };
+    }
+
+    /// Adds every position reachable in one move from `index` to the tree.
+    fn expand(&mut self, index: usize) -> Result<(), GameSolveError<T>> {
+        let moves = self.nodes[index].game.possible_moves().collect::<Vec<_>>();
+
+        for m in moves {
+            let mut game = self.nodes[index].game.clone();
+            game.make_move(&m).map_err(GameSolveError::MoveError)?;
+
+            let child = self.nodes.len();
+            self.nodes.push(Node {
+                game,
+                m: Some(m),
+                parent: Some(index),
+                children: Vec::new(),
+                attacker: !self.nodes[index].attacker,
+                proof: 1,
+                disproof: 1,
+            });
+            self.initialize(child);
+            self.nodes[index].children.push(child);
+        }
+
+        Ok(())
+    }
+
+    /// Recomputes the proof and disproof numbers of `index` from its children.
+    fn update(&mut self, index: usize) {
+        let node = &self.nodes[index];
+        let proofs = node.children.iter().map(|&child| self.nodes[child].proof);
+        let disproofs = node
+            .children
+            .iter()
+            .map(|&child| self.nodes[child].disproof);
+
+        let (proof, disproof) = if node.attacker {
+            // the attacker needs one move that proves the target
+            (proofs.min().unwrap_or(INFINITY), saturating_sum(disproofs))
+        } else {
+            // the defender needs one move that disproves it
+            (saturating_sum(proofs), disproofs.min().unwrap_or(INFINITY))
+        };
+
+        let node = &mut self.nodes[index];
+        (node.proof, node.disproof) = (proof, disproof);
+    }
+
+    /// Finds the leaf that proves or disproves the root with the least effort.
+    fn most_proving(&self) -> usize {
+        let mut index = 0;
+
+        while !self.nodes[index].children.is_empty() {
+            let node = &self.nodes[index];
+            let children = node.children.iter().copied();
+
+            index = if node.attacker {
+                children.min_by_key(|&child| self.nodes[child].proof)
+            } else {
+                children.min_by_key(|&child| self.nodes[child].disproof)
+            }
+            .expect("the node has children");
+        }
+
+        index
+    }
+
+    /// Searches until the root is proven or disproven.
+    fn run(mut self) -> Result<SearchResult<T::Move>, GameSolveError<T>> {
+        self.initialize(0);
+
+        while self.nodes[0].proof != 0 && self.nodes[0].disproof != 0 {
+            check_cancelled(self.cancellation_token)?;
+
+            let leaf = self.most_proving();
+            self.expand(leaf)?;
+
+            let mut index = Some(leaf);
+            while let Some(ancestor) = index {
+                self.update(ancestor);
+                index = self.nodes[ancestor].parent;
+            }
+        }
+
+        Ok(SearchResult {
+            proven: self.nodes[0].proof == 0,
+            best_move: self.nodes[0]
+                .children
+                .iter()
+                .find(|&&child| self.nodes[child].proof == 0)
+                .and_then(|&child| self.nodes[child].m.clone()),
+            nodes: self.nodes.len() as u64,
+        })
+    }
+}
+
+impl ProofNumber {
+    fn search<T: Game>(
+        &self,
+        game: &T,
+        target: Outcome,
+    ) -> Result<SearchResult<T::Move>, GameSolveError<T>> {
+        Search {
+            nodes: Vec::from([Node {
+                game: game.clone(),
+                m: None,
+                parent: None,
+                children: Vec::new(),
+                attacker: true,
+                proof: 1,
+                disproof: 1,
+            }]),
+            target,
+            cancellation_token: &self.cancellation_token,
+        }
+        .run()
+    }
+}
+
+impl<T: Game> Strategy<T> for ProofNumber {
+    fn evaluate(&mut self, game: &T) -> Result<Evaluation<T::Move>, GameSolveError<T>> {
+        if let Some(outcome) = Outcome::of_state(game) {
+            return Ok(Evaluation {
+                value: Value::Proven(outcome),
+                best_move: None,
+                nodes: 1,
+            });
+        }
+
+        let win = self.search(game, Outcome::Win)?;
+        if win.proven {
+            return Ok(Evaluation {
+                value: Value::Proven(Outcome::Win),
+                best_move: win.best_move,
+                nodes: win.nodes,
+            });
+        }
+
+        let tie = self.search(game, Outcome::Tie)?;
+        let nodes = win.nodes + tie.nodes;
+
+        let (outcome, best_move) = if tie.proven {
+            (Outcome::Tie, tie.best_move)
+        } else {
+            // every move loses
+            (Outcome::Loss, game.possible_moves().next())
+        };
+
+        Ok(Evaluation {
+            value: Value::Proven(outcome),
+            best_move,
+            nodes,
+        })
+    }
+}
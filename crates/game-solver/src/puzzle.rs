@@ -0,0 +1,244 @@
+//! Generates tactics-style puzzles: positions where only one move is good enough,
+//! and rates how hard they are to win.
+//! This requires the `rand` feature to be enabled.
+
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    game::{Game, GameState},
+    move_scores, multi_pv,
+    player::TwoPlayer,
+    policy::{Evaluator, Mobility},
+    transposition::TranspositionTable,
+    GameSolveError,
+};
+
+/// How many random positions are tried per requested puzzle, before giving up.
+const ATTEMPTS_PER_PUZZLE: usize = 100;
+
+/// Decides which positions make for a puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PuzzleFilter {
+    /// Exactly one move wins.
+    UniqueWin,
+    /// The best move scores at least this much more than any other move.
+    Swing(isize),
+}
+
+/// A position with a single solution.
+#[derive(Debug, Clone)]
+pub struct Puzzle<T: Game> {
+    pub position: T,
+    /// The only move that passes the [`PuzzleFilter`].
+    pub solution: T::Move,
+    /// The score of playing the solution, from the perspective of the player to move.
+    pub score: isize,
+    /// How hard the position is to win, or None if the player to move can't win it.
+    pub difficulty: Option<Difficulty>,
+}
+
+/// How hard it is to win a position, found from its winning line by [`difficulty`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Difficulty {
+    /// How many moves of both sides the winning line takes, the loser holding out as long as they can.
+    pub depth_to_win: usize,
+    /// The share of the winner's moves that keep the win, on average over their turns on the winning line.
+    pub forcing_ratio: f64,
+    /// How many moves on the winner's turns lose, but leave the opponent with fewer replies
+    /// than the winning move does, so that they look better.
+    pub traps: usize,
+}
+
+impl Difficulty {
+    /// How many of the winner's turns there are on the winning line.
+    pub fn turns(&self) -> usize {
+        self.depth_to_win.div_ceil(2)
+    }
+
+    /// A single measure of difficulty: the share of the winner's moves that throw the win away,
+    /// added up over their turns, and the traps on top. Easier positions rate lower.
+    pub fn rating(&self) -> f64 {
+        self.turns() as f64 * (1.0 - self.forcing_ratio) + self.traps as f64
+    }
+}
+
+/// How hard it is for the player to move to win `game`, or None if they can't.
+///
+/// The winning line is the principal variation of the best move, and each of the winner's turns on it
+/// is scored by every move, so the positions it reaches are solved with `transposition_table`.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use game_solver::puzzle::difficulty;
+/// use games::naive_nim::Nim;
+///
+/// let mut table = HashMap::new();
+/// // taking the only object wins right away
+/// let easy = difficulty(&Nim::new(vec![1]), &mut table).unwrap().unwrap();
+/// assert_eq!(easy.depth_to_win, 1);
+/// assert_eq!(easy.rating(), 0.0);
+///
+/// // only one of six moves evens out the heaps, and then the loser can hold out for a while
+/// let hard = difficulty(&Nim::new(vec![2, 4]), &mut table).unwrap().unwrap();
+/// assert!(hard.depth_to_win > 1);
+/// assert!(hard.rating() > easy.rating());
+///
+/// // the player to move can't win once the heaps are even
+/// assert_eq!(difficulty(&Nim::new(vec![2, 2]), &mut table).unwrap(), None);
+/// ```
+pub fn difficulty<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    game: &T,
+    transposition_table: &mut dyn TranspositionTable<T>,
+) -> Result<Option<Difficulty>, GameSolveError<T>> {
+    let Some((best, score, line)) = multi_pv(game, transposition_table, 1)?.pop() else {
+        return Ok(None);
+    };
+    if score <= 0 {
+        return Ok(None);
+    }
+
+    let line = [best].into_iter().chain(line).collect::<Vec<_>>();
+    let mut position = game.clone();
+    let mut ratios = Vec::new();
+    let mut traps = 0;
+
+    for (ply, m) in line.iter().enumerate() {
+        // the winner moves every other ply, starting with the first
+        if ply % 2 == 0 {
+            let scores = move_scores(&position, transposition_table, None, &None)
+                .collect::<Result<Vec<_>, _>>()?;
+            let winning = scores.iter().filter(|(_, score)| *score > 0).count();
+            ratios.push(winning as f64 / scores.len() as f64);
+
+            let winning_replies = replies(&position, m)?;
+            for (other, _) in scores.iter().filter(|(_, score)| *score <= 0) {
+                if replies(&position, other)? < winning_replies {
+                    traps += 1;
+                }
+            }
+        }
+
+        position.make_move(m).map_err(GameSolveError::MoveError)?;
+    }
+
+    Ok(Some(Difficulty {
+        depth_to_win: line.len(),
+        forcing_ratio: ratios.iter().sum::<f64>() / ratios.len() as f64,
+        traps,
+    }))
+}
+
+/// How many replies the opponent has after `m` is played in `game`, none if it ends the game.
+fn replies<T: Game>(game: &T, m: &T::Move) -> Result<isize, GameSolveError<T>> {
+    let mut board = game.clone();
+    board.make_move(m).map_err(GameSolveError::MoveError)?;
+
+    if board.state() != GameState::Playable {
+        return Ok(0);
+    }
+
+    Ok(Mobility.evaluate(&board))
+}
+
+/// Plays random moves from `game` until the game ends,
+/// and picks one of the positions along the way where the game is still playable.
+fn random_playout_position<T: Game>(
+    game: &T,
+    rng: &mut impl Rng,
+) -> Result<Option<T>, T::MoveError> {
+    let mut positions = Vec::new();
+    let mut board = game.clone();
+
+    while board.state() == GameState::Playable {
+        let moves = board.possible_moves().collect::<Vec<_>>();
+        let Some(m) = moves.choose(rng) else {
+            break;
+        };
+
+        positions.push(board.clone());
+        board.make_move(m)?;
+    }
+
+    Ok(positions.choose(rng).cloned())
+}
+
+/// Generates up to `count` distinct puzzles from random positions reachable from `game`.
+///
+/// Each position is reached by playing random moves to a random depth,
+/// and kept if, once solved, it passes `filter`. Positions with a single legal move are never kept.
+/// As some games (or filters) rarely have puzzles, this gives up after trying `100 * count` positions,
+/// returning fewer puzzles than asked for.
+///
+/// With `min_difficulty`, only positions whose [`Difficulty::rating`] is at least that are kept.
+///
+/// The same `rng` state always generates the same puzzles.
+pub fn generate_puzzles<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    game: &T,
+    rng: &mut impl Rng,
+    count: usize,
+    filter: PuzzleFilter,
+    min_difficulty: Option<f64>,
+) -> Result<Vec<Puzzle<T>>, GameSolveError<T>> {
+    let mut puzzles = Vec::new();
+    let mut seen = HashSet::new();
+    // scores don't depend on how a position was reached, so the table is shared between attempts
+    let mut transposition_table = HashMap::new();
+
+    for _ in 0..count * ATTEMPTS_PER_PUZZLE {
+        if puzzles.len() == count {
+            break;
+        }
+
+        let Some(position) =
+            random_playout_position(game, rng).map_err(|err| GameSolveError::MoveError(err))?
+        else {
+            continue;
+        };
+
+        if !seen.insert(position.clone()) {
+            continue;
+        }
+
+        let mut scores = move_scores(&position, &mut transposition_table, None, &None)
+            .collect::<Result<Vec<_>, _>>()?;
+        scores.sort_by_key(|(_, score)| -score);
+
+        let [(_, best), (_, second), ..] = scores[..] else {
+            continue;
+        };
+
+        let is_puzzle = match filter {
+            PuzzleFilter::UniqueWin => best > 0 && second <= 0,
+            PuzzleFilter::Swing(threshold) => best - second >= threshold,
+        };
+
+        if !is_puzzle {
+            continue;
+        }
+
+        let difficulty = difficulty(&position, &mut transposition_table)?;
+        let hard_enough = match (min_difficulty, difficulty) {
+            (None, _) => true,
+            (Some(min), Some(difficulty)) => difficulty.rating() >= min,
+            (Some(_), None) => false,
+        };
+
+        if hard_enough {
+            let (solution, score) = scores.swap_remove(0);
+            puzzles.push(Puzzle {
+                position,
+                solution,
+                score,
+                difficulty,
+            });
+        }
+    }
+
+    Ok(puzzles)
+}
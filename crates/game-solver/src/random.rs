@@ -0,0 +1,36 @@
+//! Random play, for reaching realistic positions to test and benchmark with.
+//! This requires the `rand` feature to be enabled.
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::game::{Game, GameState};
+
+/// Plays up to `plies` uniformly random moves from `start`,
+/// stopping early if the game ends.
+///
+/// Moves are chosen from the game's own possible moves,
+/// so the returned position is always reachable from `start`.
+///
+/// Returns the position, and how many moves were actually played.
+pub fn random_position<T: Game>(
+    start: &T,
+    plies: usize,
+    rng: &mut impl Rng,
+) -> Result<(T, usize), T::MoveError> {
+    let mut position = start.clone();
+
+    for played in 0..plies {
+        if position.state() != GameState::Playable {
+            return Ok((position, played));
+        }
+
+        let moves = position.possible_moves().collect::<Vec<_>>();
+        let Some(m) = moves.choose(rng) else {
+            return Ok((position, played));
+        };
+
+        position.make_move(m)?;
+    }
+
+    Ok((position, plies))
+}
@@ -0,0 +1,195 @@
+//! Games played one after the other by the same players.
+
+use alloc::vec::Vec;
+use core::{error::Error, fmt};
+
+use crate::{
+    game::{Game, GameStatus},
+    player::TwoPlayer,
+    strategy::Outcome,
+};
+
+/// How the results of both games of a [`SequentialCompound`] decide its winner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SequentialScoring {
+    /// Each game is worth a point to its winner, and whoever has more points wins;
+    /// a tie in either game is worth nothing.
+    #[default]
+    Sum,
+    /// Only the second game counts, so the first one only decides who moves first in it.
+    LastGameDecides,
+}
+
+/// Two games played one after the other: once the first one is over,
+/// the same players continue into the second one.
+///
+/// Players keep alternating across the boundary, so whoever is to move when the first game ends
+/// makes the first move of the second, playing it as that game's first player.
+/// If that isn't the player the second game starts with, the players swap roles in it.
+/// (Impartial players are relative to the position, so they never swap.)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SequentialCompound<G1: Game, G2: Game> {
+    first: G1,
+    second: G2,
+    /// The move count of the second game when it was started, to tell how many moves were made in it.
+    second_start: usize,
+    /// The player to move when the second game was started, by its own reckoning.
+    second_starter: G2::Player,
+    scoring: SequentialScoring,
+}
+
+impl<G1: Game, G2: Game> SequentialCompound<G1, G2> {
+    pub fn new(first: G1, second: G2, scoring: SequentialScoring) -> Self {
+        Self {
+            second_start: second.move_count(),
+            second_starter: second.player(),
+            first,
+            second,
+            scoring,
+        }
+    }
+
+    pub fn first(&self) -> &G1 {
+        &self.first
+    }
+
+    pub fn second(&self) -> &G2 {
+        &self.second
+    }
+
+    /// Whether the first game is over, so that moves are made in the second one.
+    pub fn in_second(&self) -> bool {
+        self.first.status().is_over()
+    }
+}
+
+/// A move of a [`SequentialCompound`], in whichever game is being played.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SequentialMove<M1, M2> {
+    First(M1),
+    Second(M2),
+}
+
+#[derive(Debug, Clone)]
+pub enum SequentialMoveError<E1, E2> {
+    /// The move couldn't be made in the first game.
+    First(E1),
+    /// The move couldn't be made in the second game.
+    Second(E2),
+    /// The move was for the game that isn't being played:
+    /// the first one once it's over, or the second one before it started.
+    WrongGame,
+}
+
+impl<E1: fmt::Display, E2: fmt::Display> fmt::Display for SequentialMoveError<E1, E2> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::First(err) => write!(f, "Could not make the move in the first game: {err}"),
+            Self::Second(err) => write!(f, "Could not make the move in the second game: {err}"),
+            Self::WrongGame => write!(f, "The move is for the game that isn't being played"),
+        }
+    }
+}
+
+impl<E1: Error, E2: Error> Error for SequentialMoveError<E1, E2> {}
+
+/// The outcome of a game that's over for the player to move, as a point for [`SequentialScoring::Sum`].
+fn points(outcome: Outcome) -> isize {
+    match outcome {
+        Outcome::Loss => -1,
+        Outcome::Tie => 0,
+        Outcome::Win => 1,
+    }
+}
+
+impl<P, G1, G2> Game for SequentialCompound<G1, G2>
+where
+    P: TwoPlayer,
+    G1: Game<Player = P>,
+    G2: Game<Player = P>,
+{
+    type Move = SequentialMove<G1::Move, G2::Move>;
+    type Iter<'a> = alloc::vec::IntoIter<Self::Move> where G1: 'a, G2: 'a;
+    type Player = P;
+    type MoveError = SequentialMoveError<G1::MoveError, G2::MoveError>;
+
+    fn move_count(&self) -> usize {
+        self.first.move_count() + self.second.move_count()
+    }
+
+    fn max_moves(&self) -> Option<usize> {
+        self.first
+            .max_moves()
+            .and_then(|first| self.second.max_moves().map(|second| first + second))
+    }
+
+    fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        match (m, self.in_second()) {
+            (SequentialMove::First(m), false) => {
+                self.first.make_move(m).map_err(SequentialMoveError::First)
+            }
+            (SequentialMove::Second(m), true) => self
+                .second
+                .make_move(m)
+                .map_err(SequentialMoveError::Second),
+            _ => Err(SequentialMoveError::WrongGame),
+        }
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        let moves: Vec<_> = if self.in_second() {
+            self.second
+                .possible_moves()
+                .map(SequentialMove::Second)
+                .collect()
+        } else {
+            self.first
+                .possible_moves()
+                .map(SequentialMove::First)
+                .collect()
+        };
+
+        moves.into_iter()
+    }
+
+    fn status(&self) -> GameStatus {
+        let Some(first) = Outcome::of_state(&self.first) else {
+            return GameStatus::InProgress;
+        };
+        let Some(second) = Outcome::of_state(&self.second) else {
+            return GameStatus::InProgress;
+        };
+
+        // the first game's outcome is for the player who started the second one
+        let first = if (self.second.move_count() - self.second_start) % 2 == 0 {
+            first
+        } else {
+            first.flip()
+        };
+
+        let outcome = match self.scoring {
+            SequentialScoring::Sum => Outcome::from_score(points(first) + points(second)),
+            SequentialScoring::LastGameDecides => second,
+        };
+
+        match outcome {
+            Outcome::Win => GameStatus::won(self),
+            Outcome::Tie => GameStatus::TIE,
+            Outcome::Loss => GameStatus::lost(self),
+        }
+    }
+
+    fn player(&self) -> Self::Player {
+        if !self.in_second() {
+            return self.first.player();
+        }
+
+        // whoever is to move once the first game is over starts the second
+        let player = self.second.player();
+        if self.first.player() == self.second_starter {
+            player
+        } else {
+            player.other()
+        }
+    }
+}
@@ -0,0 +1,193 @@
+//! A game in progress against the solver, which keeps its transposition table between moves
+//! and can think ahead ("ponder") while the opponent decides on their move.
+//! This requires the `std` feature to be enabled.
+//!
+//! Scores in the table are tied to positions, not to the position the search started from,
+//! so everything learned before a move stays valid after it.
+//! A cancelled search also never stores the score of a position it didn't finish,
+//! so a ponder can be cut short at any moment without corrupting the table.
+
+use std::{
+    hash::Hash,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::Instant,
+};
+
+use crate::{
+    game::{Game, GameState},
+    history::History,
+    player::TwoPlayer,
+    stats::Stats,
+    strategy::{negamax_evaluate, score_moves, Evaluation},
+    tie_break::TieBreaker,
+    transposition::TranspositionTable,
+    GameSolveError,
+};
+
+/// When to stop pondering.
+#[derive(Debug, Clone)]
+pub enum PonderUntil {
+    /// Stop at this instant.
+    Deadline(Instant),
+    /// Stop once the token is set, such as when the opponent has moved.
+    Cancelled(Arc<AtomicBool>),
+}
+
+/// What a ponder got through before it stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PonderReport {
+    /// How many of the opponent's replies were searched as deeply as [`Session::evaluate`] would,
+    /// so that evaluating after them only needs the table.
+    pub replies: usize,
+    /// How many positions the ponder looked at.
+    pub nodes: u64,
+    /// Whether every reply was searched before the ponder had to stop.
+    pub finished: bool,
+}
+
+/// A game in progress, along with everything the solver has learned about it.
+pub struct Session<T: Game, Table> {
+    history: History<T>,
+    /// The transposition table, which is kept between moves.
+    pub table: Table,
+    /// Cancels [`Session::evaluate`]. Pondering has its own token.
+    pub cancellation_token: Option<Arc<AtomicBool>>,
+}
+
+impl<T, P, Table> Session<T, Table>
+where
+    P: TwoPlayer + 'static,
+    T: Game<Player = P> + Eq + Hash,
+    Table: TranspositionTable<T>,
+{
+    /// Starts a session from `game`, with a (usually empty) transposition table.
+    pub fn new(game: T, table: Table) -> Self {
+        Self::from_history(History::new(game), table)
+    }
+
+    /// Continues the game of `history`, with a (usually empty) transposition table.
+    pub fn from_history(history: History<T>, table: Table) -> Self {
+        Self {
+            history,
+            table,
+            cancellation_token: None,
+        }
+    }
+
+    /// The current position.
+    pub fn game(&self) -> &T {
+        self.history.game()
+    }
+
+    /// The moves played so far, which can be taken back.
+    pub fn history(&self) -> &History<T> {
+        &self.history
+    }
+
+    /// Plays a move (by either side), keeping the table.
+    pub fn apply(&mut self, m: &T::Move) -> Result<(), T::MoveError> {
+        self.history.push(m.clone())
+    }
+
+    /// Takes back the last move, returning it, or None if no move was played.
+    ///
+    /// The table stays as it is, as its scores are tied to positions.
+    pub fn undo(&mut self) -> Option<&T::Move> {
+        self.history.undo()
+    }
+
+    /// Plays the last undone move again, returning it, or None if there is none.
+    pub fn redo(&mut self) -> Option<&T::Move> {
+        self.history.redo()
+    }
+
+    /// Finds the best move in the current position, and its score.
+    pub fn evaluate(&mut self) -> Result<Evaluation<T::Move>, GameSolveError<T>> {
+        negamax_evaluate(
+            self.history.game(),
+            &mut self.table,
+            &mut TieBreaker::default(),
+            &self.cancellation_token,
+        )
+    }
+
+    /// Searches the opponent's replies to the current position, best first,
+    /// so that answering whichever one they play only takes a look at the table.
+    ///
+    /// Stopping early isn't an error: the report tells how far the ponder got.
+    pub fn ponder(&mut self, until: PonderUntil) -> Result<PonderReport, GameSolveError<T>> {
+        let (token, _timer) = match until {
+            PonderUntil::Cancelled(token) => (token, None),
+            PonderUntil::Deadline(deadline) => {
+                let (token, timer) = deadline_token(deadline);
+                (token, Some(timer))
+            }
+        };
+        let cancellation_token = Some(token);
+
+        let game = self.history.game();
+        let stats = Stats::new(game.player(), game.move_count());
+        let mut report = PonderReport::default();
+
+        let result = self.ponder_replies(&stats, &cancellation_token, &mut report);
+        report.nodes = stats.states_explored.into_inner();
+
+        match result {
+            Ok(()) => {
+                report.finished = true;
+                Ok(report)
+            }
+            Err(GameSolveError::CancellationTokenError) => Ok(report),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn ponder_replies(
+        &mut self,
+        stats: &Stats<P>,
+        cancellation_token: &Option<Arc<AtomicBool>>,
+        report: &mut PonderReport,
+    ) -> Result<(), GameSolveError<T>> {
+        let game = self.history.game();
+        if game.state() != GameState::Playable {
+            return Ok(());
+        }
+
+        // a strong opponent plays their best replies, so those are searched first
+        let mut replies = score_moves(game, &mut self.table, stats, cancellation_token)?;
+        replies.sort_by_key(|(_, score)| -*score);
+
+        for (reply, _) in replies {
+            let mut board = game.clone();
+            board.make_move(&reply).map_err(GameSolveError::MoveError)?;
+
+            if board.state() == GameState::Playable {
+                score_moves(&board, &mut self.table, stats, cancellation_token)?;
+            }
+
+            report.replies += 1;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns a token that is set at `deadline`, unless the returned sender is dropped first.
+pub(crate) fn deadline_token(deadline: Instant) -> (Arc<AtomicBool>, mpsc::Sender<()>) {
+    let token = Arc::new(AtomicBool::new(false));
+    let (sender, receiver) = mpsc::channel::<()>();
+
+    let timer_token = token.clone();
+    thread::spawn(move || {
+        let timeout = deadline.saturating_duration_since(Instant::now());
+        if let Err(mpsc::RecvTimeoutError::Timeout) = receiver.recv_timeout(timeout) {
+            timer_token.store(true, Ordering::Relaxed);
+        }
+    });
+
+    (token, sender)
+}
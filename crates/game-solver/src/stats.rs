@@ -1,6 +1,13 @@
-use std::sync::atomic::{AtomicU64, AtomicUsize};
+use alloc::{boxed::Box, vec::Vec};
+use core::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 
-use crate::player::Player;
+use crate::{
+    game::Game,
+    player::{ImpartialPlayer, Player},
+};
+
+/// The most plies [`Stats::per_ply`] counts, for games with no max number of moves (or a very large one).
+pub const MAX_PLIES: usize = 1024;
 
 #[derive(Debug)]
 pub struct TerminalEnds {
@@ -19,13 +26,161 @@ impl Default for TerminalEnds {
     }
 }
 
+/// What the solver counted at one ply of its search, as [`Stats::per_ply`] keeps them.
+#[derive(Debug, Default)]
+pub struct PlyStats {
+    /// The amount of positions searched at this ply.
+    pub nodes: AtomicU64,
+    /// The amount of positions at this ply whose search was cut off by one of their moves.
+    pub cutoffs: AtomicU64,
+    /// How many of the cutoffs were on the first move searched, on the second, and on a later one.
+    pub cutoff_moves: [AtomicU64; 3],
+}
+
+impl PlyStats {
+    /// Counts a cutoff on the move searched after `searched` others.
+    pub(crate) fn record_cutoff(&self, searched: usize) {
+        self.cutoffs.fetch_add(1, Ordering::Relaxed);
+        self.cutoff_moves[searched.min(2)].fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// What [`Stats::per_ply`] counted at one ply, read out by [`Stats::plies`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlySummary {
+    /// How many moves after the position the search started from this ply is.
+    pub ply: usize,
+    /// The amount of positions searched at this ply.
+    pub nodes: u64,
+    /// The amount of them whose search was cut off by one of their moves.
+    pub cutoffs: u64,
+    /// The cutoffs on the first move searched, on the second, and on a later one.
+    pub cutoff_moves: [u64; 3],
+    /// How many positions were searched at the next ply for each one searched at this ply,
+    /// or None if none were searched at this ply (as when [`move_scores`](crate::move_scores) scores the moves of the first).
+    pub branching_factor: Option<f64>,
+}
+
+impl PlySummary {
+    /// The share of the cutoffs that were on the first move searched, which is higher the better the moves are ordered,
+    /// or None if there were none.
+    pub fn first_move_cutoff_rate(&self) -> Option<f64> {
+        (self.cutoffs > 0).then(|| self.cutoff_moves[0] as f64 / self.cutoffs as f64)
+    }
+}
+
 #[derive(Debug)]
 pub struct Stats<P: Player> {
     pub states_explored: AtomicU64,
     pub max_depth: AtomicUsize,
+    /// The amount of times the transposition table was used to cut off a search.
     pub cache_hits: AtomicU64,
+    /// The amount of times the transposition table was looked up.
+    pub transposition_probes: AtomicU64,
+    /// The amount of lookups that found an entry in the transposition table.
+    pub transposition_hits: AtomicU64,
+    /// The amount of lookups that found the entry of another position with the same hash,
+    /// which count as misses (see [`TranspositionTable::collisions`](crate::transposition::TranspositionTable::collisions)).
+    pub transposition_collisions: AtomicU64,
     pub pruning_cutoffs: AtomicU64,
     pub terminal_ends: TerminalEnds,
     pub original_player: P,
     pub original_move_count: usize,
+    /// What was counted at each ply, starting from the original position, if [`Stats::per_ply`] made these.
+    pub per_ply: Box<[PlyStats]>,
+}
+
+impl<P: Player> Stats<P> {
+    /// Creates empty statistics for a search starting from a game
+    /// where `player` is to move and `move_count` moves have been made.
+    pub fn new(player: P, move_count: usize) -> Self {
+        Self {
+            states_explored: AtomicU64::new(0),
+            max_depth: AtomicUsize::new(0),
+            cache_hits: AtomicU64::new(0),
+            transposition_probes: AtomicU64::new(0),
+            transposition_hits: AtomicU64::new(0),
+            transposition_collisions: AtomicU64::new(0),
+            pruning_cutoffs: AtomicU64::new(0),
+            terminal_ends: TerminalEnds::default(),
+            original_player: player,
+            original_move_count: move_count,
+            per_ply: Box::default(),
+        }
+    }
+
+    /// Creates empty statistics for a search of `game`, which also count what the search did at each ply,
+    /// up to the game's max number of moves (or [`MAX_PLIES`] past its move count, whichever is lower).
+    ///
+    /// Every ply's counters are made up front, so counting them doesn't allocate.
+    pub fn per_ply<T: Game<Player = P>>(game: &T) -> Self {
+        let plies = game
+            .max_moves()
+            .map_or(MAX_PLIES, |max_moves| {
+                max_moves.saturating_sub(game.move_count()) + 1
+            })
+            .min(MAX_PLIES);
+
+        Self {
+            per_ply: (0..plies).map(|_| PlyStats::default()).collect(),
+            ..Self::new(game.player(), game.move_count())
+        }
+    }
+
+    /// The counters of the ply of a position after `move_count` moves, if they're kept.
+    pub(crate) fn ply(&self, move_count: usize) -> Option<&PlyStats> {
+        self.per_ply
+            .get(move_count.checked_sub(self.original_move_count)?)
+    }
+
+    /// What was counted at each ply, up to the last one any position was searched at.
+    pub fn plies(&self) -> Vec<PlySummary> {
+        let nodes = self
+            .per_ply
+            .iter()
+            .map(|ply| ply.nodes.load(Ordering::Relaxed))
+            .collect::<Vec<_>>();
+        let searched = nodes
+            .iter()
+            .rposition(|&nodes| nodes > 0)
+            .map_or(0, |last| last + 1);
+
+        (0..searched)
+            .map(|ply| {
+                let stats = &self.per_ply[ply];
+                let next = nodes.get(ply + 1).copied().unwrap_or(0);
+
+                PlySummary {
+                    ply,
+                    nodes: nodes[ply],
+                    cutoffs: stats.cutoffs.load(Ordering::Relaxed),
+                    cutoff_moves: stats
+                        .cutoff_moves
+                        .each_ref()
+                        .map(|count| count.load(Ordering::Relaxed)),
+                    branching_factor: (nodes[ply] > 0).then(|| next as f64 / nodes[ply] as f64),
+                }
+            })
+            .collect()
+    }
+}
+
+impl<P: Player + 'static> Stats<P> {
+    /// Counts a finished game that `winner` won after `move_count` moves
+    /// as a win or a loss for the player the search started from.
+    pub fn record_win(&self, winner: P, move_count: usize) {
+        let winning = match castaway::cast!(winner, ImpartialPlayer) {
+            // impartial players are named relative to the player to move, which changes every move
+            Ok(winner) => {
+                ImpartialPlayer::from_move_count(self.original_move_count, move_count) == winner
+            }
+            Err(winner) => self.original_player == winner,
+        };
+
+        if winning {
+            self.terminal_ends.winning.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.terminal_ends.losing.fetch_add(1, Ordering::Relaxed);
+        }
+    }
 }
@@ -0,0 +1,301 @@
+//! A common interface to the solving algorithms, so callers can switch between them.
+//!
+//! - [`Negamax`] is the alpha-beta search behind [`solve`], which finds exact scores.
+//! - [`ProofNumber`](crate::proof_number::ProofNumber) proves wins, ties, and losses, without their distance.
+//! - `Mcts` (with the `rand` feature, in `mcts`) estimates how good a position is from random playouts.
+//! - [`HeuristicSearch`] searches a few moves ahead, scoring the positions beyond with an [`Evaluator`].
+
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{
+    cmp::Ordering,
+    hash::Hash,
+    sync::atomic::{self, AtomicBool},
+};
+
+use crate::{
+    decided_score,
+    game::Game,
+    player::TwoPlayer,
+    policy::Evaluator,
+    solve, solve_heuristic,
+    stats::Stats,
+    tie_break::TieBreaker,
+    transposition::{DepthAwareTable, TranspositionTable},
+    GameSolveError,
+};
+
+/// The result of a game, from the point of view of one player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Outcome {
+    Loss,
+    Tie,
+    Win,
+}
+
+impl Outcome {
+    /// The same result, from the point of view of the other player.
+    #[must_use]
+    pub fn flip(self) -> Self {
+        match self {
+            Self::Loss => Self::Win,
+            Self::Tie => Self::Tie,
+            Self::Win => Self::Loss,
+        }
+    }
+
+    /// The outcome of a score returned by [`solve`].
+    pub fn from_score(score: isize) -> Self {
+        match score.cmp(&0) {
+            Ordering::Less => Self::Loss,
+            Ordering::Equal => Self::Tie,
+            Ordering::Greater => Self::Win,
+        }
+    }
+
+    /// The outcome of a game that is over, for the player whose turn it would be,
+    /// or None if the game is still playable.
+    pub fn of_state<T: Game>(game: &T) -> Option<Self> {
+        game.status().score(game).map(Self::from_score)
+    }
+}
+
+/// How good a position is for the player to move, as found by a [`Strategy`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    /// A score like those returned by [`solve`], which also tells how many moves the outcome takes.
+    Exact(isize),
+    /// A proven outcome.
+    Proven(Outcome),
+    /// An estimate between -1 (certainly lost) and 1 (certainly won), which proves nothing.
+    Estimate(f64),
+    /// The score of an [`Evaluator`] a few moves ahead, which proves nothing.
+    Heuristic(isize),
+}
+
+impl Value {
+    /// The outcome this value proves, if it proves one.
+    pub fn outcome(self) -> Option<Outcome> {
+        match self {
+            Self::Exact(score) => Some(Outcome::from_score(score)),
+            Self::Proven(outcome) => Some(outcome),
+            Self::Estimate(_) | Self::Heuristic(_) => None,
+        }
+    }
+}
+
+/// What a [`Strategy`] found out about a position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Evaluation<M> {
+    /// How good the position is for the player to move.
+    pub value: Value,
+    /// The move the strategy would play, or None if the game is over.
+    pub best_move: Option<M>,
+    /// How many positions the strategy looked at.
+    pub nodes: u64,
+}
+
+/// An algorithm that evaluates positions of a game.
+///
+/// Strategies keep whatever they learn between evaluations (such as a transposition table),
+/// which is why evaluating takes `&mut self`.
+pub trait Strategy<T: Game> {
+    /// Evaluates `game` for the player to move.
+    fn evaluate(&mut self, game: &T) -> Result<Evaluation<T::Move>, GameSolveError<T>>;
+}
+
+impl<T: Game, S: Strategy<T> + ?Sized> Strategy<T> for Box<S> {
+    fn evaluate(&mut self, game: &T) -> Result<Evaluation<T::Move>, GameSolveError<T>> {
+        (**self).evaluate(game)
+    }
+}
+
+/// Returns an error if the token has cancelled the search.
+pub(crate) fn check_cancelled<T: Game>(
+    cancellation_token: &Option<Arc<AtomicBool>>,
+) -> Result<(), GameSolveError<T>> {
+    match cancellation_token {
+        Some(token) if token.load(atomic::Ordering::Relaxed) => {
+            Err(GameSolveError::CancellationTokenError)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The alpha-beta search of [`solve`], which scores every move like [`move_scores`](crate::move_scores)
+/// to find the best one, remembering their exact scores for later evaluations.
+pub struct Negamax<Table, M> {
+    /// The transposition table, which is kept between evaluations.
+    pub table: Table,
+    pub cancellation_token: Option<Arc<AtomicBool>>,
+    /// How to choose among the best moves, the first of them by default.
+    pub tie_break: TieBreaker<M>,
+}
+
+impl<Table, M> Negamax<Table, M> {
+    pub fn new(table: Table) -> Self {
+        Self {
+            table,
+            cancellation_token: None,
+            tie_break: TieBreaker::default(),
+        }
+    }
+}
+
+impl<T, P, Table> Strategy<T> for Negamax<Table, T::Move>
+where
+    P: TwoPlayer + 'static,
+    T: Game<Player = P> + Eq + Hash,
+    Table: TranspositionTable<T>,
+{
+    fn evaluate(&mut self, game: &T) -> Result<Evaluation<T::Move>, GameSolveError<T>> {
+        negamax_evaluate(
+            game,
+            &mut self.table,
+            &mut self.tie_break,
+            &self.cancellation_token,
+        )
+    }
+}
+
+/// Evaluates `game` like [`Negamax`], with a table it doesn't own.
+pub(crate) fn negamax_evaluate<T, P, Table>(
+    game: &T,
+    table: &mut Table,
+    tie_break: &mut TieBreaker<T::Move>,
+    cancellation_token: &Option<Arc<AtomicBool>>,
+) -> Result<Evaluation<T::Move>, GameSolveError<T>>
+where
+    P: TwoPlayer + 'static,
+    T: Game<Player = P> + Eq + Hash,
+    Table: TranspositionTable<T>,
+{
+    let stats = Stats::new(game.player(), game.move_count());
+
+    let best = if !game.status().is_over() {
+        tie_break.best(score_moves(game, table, &stats, cancellation_token)?)
+    } else {
+        None
+    };
+
+    let (best_move, score) = match best {
+        Some((m, score)) => (Some(m), score),
+        None => (None, solve(game, table, Some(&stats), cancellation_token)?),
+    };
+
+    Ok(Evaluation {
+        value: Value::Exact(score),
+        best_move,
+        nodes: stats.states_explored.into_inner(),
+    })
+}
+
+/// Scores every move of `game` like [`move_scores`](crate::move_scores),
+/// which leaves the exact score of every position it reaches in the table.
+pub(crate) fn score_moves<T, P, Table>(
+    game: &T,
+    table: &mut Table,
+    stats: &Stats<P>,
+    cancellation_token: &Option<Arc<AtomicBool>>,
+) -> Result<Vec<(T::Move, isize)>, GameSolveError<T>>
+where
+    P: TwoPlayer + 'static,
+    T: Game<Player = P> + Eq + Hash,
+    Table: TranspositionTable<T>,
+{
+    let mut scores = Vec::new();
+
+    for m in game.possible_moves() {
+        let mut board = game.clone();
+        board.make_move(&m).map_err(GameSolveError::MoveError)?;
+
+        let score = solve(&board, table, Some(stats), cancellation_token)?;
+
+        scores.push((m, -score));
+    }
+
+    Ok(scores)
+}
+
+/// A depth-limited alpha-beta search, which scores the positions it can't see the end of with an [`Evaluator`],
+/// for games too large to solve.
+///
+/// Positions whose outcome the search sees within `depth` moves get [`Value::Exact`] scores,
+/// and the rest [`Value::Heuristic`] ones.
+pub struct HeuristicSearch<E, Table> {
+    pub evaluator: E,
+    /// How many moves ahead to search.
+    pub depth: usize,
+    /// How many plies of [noisy moves](Game::noisy_moves) to search past `depth`, 4 by default.
+    pub quiescence: usize,
+    /// The table of the search, which is kept between evaluations.
+    pub table: Table,
+    pub cancellation_token: Option<Arc<AtomicBool>>,
+}
+
+impl<E, Table> HeuristicSearch<E, Table> {
+    pub fn new(evaluator: E, depth: usize, table: Table) -> Self {
+        Self {
+            evaluator,
+            depth,
+            quiescence: 4,
+            table,
+            cancellation_token: None,
+        }
+    }
+}
+
+impl<T, P, E, Table> Strategy<T> for HeuristicSearch<E, Table>
+where
+    P: TwoPlayer + 'static,
+    T: Game<Player = P>,
+    E: Evaluator<T>,
+    Table: DepthAwareTable<T>,
+{
+    fn evaluate(&mut self, game: &T) -> Result<Evaluation<T::Move>, GameSolveError<T>> {
+        let stats = Stats::new(game.player(), game.move_count());
+
+        let mut best: Option<(T::Move, isize)> = None;
+        if !game.status().is_over() {
+            for m in game.possible_moves() {
+                let mut board = game.clone();
+                board.make_move(&m).map_err(GameSolveError::MoveError)?;
+
+                let score = -solve_heuristic(
+                    &board,
+                    self.depth.saturating_sub(1),
+                    self.quiescence,
+                    &self.evaluator,
+                    &mut self.table,
+                    Some(&stats),
+                    &self.cancellation_token,
+                )?;
+
+                if best.as_ref().map_or(true, |(_, best)| score > *best) {
+                    best = Some((m, score));
+                }
+            }
+        }
+
+        let (best_move, score) = match best {
+            Some((m, score)) => (Some(m), score),
+            None => (
+                None,
+                solve_heuristic(
+                    game,
+                    self.depth,
+                    self.quiescence,
+                    &self.evaluator,
+                    &mut self.table,
+                    Some(&stats),
+                    &self.cancellation_token,
+                )?,
+            ),
+        };
+
+        Ok(Evaluation {
+            value: decided_score(score).map_or(Value::Heuristic(score), Value::Exact),
+            best_move,
+            nodes: stats.states_explored.into_inner(),
+        })
+    }
+}
@@ -0,0 +1,195 @@
+//! Endgame tablebases, built by [retrograde analysis](https://www.chessprogramming.org/Retrograde_Analysis).
+//!
+//! When every position of an endgame can be enumerated, it's cheaper to evaluate
+//! each of them once, working backwards from the positions where the game is over,
+//! than to have the solver search through them again and again.
+//!
+//! Only who wins and how many moves it takes are stored, so games that score their endings
+//! other than with [`GameStatus::won`] and [`GameStatus::lost`] are scored as if they didn't.
+//!
+//! [`GameStatus::won`]: crate::game::GameStatus::won
+//! [`GameStatus::lost`]: crate::game::GameStatus::lost
+
+use alloc::{vec, vec::Vec};
+use core::hash::Hash;
+
+use crate::{
+    game::{upper_bound, Game},
+    transposition::{Score, TranspositionTable},
+};
+
+/// A game whose positions can be numbered, so that a tablebase can store them in an array.
+///
+/// The implementing value (generally the starting position) describes the set of positions to enumerate:
+/// for example, a game of Nim describes every position where no heap is larger than its own heaps.
+/// Every move from a position in the set should lead to another position in the set.
+pub trait EnumerableGame: Game {
+    /// The amount of indices in the set. Every index is below this.
+    fn position_count(&self) -> usize;
+
+    /// Returns the index of `position`, or `None` if it isn't part of the set.
+    fn index(&self, position: &Self) -> Option<usize>;
+
+    /// Returns the position with the given index, or `None` if no position has this index.
+    fn unindex(&self, index: usize) -> Option<Self>;
+}
+
+/// A stored position that hasn't been (or can't be) evaluated.
+const UNKNOWN: i16 = i16::MIN;
+
+/// Converts the value of a position to the value of the position before it.
+///
+/// Values are stored from the perspective of the player to move:
+/// 0 is a tie, `n + 1` is a win where the game ends `n` moves from now, and `-(n + 1)` is a loss.
+fn previous_value(value: i16) -> i16 {
+    -(value
+        .checked_add(value.signum())
+        .expect("tablebase games end within i16::MAX moves"))
+}
+
+/// Orders values by how good they are for the player to move:
+/// quick wins, then slow wins, then ties, then slow losses, then quick losses.
+fn value_key(value: i16) -> i32 {
+    match value {
+        0 => 0,
+        value if value > 0 => i32::from(i16::MAX) - i32::from(value),
+        value => -(i32::from(i16::MAX) + i32::from(value)),
+    }
+}
+
+/// The evaluation of every position of an [`EnumerableGame`].
+pub struct Tablebase<T: EnumerableGame> {
+    positions: T,
+    values: Vec<i16>,
+}
+
+impl<T: EnumerableGame> Tablebase<T> {
+    /// Evaluates every position described by `positions` by backward induction.
+    ///
+    /// Positions where the game is over are evaluated first. Then, every pass over the array
+    /// evaluates the positions whose moves all lead to evaluated positions, until a pass changes nothing.
+    ///
+    /// Positions that are never evaluated (as they can repeat, or have moves leading outside of the set)
+    /// are left out of the tablebase, and are searched as usual by the solver.
+    pub fn new(positions: T) -> Result<Self, T::MoveError> {
+        let mut values = vec![UNKNOWN; positions.position_count()];
+
+        loop {
+            let mut changed = false;
+
+            for index in 0..values.len() {
+                if values[index] != UNKNOWN {
+                    continue;
+                }
+
+                let Some(position) = positions.unindex(index) else {
+                    continue;
+                };
+
+                let value = match position.status().score(&position) {
+                    // only who won is stored, and the moves it took are counted from here
+                    Some(score) => Some(score.signum() as i16),
+                    None => {
+                        let mut best = None;
+
+                        for m in position.possible_moves() {
+                            let mut board = position.clone();
+                            board.make_move(&m)?;
+
+                            let child = positions
+                                .index(&board)
+                                .map_or(UNKNOWN, |child| values[child]);
+
+                            if child == UNKNOWN {
+                                best = None;
+                                break;
+                            }
+
+                            let value = previous_value(child);
+                            if best.map_or(true, |best| value_key(value) > value_key(best)) {
+                                best = Some(value);
+                            }
+                        }
+
+                        best
+                    }
+                };
+
+                if let Some(value) = value {
+                    values[index] = value;
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                break;
+            }
+        }
+
+        Ok(Self { positions, values })
+    }
+
+    /// Returns the score of `position` as [`solve`](crate::solve) would, if it's in the tablebase.
+    pub fn probe(&self, position: &T) -> Option<isize> {
+        let value = *self.values.get(self.positions.index(position)?)?;
+
+        match value {
+            UNKNOWN => None,
+            0 => Some(0),
+            value => {
+                // see `win_score` for how scores are computed once the game is over
+                let final_move_count = position.move_count() + value.unsigned_abs() as usize - 1;
+                let score = upper_bound(position) - final_move_count as isize + 1;
+
+                Some(if value > 0 { score } else { -score })
+            }
+        }
+    }
+
+    /// Returns the amount of positions that were evaluated.
+    pub fn len(&self) -> usize {
+        self.values
+            .iter()
+            .filter(|&&value| value != UNKNOWN)
+            .count()
+    }
+
+    /// Returns true if no position could be evaluated.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A transposition table that looks up positions in a tablebase first,
+/// so that the solver stops searching once it reaches one of its positions.
+pub struct TablebaseProbe<'a, T: EnumerableGame, TT> {
+    tablebase: &'a Tablebase<T>,
+    table: TT,
+}
+
+impl<'a, T: EnumerableGame, TT> TablebaseProbe<'a, T, TT> {
+    pub fn new(tablebase: &'a Tablebase<T>, table: TT) -> Self {
+        Self { tablebase, table }
+    }
+}
+
+impl<T, TT> TranspositionTable<T> for TablebaseProbe<'_, T, TT>
+where
+    T: EnumerableGame + Eq + Hash,
+    TT: TranspositionTable<T>,
+{
+    fn get(&self, board: &T) -> Option<Score> {
+        self.tablebase
+            .probe(board)
+            .map(Score::Exact)
+            .or_else(|| self.table.get(board))
+    }
+
+    fn insert(&mut self, board: T, score: Score) {
+        self.table.insert(board, score);
+    }
+
+    fn has(&self, board: &T) -> bool {
+        self.tablebase.probe(board).is_some() || self.table.has(board)
+    }
+}
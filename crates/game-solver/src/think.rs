@@ -0,0 +1,247 @@
+//! Thinking about a position for a fixed amount of time, as a program playing against a clock does.
+//! This requires the `rayon` feature to be enabled.
+//!
+//! [`think`] searches one move deeper at a time, scoring the moves of the position in parallel at every depth,
+//! and answers with what the deepest depth it finished found once the deadline passes.
+//! Every search shares a [`DepthCache`], so a depth starts from what the shallower ones stored,
+//! and each move is first searched in a narrow window around its score from the depth before.
+
+use std::{
+    hash::{BuildHasher, Hash},
+    sync::{atomic::AtomicBool, Arc},
+    time::Instant,
+};
+
+use rayon::prelude::*;
+
+use crate::{
+    game::{Game, GameState},
+    player::TwoPlayer,
+    session::deadline_token,
+    stats::Stats,
+    transposition::{DepthAwareTable, DepthCache},
+    DepthLimitedSearch, GameSolveError,
+};
+
+/// How far above and below its score from the depth before a move is first searched.
+///
+/// A score outside the window is only found out to be outside it,
+/// so the move is searched again without one.
+pub const ASPIRATION_WINDOW: isize = 1;
+
+/// What [`think`] found out about a position before its deadline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Thought<M> {
+    /// The best move of the deepest search that finished,
+    /// or the first possible move if not even the shallowest one did.
+    pub best_move: M,
+    /// The score of the best move for the player to move, looking `depth` moves ahead,
+    /// as [`solve_depth_limited`](crate::solve_depth_limited) scores it:
+    /// a position whose end the search couldn't see scores as a tie.
+    pub score: isize,
+    /// The score of every move, in the order of [`Game::possible_moves`], looking `depth` moves ahead.
+    /// Empty if not even the shallowest search finished.
+    pub scores: Vec<(M, isize)>,
+    /// How many moves ahead the deepest search that finished looked, or 0 if none did.
+    pub depth: usize,
+    /// Whether the deepest search saw the end of the game, so the scores are those of [`solve`](crate::solve).
+    pub complete: bool,
+    /// The best move, followed by the best moves of both sides that the table holds, up to `depth` moves.
+    pub principal_variation: Vec<M>,
+    /// How many positions were looked at, including those of the search the deadline cut short.
+    pub nodes: u64,
+}
+
+/// Searches `game` one move deeper at a time until `deadline`, or until the search sees the end of the game,
+/// scoring its moves in parallel at every depth, returning what the deepest search that finished found.
+/// None if the game is over.
+///
+/// The search the deadline cuts short is thrown away, as the moves it didn't get to can't be compared
+/// to the ones it did; it never stores the score of a position it didn't finish, so `table` stays valid,
+/// and can be kept for the next move.
+///
+/// ```
+/// use std::{
+///     hash::RandomState,
+///     sync::Arc,
+///     time::{Duration, Instant},
+/// };
+///
+/// use game_solver::{think::think, transposition::DepthCache};
+/// use games::naive_nim::Nim;
+///
+/// let game = Nim::new(vec![3, 5, 7]);
+/// let table = Arc::new(DepthCache::<_, RandomState>::with_capacity(1 << 16));
+/// let thought = think(&game, &table, Instant::now() + Duration::from_secs(60))
+///     .unwrap()
+///     .unwrap();
+///
+/// // the nim-sum of 3, 5, and 7 is 1, so the player to move wins by taking from any heap to cancel it
+/// assert!(thought.complete);
+/// assert!(thought.score > 0);
+/// assert_eq!(thought.principal_variation[0], thought.best_move);
+/// ```
+pub fn think<T, P, S>(
+    game: &T,
+    table: &Arc<DepthCache<T, S>>,
+    deadline: Instant,
+) -> Result<Option<Thought<T::Move>>, GameSolveError<T>>
+where
+    P: TwoPlayer + Send + Sync + 'static,
+    T: Game<Player = P> + Eq + Hash + Send + Sync + 'static,
+    T::Move: Send + Sync,
+    T::MoveError: Send,
+    S: BuildHasher + Default + Send + Sync + Clone + 'static,
+{
+    if game.state() != GameState::Playable {
+        return Ok(None);
+    }
+
+    let moves = game.possible_moves().collect::<Vec<_>>();
+    let Some(first_move) = moves.first().cloned() else {
+        return Ok(None);
+    };
+
+    let (token, _timer) = deadline_token(deadline);
+    let cancellation_token = Some(token);
+    let stats = Stats::new(game.player(), game.move_count());
+    // the positions past the last move can't be played, so looking this far ahead sees the end of every game
+    let moves_left = game
+        .max_moves()
+        .map(|max_moves| max_moves.saturating_sub(game.move_count()));
+
+    let mut thought = Thought {
+        best_move: first_move.clone(),
+        score: 0,
+        scores: Vec::new(),
+        depth: 0,
+        complete: false,
+        principal_variation: vec![first_move],
+        nodes: 0,
+    };
+
+    for depth in 1.. {
+        let previous_scores = (0..moves.len())
+            .map(|index| thought.scores.get(index).map(|(_, score)| *score))
+            .collect::<Vec<_>>();
+
+        let scores = moves
+            .par_iter()
+            .zip(previous_scores)
+            .with_max_len(1)
+            .map(|(m, previous)| {
+                let score =
+                    search_move(game, m, depth, previous, table, &stats, &cancellation_token)?;
+                Ok((m.clone(), score))
+            })
+            .collect::<Result<Vec<_>, _>>();
+
+        let scores = match scores {
+            Ok(scores) => scores,
+            Err(GameSolveError::CancellationTokenError) => break,
+            Err(err) => return Err(err),
+        };
+
+        // the first of the best moves, in the order of the game's moves
+        let (best_move, score) = scores
+            .iter()
+            .fold(
+                None,
+                |best: Option<&(T::Move, isize)>, move_score| match best {
+                    Some(best) if best.1 >= move_score.1 => Some(best),
+                    _ => Some(move_score),
+                },
+            )
+            .cloned()
+            .expect("the game has moves");
+
+        thought.principal_variation = principal_variation(game, &best_move, depth, table)?;
+        thought.best_move = best_move;
+        thought.score = score;
+        thought.scores = scores;
+        thought.depth = depth;
+
+        if moves_left.is_some_and(|moves_left| depth >= moves_left) {
+            thought.complete = true;
+            break;
+        }
+    }
+
+    thought.nodes = stats.states_explored.into_inner();
+    Ok(Some(thought))
+}
+
+/// The score of `m` in `game` for the player making it, looking `depth` moves ahead (including `m`),
+/// first in a window around its score from the depth before, if there was one.
+fn search_move<T, P, S>(
+    game: &T,
+    m: &T::Move,
+    depth: usize,
+    previous: Option<isize>,
+    table: &Arc<DepthCache<T, S>>,
+    stats: &Stats<T::Player>,
+    cancellation_token: &Option<Arc<AtomicBool>>,
+) -> Result<isize, GameSolveError<T>>
+where
+    P: TwoPlayer + 'static,
+    T: Game<Player = P> + Eq + Hash + Send + Sync + 'static,
+    S: BuildHasher + Default + Send + Sync + Clone + 'static,
+{
+    let mut board = game.clone();
+    board.make_move(m).map_err(GameSolveError::MoveError)?;
+
+    let mut table = Arc::clone(table);
+    let mut search = DepthLimitedSearch {
+        table: Some(&mut table),
+        evaluator: None,
+        quiescence: 0,
+        stats: Some(stats),
+        cancellation_token,
+    };
+
+    if let Some(previous) = previous {
+        let alpha = previous.saturating_sub(ASPIRATION_WINDOW);
+        let beta = previous.saturating_add(ASPIRATION_WINDOW);
+
+        // the search scores the position after the move for the other player, whose window is flipped
+        let score = -search.search(&board, depth - 1, -beta, -alpha)?;
+        if alpha < score && score < beta {
+            return Ok(score);
+        }
+    }
+
+    Ok(-search.search(&board, depth - 1, -isize::MAX, isize::MAX)?)
+}
+
+/// `best_move`, followed by the best moves the table holds after it, up to `depth` moves.
+fn principal_variation<T, S>(
+    game: &T,
+    best_move: &T::Move,
+    depth: usize,
+    table: &Arc<DepthCache<T, S>>,
+) -> Result<Vec<T::Move>, GameSolveError<T>>
+where
+    T: Game + Eq + Hash + Send + Sync + 'static,
+    S: BuildHasher + Default + Send + Sync + Clone + 'static,
+{
+    let mut line = vec![best_move.clone()];
+    let mut board = game.clone();
+    board
+        .make_move(best_move)
+        .map_err(GameSolveError::MoveError)?;
+
+    while line.len() < depth && board.state() == GameState::Playable {
+        let Some(m) = table
+            .get(&board)
+            .and_then(|entry| entry.best_move)
+            .and_then(|index| board.possible_moves().nth(index))
+        else {
+            break;
+        };
+
+        board.make_move(&m).map_err(GameSolveError::MoveError)?;
+        line.push(m);
+    }
+
+    Ok(line)
+}
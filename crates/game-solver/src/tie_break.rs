@@ -0,0 +1,105 @@
+//! Choosing among moves that score the same.
+//!
+//! Always playing the first of them makes every game from a position the same,
+//! which makes self-play repetitive and biases the statistics of an [arena](crate::arena).
+//! A [`TieBreaker`] can instead pick one at random, from a seed, or with a function of your own.
+
+use alloc::vec::Vec;
+
+use crate::hash::{splitmix64, SPLITMIX64_GAMMA};
+
+/// How to choose among moves that score the same.
+#[derive(Debug, Clone, Copy)]
+pub enum TieBreak<M> {
+    /// The first of them, in the order of [`Game::possible_moves`](crate::game::Game::possible_moves).
+    First,
+    /// One at random, from this seed.
+    ///
+    /// The choices are made with [SplitMix64](https://prng.di.unimi.it/splitmix64.c), seeded with the seed as it is,
+    /// so the same seed makes the same choices on every platform, and with every version of this crate.
+    Random(u64),
+    /// The one at the index this returns, given the tied moves in the order of
+    /// [`Game::possible_moves`](crate::game::Game::possible_moves). An index past the last move picks the last one.
+    Custom(fn(&[M]) -> usize),
+}
+
+/// Chooses among tied moves with a [`TieBreak`], one position after another.
+///
+/// A random tie break only depends on its seed and on the ties it was given before,
+/// so playing the same game with the same seed makes the same choices.
+#[derive(Debug, Clone)]
+pub struct TieBreaker<M> {
+    tie_break: TieBreak<M>,
+    /// The state of the random number generator of [`TieBreak::Random`].
+    state: u64,
+}
+
+impl<M> TieBreaker<M> {
+    pub fn new(tie_break: TieBreak<M>) -> Self {
+        let state = match tie_break {
+            TieBreak::Random(seed) => seed,
+            TieBreak::First | TieBreak::Custom(_) => 0,
+        };
+
+        Self { tie_break, state }
+    }
+
+    /// The index of the move to play out of `tied`, which can't be empty.
+    fn pick(&mut self, tied: &[M]) -> usize {
+        match self.tie_break {
+            TieBreak::First => 0,
+            // only actual choices draw a random number
+            TieBreak::Random(_) if tied.len() == 1 => 0,
+            TieBreak::Random(_) => {
+                let random = splitmix64(self.state);
+                self.state = self.state.wrapping_add(SPLITMIX64_GAMMA);
+
+                // scales the random number down to an index, which unlike `%` is as likely to be any of them
+                ((u128::from(random) * tied.len() as u128) >> 64) as usize
+            }
+            TieBreak::Custom(choose) => choose(tied).min(tied.len() - 1),
+        }
+    }
+
+    /// Chooses one of `tied`, or None if there are none.
+    pub fn choose<'a>(&mut self, tied: &'a [M]) -> Option<&'a M> {
+        if tied.is_empty() {
+            return None;
+        }
+
+        Some(&tied[self.pick(tied)])
+    }
+
+    /// The best of the `scored` moves, choosing among those tied for the best score.
+    pub fn best<S: Ord>(&mut self, scored: impl IntoIterator<Item = (M, S)>) -> Option<(M, S)> {
+        let mut best_score = None;
+        let mut best_moves = Vec::new();
+
+        for (m, score) in scored {
+            match &best_score {
+                Some(best) if score < *best => continue,
+                Some(best) if score == *best => best_moves.push(m),
+                _ => {
+                    best_score = Some(score);
+                    best_moves = alloc::vec![m];
+                }
+            }
+        }
+
+        let score = best_score?;
+        let index = self.pick(&best_moves);
+        Some((best_moves.swap_remove(index), score))
+    }
+}
+
+impl<M> Default for TieBreaker<M> {
+    fn default() -> Self {
+        Self::new(TieBreak::First)
+    }
+}
+
+impl<M> From<TieBreak<M>> for TieBreaker<M> {
+    fn from(tie_break: TieBreak<M>) -> Self {
+        Self::new(tie_break)
+    }
+}
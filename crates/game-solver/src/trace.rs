@@ -0,0 +1,67 @@
+//! Instrumentation of the search with [`tracing`](https://docs.rs/tracing), behind the `tracing` feature.
+//!
+//! Without the feature, these macros expand to nothing, so their arguments aren't even evaluated.
+//! With it, the search emits:
+//!
+//! - a `solve` span around each search, and a `root_move` span around the search of each move
+//!   by [`move_scores`](crate::move_scores) and `par_move_scores`,
+//! - at `INFO`, the score of each `solve` and how many nodes it searched,
+//! - at `DEBUG`, the null windows tried by `solve`, and the re-searches of principal variation search,
+//! - at `TRACE`, transposition table hits and stores, and beta cutoffs.
+//!
+//! Every event has `nodes`, the number of nodes searched so far by the current `solve`.
+
+#[cfg(feature = "tracing")]
+use std::cell::Cell;
+
+#[cfg(feature = "tracing")]
+thread_local! {
+    /// The nodes searched by the `solve` running on this thread, which never spans threads.
+    static NODES: Cell<u64> = const { Cell::new(0) };
+}
+
+/// Starts counting the nodes of a new `solve`.
+#[inline(always)]
+pub(crate) fn reset_nodes() {
+    #[cfg(feature = "tracing")]
+    NODES.set(0);
+}
+
+/// Counts a node searched by the current `solve`.
+#[inline(always)]
+pub(crate) fn count_node() {
+    #[cfg(feature = "tracing")]
+    NODES.set(NODES.get() + 1);
+}
+
+/// The nodes searched so far by the current `solve`.
+#[cfg(feature = "tracing")]
+pub(crate) fn nodes() -> u64 {
+    NODES.get()
+}
+
+/// Emits an event, like [`tracing::event!`](https://docs.rs/tracing/latest/tracing/macro.event.html).
+macro_rules! trace_event {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::event!($($arg)*);
+    };
+}
+
+/// Creates and enters a span, like [`tracing::span!`](https://docs.rs/tracing/latest/tracing/macro.span.html),
+/// which is exited when the returned guard is dropped.
+#[cfg(feature = "tracing")]
+macro_rules! trace_span {
+    ($($arg:tt)*) => {
+        ::tracing::span!($($arg)*).entered()
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_span {
+    ($($arg:tt)*) => {
+        ()
+    };
+}
+
+pub(crate) use {trace_event, trace_span};
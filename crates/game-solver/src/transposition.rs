@@ -1,19 +1,41 @@
 //! Transposition tables for memoization.
+//!
+//! Once a search is done, [`TranspositionTable::entry`] tells what it concluded about any position
+//! it reached, and the tables can be iterated over to dump or analyze everything it stored.
+//!
+//! Depth-limited searches use a [`DepthAwareTable`] instead, as their scores only hold as deep as they searched.
+//!
+//! Games with small scores can use a [`CompactTable`], which stores a hash of each position
+//! and its score in a few bytes instead of the position itself,
+//! optionally with enough more to [verify](Verify) that an entry is of the position looked up.
+//!
+//! Without the `std` feature, [`BTreeMap`] (for games that are [`Ord`]) and,
+//! with the `hashbrown` feature, [`hashbrown::HashMap`] are the tables available.
 
 #[cfg(feature = "rayon")]
-use moka::future::Cache;
+use alloc::sync::Arc;
 #[cfg(feature = "rayon")]
-use std::sync::Arc;
+use moka::future::Cache;
 
 use crate::game::Game;
+#[cfg(feature = "std")]
+use crate::hash::BuildIncrementalHasher;
 
+use alloc::collections::BTreeMap;
+#[cfg(any(feature = "std", feature = "hashbrown"))]
+use core::hash::BuildHasher;
+use core::hash::Hash;
+#[cfg(feature = "std")]
 use std::{
     collections::HashMap,
-    hash::{BuildHasher, Hash},
+    hash::RandomState,
+    marker::PhantomData,
+    sync::atomic::{AtomicU64, Ordering},
 };
 
 /// A score in a transposition table.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Score {
     /// The lower bound of the score.
     /// This generally doesn't bring too much benefit,
@@ -22,6 +44,35 @@ pub enum Score {
     /// The upper bound of the score,
     /// which helps get rid of many useless branches.
     UpperBound(isize),
+    /// The exact score, such as that of a solved position,
+    /// or one looked up in a [tablebase](crate::tablebase).
+    Exact(isize),
+}
+
+impl Score {
+    /// The score, or the bound on it.
+    pub fn value(self) -> isize {
+        match self {
+            Self::LowerBound(score) | Self::UpperBound(score) | Self::Exact(score) => score,
+        }
+    }
+}
+
+/// Everything a transposition table stores about a position, which is only its score.
+///
+/// Solves search every position to the end of the game, so there's no depth to keep, and they don't keep best moves.
+/// The [`DepthEntry`]s of depth-limited searches keep both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Entry {
+    /// The score of the position, or a bound on it.
+    pub score: Score,
+}
+
+impl From<Score> for Entry {
+    fn from(score: Score) -> Self {
+        Self { score }
+    }
 }
 
 /// A memoization strategy for a perfect-information sequential game.
@@ -34,8 +85,20 @@ pub trait TranspositionTable<T: Eq + Hash + Game> {
 
     /// Returns true if the board is in the transposition table.
     fn has(&self, board: &T) -> bool;
+
+    /// Get everything stored about a board, if it exists.
+    fn entry(&self, board: &T) -> Option<Entry> {
+        self.get(board).map(Entry::from)
+    }
+
+    /// The amount of lookups that found the entry of another position with the same hash,
+    /// which count as misses. Tables that can't tell such positions apart, or never store them, count none.
+    fn collisions(&self) -> u64 {
+        0
+    }
 }
 
+#[cfg(feature = "std")]
 impl<K: Eq + Hash + Game, S: BuildHasher + Default> TranspositionTable<K> for HashMap<K, Score, S> {
     fn get(&self, board: &K) -> Option<Score> {
         self.get(board).copied()
@@ -50,6 +113,429 @@ impl<K: Eq + Hash + Game, S: BuildHasher + Default> TranspositionTable<K> for Ha
     }
 }
 
+#[cfg(feature = "hashbrown")]
+impl<K: Eq + Hash + Game, S: BuildHasher + Default> TranspositionTable<K>
+    for hashbrown::HashMap<K, Score, S>
+{
+    fn get(&self, board: &K) -> Option<Score> {
+        self.get(board).copied()
+    }
+
+    fn insert(&mut self, board: K, score: Score) {
+        self.insert(board, score);
+    }
+
+    fn has(&self, board: &K) -> bool {
+        self.contains_key(board)
+    }
+}
+
+impl<K: Ord + Hash + Game> TranspositionTable<K> for BTreeMap<K, Score> {
+    fn get(&self, board: &K) -> Option<Score> {
+        self.get(board).copied()
+    }
+
+    fn insert(&mut self, board: K, score: Score) {
+        self.insert(board, score);
+    }
+
+    fn has(&self, board: &K) -> bool {
+        self.contains_key(board)
+    }
+}
+
+/// How a score is stored in a [`CompactTable`], such as [`i8`] for games whose scores
+/// are never more than 127 away from 0.
+pub trait ScoreRepr: Copy + Eq {
+    /// The smallest score that can be stored, which a lower bound reads back as no bound at all.
+    const MIN: Self;
+    /// The largest score that can be stored, which an upper bound reads back as no bound at all.
+    const MAX: Self;
+
+    /// The score as it's stored, or None if it doesn't fit.
+    fn from_score(score: isize) -> Option<Self>;
+
+    /// The score as it was stored.
+    fn to_score(self) -> isize;
+}
+
+macro_rules! impl_score_repr {
+    ($($repr:ty),*) => {
+        $(
+            impl ScoreRepr for $repr {
+                const MIN: Self = <$repr>::MIN;
+                const MAX: Self = <$repr>::MAX;
+
+                fn from_score(score: isize) -> Option<Self> {
+                    <$repr>::try_from(score).ok()
+                }
+
+                fn to_score(self) -> isize {
+                    self as isize
+                }
+            }
+        )*
+    };
+}
+
+impl_score_repr!(i8, i16, i32);
+
+/// A [`Score`] stored as a [`ScoreRepr`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompactScore<V> {
+    LowerBound(V),
+    UpperBound(V),
+    Exact(V),
+}
+
+#[cfg(feature = "std")]
+impl<V: ScoreRepr> CompactScore<V> {
+    /// The score as it's stored, or None if it's an exact score that doesn't fit.
+    ///
+    /// A bound that doesn't fit is widened, so it still holds: an upper bound above [`V::MAX`](ScoreRepr::MAX)
+    /// is stored as `V::MAX`, and a lower bound below [`V::MIN`](ScoreRepr::MIN) as `V::MIN`,
+    /// which both read back as no bound at all. A bound past the other end is as tight as `V` can make it.
+    fn new(score: Score) -> Option<Self> {
+        let saturated =
+            |score: isize| V::from_score(score).unwrap_or(if score < 0 { V::MIN } else { V::MAX });
+
+        match score {
+            Score::LowerBound(score) => Some(Self::LowerBound(saturated(score))),
+            Score::UpperBound(score) => Some(Self::UpperBound(saturated(score))),
+            Score::Exact(score) => V::from_score(score).map(Self::Exact),
+        }
+    }
+
+    /// The score as it was stored, or None if it's a bound that was widened to no bound at all.
+    fn score(self) -> Option<Score> {
+        match self {
+            Self::LowerBound(score) if score == V::MIN => None,
+            Self::UpperBound(score) if score == V::MAX => None,
+            Self::LowerBound(score) => Some(Score::LowerBound(score.to_score())),
+            Self::UpperBound(score) => Some(Score::UpperBound(score.to_score())),
+            Self::Exact(score) => Some(Score::Exact(score.to_score())),
+        }
+    }
+}
+
+/// How a [`CompactTable`] tells apart positions whose hashes collide,
+/// by storing something more about each position alongside its hash.
+#[cfg(feature = "std")]
+pub trait Verify<T> {
+    /// What's stored about a position to check lookups against.
+    type Check;
+
+    /// What's stored about `board`.
+    fn check(&self, board: T) -> Self::Check;
+
+    /// Returns true if `check` was stored about `board`,
+    /// rather than another position with the same hash.
+    fn matches(&self, check: &Self::Check, board: &T) -> bool;
+}
+
+/// Stores nothing more than the hash, so positions whose hashes collide share an entry.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Unverified;
+
+#[cfg(feature = "std")]
+impl<T> Verify<T> for Unverified {
+    type Check = ();
+
+    fn check(&self, _board: T) {}
+
+    fn matches(&self, _check: &(), _board: &T) -> bool {
+        true
+    }
+}
+
+/// Stores a second 64-bit hash of each position, with its own random keys,
+/// which makes sharing an entry as unlikely as a 128-bit collision for 8 more bytes per entry.
+///
+/// Both hashes are of what the position's [`Hash`] writes, so for games that only write their
+/// [`IncrementalHash`](crate::hash::IncrementalHash), positions that collide in it collide in both:
+/// use [`VerifyPosition`] for those.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default)]
+pub struct VerifyHash(RandomState);
+
+#[cfg(feature = "std")]
+impl<T: Hash> Verify<T> for VerifyHash {
+    type Check = u64;
+
+    fn check(&self, board: T) -> u64 {
+        self.0.hash_one(board)
+    }
+
+    fn matches(&self, check: &u64, board: &T) -> bool {
+        *check == self.0.hash_one(board)
+    }
+}
+
+/// Stores every position whole, which never lets two share an entry,
+/// for games small enough that their positions take little more than a hash.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VerifyPosition;
+
+#[cfg(feature = "std")]
+impl<T: Eq> Verify<T> for VerifyPosition {
+    type Check = T;
+
+    fn check(&self, board: T) -> T {
+        board
+    }
+
+    fn matches(&self, check: &T, board: &T) -> bool {
+        check == board
+    }
+}
+
+/// A transposition table that stores a 64-bit hash of each position, built by `S`,
+/// instead of the position itself, along with its score as a `V`,
+/// so an entry with an [`i8`] score takes 16 bytes, however large the position is.
+///
+/// Two positions with the same hash share an entry, which with 64 bits is unlikely
+/// for any table that fits in memory, but not impossible.
+/// Tables built with [`CompactTable::verified`] or [`CompactTable::with_positions`]
+/// store enough more about each position to tell them apart,
+/// and treat a lookup that finds the entry of another position as a miss,
+/// counting it in [`TranspositionTable::collisions`].
+/// Exact scores that don't fit in `V` aren't stored, and bounds that don't are widened until they do,
+/// so a table with a narrow `V` only ever finds less than a wider one would, never anything wrong.
+///
+/// ```
+/// use std::{
+///     collections::HashMap,
+///     hash::{BuildHasher, DefaultHasher, Hasher},
+/// };
+///
+/// use game_solver::{
+///     solve,
+///     transposition::{CompactTable, TranspositionTable},
+/// };
+/// use games::naive_nim::Nim;
+///
+/// /// Hashes every position to one of 2 keys, so most of them collide.
+/// #[derive(Default)]
+/// struct TwoKeys;
+///
+/// impl BuildHasher for TwoKeys {
+///     type Hasher = TwoKeysHasher;
+///
+///     fn build_hasher(&self) -> TwoKeysHasher {
+///         TwoKeysHasher(DefaultHasher::new())
+///     }
+/// }
+///
+/// struct TwoKeysHasher(DefaultHasher);
+///
+/// impl Hasher for TwoKeysHasher {
+///     fn write(&mut self, bytes: &[u8]) {
+///         self.0.write(bytes);
+///     }
+///
+///     fn finish(&self) -> u64 {
+///         self.0.finish() % 2
+///     }
+/// }
+///
+/// let game = Nim::new(vec![3, 5, 4]);
+/// let expected = solve(&game, &mut HashMap::new(), None, &None).unwrap();
+///
+/// let mut table = CompactTable::<Nim, i8, TwoKeys>::verified();
+/// assert_eq!(solve(&game, &mut table, None, &None).unwrap(), expected);
+/// assert!(table.collisions() > 0);
+/// ```
+#[cfg(feature = "std")]
+pub struct CompactTable<T, V, S = RandomState, C: Verify<T> = Unverified> {
+    entries: HashMap<u64, (CompactScore<V>, C::Check), BuildIncrementalHasher>,
+    hasher: S,
+    verify: C,
+    collisions: AtomicU64,
+    game: PhantomData<fn(&T)>,
+}
+
+#[cfg(feature = "std")]
+impl<T, V, S: Default> CompactTable<T, V, S> {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::with_verify(Unverified)
+    }
+
+    /// Creates an empty table that stores a second hash of each position to tell those that collide apart.
+    /// See [`VerifyHash`].
+    pub fn verified() -> CompactTable<T, V, S, VerifyHash>
+    where
+        T: Hash,
+    {
+        CompactTable::with_verify(VerifyHash::default())
+    }
+
+    /// Creates an empty table that stores every position whole to tell those that collide apart.
+    /// See [`VerifyPosition`].
+    pub fn with_positions() -> CompactTable<T, V, S, VerifyPosition>
+    where
+        T: Eq,
+    {
+        CompactTable::with_verify(VerifyPosition)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, V, S: Default, C: Verify<T>> CompactTable<T, V, S, C> {
+    /// The size of every entry in the table, not counting the table's own overhead,
+    /// nor what a position stored whole holds on the heap.
+    pub const ENTRY_SIZE: usize = core::mem::size_of::<(u64, (CompactScore<V>, C::Check))>();
+
+    fn with_verify(verify: C) -> Self {
+        Self {
+            entries: HashMap::default(),
+            hasher: S::default(),
+            verify,
+            collisions: AtomicU64::new(0),
+            game: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, V, S, C: Verify<T>> CompactTable<T, V, S, C> {
+    /// The amount of positions in the table.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the table has no positions.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, V, S: Default> Default for CompactTable<T, V, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Eq + Hash + Game, V: ScoreRepr, S: BuildHasher, C: Verify<T>> TranspositionTable<T>
+    for CompactTable<T, V, S, C>
+{
+    fn get(&self, board: &T) -> Option<Score> {
+        let (score, check) = self.entries.get(&self.hasher.hash_one(board))?;
+
+        if self.verify.matches(check, board) {
+            score.score()
+        } else {
+            self.collisions.fetch_add(1, Ordering::Relaxed);
+            None
+        }
+    }
+
+    fn insert(&mut self, board: T, score: Score) {
+        // what's known about the position still holds, so it's kept if the score can't be stored
+        let Some(score) = CompactScore::new(score) else {
+            return;
+        };
+
+        // the entry of another position with the same hash is replaced
+        let hash = self.hasher.hash_one(&board);
+        self.entries.insert(hash, (score, self.verify.check(board)));
+    }
+
+    fn has(&self, board: &T) -> bool {
+        self.entries
+            .get(&self.hasher.hash_one(board))
+            .is_some_and(|(_, check)| self.verify.matches(check, board))
+    }
+
+    fn collisions(&self) -> u64 {
+        self.collisions.load(Ordering::Relaxed)
+    }
+}
+
+/// Everything a depth-limited search stores about a position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepthEntry {
+    /// The score of the position, or a bound on it, found by looking `depth` moves ahead.
+    pub score: Score,
+    /// How many moves past the position the search looked.
+    pub depth: usize,
+    /// The index of the best move found, in the order of [`Game::possible_moves`].
+    /// Deeper searches search it first, even though they can't use the score.
+    pub best_move: Option<usize>,
+}
+
+impl DepthEntry {
+    /// The score, if the search that stored it looked at least `depth` moves ahead.
+    ///
+    /// A shallower search may have missed how the game ends, so its score can't stand in for a deeper one,
+    /// but a deeper search's score is as good as this depth's, or better.
+    pub fn score_at(&self, depth: usize) -> Option<Score> {
+        (self.depth >= depth).then_some(self.score)
+    }
+
+    /// Whether a table should store this entry instead of what it has, which it does unless that was searched deeper.
+    fn replaces(&self, stored: Option<&DepthEntry>) -> bool {
+        stored.map_or(true, |stored| stored.depth <= self.depth)
+    }
+}
+
+/// A memoization strategy for depth-limited searches, which keeps how deep each score was searched.
+pub trait DepthAwareTable<T: Game> {
+    /// Get everything stored about a board, if it exists.
+    fn get(&self, board: &T) -> Option<DepthEntry>;
+
+    /// Insert a board into the table, replacing what was stored about it,
+    /// unless that was searched deeper, which is kept instead.
+    fn insert(&mut self, board: T, entry: DepthEntry);
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + Hash + Game, S: BuildHasher + Default> DepthAwareTable<K>
+    for HashMap<K, DepthEntry, S>
+{
+    fn get(&self, board: &K) -> Option<DepthEntry> {
+        self.get(board).copied()
+    }
+
+    fn insert(&mut self, board: K, entry: DepthEntry) {
+        if entry.replaces(self.get(&board)) {
+            self.insert(board, entry);
+        }
+    }
+}
+
+#[cfg(feature = "hashbrown")]
+impl<K: Eq + Hash + Game, S: BuildHasher + Default> DepthAwareTable<K>
+    for hashbrown::HashMap<K, DepthEntry, S>
+{
+    fn get(&self, board: &K) -> Option<DepthEntry> {
+        self.get(board).copied()
+    }
+
+    fn insert(&mut self, board: K, entry: DepthEntry) {
+        if entry.replaces(self.get(&board)) {
+            self.insert(board, entry);
+        }
+    }
+}
+
+impl<K: Ord + Game> DepthAwareTable<K> for BTreeMap<K, DepthEntry> {
+    fn get(&self, board: &K) -> Option<DepthEntry> {
+        self.get(board).copied()
+    }
+
+    fn insert(&mut self, board: K, entry: DepthEntry) {
+        if entry.replaces(self.get(&board)) {
+            self.insert(board, entry);
+        }
+    }
+}
+
 /// Powerful transposition table that uses an underlying concurrent
 /// [LFU](https://en.wikipedia.org/wiki/Least_frequently_used) cache,
 /// powered by [moka](https://github.com/moka-rs/moka).
@@ -77,7 +563,7 @@ impl<
     /// an estimated three fourths of the remaining memory.
     #[must_use]
     pub fn new() -> Self {
-        let score_size = std::mem::size_of::<Score>() as u64;
+        let score_size = core::mem::size_of::<Score>() as u64;
 
         Self::with_capacity(
             // get three fourths of the memory, and divide that by the size of a score
@@ -85,6 +571,20 @@ impl<
             (sysinfo::System::new_all().total_memory() * 3 / 4) / score_size,
         )
     }
+
+    /// Iterates over every position in the cache, along with its entry.
+    ///
+    /// Positions inserted or evicted while iterating may or may not be seen.
+    pub fn iter(&self) -> impl Iterator<Item = (Arc<K>, Entry)> + '_ {
+        self.0
+            .iter()
+            .map(|(board, score)| (board, Entry::from(score)))
+    }
+
+    /// The amount of positions in the cache, which may lag behind recent insertions.
+    pub fn entry_count(&self) -> u64 {
+        self.0.entry_count()
+    }
 }
 
 #[cfg(feature = "rayon")]
@@ -116,3 +616,70 @@ impl<
         self.0.contains_key(board)
     }
 }
+
+/// A [`DepthAwareTable`] that threads share, like [`TranspositionCache`] for [`TranspositionTable`]s,
+/// such as the searches of the root moves by [`think`](crate::think::think).
+#[cfg(feature = "rayon")]
+pub struct DepthCache<K: Eq + Hash + Game + Send + Sync + 'static, S: BuildHasher + Default>(
+    Cache<K, DepthEntry, S>,
+);
+
+#[cfg(feature = "rayon")]
+impl<
+        K: Eq + Hash + Game + Send + Sync,
+        S: BuildHasher + Default + Send + Sync + Clone + 'static,
+    > DepthCache<K, S>
+{
+    /// Create a new depth cache with the given capacity and hasher.
+    pub fn with_capacity(capacity: u64) -> Self {
+        Self(
+            Cache::builder()
+                .max_capacity(capacity)
+                .build_with_hasher(S::default()),
+        )
+    }
+
+    /// Create a new depth cache with
+    /// an estimated three fourths of the remaining memory.
+    #[must_use]
+    pub fn new() -> Self {
+        let entry_size = core::mem::size_of::<DepthEntry>() as u64;
+
+        Self::with_capacity((sysinfo::System::new_all().total_memory() * 3 / 4) / entry_size)
+    }
+
+    /// The amount of positions in the cache, which may lag behind recent insertions.
+    pub fn entry_count(&self) -> u64 {
+        self.0.entry_count()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<
+        K: Eq + Hash + Game + Send + Sync,
+        S: BuildHasher + Default + Send + Sync + Clone + 'static,
+    > Default for DepthCache<K, S>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<
+        K: Eq + Hash + Game + Send + Sync + 'static,
+        S: BuildHasher + Default + Send + Sync + Clone + 'static,
+    > DepthAwareTable<K> for Arc<DepthCache<K, S>>
+{
+    fn get(&self, board: &K) -> Option<DepthEntry> {
+        futures::executor::block_on(self.0.get(board))
+    }
+
+    fn insert(&mut self, board: K, entry: DepthEntry) {
+        // another thread may insert between these, which only loses one of the entries
+        let stored = futures::executor::block_on(self.0.get(&board));
+        if entry.replaces(stored.as_ref()) {
+            futures::executor::block_on(self.0.insert(board, entry));
+        }
+    }
+}
@@ -0,0 +1,427 @@
+//! Checking the solver against a brute force search, to catch the optimizations
+//! (transposition table bounds, windows, move ordering, parallel search) changing a score.
+//!
+//! The reference, [`reference_score`], is minimax at its plainest: it plays out every line of every position,
+//! without pruning, a table, or anything else the solver does, so it shares none of the solver's code.
+//! That makes it far too slow for anything but small trees, which is why it gives up past a number of positions.
+//!
+//! [`verify_position`] compares it to each of a list of [`Check`]s, and [`shortest_disagreement`] turns
+//! a position they disagree about into the fewest moves from the start that still disagree.
+
+use std::{collections::HashMap, hash::Hash};
+
+use crate::{
+    game::{Game, GameState},
+    move_scores,
+    ordering::MoveOrdering,
+    player::TwoPlayer,
+    solve, solve_ordered, GameSolveError,
+};
+
+/// What a [`Check`] found a position to be worth.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Found<M> {
+    /// The score of the position, for the player to move.
+    Score(isize),
+    /// The score of every move, for the player making it, in the order of [`Game::possible_moves`].
+    MoveScores(MoveScores<M>),
+}
+
+/// Moves along with their scores, for the player making them.
+pub type MoveScores<M> = Vec<(M, isize)>;
+
+/// Solves a position from scratch, for a [`Check`].
+pub type CheckFn<T> = fn(&T) -> Result<Found<<T as Game>::Move>, GameSolveError<T>>;
+
+/// A part of the solver to check against the reference.
+pub struct Check<T: Game> {
+    /// The name the check is reported by, such as the function it calls.
+    pub name: &'static str,
+    pub run: CheckFn<T>,
+}
+
+/// [`solve`], [`move_scores`] (sharing a table between the moves),
+/// and [`solve_ordered`] with [`MoveOrdering::TtFirst`], each with a new `HashMap` table.
+pub fn checks<T, P>() -> Vec<Check<T>>
+where
+    P: TwoPlayer + 'static,
+    T: Game<Player = P> + Eq + Hash,
+{
+    vec![
+        Check {
+            name: "solve",
+            run: |game| Ok(Found::Score(solve(game, &mut HashMap::new(), None, &None)?)),
+        },
+        Check {
+            name: "move_scores",
+            run: |game| {
+                let mut table = HashMap::new();
+                Ok(Found::MoveScores(
+                    move_scores(game, &mut table, None, &None).collect::<Result<_, _>>()?,
+                ))
+            },
+        },
+        Check {
+            name: "tt_first",
+            run: |game| {
+                Ok(Found::Score(solve_ordered(
+                    game,
+                    &mut HashMap::new(),
+                    None,
+                    &None,
+                    &MoveOrdering::TtFirst,
+                )?))
+            },
+        },
+    ]
+}
+
+/// The [`checks`], along with [`par_move_scores`](crate::par_move_scores).
+/// This requires the `rayon` feature to be enabled.
+#[cfg(feature = "rayon")]
+pub fn parallel_checks<T, P>() -> Vec<Check<T>>
+where
+    P: TwoPlayer + Send + Sync + 'static,
+    T: Game<Player = P> + Eq + Hash + Send + Sync + 'static,
+    T::Move: Send + Sync,
+    T::MoveError: Send + Sync,
+{
+    let mut checks = checks();
+    checks.push(Check {
+        name: "par_move_scores",
+        run: |game| {
+            Ok(Found::MoveScores(
+                crate::par_move_scores(game, None, &None)
+                    .into_iter()
+                    .collect::<Result<_, _>>()?,
+            ))
+        },
+    });
+    checks
+}
+
+/// The score [`solve`] should give `game`, found by minimax over every line of play.
+/// None if that takes more than `node_limit` positions.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use game_solver::{solve, verify::reference_score};
+/// use games::naive_nim::Nim;
+///
+/// let game = Nim::new(vec![1, 2, 2]);
+/// let score = solve(&game, &mut HashMap::new(), None, &None).unwrap();
+/// assert_eq!(reference_score(&game, 10_000).unwrap(), Some(score));
+/// // the tree has more than 10 positions
+/// assert_eq!(reference_score(&game, 10).unwrap(), None);
+/// ```
+pub fn reference_score<T: Game>(
+    game: &T,
+    node_limit: u64,
+) -> Result<Option<isize>, GameSolveError<T>> {
+    let mut nodes_left = node_limit;
+    minimax(game, &mut nodes_left)
+}
+
+/// The score of every move of `game` for the player making it, by [`reference_score`],
+/// which shares `node_limit` between the moves.
+pub fn reference_move_scores<T: Game>(
+    game: &T,
+    node_limit: u64,
+) -> Result<Option<MoveScores<T::Move>>, GameSolveError<T>> {
+    let mut nodes_left = node_limit;
+    let mut scores = Vec::new();
+
+    for m in game.possible_moves() {
+        let mut after = game.clone();
+        after.make_move(&m).map_err(GameSolveError::MoveError)?;
+
+        let Some(score) = minimax(&after, &mut nodes_left)? else {
+            return Ok(None);
+        };
+        scores.push((m, -score));
+    }
+
+    Ok(Some(scores))
+}
+
+fn minimax<T: Game>(game: &T, nodes_left: &mut u64) -> Result<Option<isize>, GameSolveError<T>> {
+    if *nodes_left == 0 {
+        return Ok(None);
+    }
+    *nodes_left -= 1;
+
+    if let Some(score) = game.status().score(game) {
+        return Ok(Some(score));
+    }
+
+    let mut best = None;
+    for m in game.possible_moves() {
+        let mut after = game.clone();
+        after.make_move(&m).map_err(GameSolveError::MoveError)?;
+
+        let Some(score) = minimax(&after, nodes_left)? else {
+            return Ok(None);
+        };
+        best = Some(best.map_or(-score, |best: isize| best.max(-score)));
+    }
+
+    Ok(Some(
+        best.expect("a position that isn't over has a possible move"),
+    ))
+}
+
+/// A score a [`Check`] gave that the reference doesn't.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement<M> {
+    /// The name of the check.
+    pub check: &'static str,
+    /// The move whose score is wrong, or None if it's the score of the position.
+    pub played: Option<M>,
+    /// The score by the reference.
+    pub expected: isize,
+    /// The score by the check.
+    pub found: isize,
+}
+
+/// How [`verify_position`] went.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict<M> {
+    /// Every check gave the reference's scores.
+    Agrees,
+    /// The reference gave up, as the position has more than the node limit.
+    TooLarge,
+    /// The first score a check got wrong.
+    Disagrees(Disagreement<M>),
+}
+
+/// Runs every check on `game`, comparing what it found to [`reference_move_scores`],
+/// if the reference can search `game` in `node_limit` positions.
+///
+/// ```
+/// use game_solver::verify::{checks, verify_position, Verdict};
+/// use games::naive_nim::Nim;
+///
+/// let game = Nim::new(vec![2, 3]);
+/// assert_eq!(verify_position(&game, &checks(), 100_000).unwrap(), Verdict::Agrees);
+/// ```
+pub fn verify_position<T: Game>(
+    game: &T,
+    checks: &[Check<T>],
+    node_limit: u64,
+) -> Result<Verdict<T::Move>, GameSolveError<T>> {
+    let reference = if game.state() == GameState::Playable {
+        let Some(scores) = reference_move_scores(game, node_limit)? else {
+            return Ok(Verdict::TooLarge);
+        };
+        let score = scores
+            .iter()
+            .map(|(_, score)| *score)
+            .max()
+            .expect("a position that isn't over has a possible move");
+        (score, scores)
+    } else {
+        let Some(score) = reference_score(game, node_limit)? else {
+            return Ok(Verdict::TooLarge);
+        };
+        (score, Vec::new())
+    };
+
+    Ok(match first_disagreement(game, checks, &reference)? {
+        Some(disagreement) => Verdict::Disagrees(disagreement),
+        None => Verdict::Agrees,
+    })
+}
+
+/// Runs every check on `game`, returning the first score one of them gives that `reference`,
+/// the score of `game` and the scores of its moves, doesn't.
+pub(crate) fn first_disagreement<T: Game>(
+    game: &T,
+    checks: &[Check<T>],
+    reference: &(isize, MoveScores<T::Move>),
+) -> Result<Option<Disagreement<T::Move>>, GameSolveError<T>> {
+    for check in checks {
+        if let Some(disagreement) = compare(check.name, reference, (check.run)(game)?) {
+            return Ok(Some(disagreement));
+        }
+    }
+
+    Ok(None)
+}
+
+/// The first score of `found` that isn't that of `reference`, its score and its moves' scores.
+fn compare<M: Clone>(
+    check: &'static str,
+    (score, scores): &(isize, Vec<(M, isize)>),
+    found: Found<M>,
+) -> Option<Disagreement<M>> {
+    match found {
+        Found::Score(found) => (found != *score).then_some(Disagreement {
+            check,
+            played: None,
+            expected: *score,
+            found,
+        }),
+        Found::MoveScores(found) => {
+            scores
+                .iter()
+                .zip(found)
+                .find_map(|((m, expected), (_, found))| {
+                    (found != *expected).then(|| Disagreement {
+                        check,
+                        played: Some(m.clone()),
+                        expected: *expected,
+                        found,
+                    })
+                })
+        }
+    }
+}
+
+/// A position the solver and the reference disagree about, and how to reach it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch<M> {
+    /// The moves from the start to the position.
+    pub moves: Vec<M>,
+    /// What they disagree about.
+    pub disagreement: Disagreement<M>,
+}
+
+/// The fewest of `moves` (played from `start`) that reach a position where the checks still disagree
+/// with the reference, as the smallest reproduction of a disagreement after all of them.
+/// None if they agree about every position along the way that the reference can search in `node_limit` positions.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use game_solver::{
+///     game::Game,
+///     solve,
+///     verify::{shortest_disagreement, Check, Found},
+/// };
+/// use games::naive_nim::Nim;
+///
+/// // a broken solver, which calls every position after the first two moves a tie
+/// let broken = Check::<Nim> {
+///     name: "broken",
+///     run: |game| match game.move_count() {
+///         0 | 1 => Ok(Found::Score(solve(game, &mut HashMap::new(), None, &None)?)),
+///         _ => Ok(Found::Score(0)),
+///     },
+/// };
+///
+/// let game = Nim::new(vec![1, 1, 1, 1]);
+/// let mut moves = Vec::new();
+/// let mut position = game.clone();
+/// while let Some(m) = position.possible_moves().next() {
+///     position.make_move(&m).unwrap();
+///     moves.push(m);
+/// }
+///
+/// let mismatch = shortest_disagreement(&game, &moves, &[broken], 100_000)
+///     .unwrap()
+///     .unwrap();
+/// assert_eq!(mismatch.moves.len(), 2);
+/// assert_eq!(mismatch.disagreement.found, 0);
+/// ```
+pub fn shortest_disagreement<T: Game>(
+    start: &T,
+    moves: &[T::Move],
+    checks: &[Check<T>],
+    node_limit: u64,
+) -> Result<Option<Mismatch<T::Move>>, GameSolveError<T>> {
+    let mut game = start.clone();
+
+    for played in 0..=moves.len() {
+        if played > 0 {
+            game.make_move(&moves[played - 1])
+                .map_err(GameSolveError::MoveError)?;
+        }
+
+        if let Verdict::Disagrees(disagreement) = verify_position(&game, checks, node_limit)? {
+            return Ok(Some(Mismatch {
+                moves: moves[..played].to_vec(),
+                disagreement,
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
+/// How [`verify_random`] went.
+#[cfg(feature = "rand")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verification<M> {
+    /// How many positions every check agreed about.
+    pub agreed: usize,
+    /// How many positions had too large a tree for the reference.
+    pub too_large: usize,
+    /// The positions some check disagreed about, each reached in as few moves as it can be.
+    pub mismatches: Vec<Mismatch<M>>,
+}
+
+/// Verifies `samples` random positions: each is a random number of moves into a random game from `start`,
+/// leaving out the end of the game. Mismatches are shortened with [`shortest_disagreement`].
+/// This requires the `rand` feature to be enabled.
+///
+/// ```
+/// use game_solver::verify::{checks, verify_random};
+/// use games::naive_nim::Nim;
+/// use rand::{rngs::StdRng, SeedableRng};
+///
+/// let game = Nim::new(vec![2, 3, 4]);
+/// let verification =
+///     verify_random(&game, &checks(), 20, 100_000, &mut StdRng::seed_from_u64(1)).unwrap();
+/// assert_eq!(verification.agreed + verification.too_large, 20);
+/// assert!(verification.mismatches.is_empty());
+/// ```
+#[cfg(feature = "rand")]
+pub fn verify_random<T: Game>(
+    start: &T,
+    checks: &[Check<T>],
+    samples: usize,
+    node_limit: u64,
+    rng: &mut impl rand::Rng,
+) -> Result<Verification<T::Move>, GameSolveError<T>> {
+    use rand::seq::IteratorRandom;
+
+    let mut verification = Verification {
+        agreed: 0,
+        too_large: 0,
+        mismatches: Vec::new(),
+    };
+
+    for _ in 0..samples {
+        let mut line = Vec::new();
+        let mut game = start.clone();
+        while game.state() == GameState::Playable {
+            let m = game
+                .possible_moves()
+                .choose(rng)
+                .expect("a position that isn't over has a possible move");
+            game.make_move(&m).map_err(GameSolveError::MoveError)?;
+            line.push(m);
+        }
+        // the last move ends the game, which leaves nothing to search
+        line.truncate(rng.gen_range(0..line.len().max(1)));
+
+        let mut game = start.clone();
+        for m in &line {
+            game.make_move(m).map_err(GameSolveError::MoveError)?;
+        }
+
+        match verify_position(&game, checks, node_limit)? {
+            Verdict::Agrees => verification.agreed += 1,
+            Verdict::TooLarge => verification.too_large += 1,
+            Verdict::Disagrees(disagreement) => verification.mismatches.push(
+                shortest_disagreement(start, &line, checks, node_limit)?.unwrap_or(Mismatch {
+                    moves: line,
+                    disagreement,
+                }),
+            ),
+        }
+    }
+
+    Ok(verification)
+}
@@ -0,0 +1,14 @@
+use anyhow::Result;
+use clap::Parser;
+use games::util::cli::tui::run_tui;
+
+/// Play and analyze the games played on a grid in the terminal: pick a game, choose moves with the cursor,
+/// and watch the solver score every move of the position as it searches.
+#[derive(Parser)]
+#[command(version, about, long_about = None)]
+struct Cli {}
+
+fn main() -> Result<()> {
+    Cli::parse();
+    run_tui()
+}
@@ -0,0 +1,16 @@
+use anyhow::Result;
+use clap::Parser;
+use games::util::cli::http::{serve_http, HttpArgs};
+
+/// Analyze positions over HTTP: `POST /solve` solves a position, and `POST /moves` scores its moves.
+/// Both take a JSON position, such as `{"game": "naive-nim", "position": {"heaps": [3, 5], "move_count": 0}}`.
+#[derive(Parser)]
+#[command(version, about, long_about = None)]
+struct Cli {
+    #[command(flatten)]
+    args: HttpArgs,
+}
+
+fn main() -> Result<()> {
+    serve_http(&Cli::parse().args)
+}
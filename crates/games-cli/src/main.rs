@@ -1,34 +1,175 @@
-use anyhow::Result;
-use clap::Parser;
+mod verbose;
+
+use anyhow::{anyhow, Result};
+use clap::{ArgAction, Parser, Subcommand};
+use rayon::ThreadPoolBuilder;
+use std::{env, io, process::ExitCode};
+use tracing::Level;
+
 use games::{
-    chomp::Chomp, domineering::Domineering, naive_nim::Nim, order_and_chaos::OrderAndChaos,
-    reversi::Reversi, sprouts::Sprouts, tic_tac_toe::TicTacToe, util::cli::play, Games,
+    util::cli::{
+        arena::{run_arena, ArenaArgs},
+        bench::{bench, BenchArgs},
+        census::{run_census, CensusArgs},
+        describe::{describe_game, list_games},
+        endgame::{run_endgame, EndgameArgs},
+        exit::{self, invalid},
+        exploit::{run_exploit, ExploitArgs},
+        openings::{run_openings, OpeningsArgs},
+        perft::{run_perft, PerftArgs},
+        play,
+        precompute::{run_precompute, PrecomputeArgs},
+        puzzle::{run_puzzles, PuzzleArgs},
+        serve::serve,
+        verify::{run_verify, VerifyArgs},
+        CliOptions, OutputFormat,
+    },
+    with_game, GameKind, Games,
 };
+use verbose::StderrSubscriber;
 
 /// `game-solver` is a solving utility that helps analyze various combinatorial games.
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
 struct Cli {
     #[command(subcommand)]
-    command: Games,
-    #[arg(short, long)]
-    plain: bool,
+    command: Command,
+    #[command(flatten)]
+    options: CliOptions,
+    /// Log the solver's search to stderr (best used with --plain): -v for the score of
+    /// each search, -vv for the windows it tries, and -vvv for every cutoff and table lookup.
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(flatten)]
+    Game(Games),
+    /// Play two agents against each other over many games, reporting how often the first one wins.
+    Arena(ArenaArgs),
+    /// Benchmark the solver on a fixed set of positions for each game.
+    Bench(BenchArgs),
+    /// Count the positions of a game's tree, up to --depth moves: in all, distinct, and distinct up to symmetry,
+    /// along with how its games end and how many moves there are at each ply.
+    Census(CensusArgs),
+    /// Describe a game: its rules, the parameters it's set up with, and how its moves are written.
+    Describe {
+        /// The game to describe.
+        game: GameKind,
+    },
+    /// Solve every position of Nim or Tic-Tac-Toe that ends within --plies moves, checking the solver's scores
+    /// against retrograde analysis, and counting how many positions the player to move wins, loses, and ties.
+    Endgame(EndgameArgs),
+    /// Measure how much a fixed policy, such as random play, gives away against perfect play.
+    Exploit(ExploitArgs),
+    /// Count the move sequences of a given length from a position, to validate move generation.
+    Perft(PerftArgs),
+    /// List every game, with what it's about.
+    List,
+    /// Solve every opening of a game up to --depth moves, leaving out those symmetric to another,
+    /// and print their values as a tree, with how many continuations of each win, tie, and lose.
+    Openings(OpeningsArgs),
+    /// Solve the positions listed in a manifest, writing their analyses to a results file,
+    /// and skipping those whose results an earlier run already wrote.
+    Precompute(PrecomputeArgs),
+    /// Generate puzzles: random positions where only one move wins.
+    Puzzle(PuzzleArgs),
+    /// Read commands from stdin (such as `newgame tic-tac-toe`, `move 1-1`, or `go`),
+    /// answering each on stdout, to drive the solver from another program.
+    Serve,
+    /// Check the solver against a brute force search on random positions small enough for it,
+    /// printing the fewest moves that reach any position they disagree about.
+    Verify(VerifyArgs),
 }
 
-fn main() -> Result<()> {
-    let cli = Cli::parse();
+/// Exits with the codes [`exit`] lists: 0 on success, 2 for invalid arguments, moves, or positions,
+/// 3 for a solve stopped before it finished, and 1 for anything else (101 is left to panics).
+fn main() -> ExitCode {
+    let cli = match Cli::try_parse() {
+        Ok(cli) => cli,
+        Err(err) => return unparsed(err),
+    };
 
-    match cli.command {
-        Games::Reversi(args) => play::<Reversi>(args.try_into().unwrap(), cli.plain),
-        Games::TicTacToe(args) => play::<TicTacToe>(args.try_into().unwrap(), cli.plain),
-        Games::OrderAndChaos(args) => {
-            play::<OrderAndChaos<6, 6, 5, 6>>(args.try_into().unwrap(), cli.plain)
-        }
-        Games::NaiveNim(args) => play::<Nim>(args.try_into().unwrap(), cli.plain),
-        Games::Domineering(args) => play::<Domineering<5, 5>>(args.try_into().unwrap(), cli.plain),
-        Games::Chomp(args) => play::<Chomp>(args.try_into().unwrap(), cli.plain),
-        Games::Sprouts(args) => play::<Sprouts>(args.try_into().unwrap(), cli.plain),
+    let format = cli.options.format;
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => exit::report(&err, format),
+    }
+}
+
+/// Exits for arguments that couldn't be parsed, as [`Cli::parse`] does,
+/// except that the error is reported as JSON if `--format json` was given.
+fn unparsed(err: clap::Error) -> ExitCode {
+    if !err.use_stderr() || !json_requested() {
+        err.exit();
+    }
+
+    let rendered = err.render().to_string();
+    let message = rendered.lines().next().unwrap_or_default();
+    let message = message.strip_prefix("error: ").unwrap_or(message);
+
+    exit::report(&invalid(anyhow!("{message}")), OutputFormat::Json)
+}
+
+/// Whether the arguments, which couldn't be parsed, ask for `--format json`.
+fn json_requested() -> bool {
+    let args = env::args_os().collect::<Vec<_>>();
+
+    args.iter().any(|arg| arg == "--format=json")
+        || args
+            .windows(2)
+            .any(|pair| pair[0] == "--format" && pair[1] == "json")
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let level = match cli.verbose {
+        0 => None,
+        1 => Some(Level::INFO),
+        2 => Some(Level::DEBUG),
+        _ => Some(Level::TRACE),
     };
+    if let Some(level) = level {
+        tracing::subscriber::set_global_default(StderrSubscriber::new(level))?;
+    }
+
+    // everything runs in this pool, so parallel solving uses --threads threads
+    let pool = ThreadPoolBuilder::new()
+        .num_threads(cli.options.threads.map_or(0, |threads| threads as usize))
+        .build()?;
 
-    Ok(())
+    pool.install(|| match cli.command {
+        Command::Game(args) => with_game!(args, game => play(game, &cli.options)),
+        Command::Arena(args) => with_game!(args.game.clone(), game => run_arena(game, &args)),
+        Command::Bench(args) => bench(&args),
+        Command::Census(args) => {
+            with_game!(args.game.clone(), game => run_census(game, &args, io::stdout().lock()))
+        }
+        Command::Describe { game } => {
+            print!("{}", describe_game(game));
+            Ok(())
+        }
+        Command::Endgame(args) => run_endgame(&args, io::stdout().lock()),
+        Command::Exploit(args) => with_game!(args.game.clone(), game => run_exploit(game, &args)),
+        Command::Perft(args) => {
+            with_game!(args.game.clone(), game => run_perft(game, &args));
+            Ok(())
+        }
+        Command::List => {
+            print!("{}", list_games());
+            Ok(())
+        }
+        Command::Openings(args) => {
+            with_game!(args.game.clone(), game => run_openings(game, &args, io::stdout().lock()))
+        }
+        Command::Precompute(args) => run_precompute(&args, cli.options.format, io::stdout().lock()),
+        Command::Puzzle(args) => {
+            with_game!(args.game.default_game(), game => run_puzzles(game, &args))
+        }
+        Command::Serve => serve(io::stdin().lock(), io::stdout()),
+        Command::Verify(args) => with_game!(
+            args.game.default_game(),
+            game => run_verify(game, &args, io::stdout().lock())
+        ),
+    })
 }
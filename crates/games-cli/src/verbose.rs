@@ -0,0 +1,158 @@
+//! Logs the solver's search to stderr, for `--verbose`.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{self, Write as _},
+    io::{self, Write as _},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+use tracing::{
+    field::{Field, Visit},
+    level_filters::LevelFilter,
+    span, Event, Level, Metadata, Subscriber,
+};
+
+/// Prints every event on its own line, after the spans it's in, e.g.
+/// `DEBUG root_move{index=0}:solve{move_count=1}: null window search nodes=12 window=3 evaluation=2`.
+pub struct StderrSubscriber {
+    level: Level,
+    next_id: AtomicU64,
+    /// Each open span, written as its name and fields, along with how many handles it has.
+    spans: Mutex<HashMap<u64, (String, usize)>>,
+}
+
+thread_local! {
+    /// The spans entered on this thread, innermost last.
+    static ENTERED: RefCell<Vec<u64>> = const { RefCell::new(Vec::new()) };
+}
+
+impl StderrSubscriber {
+    /// Prints events at `level`, or any level less verbose than it.
+    pub fn new(level: Level) -> Self {
+        Self {
+            level,
+            // span IDs can't be 0
+            next_id: AtomicU64::new(1),
+            spans: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+/// The message and the other fields (as ` name=value`) of a span or event.
+#[derive(Default)]
+struct Fields {
+    message: String,
+    fields: String,
+}
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{value:?}");
+        } else {
+            write!(self.fields, " {}={value:?}", field.name()).unwrap();
+        }
+    }
+}
+
+impl Subscriber for StderrSubscriber {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        *metadata.level() <= self.level
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Some(LevelFilter::from_level(self.level))
+    }
+
+    fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+        let mut fields = Fields::default();
+        span.record(&mut fields);
+
+        let name = span.metadata().name();
+        let written = if fields.fields.is_empty() {
+            name.to_string()
+        } else {
+            format!("{name}{{{}}}", fields.fields.trim_start())
+        };
+
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.spans.lock().unwrap().insert(id, (written, 1));
+
+        span::Id::from_u64(id)
+    }
+
+    // spans are written with the fields they're created with
+    fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+
+        let spans = ENTERED.with(|entered| {
+            let spans = self.spans.lock().unwrap();
+
+            entered
+                .borrow()
+                .iter()
+                .filter_map(|id| spans.get(id))
+                .map(|(written, _)| written.as_str())
+                .collect::<Vec<_>>()
+                .join(":")
+        });
+
+        let mut line = format!("{:>5} ", event.metadata().level());
+        if !spans.is_empty() {
+            line += &spans;
+            line += ": ";
+        }
+        line += &fields.message;
+        line += &fields.fields;
+
+        // there's nowhere left to report a failure to write to stderr
+        let _ = writeln!(io::stderr().lock(), "{line}");
+    }
+
+    fn enter(&self, span: &span::Id) {
+        ENTERED.with(|entered| entered.borrow_mut().push(span.into_u64()));
+    }
+
+    fn exit(&self, span: &span::Id) {
+        ENTERED.with(|entered| {
+            let mut entered = entered.borrow_mut();
+            if let Some(index) = entered.iter().rposition(|&id| id == span.into_u64()) {
+                entered.remove(index);
+            }
+        });
+    }
+
+    fn clone_span(&self, span: &span::Id) -> span::Id {
+        if let Some((_, handles)) = self.spans.lock().unwrap().get_mut(&span.into_u64()) {
+            *handles += 1;
+        }
+
+        span.clone()
+    }
+
+    fn try_close(&self, span: span::Id) -> bool {
+        let mut spans = self.spans.lock().unwrap();
+
+        let Some((_, handles)) = spans.get_mut(&span.into_u64()) else {
+            return false;
+        };
+
+        *handles -= 1;
+        if *handles == 0 {
+            spans.remove(&span.into_u64());
+            true
+        } else {
+            false
+        }
+    }
+}
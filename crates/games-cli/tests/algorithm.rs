@@ -0,0 +1,73 @@
+mod common;
+
+use common::{games_cli, games_cli_stdout};
+
+#[test]
+fn every_algorithm_plays_the_winning_nim_move() {
+    for algorithm in ["negamax", "pn", "mcts"] {
+        let output = games_cli_stdout(&["--algorithm", algorithm, "naive-nim", "1,2"]);
+
+        assert!(output.contains("Best move: 1-1"), "{algorithm}: {output}");
+        assert!(output.contains("Searched "), "{algorithm}: {output}");
+    }
+}
+
+#[test]
+fn algorithms_describe_their_values() {
+    let negamax = games_cli_stdout(&["--algorithm", "negamax", "tic-tac-toe", "2", "3"]);
+    assert!(negamax.contains("The player to move expects a tie."));
+
+    let pn = games_cli_stdout(&["--algorithm", "pn", "naive-nim", "1,2"]);
+    assert!(pn.contains("The player to move can force a win."));
+
+    let mcts = games_cli_stdout(&["--algorithm", "mcts", "naive-nim", "1,2"]);
+    assert!(mcts.contains("The player to move is estimated at +"));
+}
+
+#[test]
+fn mcts_seeds_are_reproducible() {
+    let args = [
+        "--algorithm",
+        "mcts",
+        "--iterations",
+        "200",
+        "--seed",
+        "3",
+        "tic-tac-toe",
+        "2",
+        "3",
+    ];
+
+    assert_eq!(games_cli_stdout(&args), games_cli_stdout(&args));
+}
+
+#[test]
+fn iterations_need_mcts() {
+    let output = games_cli(&["--algorithm", "pn", "--iterations", "5", "naive-nim", "1,2"]);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("--iterations is only supported by --algorithm mcts."));
+}
+
+#[test]
+fn heuristic_search_evaluates_reversi() {
+    let output = games_cli_stdout(&["--eval", "classic", "--depth", "4", "reversi"]);
+
+    assert!(
+        output.contains("by the heuristic (score for Left)."),
+        "{output}"
+    );
+    assert!(output.contains("Best move: "), "{output}");
+}
+
+#[test]
+fn heuristic_search_needs_an_evaluator() {
+    let output = games_cli(&["--eval", "classic", "--depth", "4", "naive-nim", "1,2"]);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("This game has no classic evaluator."));
+}
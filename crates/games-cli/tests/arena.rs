@@ -0,0 +1,51 @@
+use std::process::Command;
+
+fn arena(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .arg("arena")
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn the_solver_never_loses_to_random_play() {
+    let output = arena(&[
+        "tic-tac-toe",
+        "2",
+        "3",
+        "--agent-a",
+        "solver",
+        "--agent-b",
+        "random",
+        "--games",
+        "20",
+    ]);
+
+    assert!(output.contains("The solver agent against the random agent, over 20 games:"));
+    assert!(output.contains(" 0 losses"));
+    assert!(output.contains("95% confidence interval"));
+}
+
+#[test]
+fn seeds_are_reproducible() {
+    let args = [
+        "--agent-a",
+        "greedy",
+        "--agent-b",
+        "random",
+        "--games",
+        "50",
+        "--seed",
+        "4",
+        "naive-nim",
+        "2,3,4",
+    ];
+
+    let sequential = arena(&args);
+    let parallel = arena(&[&args[..], &["--parallel"]].concat());
+    assert_eq!(sequential, parallel);
+}
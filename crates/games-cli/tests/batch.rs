@@ -0,0 +1,112 @@
+mod common;
+
+use std::{fs, path::PathBuf};
+
+use common::games_cli_stdout;
+
+/// Writes a batch file with valid and invalid lines, unique to `name`.
+fn batch_file(name: &str) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("games-cli-batch-{name}-{}.txt", std::process::id()));
+
+    fs::write(
+        &path,
+        "# tic tac toe openings\n\
+         1-1 0-0\n\
+         \n\
+         1-1 0-0 1-1\n\
+         0-0 1-0 0-1 1-1 0-2\n\
+         0-0 9-9\n\
+         1-1 0-1 0-0\n",
+    )
+    .unwrap();
+
+    path
+}
+
+#[test]
+fn reports_each_line() {
+    let path = batch_file("text");
+    let output = games_cli_stdout(&["--batch", path.to_str().unwrap(), "tic-tac-toe", "2", "3"]);
+    fs::remove_file(&path).unwrap();
+
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 5);
+
+    // results are numbered by their line in the file, skipping comments and blank lines
+    assert_eq!(lines[0], "2: best: 0-1, score for Left: 0, tie");
+    assert!(lines[1].starts_with("4: error: Failed to move"));
+    assert_eq!(lines[2], "5: error: The game is already over.");
+    assert!(lines[3].starts_with("6: error: `9-9` is out of range"));
+    assert_eq!(lines[4], "7: best: 2-2, score for Right: -3, lose in 3");
+}
+
+#[test]
+fn threads_keep_file_order() {
+    let path = batch_file("csv");
+    let path_arg = path.to_str().unwrap();
+
+    let single = games_cli_stdout(&[
+        "--batch",
+        path_arg,
+        "--format",
+        "csv",
+        "--threads",
+        "1",
+        "tic-tac-toe",
+        "2",
+        "3",
+    ]);
+    let parallel = games_cli_stdout(&[
+        "--batch",
+        path_arg,
+        "--format",
+        "csv",
+        "--threads",
+        "4",
+        "tic-tac-toe",
+        "2",
+        "3",
+    ]);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(single, parallel);
+
+    let mut rows = single.lines();
+    assert_eq!(
+        rows.next(),
+        Some("line,moves,best,player,score,outcome,error")
+    );
+    assert_eq!(rows.next(), Some("2,1-1 0-0,0-1,Left,0,draw,"));
+    assert_eq!(rows.next().unwrap().split(',').nth(5), Some(""));
+}
+
+#[test]
+fn json_lines() {
+    let path = batch_file("json");
+    let output = games_cli_stdout(&[
+        "--batch",
+        path.to_str().unwrap(),
+        "--format",
+        "json",
+        "tic-tac-toe",
+        "2",
+        "3",
+    ]);
+    fs::remove_file(&path).unwrap();
+
+    let results = output
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(results.len(), 5);
+    assert_eq!(results[0]["best"], "0-1");
+    // the line has two moves, so its score is for the first player
+    assert_eq!(results[0]["player"], "Left");
+    assert_eq!(results[4]["player"], "Right");
+    assert_eq!(results[0]["outcome"]["result"], "tie");
+    assert_eq!(results[2]["line"], 5);
+    assert!(results[2]["error"].is_string());
+    assert!(results[2].get("best").is_none());
+}
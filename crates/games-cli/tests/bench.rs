@@ -0,0 +1,23 @@
+use std::process::Command;
+
+#[test]
+fn quick_json_bench() {
+    let output = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .args([
+            "bench", "--quick", "--json", "--game", "sprouts", "--game", "chomp",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let results = results.as_array().unwrap();
+
+    assert_eq!(results.len(), 5);
+    for result in results {
+        assert!(["Chomp", "Sprouts"].contains(&result["game"].as_str().unwrap()));
+        assert!(result["nodes"].as_u64().unwrap() > 0);
+        assert!(result["table_size"].as_u64().unwrap() > 0);
+    }
+}
@@ -0,0 +1,60 @@
+mod common;
+
+use common::{games_cli, games_cli_stdout};
+
+#[test]
+fn tic_tac_toe_census_counts_three_moves() {
+    let output = games_cli_stdout(&["census", "--depth", "3", "tic-tac-toe", "2", "3"]);
+
+    // 9 * 8 * 7 sequences of three moves, reaching C(9, 2) * 7 boards
+    assert!(
+        output.contains("   3           504             0  -"),
+        "{output}"
+    );
+    assert!(output.contains("Positions: 586\n"), "{output}");
+    assert!(output.contains("Unique positions: 334\n"), "{output}");
+    assert!(output.contains("Unique up to symmetry: 54\n"), "{output}");
+    assert!(output.contains("Finished games: 0 (tied 0)\n"), "{output}");
+}
+
+#[test]
+fn census_counts_finished_games() {
+    let output = games_cli_stdout(&["census", "naive-nim", "1,2"]);
+
+    // under normal play, whoever took the last object wins every game
+    assert!(output.contains("Positions: 12\n"), "{output}");
+    assert!(
+        output.contains("Finished games: 5 (Previous won 5, tied 0)\n"),
+        "{output}"
+    );
+}
+
+#[test]
+fn census_estimates_unique_positions() {
+    let output = games_cli_stdout(&[
+        "census",
+        "--depth",
+        "3",
+        "--estimate",
+        "10",
+        "tic-tac-toe",
+        "2",
+        "3",
+    ]);
+
+    assert!(output.contains("Positions: 586\n"), "{output}");
+    let unique = output
+        .lines()
+        .find_map(|line| line.strip_prefix("Unique positions: "))
+        .unwrap()
+        .parse::<f64>()
+        .unwrap();
+    assert!((unique - 334.0).abs() / 334.0 < 0.1, "{output}");
+}
+
+#[test]
+fn census_rejects_an_imprecise_estimate() {
+    let output = games_cli(&["census", "--estimate", "2", "naive-nim", "1"]);
+
+    assert!(!output.status.success());
+}
@@ -0,0 +1,21 @@
+//! Runs the CLI binary for the integration tests. Each test file includes this with `mod common;`,
+//! and uses some of it, so the rest is dead code there.
+#![allow(dead_code)]
+
+use std::process::{Command, Output};
+
+/// Runs the CLI with `args`, whether it succeeds or not.
+pub fn games_cli(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Runs the CLI with `args`, checks that it succeeded, and returns what it printed.
+pub fn games_cli_stdout(args: &[&str]) -> String {
+    let output = games_cli(args);
+    assert!(output.status.success(), "{output:?}");
+
+    String::from_utf8(output.stdout).unwrap()
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use std::fs;
+
+use common::games_cli_stdout;
+
+#[test]
+fn nim_move_scores() {
+    let output = games_cli_stdout(&["--format", "csv", "naive-nim", "1,2"]);
+
+    assert_eq!(
+        output,
+        "move,score,outcome,rank\n\
+         1-1,1,win,1\n\
+         0-1,-2,loss,2\n\
+         1-2,-2,loss,2\n"
+    );
+}
+
+#[test]
+fn writes_to_output_file() {
+    let path = std::env::temp_dir().join(format!("games-cli-csv-{}.csv", std::process::id()));
+    let path_arg = path.to_str().unwrap();
+
+    let stdout = games_cli_stdout(&[
+        "--format", "csv", "--output", path_arg, "chomp", "--width", "4", "--height", "3",
+    ]);
+    assert!(stdout.is_empty());
+
+    let csv = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let mut rows = csv.lines();
+    assert_eq!(rows.next(), Some("move,score,outcome,rank"));
+
+    // every move can be fed back in through the same parser used for input
+    for row in rows {
+        let (game_move, _) = row.split_once(',').unwrap();
+        games_cli_stdout(&[
+            "--format", "csv", "chomp", "--width", "4", "--height", "3", game_move,
+        ]);
+    }
+}
@@ -0,0 +1,68 @@
+mod common;
+
+use std::{
+    fs,
+    process::Output,
+    time::{Duration, Instant},
+};
+
+use common::games_cli;
+
+/// Runs the CLI like [`games_cli`], checking that it succeeded, and how long it took.
+fn timed(args: &[&str]) -> (Output, Duration) {
+    let start = Instant::now();
+    let output = games_cli(args);
+    assert!(output.status.success());
+
+    (output, start.elapsed())
+}
+
+#[test]
+fn second_runs_are_answered_by_the_database() {
+    let path = std::env::temp_dir().join(format!("games-cli-db-{}", std::process::id()));
+    let path_arg = path.to_str().unwrap();
+    let args = [
+        "--db", path_arg, "--format", "csv", "chomp", "--width", "7", "--height", "5",
+    ];
+
+    let (first, first_time) = timed(&args);
+    let (second, second_time) = timed(&args);
+    fs::remove_file(&path).unwrap();
+
+    let stderr = |output: &Output| String::from_utf8(output.stderr.clone()).unwrap();
+    assert!(
+        stderr(&first).contains("Found 0 of 34 moves"),
+        "{}",
+        stderr(&first)
+    );
+    assert!(
+        stderr(&second).contains("Found 34 of 34 moves"),
+        "{}",
+        stderr(&second)
+    );
+
+    assert_eq!(first.stdout, second.stdout);
+    assert!(
+        second_time < first_time,
+        "{second_time:?} >= {first_time:?}"
+    );
+}
+
+#[test]
+fn unreadable_lines_are_skipped() {
+    let path = std::env::temp_dir().join(format!("games-cli-db-corrupt-{}", std::process::id()));
+    fs::write(&path, "not a record\n\u{0}\u{1}\t\t\n").unwrap();
+    let path_arg = path.to_str().unwrap();
+
+    let (output, _) = timed(&["--db", path_arg, "--plain", "naive-nim", "1,2"]);
+    let records = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Skipped 2 unreadable lines"), "{stderr}");
+    // saving drops the lines that couldn't be read
+    assert!(!records.contains("not a record"), "{records}");
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("best: 1-1"));
+}
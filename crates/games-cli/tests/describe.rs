@@ -0,0 +1,38 @@
+mod common;
+
+use common::games_cli_stdout;
+
+#[test]
+fn list_shows_every_game() {
+    let list = games_cli_stdout(&["list"]);
+    let lines = list.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines.len(), 8);
+    for (line, game) in lines.iter().zip([
+        "reversi",
+        "tic-tac-toe",
+        "order-and-chaos",
+        "naive-nim",
+        "domineering",
+        "chomp",
+        "sprouts",
+        "takeaway",
+    ]) {
+        assert!(line.starts_with(game), "{line}");
+    }
+    assert!(lines[3].contains("Nim (Naive)"));
+}
+
+#[test]
+fn describe_shows_parameters_and_an_example() {
+    let description = games_cli_stdout(&["describe", "tic-tac-toe"]);
+
+    assert!(description.starts_with("Tic Tac Toe (tic-tac-toe)\n"));
+    assert!(description.contains(
+        "  <DIMENSIONS>: The amount of dimensions of the board. (default 2; 1 or more)\n"
+    ));
+    assert!(description.contains("\nExample: games-cli tic-tac-toe 2 3 1-1 0-0\n"));
+
+    let description = games_cli_stdout(&["describe", "reversi"]);
+    assert!(description.contains("\nParameters: none\n"));
+}
@@ -0,0 +1,69 @@
+mod common;
+
+use common::games_cli;
+
+fn sweep(args: &[&str]) -> String {
+    let output = games_cli(&[&["endgame"], args].concat());
+    assert!(output.status.success(), "{output:?}");
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn nim_endgames_agree_with_retrograde_analysis() {
+    let output = sweep(&["--plies", "2", "naive-nim", "2,2,2"]);
+
+    assert_eq!(
+        output,
+        "Swept 10 positions that end within 2 moves: the player to move wins 6, loses 4, and ties 0.\n\
+         The longest forced win takes 1 move, from 0,0,1 m0 s6.\n\
+         0 positions were disagreed about.\n"
+    );
+}
+
+#[test]
+fn tic_tac_toe_endgames_agree_with_retrograde_analysis() {
+    let output = sweep(&["--plies", "4", "tic-tac-toe", "1", "4"]);
+
+    // nobody can fill a whole line of 4 on their own
+    assert!(
+        output.starts_with("Swept 35 positions that end within 4 moves: the player to move wins 0, loses 0, and ties 35.\n"),
+        "{output}"
+    );
+    assert!(
+        output.ends_with("0 positions were disagreed about.\n"),
+        "{output}"
+    );
+}
+
+#[test]
+fn more_plies_sweep_more_positions() {
+    let count = |plies: &str| {
+        let output = sweep(&["--plies", plies, "naive-nim", "2,3,4"]);
+        output
+            .strip_prefix("Swept ")
+            .and_then(|rest| rest.split_once(' '))
+            .map(|(count, _)| count.parse::<usize>().unwrap())
+            .unwrap()
+    };
+
+    assert!(count("1") < count("3"));
+    // no game of these heaps lasts longer than 9 moves
+    assert_eq!(count("9"), count("20"));
+    assert_eq!(count("9"), 3 * 4 * 5);
+}
+
+#[test]
+fn other_games_are_invalid() {
+    let output = games_cli(&["endgame", "--plies", "2", "chomp"]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("only for naive-nim and tic-tac-toe"));
+}
+
+#[test]
+fn games_too_large_to_enumerate_are_invalid() {
+    let output = games_cli(&["endgame", "--plies", "2", "naive-nim", "30,30,30,30,30"]);
+
+    assert_eq!(output.status.code(), Some(2));
+}
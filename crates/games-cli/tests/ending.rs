@@ -0,0 +1,54 @@
+mod common;
+
+use common::games_cli_stdout;
+
+#[test]
+fn finished_games_name_their_winner() {
+    let output = games_cli_stdout(&["tic-tac-toe", "2", "3", "0-0", "1-0", "0-1", "1-1", "0-2"]);
+    assert_eq!(output.trim_end(), "The Left player won!");
+
+    // impartial players are named relative to the position: the one who just moved is Previous
+    let output = games_cli_stdout(&["naive-nim", "0"]);
+    assert_eq!(output.trim_end(), "The Previous player won!");
+}
+
+#[test]
+fn finished_games_can_be_tied() {
+    let output = games_cli_stdout(&[
+        "tic-tac-toe",
+        "2",
+        "3",
+        "0-0",
+        "1-1",
+        "0-1",
+        "0-2",
+        "2-0",
+        "1-0",
+        "1-2",
+        "2-1",
+        "2-2",
+    ]);
+
+    assert_eq!(output.trim_end(), "Game tied!");
+}
+
+#[test]
+fn full_order_and_chaos_boards_are_won_by_chaos() {
+    let output = games_cli_stdout(&[
+        "order-and-chaos",
+        "--board=XXOOXX/OOXXOO/XXOOXX/OOXXOO/XXOOXX/OOXXOO",
+    ]);
+    assert_eq!(
+        output.trim_end(),
+        "The Right player won! The board is full without a line of 5, so Chaos wins."
+    );
+
+    let output = games_cli_stdout(&[
+        "order-and-chaos",
+        "--board=XXXXX./....../....../....../....../......",
+    ]);
+    assert_eq!(
+        output.trim_end(),
+        "The Left player won! Order wins with a line of 5."
+    );
+}
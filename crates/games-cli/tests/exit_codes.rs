@@ -0,0 +1,116 @@
+mod common;
+
+use std::process::Output;
+
+use serde_json::{json, Value};
+
+use common::games_cli;
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+/// The error the CLI printed to stderr as JSON.
+fn json_error(output: &Output) -> Value {
+    let stderr = stderr(output);
+    assert_eq!(stderr.lines().count(), 1, "{stderr}");
+
+    serde_json::from_str(&stderr).unwrap()
+}
+
+#[test]
+fn lost_positions_succeed() {
+    // every move loses, which is an answer, not a failure
+    let output = games_cli(&["--plain", "naive-nim", "1,1"]);
+    assert_eq!(output.status.code(), Some(0));
+}
+
+#[test]
+fn invalid_input_exits_with_2() {
+    let output = games_cli(&["naive-nim", "1,2", "5-1"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr(&output).starts_with("Error: Could not play move 1 (5-1)"),
+        "{}",
+        stderr(&output)
+    );
+
+    for args in [
+        // options that don't go together
+        &["--seed", "3", "naive-nim", "1,2"][..],
+        // a game with no outcome classes
+        &["--outcome-class", "reversi"],
+        // a move that isn't possible
+        &["--explain", "0-3", "naive-nim", "1,2"],
+        &["--only", "0-1,1-3", "naive-nim", "1,2"],
+        // a position that can't be read
+        &["tic-tac-toe", "--position", "XXX/.../... O"],
+        // arguments the parser doesn't know
+        &["naive-nim", "--heaps", "1,2"],
+        &["chomp", "--width", "0"],
+    ] {
+        let output = games_cli(args);
+        assert_eq!(
+            output.status.code(),
+            Some(2),
+            "{args:?}: {}",
+            stderr(&output)
+        );
+        assert!(
+            stderr(&output).to_lowercase().contains("error: "),
+            "{args:?}"
+        );
+    }
+}
+
+#[test]
+fn errors_are_json_with_format_json() {
+    let output = games_cli(&["--format", "json", "naive-nim", "1,2", "5-1"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let error = json_error(&output);
+    assert_eq!(error["error"]["kind"], json!("invalid"));
+    assert_eq!(error["error"]["code"], json!(2));
+    let message = error["error"]["message"].as_str().unwrap();
+    assert!(
+        message.starts_with("Could not play move 1 (5-1): "),
+        "{message}"
+    );
+
+    // even when the arguments can't be parsed
+    let output = games_cli(&["--format=json", "naive-nim", "--heaps", "1,2"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(
+        json_error(&output),
+        json!({"error": {"kind": "invalid", "code": 2, "message": "unexpected argument '--heaps' found"}})
+    );
+
+    // and nothing is written to stdout
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn other_failures_exit_with_1() {
+    let path = std::env::temp_dir()
+        .join(format!("games-cli-exit-codes-{}", std::process::id()))
+        .join("missing")
+        .join("analysis.json");
+
+    let output = games_cli(&[
+        "--format",
+        "json",
+        "--output",
+        path.to_str().unwrap(),
+        "naive-nim",
+        "1,2",
+    ]);
+    assert_eq!(output.status.code(), Some(1));
+
+    let error = json_error(&output);
+    assert_eq!(error["error"]["kind"], json!("failed"));
+    assert_eq!(error["error"]["code"], json!(1));
+    assert!(error["error"]["message"]
+        .as_str()
+        .unwrap()
+        .starts_with("Could not create "));
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use common::games_cli;
+
+#[test]
+fn refutations_end_the_game() {
+    let output = games_cli(&["--explain", "0-1", "tic-tac-toe", "2", "3", "0-0"]);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("0-1 has a score for Right of -"),
+        "{stdout}"
+    );
+    assert!(
+        stdout
+            .trim_end()
+            .ends_with("the player who just moved wins"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn multipv_prints_k_lines() {
+    let output = games_cli(&["--multipv", "2", "tic-tac-toe", "2", "3", "1-1"]);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("\n1. "), "{stdout}");
+    assert!(stdout.contains("\n2. "), "{stdout}");
+    assert!(!stdout.contains("\n3. "), "{stdout}");
+}
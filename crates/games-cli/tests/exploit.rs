@@ -0,0 +1,29 @@
+use std::process::Command;
+
+fn exploit(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .arg("exploit")
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn random_play_loses_tic_tac_toe() {
+    let output = exploit(&["tic-tac-toe", "2", "3"]);
+
+    assert!(output.contains("Against the random policy, the player to move expects to win"));
+    assert!(output
+        .contains("Against perfect play, the player to move expects a tie (score for Left: 0)."));
+}
+
+#[test]
+fn forced_lines_give_nothing_away() {
+    // after the winning move of 1,2 Nim, the opponent's only moves lose just as fast
+    let output = exploit(&["--policy", "greedy", "naive-nim", "1,2"]);
+
+    assert!(output.contains("The greedy policy gives away 0 points."));
+}
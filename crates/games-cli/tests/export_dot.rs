@@ -0,0 +1,34 @@
+use std::{fs, process::Command};
+
+#[test]
+fn writes_tree_to_file() {
+    let path = std::env::temp_dir().join(format!("games-cli-tree-{}.dot", std::process::id()));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .args(["--export-dot", path.to_str().unwrap(), "--depth", "1"])
+        .args(["tic-tac-toe", "2", "3"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+
+    let dot = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(dot.starts_with("digraph tree {"));
+    assert_eq!(dot.lines().filter(|line| line.contains(" -> ")).count(), 9);
+}
+
+#[test]
+fn requires_depth() {
+    let output = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .args(["--export-dot", "tree.dot", "naive-nim", "1,2"])
+        .output()
+        .unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("--depth"));
+}
@@ -0,0 +1,106 @@
+mod common;
+
+use common::{games_cli, games_cli_stdout};
+
+#[test]
+fn best_text_shows_all_ties() {
+    let output = games_cli_stdout(&["--plain", "--best", "tic-tac-toe", "2", "3", "1-1"]);
+
+    // the board comes first, so we only check the analysis
+    let (_, analysis) = output.split_once("Player Right to move\n").unwrap();
+
+    assert_eq!(
+        analysis,
+        "best: 2-2, score for Right: 0, tie\n\
+         \n\
+         Moves (score for Right):\n\
+         2-2    tie  0\n\
+         2-0    tie  0\n\
+         0-2    tie  0\n\
+         0-0    tie  0\n"
+    );
+}
+
+#[test]
+fn best_csv_shows_all_ties() {
+    let output = games_cli_stdout(&["--format", "csv", "--best", "tic-tac-toe", "2", "3", "1-1"]);
+
+    assert_eq!(
+        output,
+        "move,score,outcome,rank\n\
+         2-2,0,draw,1\n\
+         2-0,0,draw,1\n\
+         0-2,0,draw,1\n\
+         0-0,0,draw,1\n"
+    );
+}
+
+#[test]
+fn top_json_truncates_moves() {
+    let output = games_cli_stdout(&[
+        "--format",
+        "json",
+        "--top",
+        "2",
+        "tic-tac-toe",
+        "2",
+        "3",
+        "1-1",
+        "0-0",
+        "2-2",
+        "0-2",
+    ]);
+
+    let (_, moves) = output.split_once("\"moves\": ").unwrap();
+
+    assert_eq!(
+        moves,
+        r#"[
+    {
+      "move": "0-1",
+      "score": 0,
+      "outcome": {
+        "result": "tie"
+      }
+    },
+    {
+      "move": "2-1",
+      "score": -4,
+      "outcome": {
+        "result": "loss",
+        "moves": 1
+      }
+    }
+  ],
+  "evaluation": {
+    "score": 0,
+    "outcome": {
+      "result": "tie"
+    }
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn only_scores_the_listed_moves() {
+    let output = games_cli_stdout(&["--only", "0-1,1-1", "tic-tac-toe", "2", "3", "0-0"]);
+
+    // the board comes first, so we only check the scores
+    let lines = output.lines().rev().take(2).collect::<Vec<_>>();
+    assert!(lines[1].starts_with("0-1, score for Right: -"), "{output}");
+    assert_eq!(lines[0], "1-1, score for Right: 0, tie", "{output}");
+}
+
+#[test]
+fn only_names_impossible_moves() {
+    let output = games_cli(&["--only", "1-1,0-0", "tic-tac-toe", "2", "3", "0-0"]);
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("Move 2 (0-0) isn't a possible move."),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use common::games_cli_stdout;
+
+/// The JSON schema is consumed by other programs, so any change to it should be deliberate.
+#[test]
+fn tic_tac_toe_snapshot() {
+    let output = games_cli_stdout(&[
+        "--format",
+        "json",
+        "tic-tac-toe",
+        "2",
+        "3",
+        "1-1",
+        "0-0",
+        "2-2",
+        "0-2",
+    ]);
+
+    assert_eq!(
+        output,
+        r#"{
+  "position": {
+    "dimensions": 2,
+    "size": 3,
+    "board": [
+      "O-O",
+      "-X-",
+      "--X"
+    ]
+  },
+  "notation": "O.O/.X./..X X",
+  "player": "Left",
+  "moves": [
+    {
+      "move": "0-1",
+      "score": 0,
+      "outcome": {
+        "result": "tie"
+      }
+    },
+    {
+      "move": "2-1",
+      "score": -4,
+      "outcome": {
+        "result": "loss",
+        "moves": 1
+      }
+    },
+    {
+      "move": "2-0",
+      "score": -4,
+      "outcome": {
+        "result": "loss",
+        "moves": 1
+      }
+    },
+    {
+      "move": "1-2",
+      "score": -4,
+      "outcome": {
+        "result": "loss",
+        "moves": 1
+      }
+    },
+    {
+      "move": "1-0",
+      "score": -4,
+      "outcome": {
+        "result": "loss",
+        "moves": 1
+      }
+    }
+  ],
+  "evaluation": {
+    "score": 0,
+    "outcome": {
+      "result": "tie"
+    }
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn moves_can_be_fed_back_in() {
+    let base = ["--format", "json", "chomp", "--width", "4", "--height", "3"];
+    let before: serde_json::Value = serde_json::from_str(&games_cli_stdout(&base)).unwrap();
+
+    let best_move = before["moves"][0]["move"].as_str().unwrap();
+    let after: serde_json::Value =
+        serde_json::from_str(&games_cli_stdout(&[&base[..], &[best_move]].concat())).unwrap();
+
+    assert_ne!(before["position"], after["position"]);
+    assert_eq!(after["evaluation"]["outcome"]["result"], "loss");
+}
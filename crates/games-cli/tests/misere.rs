@@ -0,0 +1,26 @@
+mod common;
+
+use common::games_cli_stdout;
+
+#[test]
+fn anti_reversi_is_labelled_and_scored_for_the_fewest_discs() {
+    let endgame = ".....O/.OXXOO/XOOXOO/OOOOOX/XOOOO./XXXXX. X";
+
+    let standard = games_cli_stdout(&["--plain", "reversi", "--position", endgame]);
+    assert!(standard.starts_with("Current player: X\n"), "{standard}");
+    assert!(
+        standard.contains("score for Left: -5, lose in"),
+        "{standard}"
+    );
+
+    let misere = games_cli_stdout(&["--plain", "reversi", "--misere", "--position", endgame]);
+    assert!(
+        misere.starts_with("Anti-Reversi: the fewest discs win\nCurrent player: X\n"),
+        "{misere}"
+    );
+    assert!(misere.contains(&format!("Position: {endgame} misere\n")));
+    assert!(
+        misere.contains("best: c1, score for Left: 0, tie\n"),
+        "{misere}"
+    );
+}
@@ -0,0 +1,136 @@
+mod common;
+
+use common::games_cli;
+
+fn json(args: &[&str]) -> serde_json::Value {
+    let output = games_cli(&[&["--format", "json"], args].concat());
+    assert!(output.status.success());
+
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn positions_match_their_moves() {
+    for (moves, position) in [
+        (
+            &["tic-tac-toe", "2", "3", "1-1", "0-0", "2-2"][..],
+            "O../.X./..X O",
+        ),
+        (&["naive-nim", "3,5,7", "2-1"], "3,5,6 m1"),
+        (&["domineering", "0-0"], "#..../#..../...../...../..... h"),
+        (
+            &["chomp", "--width", "4", "--height", "3", "3-0"],
+            "XXX./XXXX/.XXX m1",
+        ),
+        (&["sprouts", "3", "0-1"], "3 1-0"),
+        (&["takeaway", "21", "1", "3"], "17/21 m2"),
+    ] {
+        let from_moves = json(moves);
+        assert_eq!(from_moves["notation"], position);
+
+        // the notation describes the same position as the moves
+        let from_position = json(&[moves[0], "--position", position]);
+        assert_eq!(from_position["position"], from_moves["position"]);
+        assert_eq!(from_position["moves"], from_moves["moves"]);
+    }
+}
+
+#[test]
+fn plain_output_shows_the_notation() {
+    let output = games_cli(&["--plain", "tic-tac-toe", "--position", "X../.O./... X"]);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Position: X../.O./... X\n"), "{stdout}");
+}
+
+#[test]
+fn errors_point_at_the_mistake() {
+    let output = games_cli(&[
+        "reversi",
+        "--position",
+        "....../....../..XO../..0X../....../...... X",
+    ]);
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains(
+            "`0` isn't a square: expected one of `XO.`\n  \
+             ....../....../..XO../..0X../....../...... X\n  \
+             \x20                      ^\n"
+        ),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn boards_match_their_moves() {
+    // Order and Chaos takes too long to solve here, so its tests are in the `games` crate
+    for (moves, board) in [
+        (
+            &["tic-tac-toe", "2", "3", "1-1", "0-0", "2-2"][..],
+            "O..\n.X.\n..X",
+        ),
+        (
+            &["domineering", "0-0", "2-2"],
+            "#..../#..../..##./...../.....",
+        ),
+    ] {
+        let from_moves = json(moves);
+        let from_board = json(&[moves[0], "--board", board]);
+        assert_eq!(from_board["position"], from_moves["position"]);
+        assert_eq!(from_board["moves"], from_moves["moves"]);
+    }
+}
+
+#[test]
+fn boards_have_to_be_possible() {
+    for args in [
+        &["tic-tac-toe", "--board", "XX./.../..."][..],
+        &[
+            "order-and-chaos",
+            "--board",
+            "X...../....../....../....../....../......",
+            "--to-move",
+            "left",
+        ],
+        &["domineering", "--board", "#..../...../...../...../....."],
+    ] {
+        let output = games_cli(args);
+        assert!(!output.status.success(), "{args:?}");
+    }
+}
+
+#[test]
+fn positions_replace_moves() {
+    let output = games_cli(&["naive-nim", "--position", "3,5,7 m0", "0-1"]);
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("cannot be used with"), "{stderr}");
+}
+
+#[test]
+fn bad_moves_are_named() {
+    for (args, error) in [
+        (
+            &["tic-tac-toe", "2", "3", "1-1", "0-0", "1-1"][..],
+            "Could not play move 3 (1-1): ",
+        ),
+        (
+            &["tic-tac-toe", "2", "3", "1-1", "9-9"],
+            "Could not play move 2 (9-9): ",
+        ),
+        (
+            &["naive-nim", "3,5,7", "2-1", "0-4"],
+            "Could not play move 2 (0-4): ",
+        ),
+    ] {
+        let output = games_cli(args);
+        assert!(!output.status.success(), "{args:?}");
+
+        let stderr = String::from_utf8(output.stderr).unwrap();
+        assert!(stderr.contains(error), "{stderr}");
+    }
+}
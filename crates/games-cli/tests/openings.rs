@@ -0,0 +1,62 @@
+mod common;
+
+use common::games_cli_stdout;
+
+#[test]
+fn tic_tac_toe_has_three_first_moves() {
+    let output = games_cli_stdout(&["openings", "--depth", "1", "tic-tac-toe", "2", "3"]);
+    let report: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+    assert_eq!(report["game"], "Tic Tac Toe");
+    assert_eq!(report["depth"], 1);
+
+    // a corner, an edge, and the center, each of which ties
+    let root = &report["opening"];
+    assert_eq!(root["line"], serde_json::json!([]));
+    assert_eq!(
+        root["continuations"],
+        serde_json::json!({"win": 0, "tie": 3, "loss": 0})
+    );
+
+    let openings = root["openings"].as_array().unwrap();
+    assert_eq!(openings.len(), 3);
+    for opening in openings {
+        assert_eq!(opening["score"], 0);
+        assert_eq!(opening["outcome"]["result"], "tie");
+        assert_eq!(opening["continuations"], serde_json::Value::Null);
+        assert_eq!(opening["openings"], serde_json::json!([]));
+    }
+}
+
+#[test]
+fn markdown_nests_replies_under_their_opening() {
+    let output = games_cli_stdout(&[
+        "openings",
+        "--depth",
+        "2",
+        "--format",
+        "md",
+        "tic-tac-toe",
+        "2",
+        "3",
+    ]);
+    let lines = output.lines().collect::<Vec<_>>();
+
+    assert!(lines[0].starts_with("# Openings of Tic Tac Toe, up to 2 moves"));
+    assert!(output.contains("continuations: 0 win, 3 tie, 0 loss\n"));
+
+    // the center has two replies up to symmetry, a corner and an edge, and the edge loses for O
+    let center = lines
+        .iter()
+        .position(|line| line.starts_with("- 1-1"))
+        .unwrap();
+    assert_eq!(
+        lines[center],
+        "- 1-1: tie (score 0), continuations: 0 win, 1 tie, 1 loss"
+    );
+    let replies = lines[center + 1..]
+        .iter()
+        .take_while(|line| line.starts_with("  - "))
+        .count();
+    assert_eq!(replies, 2);
+}
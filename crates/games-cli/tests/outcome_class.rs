@@ -0,0 +1,69 @@
+mod common;
+
+use common::games_cli;
+
+fn outcome_class(args: &[&str]) -> String {
+    let output = games_cli(args);
+    assert!(output.status.success(), "{output:?}");
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn domineering_positions_have_all_four_classes() {
+    // with an even number of dominoes placed, the vertical player to move moved first, so is Left
+    for (position, class) in [
+        // a column only the vertical player (Left) can move in
+        (
+            "##.##/##.##/#.#.#/#####/####. v",
+            "This position is an L-position: Left wins, whoever moves first.",
+        ),
+        // a column and a row, which cancel out
+        (
+            "##.##/##.##/#####/..###/####. v",
+            "This position is a P-position: whoever moves second wins.",
+        ),
+        // a 2x2 square, which either player takes moving first
+        (
+            "..###/..###/#####/#####/####. v",
+            "This position is an N-position: whoever moves first wins.",
+        ),
+        // a row only the horizontal player (Right) can move in
+        (
+            "#####/#.#.#/#####/..###/####. v",
+            "This position is an R-position: Right wins, whoever moves first.",
+        ),
+    ] {
+        let output = outcome_class(&["--outcome-class", "domineering", "--position", position]);
+
+        assert!(output.contains(class), "{position}: {output}");
+    }
+}
+
+#[test]
+fn impartial_games_are_next_or_previous() {
+    let won = outcome_class(&["--outcome-class", "naive-nim", "1,2"]);
+    assert!(won.contains("an N-position"), "{won}");
+
+    let lost = outcome_class(&["--outcome-class", "naive-nim", "1,2,3"]);
+    assert!(lost.contains("a P-position"), "{lost}");
+}
+
+#[test]
+fn nim_gives_the_book_move_of_won_positions() {
+    let won = outcome_class(&["--outcome-class", "naive-nim", "3,4,5"]);
+    assert!(won.ends_with("By the book, 0-2 wins.\n"), "{won}");
+
+    let lost = outcome_class(&["--outcome-class", "naive-nim", "1,2,3"]);
+    assert!(!lost.contains("By the book"), "{lost}");
+}
+
+#[test]
+fn other_partizan_games_have_no_outcome_classes() {
+    let output = games_cli(&["--outcome-class", "tic-tac-toe", "2", "3"]);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("This game has no outcome classes."));
+}
@@ -0,0 +1,20 @@
+use std::process::Command;
+
+#[test]
+fn divide_splits_by_first_move() {
+    let output = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .args(["perft", "--divide", "3", "tic-tac-toe", "2", "3", "1-1"])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    let output = String::from_utf8(output.stdout).unwrap();
+
+    // every reply to the center opening leaves 7 squares, then 6
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(
+        lines[..8],
+        ["0-0: 42", "0-1: 42", "0-2: 42", "1-0: 42", "1-2: 42", "2-0: 42", "2-1: 42", "2-2: 42"]
+    );
+    assert!(output.contains("perft(3) = 336"));
+}
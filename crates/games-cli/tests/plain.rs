@@ -0,0 +1,122 @@
+use std::process::{Command, Stdio};
+
+/// The board `--play` shows before asking for the first move, with its output piped,
+/// as a script reading it sees it.
+fn first_board(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .arg("--play")
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+
+    // with no input, the game can't be finished
+    assert!(!output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    stdout
+        .strip_suffix("\nYour move: ")
+        .unwrap_or_else(|| panic!("no prompt after the board: {stdout:?}"))
+        .to_string()
+}
+
+#[test]
+fn tic_tac_toe() {
+    assert_eq!(
+        first_board(&["tic-tac-toe", "2", "3", "1-1", "0-0"]),
+        "Some(O) @ 0-0
+None @ 0-1
+None @ 0-2
+None @ 1-0
+Some(X) @ 1-1
+None @ 1-2
+None @ 2-0
+None @ 2-1
+None @ 2-2
+"
+    );
+}
+
+#[test]
+fn reversi_marks_moves_with_stars() {
+    assert_eq!(
+        first_board(&["reversi"]),
+        "Current player: X
+------
+---*--
+--XO*-
+-*OX--
+--*---
+------
+"
+    );
+}
+
+#[test]
+fn anti_reversi_says_so() {
+    assert!(first_board(&["reversi", "--misere"])
+        .starts_with("Anti-Reversi: the fewest discs win\nCurrent player: X\n"));
+}
+
+#[test]
+fn order_and_chaos() {
+    assert_eq!(
+        first_board(&["order-and-chaos", "0-0-x", "5-5-o"]),
+        "X-----
+------
+------
+------
+------
+-----O
+"
+    );
+}
+
+#[test]
+fn nim() {
+    assert_eq!(
+        first_board(&["naive-nim", "1,2,3"]),
+        "Heap 0: 1\nHeap 1: 2\nHeap 2: 3\n"
+    );
+}
+
+#[test]
+fn domineering() {
+    assert_eq!(
+        first_board(&["domineering", "0-0"]),
+        ".XXXX
+.XXXX
+XXXXX
+XXXXX
+XXXXX
+"
+    );
+}
+
+#[test]
+fn chomp() {
+    assert_eq!(
+        first_board(&["chomp"]),
+        "XXXXXX
+XXXXXX
+XXXXXX
+.XXXXX
+"
+    );
+}
+
+#[test]
+fn sprouts() {
+    assert_eq!(
+        first_board(&["sprouts", "3"]),
+        "graph of vertices count 0\n"
+    );
+}
+
+#[test]
+fn the_plain_flag_matches_piped_output() {
+    assert_eq!(
+        first_board(&["--plain", "domineering", "0-0"]),
+        first_board(&["domineering", "0-0"])
+    );
+}
@@ -0,0 +1,121 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+/// Plays Tic Tac Toe against the solver, where the human
+/// always tries the first free square (in reading order).
+fn play_tic_tac_toe(extra_args: &[&str]) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .arg("--play")
+        .args(extra_args)
+        .args(["tic-tac-toe", "2", "3"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // occupied squares are rejected, so the human re-prompts until it reaches a free one.
+    let moves = (0..3)
+        .flat_map(|x| (0..3).map(move |y| format!("{x}-{y}\n")))
+        .collect::<String>();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(moves.as_bytes())
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn solver_never_loses_as_second_player() {
+    let output = play_tic_tac_toe(&[]);
+
+    assert!(!output.contains("You won!"));
+    assert!(output.contains("The solver won!") || output.contains("Game tied!"));
+}
+
+#[test]
+fn solver_never_loses_as_first_player() {
+    let output = play_tic_tac_toe(&["--solver-first"]);
+
+    assert!(!output.contains("You won!"));
+    assert!(output.contains("The solver won!") || output.contains("Game tied!"));
+}
+
+#[test]
+fn seeded_solvers_never_lose_and_are_reproducible() {
+    let args = ["--solver-first", "--seed", "9"];
+    let output = play_tic_tac_toe(&args);
+
+    assert!(!output.contains("You won!"));
+    assert_eq!(output, play_tic_tac_toe(&args));
+}
+
+#[test]
+fn invalid_moves_reprompt() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .args(["--play", "naive-nim", "1,2"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"not-a-move\n5-1\n1-2\n")
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    let output = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.contains("Invalid move: could not parse `not-a-move`"));
+    assert!(output.contains("Illegal move: 5-1"));
+    assert!(output.contains("The solver won!"));
+}
+
+#[test]
+fn solver_never_loses_while_pondering() {
+    let output = play_tic_tac_toe(&["--ponder"]);
+
+    assert!(output.contains("The solver pondered "));
+    assert!(!output.contains("You won!"));
+    assert!(output.contains("The solver won!") || output.contains("Game tied!"));
+}
+
+#[test]
+fn undo_takes_back_a_move_and_its_reply() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .args(["--play", "tic-tac-toe", "2", "3"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let moves = (0..3)
+        .flat_map(|x| (0..3).map(move |y| format!("{x}-{y}\n")))
+        .collect::<String>();
+
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(format!("undo\n0-0\nundo\n{moves}").as_bytes())
+        .unwrap();
+
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let output = String::from_utf8(output.stdout).unwrap();
+
+    assert!(output.contains("You have no move to take back."));
+    assert!(output.contains("You took back 0-0, and the solver took back "));
+    assert!(!output.contains("You won!"));
+}
@@ -0,0 +1,187 @@
+mod common;
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{json, Value};
+
+use common::games_cli;
+
+/// A directory of its own for each test, as they run at the same time.
+fn directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!(
+        "games-cli-precompute-{name}-{}",
+        std::process::id()
+    ));
+    fs::create_dir_all(&directory).unwrap();
+    directory
+}
+
+/// Precomputes `manifest` into `results`, returning the summary it printed and its exit code.
+fn precompute(manifest: &Path, results: &Path) -> (Value, Option<i32>) {
+    let output = games_cli(&[
+        "--format",
+        "json",
+        "precompute",
+        manifest.to_str().unwrap(),
+        "--results",
+        results.to_str().unwrap(),
+    ]);
+
+    (
+        serde_json::from_slice(&output.stdout).unwrap(),
+        output.status.code(),
+    )
+}
+
+#[test]
+fn second_runs_solve_nothing() {
+    let directory = directory("twice");
+    let manifest = directory.join("manifest.json");
+    let results = directory.join("results.json");
+    fs::write(
+        &manifest,
+        json!([
+            {"name": "nim", "game": "naive-nim", "position": "2,3 m0"},
+            {"name": "corner", "game": "tic-tac-toe", "position": "X../.O./... X"},
+        ])
+        .to_string(),
+    )
+    .unwrap();
+
+    let (first, code) = precompute(&manifest, &results);
+    assert_eq!(code, Some(0));
+    assert_eq!(first["solved"], json!(2));
+    assert!(first["states_explored"].as_u64().unwrap() > 0, "{first}");
+    let written = fs::read_to_string(&results).unwrap();
+
+    let (second, code) = precompute(&manifest, &results);
+    assert_eq!(code, Some(0));
+    assert_eq!(second["unchanged"], json!(2));
+    assert_eq!(second["states_explored"], json!(0));
+    assert_eq!(fs::read_to_string(&results).unwrap(), written);
+
+    // the results have the analysis --format json prints
+    let results = serde_json::from_str::<Value>(&written).unwrap();
+    let nim = &results["entries"][0];
+    assert_eq!(nim["name"], json!("nim"));
+    assert_eq!(nim["exact"], json!(true));
+    assert_eq!(
+        nim["analysis"]["evaluation"]["outcome"]["result"],
+        json!("win")
+    );
+    assert_eq!(nim["analysis"]["moves"][0]["move"], json!("1-1"));
+
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn only_changed_entries_are_solved_again() {
+    let directory = directory("changed");
+    let manifest = directory.join("manifest.json");
+    let results = directory.join("results.json");
+    let write_manifest = |heaps: &str| {
+        fs::write(
+            &manifest,
+            json!([
+                {"name": "nim", "game": "naive-nim", "position": heaps},
+                {"name": "chomp", "game": "chomp", "position": "XXX/.XX m0"},
+            ])
+            .to_string(),
+        )
+        .unwrap()
+    };
+
+    write_manifest("2,3 m0");
+    precompute(&manifest, &results);
+    write_manifest("2,4 m0");
+    let (summary, code) = precompute(&manifest, &results);
+
+    assert_eq!(code, Some(0));
+    assert_eq!(
+        summary["entries"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|entry| (entry["name"].clone(), entry["status"].clone()))
+            .collect::<Vec<_>>(),
+        [
+            (json!("nim"), json!("solved")),
+            (json!("chomp"), json!("unchanged"))
+        ]
+    );
+
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn positions_that_run_out_of_time_are_solved_again() {
+    let directory = directory("timeout");
+    let manifest = directory.join("manifest.json");
+    let results = directory.join("results.json");
+    fs::write(
+        &manifest,
+        json!([
+            {"name": "reversi", "game": "reversi", "position": "....../....../..XO../..OX../....../...... X", "timeout": 0.05},
+        ])
+        .to_string(),
+    )
+    .unwrap();
+
+    for _ in 0..2 {
+        let (summary, code) = precompute(&manifest, &results);
+        assert_eq!(code, Some(3));
+        assert_eq!(summary["failed"], json!(1));
+    }
+
+    let results = serde_json::from_str::<Value>(&fs::read_to_string(&results).unwrap()).unwrap();
+    assert_eq!(
+        results["entries"][0]["error"],
+        json!("The solver ran out of time.")
+    );
+
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn solved_moves_are_merged_into_the_database() {
+    let directory = directory("db");
+    let manifest = directory.join("manifest.json");
+    let results = directory.join("results.json");
+    let db = directory.join("solved.db");
+    fs::write(
+        &manifest,
+        json!([{"name": "chomp", "game": "chomp", "position": "XXXX/XXXX/.XXX m0"}]).to_string(),
+    )
+    .unwrap();
+
+    let run = || {
+        let output = games_cli(&[
+            "--format",
+            "json",
+            "precompute",
+            manifest.to_str().unwrap(),
+            "--results",
+            results.to_str().unwrap(),
+            "--db",
+            db.to_str().unwrap(),
+        ]);
+        assert!(output.status.success());
+        serde_json::from_slice::<Value>(&output.stdout).unwrap()
+    };
+
+    assert!(run()["states_explored"].as_u64().unwrap() > 0);
+    assert!(fs::read_to_string(&db)
+        .unwrap()
+        .contains("XXXX/XXXX/.XXX m0"));
+
+    // without the results, the position is solved again, from the scores of the database
+    fs::remove_file(&results).unwrap();
+    let second = run();
+    assert_eq!(second["solved"], json!(1));
+    assert_eq!(second["states_explored"], json!(0));
+
+    fs::remove_dir_all(&directory).unwrap();
+}
@@ -0,0 +1,24 @@
+mod common;
+
+use common::games_cli;
+
+#[test]
+fn probes_print_the_stored_score() {
+    let output = games_cli(&["--probe", "1-1", "tic-tac-toe", "2", "3"]);
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(
+        stdout.contains("The table holds the exact score for Right: "),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn probes_name_bad_moves() {
+    let output = games_cli(&["--probe", "1-1 1-1", "tic-tac-toe", "2", "3"]);
+    assert!(!output.status.success());
+
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Could not play move 2 (1-1)"), "{stderr}");
+}
@@ -0,0 +1,58 @@
+use std::process::Command;
+
+fn puzzles(seed: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .args([
+            "puzzle",
+            "--game",
+            "naive-nim",
+            "--count",
+            "3",
+            "--seed",
+            seed,
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn seed_is_reproducible() {
+    let output = puzzles("7");
+
+    assert_eq!(output.matches("Puzzle ").count(), 3);
+    assert_eq!(output.matches("best: ").count(), 3);
+    assert_eq!(output, puzzles("7"));
+}
+
+#[test]
+fn min_difficulty_leaves_out_easy_puzzles() {
+    let output = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .args([
+            "puzzle",
+            "--game",
+            "naive-nim",
+            "--count",
+            "3",
+            "--min-difficulty",
+            "1.5",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let output = String::from_utf8(output.stdout).unwrap();
+
+    let ratings = output
+        .lines()
+        .filter_map(|line| line.strip_prefix("difficulty: "))
+        .map(|line| line.split(' ').next().unwrap().parse::<f64>().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(ratings.len(), 3, "{output}");
+    assert!(ratings.iter().all(|&rating| rating >= 1.5), "{output}");
+
+    // without it, the last puzzle wins right away
+    assert!(puzzles("0").contains("difficulty: 0.67 (wins in 1 move, "));
+}
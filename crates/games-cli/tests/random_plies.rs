@@ -0,0 +1,36 @@
+mod common;
+
+use common::{games_cli, games_cli_stdout};
+
+#[test]
+fn seed_is_reproducible() {
+    let args = [
+        "--format",
+        "json",
+        "--random-plies",
+        "3",
+        "--seed",
+        "5",
+        "tic-tac-toe",
+        "2",
+        "3",
+    ];
+    let output = games_cli_stdout(&args);
+
+    let report: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let board = report["position"]["board"].as_array().unwrap();
+    let filled = board
+        .iter()
+        .map(|row| row.as_str().unwrap().chars().filter(|&c| c != '-').count())
+        .sum::<usize>();
+
+    assert_eq!(filled, 3);
+    assert_eq!(output, games_cli_stdout(&args));
+}
+
+#[test]
+fn seed_requires_randomness() {
+    let output = games_cli(&["--seed", "5", "tic-tac-toe", "2", "3"]);
+
+    assert!(!output.status.success());
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use std::{
+    fs,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use common::games_cli;
+
+fn temp_path(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("games-cli-{}-{name}.txt", std::process::id()));
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn self_played_games_replay_to_their_end() {
+    let path = temp_path("self-play");
+
+    let played = games_cli(&["--self-play", "--save-game", &path, "tic-tac-toe", "2", "3"]);
+    assert!(played.status.success());
+
+    let record = fs::read_to_string(&path).unwrap();
+    let mut lines = record.lines();
+    assert_eq!(lines.next(), Some("tic-tac-toe .../.../... X"));
+    // perfect play fills the board
+    assert_eq!(lines.next().unwrap().split(' ').count(), 9);
+
+    let replayed = games_cli(&["--plain", "--replay", &path, "tic-tac-toe", "2", "3"]);
+    fs::remove_file(&path).unwrap();
+
+    assert!(replayed.status.success());
+    assert_eq!(String::from_utf8(replayed.stdout).unwrap(), "Game tied!\n");
+}
+
+#[test]
+fn unfinished_games_are_saved_and_continued() {
+    let path = temp_path("unfinished");
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .args(["--play", "--save-game", &path, "naive-nim", "3,4"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    // the input ends after a single move
+    child.stdin.take().unwrap().write_all(b"0-1\n").unwrap();
+    assert!(!child.wait_with_output().unwrap().status.success());
+
+    let record = fs::read_to_string(&path).unwrap();
+    let mut lines = record.lines();
+    assert_eq!(lines.next(), Some("naive-nim 3,4 m0"));
+    assert_eq!(lines.next().unwrap().split(' ').next(), Some("0-1"));
+
+    let continued = games_cli(&[
+        "--self-play",
+        "--replay",
+        &path,
+        "--save-game",
+        &path,
+        "naive-nim",
+        "1",
+    ]);
+    assert!(continued.status.success());
+
+    // the whole game is saved, from where the record started
+    let record = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert!(record.starts_with("naive-nim 3,4 m0\n0-1 "), "{record}");
+}
+
+#[test]
+fn bad_records_and_flags_are_reported() {
+    let path = temp_path("bad");
+
+    fs::write(&path, "naive-nim 3,4 m0\n0-9\n").unwrap();
+    let output = games_cli(&["--plain", "--replay", &path, "naive-nim", "1"]);
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("move 1 (`0-9`) can't be played"));
+
+    let output = games_cli(&["--plain", "--replay", &path, "chomp"]);
+    fs::remove_file(&path).unwrap();
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("the record is of Nim (Naive), not Chomp"));
+
+    let output = games_cli(&["--plain", "--save-game", &path, "naive-nim", "1,2"]);
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("--save-game is only supported by --play and --self-play."));
+}
@@ -0,0 +1,179 @@
+use std::{
+    fs,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+/// An Order and Chaos board with 8 empty squares and no lines, small enough to solve to the end.
+const BOARD: &str = ".XO.XX/OO.XOO/XXOO.X/O.XXOO/XXO.XX/OO.XO.";
+
+/// How every game from [`BOARD`] ends, since Chaos, the solver, can always keep it from a line.
+const WON: &str = "The solver won! The board is full without a line of 5, so Chaos wins.";
+
+/// The first three moves of the human, each of which the solver answers.
+const MOVES: [&str; 3] = ["0-0-x", "2-4-o", "3-1-x"];
+
+fn temp_path(name: &str) -> String {
+    let path = std::env::temp_dir().join(format!("games-cli-{}-{name}.json", std::process::id()));
+    path.to_str().unwrap().to_string()
+}
+
+/// Plays Order and Chaos from [`BOARD`] against the solver, entering `input` line by line,
+/// and returns everything written, whether or not the game finished.
+fn play(args: &[&str], input: &[&str]) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .arg("--play")
+        .args(args)
+        .args(["order-and-chaos", &format!("--board={BOARD}")])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let input = input.join("\n") + "\n";
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+
+    String::from_utf8(child.wait_with_output().unwrap().stdout).unwrap()
+}
+
+/// The board printed before the last prompt of `output`.
+fn last_board(output: &str) -> String {
+    let (before, _) = output.rsplit_once("\n\nYour move: ").unwrap();
+    let lines = before.lines().collect::<Vec<_>>();
+    lines[lines.len() - 6..].join("\n")
+}
+
+#[test]
+fn saved_games_resume_where_they_were_left() {
+    let path = temp_path("order-and-chaos");
+
+    let mut input = MOVES.to_vec();
+    let save = format!("save {path} --table");
+    input.push(&save);
+    let saved = play(&[], &input);
+    assert!(
+        saved.contains(&format!("Saved the game to {path}.")),
+        "{saved}"
+    );
+
+    let json = fs::read_to_string(&path).unwrap();
+    let file = serde_json::from_str::<serde_json::Value>(&json).unwrap();
+    let solved = file["table"].as_array().unwrap().len();
+    assert!(solved > 0);
+
+    let resaved_path = temp_path("order-and-chaos-resaved");
+    let resave = format!("save {resaved_path}");
+    let resumed = play(&["--resume", &path], &[&resave]);
+
+    assert!(
+        resumed.starts_with(&format!(
+            "Resumed the game after 6 moves, with {solved} solved positions.\n"
+        )),
+        "{resumed}"
+    );
+    assert_eq!(last_board(&resumed), last_board(&saved));
+
+    // the same moves were played from the same position, with the same options
+    let resaved =
+        serde_json::from_str::<serde_json::Value>(&fs::read_to_string(&resaved_path).unwrap())
+            .unwrap();
+    assert_eq!(resaved["record"], file["record"]);
+    assert_eq!(resaved["options"], file["options"]);
+    assert!(resaved.get("table").is_none());
+
+    // the solver answers the human's last move as it would have without stopping
+    let mut uninterrupted = MOVES.to_vec();
+    uninterrupted.push("5-2-x");
+    let uninterrupted = play(&[], &uninterrupted);
+    let finished = play(&["--resume", &path], &["5-2-x"]);
+
+    fs::remove_file(&path).unwrap();
+    fs::remove_file(&resaved_path).unwrap();
+
+    let last_reply = |output: &str| {
+        output
+            .lines()
+            .filter(|line| line.starts_with("The solver plays"))
+            .last()
+            .map(str::to_string)
+    };
+    assert_eq!(last_reply(&finished), last_reply(&uninterrupted));
+    assert!(finished.trim_end().ends_with(WON), "{finished}");
+}
+
+#[test]
+fn saved_games_can_be_loaded_mid_game() {
+    let path = temp_path("order-and-chaos-load");
+
+    let mut input = MOVES.to_vec();
+    let save = format!("save {path}");
+    input.push(&save);
+    play(&[], &input);
+
+    let load = format!("load {path}");
+    let loaded = play(&[], &["0-0-o", &load, "5-2-x"]);
+    fs::remove_file(&path).unwrap();
+
+    assert!(
+        loaded.contains("Resumed the game after 6 moves, with 0 solved positions.\n"),
+        "{loaded}"
+    );
+    assert!(loaded.trim_end().ends_with(WON), "{loaded}");
+}
+
+#[test]
+fn other_versions_of_the_format_are_rejected() {
+    let path = temp_path("order-and-chaos-version");
+
+    let mut input = MOVES.to_vec();
+    let save = format!("save {path}");
+    input.push(&save);
+    play(&[], &input);
+
+    let json = fs::read_to_string(&path).unwrap();
+    fs::write(&path, json.replace("\"version\":1", "\"version\":2")).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .args(["--play", "--resume", &path, "order-and-chaos"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("it was saved in version 2 of the format, but only version 1 can be read"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn saved_games_of_other_games_are_rejected() {
+    let path = temp_path("order-and-chaos-other-game");
+
+    let mut input = MOVES.to_vec();
+    let save = format!("save {path}");
+    input.push(&save);
+    play(&[], &input);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .args(["--play", "--resume", &path, "tic-tac-toe", "2", "3"])
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("the record is of Order and Chaos, not Tic Tac Toe"),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,47 @@
+use std::process::Command;
+
+fn self_play(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .arg("--self-play")
+        .args(args)
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn perfect_tic_tac_toe_is_a_tie() {
+    let output = self_play(&["tic-tac-toe", "2", "3"]);
+
+    assert!(output.contains("9. "));
+    assert!(output.trim_end().ends_with("Game tied!"));
+}
+
+#[test]
+fn first_player_wins_uneven_nim() {
+    let output = self_play(&["naive-nim", "1,2"]);
+
+    assert!(output.contains("1. 1-1 (the mover expects to win in"));
+    assert!(output.trim_end().ends_with("The first player won!"));
+}
+
+#[test]
+fn seeds_are_reproducible() {
+    let args = ["--seed", "42", "tic-tac-toe", "2", "3"];
+
+    assert_eq!(self_play(&args), self_play(&args));
+}
+
+#[test]
+fn different_seeds_play_different_perfect_games() {
+    let games =
+        ["1", "2", "3", "4", "5"].map(|seed| self_play(&["--seed", seed, "tic-tac-toe", "2", "3"]));
+
+    for game in &games {
+        assert!(game.trim_end().ends_with("Game tied!"), "{game}");
+    }
+    assert!(games.iter().any(|game| *game != games[0]));
+}
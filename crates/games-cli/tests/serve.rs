@@ -0,0 +1,133 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+};
+
+struct Server {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Server {
+    fn start() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+            .arg("serve")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+
+        Self {
+            child,
+            stdin,
+            stdout,
+        }
+    }
+
+    /// Sends a command, and waits for its response.
+    fn send(&mut self, command: &str) -> String {
+        writeln!(self.stdin, "{command}").unwrap();
+
+        let mut response = String::new();
+        self.stdout.read_line(&mut response).unwrap();
+        response.trim_end().to_string()
+    }
+
+    fn quit(mut self) {
+        writeln!(self.stdin, "quit").unwrap();
+        assert!(self.child.wait().unwrap().success());
+    }
+}
+
+#[test]
+fn solver_plays_itself_to_a_tie() {
+    let mut server = Server::start();
+    assert_eq!(server.send("newgame tic-tac-toe 2 3 1-1"), "ok");
+
+    let mut moves = 1;
+    loop {
+        let response = server.send("go");
+        let [bestmove, m, score_label, score] = response.split(' ').collect::<Vec<_>>()[..] else {
+            panic!("unexpected response {response}");
+        };
+
+        // perfect play is always a tie
+        assert_eq!([bestmove, score_label, score], ["bestmove", "score", "0"]);
+
+        moves += 1;
+        let response = server.send(&format!("move {m}"));
+        if moves == 9 {
+            assert_eq!(response, "gameover tie");
+            break;
+        }
+        assert_eq!(response, "ok");
+    }
+
+    assert_eq!(server.send("go"), "error the game is over");
+    server.quit();
+}
+
+#[test]
+fn handles_errors_and_undo() {
+    let mut server = Server::start();
+
+    assert!(server.send("move 1-1").starts_with("error no game"));
+    assert!(server
+        .send("games")
+        .starts_with("games reversi tic-tac-toe order-and-chaos"));
+    assert!(server.send("newgame checkers").starts_with("error"));
+    assert_eq!(server.send("newgame tic-tac-toe 2 3"), "ok");
+
+    assert_eq!(server.send("move 1-1"), "ok");
+    assert!(server.send("move 1-1").starts_with("error"));
+    assert!(server.send("go depth x").starts_with("error"));
+    assert!(server.send("fly").starts_with("error unknown command"));
+
+    // the corners are the only replies to the center that don't lose
+    let scores = server.send("scores");
+    let scores = scores.split(' ').collect::<Vec<_>>();
+    assert_eq!(scores[0], "scores");
+    assert_eq!(scores.len(), 9);
+    assert!(scores[1..5].iter().all(|score| score.ends_with("=0")));
+    assert!(scores[5..].iter().all(|score| !score.ends_with("=0")));
+
+    assert_eq!(server.send("undo"), "ok");
+    assert!(server.send("undo").starts_with("error"));
+    assert!(server.send("go depth 2").starts_with("bestmove "));
+
+    // with time to see the end of the game, the solver knows Tic-Tac-Toe is a tie
+    let go = server.send("go time 60000");
+    assert!(
+        go.starts_with("bestmove ") && go.ends_with(" score 0"),
+        "{go}"
+    );
+
+    server.quit();
+}
+
+#[test]
+fn redo_replays_undone_moves() {
+    let mut server = Server::start();
+    assert_eq!(server.send("newgame tic-tac-toe 2 3"), "ok");
+
+    assert!(server.send("redo").starts_with("error"));
+    assert_eq!(server.send("move 1-1"), "ok");
+    let scores = server.send("scores");
+
+    assert_eq!(server.send("undo"), "ok");
+    assert_eq!(server.send("redo"), "ok");
+    assert_eq!(server.send("scores"), scores);
+    assert!(server.send("redo").starts_with("error"));
+
+    // playing another move forgets the undone one
+    assert_eq!(server.send("undo"), "ok");
+    assert_eq!(server.send("move 0-0"), "ok");
+    assert!(server.send("redo").starts_with("error"));
+    assert!(server.send("redo 1").starts_with("error"));
+
+    server.quit();
+}
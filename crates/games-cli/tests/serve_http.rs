@@ -0,0 +1,180 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpStream,
+    process::{Child, Command, Stdio},
+};
+
+use serde_json::{json, Value};
+
+/// A running `serve-http`, which is stopped when dropped.
+struct Server {
+    child: Child,
+    address: String,
+}
+
+impl Server {
+    fn start(args: &[&str]) -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_serve-http"))
+            .args(["--address", "127.0.0.1:0"])
+            .args(args)
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        // the server picks a free port, and says which
+        let mut line = String::new();
+        BufReader::new(child.stdout.take().unwrap())
+            .read_line(&mut line)
+            .unwrap();
+        let address = line
+            .trim()
+            .strip_prefix("Listening on http://")
+            .unwrap_or_else(|| panic!("unexpected first line {line}"))
+            .to_string();
+
+        Self { child, address }
+    }
+
+    /// Sends a request, returning the status and body of the response.
+    fn request(&self, method: &str, path: &str, body: &str) -> (u16, String) {
+        let mut stream = TcpStream::connect(&self.address).unwrap();
+        write!(
+            stream,
+            "{method} {path} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            self.address,
+            body.len()
+        )
+        .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+        (status, body.to_string())
+    }
+
+    /// Posts `body` to `path`, expecting a successful response.
+    fn post(&self, path: &str, body: Value) -> Value {
+        let (status, response) = self.request("POST", path, &body.to_string());
+        assert_eq!(status, 200, "{response}");
+        serde_json::from_str(&response).unwrap()
+    }
+
+    /// Posts `body` to `path`, expecting an error with `status`.
+    fn post_error(&self, path: &str, body: &str, status: u16) -> String {
+        let (actual, response) = self.request("POST", path, body);
+        assert_eq!(actual, status, "{response}");
+
+        let response = serde_json::from_str::<Value>(&response).unwrap();
+        response["error"].as_str().unwrap().to_string()
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn tic_tac_toe() -> Value {
+    json!({
+        "game": "tic-tac-toe",
+        "position": {"dimensions": 2, "size": 3, "board": ["X--", "-O-", "---"]}
+    })
+}
+
+fn nim(heaps: &[usize]) -> Value {
+    json!({"game": "naive-nim", "position": {"heaps": heaps, "move_count": 0}})
+}
+
+#[test]
+fn solves_tic_tac_toe_and_nim() {
+    let server = Server::start(&[]);
+
+    let solved = server.post("/solve", tic_tac_toe());
+    assert_eq!(solved["player"], "Left");
+    assert_eq!(solved["evaluation"]["outcome"]["result"], "tie");
+    assert_eq!(solved["complete"], true);
+    assert!(solved["nodes"].as_u64().unwrap() > 0);
+
+    // 3 xor 5 isn't 0, so the player to move wins
+    let solved = server.post("/solve", nim(&[3, 5]));
+    assert_eq!(solved["player"], "Next");
+    assert_eq!(solved["evaluation"]["outcome"]["result"], "win");
+    assert!(solved["evaluation"]["score"].as_i64().unwrap() > 0);
+}
+
+#[test]
+fn scores_the_moves_of_tic_tac_toe_and_nim() {
+    let server = Server::start(&[]);
+
+    let scored = server.post("/moves", tic_tac_toe());
+    let moves = scored["moves"].as_array().unwrap();
+    assert_eq!(moves.len(), 7);
+    assert_eq!(scored["complete"], true);
+    // from best to worst, and the best keeps the tie
+    assert_eq!(moves[0]["score"], 0);
+    assert!(moves
+        .windows(2)
+        .all(|pair| pair[0]["score"].as_i64() >= pair[1]["score"].as_i64()));
+
+    // the only winning move evens out the heaps
+    let scored = server.post("/moves", nim(&[3, 5]));
+    let moves = scored["moves"].as_array().unwrap();
+    assert_eq!(moves.len(), 8);
+    assert_eq!(moves[0]["move"], "1-2");
+    assert_eq!(moves[0]["outcome"]["result"], "win");
+    assert_eq!(moves[1]["outcome"]["result"], "loss");
+}
+
+#[test]
+fn searches_stop_at_their_budget() {
+    let server = Server::start(&["--max-nodes", "1000000"]);
+
+    let mut request = json!({
+        "game": "domineering",
+        "position": {"board": [".....", ".....", ".....", ".....", "....."], "primary_orientation": "Vertical", "move_count": 0},
+        "nodes": 50
+    });
+
+    let solved = server.post("/solve", request.clone());
+    assert_eq!(solved["complete"], false);
+    assert!(solved.get("evaluation").is_none());
+
+    request["time_ms"] = json!(0);
+    let scored = server.post("/moves", request);
+    assert_eq!(scored["complete"], false);
+}
+
+#[test]
+fn bad_requests_are_rejected() {
+    let server = Server::start(&["--max-moves", "20"]);
+
+    assert!(server
+        .post_error("/solve", "{not json", 400)
+        .contains("can't be read"));
+    assert!(server
+        .post_error("/solve", r#"{"game": "chess", "position": {}}"#, 400)
+        .contains("can't be read"));
+
+    // an impossible position is caught when it's read
+    let impossible = json!({"game": "naive-nim", "position": {"heaps": [1, 2], "move_count": 0, "max_moves": 1}});
+    server.post_error("/moves", &impossible.to_string(), 400);
+
+    let oversized = nim(&[10, 10, 10]).to_string();
+    assert!(server
+        .post_error("/solve", &oversized, 413)
+        .contains("up to 30 moves left"));
+    server.post_error("/solve", &" ".repeat(100_000), 413);
+
+    let (status, _) = server.request("GET", "/solve", "");
+    assert_eq!(status, 405);
+    let (status, _) = server.request("POST", "/play", "{}");
+    assert_eq!(status, 404);
+
+    // the server keeps answering after errors
+    assert_eq!(server.post("/solve", nim(&[1, 1]))["complete"], true);
+}
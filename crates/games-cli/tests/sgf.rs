@@ -0,0 +1,52 @@
+mod common;
+
+use std::{fs, process::Output};
+
+use common::games_cli;
+
+const MOVES: &[&str] = &[
+    "4-2", "4-1", "3-1", "4-3", "5-0", "2-1", "5-3", "5-1", "2-0", "5-2", "1-4", "3-0", "4-0",
+    "2-4", "2-5", "5-4", "4-4", "1-3", "5-5", "1-5", "1-1", "4-5", "0-5", "0-4", "3-5", "1-0",
+    "0-3", "3-4",
+];
+
+#[test]
+fn exports_and_reads_back_a_game() {
+    let path = std::env::temp_dir().join(format!("games-cli-{}.sgf", std::process::id()));
+    let path = path.to_str().unwrap();
+
+    let exported = games_cli(
+        &[
+            &["--format", "json", "reversi", "--export-sgf", path],
+            MOVES,
+        ]
+        .concat(),
+    );
+    assert!(exported.status.success());
+
+    let read = games_cli(&["--format", "json", "reversi", "--sgf", path]);
+    let sgf = fs::read_to_string(path).unwrap();
+    fs::remove_file(path).unwrap();
+
+    assert!(read.status.success());
+    assert!(sgf.starts_with("(;GM[2]FF[4]SZ[6]AB["), "{sgf}");
+
+    let notation = |output: &Output| {
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()["notation"].clone()
+    };
+    assert_eq!(notation(&read), notation(&exported));
+}
+
+#[test]
+fn reports_where_the_file_is_wrong() {
+    let path = std::env::temp_dir().join(format!("games-cli-bad-{}.sgf", std::process::id()));
+    fs::write(&path, "(;GM[2]SZ[6]\n;B[ec]\n;B[dd])").unwrap();
+
+    let output = games_cli(&["reversi", "--sgf", path.to_str().unwrap()]);
+    fs::remove_file(&path).unwrap();
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("line 3, column 2: B[dd] is Black's move, but it's White's turn"));
+}
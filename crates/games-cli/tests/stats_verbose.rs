@@ -0,0 +1,69 @@
+use std::process::Command;
+
+/// The rows of the per-ply table `--stats-verbose` prints, split into columns.
+fn ply_table(args: &[&str]) -> Vec<Vec<String>> {
+    let output = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .args(["--plain", "--stats-verbose"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let (_, table) = stdout
+        .split_once("Per ply:\n\n")
+        .unwrap_or_else(|| panic!("no table: {stdout}"));
+
+    table
+        .lines()
+        .skip(1)
+        .take_while(|line| !line.is_empty())
+        .map(|line| line.split_whitespace().map(str::to_string).collect())
+        .collect()
+}
+
+#[test]
+fn every_cutoff_is_on_some_move() {
+    let rows = ply_table(&["naive-nim", "2,3"]);
+
+    assert!(!rows.is_empty());
+    for (ply, row) in rows.iter().enumerate() {
+        let count = |column: usize| row[column].parse::<u64>().unwrap();
+
+        assert_eq!(count(0), ply as u64);
+        assert!(count(2) <= count(1), "{row:?}");
+        assert_eq!(count(3) + count(4) + count(5), count(2), "{row:?}");
+    }
+    // the moves of the position are scored one by one, so it isn't searched itself
+    assert_eq!(rows[0][1], "0");
+    assert_eq!(rows[0][6], "-");
+}
+
+#[test]
+fn the_last_ply_has_no_positions_after_it() {
+    let rows = ply_table(&["tic-tac-toe", "2", "3", "1-1", "0-0"]);
+
+    assert_eq!(rows.last().unwrap()[6], "0.00");
+}
+
+#[test]
+fn the_table_is_not_printed_without_the_flag() {
+    let output = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .args(["--plain", "naive-nim", "2,3"])
+        .output()
+        .unwrap();
+
+    assert!(!String::from_utf8(output.stdout)
+        .unwrap()
+        .contains("Per ply:"));
+}
+
+#[test]
+fn stats_verbose_conflicts_with_json() {
+    let output = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+        .args(["--stats-verbose", "--format", "json", "naive-nim", "2,3"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+}
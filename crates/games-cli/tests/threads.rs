@@ -0,0 +1,56 @@
+mod common;
+
+use common::{games_cli, games_cli_stdout};
+
+#[test]
+fn analysis_is_the_same_on_any_thread_count() {
+    let analyses = ["1", "2", "8"].map(|threads| {
+        games_cli_stdout(&[
+            "--threads",
+            threads,
+            "--format",
+            "csv",
+            "chomp",
+            "--width",
+            "5",
+            "--height",
+            "4",
+        ])
+    });
+
+    assert!(analyses[0].starts_with("move,score,outcome,rank\n"));
+    assert_eq!(analyses[0], analyses[1]);
+    assert_eq!(analyses[0], analyses[2]);
+}
+
+#[test]
+fn arena_is_the_same_on_any_thread_count() {
+    let arenas = ["1", "4"].map(|threads| {
+        games_cli_stdout(&[
+            "--threads",
+            threads,
+            "arena",
+            "--agent-a",
+            "greedy",
+            "--agent-b",
+            "random",
+            "--games",
+            "20",
+            "--parallel",
+            "--seed",
+            "3",
+            "tic-tac-toe",
+            "2",
+            "3",
+        ])
+    });
+
+    assert_eq!(arenas[0], arenas[1]);
+}
+
+#[test]
+fn threads_must_be_positive() {
+    let output = games_cli(&["--threads", "0", "naive-nim", "1,2"]);
+
+    assert!(!output.status.success());
+}
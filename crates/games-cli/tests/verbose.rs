@@ -0,0 +1,144 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    process::Command,
+    sync::{Arc, Mutex},
+};
+
+use game_solver::move_scores;
+use games::naive_nim::Nim;
+use tracing::{
+    field::{Field, Visit},
+    span, Event, Level, Metadata, Subscriber,
+};
+
+/// An event's level and message, and whether it counted the nodes searched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Captured {
+    level: Level,
+    message: String,
+    has_nodes: bool,
+}
+
+/// Captures every span's name and every event.
+#[derive(Default, Clone)]
+struct Capture {
+    spans: Arc<Mutex<Vec<&'static str>>>,
+    events: Arc<Mutex<Vec<Captured>>>,
+}
+
+struct Visitor<'a>(&'a mut Captured);
+
+impl Visit for Visitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        match field.name() {
+            "message" => self.0.message = format!("{value:?}"),
+            "nodes" => self.0.has_nodes = true,
+            _ => (),
+        }
+    }
+}
+
+impl Subscriber for Capture {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &span::Attributes<'_>) -> span::Id {
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(span.metadata().name());
+        span::Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut captured = Captured {
+            level: *event.metadata().level(),
+            message: String::new(),
+            has_nodes: false,
+        };
+        event.record(&mut Visitor(&mut captured));
+        self.events.lock().unwrap().push(captured);
+    }
+
+    fn enter(&self, _span: &span::Id) {}
+
+    fn exit(&self, _span: &span::Id) {}
+}
+
+#[test]
+fn solving_nim_emits_events() {
+    let capture = Capture::default();
+
+    let scores = tracing::subscriber::with_default(capture.clone(), || {
+        let game = Nim::new(vec![2, 3, 4]);
+        let mut table = HashMap::new();
+
+        move_scores(&game, &mut table, None, &None)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    });
+
+    let spans = capture.spans.lock().unwrap();
+    assert_eq!(
+        spans.iter().filter(|&&name| name == "root_move").count(),
+        scores.len()
+    );
+    assert_eq!(
+        spans.iter().filter(|&&name| name == "solve").count(),
+        scores.len()
+    );
+
+    let events = capture.events.lock().unwrap();
+    assert!(events.iter().all(|event| event.has_nodes), "{events:?}");
+
+    for (level, message) in [
+        (Level::INFO, "solved"),
+        (Level::DEBUG, "null window search"),
+        (Level::DEBUG, "re-searching with the full window"),
+        (Level::TRACE, "transposition table hit"),
+        (Level::TRACE, "transposition table store"),
+        (Level::TRACE, "beta cutoff"),
+    ] {
+        assert!(
+            events
+                .iter()
+                .any(|event| event.level == level && event.message == message),
+            "no {level} event `{message}`"
+        );
+    }
+
+    assert_eq!(
+        events
+            .iter()
+            .filter(|event| event.message == "solved")
+            .count(),
+        scores.len()
+    );
+}
+
+#[test]
+fn verbose_flags_increase_the_level() {
+    let stderr = |verbose: &str| {
+        let output = Command::new(env!("CARGO_BIN_EXE_games-cli"))
+            .args(["--plain", verbose, "naive-nim", "1,2"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+
+        String::from_utf8(output.stderr).unwrap()
+    };
+
+    let info = stderr("-v");
+    assert!(info.contains(" INFO root_move{index=0}:solve{move_count=1}: solved nodes="));
+    assert!(!info.contains("DEBUG"));
+
+    let debug = stderr("-vv");
+    assert!(debug.contains("DEBUG root_move{index=0}:solve{move_count=1}: null window search"));
+    assert!(!debug.contains("TRACE"));
+
+    assert!(stderr("-vvv").contains("TRACE"));
+}
@@ -0,0 +1,32 @@
+mod common;
+
+use common::games_cli_stdout;
+
+#[test]
+fn tic_tac_toe_agrees_with_brute_force() {
+    let output = games_cli_stdout(&[
+        "verify",
+        "tic-tac-toe",
+        "--samples",
+        "10",
+        "--seed",
+        "1",
+        "--node-limit",
+        "20000",
+    ]);
+
+    assert!(
+        output.starts_with("Verified 10 positions of Tic Tac Toe: "),
+        "{output}"
+    );
+    assert!(output.contains(" 0 disagreed, "), "{output}");
+}
+
+#[test]
+fn the_same_seed_verifies_the_same_positions() {
+    let args = ["verify", "naive-nim", "--samples", "20", "--seed", "7"];
+
+    let output = games_cli_stdout(&args);
+    assert!(output.contains(" 0 disagreed, "), "{output}");
+    assert_eq!(output, games_cli_stdout(&args));
+}
@@ -2,47 +2,165 @@
 
 #[cfg(feature = "egui")]
 pub mod gui;
-use anyhow::Error;
-use array2d::Array2D;
+use anyhow::{anyhow, Error};
 use clap::Args;
 use game_solver::{
-    game::{Game, GameState, Normal, NormalImpartial},
-    player::ImpartialPlayer,
+    cgt::OutcomeClass,
+    game::{Normal, NormalImpartial},
+    hint::Hints,
+    prelude::*,
 };
-use serde::{Deserialize, Serialize};
+use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
 use thiserror::Error;
 
 use std::{
     fmt::{Debug, Display, Formatter},
-    hash::Hash,
+    hash::{Hash, Hasher},
 };
 
-use crate::util::{cli::move_failable, move_natural::NaturalMove};
+use crate::util::metadata::{GameMetadata, Parameter};
+use crate::util::{
+    bench::{parse_moves, BenchPosition},
+    cli::{
+        evaluate::Heuristics,
+        outcome::{impartial_class, OutcomeClasses},
+        replay_moves, Endings,
+    },
+    move_natural::NaturalMove,
+    notation::{fields, NotatedGame, NotationError},
+    parse::{parse_coord, parse_coord_unbounded, ParseError, ParseMove},
+    position::{parse_board, PositionError},
+    render::{Cell, Grid, Render},
+    scan::{scan, Scan},
+    symmetry::Symmetries,
+};
 
-#[derive(Clone, Hash, Eq, PartialEq)]
+/// What's left of the board is a staircase anchored on the poisoned square in its bottom left corner,
+/// so it's stored as the height of each column, from the left, which never increase.
+/// The poisoned square is counted in the height of the first column, which is never less than 1.
+#[derive(Clone, Eq, Deserialize)]
+#[serde(try_from = "ChompPosition")]
 pub struct Chomp {
     width: usize,
     height: usize,
-    // TODO: bit array 2d
-    /// True represents a square that has not been eaten
-    board: Array2D<bool>,
+    /// How many squares are left in each column, counting up from the bottom row.
+    heights: Vec<u8>,
     move_count: usize,
 }
 
+/// The tallest board, so that the height of a column fits in a byte.
+pub const MAX_HEIGHT: usize = u8::MAX as usize;
+
+/// The game the CLI plays without arguments, on an uneaten board 6 squares wide and 4 tall.
+impl Default for Chomp {
+    fn default() -> Self {
+        Self::new(6, 4)
+    }
+}
+
 impl Chomp {
+    /// An uneaten board.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the board is empty, or taller than [`MAX_HEIGHT`].
     pub fn new(width: usize, height: usize) -> Self {
-        let mut board = Array2D::filled_with(true, width, height);
-        board.set(0, height - 1, false).unwrap();
+        assert!(width > 0 && height > 0, "the board can't be empty");
+        assert!(
+            height <= MAX_HEIGHT,
+            "the board can be at most {MAX_HEIGHT} squares tall"
+        );
 
         Self {
             width,
             height,
-            board,
+            heights: vec![height as u8; width],
             move_count: 0,
         }
     }
+
+    /// Whether the square at `(x, y)`, counting rows from the top, is left to be eaten,
+    /// which the poisoned square never is.
+    fn is_left(&self, x: usize, y: usize) -> bool {
+        y + (self.heights[x] as usize) >= self.height && (x, y) != (0, self.height - 1)
+    }
+
+    /// The height of the `row`th row from the bottom of the board flipped along its diagonal:
+    /// the number of columns taller than `row`.
+    fn transposed_height(&self, row: usize) -> u8 {
+        self.heights
+            .partition_point(|&height| height as usize > row) as u8
+    }
+
+    /// Whether the board flipped along its diagonal, which plays out the same way, comes before it.
+    /// Only square boards are flipped, so that the flipped board fits on the same board.
+    fn is_transposed(&self) -> bool {
+        self.width == self.height
+            && (0..self.width)
+                .map(|row| self.transposed_height(row))
+                .lt(self.heights.iter().copied())
+    }
+
+    /// The heights of the position or of its flip along the diagonal, whichever comes first,
+    /// which are the same for both.
+    fn canonical_heights(&self) -> impl Iterator<Item = u8> + '_ {
+        let transposed = self.is_transposed();
+
+        (0..self.width).map(move |column| {
+            if transposed {
+                self.transposed_height(column)
+            } else {
+                self.heights[column]
+            }
+        })
+    }
+}
+
+/// Positions are the same if they're flips of each other along the diagonal,
+/// so that the search shares their entries.
+impl PartialEq for Chomp {
+    fn eq(&self, other: &Self) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self.move_count == other.move_count
+            && self.canonical_heights().eq(other.canonical_heights())
+    }
+}
+
+impl Hash for Chomp {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.width.hash(state);
+        self.height.hash(state);
+        self.move_count.hash(state);
+        for height in self.canonical_heights() {
+            state.write_u8(height);
+        }
+    }
 }
 
+/// What Chomp is, and how it's set up from the CLI.
+pub const METADATA: GameMetadata = GameMetadata {
+    name: "Chomp",
+    summary: "Eat a square and every square to the right of and above it; whoever eats the poisoned square loses.",
+    rules: include_str!("./README.md"),
+    parameters: &[
+        Parameter {
+            name: "--width <WIDTH>",
+            description: "The width of the board.",
+            default: "6",
+            range: "1 or more",
+        },
+        Parameter {
+            name: "--height <HEIGHT>",
+            description: "The height of the board.",
+            default: "4",
+            range: "1 to 255",
+        },
+    ],
+    move_notation: "x-y, zero-indexed, or algebraically (e.g. 2-1, or c2)",
+    example: "chomp --width 4 --height 3 2-1",
+};
+
 /// Analyzes Chomp.
 ///
 #[doc = include_str!("./README.md")]
@@ -54,9 +172,14 @@ pub struct ChompArgs {
     /// The height of the game
     #[arg(long, default_value_t = 4)]
     height: usize,
-    /// Chomp moves, ordered as x1-y1 x2-y2 ...
-    #[arg(value_parser = clap::value_parser!(ChompMove))]
+    /// Chomp moves, ordered as x1-y1 x2-y2 ... (or algebraically, as c4 d3 ...)
+    #[arg(value_parser = parse_chomp_move)]
     moves: Vec<ChompMove>,
+    /// The position to analyze, in its notation (e.g. `XXXXXX/XXXXXX/XXX.../.XX... m1`),
+    /// instead of the width, height, and moves.
+    #[arg(long, conflicts_with_all = ["width", "height", "moves"])]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<String>,
 }
 
 impl Default for ChompArgs {
@@ -65,6 +188,7 @@ impl Default for ChompArgs {
             width: 6,
             height: 4,
             moves: vec![],
+            position: None,
         }
     }
 }
@@ -73,19 +197,35 @@ impl Default for ChompArgs {
 pub enum ChompMoveError {
     #[error("position {0:?} is already filled.")]
     ValueAlreadyFilled(ChompMove),
+    #[error("position {played:?} is out of bounds of the {width}x{height} board.")]
+    OutOfBounds {
+        played: ChompMove,
+        width: usize,
+        height: usize,
+    },
 }
 
 pub type ChompMove = NaturalMove<2>;
 
+/// Parses a Chomp move, either as `x-y` or algebraically (e.g. `c4`).
+///
+/// Since the board size is only known once the game is created,
+/// bounds are checked when the move is made.
+pub fn parse_chomp_move(s: &str) -> Result<ChompMove, ParseError> {
+    parse_coord_unbounded(s).map(|(x, y)| NaturalMove([x, y]))
+}
+
 impl Normal for Chomp {}
 impl NormalImpartial for Chomp {}
 
 impl Game for Chomp {
     type Move = ChompMove;
-    type Iter<'a> = std::vec::IntoIter<Self::Move>;
+    type Iter<'a> = Scan<'a, Self, Self::Move>;
     type Player = ImpartialPlayer;
     type MoveError = ChompMoveError;
 
+    const IMPARTIAL: bool = true;
+
     fn max_moves(&self) -> Option<usize> {
         Some(self.width * self.height)
     }
@@ -94,60 +234,279 @@ impl Game for Chomp {
         self.move_count
     }
 
+    /// A move is written as the square eaten from, but made on the heights of the columns:
+    /// eating `x-y` cuts column `x` and every column to its right down to the rows below `y`.
     fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
-        if *self.board.get(m.0[0], m.0[1]).unwrap() {
-            for i in m.0[0]..self.width {
-                for j in 0..=m.0[1] {
-                    self.board.set(i, j, false).unwrap();
-                }
-            }
-            self.move_count += 1;
-            Ok(())
-        } else {
-            Err(ChompMoveError::ValueAlreadyFilled(*m))
+        let [x, y] = m.0;
+
+        if x >= self.width || y >= self.height {
+            return Err(ChompMoveError::OutOfBounds {
+                played: *m,
+                width: self.width,
+                height: self.height,
+            });
+        }
+
+        if !self.is_left(x, y) {
+            return Err(ChompMoveError::ValueAlreadyFilled(*m));
         }
+
+        let cut = (self.height - 1 - y) as u8;
+        for height in &mut self.heights[x..] {
+            *height = (*height).min(cut);
+        }
+        self.move_count += 1;
+
+        Ok(())
     }
 
     fn possible_moves(&self) -> Self::Iter<'_> {
-        let mut moves = Vec::new();
-        for i in (0..self.height).rev() {
-            for j in 0..self.width {
-                if *self.board.get(j, i).unwrap() {
-                    moves.push(NaturalMove([j, i]));
-                }
-            }
-        }
-        moves.into_iter()
+        // from the bottom row up, left to right
+        scan(self, self.width * self.height, |(game, index)| {
+            let (y, x) = (game.height - 1 - index / game.width, index % game.width);
+            game.is_left(x, y).then_some(NaturalMove([x, y]))
+        })
     }
 
     fn player(&self) -> Self::Player {
         ImpartialPlayer::Next
     }
 
-    fn state(&self) -> GameState<Self::Player> {
-        <Self as Normal>::state(&self)
+    fn status(&self) -> GameStatus {
+        <Self as Normal>::status(self)
     }
 }
 
 impl Display for Chomp {
     fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
-        for i in 0..self.height {
-            for j in 0..self.width {
-                if *self.board.get(j, i).unwrap() {
-                    write!(f, "X")?;
-                } else {
-                    write!(f, ".")?;
+        self.board_grid().write_plain(f)
+    }
+}
+
+impl Chomp {
+    /// The board as a grid, where the squares left are written as `X` on the plain board, and eaten ones as `.`.
+    fn board_grid(&self) -> Grid {
+        Grid::new(
+            (0..self.height)
+                .map(|i| {
+                    (0..self.width)
+                        .map(|j| {
+                            if self.is_left(j, i) {
+                                Cell::new('X', '█')
+                            } else {
+                                Cell::empty('.')
+                            }
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Render for Chomp {
+    fn grid(&self) -> Option<Grid> {
+        Some(self.board_grid())
+    }
+
+    /// The square that was chomped from, which is eaten along with every square above and to the right of it.
+    fn move_cells(&self, m: &Self::Move) -> Vec<(usize, usize)> {
+        let [x, y] = m.0;
+        vec![(y, x)]
+    }
+}
+
+/// Written as the rows of the board, separated by `/`, using the same characters as [`Display`],
+/// followed by the move count, e.g. `XXXXXX/XXXXXX/XXX.../.XX... m1`.
+impl NotatedGame for Chomp {
+    fn to_notation(&self) -> String {
+        format!(
+            "{} m{}",
+            self.to_string().lines().collect::<Vec<_>>().join("/"),
+            self.move_count
+        )
+    }
+
+    fn from_notation(notation: &str) -> Result<Self, NotationError> {
+        let fields = fields(notation, &["board", "move count"], &[])?;
+        let rows = fields[0].board("X.", None)?;
+        let move_count = fields[1]
+            .strip_prefix('m', "move count")?
+            .number("move count")?;
+
+        let position = ChompPosition {
+            width: rows[0].len(),
+            height: rows.len(),
+            board: rows.iter().map(|row| row.iter().collect()).collect(),
+            move_count,
+        };
+
+        let (heights, eaten) = position
+            .heights()
+            .map_err(|err| fields[0].error(err.to_string()))?;
+        position
+            .check_move_count(eaten)
+            .map_err(|err| fields[1].error(err.to_string()))?;
+
+        Ok(Self {
+            width: position.width,
+            height: position.height,
+            heights,
+            move_count,
+        })
+    }
+}
+
+impl Debug for Chomp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        <Self as Display>::fmt(self, f)
+    }
+}
+
+/// Serializes the board as its rows, using the same characters as [`Display`].
+impl Serialize for Chomp {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let board = self.to_string();
+
+        let mut state = serializer.serialize_struct("Chomp", 4)?;
+        state.serialize_field("width", &self.width)?;
+        state.serialize_field("height", &self.height)?;
+        state.serialize_field("board", &board.lines().collect::<Vec<_>>())?;
+        state.serialize_field("move_count", &self.move_count)?;
+        state.end()
+    }
+}
+
+/// A serialized [`Chomp`], before it's checked.
+#[derive(Deserialize)]
+struct ChompPosition {
+    width: usize,
+    height: usize,
+    board: Vec<String>,
+    move_count: usize,
+}
+
+impl ChompPosition {
+    /// Checks that the board is what's left after eating squares,
+    /// returning the height of each of its columns, and how many squares were eaten.
+    fn heights(&self) -> Result<(Vec<u8>, usize), PositionError> {
+        let (width, height) = (self.width, self.height);
+
+        if width == 0 || height == 0 {
+            return Err(PositionError::impossible(
+                "the board must be at least 1 square wide and tall",
+            ));
+        }
+
+        if height > MAX_HEIGHT {
+            return Err(PositionError::impossible(format!(
+                "the board can be at most {MAX_HEIGHT} squares tall"
+            )));
+        }
+
+        let rows = parse_board(&self.board, width, height, "X.")?;
+        let poison = (0, height - 1);
+
+        if rows[poison.1][poison.0] != '.' {
+            return Err(PositionError::impossible(format!(
+                "the poisoned square {}-{} can't be eaten, so it's always marked `.`",
+                poison.0, poison.1
+            )));
+        }
+
+        // the poisoned square is never eaten, but it's stored as if it was
+        let left = |x: usize, y: usize| rows[y][x] == 'X' || (x, y) == poison;
+
+        // eating a square also eats every square to its right and above it,
+        // so what's left is a staircase anchored on the poisoned square
+        for y in 0..height {
+            for x in 0..width {
+                if !left(x, y) {
+                    continue;
+                }
+
+                if x > 0 && !left(x - 1, y) {
+                    return Err(PositionError::impossible(format!(
+                        "{x}-{y} is left, but the square to its left was eaten, which would have eaten it too"
+                    )));
+                }
+
+                if y + 1 < height && !left(x, y + 1) {
+                    return Err(PositionError::impossible(format!(
+                        "{x}-{y} is left, but the square below it was eaten, which would have eaten it too"
+                    )));
                 }
             }
-            writeln!(f)?;
         }
+
+        let eaten = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter(|&(x, y)| !left(x, y))
+            .count();
+
+        let heights = (0..width)
+            .map(|x| (0..height).filter(|&y| left(x, y)).count() as u8)
+            .collect();
+
+        Ok((heights, eaten))
+    }
+
+    /// Checks that `eaten` squares can be eaten in the position's moves.
+    fn check_move_count(&self, eaten: usize) -> Result<(), PositionError> {
+        let move_count = self.move_count;
+
+        // every move eats at least one square
+        if move_count > eaten || (move_count == 0 && eaten > 0) {
+            return Err(PositionError::impossible(format!(
+                "{eaten} squares can't be eaten in {move_count} moves"
+            )));
+        }
+
         Ok(())
     }
 }
 
-impl Debug for Chomp {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        <Self as Display>::fmt(self, f)
+impl TryFrom<ChompPosition> for Chomp {
+    type Error = PositionError;
+
+    fn try_from(position: ChompPosition) -> Result<Self, Self::Error> {
+        let (heights, eaten) = position.heights()?;
+        position.check_move_count(eaten)?;
+
+        Ok(Self {
+            width: position.width,
+            height: position.height,
+            heights,
+            move_count: position.move_count,
+        })
+    }
+}
+
+impl ParseMove for Chomp {
+    fn parse_move(&self, token: &str) -> Result<Self::Move, ParseError> {
+        parse_coord(token, self.width, self.height).map(|(x, y)| NaturalMove([x, y]))
+    }
+}
+
+impl Heuristics for Chomp {}
+
+impl Hints for Chomp {}
+
+impl Endings for Chomp {}
+
+impl OutcomeClasses for Chomp {
+    fn outcome_class(&self) -> Option<Result<OutcomeClass, GameSolveError<Self>>> {
+        impartial_class(self)
+    }
+}
+
+/// A square board is symmetric to its flip along the diagonal, and the one with the smaller heights is canonical.
+impl Symmetries for Chomp {
+    fn canonical(&self) -> Self {
+        Self {
+            heights: self.canonical_heights().collect(),
+            ..self.clone()
+        }
     }
 }
 
@@ -155,22 +514,56 @@ impl TryFrom<ChompArgs> for Chomp {
     type Error = Error;
 
     fn try_from(args: ChompArgs) -> Result<Self, Self::Error> {
-        let mut game = Chomp::new(args.width, args.height);
+        if let Some(position) = args.position {
+            return Ok(Self::from_notation(&position)?);
+        }
 
-        // parse every move in args, e.g. 0-0 1-1 in args
-        for arg in args.moves {
-            move_failable(&mut game, &arg)?;
+        if args.width == 0 || args.height == 0 || args.height > MAX_HEIGHT {
+            return Err(anyhow!(
+                "The board must be at least 1 square wide, and 1 to {MAX_HEIGHT} squares tall"
+            ));
         }
 
+        let mut game = Chomp::new(args.width, args.height);
+
+        // play every move in args, e.g. 0-0 1-1 in args
+        replay_moves(&mut game, &args.moves)?;
+
         Ok(game)
     }
 }
 
+/// Positions for the `bench` subcommand.
+pub fn bench_positions() -> Vec<BenchPosition<ChompArgs>> {
+    [
+        ("empty 4x3", true, 4, 3, ""),
+        ("empty 6x4", true, 6, 4, ""),
+        ("6x4 after 5-0", true, 6, 4, "5-0"),
+        ("empty 7x6", false, 7, 6, ""),
+    ]
+    .into_iter()
+    .map(|(name, quick, width, height, moves)| BenchPosition {
+        name,
+        quick,
+        args: ChompArgs {
+            width,
+            height,
+            moves: parse_moves(moves),
+            position: None,
+        },
+    })
+    .collect()
+}
+
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
 
-    use game_solver::{move_scores, GameSolveError};
+    use game_solver::{move_scores, optimal_moves, solve, stats::Stats, GameSolveError};
+
+    use crate::util::move_score::{
+        impartial_outcomes_testing, optimal_moves_testing, window_results_testing,
+    };
 
     use super::*;
 
@@ -212,4 +605,246 @@ mod tests {
 
         assert_eq!(move_scores, new_scores);
     }
+
+    #[test]
+    fn serde_round_trip() {
+        let mut game = Chomp::new(6, 4);
+        game.make_move(&NaturalMove([3, 1])).unwrap();
+        game.make_move(&NaturalMove([1, 0])).unwrap();
+
+        let json = serde_json::to_string(&game).unwrap();
+        assert_eq!(
+            json,
+            r#"{"width":6,"height":4,"board":["X.....","XXX...","XXXXXX",".XXXXX"],"move_count":2}"#
+        );
+        assert_eq!(serde_json::from_str::<Chomp>(&json).unwrap(), game);
+    }
+
+    #[test]
+    fn serde_rejects_impossible_positions() {
+        let error = |board: &str, move_count: usize| {
+            serde_json::from_str::<Chomp>(&format!(
+                r#"{{"width":3,"height":2,"board":{board},"move_count":{move_count}}}"#
+            ))
+            .unwrap_err()
+            .to_string()
+        };
+
+        // not a staircase
+        assert!(error(r#"[".X.",".XX"]"#, 1).contains("to its left was eaten"));
+        assert!(error(r#"["XXX",".X."]"#, 1).contains("below it was eaten"));
+        // the poisoned square is always marked as eaten
+        assert!(error(r#"["XXX","XXX"]"#, 0).contains("poisoned square"));
+        // one move can eat several squares, but not none
+        assert!(error(r#"["X..",".XX"]"#, 0).contains("2 squares can't be eaten in 0 moves"));
+        assert!(error(r#"["X..",".XX"]"#, 3).contains("2 squares can't be eaten in 3 moves"));
+    }
+
+    #[test]
+    fn notation_round_trip() {
+        let mut game = Chomp::new(6, 4);
+        assert_eq!(game.to_notation(), "XXXXXX/XXXXXX/XXXXXX/.XXXXX m0");
+
+        game.make_move(&NaturalMove([3, 2])).unwrap();
+        assert_eq!(game.to_notation(), "XXX.../XXX.../XXX.../.XXXXX m1");
+        assert_eq!(Chomp::from_notation(&game.to_notation()).unwrap(), game);
+
+        let game = Chomp::new(2, 1);
+        assert_eq!(Chomp::from_notation(" .X  m0 ").unwrap(), game);
+    }
+
+    #[test]
+    fn notation_rejects_invalid_positions() {
+        let span = |notation: &str| Chomp::from_notation(notation).unwrap_err().span();
+
+        // the poisoned square is eaten
+        assert_eq!(span("XX/XX m0"), 0..5);
+        // an eaten square with squares to its right
+        assert_eq!(span(".X/.X m1"), 0..5);
+        // 4 squares can't be eaten in 5 moves
+        assert_eq!(span("X../... m5"), 8..10);
+        assert_eq!(span("X../... 1"), 8..9);
+        assert_eq!(span("X../.#. m1"), 5..6);
+        assert_eq!(span("X../..  m1"), 4..6);
+    }
+
+    #[test]
+    fn optimal_moves_match_the_best_scores() {
+        for (width, height) in [(4, 3), (5, 2), (3, 3)] {
+            let game = Chomp::new(width, height);
+
+            assert_eq!(
+                optimal_moves(&game, &mut HashMap::new()).unwrap(),
+                optimal_moves_testing(&game),
+                "{width}x{height}"
+            );
+        }
+    }
+
+    #[test]
+    fn grundy_outcomes_agree_with_negamax() {
+        impartial_outcomes_testing(&Chomp::new(5, 4), 12, 300);
+    }
+
+    /// Chomp as it was before it was stored as the heights of its columns: a grid of the squares left,
+    /// where every position is only the same as itself.
+    #[derive(Clone, PartialEq, Eq, Hash)]
+    struct GridChomp {
+        width: usize,
+        height: usize,
+        /// Whether each square is left, row by row from the top, with the poisoned square never left.
+        board: Vec<bool>,
+        move_count: usize,
+    }
+
+    impl GridChomp {
+        fn new(width: usize, height: usize) -> Self {
+            let mut board = vec![true; width * height];
+            board[(height - 1) * width] = false;
+
+            Self {
+                width,
+                height,
+                board,
+                move_count: 0,
+            }
+        }
+    }
+
+    impl Normal for GridChomp {}
+    impl NormalImpartial for GridChomp {}
+
+    impl Game for GridChomp {
+        type Move = ChompMove;
+        type Iter<'a> = std::vec::IntoIter<ChompMove>;
+        type Player = ImpartialPlayer;
+        type MoveError = ChompMoveError;
+
+        const IMPARTIAL: bool = true;
+
+        fn max_moves(&self) -> Option<usize> {
+            Some(self.width * self.height)
+        }
+
+        fn move_count(&self) -> usize {
+            self.move_count
+        }
+
+        fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+            let [x, y] = m.0;
+            if !self.board[y * self.width + x] {
+                return Err(ChompMoveError::ValueAlreadyFilled(*m));
+            }
+
+            for i in x..self.width {
+                for j in 0..=y {
+                    self.board[j * self.width + i] = false;
+                }
+            }
+            self.move_count += 1;
+
+            Ok(())
+        }
+
+        fn possible_moves(&self) -> Self::Iter<'_> {
+            (0..self.height)
+                .rev()
+                .flat_map(|y| (0..self.width).map(move |x| NaturalMove([x, y])))
+                .filter(|m| self.board[m.0[1] * self.width + m.0[0]])
+                .collect::<Vec<_>>()
+                .into_iter()
+        }
+
+        fn player(&self) -> Self::Player {
+            ImpartialPlayer::Next
+        }
+
+        fn status(&self) -> GameStatus {
+            <Self as Normal>::status(self)
+        }
+    }
+
+    /// Solves every position reachable on a `width`x`height` board as heights and as a grid,
+    /// each with a table shared between its positions, checking that they have the same moves and scores.
+    fn check_heights(width: usize, height: usize) {
+        let mut positions = vec![(Chomp::new(width, height), GridChomp::new(width, height))];
+        let mut seen = HashSet::from([GridChomp::new(width, height)]);
+        let mut table = HashMap::new();
+        let mut grid_table = HashMap::new();
+
+        while let Some((game, grid)) = positions.pop() {
+            let moves = game.possible_moves().collect::<Vec<_>>();
+            assert_eq!(moves, grid.possible_moves().collect::<Vec<_>>(), "{game}");
+
+            let Ok(score) = solve(&game, &mut table, None, &None) else {
+                panic!("the solver failed");
+            };
+            let Ok(expected) = solve(&grid, &mut grid_table, None, &None) else {
+                panic!("the solver failed");
+            };
+            assert_eq!(score, expected, "{width}x{height}\n{game}");
+
+            for m in moves {
+                let (mut game, mut grid) = (game.clone(), grid.clone());
+                game.make_move(&m).unwrap();
+                grid.make_move(&m).unwrap();
+                if seen.insert(grid.clone()) {
+                    positions.push((game, grid));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn heights_solve_as_the_grid_did() {
+        for width in 1..=5 {
+            for height in 1..=5 {
+                if width.min(height) <= 4 {
+                    check_heights(width, height);
+                }
+            }
+        }
+    }
+
+    /// The score of solving `game` from scratch, how many positions it searched, and how many its table holds.
+    fn explore<T: Game<Player = ImpartialPlayer> + Eq + Hash>(game: &T) -> (isize, u64, usize) {
+        let stats = Stats::new(game.player(), game.move_count());
+        let mut table = HashMap::new();
+        let Ok(score) = solve(game, &mut table, Some(&stats), &None) else {
+            panic!("the solver failed");
+        };
+        (score, stats.states_explored.into_inner(), table.len())
+    }
+
+    #[test]
+    fn flipped_boards_share_entries() {
+        // a 5x6 board has no flips, so the same positions are searched,
+        // but each is stored as 5 heights instead of 30 squares
+        let (score, nodes, entries) = explore(&Chomp::new(5, 6));
+        let (expected, grid_nodes, grid_entries) = explore(&GridChomp::new(5, 6));
+        assert_eq!(
+            (score, nodes, entries),
+            (expected, grid_nodes, grid_entries)
+        );
+
+        // on a square board, a position and its flip are searched once between them
+        let (score, nodes, entries) = explore(&Chomp::new(6, 6));
+        let (expected, grid_nodes, grid_entries) = explore(&GridChomp::new(6, 6));
+        assert_eq!(score, expected);
+        assert!(nodes < grid_nodes, "{nodes} >= {grid_nodes}");
+        assert!(entries < grid_entries, "{entries} >= {grid_entries}");
+
+        let mut game = Chomp::new(3, 3);
+        game.make_move(&NaturalMove([2, 2])).unwrap();
+        let mut flipped = Chomp::new(3, 3);
+        flipped.make_move(&NaturalMove([0, 0])).unwrap();
+        assert_eq!(game, flipped);
+        assert_eq!(game.canonical(), flipped.canonical());
+        assert_ne!(game.to_string(), flipped.to_string());
+    }
+
+    #[test]
+    fn windows_agree_with_the_score() {
+        window_results_testing(&Chomp::new(5, 4), 12, 40);
+    }
 }
@@ -3,22 +3,38 @@
 #[cfg(feature = "egui")]
 pub mod gui;
 use anyhow::Error;
-use array2d::Array2D;
 use clap::Args;
 use game_solver::{
-    game::{Game, GameState, Normal},
-    player::{PartizanPlayer, Player},
+    cgt::{canonical_value, hot_moves, outcome_class, Dyadic, HotMove, OutcomeClass},
+    game::{Normal, PartizanGame},
+    hash::{zobrist_key, IncrementalHash},
+    hint::Hints,
+    ordering::PlacementGame,
+    prelude::*,
 };
-use serde::{Deserialize, Serialize};
+use serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 use std::{
+    cell::RefCell,
+    collections::HashMap,
     fmt::{Debug, Display, Formatter},
-    hash::Hash,
+    hash::{Hash, Hasher},
+    iter::Map,
 };
 use thiserror::Error;
 
-use crate::util::cli::move_failable;
+use crate::util::metadata::GameMetadata;
+use crate::util::{
+    bench::{parse_moves, BenchPosition},
+    bitboard::{Bitboard, Direction, Squares},
+    cli::{evaluate::Heuristics, outcome::OutcomeClasses, replay_parsed_moves, Endings},
+    notation::{fields, read_board, NotatedGame, NotationError, ToMove},
+    parse::{parse_coord, parse_numbers, ParseError, ParseMove},
+    position::{parse_board, PositionError},
+    render::{Cell, Grid, Render},
+    symmetry::Symmetries,
+};
 
-#[derive(Clone, Hash, Eq, PartialEq, Debug, Copy)]
+#[derive(Clone, Hash, Eq, PartialEq, Debug, Copy, Serialize, Deserialize)]
 pub enum Orientation {
     Horizontal,
     Vertical,
@@ -31,16 +47,62 @@ impl Orientation {
             Orientation::Vertical => Orientation::Horizontal,
         }
     }
+
+    /// The square a domino in this orientation covers along with `(x, y)`, its top left square.
+    fn other_square(&self, x: usize, y: usize) -> (usize, usize) {
+        match *self {
+            Orientation::Horizontal => (x + 1, y),
+            Orientation::Vertical => (x, y + 1),
+        }
+    }
+
+    /// The letter written after a move to say which orientation its domino is in.
+    fn letter(&self) -> char {
+        match *self {
+            Orientation::Horizontal => 'h',
+            Orientation::Vertical => 'v',
+        }
+    }
+}
+
+impl Display for Orientation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Orientation::Horizontal => write!(f, "horizontally"),
+            Orientation::Vertical => write!(f, "vertically"),
+        }
+    }
 }
 
-#[derive(Clone, Hash, Eq, PartialEq)]
+#[derive(Clone, Eq, Deserialize)]
+#[serde(try_from = "DomineeringPosition")]
 pub struct Domineering<const WIDTH: usize, const HEIGHT: usize> {
-    // TODO: bit array 2d
-    /// True represents a square - true if empty, false otherwise
-    board: Array2D<bool>,
+    /// Every square that is covered by a domino.
+    board: Bitboard<WIDTH, HEIGHT>,
+    /// The covered squares, along with the dead ones: empty squares with no empty neighbor,
+    /// which no domino of either orientation can ever cover.
+    filled: Bitboard<WIDTH, HEIGHT>,
     move_count: usize,
     /// The orientation the first player will play as.
     primary_orientation: Orientation,
+    /// The [`IncrementalHash`] of the board, the XOR of the keys of its filled squares.
+    hash: u64,
+}
+
+/// Positions are the same if the same squares are filled, whether by dominoes or by being dead,
+/// so that the search shares the entries of positions that only differ in where their dead squares are.
+impl<const WIDTH: usize, const HEIGHT: usize> PartialEq for Domineering<WIDTH, HEIGHT> {
+    fn eq(&self, other: &Self) -> bool {
+        self.filled == other.filled
+            && self.move_count == other.move_count
+            && self.primary_orientation == other.primary_orientation
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> Hash for Domineering<WIDTH, HEIGHT> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
 }
 
 impl<const WIDTH: usize, const HEIGHT: usize> Default for Domineering<WIDTH, HEIGHT> {
@@ -50,64 +112,264 @@ impl<const WIDTH: usize, const HEIGHT: usize> Default for Domineering<WIDTH, HEI
 }
 
 impl<const WIDTH: usize, const HEIGHT: usize> Domineering<WIDTH, HEIGHT> {
+    /// An empty board, where the first player places dominoes vertically.
     pub fn new() -> Self {
         Self::new_orientation(Orientation::Vertical)
     }
 
+    /// An empty board, where the first player places dominoes in `orientation`.
     pub fn new_orientation(orientation: Orientation) -> Self {
         Self {
-            board: Array2D::filled_with(true, WIDTH, HEIGHT),
+            board: Bitboard::new(),
+            filled: Bitboard::new(),
             move_count: 0,
             primary_orientation: orientation,
+            hash: 0,
         }
+        .normalized()
+    }
+
+    /// Sets up the position on `board`, written as in the notation but without the orientation to move
+    /// (e.g. `#..../#..../...../...../.....`), without replaying the moves that led to it.
+    ///
+    /// Left places dominoes vertically, and Right horizontally. By default, Left moved first,
+    /// so it's Left's turn if an even number of dominoes were placed; `to_move` says otherwise.
+    pub fn from_board(board: &str, to_move: Option<PartizanPlayer>) -> Result<Self, NotationError> {
+        read_board(board, |rows| {
+            // every domino covers two squares
+            let dominoes = rows.matches('#').count() / 2;
+            let orientation = match to_move {
+                Some(PartizanPlayer::Left) => 'v',
+                Some(PartizanPlayer::Right) => 'h',
+                None if dominoes % 2 == 0 => 'v',
+                None => 'h',
+            };
+
+            Self::from_notation(&format!("{rows} {orientation}"))
+        })
+    }
+
+    /// The Zobrist key of a filled square.
+    fn square_key(x: usize, y: usize) -> u64 {
+        zobrist_key(y * WIDTH + x)
+    }
+
+    /// The empty squares of `board` with no empty neighbor, which no domino can ever cover,
+    /// as squares only get covered.
+    fn dead(board: Bitboard<WIDTH, HEIGHT>) -> Bitboard<WIDTH, HEIGHT> {
+        let empty = !board;
+        let neighbors = Direction::ORTHOGONAL
+            .iter()
+            .fold(Bitboard::new(), |neighbors, &direction| {
+                neighbors | empty.shift(direction)
+            });
+
+        empty & !neighbors
+    }
+
+    /// The position with its filled squares and hash found from its board,
+    /// which every position is set up with before it's played from.
+    fn normalized(mut self) -> Self {
+        self.filled = self.board | Self::dead(self.board);
+        self.hash = self.full_hash();
+        self
+    }
+
+    /// The squares dead to both orientations: empty, but with no empty neighbor to place a domino with.
+    /// They're left out of the moves and regions of the position, and hashed as if they were covered.
+    ///
+    /// The squares dead to just one orientation are the empty ones that aren't [`placeable`](Self::placeable) in it
+    /// (or the square after one that is), which the moves of that orientation already leave out.
+    pub fn dead_squares(&self) -> Bitboard<WIDTH, HEIGHT> {
+        self.filled & !self.board
     }
 }
 
-#[derive(Error, Debug, Clone)]
+/// The most empty squares of a region whose temperature [`Domineering::hottest_regions_first`] finds.
+pub const HOT_REGION_SQUARES: u32 = 10;
+
+/// Why a domino can't be placed. Each names the player to move and the orientation they place dominoes in,
+/// as a move is only the top left square of its domino.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
 pub enum DomineeringMoveError {
-    #[error("While no domino is present at {0}, player {1:?} can not move at {0} because a domino is in way of placement.")]
-    BlockingAdjacent(DomineeringMove, PartizanPlayer),
-    #[error("Player {1:?} can not move at {0} because a domino is already at {0}.")]
+    #[error("{player:?} can not move at {m}: {player:?} places dominoes {orientation}, so the domino would also cover {other}, which is covered or off the board.")]
+    BlockingAdjacent {
+        m: DomineeringMove,
+        player: PartizanPlayer,
+        orientation: Orientation,
+        /// The square the domino covers besides its top left one.
+        other: DomineeringMove,
+    },
+    #[error("{1:?} can not move at {0} because a domino is already at {0}.")]
     BlockingCurrent(DomineeringMove, PartizanPlayer),
+    #[error("{1:?} can not move at {0} because it is off the board.")]
+    OffBoard(DomineeringMove, PartizanPlayer),
 }
 
+/// The top left square of a domino, as `x-y`.
+///
+/// Which way the domino lies isn't part of the move, as it's always the [orientation](Domineering::orientation)
+/// of the player making it.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct DomineeringMove(usize, usize);
 
 impl Display for DomineeringMove {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        Debug::fmt(&self, f)
+        write!(f, "{}-{}", self.0, self.1)
+    }
+}
+
+impl Serialize for DomineeringMove {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for DomineeringMove {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let token = String::deserialize(deserializer)?;
+        let numbers = parse_numbers(&token, 2).map_err(de::Error::custom)?;
+
+        Ok(DomineeringMove(numbers[0], numbers[1]))
     }
 }
 
 impl<const WIDTH: usize, const HEIGHT: usize> Domineering<WIDTH, HEIGHT> {
+    /// The orientation the player to move places their dominoes in, which every move they make is in.
+    pub fn orientation(&self) -> Orientation {
+        self.orientation_of(self.player())
+    }
+
+    /// The orientation `player` places their dominoes in.
+    pub fn orientation_of(&self, player: PartizanPlayer) -> Orientation {
+        if player == PartizanPlayer::Left {
+            self.primary_orientation
+        } else {
+            self.primary_orientation.turn()
+        }
+    }
+
+    /// The squares a domino in `orientation` can be placed at (from its top left square).
+    fn placeable(&self, orientation: Orientation) -> Bitboard<WIDTH, HEIGHT> {
+        let empty = !self.filled;
+
+        // a domino can be placed on any empty square whose neighbor
+        // (to the right or below, depending on the orientation) is also empty
+        match orientation {
+            Orientation::Horizontal => empty & empty.shift(Direction::West),
+            Orientation::Vertical => empty & empty.shift(Direction::North),
+        }
+    }
+
+    /// The separate regions of the board, each orthogonally connected group of empty squares,
+    /// as positions of their own where every other square is covered.
+    ///
+    /// The board is the sum of its regions, as a domino never spans two of them.
+    /// Dead squares, which are regions of their own that no one can move in, are left out.
+    pub fn regions(&self) -> Vec<Self> {
+        let mut empty = !self.filled;
+        let mut regions = Vec::new();
+
+        while let Some((x, y)) = empty.iter().next() {
+            let region = empty.flood_fill(Bitboard::single(x, y));
+            empty &= !region;
+
+            regions.push(
+                Self {
+                    board: !region,
+                    ..self.clone()
+                }
+                .normalized(),
+            );
+        }
+
+        regions
+    }
+
+    /// The moves of the player to move in each of the [`regions`](Self::regions),
+    /// from the hottest region to the coldest.
+    pub fn hot_moves(&self) -> Vec<HotMove<DomineeringMove>> {
+        hot_moves(&self.regions()).expect("moves of a region can be made in it")
+    }
+
+    /// Orders moves by the temperature of the region they're in, hottest first, like [`hot_moves`](Self::hot_moves),
+    /// but cheap enough for [`solve_ordered`](game_solver::solve_ordered) to use at every position.
+    ///
+    /// The temperature of a region is remembered by its shape, wherever it is on the board.
+    /// Finding it takes about as long as solving the region, so regions of more than
+    /// [`HOT_REGION_SQUARES`] empty squares aren't valued, and are searched first, as the hottest.
+    /// Moves in equally hot regions are left in row-major order.
+    pub fn hottest_regions_first() -> MoveOrdering<Self> {
+        let temperatures = RefCell::new(HashMap::new());
+
+        MoveOrdering::Heuristic(Box::new(move |game: &Self, m: &DomineeringMove| {
+            let region = (!game.board).flood_fill(Bitboard::single(m.0, m.1));
+            if region.count() > HOT_REGION_SQUARES {
+                return isize::MAX;
+            }
+
+            let shape = Self::region_shape(region);
+            let temperature = *temperatures.borrow_mut().entry(shape).or_insert_with(|| {
+                let region = Self {
+                    board: !shape,
+                    ..Self::new()
+                }
+                .normalized();
+
+                canonical_value(&region).temperature()
+            });
+
+            Self::temperature_key(temperature)
+        }))
+    }
+
+    /// `region` moved to the top left corner of the board, so that regions of the same shape are equal.
+    fn region_shape(region: Bitboard<WIDTH, HEIGHT>) -> Bitboard<WIDTH, HEIGHT> {
+        let left = region.iter().map(|(x, _)| x).min().unwrap_or(0);
+        let top = region.iter().map(|(_, y)| y).min().unwrap_or(0);
+
+        let mut shape = region;
+        for _ in 0..left {
+            shape = shape.shift(Direction::West);
+        }
+        for _ in 0..top {
+            shape = shape.shift(Direction::North);
+        }
+
+        shape
+    }
+
+    /// `temperature` as a whole number in the same order, as the temperatures of regions
+    /// that fit on a board have small denominators.
+    fn temperature_key(temperature: Dyadic) -> isize {
+        temperature.numerator() * (1 << 16) / temperature.denominator() as isize
+    }
+
     fn place(
         &mut self,
         m: &DomineeringMove,
         orientation: Orientation,
     ) -> Result<(), DomineeringMoveError> {
-        match orientation {
-            Orientation::Horizontal => {
-                if m.0 == WIDTH - 1 {
-                    return Err(DomineeringMoveError::BlockingAdjacent(
-                        m.clone(),
-                        self.player(),
-                    ));
-                }
-                self.board.set(m.0, m.1, false).unwrap();
-                self.board.set(m.0 + 1, m.1, false).unwrap();
-            }
-            Orientation::Vertical => {
-                if m.1 == HEIGHT - 1 {
-                    return Err(DomineeringMoveError::BlockingAdjacent(
-                        m.clone(),
-                        self.player(),
-                    ));
-                }
-                self.board.set(m.0, m.1, false).unwrap();
-                self.board.set(m.0, m.1 + 1, false).unwrap();
-            }
-        };
+        let (x, y) = orientation.other_square(m.0, m.1);
+
+        if !Bitboard::<WIDTH, HEIGHT>::on_board(x, y) || self.board.get(x, y) {
+            return Err(DomineeringMoveError::BlockingAdjacent {
+                m: m.clone(),
+                player: self.player(),
+                orientation,
+                other: DomineeringMove(x, y),
+            });
+        }
+
+        self.board.set(m.0, m.1, true);
+        self.board.set(x, y, true);
+
+        // only the domino's squares and their neighbors, which may now be dead, are filled
+        let filled = self.board | Self::dead(self.board);
+        for (x, y) in (filled ^ self.filled).iter() {
+            self.hash ^= Self::square_key(x, y);
+        }
+        self.filled = filled;
 
         Ok(())
     }
@@ -117,7 +379,7 @@ impl<const WIDTH: usize, const HEIGHT: usize> Normal for Domineering<WIDTH, HEIG
 
 impl<const WIDTH: usize, const HEIGHT: usize> Game for Domineering<WIDTH, HEIGHT> {
     type Move = DomineeringMove;
-    type Iter<'a> = std::vec::IntoIter<Self::Move>;
+    type Iter<'a> = Map<Squares<WIDTH, HEIGHT>, fn((usize, usize)) -> DomineeringMove>;
     type Player = PartizanPlayer;
     type MoveError = DomineeringMoveError;
 
@@ -130,84 +392,123 @@ impl<const WIDTH: usize, const HEIGHT: usize> Game for Domineering<WIDTH, HEIGHT
     }
 
     fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
-        if *self.board.get(m.0, m.1).unwrap() {
-            self.place(
-                m,
-                if self.player() == PartizanPlayer::Left {
-                    self.primary_orientation
-                } else {
-                    self.primary_orientation.turn()
-                },
-            )?;
-
-            self.move_count += 1;
-            Ok(())
-        } else {
-            Err(DomineeringMoveError::BlockingCurrent(
+        if !Bitboard::<WIDTH, HEIGHT>::on_board(m.0, m.1) {
+            return Err(DomineeringMoveError::OffBoard(m.clone(), self.player()));
+        }
+        if self.board.get(m.0, m.1) {
+            return Err(DomineeringMoveError::BlockingCurrent(
                 m.clone(),
                 self.player(),
-            ))
+            ));
         }
+
+        self.place(m, self.orientation())?;
+        self.move_count += 1;
+
+        Ok(())
     }
 
     fn possible_moves(&self) -> Self::Iter<'_> {
-        let mut moves = Vec::new();
-        let orientation = if self.player() == PartizanPlayer::Left {
-            self.primary_orientation
-        } else {
-            self.primary_orientation.turn()
-        };
-
-        match orientation {
-            Orientation::Horizontal => {
-                for i in 0..HEIGHT {
-                    for j in 0..WIDTH - 1 {
-                        if *self.board.get(j, i).unwrap() && *self.board.get(j + 1, i).unwrap() {
-                            moves.push(DomineeringMove(j, i));
-                        }
-                    }
-                }
-            }
-            Orientation::Vertical => {
-                for i in 0..HEIGHT - 1 {
-                    for j in 0..WIDTH {
-                        if *self.board.get(j, i).unwrap() && *self.board.get(j, i + 1).unwrap() {
-                            moves.push(DomineeringMove(j, i));
-                        }
-                    }
-                }
-            }
-        }
+        self.placeable(self.orientation())
+            .iter()
+            .map(|(x, y)| DomineeringMove(x, y))
+    }
 
-        moves.into_iter()
+    fn move_count_hint(&self) -> (usize, Option<usize>) {
+        let count = self.placeable(self.orientation()).count() as usize;
+        (count, Some(count))
     }
 
-    fn state(&self) -> GameState<Self::Player> {
-        <Self as Normal>::state(&self)
+    fn status(&self) -> GameStatus {
+        <Self as Normal>::status(self)
     }
 
     fn player(&self) -> Self::Player {
-        if self.move_count % 2 == 0 {
-            PartizanPlayer::Left
-        } else {
-            PartizanPlayer::Right
-        }
+        PartizanPlayer::from_move_count(self.move_count)
+    }
+}
+
+/// The filled squares are enough to tell positions apart, as each move fills at least two of them.
+impl<const WIDTH: usize, const HEIGHT: usize> IncrementalHash for Domineering<WIDTH, HEIGHT> {
+    fn incremental_hash(&self) -> u64 {
+        self.hash
+    }
+
+    fn full_hash(&self) -> u64 {
+        (self.board | Self::dead(self.board))
+            .iter()
+            .fold(0, |hash, (x, y)| hash ^ Self::square_key(x, y))
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> PlacementGame for Domineering<WIDTH, HEIGHT> {
+    fn grid_size(&self) -> Vec<usize> {
+        vec![HEIGHT, WIDTH]
+    }
+
+    /// The top left square of the domino.
+    fn cell(&self, m: &Self::Move) -> Vec<usize> {
+        vec![m.1, m.0]
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> PartizanGame for Domineering<WIDTH, HEIGHT> {
+    fn options(&self, player: PartizanPlayer) -> Vec<Self> {
+        let orientation = self.orientation_of(player);
+
+        self.placeable(orientation)
+            .iter()
+            .map(|(x, y)| {
+                let mut option = self.clone();
+                option
+                    .place(&DomineeringMove(x, y), orientation)
+                    .expect("the domino is placeable");
+                option.move_count += 1;
+
+                option
+            })
+            .collect()
     }
 }
 
 impl<const WIDTH: usize, const HEIGHT: usize> Display for Domineering<WIDTH, HEIGHT> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
-        for i in 0..HEIGHT {
-            for j in 0..WIDTH {
-                if *self.board.get(j, i).unwrap() {
-                    write!(f, "X")?;
-                } else {
-                    write!(f, ".")?;
-                }
-            }
-            writeln!(f)?;
-        }
-        Ok(())
+        self.board_grid().write_plain(f)
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> Domineering<WIDTH, HEIGHT> {
+    /// The board as a grid, where empty squares are written as `X` on the plain board, and covered ones as `.`.
+    fn board_grid(&self) -> Grid {
+        Grid::new(
+            (0..HEIGHT)
+                .map(|i| {
+                    (0..WIDTH)
+                        .map(|j| {
+                            if !self.board.get(j, i) {
+                                Cell::empty('X')
+                            } else {
+                                Cell::new('.', '█')
+                            }
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> Render for Domineering<WIDTH, HEIGHT> {
+    fn grid(&self) -> Option<Grid> {
+        Some(self.board_grid())
+    }
+
+    /// Both squares of the domino, placed by the player who isn't to move.
+    fn move_cells(&self, m: &Self::Move) -> Vec<(usize, usize)> {
+        let DomineeringMove(x, y) = *m;
+        let (other_x, other_y) = self.orientation().turn().other_square(x, y);
+
+        vec![(y, x), (other_y, other_x)]
     }
 }
 
@@ -217,43 +518,265 @@ impl<const WIDTH: usize, const HEIGHT: usize> Debug for Domineering<WIDTH, HEIGH
     }
 }
 
+/// Serializes the board as its rows, where `X` marks a covered square.
+impl<const WIDTH: usize, const HEIGHT: usize> Serialize for Domineering<WIDTH, HEIGHT> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let board = self.board.to_string();
+
+        let mut state = serializer.serialize_struct("Domineering", 3)?;
+        state.serialize_field("board", &board.lines().collect::<Vec<_>>())?;
+        state.serialize_field("primary_orientation", &self.primary_orientation)?;
+        state.serialize_field("move_count", &self.move_count)?;
+        state.end()
+    }
+}
+
+/// A serialized [`Domineering`], before it's checked.
+#[derive(Deserialize)]
+struct DomineeringPosition {
+    board: Vec<String>,
+    primary_orientation: Orientation,
+    move_count: usize,
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> TryFrom<DomineeringPosition>
+    for Domineering<WIDTH, HEIGHT>
+{
+    type Error = PositionError;
+
+    fn try_from(position: DomineeringPosition) -> Result<Self, Self::Error> {
+        let rows = parse_board(&position.board, WIDTH, HEIGHT, "X.")?;
+
+        let mut board = Bitboard::new();
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &square) in row.iter().enumerate() {
+                board.set(x, y, square == 'X');
+            }
+        }
+
+        // every move covers exactly two squares
+        let covered = board.iter().count();
+        if covered != 2 * position.move_count {
+            return Err(PositionError::impossible(format!(
+                "{covered} squares are covered, but {} dominoes cover {}",
+                position.move_count,
+                2 * position.move_count
+            )));
+        }
+
+        Ok(Self {
+            board,
+            filled: board,
+            move_count: position.move_count,
+            primary_orientation: position.primary_orientation,
+            hash: 0,
+        }
+        .normalized())
+    }
+}
+
+/// Written as the rows of the board, separated by `/`, where covered squares are marked with `#`,
+/// followed by the orientation of the player to move (`v` or `h`), e.g. `#..../#..../...../...../..... h`.
+impl<const WIDTH: usize, const HEIGHT: usize> NotatedGame for Domineering<WIDTH, HEIGHT> {
+    fn to_notation(&self) -> String {
+        let board = self
+            .board
+            .to_string()
+            .lines()
+            .map(|row| row.replace('X', "#"))
+            .collect::<Vec<_>>()
+            .join("/");
+
+        format!("{board} {}", self.orientation().letter())
+    }
+
+    fn from_notation(notation: &str) -> Result<Self, NotationError> {
+        let fields = fields(notation, &["board", "orientation to move"], &[])?;
+        let rows = fields[0].board("#.", Some((WIDTH, HEIGHT)))?;
+
+        // every move covers exactly two squares
+        let covered = rows
+            .iter()
+            .flatten()
+            .filter(|&&square| square == '#')
+            .count();
+        if covered % 2 != 0 {
+            return Err(fields[0].error(format!(
+                "{covered} squares are covered, but dominoes cover an even number"
+            )));
+        }
+        let move_count = covered / 2;
+
+        let orientation = fields[1].choice(
+            "orientation to move",
+            &[("v", Orientation::Vertical), ("h", Orientation::Horizontal)],
+        )?;
+
+        Self::try_from(DomineeringPosition {
+            board: rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|&square| if square == '#' { 'X' } else { square })
+                        .collect()
+                })
+                .collect(),
+            // the first player plays the primary orientation, and the players alternate
+            primary_orientation: if move_count % 2 == 0 {
+                orientation
+            } else {
+                orientation.turn()
+            },
+            move_count,
+        })
+        .map_err(|err| fields[0].error(err.to_string()))
+    }
+}
+
+/// What Domineering is, and how it's set up from the CLI.
+pub const METADATA: GameMetadata = GameMetadata {
+    name: "Domineering",
+    summary: "Place dominoes on a 5x5 board, Left vertically and Right horizontally; whoever can't place one loses.",
+    rules: include_str!("./README.md"),
+    parameters: &[],
+    move_notation: "x-y of the domino's top left square, zero-indexed, or algebraically (e.g. 0-0, or a1), \
+        optionally followed by the orientation of the player to move (e.g. 0-0-v)",
+    example: "domineering 0-0 2-2",
+};
+
 /// Analyzes Domineering.
 ///
 #[doc = include_str!("./README.md")]
 #[derive(Args, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Default)]
 pub struct DomineeringArgs {
+    /// Domineering moves, ordered as x1-y1 x2-y2 ... (or algebraically, as c4 d3 ...)
     moves: Vec<String>,
+    /// The position to analyze, in its notation
+    /// (e.g. `#..../#..../...../...../..... h`), instead of the moves.
+    #[arg(long, conflicts_with = "moves")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<String>,
+    /// The board to analyze (e.g. `#..../#..../...../...../.....`), with its rows separated by `/`
+    /// or on lines of their own, instead of the moves.
+    #[arg(long, conflicts_with_all = ["moves", "position"])]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    board: Option<String>,
+    /// The player to move on the board: Left places dominoes vertically, and Right horizontally.
+    /// By default, it's whoever's turn it is if Left moved first.
+    #[arg(long, requires = "board")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    to_move: Option<ToMove>,
+}
+
+/// A move is the top left square of its domino, which can be followed by `-v` or `-h` for the orientation
+/// it's placed in (e.g. `0-0-v` or `a1-v`). That has to be the orientation of the player to move,
+/// so moves written with it can't be played out of turn.
+impl<const WIDTH: usize, const HEIGHT: usize> ParseMove for Domineering<WIDTH, HEIGHT> {
+    fn parse_move(&self, token: &str) -> Result<Self::Move, ParseError> {
+        let trimmed = token.trim();
+        let (square, orientation) = match trimmed.rsplit_once('-') {
+            Some((square, "v" | "V")) => (square, Some(Orientation::Vertical)),
+            Some((square, "h" | "H")) => (square, Some(Orientation::Horizontal)),
+            _ => (trimmed, None),
+        };
+
+        let (x, y) = parse_coord(square, WIDTH, HEIGHT).map_err(|err| match err {
+            ParseError::Malformed { expected, .. } => ParseError::malformed(token, expected),
+            ParseError::OutOfRange { expected, .. } => ParseError::out_of_range(token, expected),
+        })?;
+
+        let expected = self.orientation();
+        if orientation.is_some_and(|orientation| orientation != expected) {
+            return Err(ParseError::out_of_range(
+                token,
+                format!(
+                    "a move of {:?}, who places dominoes {expected} (e.g. {}-{})",
+                    self.player(),
+                    square,
+                    expected.letter()
+                ),
+            ));
+        }
+
+        Ok(DomineeringMove(x, y))
+    }
 }
 
+impl<const WIDTH: usize, const HEIGHT: usize> Heuristics for Domineering<WIDTH, HEIGHT> {}
+
+impl<const WIDTH: usize, const HEIGHT: usize> Hints for Domineering<WIDTH, HEIGHT> {}
+
+/// Left is the player who places dominoes in the primary orientation.
+impl<const WIDTH: usize, const HEIGHT: usize> Endings for Domineering<WIDTH, HEIGHT> {}
+
+impl<const WIDTH: usize, const HEIGHT: usize> OutcomeClasses for Domineering<WIDTH, HEIGHT> {
+    fn outcome_class(&self) -> Option<Result<OutcomeClass, GameSolveError<Self>>> {
+        Some(Ok(outcome_class(self)))
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> Symmetries for Domineering<WIDTH, HEIGHT> {}
+
 impl<const WIDTH: usize, const HEIGHT: usize> TryFrom<DomineeringArgs>
     for Domineering<WIDTH, HEIGHT>
 {
     type Error = Error;
 
     fn try_from(args: DomineeringArgs) -> Result<Self, Self::Error> {
+        if let Some(position) = args.position {
+            return Ok(Self::from_notation(&position)?);
+        }
+
+        if let Some(board) = args.board {
+            return Ok(Self::from_board(&board, args.to_move.map(Into::into))?);
+        }
+
         let mut game = Domineering::new();
 
         // parse every move in args, e.g. 0-0 1-1 in args
-        for arg in args.moves {
-            let numbers: Vec<usize> = arg
-                .split('-')
-                .map(|num| num.parse::<usize>().expect("Not a number!"))
-                .collect();
-
-            move_failable(&mut game, &DomineeringMove(numbers[0], numbers[1]))?;
-        }
+        replay_parsed_moves(&mut game, &args.moves)?;
 
         Ok(game)
     }
 }
 
+/// Positions for the `bench` subcommand, on the 5x5 board the CLI uses.
+pub fn bench_positions() -> Vec<BenchPosition<DomineeringArgs>> {
+    [
+        ("5x5 after 4 moves", true, "0-0 2-2 4-0 0-3"),
+        ("5x5 after 2 moves", true, "0-0 2-2"),
+        ("empty 5x5", false, ""),
+    ]
+    .into_iter()
+    .map(|(name, quick, moves)| BenchPosition {
+        name,
+        quick,
+        args: DomineeringArgs {
+            moves: parse_moves(moves),
+            position: None,
+            board: None,
+            to_move: None,
+        },
+    })
+    .collect()
+}
+
 #[cfg(test)]
 mod tests {
-    use std::collections::HashMap;
+    use std::{
+        cmp::Ordering,
+        collections::{BTreeMap, HashMap, HashSet},
+    };
 
-    use game_solver::{move_scores, GameSolveError};
+    use game_solver::{
+        cgt::{canonical_value, CanonicalGame, Dyadic, OutcomeClass},
+        move_scores, solve, solve_ordered,
+        stats::Stats,
+        GameSolveError,
+    };
 
     use super::*;
+    use crate::util::move_score::window_results_testing;
 
     /// Get the winner of a generic configuration of domineering
     fn winner<const WIDTH: usize, const HEIGHT: usize>(
@@ -339,4 +862,655 @@ mod tests {
 
         assert_eq!(move_scores, current_scores);
     }
+
+    #[test]
+    fn serde_round_trip() {
+        let mut game = Domineering::<5, 5>::new();
+        game.make_move(&DomineeringMove(0, 0)).unwrap();
+        game.make_move(&DomineeringMove(2, 3)).unwrap();
+
+        let json = serde_json::to_string(&game).unwrap();
+        assert_eq!(
+            json,
+            r#"{"board":["X....","X....",".....","..XX.","....."],"primary_orientation":"Vertical","move_count":2}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Domineering<5, 5>>(&json).unwrap(),
+            game
+        );
+
+        assert_eq!(
+            serde_json::from_str::<DomineeringMove>(r#""2-3""#).unwrap(),
+            DomineeringMove(2, 3)
+        );
+    }
+
+    #[test]
+    fn serde_rejects_impossible_positions() {
+        let err = serde_json::from_str::<Domineering<5, 5>>(
+            r#"{"board":["X....",".....",".....",".....","....."],"primary_orientation":"Vertical","move_count":1}"#,
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().contains("1 squares are covered"), "{err}");
+    }
+
+    #[test]
+    fn notation_round_trip() {
+        let mut game = Domineering::<5, 5>::new();
+        assert_eq!(game.to_notation(), "...../...../...../...../..... v");
+
+        game.make_move(&DomineeringMove(0, 0)).unwrap();
+        assert_eq!(game.to_notation(), "#..../#..../...../...../..... h");
+        assert_eq!(
+            Domineering::<5, 5>::from_notation(&game.to_notation()).unwrap(),
+            game
+        );
+
+        game.make_move(&DomineeringMove(2, 2)).unwrap();
+        assert_eq!(game.to_notation(), "#..../#..../..##./...../..... v");
+        assert_eq!(
+            Domineering::<5, 5>::from_notation(&game.to_notation()).unwrap(),
+            game
+        );
+
+        // the horizontal player can move first too
+        let game = Domineering::<5, 5>::new_orientation(Orientation::Horizontal);
+        assert_eq!(
+            Domineering::<5, 5>::from_notation("...../...../...../...../..... h").unwrap(),
+            game
+        );
+    }
+
+    #[test]
+    fn boards_agree_with_moves() {
+        let game: Domineering<5, 5> = DomineeringArgs {
+            moves: ["0-0", "2-2"].map(String::from).to_vec(),
+            ..Default::default()
+        }
+        .try_into()
+        .unwrap();
+
+        let board = "#..../#..../..##./...../.....";
+        let from_board = Domineering::<5, 5>::from_board(board, None).unwrap();
+        assert_eq!(from_board, game);
+        assert_eq!(from_board.move_count(), 2);
+
+        // with Right to move, Right moved first
+        let right_first = Domineering::<5, 5>::from_board(board, Some(PartizanPlayer::Right));
+        assert_eq!(
+            right_first.unwrap(),
+            Domineering::<5, 5>::from_notation(&format!("{board} h")).unwrap()
+        );
+
+        let error = |board| Domineering::<5, 5>::from_board(board, None).unwrap_err();
+        assert_eq!(error("#..../...../...../...../.....").span(), 0..29);
+        assert_eq!(error("#..../#.X../...../...../.....").span(), 8..9);
+    }
+
+    #[test]
+    fn notation_rejects_invalid_positions() {
+        let span = |notation: &str| {
+            Domineering::<5, 5>::from_notation(notation)
+                .unwrap_err()
+                .span()
+        };
+
+        // an odd number of covered squares
+        assert_eq!(span("#..../...../...../...../..... h"), 0..29);
+        assert_eq!(span("#..../#..X./...../...../..... h"), 9..10);
+        assert_eq!(span("#..../#..../...../...../..... x"), 30..31);
+        assert_eq!(span("#..../#..../...../..... h"), 0..23);
+        assert_eq!(span("#..../#..../...../...../....."), 29..29);
+    }
+
+    #[test]
+    fn canonical_values() {
+        // each player can move once, leaving nothing for the other: a hot switch
+        let square = canonical_value(&Domineering::<2, 2>::new());
+        assert_eq!(
+            square,
+            CanonicalGame::new(
+                vec![CanonicalGame::integer(1)],
+                vec![CanonicalGame::integer(-1)]
+            )
+        );
+        assert_eq!(square.to_string(), "±1");
+
+        // only the vertical player (Left) can move in a column, and only the horizontal one (Right) in a row
+        assert_eq!(
+            canonical_value(&Domineering::<1, 2>::new()),
+            CanonicalGame::integer(1)
+        );
+        assert_eq!(
+            canonical_value(&Domineering::<2, 1>::new()),
+            CanonicalGame::integer(-1)
+        );
+        assert_eq!(
+            canonical_value(&Domineering::<1, 5>::new()),
+            CanonicalGame::integer(2)
+        );
+        assert_eq!(
+            canonical_value(&Domineering::<6, 1>::new()),
+            CanonicalGame::integer(-3)
+        );
+        assert_eq!(
+            canonical_value(&Domineering::<7, 1>::new()),
+            CanonicalGame::integer(-3)
+        );
+
+        // whoever moves in an L-shaped tromino leaves a single square: {0 | 0} = *
+        let tromino = Domineering::<3, 3>::from_notation("..#/.##/### v").unwrap();
+        let star = canonical_value(&tromino);
+        assert_eq!(star, CanonicalGame::nimber(1));
+        assert_eq!(star.to_string(), "*");
+    }
+
+    #[test]
+    fn canonical_arithmetic() {
+        let zero = CanonicalGame::zero();
+        let star = CanonicalGame::nimber(1);
+        let one = CanonicalGame::integer(1);
+
+        // * is its own negative, and confused with 0: whoever moves first wins
+        assert_eq!(&star + &star, zero);
+        assert_eq!(-&star, star);
+        assert_eq!(star.partial_cmp(&zero), None);
+
+        // {0 | 1} is 1/2, and two halves make a whole
+        let half = CanonicalGame::new(vec![zero.clone()], vec![one.clone()]);
+        assert_eq!(half.to_string(), "1/2");
+        assert_eq!(&half + &half, one);
+        assert!(zero < half && half < one);
+        assert_eq!((&half + &star).to_string(), "1/2*");
+
+        // a switch is confused with every number between its options, and its negative cancels it
+        let switch = CanonicalGame::new(vec![one.clone()], vec![-&one]);
+        assert_eq!(switch.partial_cmp(&half), None);
+        assert_eq!(&switch + &-&switch, zero);
+        assert_eq!(CanonicalGame::nimber(2).to_string(), "*2");
+        assert_eq!(
+            (&CanonicalGame::nimber(2) + &CanonicalGame::nimber(3)).to_string(),
+            "*"
+        );
+    }
+
+    #[test]
+    fn thermographs() {
+        // a switch {a | b} has a temperature of (a - b) / 2, and a mean of (a + b) / 2
+        for (a, b) in [(1, -1), (3, -1), (2, 1), (1, 0)] {
+            let switch = CanonicalGame::new(
+                vec![CanonicalGame::integer(a)],
+                vec![CanonicalGame::integer(b)],
+            );
+            assert_eq!(switch.temperature(), Dyadic::new(a - b, 1), "{switch}");
+            assert_eq!(switch.mean(), Dyadic::new(a + b, 1), "{switch}");
+        }
+
+        // the walls of ±1 come together as the tax on moves rises
+        let thermograph = canonical_value(&Domineering::<2, 2>::new()).thermograph();
+        assert_eq!(
+            thermograph.left_wall(Dyadic::integer(0)),
+            Dyadic::integer(1)
+        );
+        assert_eq!(
+            thermograph.right_wall(Dyadic::integer(0)),
+            Dyadic::integer(-1)
+        );
+        assert_eq!(thermograph.left_wall(Dyadic::new(1, 1)), Dyadic::new(1, 1));
+        assert_eq!(
+            thermograph.right_wall(Dyadic::integer(2)),
+            Dyadic::integer(0)
+        );
+
+        // numbers are cold, and infinitesimals have a temperature of 0
+        let half = CanonicalGame::new(vec![CanonicalGame::zero()], vec![CanonicalGame::integer(1)]);
+        let three_quarters =
+            CanonicalGame::new(vec![half.clone()], vec![CanonicalGame::integer(1)]);
+        assert_eq!(CanonicalGame::integer(3).temperature(), Dyadic::integer(-1));
+        assert_eq!(half.temperature(), Dyadic::new(-1, 1));
+        assert_eq!(three_quarters.temperature(), Dyadic::new(-1, 2));
+        assert_eq!(three_quarters.mean(), Dyadic::new(3, 2));
+
+        let star = CanonicalGame::nimber(1);
+        let up = CanonicalGame::new(vec![CanonicalGame::zero()], vec![star.clone()]);
+        for infinitesimal in [star, up] {
+            assert_eq!(
+                infinitesimal.temperature(),
+                Dyadic::integer(0),
+                "{infinitesimal}"
+            );
+            assert_eq!(infinitesimal.mean(), Dyadic::integer(0), "{infinitesimal}");
+        }
+    }
+
+    #[test]
+    fn hottest_regions() {
+        // a 2x2 square (±1), an L-tromino (*), and a column only the vertical player can move in (1)
+        let game = Domineering::<7, 3>::from_notation("..#..#./..#.##./####### v").unwrap();
+
+        let regions = game.regions();
+        assert_eq!(regions.len(), 3);
+        assert_eq!(
+            canonical_value(&game),
+            regions
+                .iter()
+                .map(canonical_value)
+                .fold(CanonicalGame::zero(), |sum, value| sum + value)
+        );
+        assert_eq!(canonical_value(&game).to_string(), "{2* | *}");
+
+        let moves = game
+            .hot_moves()
+            .into_iter()
+            .map(|m| (m.component, m.m, m.temperature, m.mean))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            moves,
+            vec![
+                (
+                    0,
+                    DomineeringMove(0, 0),
+                    Dyadic::integer(1),
+                    Dyadic::integer(1)
+                ),
+                (
+                    0,
+                    DomineeringMove(1, 0),
+                    Dyadic::integer(1),
+                    Dyadic::integer(1)
+                ),
+                (
+                    1,
+                    DomineeringMove(3, 0),
+                    Dyadic::integer(0),
+                    Dyadic::integer(0)
+                ),
+                (
+                    2,
+                    DomineeringMove(6, 0),
+                    Dyadic::integer(-1),
+                    Dyadic::integer(0)
+                ),
+            ]
+        );
+    }
+
+    /// Checks the outcome class of an empty board against the solver, with either player moving first,
+    /// and against its canonical value.
+    fn check_outcome_class<const WIDTH: usize, const HEIGHT: usize>() -> OutcomeClass {
+        let game = Domineering::<WIDTH, HEIGHT>::new();
+        let class = outcome_class(&game);
+
+        // the vertical player is Left, so the horizontal one is Right
+        let solved = OutcomeClass::from_first_player_wins(
+            winner::<WIDTH, HEIGHT>(Orientation::Vertical) == Some(PartizanPlayer::Left),
+            winner::<WIDTH, HEIGHT>(Orientation::Horizontal) == Some(PartizanPlayer::Left),
+        );
+        assert_eq!(class, solved, "{WIDTH}x{HEIGHT}");
+
+        let by_value = match canonical_value(&game).partial_cmp(&CanonicalGame::zero()) {
+            Some(Ordering::Greater) => OutcomeClass::Left,
+            Some(Ordering::Less) => OutcomeClass::Right,
+            Some(Ordering::Equal) => OutcomeClass::Previous,
+            None => OutcomeClass::Next,
+        };
+        assert_eq!(class, by_value, "{WIDTH}x{HEIGHT}");
+
+        class
+    }
+
+    #[test]
+    fn outcome_classes() {
+        assert_eq!(check_outcome_class::<1, 1>(), OutcomeClass::Previous);
+        assert_eq!(check_outcome_class::<1, 2>(), OutcomeClass::Left);
+        assert_eq!(check_outcome_class::<2, 1>(), OutcomeClass::Right);
+        assert_eq!(check_outcome_class::<2, 2>(), OutcomeClass::Next);
+        assert_eq!(check_outcome_class::<3, 3>(), OutcomeClass::Next);
+        assert_eq!(check_outcome_class::<2, 3>(), OutcomeClass::Next);
+        assert_eq!(check_outcome_class::<3, 2>(), OutcomeClass::Next);
+        assert_eq!(check_outcome_class::<4, 4>(), OutcomeClass::Next);
+        assert_eq!(check_outcome_class::<3, 4>(), OutcomeClass::Left);
+        assert_eq!(check_outcome_class::<5, 2>(), OutcomeClass::Left);
+        assert_eq!(check_outcome_class::<1, 4>(), OutcomeClass::Left);
+
+        // two 2x2 squares cancel out, so whoever moves second wins
+        let squares = Domineering::<2, 5>::from_notation("../../##/../.. v").unwrap();
+        assert_eq!(outcome_class(&squares), OutcomeClass::Previous);
+    }
+
+    #[test]
+    fn hottest_regions_first_follows_hot_moves() {
+        let game = Domineering::<7, 3>::from_notation("..#..#./..#.##./####### v").unwrap();
+        let temperatures = game
+            .hot_moves()
+            .into_iter()
+            .map(|m| (m.m, m.temperature))
+            .collect::<BTreeMap<_, _>>();
+
+        let ordered = Domineering::hottest_regions_first().order(&game, &HashMap::new());
+        assert_eq!(ordered.len(), temperatures.len());
+
+        let ordered_temperatures = ordered.iter().map(|m| temperatures[m]).collect::<Vec<_>>();
+        assert!(
+            ordered_temperatures
+                .windows(2)
+                .all(|pair| pair[0] >= pair[1]),
+            "{ordered_temperatures:?}"
+        );
+    }
+
+    #[test]
+    fn hottest_regions_first_searches_fewer_positions() {
+        let game = Domineering::<5, 5>::try_from(DomineeringArgs {
+            moves: parse_moves("0-0 2-2 4-0 0-3"),
+            position: None,
+            board: None,
+            to_move: None,
+        })
+        .unwrap();
+
+        let explore = |ordering: &MoveOrdering<Domineering<5, 5>>| {
+            let stats = Stats::new(game.player(), game.move_count());
+            let score =
+                solve_ordered(&game, &mut HashMap::new(), Some(&stats), &None, ordering).unwrap();
+            (score, stats.states_explored.into_inner())
+        };
+
+        let (score, nodes) = explore(&MoveOrdering::GameOrder);
+        let (ordered_score, ordered_nodes) = explore(&Domineering::hottest_regions_first());
+        assert_eq!(ordered_score, score);
+        assert!(ordered_nodes < nodes, "{ordered_nodes} >= {nodes}");
+    }
+
+    #[test]
+    fn moves_in_the_wrong_orientation_are_rejected() {
+        // Left places dominoes vertically, so only Right can place one along the bottom row
+        let mut game = Domineering::<5, 5>::new();
+        assert_eq!(game.orientation(), Orientation::Vertical);
+
+        let err = game.make_move(&DomineeringMove(0, 4)).unwrap_err();
+        assert_eq!(
+            err,
+            DomineeringMoveError::BlockingAdjacent {
+                m: DomineeringMove(0, 4),
+                player: PartizanPlayer::Left,
+                orientation: Orientation::Vertical,
+                other: DomineeringMove(0, 5),
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "Left can not move at 0-4: Left places dominoes vertically, \
+             so the domino would also cover 0-5, which is covered or off the board."
+        );
+        assert_eq!(
+            game.make_move(&DomineeringMove(5, 0)).unwrap_err(),
+            DomineeringMoveError::OffBoard(DomineeringMove(5, 0), PartizanPlayer::Left)
+        );
+
+        // a move written with its orientation has to be in that of the player to move
+        assert_eq!(game.parse_move("0-0-v"), Ok(DomineeringMove(0, 0)));
+        assert_eq!(game.parse_move("a1-V"), Ok(DomineeringMove(0, 0)));
+        let err = game.parse_move("0-4-h").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`0-4-h` is out of range: expected a move of Left, who places dominoes vertically (e.g. 0-4-v)"
+        );
+        assert!(game.parse_move("0-0-x").is_err());
+        assert!(game.parse_move("5-0-v").is_err());
+
+        game.make_move(&DomineeringMove(0, 0)).unwrap();
+        assert_eq!(game.orientation(), Orientation::Horizontal);
+        assert_eq!(game.parse_move("0-4-h"), Ok(DomineeringMove(0, 4)));
+        game.make_move(&DomineeringMove(0, 4)).unwrap();
+        assert_eq!(game.to_notation(), "#..../#..../...../...../##... v");
+    }
+
+    #[test]
+    fn replayed_moves_alternate() {
+        let replay = |moves: &str| {
+            Domineering::<5, 5>::try_from(DomineeringArgs {
+                moves: parse_moves(moves),
+                ..Default::default()
+            })
+        };
+
+        assert_eq!(
+            replay("0-0-v 2-2-h 4-0-v").unwrap(),
+            replay("0-0 2-2 4-0").unwrap()
+        );
+
+        // Right moves second, so the second domino can't be vertical
+        let err = replay("0-0-v 2-2-v").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Could not play move 2 (2-2-v): `2-2-v` is out of range: \
+             expected a move of Right, who places dominoes horizontally (e.g. 2-2-h)"
+        );
+
+        // and without the orientation, a square only a vertical domino fits on is no move of Right's
+        let err = replay("0-0 4-0").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Right places dominoes horizontally"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn possible_moves_are_in_the_orientation_of_the_mover() {
+        let mut game = Domineering::<4, 3>::new_orientation(Orientation::Horizontal);
+        let mut rng = 7usize;
+
+        while Game::state(&game) == GameState::Playable {
+            let orientation = game.orientation();
+            let moves = game.possible_moves().collect::<Vec<_>>();
+
+            for m in &moves {
+                let mut next = game.clone();
+                next.make_move(m).unwrap();
+
+                // every move covers its square and the one after it in the mover's orientation, and no others
+                let (x, y) = orientation.other_square(m.0, m.1);
+                let mut covered = game.board;
+                covered.set(m.0, m.1, true);
+                covered.set(x, y, true);
+                assert_eq!(next.board, covered, "{m} on\n{game}");
+            }
+
+            // and every domino of that orientation that fits is a move
+            let mut fitting = (0..4)
+                .flat_map(|x| (0..3).map(move |y| DomineeringMove(x, y)))
+                .filter(|m| game.clone().make_move(m).is_ok())
+                .collect::<Vec<_>>();
+            let mut sorted = moves.clone();
+            fitting.sort();
+            sorted.sort();
+            assert_eq!(sorted, fitting);
+
+            rng = rng * 31 + 11;
+            game.make_move(&moves[rng % moves.len()]).unwrap();
+        }
+    }
+
+    /// Domineering as it was before dead squares were filled in: positions are only the same
+    /// if the same squares are covered.
+    #[derive(Clone)]
+    struct Unnormalized<const WIDTH: usize, const HEIGHT: usize>(Domineering<WIDTH, HEIGHT>);
+
+    impl<const WIDTH: usize, const HEIGHT: usize> PartialEq for Unnormalized<WIDTH, HEIGHT> {
+        fn eq(&self, other: &Self) -> bool {
+            self.0.board == other.0.board
+                && self.0.move_count == other.0.move_count
+                && self.0.primary_orientation == other.0.primary_orientation
+        }
+    }
+
+    impl<const WIDTH: usize, const HEIGHT: usize> Eq for Unnormalized<WIDTH, HEIGHT> {}
+
+    impl<const WIDTH: usize, const HEIGHT: usize> Hash for Unnormalized<WIDTH, HEIGHT> {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.0.board.hash(state);
+            self.0.move_count.hash(state);
+        }
+    }
+
+    impl<const WIDTH: usize, const HEIGHT: usize> Game for Unnormalized<WIDTH, HEIGHT> {
+        type Move = DomineeringMove;
+        type Iter<'a> = <Domineering<WIDTH, HEIGHT> as Game>::Iter<'a>;
+        type Player = PartizanPlayer;
+        type MoveError = DomineeringMoveError;
+
+        fn max_moves(&self) -> Option<usize> {
+            self.0.max_moves()
+        }
+
+        fn move_count(&self) -> usize {
+            self.0.move_count()
+        }
+
+        fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+            self.0.make_move(m)
+        }
+
+        fn possible_moves(&self) -> Self::Iter<'_> {
+            self.0.possible_moves()
+        }
+
+        fn status(&self) -> GameStatus {
+            Game::status(&self.0)
+        }
+
+        fn player(&self) -> Self::Player {
+            self.0.player()
+        }
+    }
+
+    /// Every position reachable from the empty board in up to `plies` moves, each covering different squares.
+    fn reachable<const WIDTH: usize, const HEIGHT: usize>(
+        plies: usize,
+    ) -> Vec<Domineering<WIDTH, HEIGHT>> {
+        let mut positions = vec![Domineering::<WIDTH, HEIGHT>::new()];
+        let mut seen = positions
+            .iter()
+            .cloned()
+            .map(Unnormalized)
+            .collect::<HashSet<_>>();
+        let mut frontier = positions.clone();
+
+        for _ in 0..plies {
+            let mut next = Vec::new();
+            for game in &frontier {
+                for m in game.possible_moves() {
+                    let mut child = game.clone();
+                    child.make_move(&m).unwrap();
+                    if seen.insert(Unnormalized(child.clone())) {
+                        next.push(child);
+                    }
+                }
+            }
+
+            positions.extend(next.iter().cloned());
+            frontier = next;
+        }
+
+        positions
+    }
+
+    /// Solves every position reachable in 4 plies with and without dead squares filled in,
+    /// each way with a table shared between them, returning how many positions each table holds.
+    fn check_dead_squares<const WIDTH: usize, const HEIGHT: usize>() -> (usize, usize) {
+        let mut normalized = HashMap::new();
+        let mut unnormalized = HashMap::new();
+
+        for game in reachable::<WIDTH, HEIGHT>(4) {
+            assert_eq!(game.full_hash(), game.incremental_hash(), "{game}");
+            assert_eq!(
+                game.dead_squares(),
+                Domineering::<WIDTH, HEIGHT>::dead(game.board)
+            );
+
+            let Ok(score) = solve(&game, &mut normalized, None, &None) else {
+                panic!("the solver failed");
+            };
+            let Ok(expected) = solve(&Unnormalized(game.clone()), &mut unnormalized, None, &None)
+            else {
+                panic!("the solver failed");
+            };
+            assert_eq!(score, expected, "{WIDTH}x{HEIGHT}\n{game}");
+        }
+
+        (normalized.len(), unnormalized.len())
+    }
+
+    #[test]
+    fn dead_squares_keep_scores() {
+        let (normalized, unnormalized) = check_dead_squares::<4, 4>();
+        assert!(normalized < unnormalized, "{normalized} >= {unnormalized}");
+
+        let (normalized, unnormalized) = check_dead_squares::<4, 5>();
+        assert!(normalized < unnormalized, "{normalized} >= {unnormalized}");
+    }
+
+    #[test]
+    fn dead_squares_are_filled() {
+        // the corner can't be covered once both of its neighbors are
+        let mut game = Domineering::<3, 3>::new_orientation(Orientation::Horizontal);
+        game.make_move(&DomineeringMove(1, 0)).unwrap();
+        assert!(game.dead_squares().is_empty());
+        game.make_move(&DomineeringMove(0, 1)).unwrap();
+        assert_eq!(game.dead_squares(), Bitboard::single(0, 0));
+
+        // so it's hashed as if it were covered, but is no region of its own, and the board is written as it is
+        let mut covered = game.clone();
+        covered.board.set(0, 0, true);
+        let covered = covered.normalized();
+        assert_eq!(game.incremental_hash(), covered.incremental_hash());
+        assert_eq!(game.regions().len(), 1);
+        assert_eq!(game.to_notation(), ".##/#../#.. h");
+    }
+
+    #[test]
+    fn dead_squares_shrink_the_search() {
+        // 5x5 after 4 moves, as benchmarked
+        let game = Domineering::<5, 5>::try_from(DomineeringArgs {
+            moves: parse_moves("0-0 2-2 4-0 0-3"),
+            ..Default::default()
+        })
+        .unwrap();
+
+        let explore = |game: &Domineering<5, 5>| {
+            let stats = Stats::new(game.player(), game.move_count());
+            let mut table = HashMap::new();
+            let score = solve(game, &mut table, Some(&stats), &None).unwrap();
+            (score, stats.states_explored.into_inner(), table.len())
+        };
+        let explore_unnormalized = |game: &Domineering<5, 5>| {
+            let game = Unnormalized(game.clone());
+            let stats = Stats::new(game.player(), game.move_count());
+            let mut table = HashMap::new();
+            let Ok(score) = solve(&game, &mut table, Some(&stats), &None) else {
+                panic!("the solver failed");
+            };
+            (score, stats.states_explored.into_inner(), table.len())
+        };
+
+        let (score, nodes, entries) = explore(&game);
+        let (expected, unnormalized_nodes, unnormalized_entries) = explore_unnormalized(&game);
+        assert_eq!(score, expected);
+        assert!(
+            nodes < unnormalized_nodes,
+            "{nodes} >= {unnormalized_nodes}"
+        );
+        assert!(
+            entries < unnormalized_entries,
+            "{entries} >= {unnormalized_entries}"
+        );
+    }
+
+    #[test]
+    fn windows_agree_with_the_score() {
+        window_results_testing(&Domineering::<4, 4>::new(), 6, 30);
+    }
 }
@@ -6,14 +6,21 @@ pub mod naive_nim;
 pub mod order_and_chaos;
 pub mod reversi;
 pub mod sprouts;
+pub mod takeaway;
 pub mod tic_tac_toe;
 
 use crate::{
-    chomp::ChompArgs, domineering::DomineeringArgs, naive_nim::NimArgs,
-    order_and_chaos::OrderAndChaosArgs, reversi::ReversiArgs, sprouts::SproutsArgs,
-    tic_tac_toe::TicTacToeArgs,
+    chomp::{Chomp, ChompArgs},
+    domineering::{Domineering, DomineeringArgs},
+    naive_nim::{Nim, NimArgs},
+    order_and_chaos::{OrderAndChaos, OrderAndChaosArgs},
+    reversi::{Reversi, ReversiArgs},
+    sprouts::{Sprouts, SproutsArgs},
+    takeaway::{Takeaway, TakeawayArgs},
+    tic_tac_toe::{TicTacToe, TicTacToeArgs},
+    util::metadata::GameMetadata,
 };
-use clap::Subcommand;
+use clap::{Subcommand, ValueEnum};
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 
@@ -26,9 +33,209 @@ pub enum Games {
     Domineering(DomineeringArgs),
     Chomp(ChompArgs),
     Sprouts(SproutsArgs),
+    Takeaway(TakeawayArgs),
 }
 
-pub static DEFAULT_GAMES: Lazy<[Games; 7]> = Lazy::new(|| {
+/// Every game, without its arguments, for choosing a game through a flag.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GameKind {
+    Reversi,
+    TicTacToe,
+    OrderAndChaos,
+    NaiveNim,
+    Domineering,
+    Chomp,
+    Sprouts,
+    Takeaway,
+}
+
+impl GameKind {
+    /// What the game is, and how it's set up from the CLI.
+    pub fn metadata(self) -> &'static GameMetadata {
+        match self {
+            Self::Reversi => &reversi::METADATA,
+            Self::TicTacToe => &tic_tac_toe::METADATA,
+            Self::OrderAndChaos => &order_and_chaos::METADATA,
+            Self::NaiveNim => &naive_nim::METADATA,
+            Self::Domineering => &domineering::METADATA,
+            Self::Chomp => &chomp::METADATA,
+            Self::Sprouts => &sprouts::METADATA,
+            Self::Takeaway => &takeaway::METADATA,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        self.metadata().name
+    }
+
+    /// The game with its default arguments.
+    pub fn default_game(self) -> Games {
+        match self {
+            Self::Reversi => Games::Reversi(Default::default()),
+            Self::TicTacToe => Games::TicTacToe(Default::default()),
+            Self::OrderAndChaos => Games::OrderAndChaos(Default::default()),
+            Self::NaiveNim => Games::NaiveNim(Default::default()),
+            Self::Domineering => Games::Domineering(Default::default()),
+            Self::Chomp => Games::Chomp(Default::default()),
+            Self::Sprouts => Games::Sprouts(Default::default()),
+            Self::Takeaway => Games::Takeaway(Default::default()),
+        }
+    }
+
+    /// The game's standard starting position, the one its default arguments set up,
+    /// so that every game can be gone through with [`with_position!`] without arguments to parse.
+    ///
+    /// ```
+    /// use clap::ValueEnum;
+    /// use game_solver::game::{Game, GameState};
+    /// use games::{with_position, GameKind};
+    ///
+    /// for kind in GameKind::value_variants() {
+    ///     with_position!(kind.standard_position(), game => {
+    ///         assert_eq!(game.move_count(), 0);
+    ///         assert_eq!(game.state(), GameState::Playable);
+    ///     });
+    /// }
+    /// ```
+    pub fn standard_position(self) -> GamePosition {
+        match self {
+            Self::Reversi => GamePosition::Reversi(Default::default()),
+            Self::TicTacToe => GamePosition::TicTacToe(Default::default()),
+            Self::OrderAndChaos => GamePosition::OrderAndChaos(Default::default()),
+            Self::NaiveNim => GamePosition::NaiveNim(Default::default()),
+            Self::Domineering => GamePosition::Domineering(Default::default()),
+            Self::Chomp => GamePosition::Chomp(Default::default()),
+            Self::Sprouts => GamePosition::Sprouts(Default::default()),
+            Self::Takeaway => GamePosition::Takeaway(Default::default()),
+        }
+    }
+}
+
+/// Builds the concrete game described by `$args`, and evaluates `$body` with it bound to `$game`.
+///
+/// Arguments that don't describe a game are an [invalid](crate::util::cli::exit::invalid) error.
+#[macro_export]
+macro_rules! with_game {
+    ($args:expr, $game:ident => $body:expr) => {
+        match $args {
+            $crate::Games::Reversi(args) => {
+                let $game: $crate::reversi::Reversi =
+                    args.try_into().map_err($crate::util::cli::exit::invalid)?;
+                $body
+            }
+            $crate::Games::TicTacToe(args) => {
+                let $game: $crate::tic_tac_toe::TicTacToe =
+                    args.try_into().map_err($crate::util::cli::exit::invalid)?;
+                $body
+            }
+            $crate::Games::OrderAndChaos(args) => {
+                let $game: $crate::order_and_chaos::OrderAndChaos<6, 6, 5, 6> =
+                    args.try_into().map_err($crate::util::cli::exit::invalid)?;
+                $body
+            }
+            $crate::Games::NaiveNim(args) => {
+                let $game: $crate::naive_nim::Nim =
+                    args.try_into().map_err($crate::util::cli::exit::invalid)?;
+                $body
+            }
+            $crate::Games::Domineering(args) => {
+                let $game: $crate::domineering::Domineering<5, 5> =
+                    args.try_into().map_err($crate::util::cli::exit::invalid)?;
+                $body
+            }
+            $crate::Games::Chomp(args) => {
+                let $game: $crate::chomp::Chomp =
+                    args.try_into().map_err($crate::util::cli::exit::invalid)?;
+                $body
+            }
+            $crate::Games::Sprouts(args) => {
+                let $game: $crate::sprouts::Sprouts =
+                    args.try_into().map_err($crate::util::cli::exit::invalid)?;
+                $body
+            }
+            $crate::Games::Takeaway(args) => {
+                let $game: $crate::takeaway::Takeaway =
+                    args.try_into().map_err($crate::util::cli::exit::invalid)?;
+                $body
+            }
+        }
+    };
+}
+
+/// A position of any game, tagged with the game's CLI name, e.g.
+/// `{"game": "naive-nim", "position": {"heaps": [3, 5, 7], "move_count": 0, "max_moves": 15}}`.
+///
+/// Unlike [`Games`], which replays a list of moves, this holds the position itself,
+/// so it can be stored or sent without its history.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Clone, Debug)]
+#[serde(tag = "game", content = "position", rename_all = "kebab-case")]
+pub enum GamePosition {
+    Reversi(Reversi),
+    TicTacToe(TicTacToe),
+    OrderAndChaos(OrderAndChaos<6, 6, 5, 6>),
+    NaiveNim(Nim),
+    Domineering(Domineering<5, 5>),
+    Chomp(Chomp),
+    Sprouts(Sprouts),
+    Takeaway(Takeaway),
+}
+
+impl GamePosition {
+    pub fn kind(&self) -> GameKind {
+        match self {
+            Self::Reversi(_) => GameKind::Reversi,
+            Self::TicTacToe(_) => GameKind::TicTacToe,
+            Self::OrderAndChaos(_) => GameKind::OrderAndChaos,
+            Self::NaiveNim(_) => GameKind::NaiveNim,
+            Self::Domineering(_) => GameKind::Domineering,
+            Self::Chomp(_) => GameKind::Chomp,
+            Self::Sprouts(_) => GameKind::Sprouts,
+            Self::Takeaway(_) => GameKind::Takeaway,
+        }
+    }
+}
+
+macro_rules! impl_from_game {
+    ($($variant:ident($game:ty)),* $(,)?) => {
+        $(
+            impl From<$game> for GamePosition {
+                fn from(game: $game) -> Self {
+                    Self::$variant(game)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_game!(
+    Reversi(Reversi),
+    TicTacToe(TicTacToe),
+    OrderAndChaos(OrderAndChaos<6, 6, 5, 6>),
+    NaiveNim(Nim),
+    Domineering(Domineering<5, 5>),
+    Chomp(Chomp),
+    Sprouts(Sprouts),
+    Takeaway(Takeaway),
+);
+
+/// Evaluates `$body` with the game inside the [`GamePosition`] `$position` bound to `$game`.
+#[macro_export]
+macro_rules! with_position {
+    ($position:expr, $game:ident => $body:expr) => {
+        match $position {
+            $crate::GamePosition::Reversi($game) => $body,
+            $crate::GamePosition::TicTacToe($game) => $body,
+            $crate::GamePosition::OrderAndChaos($game) => $body,
+            $crate::GamePosition::NaiveNim($game) => $body,
+            $crate::GamePosition::Domineering($game) => $body,
+            $crate::GamePosition::Chomp($game) => $body,
+            $crate::GamePosition::Sprouts($game) => $body,
+            $crate::GamePosition::Takeaway($game) => $body,
+        }
+    };
+}
+
+pub static DEFAULT_GAMES: Lazy<[Games; 8]> = Lazy::new(|| {
     [
         Games::Reversi(Default::default()),
         Games::TicTacToe(Default::default()),
@@ -37,32 +244,30 @@ pub static DEFAULT_GAMES: Lazy<[Games; 7]> = Lazy::new(|| {
         Games::Domineering(Default::default()),
         Games::Chomp(Default::default()),
         Games::Sprouts(Default::default()),
+        Games::Takeaway(Default::default()),
     ]
 });
 
 impl Games {
-    pub fn name(&self) -> String {
-        match *self {
-            Self::Reversi(_) => "Reversi".to_string(),
-            Self::TicTacToe(_) => "Tic Tac Toe".to_string(),
-            Self::OrderAndChaos(_) => "Order and Chaos".to_string(),
-            Self::NaiveNim(_) => "Nim (Naive)".to_string(),
-            Self::Domineering(_) => "Domineering".to_string(),
-            Self::Chomp(_) => "Chomp".to_string(),
-            Self::Sprouts(_) => "Sprouts".to_string(),
+    pub fn kind(&self) -> GameKind {
+        match self {
+            Self::Reversi(_) => GameKind::Reversi,
+            Self::TicTacToe(_) => GameKind::TicTacToe,
+            Self::OrderAndChaos(_) => GameKind::OrderAndChaos,
+            Self::NaiveNim(_) => GameKind::NaiveNim,
+            Self::Domineering(_) => GameKind::Domineering,
+            Self::Chomp(_) => GameKind::Chomp,
+            Self::Sprouts(_) => GameKind::Sprouts,
+            Self::Takeaway(_) => GameKind::Takeaway,
         }
     }
 
+    pub fn name(&self) -> String {
+        self.kind().name().to_string()
+    }
+
     pub fn description(&self) -> &str {
-        match *self {
-            Self::Reversi(_) => include_str!("./reversi/README.md"),
-            Self::TicTacToe(_) => include_str!("./tic_tac_toe/README.md"),
-            Self::OrderAndChaos(_) => include_str!("./order_and_chaos/README.md"),
-            Self::NaiveNim(_) => include_str!("./naive_nim/README.md"),
-            Self::Domineering(_) => include_str!("./domineering/README.md"),
-            Self::Chomp(_) => include_str!("./chomp/README.md"),
-            Self::Sprouts(_) => include_str!("./sprouts/README.md"),
-        }
+        self.kind().metadata().rules
     }
 
     #[cfg(feature = "egui")]
@@ -111,6 +316,160 @@ impl Games {
                 &mut cache,
                 "crates/games/src/sprouts/README.md"
             ),
+            Self::Takeaway(_) => egui_commonmark::commonmark_str!(
+                "takeaway",
+                ui,
+                &mut cache,
+                "crates/games/src/takeaway/README.md"
+            ),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::cli::replay_parsed_moves;
+    use game_solver::{
+        game::{Game, GameState},
+        move_scores, solve,
+    };
+    use std::collections::HashMap;
+
+    fn default_position(games: Games) -> anyhow::Result<GamePosition> {
+        with_game!(games, game => Ok(game.into()))
+    }
+
+    #[test]
+    fn every_game_has_metadata() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        #[command(no_binary_name = true)]
+        struct Example {
+            #[command(subcommand)]
+            game: Games,
+        }
+
+        for games in DEFAULT_GAMES.iter() {
+            let kind = games.kind();
+            let metadata = kind.metadata();
+
+            assert!(!metadata.name.is_empty());
+            assert!(!metadata.summary.is_empty(), "{}", metadata.name);
+            assert!(!metadata.rules.is_empty(), "{}", metadata.name);
+            assert!(!metadata.move_notation.is_empty(), "{}", metadata.name);
+            assert!(metadata.parameters.iter().all(|parameter| {
+                !parameter.name.is_empty()
+                    && !parameter.description.is_empty()
+                    && !parameter.default.is_empty()
+                    && !parameter.range.is_empty()
+            }));
+
+            // the example is a game the CLI can set up
+            let example = Example::try_parse_from(metadata.example.split_whitespace())
+                .unwrap_or_else(|err| panic!("{}: {err}", metadata.example));
+            assert_eq!(example.game.kind(), kind);
+            default_position(example.game)
+                .unwrap_or_else(|err| panic!("{}: {err}", metadata.example));
+        }
+    }
+
+    #[test]
+    fn standard_positions_are_set_up_by_the_default_arguments() -> anyhow::Result<()> {
+        for &kind in GameKind::value_variants() {
+            let position = kind.standard_position();
+            assert_eq!(position.kind(), kind);
+
+            with_game!(kind.default_game(), game => {
+                assert_eq!(GamePosition::from(game), position, "{kind:?}");
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The smallest configuration of each game, or for the games on a board of a fixed size,
+    /// their standard position after the moves of one of their benchmarks, leaving a few squares empty.
+    fn smallest_position(kind: GameKind) -> GamePosition {
+        let (position, moves) = match kind {
+            GameKind::Reversi => (
+                kind.standard_position(),
+                "4-2 4-1 3-1 4-3 5-0 2-1 5-3 5-1 2-0 5-2 1-4 3-0 4-0 2-4 2-5 5-4 \
+                 4-4 1-3 5-5 1-5 1-1 4-5 0-5 0-4 3-5 1-0",
+            ),
+            GameKind::OrderAndChaos => (
+                kind.standard_position(),
+                "0-1-o 1-0-x 1-4-o 2-3-x 3-1-o 4-0-x 4-4-o 5-3-x 0-2-o 1-3-x 2-4-o 3-5-x \
+                 5-0-o 0-3-x 2-0-o 3-4-x 5-2-o 1-2-x 3-3-o 0-0-x 3-0-o 0-4-x 4-2-o 2-2-x",
+            ),
+            GameKind::Domineering => (kind.standard_position(), "0-0 2-2 4-0 0-3"),
+            GameKind::TicTacToe => (TicTacToe::new(2, 2).into(), ""),
+            GameKind::NaiveNim => (Nim::new(vec![1]).into(), ""),
+            GameKind::Chomp => (Chomp::new(2, 1).into(), ""),
+            GameKind::Sprouts => (Sprouts::new(1).into(), ""),
+            GameKind::Takeaway => (Takeaway::new(1).into(), ""),
         };
+        let moves = moves
+            .split_whitespace()
+            .map(String::from)
+            .collect::<Vec<_>>();
+
+        with_position!(position, game => {
+            let mut game = game;
+            replay_parsed_moves(&mut game, &moves).unwrap_or_else(|err| panic!("{kind:?}: {err}"));
+            game.into()
+        })
+    }
+
+    #[test]
+    fn every_game_solves_its_smallest_configuration() {
+        for &kind in GameKind::value_variants() {
+            with_position!(smallest_position(kind), game => {
+                assert_eq!(game.state(), GameState::Playable, "{kind:?}");
+
+                let score = solve(&game, &mut HashMap::new(), None, &None).unwrap();
+                let best = move_scores(&game, &mut HashMap::new(), None, &None)
+                    .map(|move_score| move_score.unwrap().1)
+                    .max();
+                assert_eq!(best, Some(score), "{kind:?}");
+            });
+        }
+    }
+
+    #[test]
+    fn position_envelope_round_trips() {
+        for games in DEFAULT_GAMES.iter() {
+            let position = default_position(games.clone()).unwrap();
+
+            let json = serde_json::to_string(&position).unwrap();
+            let parsed = serde_json::from_str::<GamePosition>(&json).unwrap();
+
+            assert_eq!(parsed, position, "{json}");
+
+            // tagged with the same name the CLI uses for the game
+            let value = serde_json::to_value(&position).unwrap();
+            assert_eq!(
+                value["game"],
+                position.kind().to_possible_value().unwrap().get_name()
+            );
+        }
+    }
+
+    #[test]
+    fn position_envelope_is_tagged_with_the_cli_name() {
+        let position = serde_json::from_str::<GamePosition>(
+            r#"{"game": "naive-nim", "position": {"heaps": [1, 2], "move_count": 0}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(position, GamePosition::NaiveNim(Nim::new(vec![1, 2])));
+        assert_eq!(position.kind(), GameKind::NaiveNim);
+
+        let err = serde_json::from_str::<GamePosition>(
+            r#"{"game": "naive-nim", "position": {"heaps": [1, 2], "move_count": 0, "max_moves": 1}}"#,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("impossible position"), "{err}");
     }
 }
@@ -2,22 +2,40 @@
 
 #[cfg(feature = "egui")]
 pub mod gui;
-use anyhow::Error;
+use anyhow::{anyhow, Error};
 use clap::Args;
 use game_solver::{
-    game::{Game, GameState, Normal, NormalImpartial},
-    player::ImpartialPlayer,
+    cgt::OutcomeClass,
+    game::{Normal, NormalImpartial},
+    hint::Hints,
+    prelude::*,
+    tablebase::EnumerableGame,
 };
-use serde::{Deserialize, Serialize};
+use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
 use std::{
     fmt::{Debug, Display},
     hash::Hash,
 };
 use thiserror::Error;
 
-use crate::util::{cli::move_failable, move_natural::NaturalMove};
+use crate::util::metadata::{GameMetadata, Parameter};
+use crate::util::{
+    bench::{parse_moves, BenchPosition},
+    cli::{
+        evaluate::Heuristics,
+        outcome::{impartial_class, OutcomeClasses},
+        replay_moves, Endings,
+    },
+    move_natural::NaturalMove,
+    notation::{fields, NotatedGame, NotationError},
+    parse::{parse_move_list, parse_number, ParseError, ParseMove},
+    position::PositionError,
+    render::Render,
+    symmetry::Symmetries,
+};
 
-#[derive(Clone, Hash, Eq, PartialEq)]
+#[derive(Hash, Eq, PartialEq, Deserialize)]
+#[serde(try_from = "NimPosition")]
 pub struct Nim {
     heaps: Vec<usize>,
     move_count: usize,
@@ -26,6 +44,13 @@ pub struct Nim {
 
 type NimMove = NaturalMove<2>;
 
+/// The game the CLI plays without arguments, with heaps of 3, 5, and 7.
+impl Default for Nim {
+    fn default() -> Self {
+        Self::new(vec![3, 5, 7])
+    }
+}
+
 impl Nim {
     /// Create a new game of Nim with the given heaps,
     /// where heaps is a list of the number of objects in each heap.
@@ -37,6 +62,50 @@ impl Nim {
             max_moves: heaps.iter().sum::<usize>(),
         }
     }
+
+    /// The move that leaves heaps whose sizes XOR to 0, which by Bouton's theorem wins,
+    /// taking from the first heap it can be taken from; or None if the heaps already XOR to 0,
+    /// and every move loses.
+    ///
+    /// ```
+    /// use game_solver::game::Game;
+    /// use games::naive_nim::Nim;
+    ///
+    /// // 3 ^ 4 ^ 5 = 2, so 2 is taken from the heap of 3, leaving 1 ^ 4 ^ 5 = 0
+    /// let game = Nim::new(vec![3, 4, 5]);
+    /// assert_eq!(game.optimal_move().unwrap().to_string(), "0-2");
+    /// assert_eq!(game.possible_moves().next(), game.optimal_move());
+    ///
+    /// assert_eq!(Nim::new(vec![1, 2, 3]).optimal_move(), None);
+    /// ```
+    pub fn optimal_move(&self) -> Option<NimMove> {
+        let sum = self.heaps.iter().fold(0, |sum, heap| sum ^ heap);
+        if sum == 0 {
+            return None;
+        }
+
+        self.heaps
+            .iter()
+            .position(|&heap| heap ^ sum < heap)
+            .map(|heap| NaturalMove([heap, self.heaps[heap] - (self.heaps[heap] ^ sum)]))
+    }
+}
+
+impl Clone for Nim {
+    fn clone(&self) -> Self {
+        Self {
+            heaps: self.heaps.clone(),
+            move_count: self.move_count,
+            max_moves: self.max_moves,
+        }
+    }
+
+    /// Copies the heaps into the ones already allocated, as the solver does with the boards it reuses.
+    fn clone_from(&mut self, source: &Self) {
+        self.heaps.clone_from(&source.heaps);
+        self.move_count = source.move_count;
+        self.max_moves = source.max_moves;
+    }
 }
 
 #[derive(Error, Debug, Clone)]
@@ -56,12 +125,14 @@ impl NormalImpartial for Nim {}
 impl Game for Nim {
     /// where Move is a tuple of the heap index and the number of objects to remove
     type Move = NimMove;
-    type Iter<'a> = std::vec::IntoIter<Self::Move>;
+    type Iter<'a> = NimMoves<'a>;
 
     /// Define Nim as a zero-sum impartial game
     type Player = ImpartialPlayer;
     type MoveError = NimMoveError;
 
+    const IMPARTIAL: bool = true;
+
     fn max_moves(&self) -> Option<usize> {
         Some(self.max_moves)
     }
@@ -95,20 +166,40 @@ impl Game for Nim {
     }
 
     fn possible_moves(&self) -> Self::Iter<'_> {
-        let mut moves = Vec::new();
+        NimMoves {
+            heaps: &self.heaps,
+            optimal: self.optimal_move(),
+            started: false,
+            // one more than the largest take, which the first heap taken from takes one fewer than
+            size: self.heaps.iter().max().map_or(0, |&heap| heap + 1),
+            heap: self.heaps.len(),
+            left: self.heaps.iter().sum(),
+        }
+    }
 
-        // loop through every heap and add every possible move
-        for (i, &heap) in self.heaps.iter().enumerate() {
-            for j in 1..=heap {
-                moves.push(NaturalMove([i, j]));
+    fn move_count_hint(&self) -> (usize, Option<usize>) {
+        let count = self.heaps.iter().sum();
+        (count, Some(count))
+    }
+
+    /// Only the last heap left can be taken to win, which is found without making any moves.
+    ///
+    /// The solver doesn't ask for it, as taking the last heap is the optimal move, which it searches first anyway.
+    fn find_immediately_resolvable_game(&self) -> Result<Option<Self>, Self::MoveError> {
+        let mut left = self.heaps.iter().enumerate().filter(|(_, &heap)| heap > 0);
+
+        match (left.next(), left.next()) {
+            (Some((heap, &amount)), None) => {
+                let mut game = self.clone();
+                game.make_move(&NaturalMove([heap, amount]))?;
+                Ok(Some(game))
             }
+            _ => Ok(None),
         }
-
-        moves.into_iter()
     }
 
-    fn state(&self) -> GameState<Self::Player> {
-        <Self as Normal>::state(&self)
+    fn status(&self) -> GameStatus {
+        <Self as Normal>::status(self)
     }
 
     fn player(&self) -> Self::Player {
@@ -116,6 +207,108 @@ impl Game for Nim {
     }
 }
 
+/// The possible moves of a game of Nim, best first for the search:
+/// the [optimal move](Nim::optimal_move) if there is one, then the rest from those taking the most objects
+/// to those taking the fewest, and from the first heap to the last between those taking as many.
+pub struct NimMoves<'a> {
+    heaps: &'a [usize],
+    /// The optimal move, which is made first, and skipped after.
+    optimal: Option<NimMove>,
+    /// Whether the optimal move, if any, was made.
+    started: bool,
+    /// How many objects the moves being made take.
+    size: usize,
+    /// The heap the next move of this size is taken from.
+    heap: usize,
+    /// How many moves are left to make.
+    left: usize,
+}
+
+impl Iterator for NimMoves<'_> {
+    type Item = NimMove;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.left == 0 {
+            return None;
+        }
+
+        if !self.started {
+            self.started = true;
+            if let Some(optimal) = self.optimal {
+                self.left -= 1;
+                return Some(optimal);
+            }
+        }
+
+        loop {
+            // take one fewer once every heap was taken from, which there's always a move of while any are left
+            if self.heap == self.heaps.len() {
+                self.size -= 1;
+                self.heap = 0;
+            }
+
+            let m = NaturalMove([self.heap, self.size]);
+            self.heap += 1;
+
+            if self.heaps[m.0[0]] >= self.size && Some(m) != self.optimal {
+                self.left -= 1;
+                return Some(m);
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.left, Some(self.left))
+    }
+}
+
+impl ExactSizeIterator for NimMoves<'_> {}
+
+/// Enumerates every position where each heap has at most as many objects as in this position.
+impl EnumerableGame for Nim {
+    fn position_count(&self) -> usize {
+        self.heaps.iter().map(|heap| heap + 1).product()
+    }
+
+    fn index(&self, position: &Self) -> Option<usize> {
+        if position.heaps.len() != self.heaps.len() {
+            return None;
+        }
+
+        // read the heaps as digits of a mixed-radix number
+        let mut index = 0;
+        for (&heap, &max) in position.heaps.iter().zip(&self.heaps) {
+            if heap > max {
+                return None;
+            }
+
+            index = index * (max + 1) + heap;
+        }
+
+        Some(index)
+    }
+
+    fn unindex(&self, mut index: usize) -> Option<Self> {
+        if index >= self.position_count() {
+            return None;
+        }
+
+        let mut heaps = vec![0; self.heaps.len()];
+        for (heap, &max) in heaps.iter_mut().zip(&self.heaps).rev() {
+            *heap = index % (max + 1);
+            index /= max + 1;
+        }
+
+        // the amount of moves it took to get here can't be known from the heaps,
+        // but it doesn't change who wins
+        Some(Self {
+            heaps,
+            move_count: self.move_count,
+            max_moves: self.max_moves,
+        })
+    }
+}
+
 impl Display for Nim {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         for (i, heap) in self.heaps.iter().enumerate() {
@@ -132,6 +325,116 @@ impl Debug for Nim {
     }
 }
 
+impl Serialize for Nim {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Nim", 3)?;
+        state.serialize_field("heaps", &self.heaps)?;
+        state.serialize_field("move_count", &self.move_count)?;
+        state.serialize_field("max_moves", &self.max_moves)?;
+        state.end()
+    }
+}
+
+/// A serialized [`Nim`], before it's checked.
+#[derive(Deserialize)]
+struct NimPosition {
+    heaps: Vec<usize>,
+    move_count: usize,
+    /// The objects the game started with. If missing, the game is assumed to have
+    /// started with the objects left, plus one for every move made.
+    max_moves: Option<usize>,
+}
+
+impl TryFrom<NimPosition> for Nim {
+    type Error = PositionError;
+
+    fn try_from(position: NimPosition) -> Result<Self, Self::Error> {
+        // every move removes at least one object
+        let least_max_moves = position.heaps.iter().sum::<usize>() + position.move_count;
+        let max_moves = position.max_moves.unwrap_or(least_max_moves);
+
+        if max_moves < least_max_moves {
+            return Err(PositionError::impossible(format!(
+                "{} moves were made with objects left over, so the game started with at least {least_max_moves} objects, not {max_moves}",
+                position.move_count
+            )));
+        }
+
+        Ok(Self {
+            heaps: position.heaps,
+            move_count: position.move_count,
+            max_moves,
+        })
+    }
+}
+
+/// Written as the heaps, separated by commas, followed by the move count: `3,5,7 m2`.
+///
+/// If the game started with more objects than are left plus one for every move made
+/// (as moves can take several objects), they're written last: `3,5,7 m2 s19`.
+impl NotatedGame for Nim {
+    fn to_notation(&self) -> String {
+        let heaps = self
+            .heaps
+            .iter()
+            .map(|heap| heap.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let least_max_moves = self.heaps.iter().sum::<usize>() + self.move_count;
+
+        if self.max_moves == least_max_moves {
+            format!("{heaps} m{}", self.move_count)
+        } else {
+            format!("{heaps} m{} s{}", self.move_count, self.max_moves)
+        }
+    }
+
+    fn from_notation(notation: &str) -> Result<Self, NotationError> {
+        let fields = fields(notation, &["heaps", "move count"], &["starting objects"])?;
+
+        let heaps = fields[0]
+            .split(',')
+            .iter()
+            .map(|heap| heap.number("heap size"))
+            .collect::<Result<Vec<_>, _>>()?;
+        let move_count = fields[1]
+            .strip_prefix('m', "move count")?
+            .number("move count")?;
+        let max_moves = fields
+            .get(2)
+            .map(|field| {
+                field
+                    .strip_prefix('s', "starting objects")?
+                    .number("starting objects")
+            })
+            .transpose()?;
+
+        Self::try_from(NimPosition {
+            heaps,
+            move_count,
+            max_moves,
+        })
+        // only too few starting objects make a position impossible
+        .map_err(|err| fields.last().unwrap().error(err.to_string()))
+    }
+}
+
+/// What Nim is, and how it's set up from the CLI.
+pub const METADATA: GameMetadata = GameMetadata {
+    name: "Nim (Naive)",
+    summary: "Take any amount of objects from a single heap; whoever takes the last one wins.",
+    rules: include_str!("./README.md"),
+    parameters: &[Parameter {
+        name: "<CONFIGURATION>",
+        description: "The amount of objects in each heap.",
+        default: "3,5,7",
+        range: "a comma-separated list of heap sizes",
+    }],
+    move_notation:
+        "heap-amount, with zero-indexed heaps (e.g. 0-2 takes 2 objects from the first heap)",
+    example: "naive-nim 3,5,7 0-2 2-4",
+};
+
 /// Analyzes Nim.
 ///
 #[doc = include_str!("./README.md")]
@@ -140,53 +443,132 @@ pub struct NimArgs {
     /// The configuration of the game. For example, 3,5,7
     /// creates a Nim game that has three heaps, where each
     /// heap has 3, 5, and 7 objects respectively
-    configuration: String,
+    #[arg(required_unless_present = "position")]
+    configuration: Option<String>,
     /// Nim moves, ordered as x1-y1 x2-y2 ...
     #[arg(value_parser = clap::value_parser!(NimMove))]
     moves: Vec<NimMove>,
+    /// The position to analyze, in its notation (e.g. `3,5,7 m2`),
+    /// instead of the configuration and moves.
+    #[arg(long, conflicts_with_all = ["configuration", "moves"])]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<String>,
 }
 
 impl Default for NimArgs {
     fn default() -> Self {
         Self {
-            configuration: "3,5,7".to_string(),
+            configuration: Some("3,5,7".to_string()),
             moves: vec![],
+            position: None,
         }
     }
 }
 
+impl ParseMove for Nim {
+    fn parse_move(&self, token: &str) -> Result<Self::Move, ParseError> {
+        token.parse()
+    }
+}
+
+impl Heuristics for Nim {}
+
+impl Hints for Nim {
+    /// The [optimal move](Nim::optimal_move), which leaves heaps that XOR to 0.
+    fn book_move(&self) -> Option<NimMove> {
+        self.optimal_move()
+    }
+}
+
+impl Endings for Nim {}
+
+impl OutcomeClasses for Nim {
+    fn outcome_class(&self) -> Option<Result<OutcomeClass, GameSolveError<Self>>> {
+        impartial_class(self)
+    }
+
+    /// The [optimal move](Nim::optimal_move), which leaves heaps that XOR to 0.
+    fn book_move(&self) -> Option<String> {
+        self.optimal_move().map(|m| m.to_string())
+    }
+}
+
+impl Symmetries for Nim {}
+
+impl Render for Nim {}
+
 impl TryFrom<NimArgs> for Nim {
     type Error = Error;
 
     fn try_from(args: NimArgs) -> Result<Self, Self::Error> {
+        if let Some(position) = args.position {
+            return Ok(Self::from_notation(&position)?);
+        }
+
+        let configuration = args
+            .configuration
+            .ok_or_else(|| anyhow!("expected the configuration of the game, or a position"))?;
+
         // parse the original configuration of the game from args
         // e.g. 3,5,7 for 3 heaps with 3, 5, and 7 objects respectively
-        let config = args
-            .configuration
-            .split(',')
-            .map(|num| num.parse::<usize>().expect("Not a number!"))
-            .collect::<Vec<_>>();
+        let config = parse_move_list(&configuration, parse_number)?;
 
         // create a new game of Nim with the given configuration
         let mut game = Nim::new(config);
 
-        // parse every move in args, e.g. 0-0 1-1 in args
-        for nim_move in args.moves {
-            move_failable(&mut game, &nim_move)?;
-        }
+        // play every move in args, e.g. 0-0 1-1 in args
+        replay_moves(&mut game, &args.moves)?;
 
         Ok(game)
     }
 }
 
+/// Positions for the `bench` subcommand.
+pub fn bench_positions() -> Vec<BenchPosition<NimArgs>> {
+    [
+        ("3,5,7", true, ""),
+        ("3,5,7 after 2-3", true, "2-3"),
+        ("1,3,5,7,9", true, ""),
+        ("2,4,6,8,10", false, ""),
+    ]
+    .into_iter()
+    .map(|(name, quick, moves)| BenchPosition {
+        name,
+        quick,
+        args: NimArgs {
+            configuration: Some(name.split(' ').next().unwrap().to_string()),
+            moves: parse_moves(moves),
+            position: None,
+        },
+    })
+    .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
 
-    use game_solver::{move_scores, CollectedMoves};
+    use game_solver::player::{PartizanPlayer, TwoPlayer};
+    use game_solver::{
+        cgt::impartial_outcome_class,
+        compound_game::{remoteness, ConjunctiveCompound, SelectiveCompound},
+        dot::export_tree,
+        move_scores, optimal_moves,
+        ordering::MoveOrdering,
+        puzzle::difficulty,
+        sequential_game::{SequentialCompound, SequentialScoring},
+        solve, solve_depth_limited, solve_ordered,
+        stats::Stats,
+        strategy::Outcome,
+        tablebase::{Tablebase, TablebaseProbe},
+        CollectedMoves,
+    };
     use itertools::Itertools;
 
-    use crate::util::move_score::best_move_score_testing;
+    use crate::util::move_score::{
+        best_move_score_testing, impartial_outcomes_testing, optimal_moves_testing,
+        window_results_testing,
+    };
 
     use super::*;
 
@@ -230,4 +612,466 @@ mod tests {
         );
         assert_eq!(best_move_score_testing(play(Nim::new(vec![7, 7]))).1, -1);
     }
+
+    #[test]
+    fn depth_limited() {
+        // a single heap can be won in one move
+        assert_eq!(
+            solve_depth_limited(&Nim::new(vec![7]), 1, &None).unwrap(),
+            7
+        );
+
+        // symmetrical heaps can't be resolved in a couple of moves
+        assert_eq!(
+            solve_depth_limited(&Nim::new(vec![3, 3]), 2, &None).unwrap(),
+            0
+        );
+
+        // but they can if we look to the end of the game
+        let game = Nim::new(vec![2, 2]);
+        assert_eq!(
+            solve_depth_limited(&game, 4, &None).unwrap(),
+            solve(&game, &mut HashMap::new(), None, &None).unwrap()
+        );
+    }
+
+    #[test]
+    fn export_dot_tree() {
+        let mut output = Vec::new();
+        export_tree(&Nim::new(vec![1, 2]), 2, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        let edges = output.lines().filter(|line| line.contains(" -> ")).count();
+        let nodes = output
+            .lines()
+            .filter(|line| line.contains("[label=") && !line.contains(" -> "))
+            .count();
+
+        // 3 moves from the root, then 2, 2, and 1 replies
+        assert_eq!(edges, 3 + 2 + 2 + 1);
+        assert_eq!(nodes, edges + 1);
+
+        // emptying the second heap wins right away, which is searched after evening the heaps out
+        assert!(output.contains(
+            "n4 [label=\"Heap 0: 1\\lHeap 1: 0\\lscore +2, win in 1\\l\", fillcolor=palegreen];"
+        ));
+        assert!(output.contains("n0 -> n1 [label=\"1-1\"];"));
+        assert!(output.starts_with("digraph tree {") && output.ends_with("}\n"));
+    }
+
+    #[test]
+    fn index_round_trips() {
+        let positions = Nim::new(vec![2, 3, 1]);
+        assert_eq!(positions.position_count(), 3 * 4 * 2);
+
+        for index in 0..positions.position_count() {
+            let position = positions.unindex(index).unwrap();
+            assert_eq!(positions.index(&position), Some(index));
+        }
+
+        assert_eq!(positions.unindex(positions.position_count()), None);
+        assert_eq!(positions.index(&Nim::new(vec![3, 0, 0])), None);
+        assert_eq!(positions.index(&Nim::new(vec![1, 1])), None);
+    }
+
+    #[test]
+    fn tablebase_matches_search() {
+        let positions = Nim::new(vec![2, 3, 4]);
+        let tablebase = Tablebase::new(positions.clone()).unwrap();
+        assert_eq!(tablebase.len(), positions.position_count());
+
+        for index in 0..positions.position_count() {
+            let position = positions.unindex(index).unwrap();
+
+            // the solver's search window doesn't account for games that are already over
+            if Game::state(&position) != GameState::Playable {
+                continue;
+            }
+
+            assert_eq!(
+                tablebase.probe(&position),
+                Some(solve(&position, &mut HashMap::new(), None, &None).unwrap()),
+                "{position:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn tablebase_as_leaf_oracle() {
+        // only the endgames, once every heap is down to 2 objects, are in the tablebase
+        let tablebase = Tablebase::new(Nim::new(vec![2, 2, 2])).unwrap();
+
+        for heaps in [vec![3, 3, 4], vec![2, 4, 5], vec![1, 3, 5]] {
+            let game = Nim::new(heaps);
+
+            let with_tablebase = move_scores(
+                &game,
+                &mut TablebaseProbe::new(&tablebase, HashMap::new()),
+                None,
+                &None,
+            )
+            .map(Result::unwrap)
+            .collect_vec();
+            let without_tablebase = play(game).into_iter().map(Result::unwrap).collect_vec();
+
+            assert_eq!(with_tablebase, without_tablebase);
+        }
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let mut game = Nim::new(vec![3, 5, 7]);
+        game.make_move(&NaturalMove([2, 3])).unwrap();
+        game.make_move(&NaturalMove([0, 1])).unwrap();
+
+        let json = serde_json::to_string(&game).unwrap();
+        assert_eq!(json, r#"{"heaps":[2,5,4],"move_count":2,"max_moves":15}"#);
+        assert_eq!(serde_json::from_str::<Nim>(&json).unwrap(), game);
+
+        // without the starting objects, the game starts from the objects left
+        let game = serde_json::from_str::<Nim>(r#"{"heaps":[3,5,7],"move_count":0}"#).unwrap();
+        assert_eq!(game, Nim::new(vec![3, 5, 7]));
+    }
+
+    #[test]
+    fn players_are_named_relative_to_the_position() {
+        let mut game = Nim::new(vec![1]);
+        assert_eq!(game.player().to_string(), "Next");
+        assert!(game.player().is_maximizing());
+
+        game.make_move(&NaturalMove([0, 1])).unwrap();
+        let winner = game.winner().unwrap();
+        assert_eq!(winner, ImpartialPlayer::Previous);
+        assert_eq!(serde_json::to_string(&winner).unwrap(), r#""Previous""#);
+        assert_eq!(
+            serde_json::from_str::<ImpartialPlayer>(r#""Previous""#).unwrap(),
+            winner
+        );
+
+        // Left moved first, so Left made the last move
+        assert_eq!(
+            winner.to_partizan(PartizanPlayer::Right),
+            PartizanPlayer::Left
+        );
+    }
+
+    #[test]
+    fn serde_rejects_impossible_positions() {
+        let err = serde_json::from_str::<Nim>(r#"{"heaps":[3,5,7],"move_count":2,"max_moves":15}"#)
+            .unwrap_err();
+        assert!(err.to_string().contains("at least 17 objects"), "{err}");
+    }
+
+    #[test]
+    fn notation_round_trip() {
+        let mut game = Nim::new(vec![3, 5, 7]);
+        assert_eq!(game.to_notation(), "3,5,7 m0");
+
+        game.make_move(&NaturalMove([2, 1])).unwrap();
+        assert_eq!(game.to_notation(), "3,5,6 m1");
+        assert_eq!(Nim::from_notation("3,5,6 m1").unwrap(), game);
+
+        // taking several objects at once leaves more objects at the start than can be worked out
+        game.make_move(&NaturalMove([1, 4])).unwrap();
+        assert_eq!(game.to_notation(), "3,1,6 m2 s15");
+        assert_eq!(Nim::from_notation(" 3,1,6\tm2  s15 ").unwrap(), game);
+    }
+
+    #[test]
+    fn notation_rejects_invalid_positions() {
+        let span = |notation: &str| Nim::from_notation(notation).unwrap_err().span();
+
+        assert_eq!(span("3,x,7 m0"), 2..3);
+        assert_eq!(span("3,,7 m0"), 2..2);
+        assert_eq!(span("3,5,7 2"), 6..7);
+        assert_eq!(span("3,5,7 m-1"), 7..9);
+        assert_eq!(span("3,5,7"), 5..5);
+        assert_eq!(span("3,5,7 m2 s16"), 9..12);
+        assert_eq!(span("3,5,7 m2 s20 s20"), 13..16);
+    }
+
+    #[test]
+    fn optimal_moves_match_the_best_scores() {
+        for heaps in [vec![3, 5, 7], vec![2, 2], vec![1, 2, 4], vec![4]] {
+            let game = Nim::new(heaps.clone());
+
+            assert_eq!(
+                optimal_moves(&game, &mut HashMap::new()).unwrap(),
+                optimal_moves_testing(&game),
+                "{heaps:?}"
+            );
+        }
+    }
+
+    fn outcome<T: Game<Player = ImpartialPlayer> + Eq + Hash + std::fmt::Debug>(
+        game: &T,
+    ) -> Outcome {
+        Outcome::from_score(solve(game, &mut HashMap::new(), None, &None).unwrap())
+    }
+
+    #[test]
+    fn a_lost_second_game_leaves_the_first_one() {
+        // whoever takes the last object of the first game makes the other player start the second,
+        // which they lose, so the first game is played as if it were alone
+        for second in [vec![1, 1], vec![2, 2], vec![1, 2, 3]] {
+            for first in [
+                vec![3],
+                vec![1, 2],
+                vec![2, 2],
+                vec![1, 1, 1],
+                vec![1, 3, 4],
+            ] {
+                let sequence = SequentialCompound::new(
+                    Nim::new(first.clone()),
+                    Nim::new(second.clone()),
+                    SequentialScoring::LastGameDecides,
+                );
+
+                assert_eq!(
+                    outcome(&sequence),
+                    outcome(&Nim::new(first.clone())),
+                    "{first:?} then {second:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn a_won_second_game_turns_the_first_one_misere() {
+        // now nobody wants to take the last object of the first game,
+        // so whoever moves first wins if some heap has more than one object and the nim-sum isn't 0,
+        // or if every heap has at most one object and the nim-sum is 0
+        for first in [
+            vec![3],
+            vec![1, 2],
+            vec![2, 2],
+            vec![1, 1, 1],
+            vec![1, 1],
+            vec![1, 3, 4],
+        ] {
+            let sequence = SequentialCompound::new(
+                Nim::new(first.clone()),
+                Nim::new(vec![2, 1]),
+                SequentialScoring::LastGameDecides,
+            );
+
+            let nim_sum = first.iter().fold(0, |sum, heap| sum ^ heap);
+            let wins = if first.iter().any(|&heap| heap > 1) {
+                nim_sum != 0
+            } else {
+                nim_sum == 0
+            };
+
+            assert_eq!(outcome(&sequence) == Outcome::Win, wins, "{first:?}");
+        }
+    }
+
+    #[test]
+    fn sequential_scoring() {
+        // the first player takes the first object, and the second player the second
+        let sequence =
+            |scoring| SequentialCompound::new(Nim::new(vec![1]), Nim::new(vec![1]), scoring);
+
+        assert_eq!(outcome(&sequence(SequentialScoring::Sum)), Outcome::Tie);
+        assert_eq!(
+            outcome(&sequence(SequentialScoring::LastGameDecides)),
+            Outcome::Loss
+        );
+    }
+
+    #[test]
+    fn remoteness_is_how_long_the_solver_plays() {
+        for heaps in [
+            vec![3],
+            vec![1, 1],
+            vec![1, 2],
+            vec![2, 2],
+            vec![1, 2, 3],
+            vec![2, 3, 4],
+        ] {
+            let game = Nim::new(heaps.clone());
+            let score = solve(&game, &mut HashMap::new(), None, &None).unwrap();
+            let remoteness = remoteness(&game).unwrap();
+
+            // scores are measured from the move count the game ends at
+            assert_eq!(
+                remoteness as isize,
+                game.max_moves().unwrap() as isize + 1 - score.abs(),
+                "{heaps:?}"
+            );
+            assert_eq!(remoteness % 2 == 0, score < 0, "{heaps:?}");
+        }
+
+        // the player to move takes a whole heap
+        assert_eq!(remoteness(&Nim::new(vec![5])).unwrap(), 1);
+        assert_eq!(remoteness(&Nim::new(vec![0])).unwrap(), 0);
+    }
+
+    #[test]
+    fn conjunctive_compounds_last_as_long_as_their_shortest_component() {
+        for components in [
+            vec![vec![1, 2], vec![2, 2]],
+            vec![vec![2, 2], vec![1, 1]],
+            vec![vec![3], vec![1, 1]],
+            vec![vec![1, 1], vec![2, 2], vec![1, 2]],
+        ] {
+            let games = components
+                .iter()
+                .map(|heaps| Nim::new(heaps.clone()))
+                .collect::<Vec<_>>();
+            let shortest = games
+                .iter()
+                .map(|game| remoteness(game).unwrap())
+                .min()
+                .unwrap();
+            let compound = ConjunctiveCompound::new(games);
+
+            assert_eq!(remoteness(&compound).unwrap(), shortest, "{components:?}");
+            assert_eq!(
+                outcome(&compound) == Outcome::Loss,
+                shortest % 2 == 0,
+                "{components:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn selective_compounds_are_won_by_winning_any_component() {
+        for components in [
+            vec![vec![1, 1], vec![2, 2]],
+            vec![vec![1, 2], vec![2, 2]],
+            vec![vec![3], vec![1, 1]],
+            vec![vec![1, 1]],
+            vec![vec![1, 2, 3], vec![1]],
+        ] {
+            let won = components
+                .iter()
+                .any(|heaps| heaps.iter().fold(0, |sum, heap| sum ^ heap) != 0);
+            let compound = SelectiveCompound::new(
+                components
+                    .iter()
+                    .map(|heaps| Nim::new(heaps.clone()))
+                    .collect(),
+            );
+
+            assert_eq!(outcome(&compound) == Outcome::Win, won, "{components:?}");
+        }
+    }
+
+    #[test]
+    fn impartial_outcome_classes() {
+        for heaps in [vec![1, 2, 3], vec![1, 2], vec![4, 4], vec![]] {
+            let nim_sum = heaps.iter().fold(0, |sum, heap| sum ^ heap);
+            let class = impartial_outcome_class(&Nim::new(heaps.clone())).unwrap();
+
+            let expected = if nim_sum != 0 {
+                OutcomeClass::Next
+            } else {
+                OutcomeClass::Previous
+            };
+            assert_eq!(class, expected, "{heaps:?}");
+        }
+    }
+
+    #[test]
+    fn grundy_outcomes_agree_with_negamax() {
+        impartial_outcomes_testing(&Nim::new(vec![2, 3, 5, 6]), 10, 300);
+
+        // compounds of impartial games are impartial too
+        let heaps = || vec![Nim::new(vec![2, 3]), Nim::new(vec![1, 4])];
+        impartial_outcomes_testing(&ConjunctiveCompound::new(heaps()), 5, 200);
+        impartial_outcomes_testing(&SelectiveCompound::new(heaps()), 8, 200);
+    }
+
+    #[test]
+    fn deeper_wins_are_harder() {
+        let mut table = HashMap::new();
+        let difficulties = [
+            vec![1],
+            vec![1, 2],
+            vec![2, 4],
+            vec![3, 5],
+            vec![1, 4, 6],
+            vec![3, 5, 7],
+        ]
+        .map(|heaps| difficulty(&Nim::new(heaps), &mut table).unwrap().unwrap());
+
+        for pair in difficulties.windows(2) {
+            assert!(pair[0].depth_to_win < pair[1].depth_to_win, "{pair:?}");
+            assert!(pair[0].rating() < pair[1].rating(), "{pair:?}");
+        }
+
+        // taking the last object is the only move, and no trap at all
+        assert_eq!(difficulties[0].forcing_ratio, 1.0);
+        assert_eq!(difficulties[0].rating(), 0.0);
+    }
+
+    #[test]
+    fn moves_are_ordered_best_first() {
+        let game = Nim::new(vec![3, 4, 5]);
+        let moves = game.possible_moves().map(|m| m.to_string()).collect_vec();
+        assert_eq!(
+            moves,
+            ["0-2", "2-5", "1-4", "2-4", "0-3", "1-3", "2-3", "1-2", "2-2", "0-1", "1-1", "2-1"]
+        );
+
+        // with the heaps XORing to 0, the largest takes come first
+        let game = Nim::new(vec![1, 2, 3]);
+        let moves = game.possible_moves().map(|m| m.to_string()).collect_vec();
+        assert_eq!(moves, ["2-3", "1-2", "2-2", "0-1", "1-1", "2-1"]);
+
+        // every move is still made once, however many were asked for
+        let game = Nim::new(vec![0, 6, 2, 7, 0]);
+        let mut moves = game.possible_moves();
+        assert_eq!(moves.len(), 15);
+        moves.nth(3);
+        assert_eq!(moves.len(), 11);
+        let mut moves = game.possible_moves().collect_vec();
+        moves.sort();
+        assert_eq!(moves.len(), 15);
+        moves.dedup();
+        assert_eq!(moves.len(), 15);
+        assert!(Nim::new(vec![0, 0]).possible_moves().next().is_none());
+    }
+
+    #[test]
+    fn optimal_moves_are_searched_first() {
+        // the order moves were made in before: 1, 2, and up to every object of each heap in turn
+        let smallest_first = MoveOrdering::<Nim>::Heuristic(Box::new(|_, m: &NimMove| {
+            let [heap, amount] = m.0;
+            -((heap * 1000 + amount) as isize)
+        }));
+
+        let explore = |game: &Nim, ordering: &MoveOrdering<Nim>| {
+            let stats = Stats::new(game.player(), game.move_count());
+            let score =
+                solve_ordered(game, &mut HashMap::new(), Some(&stats), &None, ordering).unwrap();
+            (score, stats.states_explored.into_inner())
+        };
+
+        for heaps in [vec![2, 5, 6, 7], vec![3, 5, 7, 9], vec![2, 4, 6, 9]] {
+            let game = Nim::new(heaps.clone());
+            let (score, nodes) = explore(&game, &MoveOrdering::GameOrder);
+            let (expected, smallest_first_nodes) = explore(&game, &smallest_first);
+
+            assert_eq!(score, expected, "{heaps:?}");
+            assert_eq!(game.optimal_move().is_some(), score > 0, "{heaps:?}");
+            assert!(
+                nodes * 3 < smallest_first_nodes,
+                "{heaps:?}: {nodes} positions, and {smallest_first_nodes} smallest first"
+            );
+        }
+    }
+
+    #[test]
+    fn windows_agree_with_the_score() {
+        window_results_testing(&Nim::new(vec![2, 3, 5]), 8, 40);
+    }
+
+    #[test]
+    fn resolvable_games_agree_with_making_every_move() {
+        use crate::util::resolve::tests::agrees_with_making_every_move;
+
+        assert_eq!(agrees_with_making_every_move(Nim::new(vec![3, 4, 5])), 544);
+    }
 }
@@ -2,21 +2,32 @@
 
 #[cfg(feature = "egui")]
 pub mod gui;
-use anyhow::{anyhow, Error};
+use anyhow::Error;
 use array2d::Array2D;
 use clap::Args;
-use game_solver::{
-    game::{Game, GameState},
-    player::PartizanPlayer,
-};
-use serde::{Deserialize, Serialize};
+use game_solver::{hint::Hints, ordering::PlacementGame, prelude::*};
+use game_solver_derive::placement_game;
+use serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 use std::{
     fmt::{Debug, Display, Formatter},
     hash::Hash,
+    str::FromStr,
 };
 use thiserror::Error;
 
-use crate::util::cli::move_failable;
+use crate::util::metadata::GameMetadata;
+use crate::util::{
+    bench::{parse_moves, BenchPosition},
+    cli::{evaluate::Heuristics, outcome::OutcomeClasses, replay_parsed_moves, Endings},
+    lines::{run_through, Lines},
+    notation::{fields, read_board, NotatedGame, NotationError, ToMove},
+    parse::{parse_numbers, ParseError, ParseMove},
+    position::{parse_board, PositionError},
+    render::{Cell, Grid, Render},
+    resolve::find_resolvable,
+    scan::{scan, Scan},
+    symmetry::Symmetries,
+};
 
 #[derive(Clone, Copy, Hash, Eq, PartialEq, Debug)]
 pub enum CellType {
@@ -37,7 +48,8 @@ impl Display for CellType {
     }
 }
 
-#[derive(Clone, Hash, Eq, PartialEq)]
+#[derive(Clone, Hash, Eq, PartialEq, Deserialize)]
+#[serde(try_from = "OrderAndChaosPosition")]
 pub struct OrderAndChaos<
     const WIDTH: usize,
     const HEIGHT: usize,
@@ -67,8 +79,11 @@ impl<
         const MAX_WIN_LENGTH: usize,
     > OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
 {
-    /// Create a new game of Nim with the given heaps,
-    /// where heaps is a list of the number of objects in each heap.
+    /// An empty board, where Order moves first.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `MIN_WIN_LENGTH` is more than `MAX_WIN_LENGTH`, or a line that long can't fit on the board.
     pub fn new() -> Self {
         assert!(MIN_WIN_LENGTH <= MAX_WIN_LENGTH, "MIN > MAX win length?");
         // [a, b][(a < b) as usize] is essentially the max function: https://stackoverflow.com/a/53646925/7589775
@@ -82,6 +97,78 @@ impl<
             move_count: 0,
         }
     }
+
+    /// Sets up the position on `board`, written as in the notation (e.g. `X...../.O..../....../....../....../......`),
+    /// without replaying the moves that led to it.
+    ///
+    /// As Order moves first, it's Order's turn if an even number of squares are filled.
+    /// `to_move` is checked against that.
+    pub fn from_board(board: &str, to_move: Option<PartizanPlayer>) -> Result<Self, NotationError> {
+        read_board(board, |rows| {
+            let game = Self::from_notation(rows)?;
+
+            match to_move {
+                Some(player) if player != game.player() => Err(NotationError::new(
+                    rows,
+                    0..rows.len(),
+                    format!(
+                        "it's {:?}'s turn, as {} squares are filled",
+                        game.player(),
+                        game.move_count
+                    ),
+                )),
+                _ => Ok(game),
+            }
+        })
+    }
+
+    /// Returns Order if they have lined up enough pieces of the same type:
+    /// a run of at least `MIN_WIN_LENGTH` and at most `MAX_WIN_LENGTH` of them in a row,
+    /// a column, or either diagonal.
+    fn lined_up(&self) -> Option<PartizanPlayer> {
+        // we need at least MIN_WIN_LENGTH plays to get a win
+        if self.move_count < MIN_WIN_LENGTH {
+            return None;
+        }
+
+        // right, down, down and right, and down and left
+        const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+        for row in 0..HEIGHT as isize {
+            for column in 0..WIDTH as isize {
+                let Some(cell_type) = self.cell_at(row, column) else {
+                    continue;
+                };
+
+                for (down, right) in DIRECTIONS {
+                    // every run is only measured from its first piece, so it's measured whole
+                    if self.cell_at(row - down, column - right) == Some(cell_type) {
+                        continue;
+                    }
+
+                    let length = (0..)
+                        .take_while(|&k| {
+                            self.cell_at(row + k * down, column + k * right) == Some(cell_type)
+                        })
+                        .count();
+
+                    if (MIN_WIN_LENGTH..=MAX_WIN_LENGTH).contains(&length) {
+                        return Some(PartizanPlayer::Left);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// The piece at (`row`, `column`), or None if it's empty or off the board.
+    fn cell_at(&self, row: isize, column: isize) -> Option<CellType> {
+        let row = usize::try_from(row).ok()?;
+        let column = usize::try_from(column).ok()?;
+
+        self.board.get(row, column).copied().flatten()
+    }
 }
 
 #[derive(Error, Clone, Debug)]
@@ -101,10 +188,50 @@ pub struct OrderAndChaosMove(((usize, usize), CellType));
 
 impl Display for OrderAndChaosMove {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} @ ({}, {})", self.0 .1, self.0 .0 .0, self.0 .0 .1)
+        let ((row, column), cell) = self.0;
+        write!(f, "{row}-{column}-{}", cell.to_string().to_lowercase())
+    }
+}
+
+impl Serialize for OrderAndChaosMove {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderAndChaosMove {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl FromStr for OrderAndChaosMove {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let expected =
+            "a row, column, and cell separated by hyphens (row-column-x or row-column-o)";
+
+        let (coordinate, cell) = s
+            .rsplit_once('-')
+            .ok_or_else(|| ParseError::malformed(s, expected))?;
+
+        let numbers =
+            parse_numbers(coordinate, 2).map_err(|_| ParseError::malformed(s, expected))?;
+
+        let cell = match cell.to_ascii_lowercase().as_str() {
+            "x" => CellType::X,
+            "o" => CellType::O,
+            _ => return Err(ParseError::malformed(s, expected)),
+        };
+
+        Ok(OrderAndChaosMove(((numbers[0], numbers[1]), cell)))
     }
 }
 
+#[placement_game(cells = WIDTH * HEIGHT, winner = lined_up, full_board = Right)]
 impl<
         const WIDTH: usize,
         const HEIGHT: usize,
@@ -115,21 +242,13 @@ impl<
     /// where Move is a tuple of:
     /// ((row, column), player)
     type Move = OrderAndChaosMove;
-    type Iter<'a> = std::vec::IntoIter<Self::Move>;
+    type Iter<'a> = Scan<'a, Self, Self::Move>;
     /// Define Order and Chaos as a zero-sum game,
     /// where Left is Order,
     /// and Right is Chaos
     type Player = PartizanPlayer;
     type MoveError = OrderAndChaosMoveError;
 
-    fn max_moves(&self) -> Option<usize> {
-        Some(WIDTH * HEIGHT)
-    }
-
-    fn move_count(&self) -> usize {
-        self.move_count
-    }
-
     fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
         let ((row, column), player) = m.0;
         // check for indexing OOB
@@ -154,108 +273,123 @@ impl<
     }
 
     fn possible_moves(&self) -> Self::Iter<'_> {
-        let mut moves = Vec::new();
-
-        for row in 0..HEIGHT {
-            for column in 0..WIDTH {
-                if self.board[(row, column)].is_none() {
-                    moves.push(OrderAndChaosMove(((row, column), CellType::X)));
-                    moves.push(OrderAndChaosMove(((row, column), CellType::O)));
-                }
-            }
-        }
+        // row by row, an X then an O in every empty cell
+        scan(self, WIDTH * HEIGHT * 2, |(game, index)| {
+            let cell = (index / 2 / WIDTH, index / 2 % WIDTH);
+            let cell_type = if index % 2 == 0 {
+                CellType::X
+            } else {
+                CellType::O
+            };
 
-        moves.into_iter()
+            game.board[cell]
+                .is_none()
+                .then_some(OrderAndChaosMove((cell, cell_type)))
+        })
     }
 
-    fn state(&self) -> GameState<Self::Player> {
-        // we need at least MIN_WIN_LENGTH plays to get a win
-        if self.move_count < MIN_WIN_LENGTH {
-            return GameState::Playable;
-        }
+    /// Order often has a line to finish, and finding it before searching any move
+    /// makes up for checking the lines through every empty cell.
+    fn cheap_win_check(&self) -> bool {
+        true
+    }
 
-        // check every horizontal row
-        for i in 0..HEIGHT {
-            // for the first (some) elements in that row
-            // TODO: this will not work if min_width_length > width! can we consider this?
-            'row_check: for j in 0..=(WIDTH - MIN_WIN_LENGTH) {
-                // find a piece? lets see if it continues going
-                if let Some(cell_type) = self.board[(j, i)] {
-                    for k in (j + 1)..((MIN_WIN_LENGTH + j).min(WIDTH)) {
-                        if let Some(found_cell_type) = self.board[(k, i)] {
-                            if cell_type == found_cell_type {
-                                continue;
-                            } else {
-                                break 'row_check;
-                            }
-                        } else {
-                            break 'row_check;
-                        }
-                    }
+    /// Checks the runs through each move's cell, which are the only ones it can line up,
+    /// without making any moves.
+    fn find_immediately_resolvable_game(&self) -> Result<Option<Self>, Self::MoveError> {
+        Lines::with(&[HEIGHT, WIDTH], |lines| {
+            find_resolvable(self, |m| {
+                let ((row, column), cell_type) = m.0;
+                let lined_up = lines.through(row * WIDTH + column).any(|(line, at)| {
+                    let length = run_through(line, at, |cell| {
+                        self.board[(cell / WIDTH, cell % WIDTH)] == Some(cell_type)
+                    });
+                    (MIN_WIN_LENGTH..=MAX_WIN_LENGTH).contains(&length)
+                });
 
-                    return GameState::Win(PartizanPlayer::Left);
+                if lined_up {
+                    GameState::Win(PartizanPlayer::Left)
+                } else if self.move_count + 1 == WIDTH * HEIGHT {
+                    GameState::Win(PartizanPlayer::Right)
+                } else {
+                    GameState::Playable
                 }
-            }
-        }
+            })
+        })
+    }
+}
 
-        // check every column
-        for i in 0..WIDTH {
-            'column_check: for j in 0..=(HEIGHT - MIN_WIN_LENGTH) {
-                // find a piece? see if it continues going
-                if let Some(cell_type) = self.board[(i, j)] {
-                    for k in (j + 1)..((MIN_WIN_LENGTH + j).min(HEIGHT)) {
-                        if let Some(found_cell_type) = self.board[(i, k)] {
-                            if cell_type == found_cell_type {
-                                continue;
-                            } else {
-                                break 'column_check;
-                            }
-                        } else {
-                            break 'column_check;
-                        }
-                    }
+impl<
+        const WIDTH: usize,
+        const HEIGHT: usize,
+        const MIN_WIN_LENGTH: usize,
+        const MAX_WIN_LENGTH: usize,
+    > PlacementGame for OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
+{
+    fn grid_size(&self) -> Vec<usize> {
+        vec![HEIGHT, WIDTH]
+    }
 
-                    return GameState::Win(PartizanPlayer::Left);
-                }
-            }
-        }
+    fn cell(&self, m: &Self::Move) -> Vec<usize> {
+        let ((row, column), _) = m.0;
+        vec![row, column]
+    }
+}
 
-        // check every diag - we can essentially
-        // check every value in the top right corner
-        for i in 0..=(WIDTH - MIN_WIN_LENGTH) {
-            'diag_check: for j in 0..=(HEIGHT - MIN_WIN_LENGTH) {
-                if let Some(cell_type) = self.board[(i, j)] {
-                    // found a cell! lets continue going down!
-                    for k in 1..MIN_WIN_LENGTH.min(WIDTH).min(HEIGHT) {
-                        if let Some(found_cell_type) = self.board[(i + k, j + k)] {
-                            if found_cell_type == cell_type {
-                                continue;
-                            } else {
-                                break 'diag_check;
-                            }
-                        } else {
-                            break 'diag_check;
-                        }
-                    }
+impl<
+        const WIDTH: usize,
+        const HEIGHT: usize,
+        const MIN_WIN_LENGTH: usize,
+        const MAX_WIN_LENGTH: usize,
+    > Display for OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
+{
+    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
+        self.board_grid().write_plain(f)
+    }
+}
 
-                    return GameState::Win(PartizanPlayer::Left);
-                }
-            }
-        }
+impl<
+        const WIDTH: usize,
+        const HEIGHT: usize,
+        const MIN_WIN_LENGTH: usize,
+        const MAX_WIN_LENGTH: usize,
+    > OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
+{
+    /// The board as a grid, where empty squares are written as `-` on the plain board.
+    /// Either player can place either symbol, so `X` is drawn in the colors of Order, and `O` in those of Chaos.
+    fn board_grid(&self) -> Grid {
+        let playable = self.state() == GameState::Playable;
 
-        if self.move_count == WIDTH * HEIGHT {
-            return GameState::Win(PartizanPlayer::Right);
-        }
+        Grid::new(
+            (0..HEIGHT)
+                .map(|row| {
+                    (0..WIDTH)
+                        .map(|column| match self.board[(row, column)] {
+                            Some(CellType::X) => Cell::piece('X', PartizanPlayer::Left),
+                            Some(CellType::O) => Cell::piece('O', PartizanPlayer::Right),
+                            None if playable => Cell::empty('-').legal(),
+                            None => Cell::empty('-'),
+                        })
+                        .collect()
+                })
+                .collect(),
+        )
+    }
+}
 
-        GameState::Playable
+impl<
+        const WIDTH: usize,
+        const HEIGHT: usize,
+        const MIN_WIN_LENGTH: usize,
+        const MAX_WIN_LENGTH: usize,
+    > Render for OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
+{
+    fn grid(&self) -> Option<Grid> {
+        Some(self.board_grid())
     }
 
-    fn player(&self) -> PartizanPlayer {
-        if self.move_count % 2 == 0 {
-            PartizanPlayer::Left
-        } else {
-            PartizanPlayer::Right
-        }
+    fn move_cells(&self, m: &Self::Move) -> Vec<(usize, usize)> {
+        vec![m.0 .0]
     }
 }
 
@@ -264,41 +398,135 @@ impl<
         const HEIGHT: usize,
         const MIN_WIN_LENGTH: usize,
         const MAX_WIN_LENGTH: usize,
-    > Display for OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
+    > Debug for OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
 {
-    fn fmt(&self, f: &mut Formatter) -> Result<(), std::fmt::Error> {
-        for row in 0..HEIGHT {
-            for column in 0..WIDTH {
-                match self.board[(row, column)] {
-                    Some(CellType::X) => write!(f, "X")?,
-                    Some(CellType::O) => write!(f, "O")?,
-                    None => write!(f, "-")?,
-                }
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        <Self as Display>::fmt(self, f)
+    }
+}
+
+/// Serializes the board as its rows, using the same characters as [`Display`].
+impl<
+        const WIDTH: usize,
+        const HEIGHT: usize,
+        const MIN_WIN_LENGTH: usize,
+        const MAX_WIN_LENGTH: usize,
+    > Serialize for OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let board = self.to_string();
+
+        let mut state = serializer.serialize_struct("OrderAndChaos", 1)?;
+        state.serialize_field("board", &board.lines().collect::<Vec<_>>())?;
+        state.end()
+    }
+}
+
+/// A serialized [`OrderAndChaos`], before it's checked.
+/// As every move fills one square, the move count is left out.
+#[derive(Deserialize)]
+struct OrderAndChaosPosition {
+    board: Vec<String>,
+}
+
+impl<
+        const WIDTH: usize,
+        const HEIGHT: usize,
+        const MIN_WIN_LENGTH: usize,
+        const MAX_WIN_LENGTH: usize,
+    > TryFrom<OrderAndChaosPosition>
+    for OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
+{
+    type Error = PositionError;
+
+    fn try_from(position: OrderAndChaosPosition) -> Result<Self, Self::Error> {
+        let rows = parse_board(&position.board, WIDTH, HEIGHT, "XO-")?;
+
+        let mut game = Self::new();
+        for (row, squares) in rows.iter().enumerate() {
+            for (column, &square) in squares.iter().enumerate() {
+                game.board[(row, column)] = match square {
+                    'X' => Some(CellType::X),
+                    'O' => Some(CellType::O),
+                    _ => continue,
+                };
+                game.move_count += 1;
             }
-            writeln!(f)?;
         }
-        Ok(())
+
+        Ok(game)
     }
 }
 
+/// Written as the rows of the board, separated by `/`, where empty squares are marked with `.`,
+/// e.g. `X...../.O..../....../....../....../......`.
+/// As every move fills one square, the player to move follows from the board.
 impl<
         const WIDTH: usize,
         const HEIGHT: usize,
         const MIN_WIN_LENGTH: usize,
         const MAX_WIN_LENGTH: usize,
-    > Debug for OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
+    > NotatedGame for OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
 {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        <Self as Display>::fmt(self, f)
+    fn to_notation(&self) -> String {
+        self.to_string()
+            .lines()
+            .map(|row| row.replace('-', "."))
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    fn from_notation(notation: &str) -> Result<Self, NotationError> {
+        let fields = fields(notation, &["board"], &[])?;
+        let rows = fields[0].board("XO.", Some((WIDTH, HEIGHT)))?;
+
+        Self::try_from(OrderAndChaosPosition {
+            board: rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|&square| if square == '.' { '-' } else { square })
+                        .collect()
+                })
+                .collect(),
+        })
+        .map_err(|err| fields[0].error(err.to_string()))
     }
 }
 
+/// What Order and Chaos is, and how it's set up from the CLI.
+pub const METADATA: GameMetadata = GameMetadata {
+    name: "Order and Chaos",
+    summary:
+        "Order tries to get 5 of a kind in a row on a 6x6 board, and Chaos tries to stop them.",
+    rules: include_str!("./README.md"),
+    parameters: &[],
+    move_notation: "row-column-cell, where the cell is x or o (e.g. 2-3-x)",
+    example: "order-and-chaos 2-3-x 3-3-o",
+};
+
 /// Analyzes Order and Chaos.
 ///
 #[doc = include_str!("./README.md")]
 #[derive(Args, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Default)]
 pub struct OrderAndChaosArgs {
+    /// Order and Chaos moves, ordered as row1-column1-cell1 row2-column2-cell2 ...,
+    /// where cell is either x or o.
     moves: Vec<String>,
+    /// The position to analyze, in its notation
+    /// (e.g. `X...../.O..../....../....../....../......`), instead of the moves.
+    #[arg(long, conflicts_with = "moves")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<String>,
+    /// The board to analyze, with its rows separated by `/` or on lines of their own, instead of the moves.
+    /// As Order moves first, whose turn it is follows from how many squares are filled.
+    #[arg(long, conflicts_with_all = ["moves", "position"])]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    board: Option<String>,
+    /// The player to move on the board (Left is Order), which has to be the one whose turn it is.
+    #[arg(long, requires = "board")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    to_move: Option<ToMove>,
 }
 
 impl<
@@ -306,41 +534,127 @@ impl<
         const HEIGHT: usize,
         const MIN_WIN_LENGTH: usize,
         const MAX_WIN_LENGTH: usize,
-    > TryFrom<OrderAndChaosArgs> for OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
+    > ParseMove for OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
 {
-    type Error = Error;
+    fn parse_move(&self, token: &str) -> Result<Self::Move, ParseError> {
+        token.parse()
+    }
+}
 
-    fn try_from(value: OrderAndChaosArgs) -> Result<Self, Self::Error> {
-        let mut game = OrderAndChaos::new();
+impl<
+        const WIDTH: usize,
+        const HEIGHT: usize,
+        const MIN_WIN_LENGTH: usize,
+        const MAX_WIN_LENGTH: usize,
+    > Heuristics for OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
+{
+}
 
-        // parse every move in args, e.g. 0-0-x 1-1-o in args
-        for arg in value.moves {
-            let args: Vec<&str> = arg.split('-').collect();
+impl<
+        const WIDTH: usize,
+        const HEIGHT: usize,
+        const MIN_WIN_LENGTH: usize,
+        const MAX_WIN_LENGTH: usize,
+    > Hints for OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
+{
+}
 
-            let numbers = args[0..2]
-                .iter()
-                .map(|num| num.parse::<usize>().expect("Not a number!"))
-                .collect::<Vec<_>>();
+/// Chaos wins once the board is full without a line, whoever made the last move,
+/// which isn't a tie, as it would be in other placement games.
+impl<
+        const WIDTH: usize,
+        const HEIGHT: usize,
+        const MIN_WIN_LENGTH: usize,
+        const MAX_WIN_LENGTH: usize,
+    > Endings for OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
+{
+    fn decided_by(&self) -> Option<String> {
+        match self.state() {
+            GameState::Win(PartizanPlayer::Left) => {
+                Some(format!("Order wins with a line of {MIN_WIN_LENGTH}."))
+            }
+            GameState::Win(PartizanPlayer::Right) => Some(format!(
+                "The board is full without a line of {MIN_WIN_LENGTH}, so Chaos wins."
+            )),
+            GameState::Playable | GameState::Tie => None,
+        }
+    }
+}
 
-            let player = match args[2].to_ascii_lowercase().as_str() {
-                "x" => Ok(CellType::X),
-                "o" => Ok(CellType::O),
-                _ => Err(anyhow!("Invalid player!")),
-            }?;
+impl<
+        const WIDTH: usize,
+        const HEIGHT: usize,
+        const MIN_WIN_LENGTH: usize,
+        const MAX_WIN_LENGTH: usize,
+    > OutcomeClasses for OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
+{
+}
 
-            assert_eq!(args.len(), 3);
+impl<
+        const WIDTH: usize,
+        const HEIGHT: usize,
+        const MIN_WIN_LENGTH: usize,
+        const MAX_WIN_LENGTH: usize,
+    > Symmetries for OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
+{
+}
 
-            let move_to_make = OrderAndChaosMove(((numbers[0], numbers[1]), player));
-            move_failable(&mut game, &move_to_make)?;
+impl<
+        const WIDTH: usize,
+        const HEIGHT: usize,
+        const MIN_WIN_LENGTH: usize,
+        const MAX_WIN_LENGTH: usize,
+    > TryFrom<OrderAndChaosArgs> for OrderAndChaos<WIDTH, HEIGHT, MIN_WIN_LENGTH, MAX_WIN_LENGTH>
+{
+    type Error = Error;
+
+    fn try_from(value: OrderAndChaosArgs) -> Result<Self, Self::Error> {
+        if let Some(position) = value.position {
+            return Ok(Self::from_notation(&position)?);
+        }
+
+        if let Some(board) = value.board {
+            return Ok(Self::from_board(&board, value.to_move.map(Into::into))?);
         }
 
+        let mut game = OrderAndChaos::new();
+
+        // parse every move in args, e.g. 0-0-x 1-1-o in args
+        replay_parsed_moves(&mut game, &value.moves)?;
+
         Ok(game)
     }
 }
 
+/// Positions for the `bench` subcommand. The empty board is out of reach,
+/// so these are taken from the same game at different stages.
+pub fn bench_positions() -> Vec<BenchPosition<OrderAndChaosArgs>> {
+    let game = "0-1-o 1-0-x 1-4-o 2-3-x 3-1-o 4-0-x 4-4-o 5-3-x 0-2-o 1-3-x 2-4-o 3-5-x \
+                5-0-o 0-3-x 2-0-o 3-4-x 5-2-o 1-2-x 3-3-o 0-0-x 3-0-o 0-4-x 4-2-o 2-2-x";
+
+    [
+        ("12 empty squares", true, 24),
+        ("16 empty squares", false, 20),
+    ]
+    .into_iter()
+    .map(|(name, quick, move_count)| BenchPosition {
+        name,
+        quick,
+        args: OrderAndChaosArgs {
+            moves: parse_moves::<String>(game)[..move_count].to_vec(),
+            position: None,
+            board: None,
+            to_move: None,
+        },
+    })
+    .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::move_score::window_results_testing;
+    use game_solver::game::GameState;
 
     fn from_string(string: &str) -> OrderAndChaos<6, 6, 5, 6> {
         let board_internal = string
@@ -464,4 +778,258 @@ mod tests {
 
         assert_eq!(diagonal_board.state(), GameState::Playable);
     }
+
+    #[test]
+    fn serde_round_trip() {
+        let mut game = OrderAndChaos::<6, 6, 5, 6>::new();
+        game.make_move(&"0-1-x".parse().unwrap()).unwrap();
+        game.make_move(&"4-2-o".parse().unwrap()).unwrap();
+
+        let json = serde_json::to_string(&game).unwrap();
+        assert_eq!(
+            serde_json::from_str::<OrderAndChaos<6, 6, 5, 6>>(&json).unwrap(),
+            game
+        );
+
+        assert_eq!(
+            serde_json::from_str::<OrderAndChaosMove>(r#""4-2-o""#).unwrap(),
+            OrderAndChaosMove(((4, 2), CellType::O))
+        );
+    }
+
+    #[test]
+    fn serde_rejects_malformed_boards() {
+        let err = serde_json::from_str::<OrderAndChaos<6, 6, 5, 6>>(r#"{"board":["------"]}"#)
+            .unwrap_err();
+
+        assert!(err.to_string().contains("6 rows"), "{err}");
+    }
+
+    #[test]
+    fn notation_round_trip() {
+        let game: OrderAndChaos<6, 6, 5, 6> = OrderAndChaosArgs {
+            moves: vec!["0-0-x".to_string(), "1-1-o".to_string()],
+            ..Default::default()
+        }
+        .try_into()
+        .unwrap();
+
+        let notation = "X...../.O..../....../....../....../......";
+        assert_eq!(game.to_notation(), notation);
+        assert_eq!(
+            OrderAndChaos::<6, 6, 5, 6>::from_notation(notation).unwrap(),
+            game
+        );
+        assert_eq!(
+            OrderAndChaos::<6, 6, 5, 6>::from_notation(&format!(" {notation}\n")).unwrap(),
+            game
+        );
+    }
+
+    #[test]
+    fn boards_agree_with_moves() {
+        let game: OrderAndChaos<6, 6, 5, 6> = OrderAndChaosArgs {
+            moves: ["0-0-x", "1-1-o", "5-2-o"].map(String::from).to_vec(),
+            ..Default::default()
+        }
+        .try_into()
+        .unwrap();
+
+        let board = "X.....\n.O....\n......\n......\n......\n..O...";
+        let from_board = OrderAndChaos::<6, 6, 5, 6>::from_board(board, None).unwrap();
+        assert_eq!(from_board, game);
+        assert_eq!(from_board.player(), PartizanPlayer::Right);
+
+        let error =
+            OrderAndChaos::<6, 6, 5, 6>::from_board(board, Some(PartizanPlayer::Left)).unwrap_err();
+        assert_eq!(
+            error.message(),
+            "it's Right's turn, as 3 squares are filled"
+        );
+        assert!(OrderAndChaos::<6, 6, 5, 6>::from_board("X...../.x....", None).is_err());
+    }
+
+    #[test]
+    fn notation_rejects_malformed_boards() {
+        let span = |notation: &str| {
+            OrderAndChaos::<6, 6, 5, 6>::from_notation(notation)
+                .unwrap_err()
+                .span()
+        };
+
+        assert_eq!(span("X...../.O..../....../....../......"), 0..34);
+        assert_eq!(span("X...../.O..../....../....../....../....."), 35..40);
+        assert_eq!(span("X...../.o..../....../....../....../......"), 8..9);
+        assert_eq!(span("X...../.O..../....../....../....../...... X"), 42..43);
+    }
+
+    #[test]
+    fn windows_agree_with_the_score() {
+        // 8 empty squares and no lines, small enough to solve to the end
+        let start = from_string(
+            ".XO.XX
+OO.XOO
+XXOO.X
+O.XXOO
+XXO.XX
+OO.XO.",
+        );
+        window_results_testing(&start, 6, 30);
+    }
+
+    #[test]
+    fn win_anti_diagonal() {
+        let diagonal_board = from_string(
+            "......\
+        ....X.\
+        ...X..\
+        ..X...\
+        .X....\
+        X.....",
+        );
+
+        assert_eq!(diagonal_board.state(), GameState::Win(PartizanPlayer::Left));
+    }
+
+    #[test]
+    fn win_after_a_shorter_run() {
+        // the run of O's is cut short at the start of the row, but not after it
+        let horizontal_board = from_string(
+            "OXXXXX\
+        ......\
+        ......\
+        ......\
+        ......\
+        ......",
+        );
+
+        assert_eq!(
+            horizontal_board.state(),
+            GameState::Win(PartizanPlayer::Left)
+        );
+    }
+
+    type Small = OrderAndChaos<4, 4, 4, 4>;
+
+    fn small(cells: &[Option<CellType>; 16]) -> Small {
+        OrderAndChaos {
+            board: Array2D::from_row_major(cells, 4, 4).unwrap(),
+            move_count: cells.iter().filter(|cell| cell.is_some()).count(),
+        }
+    }
+
+    /// The 10 lines of a 4x4 board, as the indices of their cells.
+    fn small_lines() -> Vec<[usize; 4]> {
+        let mut lines = Vec::new();
+        for i in 0..4 {
+            lines.push([i * 4, i * 4 + 1, i * 4 + 2, i * 4 + 3]);
+            lines.push([i, i + 4, i + 8, i + 12]);
+        }
+        lines.push([0, 5, 10, 15]);
+        lines.push([3, 6, 9, 12]);
+        lines
+    }
+
+    /// Whether Order wins from `cells` by brute force, written out from the rules:
+    /// Order wins once a line holds four of the same piece, and Chaos once the board is full without one,
+    /// whoever moved last.
+    fn order_wins(cells: &mut [Option<CellType>; 16], order_to_move: bool) -> bool {
+        let lined_up = small_lines().iter().any(|line| {
+            cells[line[0]].is_some() && line.iter().all(|&i| cells[i] == cells[line[0]])
+        });
+        if lined_up {
+            return true;
+        }
+
+        let empty = (0..16).filter(|&i| cells[i].is_none()).collect::<Vec<_>>();
+        if empty.is_empty() {
+            return false;
+        }
+
+        let mut wins = empty
+            .iter()
+            .flat_map(|&i| [(i, CellType::X), (i, CellType::O)])
+            .map(|(i, cell)| {
+                cells[i] = Some(cell);
+                let wins = order_wins(cells, !order_to_move);
+                cells[i] = None;
+                wins
+            });
+
+        if order_to_move {
+            wins.any(|wins| wins)
+        } else {
+            wins.all(|wins| wins)
+        }
+    }
+
+    #[test]
+    fn every_full_small_board_is_won_by_one_side() {
+        for filled in 0..1 << 16 {
+            let cells: [Option<CellType>; 16] = std::array::from_fn(|i| {
+                Some(if filled >> i & 1 == 1 {
+                    CellType::X
+                } else {
+                    CellType::O
+                })
+            });
+
+            let expected = if order_wins(&mut cells.clone(), true) {
+                PartizanPlayer::Left
+            } else {
+                PartizanPlayer::Right
+            };
+            assert_eq!(
+                small(&cells).state(),
+                GameState::Win(expected),
+                "{filled:016b}"
+            );
+        }
+    }
+
+    #[test]
+    fn small_endgames_solve_as_brute_force_does() {
+        use game_solver::{random::random_position, solve};
+        use rand::{rngs::StdRng, SeedableRng};
+        use std::collections::HashMap;
+
+        let mut rng = StdRng::seed_from_u64(189);
+        let mut solved = 0;
+
+        while solved < 40 {
+            // 6 empty squares or fewer, which brute force gets through
+            let Ok((position, _)) = random_position(&Small::new(), 10, &mut rng) else {
+                continue;
+            };
+            if position.state() != GameState::Playable {
+                continue;
+            }
+
+            let mut cells: [Option<CellType>; 16] =
+                std::array::from_fn(|i| position.board[(i / 4, i % 4)]);
+            let order_to_move = position.player() == PartizanPlayer::Left;
+            let order_wins = order_wins(&mut cells, order_to_move);
+
+            let score = solve(&position, &mut HashMap::new(), None, &None).unwrap();
+            assert_ne!(score, 0, "Order and Chaos can't be tied:\n{position}");
+            assert_eq!(score > 0, order_wins == order_to_move, "\n{position}");
+
+            solved += 1;
+        }
+    }
+
+    #[test]
+    fn resolvable_games_agree_with_making_every_move() {
+        use crate::util::resolve::tests::agrees_with_making_every_move;
+
+        // every position that isn't over, including ones with runs too long to win
+        assert_eq!(
+            agrees_with_making_every_move(OrderAndChaos::<3, 3, 3, 3>::new()),
+            11093
+        );
+        assert_eq!(
+            agrees_with_making_every_move(OrderAndChaos::<5, 2, 3, 3>::new()),
+            44037
+        );
+    }
 }
@@ -0,0 +1,186 @@
+//! A classic Reversi evaluator, for searching positions too far from the end to solve.
+
+use game_solver::{game::Game, player::Player, policy::Evaluator};
+
+use super::ReversiMove;
+
+use super::{Reversi, HEIGHT, WIDTH};
+
+/// The weights of [`ClassicEvaluator`], which can be tuned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassicParams {
+    /// The value of a disc on each square, by row: corners can't be flipped,
+    /// while the squares next to them give the corners away.
+    pub square_weights: [[isize; WIDTH]; HEIGHT],
+    /// The value of each move a player has over their opponent.
+    pub mobility: isize,
+    /// The cost of each disc next to an empty square (a frontier disc),
+    /// which hands the opponent moves.
+    pub frontier: isize,
+}
+
+impl Default for ClassicParams {
+    fn default() -> Self {
+        Self {
+            square_weights: [
+                [100, -20, 10, 10, -20, 100],
+                [-20, -50, -2, -2, -50, -20],
+                [10, -2, 1, 1, -2, 10],
+                [10, -2, 1, 1, -2, 10],
+                [-20, -50, -2, -2, -50, -20],
+                [100, -20, 10, 10, -20, 100],
+            ],
+            mobility: 5,
+            frontier: 3,
+        }
+    }
+}
+
+/// Scores a position by where each player's discs are, how many moves each player has,
+/// and how many of their discs are on the frontier, for the player to move.
+///
+/// Swapping the colors of every disc negates the score.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ClassicEvaluator {
+    pub params: ClassicParams,
+}
+
+impl ClassicEvaluator {
+    pub fn new(params: ClassicParams) -> Self {
+        Self { params }
+    }
+}
+
+impl Reversi {
+    /// Whether the square at `x`, `y` is next to an empty one.
+    fn on_frontier(&self, x: usize, y: usize) -> bool {
+        (-1..=1).any(|x_dir| {
+            (-1..=1).any(|y_dir| {
+                let neighbour = (x.wrapping_add_signed(x_dir), y.wrapping_add_signed(y_dir));
+                self.board.get(neighbour.0, neighbour.1) == Some(&None)
+            })
+        })
+    }
+}
+
+impl Evaluator<Reversi> for ClassicEvaluator {
+    fn evaluate(&self, game: &Reversi) -> isize {
+        let player = game.player();
+        let mut score = 0;
+
+        for y in 0..HEIGHT {
+            for x in 0..WIDTH {
+                match *game.board.get(x, y).unwrap() {
+                    None => {
+                        let square = ReversiMove([x, y]);
+                        if game.flips(&square, player).is_some() {
+                            score += self.params.mobility;
+                        }
+                        if game.flips(&square, player.next()).is_some() {
+                            score -= self.params.mobility;
+                        }
+                    }
+                    Some(disc) => {
+                        let sign = if disc == player { 1 } else { -1 };
+
+                        score += sign * self.params.square_weights[y][x];
+                        if game.on_frontier(x, y) {
+                            score -= sign * self.params.frontier;
+                        }
+                    }
+                }
+            }
+        }
+
+        score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{bench::parse_moves, notation::NotatedGame};
+    use game_solver::{
+        arena::{Arena, PolicyAgent, StrategyAgent},
+        move_scores_for,
+        policy::RandomPolicy,
+        strategy::{HeuristicSearch, Strategy},
+    };
+    use std::collections::HashMap;
+
+    use super::super::ReversiMove;
+
+    /// `game`, with the color of every disc swapped.
+    fn swap_colors(game: &Reversi) -> Reversi {
+        let mut swapped = game.clone();
+        for y in 0..HEIGHT {
+            for x in 0..WIDTH {
+                let disc = game.board.get(x, y).unwrap().map(|disc| disc.next());
+                swapped.board.set(x, y, disc).unwrap();
+            }
+        }
+
+        swapped
+    }
+
+    #[test]
+    fn swapping_colors_negates_the_evaluation() {
+        let evaluator = ClassicEvaluator::default();
+        let mut game = Reversi::new();
+
+        for m in parse_moves::<ReversiMove>(
+            "4-2 4-1 3-1 4-3 5-0 2-1 5-3 5-1 2-0 5-2 1-4 3-0 4-0 2-4 2-5 5-4 4-4 1-3 5-5 1-5",
+        ) {
+            assert_eq!(
+                evaluator.evaluate(&swap_colors(&game)),
+                -evaluator.evaluate(&game),
+                "{game}"
+            );
+
+            game.make_move(&m).unwrap();
+        }
+    }
+
+    #[test]
+    fn searching_beats_random_play() {
+        let game = Reversi::new();
+
+        let results = Arena::new(20, 3)
+            .run(
+                &game,
+                |_| {
+                    StrategyAgent(HeuristicSearch::new(
+                        ClassicEvaluator::default(),
+                        2,
+                        HashMap::new(),
+                    ))
+                },
+                |seed| PolicyAgent(RandomPolicy::new(seed)),
+            )
+            .unwrap();
+
+        assert!(results.wins >= 16, "{results:?}");
+    }
+
+    #[test]
+    fn quiescence_sees_corners_past_the_horizon() {
+        // 2-0 looks best to a plain depth-4 search, but loses, while taking the 5-5 corner holds the tie
+        let game = Reversi::from_notation(".X..OX/.OXOX./.OOXO./XOOOX./.OOX../.OXXX. O").unwrap();
+
+        let best_move = |quiescence| {
+            let mut search = HeuristicSearch::new(ClassicEvaluator::default(), 4, HashMap::new());
+            search.quiescence = quiescence;
+            search.evaluate(&game).unwrap().best_move.unwrap()
+        };
+        let plain = best_move(0);
+        let quiet = best_move(4);
+
+        assert_eq!(plain, ReversiMove([2, 0]));
+        assert_eq!(quiet, ReversiMove([5, 5]));
+
+        let scores = move_scores_for(&game, &mut HashMap::new(), &[plain, quiet], None, &None)
+            .map(|score| score.unwrap().1)
+            .collect::<Vec<_>>();
+        assert!(scores[0] < 0 && scores[1] == 0, "{scores:?}");
+    }
+}
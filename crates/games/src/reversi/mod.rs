@@ -1,35 +1,109 @@
 #![doc = include_str!("./README.md")]
 
+pub mod evaluator;
 #[cfg(feature = "egui")]
 pub mod gui;
+pub mod sgf;
 
-use anyhow::Error;
+use anyhow::{anyhow, Error};
 use array2d::Array2D;
 use clap::Args;
+use evaluator::ClassicEvaluator;
 use game_solver::{
-    game::{Game, GameState},
-    player::{PartizanPlayer, Player},
+    hint::{Effort, Hints},
+    ordering::PlacementGame,
+    policy::Evaluator,
+    prelude::*,
 };
-use serde::{Deserialize, Serialize};
+use serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt::{self, Debug};
 use std::hash::Hash;
-
-use crate::util::{cli::move_failable, move_natural::NaturalMove};
+use std::str::FromStr;
+use std::{fs, path::PathBuf, time::Duration};
+
+use crate::util::metadata::GameMetadata;
+use crate::util::{
+    bench::{parse_moves, BenchPosition},
+    cli::{
+        evaluate::{Eval, Heuristics},
+        outcome::OutcomeClasses,
+        replay_moves, Endings,
+    },
+    notation::{fields, NotatedGame, NotationError},
+    parse::{column_name, parse_coord, ParseError, ParseMove},
+    position::{parse_board, PositionError},
+    render::{Cell, Grid, Render},
+    scan::{scan, Scan},
+    symmetry::Symmetries,
+};
 
 pub const WIDTH: usize = 6;
 pub const HEIGHT: usize = 6;
 
-pub type ReversiMove = NaturalMove<2>;
+/// The square a disc is placed on, as `[x, y]` from the top left corner.
+///
+/// It's written algebraically, with the column as a letter and the row counted from 1 (e.g. `c4` for `[2, 3]`),
+/// as Othello is, and read either that way or as `x-y` (e.g. `2-3`), counting both from 0.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ReversiMove(pub [usize; 2]);
+
+impl FromStr for ReversiMove {
+    type Err = ParseError;
+
+    /// Reads a square on the board, rejecting any outside of it.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_coord(s, WIDTH, HEIGHT).map(|(x, y)| ReversiMove([x, y]))
+    }
+}
+
+impl fmt::Display for ReversiMove {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [x, y] = self.0;
+        write!(f, "{}{}", column_name(x), y + 1)
+    }
+}
 
-#[derive(Clone, Hash, Eq, PartialEq)]
+/// Serializes as the same algebraic notation the move is displayed in.
+impl Serialize for ReversiMove {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ReversiMove {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Parses a Reversi move, either algebraically (e.g. `c4`) or as `x-y`.
+pub fn parse_reversi_move(s: &str) -> Result<ReversiMove, ParseError> {
+    s.parse()
+}
+
+#[derive(Clone, Hash, Eq, PartialEq, Deserialize)]
+#[serde(try_from = "ReversiPosition")]
 pub struct Reversi {
     /// None if empty, Some(Player) if occupied
     board: Array2D<Option<PartizanPlayer>>,
     move_count: usize,
+    /// Whether this is Anti-Reversi, where the player with the fewest discs at the end wins.
+    /// Part of the position, so the scores of the two games never share a transposition table entry.
+    misere: bool,
+}
+
+/// The game the CLI plays without arguments, from [the usual opening](Reversi::new).
+impl Default for Reversi {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Reversi {
-    fn new() -> Self {
+    /// The usual opening: two discs of each player crossed in the center of the board, where Left moves first.
+    pub fn new() -> Self {
         let mut board = Array2D::filled_with(None, WIDTH, HEIGHT);
 
         // set middle squares to occupied:
@@ -49,21 +123,36 @@ impl Reversi {
         Self {
             board,
             move_count: 0,
+            misere: false,
         }
     }
 
+    /// Whether this is Anti-Reversi, where the player with the fewest discs at the end wins.
+    pub fn is_misere(&self) -> bool {
+        self.misere
+    }
+
     fn on_board(&self, x: usize, y: usize) -> bool {
         x < WIDTH && y < HEIGHT
     }
 
     fn is_valid_move(&self, m: &<Self as Game>::Move) -> Option<Vec<<Self as Game>::Move>> {
+        self.flips(m, self.player())
+    }
+
+    /// The discs `player` would flip by placing a disc at `m`, or None if they can't place one there.
+    fn flips(
+        &self,
+        m: &<Self as Game>::Move,
+        player: PartizanPlayer,
+    ) -> Option<Vec<<Self as Game>::Move>> {
         let cell = *self.board.get(m.0[0], m.0[1]).unwrap();
 
         if cell.is_some() {
             return None;
         }
 
-        let opposing_tile = self.player().next();
+        let opposing_tile = player.next();
 
         let mut tiles_to_flip = Vec::new();
 
@@ -105,7 +194,7 @@ impl Reversi {
                 continue;
             }
 
-            if self.board.get(x, y) == Some(&Some(self.player())) {
+            if self.board.get(x, y) == Some(&Some(player)) {
                 loop {
                     x = x.checked_add_signed(-*x_dir).unwrap();
                     y = y.checked_add_signed(-*y_dir).unwrap();
@@ -114,7 +203,7 @@ impl Reversi {
                         break;
                     }
 
-                    tiles_to_flip.push(NaturalMove([x, y]));
+                    tiles_to_flip.push(ReversiMove([x, y]));
                 }
             }
         }
@@ -129,7 +218,7 @@ impl Reversi {
 
 impl Game for Reversi {
     type Move = ReversiMove;
-    type Iter<'a> = std::vec::IntoIter<Self::Move>;
+    type Iter<'a> = Scan<'a, Self, Self::Move>;
     type Player = PartizanPlayer;
     type MoveError = array2d::Error;
 
@@ -156,20 +245,30 @@ impl Game for Reversi {
     }
 
     fn possible_moves(&self) -> Self::Iter<'_> {
-        let mut moves = Vec::new();
-        for x in 0..WIDTH {
-            for y in 0..HEIGHT {
-                if self.is_valid_move(&NaturalMove([x, y])).is_some() {
-                    moves.push(NaturalMove([x, y]));
-                }
-            }
-        }
-        moves.into_iter()
+        // column by column
+        scan(self, WIDTH * HEIGHT, |(game, index)| {
+            let m = ReversiMove([index / HEIGHT, index % HEIGHT]);
+            game.is_valid_move(&m).map(|_| m)
+        })
     }
 
-    fn state(&self) -> GameState<Self::Player> {
-        if self.possible_moves().len() > 0 {
-            return GameState::Playable;
+    /// Taking a corner, as a corner disc can never be flipped back.
+    fn noisy_moves(&self) -> Vec<Self::Move> {
+        [
+            (0, 0),
+            (WIDTH - 1, 0),
+            (0, HEIGHT - 1),
+            (WIDTH - 1, HEIGHT - 1),
+        ]
+        .into_iter()
+        .map(|(x, y)| ReversiMove([x, y]))
+        .filter(|corner| self.is_valid_move(corner).is_some())
+        .collect()
+    }
+
+    fn status(&self) -> GameStatus {
+        if self.has_moves() {
+            return GameStatus::InProgress;
         }
 
         let mut player_one_count = 0;
@@ -185,19 +284,32 @@ impl Game for Reversi {
             }
         }
 
+        // the moves are the same either way, only who the discs count for changes
+        let (player_one_count, player_two_count) = if self.misere {
+            (player_two_count, player_one_count)
+        } else {
+            (player_one_count, player_two_count)
+        };
+
         match player_one_count.cmp(&player_two_count) {
-            std::cmp::Ordering::Greater => GameState::Win(PartizanPlayer::Left),
-            std::cmp::Ordering::Less => GameState::Win(PartizanPlayer::Right),
-            std::cmp::Ordering::Equal => GameState::Tie,
+            std::cmp::Ordering::Greater => GameStatus::won_by(self, PartizanPlayer::Left),
+            std::cmp::Ordering::Less => GameStatus::won_by(self, PartizanPlayer::Right),
+            std::cmp::Ordering::Equal => GameStatus::TIE,
         }
     }
 
     fn player(&self) -> PartizanPlayer {
-        if self.move_count % 2 == 0 {
-            PartizanPlayer::Left
-        } else {
-            PartizanPlayer::Right
-        }
+        PartizanPlayer::from_move_count(self.move_count)
+    }
+}
+
+impl PlacementGame for Reversi {
+    fn grid_size(&self) -> Vec<usize> {
+        vec![HEIGHT, WIDTH]
+    }
+
+    fn cell(&self, m: &Self::Move) -> Vec<usize> {
+        vec![m.0[1], m.0[0]]
     }
 }
 
@@ -211,24 +323,194 @@ fn player_to_char(player: Option<PartizanPlayer>) -> char {
 
 impl fmt::Display for Reversi {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "Current player: {}", player_to_char(Some(self.player())))?;
+        self.board_grid().write_plain(f)
+    }
+}
 
+impl Reversi {
+    /// The board as a grid, noting whose turn it is,
+    /// where the squares the player to move can place a disc on are written as `*` on the plain board.
+    fn board_grid(&self) -> Grid {
         let moves = self.possible_moves().collect::<Vec<_>>();
 
-        for y in 0..HEIGHT {
-            for x in 0..WIDTH {
-                let character = if moves.contains(&NaturalMove([x, y])) {
-                    '*'
-                } else {
-                    player_to_char(*self.board.get(x, y).unwrap())
+        let rows = (0..HEIGHT)
+            .map(|y| {
+                (0..WIDTH)
+                    .map(|x| match *self.board.get(x, y).unwrap() {
+                        Some(player) => Cell::piece(player_to_char(Some(player)), player),
+                        None if moves.contains(&ReversiMove([x, y])) => Cell::empty('*').legal(),
+                        None => Cell::empty(player_to_char(None)),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut grid = Grid::new(rows);
+        if self.misere {
+            grid = grid.note("Anti-Reversi: the fewest discs win");
+        }
+        grid.note(format!(
+            "Current player: {}",
+            player_to_char(Some(self.player()))
+        ))
+    }
+}
+
+impl Render for Reversi {
+    fn grid(&self) -> Option<Grid> {
+        Some(self.board_grid())
+    }
+
+    fn move_cells(&self, m: &Self::Move) -> Vec<(usize, usize)> {
+        let [x, y] = m.0;
+        vec![(y, x)]
+    }
+}
+
+impl Reversi {
+    /// The rows of the board, where empty squares are marked with `empty`.
+    fn rows(&self, empty: char) -> Vec<String> {
+        (0..HEIGHT)
+            .map(|y| {
+                (0..WIDTH)
+                    .map(|x| match *self.board.get(x, y).unwrap() {
+                        None => empty,
+                        disc => player_to_char(disc),
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Serializes the board as its rows, using the same characters as [`Display`]
+/// (without marking the possible moves). Anti-Reversi is marked with `"misere": true`.
+impl Serialize for Reversi {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Reversi", 3)?;
+        state.serialize_field("board", &self.rows('-'))?;
+        state.serialize_field("move_count", &self.move_count)?;
+        if self.misere {
+            state.serialize_field("misere", &true)?;
+        } else {
+            state.skip_field("misere")?;
+        }
+        state.end()
+    }
+}
+
+/// A serialized [`Reversi`], before it's checked.
+#[derive(Deserialize)]
+struct ReversiPosition {
+    board: Vec<String>,
+    move_count: usize,
+    #[serde(default)]
+    misere: bool,
+}
+
+impl TryFrom<ReversiPosition> for Reversi {
+    type Error = PositionError;
+
+    fn try_from(position: ReversiPosition) -> Result<Self, Self::Error> {
+        let rows = parse_board(&position.board, WIDTH, HEIGHT, "XO-")?;
+
+        let mut board = Array2D::filled_with(None, WIDTH, HEIGHT);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, &square) in row.iter().enumerate() {
+                let disc = match square {
+                    'X' => Some(PartizanPlayer::Left),
+                    'O' => Some(PartizanPlayer::Right),
+                    _ => None,
                 };
+                board.set(x, y, disc).unwrap();
+            }
+        }
 
-                write!(f, "{}", character)?;
+        // the game starts with the 4 center squares filled, and discs are flipped but never removed
+        for (x, y) in [
+            (WIDTH / 2 - 1, HEIGHT / 2 - 1),
+            (WIDTH / 2, HEIGHT / 2 - 1),
+            (WIDTH / 2 - 1, HEIGHT / 2),
+            (WIDTH / 2, HEIGHT / 2),
+        ] {
+            if board.get(x, y).unwrap().is_none() {
+                return Err(PositionError::impossible(format!(
+                    "the center square {x}-{y} is empty, but it starts with a disc"
+                )));
             }
-            writeln!(f)?;
         }
 
-        Ok(())
+        // every move places exactly one disc
+        let discs = board.elements_row_major_iter().flatten().count();
+        if discs != 4 + position.move_count {
+            return Err(PositionError::impossible(format!(
+                "there are {discs} discs, but after {} moves there should be {}",
+                position.move_count,
+                4 + position.move_count
+            )));
+        }
+
+        Ok(Self {
+            board,
+            move_count: position.move_count,
+            misere: position.misere,
+        })
+    }
+}
+
+/// Written as the rows of the board, separated by `/`, where empty squares are marked with `.`,
+/// followed by the player to move, e.g. `....../....../..XO../..OX../....../...... X`,
+/// and `misere` for Anti-Reversi.
+impl NotatedGame for Reversi {
+    fn to_notation(&self) -> String {
+        format!(
+            "{} {}{}",
+            self.rows('.').join("/"),
+            player_to_char(Some(self.player())),
+            if self.misere { " misere" } else { "" }
+        )
+    }
+
+    fn from_notation(notation: &str) -> Result<Self, NotationError> {
+        let fields = fields(notation, &["board", "player to move"], &["variant"])?;
+        let rows = fields[0].board("XO.", Some((WIDTH, HEIGHT)))?;
+
+        // there are no passes, so every disc past the first 4 was a move
+        let discs = rows
+            .iter()
+            .flatten()
+            .filter(|&&square| square != '.')
+            .count();
+        let game = Self::try_from(ReversiPosition {
+            board: rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|&square| if square == '.' { '-' } else { square })
+                        .collect()
+                })
+                .collect(),
+            move_count: discs.saturating_sub(4),
+            misere: match fields.get(2) {
+                Some(variant) => variant.choice("variant", &[("misere", true)])?,
+                None => false,
+            },
+        })
+        .map_err(|err| fields[0].error(err.to_string()))?;
+
+        let player = fields[1].choice(
+            "player to move",
+            &[("X", PartizanPlayer::Left), ("O", PartizanPlayer::Right)],
+        )?;
+        if player != game.player() {
+            return Err(fields[1].error(format!(
+                "it's {}'s turn, as {} moves were made",
+                player_to_char(Some(game.player())),
+                game.move_count
+            )));
+        }
+
+        Ok(game)
     }
 }
 
@@ -238,27 +520,408 @@ impl Debug for Reversi {
     }
 }
 
+/// What Reversi is, and how it's set up from the CLI.
+pub const METADATA: GameMetadata = GameMetadata {
+    name: "Reversi",
+    summary: "Outflank your opponent's discs to flip them to your color, on a 6x6 board.",
+    rules: include_str!("./README.md"),
+    parameters: &[],
+    move_notation: "x-y, zero-indexed, or algebraically (e.g. 4-2, or e3)",
+    example: "reversi 4-2 4-3",
+};
+
 /// Analyzes Reversi.
 ///
 #[doc = include_str!("./README.md")]
 #[derive(Args, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone, Default)]
 pub struct ReversiArgs {
-    /// Reversi moves, ordered as x1-y1 x2-y2 ...
-    #[arg(value_parser = clap::value_parser!(ReversiMove))]
+    /// Reversi moves, written algebraically as c4 d3 ... (or as x1-y1 x2-y2 ..., counting from 0)
+    #[arg(value_parser = parse_reversi_move)]
     moves: Vec<ReversiMove>,
+    /// The position to analyze, in its notation
+    /// (e.g. `....../....../..XO../..OX../....../...... X`), instead of the moves.
+    #[arg(long, conflicts_with = "moves")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<String>,
+    /// Read the game from an SGF file, playing its main line, instead of the moves.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["moves", "position"])]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sgf: Option<PathBuf>,
+    /// Write the position to an SGF file, as a setup of its discs.
+    #[arg(long, value_name = "FILE")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    export_sgf: Option<PathBuf>,
+    /// Play Anti-Reversi, where the player with the fewest discs at the end wins, with the same moves.
+    #[arg(long)]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    misere: bool,
+}
+
+impl ParseMove for Reversi {
+    fn parse_move(&self, token: &str) -> Result<Self::Move, ParseError> {
+        parse_reversi_move(token)
+    }
 }
 
+impl Heuristics for Reversi {
+    fn evaluator(kind: Eval) -> Option<Box<dyn Evaluator<Self>>> {
+        match kind {
+            Eval::Classic => Some(Box::new(ClassicEvaluator::default())),
+        }
+    }
+}
+
+impl Hints for Reversi {
+    fn hint_evaluator() -> Option<Box<dyn Evaluator<Self>>> {
+        Some(Box::new(ClassicEvaluator::default()))
+    }
+
+    /// The opening is far from the end, so a quick hint gets longer to search deeper with the evaluator.
+    fn hint_budget(effort: Effort) -> Option<Duration> {
+        match effort {
+            Effort::Instant => Some(Duration::from_millis(10)),
+            Effort::Quick => Some(Duration::from_secs(1)),
+            Effort::Exact => None,
+        }
+    }
+}
+
+impl Endings for Reversi {}
+
+impl OutcomeClasses for Reversi {}
+
+impl Symmetries for Reversi {}
+
 impl TryFrom<ReversiArgs> for Reversi {
     type Error = Error;
 
     fn try_from(value: ReversiArgs) -> Result<Self, Self::Error> {
-        let mut game = Reversi::new();
+        let mut game = if let Some(position) = value.position {
+            Self::from_notation(&position)?
+        } else if let Some(path) = value.sgf {
+            let sgf = fs::read_to_string(&path)
+                .map_err(|err| anyhow!("Could not read {}: {err}", path.display()))?;
+
+            Self::from_sgf(&sgf).map_err(|err| anyhow!("{}, {err}", path.display()))?
+        } else {
+            let mut game = Reversi::new();
 
-        // parse every move in args, e.g. 0-0 1-1 in args
-        for game_move in value.moves {
-            move_failable(&mut game, &game_move)?;
+            // play every move in args, e.g. 0-0 1-1 in args
+            replay_moves(&mut game, &value.moves)?;
+
+            game
+        };
+
+        // a position written as Anti-Reversi stays so without the flag
+        game.misere |= value.misere;
+
+        if let Some(path) = value.export_sgf {
+            fs::write(&path, game.to_sgf() + "\n")
+                .map_err(|err| anyhow!("Could not write {}: {err}", path.display()))?;
         }
 
         Ok(game)
     }
 }
+
+/// Positions for the `bench` subcommand. The empty board is out of reach,
+/// so these are taken from the same game at different stages.
+pub fn bench_positions() -> Vec<BenchPosition<ReversiArgs>> {
+    let game = "4-2 4-1 3-1 4-3 5-0 2-1 5-3 5-1 2-0 5-2 1-4 3-0 4-0 2-4 2-5 5-4 \
+                4-4 1-3 5-5 1-5 1-1 4-5 0-5 0-4 3-5 1-0 0-3 3-4 1-2 0-1 0-2 0-0";
+
+    [
+        ("12 empty squares", true, 20),
+        ("16 empty squares", false, 16),
+    ]
+    .into_iter()
+    .map(|(name, quick, move_count)| BenchPosition {
+        name,
+        quick,
+        args: ReversiArgs {
+            moves: parse_moves::<ReversiMove>(game)[..move_count].to_vec(),
+            position: None,
+            sgf: None,
+            export_sgf: None,
+            misere: false,
+        },
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::move_score::window_results_testing;
+    use game_solver::{
+        iterative_deepening, perft, solve, solve_depth_limited, solve_depth_limited_with_table,
+    };
+    use std::collections::HashMap;
+    #[cfg(feature = "rayon")]
+    use {
+        game_solver::{think::think, transposition::DepthCache},
+        std::{
+            hash::RandomState,
+            sync::Arc,
+            time::{Duration, Instant},
+        },
+    };
+
+    #[test]
+    fn perft_counts() {
+        let game = Reversi::new();
+
+        // the 6x6 board has no passes this early, so these match standard 6x6 Othello.
+        let counts = [1, 4, 12, 56, 244, 1364, 7604, 47740];
+
+        for (depth, count) in counts.into_iter().enumerate() {
+            assert_eq!(perft(&game, depth), count, "perft({depth})");
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn thinking_briefly_still_finds_a_legal_move() {
+        let game = Reversi::new();
+        let table = Arc::new(DepthCache::<_, RandomState>::with_capacity(1 << 16));
+
+        let start = Instant::now();
+        let thought = think(&game, &table, start + Duration::from_millis(10))
+            .unwrap()
+            .unwrap();
+
+        assert!(
+            start.elapsed() < Duration::from_secs(1),
+            "{:?}",
+            start.elapsed()
+        );
+        assert!(game.possible_moves().any(|m| m == thought.best_move));
+        assert!(!thought.complete);
+    }
+
+    #[test]
+    fn iterative_deepening_matches_a_fresh_search() {
+        let opening = Reversi::new();
+        let mut endgame = Reversi::new();
+        for m in parse_moves::<ReversiMove>(
+            "4-2 4-1 3-1 4-3 5-0 2-1 5-3 5-1 2-0 5-2 1-4 3-0 4-0 2-4 2-5 5-4 4-4 1-3 5-5 1-5 1-1 4-5",
+        ) {
+            endgame.make_move(&m).unwrap();
+        }
+
+        for game in [opening, endgame] {
+            let mut table = HashMap::new();
+            let deepened = iterative_deepening(&game, 6, &mut table, &None).unwrap();
+
+            // the table is full of shallower scores, which must not stand in for deeper ones
+            assert!(table.values().any(|entry| entry.depth < 6));
+            assert_eq!(
+                deepened,
+                solve_depth_limited_with_table(&game, 6, &mut HashMap::new(), &None).unwrap()
+            );
+            assert_eq!(deepened, solve_depth_limited(&game, 6, &None).unwrap());
+        }
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let mut game = Reversi::new();
+        for _ in 0..3 {
+            let m = game.possible_moves().next().unwrap();
+            game.make_move(&m).unwrap();
+        }
+
+        let json = serde_json::to_string(&game).unwrap();
+        assert!(json.ends_with(r#""move_count":3}"#), "{json}");
+        assert_eq!(serde_json::from_str::<Reversi>(&json).unwrap(), game);
+    }
+
+    #[test]
+    fn serde_rejects_impossible_positions() {
+        let error = |board: &str, move_count: usize| {
+            serde_json::from_str::<Reversi>(&format!(
+                r#"{{"board":{board},"move_count":{move_count}}}"#
+            ))
+            .unwrap_err()
+            .to_string()
+        };
+
+        let start = r#"["------","------","--XO--","--OX--","------","------"]"#;
+        assert!(error(start, 1).contains("there are 4 discs"));
+
+        let empty_center = r#"["------","------","--X---","--OX--","------","------"]"#;
+        assert!(error(empty_center, 0).contains("center square"));
+    }
+
+    #[test]
+    fn notation_round_trip() {
+        let mut game = Reversi::new();
+        assert_eq!(
+            game.to_notation(),
+            "....../....../..XO../..OX../....../...... X"
+        );
+
+        for _ in 0..3 {
+            assert_eq!(Reversi::from_notation(&game.to_notation()).unwrap(), game);
+
+            let m = game.possible_moves().next().unwrap();
+            game.make_move(&m).unwrap();
+        }
+
+        assert!(game.to_notation().ends_with(" O"));
+    }
+
+    #[test]
+    fn misere_round_trips_and_is_labelled() {
+        let mut game = Reversi::new();
+        game.misere = true;
+        game.make_move(&ReversiMove([4, 2])).unwrap();
+
+        let notation = game.to_notation();
+        assert!(notation.ends_with(" O misere"), "{notation}");
+        assert_eq!(Reversi::from_notation(&notation).unwrap(), game);
+
+        let json = serde_json::to_string(&game).unwrap();
+        assert!(json.ends_with(r#""misere":true}"#), "{json}");
+        assert_eq!(serde_json::from_str::<Reversi>(&json).unwrap(), game);
+
+        assert!(game
+            .to_string()
+            .starts_with("Anti-Reversi: the fewest discs win\n"));
+
+        // the same discs are a different position
+        let mut standard = game.clone();
+        standard.misere = false;
+        assert_ne!(standard, game);
+        assert_eq!(
+            Reversi::from_notation(&standard.to_notation()).unwrap(),
+            standard
+        );
+    }
+
+    #[test]
+    fn misere_is_won_with_the_fewest_discs() {
+        let full = ["XXXXXX"; HEIGHT].join("/") + " X";
+
+        let standard = Reversi::from_notation(&full).unwrap();
+        assert_eq!(standard.state(), GameState::Win(PartizanPlayer::Left));
+
+        let misere = Reversi::from_notation(&(full + " misere")).unwrap();
+        assert!(misere.is_misere());
+        assert_eq!(misere.state(), GameState::Win(PartizanPlayer::Right));
+    }
+
+    #[test]
+    fn misere_solves_exactly() {
+        let solve = |notation: &str| {
+            solve(
+                &Reversi::from_notation(notation).unwrap(),
+                &mut HashMap::new(),
+                None,
+                &None,
+            )
+            .unwrap()
+        };
+
+        // X loses the standard game, but can hold Anti-Reversi to a tie
+        let endgame = ".....O/.OXXOO/XOOXOO/OOOOOX/XOOOO./XXXXX. X";
+        assert_eq!(solve(endgame), -5);
+        assert_eq!(solve(&format!("{endgame} misere")), 0);
+
+        // X wins both games, with different moves, so one score isn't the other negated
+        let midgame = ".O...O/OOOXOO/OOXOXO/OOXXOX/.O.XXO/XXXX.. X";
+        let standard = solve(midgame);
+        let misere = solve(&format!("{midgame} misere"));
+        assert_eq!((standard, misere), (6, 6));
+        assert_ne!(standard, -misere);
+    }
+
+    #[test]
+    fn notation_rejects_invalid_positions() {
+        let span = |notation: &str| Reversi::from_notation(notation).unwrap_err().span();
+
+        // it's X's turn
+        assert_eq!(span("....../....../..XO../..OX../....../...... O"), 42..43);
+        // an unknown variant
+        assert_eq!(
+            span("....../....../..XO../..OX../....../...... X anti"),
+            44..48
+        );
+        // an empty center square
+        assert_eq!(span("....../....../..X.../..OX../....../...... O"), 0..41);
+        // 5 rows
+        assert_eq!(span("....../..XO../..OX../....../...... X"), 0..34);
+        // a long row
+        assert_eq!(span("....../....../..XO.../..OX../....../...... X"), 14..21);
+        assert_eq!(span("....../....../..XO../..0X../....../...... X"), 23..24);
+    }
+
+    #[test]
+    fn moves_are_read_algebraically_or_numerically() {
+        assert_eq!("c4".parse(), Ok(ReversiMove([2, 3])));
+        assert_eq!("C4".parse(), Ok(ReversiMove([2, 3])));
+        assert_eq!("2-3".parse(), Ok(ReversiMove([2, 3])));
+
+        let game = Reversi::new();
+        let moves = ["d2", "e3", "b4", "c5"].map(|m| m.parse::<ReversiMove>().unwrap());
+        assert!(game.possible_moves().all(|m| moves.contains(&m)));
+        assert_eq!(game.possible_moves().count(), moves.len());
+    }
+
+    #[test]
+    fn moves_on_the_edge_of_the_board_are_read() {
+        for (token, square) in [
+            ("a1", [0, 0]),
+            ("f1", [5, 0]),
+            ("a6", [0, 5]),
+            ("F6", [5, 5]),
+            ("0-0", [0, 0]),
+            ("5-5", [5, 5]),
+        ] {
+            assert_eq!(token.parse(), Ok(ReversiMove(square)), "{token}");
+        }
+    }
+
+    #[test]
+    fn moves_off_the_board_say_where_it_ends() {
+        for token in ["g1", "a7", "6-0", "0-6", "8-8"] {
+            assert!(
+                matches!(
+                    token.parse::<ReversiMove>(),
+                    Err(ParseError::OutOfRange { .. })
+                ),
+                "{token}"
+            );
+        }
+        // rows are counted from 1
+        assert!(matches!(
+            "a0".parse::<ReversiMove>(),
+            Err(ParseError::Malformed { .. })
+        ));
+
+        let err = "8-8".parse::<ReversiMove>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`8-8` is out of range: expected a square on a 6x6 board (`a1` to `f6`, or `0-0` to `5-5`)"
+        );
+    }
+
+    #[test]
+    fn every_square_reads_back_as_it_is_written() {
+        for x in 0..WIDTH {
+            for y in 0..HEIGHT {
+                let m = ReversiMove([x, y]);
+                assert_eq!(m.to_string().parse(), Ok(m));
+                assert_eq!(format!("{x}-{y}").parse(), Ok(m));
+            }
+        }
+
+        assert_eq!(ReversiMove([2, 3]).to_string(), "c4");
+    }
+
+    #[test]
+    fn windows_agree_with_the_score() {
+        let start = Reversi::from_notation(".....O/.OXXOO/XOOXOO/OOOOOX/XOOOO./XXXXX. X").unwrap();
+        window_results_testing(&start, 4, 20);
+    }
+}
@@ -0,0 +1,550 @@
+//! Reading and writing Reversi games in SGF, the Smart Game Format, where Othello is `GM[2]`.
+//!
+//! Only the properties Othello needs are understood:
+//!
+//! - `GM` and `SZ`, which must describe Othello on this board,
+//! - `B` and `W` for the moves of Black (`X`) and White (`O`), where `B[]` or `B[tt]` is a pass,
+//! - `AB`, `AW`, and `AE` in the first node, to set up discs, along with `PL` for the player to move.
+//!
+//! Any other property, such as the comments in `C`, is skipped, and only the main line
+//! (the first variation of every branch) is read.
+
+use array2d::Array2D;
+use game_solver::{
+    game::{Game, GameState},
+    player::PartizanPlayer,
+};
+use thiserror::Error;
+
+use super::ReversiMove;
+use super::{player_to_char, Reversi, ReversiPosition, HEIGHT, WIDTH};
+
+/// An error in an SGF file, at the line and column (both starting at 1) where it was found.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("line {line}, column {column}: {message}")]
+pub struct SgfError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+/// A property of a node, such as `B[cd]`, with the byte offset of its identifier.
+struct Property {
+    offset: usize,
+    ident: String,
+    values: Vec<String>,
+}
+
+type Node = Vec<Property>;
+
+/// A recursive descent parser for SGF's game trees.
+struct Parser<'a> {
+    text: &'a str,
+    offset: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Self { text, offset: 0 }
+    }
+
+    /// Creates an error at the byte `offset` of the file.
+    fn error(&self, offset: usize, message: impl Into<String>) -> SgfError {
+        let before = &self.text[..offset];
+        let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+
+        SgfError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: message.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.text[self.offset..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(ch) = self.peek().filter(|ch| ch.is_whitespace()) {
+            self.offset += ch.len_utf8();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), SgfError> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some(ch) if ch == expected => {
+                self.offset += ch.len_utf8();
+                Ok(())
+            }
+            Some(ch) => Err(self.error(
+                self.offset,
+                format!("expected `{expected}`, but found `{ch}`"),
+            )),
+            None => Err(self.error(
+                self.offset,
+                format!("expected `{expected}`, but the file ended"),
+            )),
+        }
+    }
+
+    /// Parses the first game of the file, returning the nodes of its main line.
+    /// Anything before it (such as an email header) or after it (such as other games) is ignored.
+    fn first_game(&mut self) -> Result<Vec<Node>, SgfError> {
+        match self.text.find('(') {
+            Some(start) => self.offset = start,
+            None => return Err(self.error(self.text.len(), "expected a game, starting with `(`")),
+        }
+
+        self.game_tree()
+    }
+
+    /// Parses a game tree, returning its own nodes followed by those of its first variation.
+    fn game_tree(&mut self) -> Result<Vec<Node>, SgfError> {
+        self.expect('(')?;
+
+        let mut nodes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some(';') {
+                break;
+            }
+
+            self.offset += 1;
+            nodes.push(self.node()?);
+        }
+
+        if nodes.is_empty() {
+            return Err(self.error(self.offset, "expected a node, starting with `;`"));
+        }
+
+        let mut main_line = true;
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('(') {
+                break;
+            }
+
+            // other variations are parsed to find where they end, but aren't played
+            let variation = self.game_tree()?;
+            if main_line {
+                nodes.extend(variation);
+                main_line = false;
+            }
+        }
+
+        self.expect(')')?;
+
+        Ok(nodes)
+    }
+
+    fn node(&mut self) -> Result<Node, SgfError> {
+        let mut properties = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+
+            let offset = self.offset;
+            let ident = self.text[offset..]
+                .chars()
+                .take_while(char::is_ascii_alphabetic)
+                .collect::<String>();
+
+            if ident.is_empty() {
+                return Ok(properties);
+            }
+
+            self.offset += ident.len();
+
+            let mut values = Vec::new();
+            loop {
+                self.skip_whitespace();
+                if self.peek() != Some('[') {
+                    break;
+                }
+
+                values.push(self.value()?);
+            }
+
+            if values.is_empty() {
+                return Err(self.error(
+                    self.offset,
+                    format!("expected a value for `{ident}`, between `[` and `]`"),
+                ));
+            }
+
+            properties.push(Property {
+                offset,
+                // older versions of SGF allowed lowercase letters in identifiers, e.g. `AddBlack`
+                ident: ident.chars().filter(char::is_ascii_uppercase).collect(),
+                values,
+            });
+        }
+    }
+
+    fn value(&mut self) -> Result<String, SgfError> {
+        let start = self.offset;
+        self.offset += 1;
+
+        let mut value = String::new();
+        let mut chars = self.text[self.offset..].char_indices();
+
+        while let Some((index, ch)) = chars.next() {
+            match ch {
+                ']' => {
+                    self.offset += index + 1;
+                    return Ok(value);
+                }
+                '\\' => match chars.next() {
+                    // an escaped line break is a soft line break, which is removed
+                    Some((_, '\n')) => (),
+                    Some((_, escaped)) => value.push(escaped),
+                    None => break,
+                },
+                _ => value.push(ch),
+            }
+        }
+
+        Err(self.error(start, "this value is never closed with `]`"))
+    }
+}
+
+fn color(player: PartizanPlayer) -> &'static str {
+    match player {
+        PartizanPlayer::Left => "Black",
+        PartizanPlayer::Right => "White",
+    }
+}
+
+/// Writes the square at `x`, `y` as a point, where `aa` is the top left.
+fn point(x: usize, y: usize) -> String {
+    [x, y].iter().map(|&i| (b'a' + i as u8) as char).collect()
+}
+
+/// Reads a point, or `None` if it isn't on the board.
+fn parse_point(value: &str) -> Option<(usize, usize)> {
+    let &[x, y] = value.as_bytes() else {
+        return None;
+    };
+
+    let coordinate =
+        |c: u8, size: usize| c.checked_sub(b'a').map(usize::from).filter(|&i| i < size);
+
+    Some((coordinate(x, WIDTH)?, coordinate(y, HEIGHT)?))
+}
+
+impl Reversi {
+    /// Reads the first game of an SGF file, setting up its first node
+    /// and playing the moves of its main line.
+    ///
+    /// As this game ends when the player to move can't move, a pass may only be the last move.
+    pub fn from_sgf(sgf: &str) -> Result<Self, SgfError> {
+        let mut parser = Parser::new(sgf);
+        let nodes = parser.first_game()?;
+
+        let mut game = Reversi::new();
+        let mut passed = None;
+
+        for (index, node) in nodes.iter().enumerate() {
+            let mut setup = None;
+            let mut to_move = None;
+            let mut game_move = None;
+
+            for property in node {
+                let value = property.values[0].trim();
+                let error = |message: String| parser.error(property.offset, message);
+
+                match property.ident.as_str() {
+                    "GM" if value != "2" => {
+                        return Err(error(format!("GM[{value}] isn't Othello, which is GM[2]")))
+                    }
+                    "SZ" if value != WIDTH.to_string() && value != format!("{WIDTH}:{HEIGHT}") => {
+                        return Err(error(format!(
+                            "SZ[{value}] isn't supported, as the board is {WIDTH}x{HEIGHT}"
+                        )))
+                    }
+                    "AB" | "AW" | "AE" if index > 0 => {
+                        return Err(error(format!(
+                            "{} can only set up discs in the first node",
+                            property.ident
+                        )))
+                    }
+                    "AB" | "AW" | "AE" => {
+                        let board =
+                            setup.get_or_insert_with(|| (property.offset, game.board.clone()));
+                        let disc = match property.ident.as_str() {
+                            "AB" => Some(PartizanPlayer::Left),
+                            "AW" => Some(PartizanPlayer::Right),
+                            _ => None,
+                        };
+
+                        for value in &property.values {
+                            let (x, y) = parse_point(value.trim()).ok_or_else(|| {
+                                error(format!("`{value}` isn't a square on the board"))
+                            })?;
+                            board.1.set(x, y, disc).unwrap();
+                        }
+                    }
+                    "PL" => {
+                        to_move = Some((
+                            property.offset,
+                            match value {
+                                "B" => PartizanPlayer::Left,
+                                "W" => PartizanPlayer::Right,
+                                _ => {
+                                    return Err(error(format!(
+                                        "PL[{value}] isn't a player: expected B or W"
+                                    )))
+                                }
+                            },
+                        ))
+                    }
+                    "B" | "W" if game_move.is_some() => {
+                        return Err(error("a node can only have one move".to_string()))
+                    }
+                    "B" | "W" => game_move = Some(property),
+                    _ => (),
+                }
+            }
+
+            if let Some((offset, board)) = setup {
+                game = setup_position(&board).map_err(|message| parser.error(offset, message))?;
+            }
+
+            if let Some((offset, player)) = to_move {
+                if player != game.player() {
+                    return Err(parser.error(
+                        offset,
+                        format!(
+                            "it's {}'s turn, as there are {} discs past the first 4",
+                            color(game.player()),
+                            game.move_count
+                        ),
+                    ));
+                }
+            }
+
+            let Some(property) = game_move else {
+                continue;
+            };
+
+            let value = property.values[0].trim();
+            let error = |message: String| parser.error(property.offset, message);
+            let player = if property.ident == "B" {
+                PartizanPlayer::Left
+            } else {
+                PartizanPlayer::Right
+            };
+
+            if let Some(passer) = passed {
+                return Err(error(format!(
+                    "{} passed, so the game is over: it ends when the player to move can't move",
+                    color(passer)
+                )));
+            }
+
+            if player != game.player() {
+                return Err(error(format!(
+                    "{}[{value}] is {}'s move, but it's {}'s turn",
+                    property.ident,
+                    color(player),
+                    color(game.player())
+                )));
+            }
+
+            let playable = game.state() == GameState::Playable;
+
+            if value.is_empty() || value == "tt" {
+                if playable {
+                    return Err(error(format!("{} passed, but has a move", color(player))));
+                }
+
+                passed = Some(player);
+                continue;
+            }
+
+            let (x, y) = parse_point(value)
+                .ok_or_else(|| error(format!("`{value}` isn't a square on the board")))?;
+
+            if !playable {
+                return Err(error(format!(
+                    "{} can't move, so the game is over",
+                    color(player)
+                )));
+            }
+
+            if game.is_valid_move(&ReversiMove([x, y])).is_none() {
+                return Err(error(format!(
+                    "{}[{value}] isn't a legal move, as it flips no discs",
+                    property.ident
+                )));
+            }
+
+            game.make_move(&ReversiMove([x, y])).unwrap();
+        }
+
+        Ok(game)
+    }
+
+    /// Writes the position as SGF. As a position doesn't keep the moves that led to it,
+    /// its discs are written as setup (`AB` and `AW`), followed by the player to move.
+    pub fn to_sgf(&self) -> String {
+        let mut sgf = format!("(;GM[2]FF[4]SZ[{WIDTH}]");
+
+        for (ident, player) in [("AB", PartizanPlayer::Left), ("AW", PartizanPlayer::Right)] {
+            let points = (0..HEIGHT)
+                .flat_map(|y| (0..WIDTH).map(move |x| (x, y)))
+                .filter(|&(x, y)| *self.board.get(x, y).unwrap() == Some(player))
+                .map(|(x, y)| point(x, y))
+                .collect::<Vec<_>>();
+
+            // a property needs at least one value
+            if !points.is_empty() {
+                sgf += &format!("{ident}[{}]", points.join("]["));
+            }
+        }
+
+        let to_move = match self.player() {
+            PartizanPlayer::Left => "B",
+            PartizanPlayer::Right => "W",
+        };
+
+        sgf + &format!("PL[{to_move}])")
+    }
+}
+
+/// Checks a board that was set up, as if every disc past the first 4 was a move.
+fn setup_position(board: &Array2D<Option<PartizanPlayer>>) -> Result<Reversi, String> {
+    let discs = board.elements_row_major_iter().flatten().count();
+
+    Reversi::try_from(ReversiPosition {
+        board: (0..HEIGHT)
+            .map(|y| {
+                (0..WIDTH)
+                    .map(|x| player_to_char(*board.get(x, y).unwrap()))
+                    .collect()
+            })
+            .collect(),
+        move_count: discs.saturating_sub(4),
+        misere: false,
+    })
+    .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{reversi::ReversiMove, util::bench::parse_moves};
+
+    /// The moves of the game used for benchmarks, without its last 8 moves.
+    const MOVES: &str = "4-2 4-1 3-1 4-3 5-0 2-1 5-3 5-1 2-0 5-2 1-4 3-0 4-0 2-4 2-5 5-4 \
+                         4-4 1-3 5-5 1-5 1-1 4-5 0-5 0-4";
+
+    fn play(moves: &str) -> Reversi {
+        let mut game = Reversi::new();
+        for m in parse_moves::<ReversiMove>(moves) {
+            game.make_move(&m).unwrap();
+        }
+        game
+    }
+
+    /// The moves as an SGF file, spread over lines, with comments and properties that aren't read.
+    fn record() -> String {
+        let moves = parse_moves::<ReversiMove>(MOVES)
+            .iter()
+            .enumerate()
+            .map(|(index, m)| {
+                let ident = if index % 2 == 0 { "B" } else { "W" };
+                format!(";{ident}[{}]", point(m.0[0], m.0[1]))
+            })
+            .collect::<Vec<_>>();
+
+        format!(
+            "(;GM[2]FF[4]SZ[6]\n PB[Black]PW[White]\n C[a comment, with an \\] inside]\n{}\n  {} XX[unknown] C[]\n{})\n",
+            moves[..4].join(" "),
+            moves[4..12].join("\n"),
+            moves[12..].join("")
+        )
+    }
+
+    #[test]
+    fn reads_a_game_record() {
+        let game = Reversi::from_sgf(&record()).unwrap();
+        assert_eq!(game, play(MOVES));
+
+        // the export sets up the same position
+        assert_eq!(Reversi::from_sgf(&game.to_sgf()).unwrap(), game);
+        assert!(game.to_sgf().ends_with("PL[B])"), "{}", game.to_sgf());
+    }
+
+    #[test]
+    fn writes_the_start() {
+        let game = Reversi::new();
+
+        assert_eq!(game.to_sgf(), "(;GM[2]FF[4]SZ[6]AB[cc][dd]AW[dc][cd]PL[B])");
+        assert_eq!(Reversi::from_sgf(&game.to_sgf()).unwrap(), game);
+    }
+
+    #[test]
+    fn follows_the_main_line() {
+        let game = Reversi::from_sgf("(;GM[2]SZ[6:6];B[ec](;W[eb])(;W[cb]))").unwrap();
+
+        assert_eq!(game, play("4-2 4-1"));
+    }
+
+    #[test]
+    fn accepts_a_final_pass() {
+        // White has no discs left, so no moves either
+        let wiped_out = "(;GM[2]AB[cc][dc][cd][dd][bb]PL[W];W[tt])";
+        let game = Reversi::from_sgf(wiped_out).unwrap();
+        assert_eq!(game.state(), GameState::Win(PartizanPlayer::Left));
+
+        let error = Reversi::from_sgf(&wiped_out.replace(";W[tt]", ";W[];B[aa]")).unwrap_err();
+        assert!(error.message.starts_with("White passed"), "{error}");
+    }
+
+    #[test]
+    fn reports_where_errors_are() {
+        let error = |sgf: &str| {
+            let error = Reversi::from_sgf(sgf).unwrap_err();
+            (error.line, error.column, error.message)
+        };
+
+        assert_eq!(
+            error("(;GM[1]SZ[6])"),
+            (1, 3, "GM[1] isn't Othello, which is GM[2]".to_string())
+        );
+        assert_eq!(error("(;GM[2]\n SZ[8])").1, 2);
+        assert_eq!(
+            error("(;GM[2]\n;B[ec]\n;B[dd])"),
+            (
+                3,
+                2,
+                "B[dd] is Black's move, but it's White's turn".to_string()
+            )
+        );
+        assert_eq!(
+            error("(;B[aa])"),
+            (
+                1,
+                3,
+                "B[aa] isn't a legal move, as it flips no discs".to_string()
+            )
+        );
+        assert_eq!(error("(;B[ec];W[zz])").1, 9);
+        assert_eq!(error("(;B[])").2, "Black passed, but has a move");
+        assert_eq!(
+            error("(;PL[W])").2,
+            "it's Black's turn, as there are 0 discs past the first 4".to_string()
+        );
+        assert_eq!(error("(;GM[2] C[unclosed").1, 10);
+        assert_eq!(error("(;B[ec] 12)").2, "expected `)`, but found `1`");
+        assert_eq!(error("(;B[ec]").2, "expected `)`, but the file ended");
+        assert_eq!(error("no game here").1, 13);
+        assert_eq!(
+            error("(;B[ec] W)").2,
+            "expected a value for `W`, between `[` and `]`"
+        );
+    }
+}
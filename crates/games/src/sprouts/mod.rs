@@ -3,14 +3,17 @@
 use std::{
     fmt::{Debug, Display},
     hash::Hash,
+    iter,
     str::FromStr,
 };
 
-use anyhow::Error;
+use anyhow::{anyhow, Error};
 use clap::Args;
 use game_solver::{
-    game::{Game, Normal, NormalImpartial},
-    player::ImpartialPlayer,
+    cgt::OutcomeClass,
+    game::{Normal, NormalImpartial},
+    hint::Hints,
+    prelude::*,
 };
 use itertools::Itertools;
 use petgraph::{
@@ -18,15 +21,29 @@ use petgraph::{
     visit::{IntoEdgeReferences, IntoNodeIdentifiers},
     Undirected,
 };
-use serde::{Deserialize, Serialize};
+use serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 
-use crate::util::cli::move_failable;
+use crate::util::metadata::{GameMetadata, Parameter};
+use crate::util::{
+    bench::{parse_moves, BenchPosition},
+    cli::{
+        evaluate::Heuristics,
+        outcome::{impartial_class, OutcomeClasses},
+        replay_moves, Endings,
+    },
+    notation::{split_fields, NotatedGame, NotationError},
+    parse::{parse_numbers, ParseError, ParseMove},
+    position::PositionError,
+    render::Render,
+    scan::{scan, Scan},
+    symmetry::Symmetries,
+};
 
 /// We aren't dealing with large sprout counts for now.
 pub type SproutsIx = u8;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct SproutsMove {
     from: NodeIndex<SproutsIx>,
     to: NodeIndex<SproutsIx>,
@@ -34,13 +51,28 @@ pub struct SproutsMove {
 
 impl Display for SproutsMove {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({} {})", self.from.index(), self.to.index())
+        write!(f, "{}-{}", self.from.index(), self.to.index())
+    }
+}
+
+impl Serialize for SproutsMove {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for SproutsMove {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
     }
 }
 
 type SproutsGraph = MatrixGraph<(), (), Undirected, Option<()>, SproutsIx>;
 
-#[derive(Clone)]
+#[derive(Clone, Deserialize)]
+#[serde(try_from = "SproutsPosition")]
 pub struct Sprouts(SproutsGraph);
 
 // SproutsGraph, given that its vertices and edges are unlabelled,
@@ -67,7 +99,15 @@ impl PartialEq for Sprouts {
 
 impl Eq for Sprouts {}
 
+/// The game the CLI plays without arguments, starting from 6 spots.
+impl Default for Sprouts {
+    fn default() -> Self {
+        Self::new(6)
+    }
+}
+
 impl Sprouts {
+    /// A game starting from `node_count` spots, with no lines drawn between them.
     pub fn new(node_count: SproutsIx) -> Self {
         let mut graph = SproutsGraph::default();
 
@@ -95,11 +135,13 @@ impl Normal for Sprouts {}
 impl NormalImpartial for Sprouts {}
 impl Game for Sprouts {
     type Move = SproutsMove;
-    type Iter<'a> = std::vec::IntoIter<Self::Move>;
+    type Iter<'a> = Scan<'a, Self, Self::Move>;
 
     type Player = ImpartialPlayer;
     type MoveError = SproutsMoveError;
 
+    const IMPARTIAL: bool = true;
+
     fn max_moves(&self) -> Option<usize> {
         // TODO: i actually want to find what the proper paper is, but
         // https://en.wikipedia.org/wiki/Sprouts_(game)#Maximum_number_of_moves
@@ -162,60 +204,37 @@ impl Game for Sprouts {
     }
 
     fn possible_moves(&self) -> Self::Iter<'_> {
-        let mut sprouts_moves = vec![];
-
-        for id in self.0.node_identifiers() {
-            let edge_count = self.0.edges(id).count();
+        // every pair of sprouts, each sprout with itself first
+        let sprouts = self.0.node_count();
+        scan(self, sprouts * sprouts, |(game, index)| {
+            let sprouts = game.0.node_count();
+            let (id, sub_id) = (
+                NodeIndex::new(index / sprouts),
+                NodeIndex::new(index % sprouts),
+            );
+            let edge_count = game.0.edges(id).count();
 
             // TODO: use MAX_SPROUTS for all values
-            match edge_count {
-                0 | 1 => {
-                    if !self.0.has_edge(id, id) {
-                        sprouts_moves.push(SproutsMove { from: id, to: id });
-                    }
-                    for sub_id in self.0.node_identifiers() {
-                        if id >= sub_id {
-                            continue;
-                        }
-                        if self.0.edges(sub_id).count() >= MAX_SPROUTS {
-                            continue;
-                        }
-                        if self.0.has_edge(id, sub_id) {
-                            continue;
-                        }
-                        sprouts_moves.push(SproutsMove {
-                            from: id,
-                            to: sub_id,
-                        })
-                    }
+            let possible = match edge_count {
+                0 | 1 if id == sub_id => !game.0.has_edge(id, id),
+                0..=2 => {
+                    id < sub_id
+                        && game.0.edges(sub_id).count() < MAX_SPROUTS
+                        && !game.0.has_edge(id, sub_id)
                 }
-                2 => {
-                    for sub_id in self.0.node_identifiers() {
-                        if id >= sub_id {
-                            continue;
-                        }
-                        if self.0.edges(sub_id).count() >= MAX_SPROUTS {
-                            continue;
-                        }
-                        if self.0.has_edge(id, sub_id) {
-                            continue;
-                        }
-                        sprouts_moves.push(SproutsMove {
-                            from: id,
-                            to: sub_id,
-                        })
-                    }
-                }
-                MAX_SPROUTS => (),
+                MAX_SPROUTS => false,
                 _ => panic!("No node should have more than three edges"),
-            }
-        }
+            };
 
-        sprouts_moves.into_iter()
+            possible.then_some(SproutsMove {
+                from: id,
+                to: sub_id,
+            })
+        })
     }
 
-    fn state(&self) -> game_solver::game::GameState<Self::Player> {
-        <Self as Normal>::state(&self)
+    fn status(&self) -> game_solver::game::GameStatus {
+        <Self as Normal>::status(self)
     }
 }
 
@@ -244,6 +263,119 @@ impl Display for Sprouts {
     }
 }
 
+/// Serializes the sprout count and every line drawn between two sprouts,
+/// in the same notation as [`SproutsMove`].
+impl Serialize for Sprouts {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let lines = self
+            .0
+            .edge_references()
+            .map(|(from, to, _)| SproutsMove { from, to })
+            .collect::<Vec<_>>();
+
+        let mut state = serializer.serialize_struct("Sprouts", 2)?;
+        state.serialize_field("sprouts", &self.0.node_count())?;
+        state.serialize_field("lines", &lines)?;
+        state.end()
+    }
+}
+
+/// A serialized [`Sprouts`], before it's checked.
+#[derive(Deserialize)]
+struct SproutsPosition {
+    sprouts: usize,
+    lines: Vec<SproutsMove>,
+}
+
+impl TryFrom<SproutsPosition> for Sprouts {
+    type Error = PositionError;
+
+    fn try_from(position: SproutsPosition) -> Result<Self, Self::Error> {
+        let sprouts = SproutsIx::try_from(position.sprouts).map_err(|_| {
+            PositionError::impossible(format!(
+                "there can be at most {} sprouts, not {}",
+                SproutsIx::MAX,
+                position.sprouts
+            ))
+        })?;
+
+        // lines are drawn in the order they're listed, but the order doesn't change the position
+        let mut game = Sprouts::new(sprouts);
+        for line in position.lines {
+            game.draw(&line)?;
+        }
+
+        Ok(game)
+    }
+}
+
+impl Sprouts {
+    /// Draws a line of a position that's being read back, checking that it can be drawn.
+    fn draw(&mut self, line: &SproutsMove) -> Result<(), PositionError> {
+        self.make_move(line).map_err(|err| {
+            PositionError::impossible(format!("the line {line} can't be drawn: {err}"))
+        })
+    }
+}
+
+/// Written as the sprout count, followed by every line drawn between two sprouts,
+/// in the same notation as [`SproutsMove`], e.g. `3 1-0 2-2`.
+impl NotatedGame for Sprouts {
+    fn to_notation(&self) -> String {
+        iter::once(self.0.node_count().to_string())
+            .chain(
+                self.0
+                    .edge_references()
+                    .map(|(from, to, _)| SproutsMove { from, to }.to_string()),
+            )
+            .join(" ")
+    }
+
+    fn from_notation(notation: &str) -> Result<Self, NotationError> {
+        let fields = split_fields(notation);
+        let Some((count, lines)) = fields.split_first() else {
+            return Err(NotationError::new(
+                notation,
+                0..0,
+                "expected the sprout count",
+            ));
+        };
+
+        let mut game = Self::try_from(SproutsPosition {
+            sprouts: count.number("sprout count")?,
+            lines: Vec::new(),
+        })
+        .map_err(|err| count.error(err.to_string()))?;
+
+        for field in lines {
+            let line = field
+                .text()
+                .parse::<SproutsMove>()
+                .map_err(|err| field.error(err.to_string()))?;
+            game.draw(&line)
+                .map_err(|err| field.error(err.to_string()))?;
+        }
+
+        Ok(game)
+    }
+}
+
+/// What Sprouts is, and how it's set up from the CLI.
+pub const METADATA: GameMetadata = GameMetadata {
+    name: "Sprouts",
+    summary:
+        "Join two sprouts with a line, adding a sprout on it; a sprout can have at most 3 lines.",
+    rules: include_str!("./README.md"),
+    parameters: &[Parameter {
+        name: "<STARTING_SPROUTS>",
+        description: "The amount of sprouts to start off with.",
+        default: "6",
+        range: "0 or more",
+    }],
+    move_notation: "i-j, the zero-indexed sprouts to join (e.g. 0-1)",
+    example: "sprouts 3 0-1",
+};
+
 /// Analyzes Sprouts.
 ///
 #[doc = include_str!("./README.md")]
@@ -251,50 +383,177 @@ impl Display for Sprouts {
 pub struct SproutsArgs {
     /// The amount of sprouts (nodes)
     /// to start off with.
-    starting_sprouts: SproutsIx,
+    #[arg(required_unless_present = "position")]
+    starting_sprouts: Option<SproutsIx>,
     /// Sprouts moves, ordered as i1-j1 i2-j2 ...
     #[arg(value_parser = clap::value_parser!(SproutsMove))]
     moves: Vec<SproutsMove>,
+    /// The position to analyze, in its notation (e.g. `3 1-0 2-2`),
+    /// instead of the starting sprouts and moves.
+    #[arg(long, conflicts_with_all = ["starting_sprouts", "moves"])]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<String>,
 }
 
 impl Default for SproutsArgs {
     fn default() -> Self {
         Self {
-            starting_sprouts: 6,
+            starting_sprouts: Some(6),
             moves: vec![],
+            position: None,
         }
     }
 }
 
+impl ParseMove for Sprouts {
+    fn parse_move(&self, token: &str) -> Result<Self::Move, ParseError> {
+        token.parse()
+    }
+}
+
+impl Heuristics for Sprouts {}
+
+impl Hints for Sprouts {}
+
+impl Endings for Sprouts {}
+
+impl OutcomeClasses for Sprouts {
+    fn outcome_class(&self) -> Option<Result<OutcomeClass, GameSolveError<Self>>> {
+        impartial_class(self)
+    }
+}
+
+impl Symmetries for Sprouts {}
+
+impl Render for Sprouts {}
+
 impl TryFrom<SproutsArgs> for Sprouts {
     type Error = Error;
 
     fn try_from(args: SproutsArgs) -> Result<Self, Self::Error> {
-        let mut game = Sprouts::new(args.starting_sprouts);
-
-        for sprouts_move in args.moves {
-            move_failable(&mut game, &sprouts_move)?;
+        if let Some(position) = args.position {
+            return Ok(Self::from_notation(&position)?);
         }
 
+        let starting_sprouts = args
+            .starting_sprouts
+            .ok_or_else(|| anyhow!("expected the starting sprouts, or a position"))?;
+
+        let mut game = Sprouts::new(starting_sprouts);
+
+        replay_moves(&mut game, &args.moves)?;
+
         Ok(game)
     }
 }
 
 impl FromStr for SproutsMove {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let components = s.split("-").collect::<Vec<_>>();
+        let numbers = parse_numbers(s, 2)?;
 
-        assert_eq!(
-            components.len(),
-            2,
-            "a move shouldn't connect more than two sprouts"
-        );
+        let sprout = |index: usize| {
+            SproutsIx::try_from(index).map_err(|_| {
+                ParseError::out_of_range(
+                    s,
+                    format!("sprout indices no greater than {}", SproutsIx::MAX),
+                )
+            })
+        };
 
         Ok(SproutsMove {
-            from: str::parse::<SproutsIx>(components[0])?.into(),
-            to: str::parse::<SproutsIx>(components[1])?.into(),
+            from: sprout(numbers[0])?.into(),
+            to: sprout(numbers[1])?.into(),
         })
     }
 }
+
+/// Positions for the `bench` subcommand.
+pub fn bench_positions() -> Vec<BenchPosition<SproutsArgs>> {
+    [
+        ("2 sprouts", true, 2, ""),
+        ("3 sprouts", true, 3, ""),
+        ("4 sprouts", false, 4, ""),
+        ("4 sprouts after 0-1", false, 4, "0-1"),
+    ]
+    .into_iter()
+    .map(|(name, quick, starting_sprouts, moves)| BenchPosition {
+        name,
+        quick,
+        args: SproutsArgs {
+            starting_sprouts: Some(starting_sprouts),
+            moves: parse_moves(moves),
+            position: None,
+        },
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::move_score::{impartial_outcomes_testing, window_results_testing};
+
+    #[test]
+    fn serde_round_trip() {
+        let game: Sprouts = SproutsArgs {
+            starting_sprouts: Some(3),
+            moves: vec!["0-1".parse().unwrap(), "2-2".parse().unwrap()],
+            position: None,
+        }
+        .try_into()
+        .unwrap();
+
+        let json = serde_json::to_string(&game).unwrap();
+        assert_eq!(json, r#"{"sprouts":3,"lines":["1-0","2-2"]}"#);
+        assert_eq!(serde_json::from_str::<Sprouts>(&json).unwrap(), game);
+    }
+
+    #[test]
+    fn serde_rejects_impossible_positions() {
+        let error = |json: &str| {
+            serde_json::from_str::<Sprouts>(json)
+                .unwrap_err()
+                .to_string()
+        };
+
+        assert!(error(r#"{"sprouts":2,"lines":["0-5"]}"#).contains("the line 0-5 can't be drawn"));
+        assert!(error(r#"{"sprouts":2,"lines":["0-1","0-1"]}"#).contains("already been made"));
+        assert!(error(r#"{"sprouts":300,"lines":[]}"#).contains("at most 255 sprouts"));
+    }
+
+    #[test]
+    fn notation_round_trip() {
+        let mut game = Sprouts::new(3);
+        assert_eq!(game.to_notation(), "3");
+
+        game.make_move(&"0-1".parse().unwrap()).unwrap();
+        game.make_move(&"2-2".parse().unwrap()).unwrap();
+        assert_eq!(game.to_notation(), "3 1-0 2-2");
+        assert_eq!(Sprouts::from_notation("3 1-0 2-2").unwrap(), game);
+        assert_eq!(Sprouts::from_notation("\t3  0-1\n2-2").unwrap(), game);
+    }
+
+    #[test]
+    fn notation_rejects_invalid_positions() {
+        let span = |notation: &str| Sprouts::from_notation(notation).unwrap_err().span();
+
+        assert_eq!(span("3 0-1 0-1"), 6..9);
+        assert_eq!(span("3 0-1 0_1"), 6..9);
+        assert_eq!(span("2 0-5"), 2..5);
+        assert_eq!(span("300 0-1"), 0..3);
+        assert_eq!(span("x"), 0..1);
+        assert_eq!(span(" "), 0..0);
+    }
+
+    #[test]
+    fn grundy_outcomes_agree_with_negamax() {
+        impartial_outcomes_testing(&Sprouts::new(3), 6, 200);
+    }
+
+    #[test]
+    fn windows_agree_with_the_score() {
+        window_results_testing(&Sprouts::new(3), 6, 20);
+    }
+}
@@ -0,0 +1,571 @@
+#![doc = include_str!("./README.md")]
+
+use std::fmt::{Debug, Display};
+
+use anyhow::{anyhow, Error};
+use clap::Args;
+use game_solver::{
+    cgt::OutcomeClass,
+    game::{Normal, NormalImpartial},
+    hint::Hints,
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::util::metadata::{GameMetadata, Parameter};
+use crate::util::{
+    bench::{parse_moves, BenchPosition},
+    cli::{
+        evaluate::Heuristics,
+        outcome::{impartial_class, OutcomeClasses},
+        replay_moves, Endings,
+    },
+    notation::{fields, NotatedGame, NotationError},
+    parse::{parse_number, ParseError, ParseMove},
+    position::PositionError,
+    render::Render,
+    symmetry::Symmetries,
+};
+
+/// The most objects a move can take.
+pub const MAX_TAKE: usize = 3;
+
+/// A heap that players take 1 to [`MAX_TAKE`] objects from in turn, until whoever takes the last one wins.
+///
+/// The solver tells positions apart by [`Eq`] and [`Hash`], so only what makes a position
+/// different is kept: the objects left, the objects the heap started with, and the moves made.
+#[derive(Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "TakeawayPosition")]
+pub struct Takeaway {
+    /// The objects left in the heap.
+    left: usize,
+    /// The objects the heap started with, which bounds how many moves the game can last.
+    start: usize,
+    /// The moves made so far.
+    move_count: usize,
+}
+
+/// The game the CLI plays without arguments, starting from a heap of 21 objects.
+impl Default for Takeaway {
+    fn default() -> Self {
+        Self::new(21)
+    }
+}
+
+impl Takeaway {
+    /// A game starting from a heap of `heap` objects.
+    pub fn new(heap: usize) -> Self {
+        Self {
+            left: heap,
+            start: heap,
+            move_count: 0,
+        }
+    }
+
+    /// The objects left in the heap.
+    pub fn left(&self) -> usize {
+        self.left
+    }
+
+    /// The take that leaves a multiple of 4 objects, which wins;
+    /// or None if the heap already is one, and every take loses.
+    ///
+    /// ```
+    /// use games::takeaway::Takeaway;
+    ///
+    /// assert_eq!(Takeaway::new(21).winning_take(), Some(1));
+    /// assert_eq!(Takeaway::new(20).winning_take(), None);
+    /// ```
+    pub fn winning_take(&self) -> Option<usize> {
+        Some(self.left % (MAX_TAKE + 1)).filter(|&take| take > 0)
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TakeawayMoveError {
+    #[error("can't take {0} objects: a move takes 1 to {MAX_TAKE}.")]
+    OutOfRange(usize),
+    #[error("can't take {take} objects when there are only {left} left.")]
+    TooManyObjects { take: usize, left: usize },
+}
+
+// Whoever can't move (as the heap is empty) loses, which `Normal` turns into the game's state.
+impl Normal for Takeaway {}
+// Both players have the same moves, so the game is impartial: it's worth a Nim heap of `left % 4`.
+impl NormalImpartial for Takeaway {}
+impl Game for Takeaway {
+    /// How many objects to take.
+    type Move = usize;
+    /// The moves are the numbers of objects that can be taken, so they're just a range.
+    type Iter<'a> = std::iter::Rev<std::ops::RangeInclusive<usize>>;
+
+    /// In an impartial game, the players are only the one to move and the one who just moved.
+    type Player = ImpartialPlayer;
+    type MoveError = TakeawayMoveError;
+
+    const IMPARTIAL: bool = true;
+
+    /// Every move takes at least one object, so there can't be more moves than the heap started with.
+    /// Scores are measured from this bound, which is why the heap the game started with is kept.
+    fn max_moves(&self) -> Option<usize> {
+        Some(self.start)
+    }
+
+    fn move_count(&self) -> usize {
+        self.move_count
+    }
+
+    /// Takes `m` objects, checking first that it can: the solver only makes possible moves,
+    /// but moves from the CLI are checked here.
+    fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        let take = *m;
+
+        if !(1..=MAX_TAKE).contains(&take) {
+            return Err(TakeawayMoveError::OutOfRange(take));
+        }
+
+        if take > self.left {
+            return Err(TakeawayMoveError::TooManyObjects {
+                take,
+                left: self.left,
+            });
+        }
+
+        self.left -= take;
+        self.move_count += 1;
+        Ok(())
+    }
+
+    /// Every take from the most to the least that can be taken.
+    ///
+    /// The best move would ideally come first, but with at most 3 moves,
+    /// the search finds it fast enough either way.
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        (1..=self.left.min(MAX_TAKE)).rev()
+    }
+
+    /// Exact, so the solver never has to make the moves to tell whether there are any.
+    fn move_count_hint(&self) -> (usize, Option<usize>) {
+        let count = self.left.min(MAX_TAKE);
+        (count, Some(count))
+    }
+
+    // `find_immediately_resolvable_game` and `cheap_win_check` keep their defaults:
+    // the solver finds the last take as soon as it searches it, which it does first.
+
+    fn status(&self) -> GameStatus {
+        <Self as Normal>::status(self)
+    }
+
+    /// Whoever's turn it is, is always the next player in an impartial game.
+    fn player(&self) -> Self::Player {
+        ImpartialPlayer::Next
+    }
+}
+
+impl Display for Takeaway {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} of {} objects left", self.left, self.start)
+    }
+}
+
+impl Debug for Takeaway {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        <Self as Display>::fmt(self, f)
+    }
+}
+
+/// A serialized [`Takeaway`], before it's checked.
+#[derive(Deserialize)]
+struct TakeawayPosition {
+    left: usize,
+    start: usize,
+    move_count: usize,
+}
+
+impl TryFrom<TakeawayPosition> for Takeaway {
+    type Error = PositionError;
+
+    /// Checks that the moves made could have taken what was taken: each took 1 to [`MAX_TAKE`] objects.
+    fn try_from(position: TakeawayPosition) -> Result<Self, Self::Error> {
+        let TakeawayPosition {
+            left,
+            start,
+            move_count,
+        } = position;
+
+        let Some(taken) = start.checked_sub(left) else {
+            return Err(PositionError::impossible(format!(
+                "{left} objects can't be left of a heap of {start}"
+            )));
+        };
+
+        if taken < move_count || taken > move_count * MAX_TAKE {
+            return Err(PositionError::impossible(format!(
+                "{move_count} moves can't take {taken} objects"
+            )));
+        }
+
+        Ok(Self {
+            left,
+            start,
+            move_count,
+        })
+    }
+}
+
+/// Written as the objects left out of the objects the heap started with, followed by the move count: `17/21 m2`.
+impl NotatedGame for Takeaway {
+    fn to_notation(&self) -> String {
+        format!("{}/{} m{}", self.left, self.start, self.move_count)
+    }
+
+    fn from_notation(notation: &str) -> Result<Self, NotationError> {
+        let fields = fields(notation, &["heap", "move count"], &[])?;
+
+        let heap = fields[0].split('/');
+        let [left, start] = heap.as_slice() else {
+            return Err(fields[0]
+                .error("expected the objects left and the starting objects, as left/start"));
+        };
+        let move_count = fields[1]
+            .strip_prefix('m', "move count")?
+            .number("move count")?;
+
+        Self::try_from(TakeawayPosition {
+            left: left.number("objects left")?,
+            start: start.number("starting objects")?,
+            move_count,
+        })
+        .map_err(|err| fields[0].error(err.to_string()))
+    }
+}
+
+/// What Takeaway is, and how it's set up from the CLI.
+pub const METADATA: GameMetadata = GameMetadata {
+    name: "Takeaway",
+    summary: "Take 1 to 3 objects from a single heap; whoever takes the last one wins.",
+    rules: include_str!("./README.md"),
+    parameters: &[Parameter {
+        name: "<HEAP>",
+        description: "The amount of objects in the heap.",
+        default: "21",
+        range: "0 or more",
+    }],
+    move_notation: "the amount of objects to take (e.g. 2)",
+    example: "takeaway 21 1 3",
+};
+
+/// Analyzes Takeaway.
+///
+#[doc = include_str!("./README.md")]
+#[derive(Args, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct TakeawayArgs {
+    /// The amount of objects in the heap.
+    #[arg(required_unless_present = "position")]
+    heap: Option<usize>,
+    /// The amount of objects each move takes, in order.
+    moves: Vec<usize>,
+    /// The position to analyze, in its notation (e.g. `17/21 m2`),
+    /// instead of the heap and moves.
+    #[arg(long, conflicts_with_all = ["heap", "moves"])]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<String>,
+}
+
+impl Default for TakeawayArgs {
+    fn default() -> Self {
+        Self {
+            heap: Some(21),
+            moves: vec![],
+            position: None,
+        }
+    }
+}
+
+impl ParseMove for Takeaway {
+    fn parse_move(&self, token: &str) -> Result<Self::Move, ParseError> {
+        parse_number(token)
+    }
+}
+
+impl Heuristics for Takeaway {}
+
+impl Hints for Takeaway {
+    /// The [winning take](Takeaway::winning_take), which leaves a multiple of 4 objects.
+    fn book_move(&self) -> Option<usize> {
+        self.winning_take()
+    }
+}
+
+impl Endings for Takeaway {}
+
+impl OutcomeClasses for Takeaway {
+    fn outcome_class(&self) -> Option<Result<OutcomeClass, GameSolveError<Self>>> {
+        impartial_class(self)
+    }
+
+    /// The [winning take](Takeaway::winning_take), which leaves a multiple of 4 objects.
+    fn book_move(&self) -> Option<String> {
+        self.winning_take().map(|take| take.to_string())
+    }
+}
+
+impl Symmetries for Takeaway {}
+
+impl Render for Takeaway {}
+
+impl TryFrom<TakeawayArgs> for Takeaway {
+    type Error = Error;
+
+    fn try_from(args: TakeawayArgs) -> Result<Self, Self::Error> {
+        if let Some(position) = args.position {
+            return Ok(Self::from_notation(&position)?);
+        }
+
+        let heap = args
+            .heap
+            .ok_or_else(|| anyhow!("expected the amount of objects in the heap, or a position"))?;
+
+        let mut game = Takeaway::new(heap);
+        replay_moves(&mut game, &args.moves)?;
+
+        Ok(game)
+    }
+}
+
+/// Positions for the `bench` subcommand.
+pub fn bench_positions() -> Vec<BenchPosition<TakeawayArgs>> {
+    [
+        ("21", true, 21, ""),
+        ("21 after 1", true, 21, "1"),
+        ("100", false, 100, ""),
+    ]
+    .into_iter()
+    .map(|(name, quick, heap, moves)| BenchPosition {
+        name,
+        quick,
+        args: TakeawayArgs {
+            heap: Some(heap),
+            moves: parse_moves(moves),
+            position: None,
+        },
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use game_solver::{
+        cgt::impartial_outcome_class, move_scores, optimal_moves, player::Player, solve,
+    };
+
+    use super::*;
+    use crate::util::{
+        invariants::{check_playthrough, check_position},
+        move_score::{impartial_outcomes_testing, optimal_moves_testing, window_results_testing},
+    };
+
+    /// Every position reachable from a heap of 21, each once.
+    fn every_position() -> Vec<Takeaway> {
+        let mut seen = HashSet::new();
+        let mut unchecked = vec![Takeaway::new(21)];
+
+        while let Some(game) = unchecked.pop() {
+            for m in game.possible_moves() {
+                let mut next = game.clone();
+                next.make_move(&m).unwrap();
+                unchecked.push(next);
+            }
+            seen.insert(game);
+        }
+
+        seen.into_iter().collect()
+    }
+
+    #[test]
+    fn every_position_upholds_the_invariants() {
+        let positions = every_position();
+        // every amount left, after as many moves as could have taken the rest
+        assert_eq!(positions.len(), 169);
+
+        for game in &positions {
+            check_position(game).unwrap();
+        }
+
+        for choices in [&[][..], &[0; 30], &[2; 30], &[1, 2, 0, 2, 1, 0, 1]] {
+            let end = check_playthrough(Takeaway::new(21), choices).unwrap();
+            // 30 moves always take the whole heap, and the others never do
+            assert_eq!(Game::state(&end) == GameState::Playable, choices.len() < 30);
+        }
+    }
+
+    #[test]
+    fn moves_take_one_to_three_objects() {
+        for game in every_position() {
+            let moves = game.possible_moves().collect::<Vec<_>>();
+            let expected = (1..=game.left.min(3)).rev().collect::<Vec<_>>();
+            assert_eq!(moves, expected, "{game:?}");
+            assert_eq!(game.move_count_hint(), (moves.len(), Some(moves.len())));
+            assert_eq!(game.has_moves(), game.left > 0);
+
+            for take in moves {
+                let mut next = game.clone();
+                next.make_move(&take).unwrap();
+                assert_eq!(next.left, game.left - take);
+                assert_eq!(next.move_count, game.move_count + 1);
+                assert!(next.move_count <= next.max_moves().unwrap());
+            }
+
+            for take in [0, 4, game.left + 1] {
+                if take > 0 && take <= game.left.min(3) {
+                    continue;
+                }
+                let mut next = game.clone();
+                assert!(next.make_move(&take).is_err(), "{game:?} {take}");
+                assert_eq!(next, game);
+            }
+        }
+    }
+
+    #[test]
+    fn taking_the_last_object_wins() {
+        for game in every_position() {
+            match game.left {
+                0 => assert_eq!(
+                    Game::state(&game),
+                    GameState::Win(ImpartialPlayer::Previous)
+                ),
+                _ => assert_eq!(Game::state(&game), GameState::Playable),
+            }
+            assert_eq!(game.player(), ImpartialPlayer::Next);
+        }
+
+        assert_eq!(
+            Takeaway::new(2).make_move(&3),
+            Err(TakeawayMoveError::TooManyObjects { take: 3, left: 2 })
+        );
+        assert_eq!(
+            Takeaway::new(5).make_move(&4),
+            Err(TakeawayMoveError::OutOfRange(4))
+        );
+    }
+
+    #[test]
+    fn multiples_of_four_are_lost() {
+        for heap in 0..=21 {
+            let game = Takeaway::new(heap);
+            let score = solve(&game, &mut HashMap::new(), None, &None).unwrap();
+            let class = impartial_outcome_class(&game).unwrap();
+
+            assert_eq!(score > 0, heap % 4 != 0, "{heap}");
+            assert_eq!(class == OutcomeClass::Next, heap % 4 != 0, "{heap}");
+            assert_eq!(game.winning_take().is_some(), heap % 4 != 0, "{heap}");
+        }
+    }
+
+    #[test]
+    fn the_winning_take_is_the_best_move() {
+        for game in every_position() {
+            let Some(take) = game.winning_take() else {
+                continue;
+            };
+
+            assert_eq!(
+                optimal_moves(&game, &mut HashMap::new()).unwrap(),
+                optimal_moves_testing(&game),
+                "{game:?}"
+            );
+            assert!(optimal_moves_testing(&game).contains(&take), "{game:?}");
+        }
+
+        // only taking 1 wins the 21 game, and the win takes as long as the heap can last
+        let scores = move_scores(&Takeaway::new(21), &mut HashMap::new(), None, &None)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(scores.iter().filter(|(_, score)| *score > 0).count(), 1);
+        assert!(scores.contains(&(1, 21 - 11 + 1)));
+    }
+
+    #[test]
+    fn clones_and_winners_agree() {
+        for game in every_position() {
+            let mut clone = Takeaway::new(0);
+            clone.clone_from(&game);
+            assert_eq!(clone, game);
+
+            assert_eq!(game.winner().is_some(), game.left == 0);
+            assert!(!game.is_tie());
+            if let Some(winner) = game.winner() {
+                assert_eq!(winner, game.player().previous());
+            }
+        }
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        let mut game = Takeaway::new(21);
+        game.make_move(&1).unwrap();
+        game.make_move(&3).unwrap();
+
+        let json = serde_json::to_string(&game).unwrap();
+        assert_eq!(json, r#"{"left":17,"start":21,"move_count":2}"#);
+        assert_eq!(serde_json::from_str::<Takeaway>(&json).unwrap(), game);
+
+        let err = |json: &str| {
+            serde_json::from_str::<Takeaway>(json)
+                .unwrap_err()
+                .to_string()
+        };
+        assert!(err(r#"{"left":22,"start":21,"move_count":0}"#).contains("can't be left"));
+        assert!(err(r#"{"left":10,"start":21,"move_count":2}"#).contains("2 moves can't take 11"));
+        assert!(err(r#"{"left":20,"start":21,"move_count":2}"#).contains("2 moves can't take 1"));
+    }
+
+    #[test]
+    fn notation_round_trip() {
+        for game in every_position() {
+            assert_eq!(Takeaway::from_notation(&game.to_notation()).unwrap(), game);
+        }
+
+        assert_eq!(Takeaway::new(21).to_notation(), "21/21 m0");
+        assert_eq!(
+            Takeaway::from_notation(" 17/21\tm2 ")
+                .unwrap()
+                .to_notation(),
+            "17/21 m2"
+        );
+    }
+
+    #[test]
+    fn notation_rejects_invalid_positions() {
+        let span = |notation: &str| Takeaway::from_notation(notation).unwrap_err().span();
+
+        assert_eq!(span("17 m2"), 0..2);
+        assert_eq!(span("17/x m2"), 3..4);
+        assert_eq!(span("17/21 2"), 6..7);
+        assert_eq!(span("10/21 m2"), 0..5);
+        assert_eq!(span("17/21"), 5..5);
+        assert_eq!(span("17/21 m2 m2"), 9..11);
+    }
+
+    #[test]
+    fn moves_parse_back() {
+        let game = Takeaway::new(21);
+        assert_eq!(game.parse_move("2"), Ok(2));
+        assert!(game.parse_move("two").is_err());
+    }
+
+    #[test]
+    fn grundy_outcomes_agree_with_negamax() {
+        impartial_outcomes_testing(&Takeaway::new(21), 21, 300);
+    }
+
+    #[test]
+    fn windows_agree_with_the_score() {
+        window_results_testing(&Takeaway::new(21), 21, 40);
+    }
+}
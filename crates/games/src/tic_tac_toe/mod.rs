@@ -5,21 +5,36 @@ pub mod gui;
 use anyhow::{anyhow, Error};
 use clap::Args;
 use game_solver::{
-    game::{Game, GameState},
-    player::{PartizanPlayer, Player},
+    hash::{zobrist_key, IncrementalHash},
+    hint::Hints,
+    ordering::PlacementGame,
+    prelude::*,
+    tablebase::EnumerableGame,
 };
+use game_solver_derive::placement_game;
 use itertools::Itertools;
-use ndarray::{iter::IndexedIter, ArrayD, Dim, Dimension, IntoDimension, IxDyn, IxDynImpl};
-use serde::{Deserialize, Serialize};
+use ndarray::{iter::IndexedIter, ArrayD, Axis, Dim, Dimension, IntoDimension, IxDyn, IxDynImpl};
+use serde::{de, ser::SerializeStruct, Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 
 use std::{
     fmt::{Debug, Display, Formatter},
-    hash::Hash,
+    hash::{Hash, Hasher},
     iter::FilterMap,
 };
 
-use crate::util::cli::move_failable;
+use crate::util::metadata::{GameMetadata, Parameter};
+use crate::util::{
+    bench::{parse_moves, BenchPosition},
+    cli::{evaluate::Heuristics, outcome::OutcomeClasses, replay_parsed_moves, Endings},
+    lines::{run_through, Lines},
+    notation::{fields, read_board, NotatedGame, NotationError, ToMove},
+    parse::{parse_number, parse_numbers, ParseError, ParseMove},
+    position::{parse_board, PositionError},
+    render::{Cell, Grid, Render},
+    resolve::find_resolvable,
+    symmetry::Symmetries,
+};
 
 #[derive(Clone, Copy, Hash, Eq, PartialEq, Debug)]
 pub enum Square {
@@ -43,13 +58,22 @@ impl Square {
     }
 }
 
-#[derive(Clone, Hash, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq, Deserialize)]
+#[serde(try_from = "TicTacToePosition")]
 pub struct TicTacToe {
     dim: usize,
     size: usize,
     /// True represents a square that has not been eaten
     board: ArrayD<Option<Square>>,
     move_count: usize,
+    /// The [`IncrementalHash`] of the board, the XOR of the keys of its filled squares.
+    hash: u64,
+}
+
+impl Hash for TicTacToe {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -61,13 +85,38 @@ pub enum TicTacToeMoveError {
     NonEmptySquare(TicTacToeMove),
 }
 
+/// What Tic Tac Toe is, and how it's set up from the CLI.
+pub const METADATA: GameMetadata = GameMetadata {
+    name: "Tic Tac Toe",
+    summary: "Fill a line of the board with your mark, in any number of dimensions.",
+    rules: include_str!("./README.md"),
+    parameters: &[
+        Parameter {
+            name: "<DIMENSIONS>",
+            description: "The amount of dimensions of the board.",
+            default: "2",
+            range: "1 or more",
+        },
+        Parameter {
+            name: "<SIZE>",
+            description: "The length of the board in every dimension.",
+            default: "3",
+            range: "1 or more",
+        },
+    ],
+    move_notation:
+        "an index for every dimension, separated by dashes (e.g. 1-1 for two dimensions)",
+    example: "tic-tac-toe 2 3 1-1 0-0",
+};
+
 /// Analyzes Tic Tac Toe.
 ///
 #[doc = include_str!("./README.md")]
 #[derive(Args, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct TicTacToeArgs {
     /// The amount of dimensions in the game.
-    dimensions: usize,
+    #[arg(required_unless_present_any = ["position", "board"])]
+    dimensions: Option<usize>,
     /// The size of the board - i.e. with two dimensions
     /// and a size of three, the board would look like
     ///
@@ -76,18 +125,96 @@ pub struct TicTacToeArgs {
     /// * * *
     /// * * *
     /// ```
-    size: usize,
-    /// The moves to make in the game, by dimension and index in that dimension.
+    #[arg(required_unless_present_any = ["position", "board"])]
+    size: Option<usize>,
+    /// The moves to make in the game, by dimension and index in that dimension,
+    /// ordered as x1-y1 x2-y2 ... for two dimensions.
     moves: Vec<String>,
+    /// The position to analyze, in its notation (e.g. `XOX/.O./..X X`),
+    /// instead of the dimensions, size, and moves.
+    #[arg(long, conflicts_with_all = ["dimensions", "size", "moves"])]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<String>,
+    /// The board to analyze (e.g. `XO./.X./..O`), with its rows separated by `/` or on lines of their own,
+    /// instead of the dimensions, size, and moves. As X moves first, whose turn it is follows from the board.
+    #[arg(long, conflicts_with_all = ["dimensions", "size", "moves", "position"])]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    board: Option<String>,
+    /// The player to move on the board (Left is X), which has to be the one whose turn it is.
+    #[arg(long, requires = "board")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    to_move: Option<ToMove>,
 }
 
 impl Default for TicTacToeArgs {
     fn default() -> Self {
         Self {
-            dimensions: 2,
-            size: 3,
+            dimensions: Some(2),
+            size: Some(3),
             moves: vec![],
+            position: None,
+            board: None,
+            to_move: None,
+        }
+    }
+}
+
+impl ParseMove for TicTacToe {
+    fn parse_move(&self, token: &str) -> Result<Self::Move, ParseError> {
+        let numbers = parse_numbers(token, self.dim)?;
+
+        if numbers.iter().any(|&n| n >= self.size) {
+            return Err(ParseError::out_of_range(
+                token,
+                format!("every index to be less than the board size {}", self.size),
+            ));
         }
+
+        Ok(TicTacToeMove(numbers.into_dimension()))
+    }
+}
+
+impl Heuristics for TicTacToe {}
+
+impl Hints for TicTacToe {}
+impl Endings for TicTacToe {}
+
+impl OutcomeClasses for TicTacToe {}
+
+/// The board can be rotated and reflected: its axes can be put in any order, and any of them reversed.
+impl Symmetries for TicTacToe {
+    fn canonical(&self) -> Self {
+        // empty squares first, then X, then O
+        let key = |board: &ArrayD<Option<Square>>| {
+            board
+                .iter()
+                .map(|square| match square {
+                    None => 0,
+                    Some(Square::X) => 1,
+                    Some(Square::O) => 2,
+                })
+                .collect::<Vec<u8>>()
+        };
+
+        let board = (0..self.dim)
+            .permutations(self.dim)
+            .cartesian_product(0..1usize << self.dim)
+            .map(|(axes, reversed)| {
+                let mut board = self.board.clone().permuted_axes(axes.as_slice());
+                for axis in (0..self.dim).filter(|axis| reversed >> axis & 1 == 1) {
+                    board.invert_axis(Axis(axis));
+                }
+                board
+            })
+            .min_by_key(key)
+            .expect("every board is symmetric to itself");
+
+        let mut game = Self {
+            board: board.as_standard_layout().into_owned(),
+            ..self.clone()
+        };
+        game.hash = game.full_hash();
+        game
     }
 }
 
@@ -95,18 +222,25 @@ impl TryFrom<TicTacToeArgs> for TicTacToe {
     type Error = Error;
 
     fn try_from(value: TicTacToeArgs) -> Result<Self, Self::Error> {
-        let mut game = TicTacToe::new(value.dimensions, value.size);
-
-        // parse every move in args, e.g. 0-0 1-1 in args
-        for arg in value.moves {
-            let numbers: Result<Vec<usize>, Self::Error> = arg
-                .split('-')
-                .map(|num| num.parse::<usize>().map_err(|_| anyhow!("Not a number!")))
-                .collect();
+        if let Some(position) = value.position {
+            return Ok(Self::from_notation(&position)?);
+        }
 
-            move_failable(&mut game, &TicTacToeMove(numbers?.into_dimension()))?;
+        if let Some(board) = value.board {
+            return Ok(Self::from_board(&board, value.to_move.map(Into::into))?);
         }
 
+        let (Some(dimensions), Some(size)) = (value.dimensions, value.size) else {
+            return Err(anyhow!(
+                "expected the dimensions and size of the board, or a position"
+            ));
+        };
+
+        let mut game = TicTacToe::new(dimensions, size);
+
+        // parse every move in args, e.g. 0-0 1-1 in args
+        replay_parsed_moves(&mut game, &value.moves)?;
+
         Ok(game)
     }
 }
@@ -124,8 +258,16 @@ fn add_checked(a: Dim<IxDynImpl>, b: Vec<i32>) -> Option<Dim<IxDynImpl>> {
     Some(result)
 }
 
+/// The game the CLI plays without arguments, on the classic 3x3 board.
+impl Default for TicTacToe {
+    fn default() -> Self {
+        Self::new(2, 3)
+    }
+}
+
 impl TicTacToe {
-    fn new(dim: usize, size: usize) -> Self {
+    /// An empty board of `dim` dimensions, each `size` squares long, where X moves first.
+    pub fn new(dim: usize, size: usize) -> Self {
         // we want [SIZE; dim] but dim isn't a const - we have to get the slice from a vec
         let board = ArrayD::from_elem(IxDyn(&vec![size; dim]), None);
 
@@ -134,9 +276,33 @@ impl TicTacToe {
             size,
             board,
             move_count: 0,
+            hash: 0,
         }
     }
 
+    /// Sets up the position on `board`, written as in the notation but without the player to move
+    /// (e.g. `XO./.X./..O`), without replaying the moves that led to it.
+    ///
+    /// As X moves first, it's X's turn if there are as many Xs as Os, and O's if there's one more X.
+    /// `to_move` is checked against that.
+    pub fn from_board(board: &str, to_move: Option<PartizanPlayer>) -> Result<Self, NotationError> {
+        read_board(board, |rows| {
+            let player =
+                to_move.unwrap_or(if rows.matches('X').count() > rows.matches('O').count() {
+                    PartizanPlayer::Right
+                } else {
+                    PartizanPlayer::Left
+                });
+
+            Self::from_notation(&format!("{rows} {:?}", Square::from_player(player)))
+        })
+    }
+
+    /// The Zobrist key of `square` at the `index`th square of the board, in row-major order.
+    fn square_key(index: usize, square: Square) -> u64 {
+        zobrist_key(2 * index + (square == Square::O) as usize)
+    }
+
     /// Returns the square on this winning line.
     fn winning_line(&self, point: &Dim<IxDynImpl>, offset: &[i32]) -> Option<Square> {
         let square = self.board.get(point).unwrap();
@@ -175,32 +341,9 @@ impl TicTacToe {
             None
         }
     }
-}
-
-impl Game for TicTacToe {
-    type Move = TicTacToeMove;
-    type Iter<'a> = FilterMap<
-        IndexedIter<'a, Option<Square>, Dim<IxDynImpl>>,
-        fn((Dim<IxDynImpl>, &Option<Square>)) -> Option<Self::Move>,
-    >;
-    type Player = PartizanPlayer;
-    type MoveError = TicTacToeMoveError;
-
-    fn max_moves(&self) -> Option<usize> {
-        Some(self.size.pow(self.dim as u32))
-    }
-
-    fn move_count(&self) -> usize {
-        self.move_count
-    }
-
-    fn state(&self) -> GameState<Self::Player> {
-        // check if tie
-        if Some(self.move_count()) == self.max_moves() {
-            return GameState::Tie;
-        }
 
-        // check every move
+    /// Returns the player who has filled a line, if any.
+    fn line_owner(&self) -> Option<PartizanPlayer> {
         for (index, square) in self.board.indexed_iter() {
             if square.is_none() {
                 continue;
@@ -209,13 +352,24 @@ impl Game for TicTacToe {
             let point = index.into_dimension();
             for offset in offsets(&point, self.size) {
                 if let Some(square) = self.winning_line(&point, &offset) {
-                    return GameState::Win(square.to_player());
+                    return Some(square.to_player());
                 }
             }
         }
 
-        GameState::Playable
+        None
     }
+}
+
+#[placement_game(grid = board, winner = line_owner)]
+impl Game for TicTacToe {
+    type Move = TicTacToeMove;
+    type Iter<'a> = FilterMap<
+        IndexedIter<'a, Option<Square>, Dim<IxDynImpl>>,
+        fn((Dim<IxDynImpl>, &Option<Square>)) -> Option<Self::Move>,
+    >;
+    type Player = PartizanPlayer;
+    type MoveError = TicTacToeMoveError;
 
     fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
         if self.board.get(m.0.clone()).unwrap().is_none() {
@@ -223,6 +377,12 @@ impl Game for TicTacToe {
 
             *self.board.get_mut(m.0.clone()).unwrap() = Some(square);
             self.move_count += 1;
+
+            let index =
+                m.0.slice()
+                    .iter()
+                    .fold(0, |index, &i| index * self.size + i);
+            self.hash ^= Self::square_key(index, square);
             Ok(())
         } else {
             Err(TicTacToeMoveError::NonEmptySquare(m.clone()))
@@ -241,40 +401,155 @@ impl Game for TicTacToe {
             })
     }
 
+    fn move_count_hint(&self) -> (usize, Option<usize>) {
+        // every move fills an empty square
+        let count = self.board.len() - self.move_count;
+        (count, Some(count))
+    }
+
+    /// Nobody can win before their `size`th move, which rules out most positions,
+    /// and after that only the lines through each empty square are checked, without making any moves.
+    fn cheap_win_check(&self) -> bool {
+        true
+    }
+
     fn find_immediately_resolvable_game(&self) -> Result<Option<Self>, Self::MoveError> {
         // check if the amount of moves is less than (size * 2) - 1
-        // if it is, then it's impossible to win
-        if self.move_count + 1 < self.size * 2 - 1 {
+        // if it is, then it's impossible to win, and the game can only end by filling the board
+        if self.move_count + 1 < self.size * 2 - 1 && self.move_count + 1 < self.board.len() {
             return Ok(None);
         }
 
-        let mut best_non_winning_game: Option<Self> = None;
-
-        for m in &mut self.possible_moves() {
-            let mut new_self = self.clone();
-            new_self.make_move(&m)?;
-            match new_self.state() {
-                GameState::Playable => continue,
-                GameState::Tie => best_non_winning_game = Some(new_self),
-                GameState::Win(winning_player) => {
-                    if winning_player == self.player().turn() {
-                        return Ok(Some(new_self));
-                    } else if best_non_winning_game.is_none() {
-                        best_non_winning_game = Some(new_self)
-                    }
+        // only the lines through a move's square can be finished by it
+        let square = Square::from_player(self.player());
+        let board = self
+            .board
+            .as_slice()
+            .expect("boards are kept in row-major order");
+
+        Lines::with(self.board.shape(), |lines| {
+            find_resolvable(self, |m| {
+                let cell = m.0.slice().iter().fold(0, |cell, &i| cell * self.size + i);
+                let finished = lines.through(cell).any(|(line, at)| {
+                    line.len() == self.size
+                        && run_through(line, at, |other| board[other] == Some(square)) == self.size
+                });
+
+                if finished {
+                    GameState::Win(self.player())
+                } else if self.move_count + 1 == board.len() {
+                    GameState::Tie
+                } else {
+                    GameState::Playable
                 }
+            })
+        })
+    }
+
+    /// A mark on a square that's on no line either player can still fill can't finish a line or block one,
+    /// so it's never better than a mark on a square that is: both use up a move and a square.
+    /// Once no line can be filled at all, every move ties, so all but the first are left out.
+    fn dominated_moves(&self) -> Vec<Self::Move> {
+        let board = self
+            .board
+            .as_slice()
+            .expect("boards are kept in row-major order");
+
+        Lines::with(self.board.shape(), |lines| {
+            // a line that's as long as the board can still be filled if only one player has marked it
+            let open = |line: &[usize]| {
+                let mut marks = line.iter().filter_map(|&cell| board[cell]);
+                line.len() == self.size
+                    && marks
+                        .next()
+                        .map_or(true, |first| marks.all(|mark| mark == first))
             };
+
+            let dead = self
+                .possible_moves()
+                .filter(|m| {
+                    let cell = m.0.slice().iter().fold(0, |cell, &i| cell * self.size + i);
+                    !lines.through(cell).any(|(line, _)| open(line))
+                })
+                .collect::<Vec<_>>();
+
+            // no line can be filled anymore
+            if dead.len() == self.board.len() - self.move_count {
+                dead.into_iter().skip(1).collect()
+            } else {
+                dead
+            }
+        })
+    }
+}
+
+/// The filled squares are enough to tell positions apart, as they tell whose move it is.
+impl IncrementalHash for TicTacToe {
+    fn incremental_hash(&self) -> u64 {
+        self.hash
+    }
+
+    fn full_hash(&self) -> u64 {
+        self.board
+            .iter()
+            .enumerate()
+            .filter_map(|(index, square)| square.map(|square| Self::square_key(index, square)))
+            .fold(0, |hash, key| hash ^ key)
+    }
+}
+
+/// Enumerates every board of the same dimensions and size that can be reached,
+/// reading its squares in row-major order as the digits of a number in base 3.
+///
+/// There are 3 to the power of the squares of them, so only small boards can be enumerated.
+impl EnumerableGame for TicTacToe {
+    fn position_count(&self) -> usize {
+        3usize
+            .checked_pow(self.board.len() as u32)
+            .unwrap_or(usize::MAX)
+    }
+
+    fn index(&self, position: &Self) -> Option<usize> {
+        if position.dim != self.dim || position.size != self.size {
+            return None;
         }
 
-        Ok(best_non_winning_game)
+        Some(position.board.iter().fold(0, |index, square| {
+            index * 3
+                + match square {
+                    None => 0,
+                    Some(Square::X) => 1,
+                    Some(Square::O) => 2,
+                }
+        }))
     }
 
-    fn player(&self) -> Self::Player {
-        if self.move_count % 2 == 0 {
-            PartizanPlayer::Left
-        } else {
-            PartizanPlayer::Right
+    fn unindex(&self, mut index: usize) -> Option<Self> {
+        if index >= self.position_count() {
+            return None;
+        }
+
+        let mut squares = vec![None; self.board.len()];
+        for square in squares.iter_mut().rev() {
+            *square = match index % 3 {
+                0 => None,
+                1 => Some(Square::X),
+                _ => Some(Square::O),
+            };
+            index /= 3;
         }
+
+        Self::from_squares(self.dim, self.size, squares).ok()
+    }
+}
+
+impl PlacementGame for TicTacToe {
+    fn grid_size(&self) -> Vec<usize> {
+        vec![self.size; self.dim]
+    }
+
+    fn cell(&self, m: &Self::Move) -> Vec<usize> {
+        m.0.as_array_view().to_vec()
     }
 }
 
@@ -303,7 +578,27 @@ fn offsets(dim: &Dim<IxDynImpl>, size: usize) -> Vec<Vec<i32>> {
 
 impl Display for TicTacToeMove {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", self.0.as_array_view().as_slice().unwrap())
+        write!(f, "{}", self.0.as_array_view().iter().join("-"))
+    }
+}
+
+impl Serialize for TicTacToeMove {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TicTacToeMove {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let token = String::deserialize(deserializer)?;
+
+        let numbers = token
+            .split('-')
+            .map(parse_number)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(de::Error::custom)?;
+
+        Ok(TicTacToeMove(numbers.into_dimension()))
     }
 }
 
@@ -316,17 +611,285 @@ impl Display for TicTacToe {
     }
 }
 
+/// Only two dimensional boards are drawn as a grid.
+impl Render for TicTacToe {
+    fn grid(&self) -> Option<Grid> {
+        if self.dim != 2 {
+            return None;
+        }
+
+        let playable = self.state() == GameState::Playable;
+
+        Some(Grid::new(
+            (0..self.size)
+                .map(|row| {
+                    (0..self.size)
+                        .map(|column| match self.board[IxDyn(&[row, column])] {
+                            Some(Square::X) => Cell::piece('X', PartizanPlayer::Left),
+                            Some(Square::O) => Cell::piece('O', PartizanPlayer::Right),
+                            None if playable => Cell::empty('-').legal(),
+                            None => Cell::empty('-'),
+                        })
+                        .collect()
+                })
+                .collect(),
+        ))
+    }
+
+    fn move_cells(&self, m: &Self::Move) -> Vec<(usize, usize)> {
+        match m.0.as_array_view().as_slice() {
+            Some(&[row, column]) => vec![(row, column)],
+            _ => Vec::new(),
+        }
+    }
+}
+
 impl Debug for TicTacToe {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         <Self as Display>::fmt(self, f)
     }
 }
 
+impl TicTacToe {
+    /// The rows of the board along its last dimension, where empty squares are marked with `empty`.
+    fn rows(&self, empty: char) -> Vec<String> {
+        let squares = self
+            .board
+            .iter()
+            .map(|square| match square {
+                Some(Square::X) => 'X',
+                Some(Square::O) => 'O',
+                None => empty,
+            })
+            .collect::<Vec<_>>();
+
+        squares
+            .chunks(self.size)
+            .map(|row| row.iter().collect())
+            .collect()
+    }
+}
+
+/// Serializes the board as its rows along the last dimension,
+/// where empty squares are marked with `-`.
+impl Serialize for TicTacToe {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("TicTacToe", 3)?;
+        state.serialize_field("dimensions", &self.dim)?;
+        state.serialize_field("size", &self.size)?;
+        state.serialize_field("board", &self.rows('-'))?;
+        state.end()
+    }
+}
+
+/// A serialized [`TicTacToe`], before it's checked.
+#[derive(Deserialize)]
+struct TicTacToePosition {
+    dimensions: usize,
+    size: usize,
+    board: Vec<String>,
+}
+
+impl TryFrom<TicTacToePosition> for TicTacToe {
+    type Error = PositionError;
+
+    fn try_from(position: TicTacToePosition) -> Result<Self, Self::Error> {
+        let TicTacToePosition {
+            dimensions,
+            size,
+            board,
+        } = position;
+
+        if dimensions == 0 || size == 0 {
+            return Err(PositionError::impossible(
+                "the board needs at least one dimension and a size of at least 1",
+            ));
+        }
+
+        let rows = size
+            .checked_pow(dimensions as u32 - 1)
+            .ok_or_else(|| PositionError::impossible("the board is too large"))?;
+        let squares = parse_board(&board, size, rows, "XO-")?
+            .into_iter()
+            .flatten()
+            .map(|square| match square {
+                'X' => Some(Square::X),
+                'O' => Some(Square::O),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        Self::from_squares(dimensions, size, squares)
+    }
+}
+
+impl TicTacToe {
+    /// The position with `squares` filled, in row-major order, checking that it can be reached.
+    fn from_squares(
+        dimensions: usize,
+        size: usize,
+        squares: Vec<Option<Square>>,
+    ) -> Result<Self, PositionError> {
+        let x_count = squares.iter().filter(|&&s| s == Some(Square::X)).count();
+        let o_count = squares.iter().filter(|&&s| s == Some(Square::O)).count();
+
+        // X moves first, and the players alternate
+        if x_count != o_count && x_count != o_count + 1 {
+            return Err(PositionError::impossible(format!(
+                "X moves first, so X must have as many squares as O or one more, but X has {x_count} and O has {o_count}"
+            )));
+        }
+
+        let mut game = Self {
+            dim: dimensions,
+            size,
+            board: ArrayD::from_shape_vec(IxDyn(&vec![size; dimensions]), squares)
+                .expect("the board has size^dimensions squares"),
+            move_count: x_count + o_count,
+            hash: 0,
+        };
+        game.hash = game.full_hash();
+
+        if let GameState::Win(winner) = game.state() {
+            if winner == game.player() {
+                return Err(PositionError::impossible(format!(
+                    "{:?} already won, but the game went on",
+                    Square::from_player(winner)
+                )));
+            }
+        }
+
+        Ok(game)
+    }
+}
+
+/// Written as the rows of the board along its last dimension, separated by `/`,
+/// where empty squares are marked with `.`, followed by the player to move: `XOX/.O./..X X`.
+///
+/// The board has as many dimensions as fit its rows: a board 3 squares wide
+/// has 3 rows in two dimensions, 9 in three, and so on.
+impl NotatedGame for TicTacToe {
+    fn to_notation(&self) -> String {
+        format!(
+            "{} {:?}",
+            self.rows('.').join("/"),
+            Square::from_player(self.player())
+        )
+    }
+
+    fn from_notation(notation: &str) -> Result<Self, NotationError> {
+        let fields = fields(notation, &["board", "player to move"], &[])?;
+        let rows = fields[0].board("XO.", None)?;
+        let size = rows[0].len();
+
+        // every dimension past the first multiplies the rows by the size
+        let mut dimensions = 1;
+        let mut row_count = 1usize;
+        while row_count < rows.len() && size > 1 {
+            row_count = row_count.saturating_mul(size);
+            dimensions += 1;
+        }
+
+        if row_count != rows.len() {
+            return Err(fields[0].error(format!(
+                "a board {size} squares wide has 1, {size}, {}, ... rows, not {}",
+                size * size,
+                rows.len()
+            )));
+        }
+
+        let game = Self::try_from(TicTacToePosition {
+            dimensions,
+            size,
+            board: rows
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|&square| if square == '.' { '-' } else { square })
+                        .collect()
+                })
+                .collect(),
+        })
+        .map_err(|err| fields[0].error(err.to_string()))?;
+
+        let player = fields[1].choice("player to move", &[("X", Square::X), ("O", Square::O)])?;
+        let expected = Square::from_player(game.player());
+        if player != expected {
+            return Err(fields[1].error(format!(
+                "it's {expected:?}'s turn, as {} moves were made",
+                game.move_count
+            )));
+        }
+
+        Ok(game)
+    }
+}
+
+/// Positions for the `bench` subcommand.
+pub fn bench_positions() -> Vec<BenchPosition<TicTacToeArgs>> {
+    [
+        ("empty 3x3", true, 2, 3, ""),
+        ("3x3 after 1-1 0-0", true, 2, 3, "1-1 0-0"),
+        ("empty 4x4", false, 2, 4, ""),
+    ]
+    .into_iter()
+    .map(|(name, quick, dimensions, size, moves)| BenchPosition {
+        name,
+        quick,
+        args: TicTacToeArgs {
+            dimensions: Some(dimensions),
+            size: Some(size),
+            moves: parse_moves(moves),
+            position: None,
+            board: None,
+            to_move: None,
+        },
+    })
+    .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use game_solver::{move_scores, GameSolveError};
-    use std::collections::HashMap;
+    use crate::{
+        chomp::Chomp,
+        util::move_score::{optimal_moves_testing, window_results_testing},
+    };
+    use game_solver::{
+        census::census_with,
+        dominance::check_dominated_moves,
+        mcts::Mcts,
+        move_scores, optimal_moves,
+        ordering::MoveOrdering,
+        perft,
+        player::ImpartialPlayer,
+        proof_number::ProofNumber,
+        puzzle::{difficulty, generate_puzzles, PuzzleFilter},
+        random::random_position,
+        session::{PonderUntil, Session},
+        solve, solve_detailed, solve_ordered,
+        stats::Stats,
+        strategy::{Negamax, Outcome, Strategy},
+        transposition::Score,
+        GameSolveError,
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::collections::HashSet;
+    use std::{
+        collections::HashMap,
+        sync::{atomic::AtomicBool, Arc},
+    };
+    #[cfg(feature = "rayon")]
+    use {
+        game_solver::{
+            think::think,
+            transposition::{DepthCache, Entry, TranspositionCache, TranspositionTable},
+        },
+        std::{
+            collections::hash_map::RandomState,
+            time::{Duration, Instant},
+        },
+    };
 
     fn move_scores_unwrapped(game: &TicTacToe) -> Vec<(TicTacToeMove, isize)> {
         move_scores(game, &mut HashMap::new(), None, &None)
@@ -425,4 +988,607 @@ mod tests {
             .iter()
             .all(|(_, score)| *score == 0));
     }
+
+    #[test]
+    fn perft_counts() {
+        let game = TicTacToe::new(2, 3);
+
+        // the first wins happen on the fifth move, so perft(6) already
+        // leaves out sequences that ended early. The full counts (to perft(9))
+        // are 148176, 200448, and 127872, but take too long to check in debug builds.
+        let counts = [1, 9, 72, 504, 3024, 15120, 54720];
+
+        for (depth, count) in counts.into_iter().enumerate() {
+            assert_eq!(perft(&game, depth), count, "perft({depth})");
+        }
+    }
+
+    #[test]
+    fn puzzles_have_one_winning_move() {
+        let game = TicTacToe::new(2, 3);
+        let puzzles = generate_puzzles(
+            &game,
+            &mut StdRng::seed_from_u64(0),
+            5,
+            PuzzleFilter::UniqueWin,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(puzzles.len(), 5);
+
+        for puzzle in puzzles {
+            let winning_moves = move_scores_unwrapped(&puzzle.position)
+                .into_iter()
+                .filter(|(_, score)| *score > 0)
+                .collect::<Vec<_>>();
+
+            assert_eq!(winning_moves, [(puzzle.solution, puzzle.score)]);
+        }
+    }
+
+    #[test]
+    fn forks_are_easier_than_deep_chomp_wins() {
+        let mut table = HashMap::new();
+        // blocking O's diagonal also forks X's top row and right column
+        let fork = TicTacToe::from_notation("X../.O./O.X X").unwrap();
+        let fork = difficulty(&fork, &mut table).unwrap().unwrap();
+        assert_eq!(fork.depth_to_win, 3);
+        assert_eq!(fork.traps, 0);
+
+        let chomp = difficulty(&Chomp::new(6, 4), &mut HashMap::new())
+            .unwrap()
+            .unwrap();
+        assert!(chomp.depth_to_win > fork.depth_to_win);
+        assert!(chomp.forcing_ratio > 0.0);
+        assert!(chomp.rating() > fork.rating(), "{chomp:?} {fork:?}");
+    }
+
+    #[test]
+    fn dominated_squares_are_on_no_open_line() {
+        let dominated = |board: &str| {
+            let game = TicTacToe::from_board(board, None).unwrap();
+            game.dominated_moves()
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(dominated(".../.../...").is_empty());
+        // the top row and the third column are blocked, and the square they share is on no diagonal
+        assert_eq!(dominated("XO../..X./..O./...."), ["0-2"]);
+        // no line can be filled, so only the first move is searched
+        assert_eq!(dominated("XO.."), ["3"]);
+    }
+
+    #[test]
+    fn leaving_out_dominated_moves_keeps_the_scores() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for (game, plies) in [
+            (TicTacToe::new(2, 3), [2, 3, 4, 5]),
+            (TicTacToe::new(2, 4), [6, 7, 8, 9]),
+            (TicTacToe::new(1, 4), [0, 1, 2, 3]),
+        ] {
+            for plies in plies.repeat(2) {
+                let (position, _) = random_position(&game, plies, &mut rng).unwrap();
+                let (left_out, searched) = check_dominated_moves(&position).unwrap();
+                assert_eq!(left_out, searched, "{}", position.to_notation());
+            }
+        }
+    }
+
+    #[test]
+    fn random_positions_agree_with_best_reply() {
+        let game = TicTacToe::new(2, 3);
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for plies in [2, 3, 4, 5, 6, 7].repeat(3) {
+            let (position, played) = random_position(&game, plies, &mut rng).unwrap();
+            // the game can end before every move is played
+            if position.state() != GameState::Playable {
+                continue;
+            }
+            assert_eq!(played, plies);
+            assert_eq!(position.move_count(), plies);
+
+            let score = solve(&position, &mut HashMap::new(), None, &None).unwrap();
+            let (best_move, best_score) = move_scores_unwrapped(&position)
+                .into_iter()
+                .max_by_key(|(_, score)| *score)
+                .unwrap();
+
+            // the score of a position is the score of its best move,
+            // which is the negated score of the position after it
+            assert_eq!(score, best_score);
+
+            let mut reply = position.clone();
+            reply.make_move(&best_move).unwrap();
+            if reply.state() == GameState::Playable {
+                assert_eq!(
+                    solve(&reply, &mut HashMap::new(), None, &None).unwrap(),
+                    -score
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn serde_round_trip() {
+        for (dimensions, moves) in [(2, "1-1 0-0 2-2"), (3, "1-1-1 0-0-2 2-1-0 0-0-0")] {
+            let game: TicTacToe = TicTacToeArgs {
+                dimensions: Some(dimensions),
+                size: Some(3),
+                moves: moves.split(' ').map(String::from).collect(),
+                position: None,
+                board: None,
+                to_move: None,
+            }
+            .try_into()
+            .unwrap();
+
+            let json = serde_json::to_string(&game).unwrap();
+            assert_eq!(serde_json::from_str::<TicTacToe>(&json).unwrap(), game);
+        }
+
+        assert_eq!(
+            serde_json::from_str::<TicTacToeMove>(r#""2-0-1""#).unwrap(),
+            TicTacToeMove(vec![2, 0, 1].into_dimension())
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn thinking_long_enough_solves_the_position() {
+        for notation in [".../.../... X", "X../.O./... X", "XO./.X./..O X"] {
+            let game = TicTacToe::from_notation(notation).unwrap();
+            let table = Arc::new(DepthCache::<_, RandomState>::with_capacity(1 << 16));
+            let thought = think(&game, &table, Instant::now() + Duration::from_secs(600))
+                .unwrap()
+                .unwrap();
+
+            assert!(thought.complete, "{notation}");
+            assert_eq!(
+                thought.score,
+                solve(&game, &mut HashMap::new(), None, &None).unwrap(),
+                "{notation}"
+            );
+            assert!(
+                optimal_moves_testing(&game).contains(&thought.best_move),
+                "{notation}"
+            );
+            assert_eq!(thought.principal_variation[0], thought.best_move);
+        }
+    }
+
+    #[test]
+    fn players_serialize_by_name() {
+        let game = TicTacToe::from_notation("XXX/OO./... O").unwrap();
+        let winner = game.winner().unwrap();
+        assert_eq!(winner.to_string(), "Left");
+        assert_eq!(serde_json::to_string(&winner).unwrap(), r#""Left""#);
+        assert_eq!(
+            serde_json::from_str::<PartizanPlayer>(r#""Right""#).unwrap(),
+            game.player()
+        );
+
+        assert_eq!(
+            PartizanPlayer::from_move_count(game.move_count()),
+            game.player()
+        );
+        assert_eq!(
+            ImpartialPlayer::of(winner, game.player()),
+            ImpartialPlayer::Previous
+        );
+    }
+
+    #[test]
+    fn serde_rejects_impossible_positions() {
+        let error = |board: &str| {
+            serde_json::from_str::<TicTacToe>(&format!(
+                r#"{{"dimensions":2,"size":3,"board":{board}}}"#
+            ))
+            .unwrap_err()
+            .to_string()
+        };
+
+        assert!(error(r#"["O--","---","---"]"#).contains("X moves first"));
+        assert!(error(r#"["XXX","OO-","O--"]"#).contains("X already won"));
+        assert!(error(r#"["X--","---"]"#).contains("3 rows"));
+        assert!(error(r#"["X--","-?-","---"]"#).contains("`?`"));
+    }
+
+    #[test]
+    fn notation_round_trip() {
+        for (dimensions, moves, notation) in [
+            (2, vec![], ".../.../... X"),
+            (2, vec!["0-0", "1-1", "2-0"], "X../.O./X.. O"),
+            (
+                3,
+                vec!["1-1-1", "0-0-2"],
+                "..O/.../.../.../.X./.../.../.../... X",
+            ),
+        ] {
+            let game: TicTacToe = TicTacToeArgs {
+                dimensions: Some(dimensions),
+                size: Some(3),
+                moves: moves.into_iter().map(String::from).collect(),
+                position: None,
+                board: None,
+                to_move: None,
+            }
+            .try_into()
+            .unwrap();
+
+            assert_eq!(game.to_notation(), notation);
+            assert_eq!(TicTacToe::from_notation(notation).unwrap(), game);
+        }
+
+        // fields can be separated by any whitespace
+        assert_eq!(
+            TicTacToe::from_notation("\tX../.O./X..   O ").unwrap(),
+            TicTacToe::from_notation("X../.O./X.. O").unwrap()
+        );
+    }
+
+    #[test]
+    fn boards_agree_with_moves() {
+        for (moves, board) in [
+            (vec![], ".../.../..."),
+            (vec!["1-1"], ".../.X./..."),
+            (vec!["1-1", "0-0", "2-2"], "O../.X./..X"),
+        ] {
+            let game: TicTacToe = TicTacToeArgs {
+                moves: moves.into_iter().map(String::from).collect(),
+                ..Default::default()
+            }
+            .try_into()
+            .unwrap();
+
+            let from_board = TicTacToe::from_board(board, None).unwrap();
+            assert_eq!(from_board, game);
+            assert_eq!(from_board.move_count(), game.move_count());
+            assert_eq!(
+                TicTacToe::from_board(board, Some(game.player())).unwrap(),
+                game
+            );
+        }
+
+        // rows can be on their own lines
+        assert_eq!(
+            TicTacToe::from_board("O..\n.X.\n..X\n", None).unwrap(),
+            TicTacToe::from_board("O../.X./..X", None).unwrap()
+        );
+    }
+
+    #[test]
+    fn symmetric_boards_share_a_canonical_position() {
+        let canonical = |board| {
+            TicTacToe::from_board(board, None)
+                .unwrap()
+                .canonical()
+                .to_notation()
+        };
+
+        // every corner, and every edge, is the same opening
+        for corners in [".../.../..X", "X../.../...", "..X/.../..."] {
+            assert_eq!(canonical(corners), canonical(".../.../X.."));
+        }
+        assert_eq!(canonical(".X./.../..."), canonical(".../X../..."));
+        assert_ne!(canonical(".X./.../..."), canonical("X../.../..."));
+
+        // the hash is the one of the board it ends up with
+        let game = TicTacToe::from_board("XO./.../...", None)
+            .unwrap()
+            .canonical();
+        assert_eq!(game.incremental_hash(), game.full_hash());
+        assert_eq!(game.move_count(), 2);
+
+        // three dimensions have more symmetries than rotating each layer
+        let corner = |moves: &[&str]| {
+            let game: TicTacToe = TicTacToeArgs {
+                dimensions: Some(3),
+                moves: moves.iter().map(|m| m.to_string()).collect(),
+                ..Default::default()
+            }
+            .try_into()
+            .unwrap();
+            game.canonical()
+        };
+        assert_eq!(corner(&["0-0-0"]), corner(&["2-0-2"]));
+        assert_eq!(corner(&["0-1-1"]), corner(&["1-1-2"]));
+    }
+
+    #[test]
+    fn census_finds_the_classic_counts() {
+        let game = TicTacToe::new(2, 3);
+        let census = census_with(
+            &game,
+            usize::MAX,
+            TicTacToe::canonical,
+            &mut HashSet::new(),
+            &mut HashSet::new(),
+        );
+
+        assert_eq!(census.terminal, 255_168);
+        assert_eq!(census.unique, 5_478);
+        assert_eq!(census.canonical, 765);
+        assert_eq!(
+            census.wins,
+            [
+                (PartizanPlayer::Left, 131_184),
+                (PartizanPlayer::Right, 77_904)
+            ]
+        );
+        assert_eq!(census.ties, 46_080);
+
+        // nobody can win before X's third move
+        assert_eq!(census.plies[5].terminal, 1_440);
+        assert!(census.plies[..5].iter().all(|ply| ply.terminal == 0));
+    }
+
+    #[test]
+    fn enumerated_boards_round_trip() {
+        let game = TicTacToe::new(2, 3);
+        let boards = (0..game.position_count())
+            .filter_map(|index| game.unindex(index).map(|board| (index, board)))
+            .collect::<Vec<_>>();
+
+        // more than the 5478 reachable boards, as a line for each player isn't ruled out
+        assert_eq!(boards.len(), 5_556);
+        assert_eq!(game.index(&game), Some(0));
+        for (index, board) in &boards {
+            assert_eq!(game.index(board), Some(*index), "{board}");
+        }
+
+        // the last square is the lowest digit, and O can't have more squares than X
+        assert_eq!(game.unindex(1).unwrap().to_notation(), ".../.../..X O");
+        assert_eq!(game.unindex(2), None);
+        assert_eq!(game.index(&TicTacToe::new(2, 4)), None);
+    }
+
+    #[test]
+    fn boards_reject_impossible_positions() {
+        let error = |board: &str, to_move| TicTacToe::from_board(board, to_move).unwrap_err();
+
+        assert_eq!(error("XO./.Z./..O", None).span(), 5..6);
+        assert!(error("XX./.X./..O", None)
+            .message()
+            .contains("X has 3 and O has 1"));
+        assert!(error("OO./.X./...", None)
+            .message()
+            .contains("X has 1 and O has 2"));
+
+        // the counts say whose turn it is
+        let wrong_turn = error("X../.O./...", Some(PartizanPlayer::Right));
+        assert_eq!(wrong_turn.span(), 0..11);
+        assert!(
+            wrong_turn.message().contains("it's X's turn"),
+            "{wrong_turn}"
+        );
+    }
+
+    #[test]
+    fn notation_rejects_invalid_positions() {
+        let span = |notation: &str| TicTacToe::from_notation(notation).unwrap_err().span();
+
+        // an unknown square
+        assert_eq!(span("XOX/.Z./..X X"), 5..6);
+        // a short row
+        assert_eq!(span("XOX/.O/..X X"), 4..6);
+        // rows that don't fit any number of dimensions
+        assert_eq!(span("XOX/.O./..X/... X"), 0..15);
+        // O moved first
+        assert_eq!(span("O../.../... X"), 0..11);
+        // it's O's turn
+        assert_eq!(span("X../.../... X"), 12..13);
+        assert_eq!(span("X../.../... Z"), 12..13);
+        // missing and extra fields
+        assert_eq!(span("X../.../..."), 11..11);
+        assert_eq!(span("X../.../... O O"), 14..15);
+        assert_eq!(span(""), 0..0);
+    }
+
+    /// Solves positions with negamax, sharing one transposition table.
+    struct Solved(Negamax<HashMap<TicTacToe, Score>, TicTacToeMove>);
+
+    impl Solved {
+        fn new() -> Self {
+            Self(Negamax::new(HashMap::new()))
+        }
+
+        /// The outcome of `game` for the player to move.
+        fn outcome(&mut self, game: &TicTacToe) -> Option<Outcome> {
+            self.0.evaluate(game).unwrap().value.outcome()
+        }
+
+        /// The outcome of `game` for the player to move, if they play `m`.
+        fn outcome_after(&mut self, game: &TicTacToe, m: &TicTacToeMove) -> Option<Outcome> {
+            let mut board = game.clone();
+            board.make_move(m).unwrap();
+            self.outcome(&board).map(Outcome::flip)
+        }
+    }
+
+    #[test]
+    fn proof_number_search_agrees_with_negamax() {
+        // the start, and (up to symmetry) every position after one move
+        let positions = [
+            ".../.../... X",
+            "X../.../... O",
+            ".X./.../... O",
+            ".../.X./... O",
+        ]
+        .map(|notation| TicTacToe::from_notation(notation).unwrap());
+
+        let mut solved = Solved::new();
+        let mut proof_number = ProofNumber::new();
+        for game in positions {
+            let expected = solved.outcome(&game);
+            let evaluation = proof_number.evaluate(&game).unwrap();
+
+            assert_eq!(evaluation.value.outcome(), expected, "{game}");
+            let m = evaluation.best_move.unwrap();
+            assert_eq!(solved.outcome_after(&game, &m), expected, "{game}");
+        }
+    }
+
+    #[test]
+    fn mcts_finds_the_only_good_moves() {
+        let mut solved = Solved::new();
+        let mut mcts = Mcts::new(StdRng::seed_from_u64(0));
+        mcts.iterations = 3000;
+
+        for notation in [
+            // O has to take the middle to not lose
+            "X../.../... O",
+            // X wins by completing the top row
+            "XX./OO./... X",
+            // O has to block the top row
+            "XX./.O./... O",
+        ] {
+            let game = TicTacToe::from_notation(notation).unwrap();
+            let evaluation = mcts.evaluate(&game).unwrap();
+            let m = evaluation.best_move.unwrap();
+
+            assert_eq!(
+                solved.outcome_after(&game, &m),
+                solved.outcome(&game),
+                "{notation}"
+            );
+        }
+    }
+
+    #[test]
+    fn pondered_replies_are_answered_from_the_table() {
+        let game = TicTacToe::from_notation("X../.../... O").unwrap();
+        let reply = TicTacToeMove(vec![1, 1].into_dimension());
+
+        let mut session = Session::new(game.clone(), HashMap::new());
+        let never = Arc::new(AtomicBool::new(false));
+        let report = session.ponder(PonderUntil::Cancelled(never)).unwrap();
+        assert!(report.finished);
+        assert_eq!(report.replies, 8);
+
+        session.apply(&reply).unwrap();
+        let pondered = session.evaluate().unwrap();
+
+        let mut fresh = Session::new(game, HashMap::new());
+        fresh.apply(&reply).unwrap();
+        let fresh = fresh.evaluate().unwrap();
+
+        assert_eq!(pondered.value, fresh.value);
+        // the ponder already searched every position after the reply
+        assert!(
+            pondered.nodes * 10 < fresh.nodes,
+            "{} vs {}",
+            pondered.nodes,
+            fresh.nodes
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn solved_positions_can_be_dumped() {
+        let game = TicTacToe::new(2, 3);
+        let mut table = Arc::new(TranspositionCache::<TicTacToe, RandomState>::with_capacity(
+            1 << 16,
+        ));
+        let score = solve(&game, &mut table, None, &None).unwrap();
+
+        assert_eq!(table.entry(&game), Some(Entry::from(Score::Exact(score))));
+
+        let entries = table.iter().collect::<Vec<_>>();
+        assert!(entries.len() > 1);
+        assert!(entries
+            .iter()
+            .any(|(position, entry)| **position == game && entry.score == Score::Exact(score)));
+    }
+
+    #[test]
+    fn optimal_moves_match_the_best_scores() {
+        for notation in ["X../.../... O", "X../.O./..X O", "XO./.X./... O"] {
+            let game = TicTacToe::from_notation(notation).unwrap();
+
+            assert_eq!(
+                optimal_moves(&game, &mut HashMap::new()).unwrap(),
+                optimal_moves_testing(&game),
+                "{notation}"
+            );
+        }
+    }
+
+    #[test]
+    fn central_cells_first_searches_fewer_positions() {
+        let game = TicTacToe::from_notation("X.../.OX./..X./O... O").unwrap();
+
+        let explore = |ordering: &MoveOrdering<TicTacToe>| {
+            let stats = Stats::new(game.player(), game.move_count());
+            let score =
+                solve_ordered(&game, &mut HashMap::new(), Some(&stats), &None, ordering).unwrap();
+            (score, stats.states_explored.into_inner())
+        };
+
+        let (score, nodes) = explore(&MoveOrdering::GameOrder);
+
+        let (tt_first_score, _) = explore(&MoveOrdering::TtFirst);
+        assert_eq!(tt_first_score, score);
+
+        for ordering in [
+            MoveOrdering::central_cells_first(),
+            MoveOrdering::Chain(vec![
+                MoveOrdering::TtFirst,
+                MoveOrdering::central_cells_first(),
+            ]),
+        ] {
+            let (ordered_score, ordered_nodes) = explore(&ordering);
+            assert_eq!(ordered_score, score);
+            assert!(ordered_nodes < nodes, "{ordered_nodes} >= {nodes}");
+        }
+    }
+
+    #[test]
+    fn table_first_cuts_off_on_the_first_move_more_often() {
+        let game = TicTacToe::from_notation("X.../.OX./..X./O... O").unwrap();
+
+        let first_move_cutoff_rate = |ordering: &MoveOrdering<TicTacToe>| {
+            let (score, stats) =
+                solve_detailed(&game, &mut HashMap::new(), &None, ordering).unwrap();
+            let plies = stats.plies();
+
+            // every cutoff is counted once, at its ply and by the move it was on
+            let cutoffs = plies.iter().map(|ply| ply.cutoffs).sum::<u64>();
+            assert_eq!(cutoffs, stats.pruning_cutoffs.into_inner());
+            for ply in &plies {
+                assert_eq!(ply.cutoff_moves.iter().sum::<u64>(), ply.cutoffs);
+            }
+            assert_eq!(
+                plies.iter().map(|ply| ply.nodes).sum::<u64>(),
+                stats.states_explored.into_inner()
+            );
+
+            let first_moves = plies.iter().map(|ply| ply.cutoff_moves[0]).sum::<u64>();
+            (score, first_moves as f64 / cutoffs as f64)
+        };
+
+        let (score, rate) = first_move_cutoff_rate(&MoveOrdering::GameOrder);
+        let (tt_first_score, tt_first_rate) = first_move_cutoff_rate(&MoveOrdering::TtFirst);
+        assert_eq!(tt_first_score, score);
+        assert!(tt_first_rate > rate, "{tt_first_rate} <= {rate}");
+    }
+
+    #[test]
+    fn windows_agree_with_the_score() {
+        window_results_testing(&TicTacToe::new(2, 3), 8, 40);
+    }
+
+    #[test]
+    fn resolvable_games_agree_with_making_every_move() {
+        use crate::util::resolve::tests::agrees_with_making_every_move;
+
+        // every position that isn't over, on boards of every dimension
+        assert_eq!(agrees_with_making_every_move(TicTacToe::new(2, 3)), 4520);
+        assert_eq!(agrees_with_making_every_move(TicTacToe::new(1, 4)), 29);
+        assert_eq!(agrees_with_making_every_move(TicTacToe::new(3, 2)), 65);
+    }
 }
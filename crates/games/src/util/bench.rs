@@ -0,0 +1,240 @@
+//! Benchmarks the solver on a fixed set of positions for every game,
+//! to serve as a regression yardstick when changing the solver.
+
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    hash::Hash,
+    str::FromStr,
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Error, Result};
+use clap::ValueEnum;
+use game_solver::{game::Game, player::TwoPlayer, solve, stats::Stats};
+use serde::Serialize;
+
+use crate::{
+    chomp::{self, Chomp},
+    domineering::{self, Domineering},
+    naive_nim::{self, Nim},
+    order_and_chaos::{self, OrderAndChaos},
+    reversi::{self, Reversi},
+    sprouts::{self, Sprouts},
+    takeaway::{self, Takeaway},
+    tic_tac_toe::{self, TicTacToe},
+    GameKind,
+};
+
+/// A position to benchmark, described by the same arguments the CLI takes.
+pub struct BenchPosition<A> {
+    pub name: &'static str,
+    /// Whether this position is part of the quick profile,
+    /// which should finish in a few seconds.
+    pub quick: bool,
+    pub args: A,
+}
+
+/// Parses a whitespace-separated list of moves.
+///
+/// Benchmark positions are written by hand, so this panics on an invalid move.
+pub fn parse_moves<M: FromStr>(list: &str) -> Vec<M>
+where
+    M::Err: Debug,
+{
+    list.split_whitespace()
+        .map(|m| m.parse().expect("benchmark moves are valid"))
+        .collect()
+}
+
+/// The result of solving one benchmark position.
+#[derive(Serialize, Debug, Clone)]
+pub struct BenchResult {
+    pub game: &'static str,
+    pub position: &'static str,
+    pub score: isize,
+    pub iterations: usize,
+    /// The average time it took to solve the position, in milliseconds.
+    pub mean_ms: f64,
+    /// The amount of positions the solver visited.
+    pub nodes: u64,
+    /// The fraction of transposition table lookups that found an entry.
+    pub tt_hit_rate: f64,
+    /// The amount of positions stored in the transposition table once solved.
+    pub table_size: usize,
+}
+
+/// Solves the position from scratch `iterations` times.
+///
+/// The solver is deterministic, so the statistics are the same for every iteration.
+fn bench_position<
+    T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash,
+    A: TryInto<T, Error = Error>,
+>(
+    game: GameKind,
+    position: BenchPosition<A>,
+    iterations: usize,
+) -> Result<BenchResult> {
+    let board: T = position.args.try_into()?;
+
+    let mut elapsed = Duration::ZERO;
+    let mut last_run = None;
+
+    for _ in 0..iterations.max(1) {
+        let stats = Stats::new(board.player(), board.move_count());
+        let mut transposition_table = HashMap::new();
+
+        let start = Instant::now();
+        let score = solve(&board, &mut transposition_table, Some(&stats), &None)
+            .map_err(|err| anyhow!("The solver failed: {err}"))?;
+        elapsed += start.elapsed();
+
+        last_run = Some((score, stats, transposition_table.len()));
+    }
+
+    let (score, stats, table_size) = last_run.expect("there is at least one iteration");
+    let probes = stats.transposition_probes.load(Ordering::Relaxed);
+    let hits = stats.transposition_hits.load(Ordering::Relaxed);
+
+    Ok(BenchResult {
+        game: game.name(),
+        position: position.name,
+        score,
+        iterations: iterations.max(1),
+        mean_ms: elapsed.as_secs_f64() * 1000.0 / iterations.max(1) as f64,
+        nodes: stats.states_explored.load(Ordering::Relaxed),
+        tt_hit_rate: if probes == 0 {
+            0.0
+        } else {
+            hits as f64 / probes as f64
+        },
+        table_size,
+    })
+}
+
+fn bench_positions<
+    T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash,
+    A: TryInto<T, Error = Error>,
+>(
+    game: GameKind,
+    positions: Vec<BenchPosition<A>>,
+    quick: bool,
+    iterations: usize,
+    on_result: &mut impl FnMut(BenchResult),
+) -> Result<()> {
+    for position in positions {
+        if quick && !position.quick {
+            continue;
+        }
+
+        on_result(bench_position::<T, A>(game, position, iterations)?);
+    }
+
+    Ok(())
+}
+
+/// Benchmarks every position of `games` (or of every game, if empty),
+/// calling `on_result` as soon as each position is solved.
+///
+/// With `quick`, only the positions in the quick profile are solved.
+pub fn run_benchmarks(
+    games: &[GameKind],
+    quick: bool,
+    iterations: usize,
+    mut on_result: impl FnMut(BenchResult),
+) -> Result<()> {
+    for &game in GameKind::value_variants() {
+        if !games.is_empty() && !games.contains(&game) {
+            continue;
+        }
+
+        let on_result = &mut on_result;
+
+        match game {
+            GameKind::Reversi => bench_positions::<Reversi, _>(
+                game,
+                reversi::bench_positions(),
+                quick,
+                iterations,
+                on_result,
+            )?,
+            GameKind::TicTacToe => bench_positions::<TicTacToe, _>(
+                game,
+                tic_tac_toe::bench_positions(),
+                quick,
+                iterations,
+                on_result,
+            )?,
+            GameKind::OrderAndChaos => bench_positions::<OrderAndChaos<6, 6, 5, 6>, _>(
+                game,
+                order_and_chaos::bench_positions(),
+                quick,
+                iterations,
+                on_result,
+            )?,
+            GameKind::NaiveNim => bench_positions::<Nim, _>(
+                game,
+                naive_nim::bench_positions(),
+                quick,
+                iterations,
+                on_result,
+            )?,
+            GameKind::Domineering => bench_positions::<Domineering<5, 5>, _>(
+                game,
+                domineering::bench_positions(),
+                quick,
+                iterations,
+                on_result,
+            )?,
+            GameKind::Chomp => bench_positions::<Chomp, _>(
+                game,
+                chomp::bench_positions(),
+                quick,
+                iterations,
+                on_result,
+            )?,
+            GameKind::Sprouts => bench_positions::<Sprouts, _>(
+                game,
+                sprouts::bench_positions(),
+                quick,
+                iterations,
+                on_result,
+            )?,
+            GameKind::Takeaway => bench_positions::<Takeaway, _>(
+                game,
+                takeaway::bench_positions(),
+                quick,
+                iterations,
+                on_result,
+            )?,
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quick_profile_solves() {
+        let mut results = Vec::new();
+        run_benchmarks(&[GameKind::Chomp, GameKind::Sprouts], true, 2, |result| {
+            results.push(result)
+        })
+        .unwrap();
+
+        assert_eq!(results.len(), 5);
+        assert!(results.iter().all(|result| result.iterations == 2));
+
+        // 2 sprouts is won by the first player in 3 moves, visiting 17 positions.
+        let two_sprouts = results
+            .iter()
+            .find(|result| result.position == "2 sprouts")
+            .unwrap();
+        assert_eq!(two_sprouts.score, 3);
+        assert_eq!(two_sprouts.nodes, 17);
+    }
+}
@@ -0,0 +1,606 @@
+//! A fixed-size bitboard for grid-based games.
+//!
+//! Squares are indexed by `(x, y)`, where `x` is the column and `y` is the row,
+//! and are stored row-major in a single `u128` (bit `y * W + x`).
+//! This means a board can hold at most 128 squares.
+
+use std::{
+    fmt::{Debug, Display, Formatter},
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, Not},
+};
+
+/// One of the eight directions a board can be shifted in.
+///
+/// North decreases `y`, and west decreases `x`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// Every direction, clockwise starting from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// The four orthogonal directions.
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// One direction per line orientation (horizontal, vertical, and both diagonals),
+    /// which is all that is needed to find every line on a board.
+    pub const LINES: [Direction; 4] = [
+        Direction::East,
+        Direction::South,
+        Direction::SouthEast,
+        Direction::SouthWest,
+    ];
+
+    /// The `(x, y)` offset of this direction.
+    pub const fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::NorthEast => (1, -1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (0, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, -1),
+        }
+    }
+
+    /// The direction pointing the opposite way.
+    pub const fn opposite(self) -> Direction {
+        match self {
+            Direction::North => Direction::South,
+            Direction::NorthEast => Direction::SouthWest,
+            Direction::East => Direction::West,
+            Direction::SouthEast => Direction::NorthWest,
+            Direction::South => Direction::North,
+            Direction::SouthWest => Direction::NorthEast,
+            Direction::West => Direction::East,
+            Direction::NorthWest => Direction::SouthEast,
+        }
+    }
+}
+
+/// A `W`x`H` grid of bits.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, PartialOrd, Ord)]
+pub struct Bitboard<const W: usize, const H: usize>(u128);
+
+impl<const W: usize, const H: usize> Bitboard<W, H> {
+    /// Every square on the board.
+    pub const FULL: Self = {
+        assert!(W * H <= 128, "A bitboard can hold at most 128 squares");
+        if W * H == 128 {
+            Self(u128::MAX)
+        } else {
+            Self((1 << (W * H)) - 1)
+        }
+    };
+
+    /// No squares on the board.
+    pub const EMPTY: Self = Self(0);
+
+    /// Every square in the first (`x = 0`) column.
+    const FIRST_COLUMN: u128 = {
+        let mut mask = 0;
+        let mut y = 0;
+        while y < H {
+            mask |= 1 << (y * W);
+            y += 1;
+        }
+        mask
+    };
+
+    /// Every square in the last (`x = W - 1`) column.
+    const LAST_COLUMN: u128 = Self::FIRST_COLUMN << (W - 1);
+
+    /// Creates an empty board.
+    pub const fn new() -> Self {
+        Self::EMPTY
+    }
+
+    /// Creates a board from its raw row-major bits, discarding any bits outside of the board.
+    pub const fn from_bits(bits: u128) -> Self {
+        Self(bits & Self::FULL.0)
+    }
+
+    /// The raw row-major bits of this board.
+    pub const fn bits(self) -> u128 {
+        self.0
+    }
+
+    /// Creates a board with only the square at `(x, y)` set.
+    pub const fn single(x: usize, y: usize) -> Self {
+        assert!(x < W && y < H, "Square out of bounds");
+        Self(1 << (y * W + x))
+    }
+
+    /// Returns true if `(x, y)` is on the board.
+    pub const fn on_board(x: usize, y: usize) -> bool {
+        x < W && y < H
+    }
+
+    /// Returns true if the square at `(x, y)` is set.
+    ///
+    /// Squares outside of the board are never set.
+    pub const fn get(self, x: usize, y: usize) -> bool {
+        Self::on_board(x, y) && self.0 & (1 << (y * W + x)) != 0
+    }
+
+    /// Sets (or clears) the square at `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(x, y)` is out of bounds.
+    pub fn set(&mut self, x: usize, y: usize, value: bool) {
+        let square = Self::single(x, y).0;
+        if value {
+            self.0 |= square;
+        } else {
+            self.0 &= !square;
+        }
+    }
+
+    /// The amount of set squares.
+    pub const fn count(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// Returns true if no squares are set.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns true if every square in `other` is also set in this board.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Moves every square one step in `direction`.
+    /// Squares that would leave the board are discarded rather than wrapping around.
+    pub const fn shift(self, direction: Direction) -> Self {
+        let bits = self.0;
+        let shifted = match direction {
+            Direction::North => bits >> W,
+            Direction::South => bits << W,
+            Direction::East => (bits & !Self::LAST_COLUMN) << 1,
+            Direction::West => (bits & !Self::FIRST_COLUMN) >> 1,
+            Direction::NorthEast => (bits & !Self::LAST_COLUMN) >> (W - 1),
+            Direction::NorthWest => (bits & !Self::FIRST_COLUMN) >> (W + 1),
+            Direction::SouthEast => (bits & !Self::LAST_COLUMN) << (W + 1),
+            Direction::SouthWest => (bits & !Self::FIRST_COLUMN) << (W - 1),
+        };
+
+        Self::from_bits(shifted)
+    }
+
+    /// Returns every square that begins a run of at least `length` set squares
+    /// in `direction` (including itself).
+    pub fn runs(self, direction: Direction, length: usize) -> Self {
+        let mut runs = self;
+        for _ in 1..length {
+            runs = runs & runs.shift(direction.opposite());
+        }
+        runs
+    }
+
+    /// Returns true if there are at least `length` set squares in a row,
+    /// horizontally, vertically, or diagonally.
+    pub fn has_line(self, length: usize) -> bool {
+        Direction::LINES
+            .iter()
+            .any(|&direction| !self.runs(direction, length).is_empty())
+    }
+
+    /// Every possible line of `length` squares on this board,
+    /// horizontally, vertically, and diagonally.
+    ///
+    /// This is useful for k-in-a-row games: a player has won if
+    /// any of these masks is fully contained in their pieces.
+    pub fn line_masks(length: usize) -> Vec<Self> {
+        let mut masks = Vec::new();
+
+        if length == 0 {
+            return masks;
+        }
+
+        for direction in Direction::LINES {
+            let (dx, dy) = direction.offset();
+            for y in 0..H {
+                for x in 0..W {
+                    let end_x = x as isize + dx * (length as isize - 1);
+                    let end_y = y as isize + dy * (length as isize - 1);
+
+                    if end_x < 0 || end_y < 0 || end_x as usize >= W || end_y as usize >= H {
+                        continue;
+                    }
+
+                    let mut mask = Self::EMPTY;
+                    for i in 0..length as isize {
+                        mask.set(
+                            (x as isize + dx * i) as usize,
+                            (y as isize + dy * i) as usize,
+                            true,
+                        );
+                    }
+                    masks.push(mask);
+                }
+            }
+        }
+
+        masks
+    }
+
+    /// Returns every square in this board that is orthogonally connected to `seed`,
+    /// only travelling through squares set in this board.
+    ///
+    /// Squares in `seed` that aren't set in this board are ignored.
+    pub fn flood_fill(self, seed: Self) -> Self {
+        let mut filled = seed & self;
+
+        loop {
+            let mut next = filled;
+            for direction in Direction::ORTHOGONAL {
+                next |= filled.shift(direction);
+            }
+            next &= self;
+
+            if next == filled {
+                return filled;
+            }
+
+            filled = next;
+        }
+    }
+
+    /// Iterates over every set square as `(x, y)`, in row-major order.
+    pub fn iter(self) -> Squares<W, H> {
+        Squares(self.0)
+    }
+}
+
+/// The set squares of a [`Bitboard`], from [`Bitboard::iter`].
+#[derive(Clone, Debug)]
+pub struct Squares<const W: usize, const H: usize>(u128);
+
+impl<const W: usize, const H: usize> Iterator for Squares<W, H> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0 == 0 {
+            return None;
+        }
+
+        let index = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Some((index % W, index / W))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.0.count_ones() as usize;
+        (count, Some(count))
+    }
+}
+
+impl<const W: usize, const H: usize> ExactSizeIterator for Squares<W, H> {}
+
+impl<const W: usize, const H: usize> BitAnd for Bitboard<W, H> {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl<const W: usize, const H: usize> BitAndAssign for Bitboard<W, H> {
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl<const W: usize, const H: usize> BitOr for Bitboard<W, H> {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl<const W: usize, const H: usize> BitOrAssign for Bitboard<W, H> {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl<const W: usize, const H: usize> BitXor for Bitboard<W, H> {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        Self(self.0 ^ rhs.0)
+    }
+}
+
+impl<const W: usize, const H: usize> Not for Bitboard<W, H> {
+    type Output = Self;
+
+    /// Inverts every square on the board (squares outside of the board stay unset).
+    fn not(self) -> Self::Output {
+        Self::from_bits(!self.0)
+    }
+}
+
+impl<const W: usize, const H: usize> Display for Bitboard<W, H> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for y in 0..H {
+            for x in 0..W {
+                write!(f, "{}", if self.get(x, y) { 'X' } else { '.' })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const W: usize, const H: usize> Debug for Bitboard<W, H> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        <Self as Display>::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Board = Bitboard<4, 3>;
+
+    /// Creates a board from a string of `X`s and `.`s, ignoring whitespace.
+    fn from_string<const W: usize, const H: usize>(string: &str) -> Bitboard<W, H> {
+        let mut board = Bitboard::new();
+        for (i, ch) in string.chars().filter(|ch| !ch.is_whitespace()).enumerate() {
+            board.set(i % W, i / W, ch == 'X');
+        }
+        board
+    }
+
+    #[test]
+    fn set_get_count() {
+        let mut board = Board::new();
+        assert!(board.is_empty());
+
+        board.set(0, 0, true);
+        board.set(3, 2, true);
+        board.set(1, 1, true);
+        assert!(board.get(0, 0));
+        assert!(board.get(3, 2));
+        assert!(board.get(1, 1));
+        assert!(!board.get(1, 0));
+        assert!(!board.get(4, 0));
+        assert_eq!(board.count(), 3);
+
+        board.set(1, 1, false);
+        assert!(!board.get(1, 1));
+        assert_eq!(board.count(), 2);
+
+        assert_eq!(Board::FULL.count(), 12);
+        assert_eq!((!Board::EMPTY), Board::FULL);
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_out_of_bounds() {
+        Board::new().set(4, 0, true);
+    }
+
+    #[test]
+    fn full_128() {
+        assert_eq!(Bitboard::<16, 8>::FULL.count(), 128);
+        assert_eq!(Bitboard::<16, 8>::FULL.shift(Direction::East).count(), 120);
+    }
+
+    #[test]
+    fn shift_masks_edges() {
+        let board = from_string::<4, 3>(
+            "X..X
+             X..X
+             X..X",
+        );
+
+        assert_eq!(
+            board.shift(Direction::East),
+            from_string(
+                ".X..
+                 .X..
+                 .X.."
+            )
+        );
+        assert_eq!(
+            board.shift(Direction::West),
+            from_string(
+                "..X.
+                 ..X.
+                 ..X."
+            )
+        );
+        assert_eq!(
+            board.shift(Direction::North),
+            from_string(
+                "X..X
+                 X..X
+                 ...."
+            )
+        );
+        assert_eq!(
+            board.shift(Direction::South),
+            from_string(
+                "....
+                 X..X
+                 X..X"
+            )
+        );
+        assert_eq!(
+            board.shift(Direction::SouthEast),
+            from_string(
+                "....
+                 .X..
+                 .X.."
+            )
+        );
+        assert_eq!(
+            board.shift(Direction::NorthWest),
+            from_string(
+                "..X.
+                 ..X.
+                 ...."
+            )
+        );
+        assert_eq!(
+            board.shift(Direction::NorthEast),
+            from_string(
+                ".X..
+                 .X..
+                 ...."
+            )
+        );
+        assert_eq!(
+            board.shift(Direction::SouthWest),
+            from_string(
+                "....
+                 ..X.
+                 ..X."
+            )
+        );
+    }
+
+    #[test]
+    fn shift_matches_offset() {
+        for direction in Direction::ALL {
+            let (dx, dy) = direction.offset();
+            for y in 0..3 {
+                for x in 0..4 {
+                    let shifted = Board::single(x, y).shift(direction);
+                    let (nx, ny) = (x as isize + dx, y as isize + dy);
+                    if nx < 0 || ny < 0 || !Board::on_board(nx as usize, ny as usize) {
+                        assert!(shifted.is_empty(), "{direction:?} from {x},{y}");
+                    } else {
+                        assert_eq!(shifted, Board::single(nx as usize, ny as usize));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lines() {
+        let horizontal = from_string::<4, 3>(
+            "....
+             XXX.
+             ....",
+        );
+        assert!(horizontal.has_line(3));
+        assert!(!horizontal.has_line(4));
+
+        let vertical = from_string::<4, 3>(
+            ".X..
+             .X..
+             .X..",
+        );
+        assert!(vertical.has_line(3));
+
+        let diagonal = from_string::<4, 3>(
+            "...X
+             ..X.
+             .X..",
+        );
+        assert!(diagonal.has_line(3));
+        assert!(!diagonal.has_line(4));
+
+        let wrapping = from_string::<4, 3>(
+            "..XX
+             X...
+             ....",
+        );
+        assert!(!wrapping.has_line(3));
+    }
+
+    #[test]
+    fn line_masks() {
+        // 3x3 tic tac toe has 8 lines
+        assert_eq!(Bitboard::<3, 3>::line_masks(3).len(), 8);
+        // connect four has 69 lines
+        assert_eq!(Bitboard::<7, 6>::line_masks(4).len(), 69);
+
+        for mask in Board::line_masks(3) {
+            assert_eq!(mask.count(), 3);
+            assert!(mask.has_line(3));
+        }
+    }
+
+    #[test]
+    fn flood_fill() {
+        let board = from_string::<4, 3>(
+            "XX.X
+             .X.X
+             X..X",
+        );
+
+        assert_eq!(
+            board.flood_fill(Board::single(0, 0)),
+            from_string(
+                "XX..
+                 .X..
+                 ...."
+            )
+        );
+        assert_eq!(
+            board.flood_fill(Board::single(3, 2)),
+            from_string(
+                "...X
+                 ...X
+                 ...X"
+            )
+        );
+        assert_eq!(board.flood_fill(Board::single(0, 2)), Board::single(0, 2));
+        assert!(board.flood_fill(Board::single(2, 0)).is_empty());
+    }
+
+    #[test]
+    fn iter() {
+        let board = from_string::<4, 3>(
+            "X...
+             ..X.
+             ...X",
+        );
+
+        assert_eq!(
+            board.iter().collect::<Vec<_>>(),
+            vec![(0, 0), (2, 1), (3, 2)]
+        );
+
+        let mut squares = board.iter();
+        assert_eq!(squares.len(), 3);
+        squares.next();
+        assert_eq!(squares.len(), 2);
+        assert_eq!(Board::EMPTY.iter().len(), 0);
+    }
+}
@@ -0,0 +1,232 @@
+#[cfg(not(feature = "rayon"))]
+use std::collections::HashMap;
+#[cfg(feature = "rayon")]
+use std::{collections::hash_map::RandomState, sync::Arc};
+use std::{fmt::Display, hash::Hash};
+
+use anyhow::{anyhow, Result};
+use clap::{Args, ValueEnum};
+#[cfg(not(feature = "rayon"))]
+use game_solver::transposition::Score;
+#[cfg(feature = "rayon")]
+use game_solver::transposition::TranspositionCache;
+use game_solver::{
+    arena::{Agent, Arena, ArenaResults, PolicyAgent, StrategyAgent},
+    game::Game,
+    player::TwoPlayer,
+    policy::{GreedyPolicy, Mobility, RandomPolicy},
+    strategy::Negamax,
+    tie_break::{TieBreak, TieBreaker},
+};
+
+use crate::Games;
+
+use super::evaluate::Algorithm;
+
+/// An agent to play in the arena.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AgentKind {
+    /// The exact solver, which plays perfectly.
+    Solver,
+    /// Proof-number search, which plays a proven best outcome, not always the fastest one.
+    Pn,
+    /// Monte Carlo tree search.
+    Mcts,
+    /// Random moves.
+    Random,
+    /// The move that leaves the fewest replies.
+    Greedy,
+}
+
+impl AgentKind {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Solver => "solver",
+            Self::Pn => "proof-number search",
+            Self::Mcts => "MCTS",
+            Self::Random => "random",
+            Self::Greedy => "greedy",
+        }
+    }
+
+    /// Builds the agent for one game, from the game's seed.
+    ///
+    /// The solver keeps what it learns in `table` from one game to the next,
+    /// and chooses among its best moves at random.
+    pub fn agent<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash + Send + Sync + 'static>(
+        self,
+        seed: u64,
+        table: &SharedTable<T>,
+    ) -> Box<dyn Agent<T>> {
+        match self {
+            Self::Solver => {
+                let mut negamax = Negamax::new(table.clone());
+                // playing the same line every game would only test the opponent on it
+                negamax.tie_break = TieBreaker::new(TieBreak::Random(seed));
+                Box::new(StrategyAgent(negamax))
+            }
+            Self::Pn => Box::new(StrategyAgent(Algorithm::Pn.strategy(None, None))),
+            Self::Mcts => Box::new(StrategyAgent(Algorithm::Mcts.strategy(None, Some(seed)))),
+            Self::Random => Box::new(PolicyAgent(RandomPolicy::new(seed))),
+            Self::Greedy => Box::new(PolicyAgent(GreedyPolicy(Mobility))),
+        }
+    }
+}
+
+/// A transposition table shared by the solvers of every game.
+#[cfg(feature = "rayon")]
+pub type SharedTable<T> = Arc<TranspositionCache<T, RandomState>>;
+/// The empty table the solver of each game starts from, without the `rayon` feature.
+#[cfg(not(feature = "rayon"))]
+pub type SharedTable<T> = HashMap<T, Score>;
+
+/// Plays two agents against each other over many games, each moving first in half of them.
+#[derive(Args, Clone)]
+pub struct ArenaArgs {
+    /// The agent whose results are reported.
+    #[arg(long, value_enum, global = true, default_value = "solver")]
+    pub agent_a: AgentKind,
+    /// Its opponent.
+    #[arg(long, value_enum, global = true, default_value = "random")]
+    pub agent_b: AgentKind,
+    /// How many games to play.
+    #[arg(long, global = true, default_value_t = 100, value_parser = clap::value_parser!(u64).range(1..))]
+    pub games: u64,
+    /// The seed for the agents of every game. The same seed always plays the same games.
+    #[arg(long, global = true, default_value_t = 0)]
+    pub seed: u64,
+    /// Play many games at once (with the `rayon` feature; otherwise, they're played one after the other).
+    #[arg(long, global = true)]
+    pub parallel: bool,
+    #[command(subcommand)]
+    pub game: Games,
+}
+
+/// Plays the games of the arena from `game`.
+pub fn arena_results<
+    T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash + Send + Sync + 'static,
+>(
+    game: &T,
+    args: &ArenaArgs,
+) -> Result<ArenaResults>
+where
+    T::MoveError: Send,
+{
+    let arena = Arena::new(args.games, args.seed);
+    #[cfg(feature = "rayon")]
+    let table = Arc::new(TranspositionCache::with_capacity(1 << 24));
+    #[cfg(not(feature = "rayon"))]
+    let table = HashMap::new();
+    let agent_a = |seed| args.agent_a.agent::<T>(seed, &table);
+    let agent_b = |seed| args.agent_b.agent::<T>(seed, &table);
+
+    #[cfg(feature = "rayon")]
+    let results = if args.parallel {
+        arena.par_run(game, agent_a, agent_b)
+    } else {
+        arena.run(game, agent_a, agent_b)
+    };
+    #[cfg(not(feature = "rayon"))]
+    let results = arena.run(game, agent_a, agent_b);
+
+    results.map_err(|err| anyhow!("An agent failed: {err}"))
+}
+
+/// Prints the results of the arena from `game`, for the first agent.
+pub fn run_arena<
+    T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash + Send + Sync + Display + 'static,
+>(
+    game: T,
+    args: &ArenaArgs,
+) -> Result<()>
+where
+    T::MoveError: Send,
+{
+    let results = arena_results(&game, args)?;
+    let (low, high) = results.win_rate_interval();
+
+    println!("{game}");
+    println!(
+        "The {} agent against the {} agent, over {} games:",
+        args.agent_a.name(),
+        args.agent_b.name(),
+        results.games()
+    );
+    println!(
+        "{} wins, {} draws, {} losses",
+        results.wins, results.draws, results.losses
+    );
+    println!(
+        "Win rate: {:.1}% (95% confidence interval: {:.1}% to {:.1}%)",
+        results.win_rate() * 100.0,
+        low * 100.0,
+        high * 100.0
+    );
+    println!("Average length: {:.1} moves", results.average_length());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tic_tac_toe::TicTacToe, Games};
+
+    fn args(agent_a: AgentKind, agent_b: AgentKind, games: u64, parallel: bool) -> ArenaArgs {
+        ArenaArgs {
+            agent_a,
+            agent_b,
+            games,
+            seed: 7,
+            parallel,
+            game: Games::TicTacToe(Default::default()),
+        }
+    }
+
+    #[test]
+    fn the_solver_never_loses_to_random_play() {
+        let game = TicTacToe::new(2, 3);
+        let results = arena_results(
+            &game,
+            &args(AgentKind::Solver, AgentKind::Random, 60, false),
+        )
+        .unwrap();
+
+        assert_eq!(results.games(), 60);
+        assert_eq!(results.losses, 0);
+        assert!(results.wins > 0);
+    }
+
+    #[test]
+    fn parallel_runs_play_the_same_games() {
+        let game = TicTacToe::new(2, 3);
+        let sequential = arena_results(
+            &game,
+            &args(AgentKind::Random, AgentKind::Greedy, 200, false),
+        )
+        .unwrap();
+        let parallel = arena_results(
+            &game,
+            &args(AgentKind::Random, AgentKind::Greedy, 200, true),
+        )
+        .unwrap();
+
+        assert_eq!(sequential, parallel);
+    }
+
+    #[test]
+    fn confidence_intervals_hold_the_win_rate() {
+        let results = ArenaResults {
+            wins: 30,
+            draws: 50,
+            losses: 20,
+            moves: 700,
+        };
+        let (low, high) = results.win_rate_interval();
+
+        assert!(low < 0.3 && 0.3 < high);
+        assert!((0.21..0.23).contains(&low), "{low}");
+        assert!((0.39..0.41).contains(&high), "{high}");
+        assert_eq!(results.average_length(), 7.0);
+    }
+}
@@ -0,0 +1,210 @@
+#[cfg(not(feature = "rayon"))]
+use std::collections::HashMap;
+#[cfg(feature = "rayon")]
+use std::{collections::hash_map::RandomState, sync::Arc};
+use std::{fmt::Display, fs, hash::Hash, io::Write, path::Path};
+
+use anyhow::{anyhow, Result};
+#[cfg(feature = "rayon")]
+use game_solver::transposition::TranspositionCache;
+use game_solver::{
+    game::{score_to_outcome, Game, GameState},
+    move_scores,
+    player::TwoPlayer,
+    transposition::TranspositionTable,
+};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::util::parse::ParseMove;
+
+use super::{
+    csv::{escape_field, outcome_name},
+    exit::{invalid, solver_failed},
+    move_failable,
+    report::{analysis::Outcome, scores::summarize},
+    OutputFormat,
+};
+
+/// The analysis of one line of a batch file.
+/// Either the best move (and its score) or the error is set.
+#[derive(Serialize)]
+struct BatchResult<M> {
+    /// The line number in the batch file, starting at 1.
+    line: usize,
+    moves: String,
+    #[serde(rename = "best", skip_serializing_if = "Option::is_none")]
+    best_move: Option<M>,
+    /// The player to move after the line, whose score it is.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    player: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<isize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    outcome: Option<Outcome>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Plays the moves of `line` from `game`, and finds the best move of the resulting position.
+///
+/// Returns the position along with its best move and score.
+fn analyze_line<
+    T: Game<Player = impl TwoPlayer + 'static> + ParseMove + Eq + Hash + Sync + Send + 'static,
+>(
+    game: &T,
+    line: &str,
+    transposition_table: &mut dyn TranspositionTable<T>,
+) -> Result<(T, T::Move, isize)>
+where
+    T::MoveError: Display,
+{
+    let mut position = game.clone();
+
+    for token in line.split_whitespace() {
+        let m = position.parse_move(token)?;
+        move_failable(&mut position, &m)?;
+    }
+
+    if position.state() != GameState::Playable {
+        return Err(anyhow!("The game is already over."));
+    }
+
+    let mut best: Option<(T::Move, isize)> = None;
+    for result in move_scores(&position, transposition_table, None, &None) {
+        let (m, score) = result.map_err(solver_failed)?;
+
+        if best
+            .as_ref()
+            .map_or(true, |(_, best_score)| score > *best_score)
+        {
+            best = Some((m, score));
+        }
+    }
+
+    let (best_move, score) = best.ok_or_else(|| anyhow!("There are no moves to play."))?;
+
+    Ok((position, best_move, score))
+}
+
+/// Solves every line of the batch file at `path` (a list of moves to play from `game`),
+/// writing one result per line to `output`, in the order of the file.
+///
+/// Lines are solved in parallel on the rayon thread pool this is called in (with the `rayon` feature,
+/// and one after the other without it), sharing one transposition table. A line that fails is reported, and doesn't stop the others.
+/// Blank lines, and lines starting with `#`, are skipped.
+pub fn batch_output<
+    T: Game<Player = impl TwoPlayer + Sync + Send + 'static>
+        + ParseMove
+        + Eq
+        + Hash
+        + Sync
+        + Send
+        + 'static,
+>(
+    game: &T,
+    path: &Path,
+    format: OutputFormat,
+    mut output: impl Write,
+) -> Result<()>
+where
+    T::Move: Display + Serialize + Sync + Send,
+    T::MoveError: Display + Sync + Send,
+{
+    let contents = fs::read_to_string(path)
+        .map_err(|err| invalid(anyhow!("Could not read {}: {err}", path.display())))?;
+
+    let lines = contents
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .collect::<Vec<_>>();
+
+    #[cfg(feature = "rayon")]
+    let results = {
+        let transposition_table = Arc::new(TranspositionCache::<T, RandomState>::new());
+
+        lines
+            .par_iter()
+            .map(|&(line, moves)| {
+                let analysis = analyze_line(game, moves, &mut Arc::clone(&transposition_table));
+                (line, moves, analysis)
+            })
+            .collect::<Vec<_>>()
+    };
+    #[cfg(not(feature = "rayon"))]
+    let results = {
+        let mut transposition_table = HashMap::new();
+
+        lines
+            .iter()
+            .map(|&(line, moves)| {
+                let analysis = analyze_line(game, moves, &mut transposition_table);
+                (line, moves, analysis)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    if format == OutputFormat::Csv {
+        writeln!(output, "line,moves,best,player,score,outcome,error")?;
+    }
+
+    for (line, moves, analysis) in results {
+        match format {
+            OutputFormat::Text => match &analysis {
+                Ok((position, best_move, score)) => writeln!(
+                    output,
+                    "{line}: {}",
+                    summarize(position, &(best_move.clone(), *score))
+                )?,
+                Err(err) => writeln!(output, "{line}: error: {err}")?,
+            },
+            OutputFormat::Json => {
+                let result = match analysis {
+                    Ok((position, best_move, score)) => BatchResult {
+                        line,
+                        moves: moves.to_string(),
+                        best_move: Some(best_move),
+                        player: Some(position.player().to_string()),
+                        score: Some(score),
+                        outcome: Some(score_to_outcome(&position, score).into()),
+                        error: None,
+                    },
+                    Err(err) => BatchResult {
+                        line,
+                        moves: moves.to_string(),
+                        best_move: None,
+                        player: None,
+                        score: None,
+                        outcome: None,
+                        error: Some(err.to_string()),
+                    },
+                };
+
+                // one JSON document per line
+                serde_json::to_writer(&mut output, &result)?;
+                writeln!(output)?;
+            }
+            OutputFormat::Csv => match &analysis {
+                Ok((position, best_move, score)) => writeln!(
+                    output,
+                    "{line},{},{},{},{score},{},",
+                    escape_field(moves),
+                    escape_field(&best_move.to_string()),
+                    position.player(),
+                    outcome_name(&score_to_outcome(position, *score).into())
+                )?,
+                Err(err) => writeln!(
+                    output,
+                    "{line},{},,,,,{}",
+                    escape_field(moves),
+                    escape_field(&err.to_string())
+                )?,
+            },
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anyhow::Result;
+use clap::Args;
+
+use crate::{
+    util::bench::{run_benchmarks, BenchResult},
+    GameKind,
+};
+
+/// Benchmarks the solver on a fixed set of positions for each game.
+#[derive(Args, Debug, Clone)]
+pub struct BenchArgs {
+    /// Only benchmark these games.
+    #[arg(long, value_enum)]
+    pub game: Vec<GameKind>,
+    /// How many times each position is solved. Times are averaged across iterations.
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+    pub iterations: u64,
+    /// Only benchmark the positions that solve in a few seconds.
+    #[arg(long)]
+    pub quick: bool,
+    /// Print the results as JSON, instead of a table.
+    #[arg(long)]
+    pub json: bool,
+}
+
+fn print_row(result: &BenchResult) {
+    println!(
+        "{:<16} {:<20} {:>6} {:>12.3} {:>12} {:>7.1}% {:>12}",
+        result.game,
+        result.position,
+        result.score,
+        result.mean_ms,
+        result.nodes,
+        result.tt_hit_rate * 100.0,
+        result.table_size
+    );
+}
+
+/// Runs the benchmarks, printing each result as soon as it's ready
+/// (or every result at the end, as JSON).
+pub fn bench(args: &BenchArgs) -> Result<()> {
+    let iterations = args.iterations as usize;
+
+    if args.json {
+        let mut results = Vec::new();
+        run_benchmarks(&args.game, args.quick, iterations, |result| {
+            results.push(result)
+        })?;
+
+        println!("{}", serde_json::to_string_pretty(&results)?);
+
+        return Ok(());
+    }
+
+    println!(
+        "{:<16} {:<20} {:>6} {:>12} {:>12} {:>8} {:>12}",
+        "game", "position", "score", "mean (ms)", "nodes", "tt hits", "table size"
+    );
+    run_benchmarks(&args.game, args.quick, iterations, |result| {
+        print_row(&result)
+    })
+}
@@ -0,0 +1,115 @@
+use std::{collections::HashSet, fmt::Debug, hash::Hash, io::Write, time::Instant};
+
+use anyhow::Result;
+use clap::Args;
+#[cfg(feature = "hyperloglog")]
+use game_solver::census::HyperLogLog;
+use game_solver::{
+    census::{census_with, Census, DistinctCounter},
+    game::Game,
+};
+
+use crate::{util::symmetry::Symmetries, Games};
+
+/// Counts the positions of a game tree, up to a depth.
+#[derive(Args, Clone)]
+pub struct CensusArgs {
+    /// How many moves deep to walk the game tree. Without it, every game is played to its end.
+    #[arg(long)]
+    pub depth: Option<usize>,
+    /// Estimate how many distinct positions there are, with a HyperLogLog of 2^P registers,
+    /// instead of keeping every one of them, for game trees too big for memory.
+    #[cfg(feature = "hyperloglog")]
+    #[arg(long, value_name = "P", value_parser = clap::value_parser!(u32).range(4..=18))]
+    pub estimate: Option<u32>,
+    #[command(subcommand)]
+    pub game: Games,
+}
+
+/// Writes the census of `game` to `output`: a row for every ply, then the totals,
+/// and how long it took.
+pub fn run_census<T: Game + Symmetries + Eq + Hash + 'static>(
+    game: T,
+    args: &CensusArgs,
+    mut output: impl Write,
+) -> Result<()>
+where
+    T::Player: Debug,
+{
+    let start = Instant::now();
+    let depth = args.depth.unwrap_or(usize::MAX);
+
+    let (mut unique, mut canonical) = counters(args);
+    let census = census_with(
+        &game,
+        depth,
+        T::canonical,
+        unique.as_mut(),
+        canonical.as_mut(),
+    );
+
+    write_census(&census, &mut output)?;
+    writeln!(output, "Took {:.3}s", start.elapsed().as_secs_f64())?;
+
+    Ok(output.flush()?)
+}
+
+/// The counters of the distinct positions, and of their canonical positions.
+type Counters<T> = (Box<dyn DistinctCounter<T>>, Box<dyn DistinctCounter<T>>);
+
+/// HyperLogLogs with `--estimate`, and sets otherwise.
+#[cfg(feature = "hyperloglog")]
+fn counters<T: Eq + Hash + 'static>(args: &CensusArgs) -> Counters<T> {
+    match args.estimate {
+        Some(precision) => (
+            Box::new(HyperLogLog::new(precision)),
+            Box::new(HyperLogLog::new(precision)),
+        ),
+        None => (Box::new(HashSet::new()), Box::new(HashSet::new())),
+    }
+}
+
+#[cfg(not(feature = "hyperloglog"))]
+fn counters<T: Eq + Hash + 'static>(_args: &CensusArgs) -> Counters<T> {
+    (Box::new(HashSet::new()), Box::new(HashSet::new()))
+}
+
+fn write_census<P: Debug>(census: &Census<P>, mut output: impl Write) -> Result<()> {
+    writeln!(
+        output,
+        "{:>4}  {:>12}  {:>12}  moves (min/mean/max)",
+        "ply", "nodes", "terminal"
+    )?;
+    for (ply, counts) in census.plies.iter().enumerate() {
+        let moves = match counts.branching_factor() {
+            Some(mean) => format!("{}/{mean:.2}/{}", counts.min_moves, counts.max_moves),
+            None => "-".to_string(),
+        };
+
+        writeln!(
+            output,
+            "{ply:>4}  {:>12}  {:>12}  {moves}",
+            counts.nodes, counts.terminal
+        )?;
+    }
+    writeln!(output)?;
+
+    writeln!(output, "Positions: {}", census.nodes)?;
+    writeln!(output, "Unique positions: {}", census.unique)?;
+    writeln!(output, "Unique up to symmetry: {}", census.canonical)?;
+
+    let mut endings = census
+        .wins
+        .iter()
+        .map(|(player, wins)| format!("{player:?} won {wins}"))
+        .collect::<Vec<_>>();
+    endings.push(format!("tied {}", census.ties));
+    writeln!(
+        output,
+        "Finished games: {} ({})",
+        census.terminal,
+        endings.join(", ")
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+use std::{borrow::Cow, fmt::Display, io::Write};
+
+use anyhow::Result;
+use game_solver::game::Game;
+
+use super::report::analysis::{MoveReport, Outcome, Report};
+
+/// Quotes a field if it contains the delimiter, a quote, or a line break,
+/// doubling any quotes inside of it (as described in RFC 4180).
+pub(super) fn escape_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// The name of an outcome in the `outcome` column.
+pub(super) fn outcome_name(outcome: &Outcome) -> &'static str {
+    match outcome {
+        Outcome::Win { .. } => "win",
+        Outcome::Loss { .. } => "loss",
+        Outcome::Tie => "draw",
+    }
+}
+
+/// Writes one row per move, in the order given.
+/// Moves with the same score share the same rank.
+fn write_moves<M: Display>(moves: &[MoveReport<M>], mut output: impl Write) -> Result<()> {
+    writeln!(output, "move,score,outcome,rank")?;
+
+    let mut rank = 0;
+    let mut previous_score = None;
+
+    for (index, report) in moves.iter().enumerate() {
+        if previous_score != Some(report.score) {
+            rank = index + 1;
+            previous_score = Some(report.score);
+        }
+
+        writeln!(
+            output,
+            "{},{},{},{rank}",
+            escape_field(&report.game_move.to_string()),
+            report.score,
+            outcome_name(&report.outcome)
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Writes the score of every move in the analysis of a position to `output` as CSV,
+/// from best to worst.
+pub fn csv_output<T: Game>(report: &Report<T>, output: impl Write) -> Result<()>
+where
+    T::Move: Display,
+{
+    write_moves(&report.moves, output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_fields() {
+        assert_eq!(escape_field("1-2"), "1-2");
+        assert_eq!(escape_field("1,2"), "\"1,2\"");
+        assert_eq!(escape_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn ranks_moves() {
+        let moves = [
+            ("a,b", 3, Outcome::Win { moves: 2 }),
+            ("c", 0, Outcome::Tie),
+            ("d", 0, Outcome::Tie),
+            ("e", -2, Outcome::Loss { moves: 1 }),
+        ]
+        .map(|(game_move, score, outcome)| MoveReport {
+            game_move,
+            score,
+            outcome,
+        });
+
+        let mut output = Vec::new();
+        write_moves(&moves, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "move,score,outcome,rank\n\
+             \"a,b\",3,win,1\n\
+             c,0,draw,2\n\
+             d,0,draw,2\n\
+             e,-2,loss,4\n"
+        );
+    }
+}
@@ -0,0 +1,295 @@
+//! A database of solved positions, kept in a file so that later runs don't have to solve them again.
+//!
+//! Each line of the file is a record: the game's CLI name, its notation of the position, and the position's score,
+//! separated by tabs. Lines that can't be read, such as one cut short by a crash, are skipped.
+//!
+//! Saving merges the records with those already in the file, which another run may have added,
+//! then replaces the file by renaming a temporary one over it. Readers never see a half-written file,
+//! and if two runs save at once, the last one wins.
+
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    hash::Hash,
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use game_solver::{
+    game::{Game, GameState},
+    player::TwoPlayer,
+    solve, CollectedMoves, GameSolveError,
+};
+
+use crate::{util::notation::NotatedGame, GamePosition};
+
+/// Solved positions, read from a file and written back to it with [`SolveDb::save`].
+#[derive(Debug)]
+pub struct SolveDb {
+    path: PathBuf,
+    records: HashMap<String, isize>,
+    /// The records added since the file was read, which [`SolveDb::save`] writes.
+    added: HashMap<String, isize>,
+    /// How many lines of the file couldn't be read.
+    skipped: usize,
+}
+
+/// The key of `game`'s position, which includes the game's CLI name (e.g. `tic-tac-toe`)
+/// so that positions of games with the same notation don't collide.
+/// Unlike the name of its type, it's the same whatever compiler built the CLI, so the records outlive it.
+fn key<T: NotatedGame + Clone>(game: &T) -> String
+where
+    GamePosition: From<T>,
+{
+    let kind = GamePosition::from(game.clone()).kind();
+    let name = kind.to_possible_value().expect("every game has a name");
+
+    format!("{}\t{}", name.get_name(), game.to_notation())
+}
+
+/// Reads the records of the file at `path`, returning them and how many lines were skipped.
+/// A missing file has no records.
+fn read_records(path: &Path) -> Result<(HashMap<String, isize>, usize)> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok((HashMap::new(), 0)),
+        Err(err) => return Err(anyhow!("Could not read {}: {err}", path.display())),
+    };
+
+    let mut records = HashMap::new();
+    let mut skipped = 0;
+
+    for line in BufReader::new(file).split(b'\n') {
+        let line = line.map_err(|err| anyhow!("Could not read {}: {err}", path.display()))?;
+
+        let record = String::from_utf8(line).ok().and_then(|line| {
+            let (key, score) = line.rsplit_once('\t')?;
+            // a key is the game's name and the notation, so it has a tab of its own
+            if !key.contains('\t') {
+                return None;
+            }
+
+            Some((key.to_string(), score.parse::<isize>().ok()?))
+        });
+
+        match record {
+            Some((key, score)) => {
+                records.insert(key, score);
+            }
+            None => skipped += 1,
+        }
+    }
+
+    Ok((records, skipped))
+}
+
+impl SolveDb {
+    /// Opens the database at `path`, which is created when it's first saved.
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let (records, skipped) = read_records(&path)?;
+
+        Ok(Self {
+            path,
+            records,
+            added: HashMap::new(),
+            skipped,
+        })
+    }
+
+    /// How many lines of the file couldn't be read when it was opened.
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// The score of `game`, if it was solved before.
+    pub fn get<T: NotatedGame + Clone>(&self, game: &T) -> Option<isize>
+    where
+        GamePosition: From<T>,
+    {
+        self.records.get(&key(game)).copied()
+    }
+
+    /// Records the score of `game`, which is written to the file by [`SolveDb::save`].
+    pub fn insert<T: NotatedGame + Clone>(&mut self, game: &T, score: isize)
+    where
+        GamePosition: From<T>,
+    {
+        let key = key(game);
+        // a record is one line, and there's no need to save what's already in the file
+        if key.contains('\n') || self.records.get(&key) == Some(&score) {
+            return;
+        }
+
+        self.records.insert(key.clone(), score);
+        self.added.insert(key, score);
+    }
+
+    /// Writes the records added since the database was opened to its file,
+    /// along with every record already in it.
+    pub fn save(&mut self) -> Result<()> {
+        if self.added.is_empty() {
+            return Ok(());
+        }
+
+        // another run may have saved records since this one opened the file
+        let (mut records, _) = read_records(&self.path)?;
+        records.extend(self.added.drain());
+
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(format!(".{}.tmp", std::process::id()));
+        let temporary = PathBuf::from(temporary);
+
+        let write = || -> io::Result<()> {
+            let mut output = BufWriter::new(File::create(&temporary)?);
+            for (key, score) in &records {
+                writeln!(output, "{key}\t{score}")?;
+            }
+            output.into_inner()?.sync_all()?;
+
+            fs::rename(&temporary, &self.path)
+        };
+
+        write().map_err(|err| {
+            let _ = fs::remove_file(&temporary);
+            anyhow!("Could not write {}: {err}", self.path.display())
+        })?;
+
+        self.records.extend(records);
+
+        Ok(())
+    }
+}
+
+/// Scores every move of `game` like [`move_scores`](game_solver::move_scores),
+/// taking the scores of the positions they lead to from `db` when it has them,
+/// and adding the ones it had to solve (and that of `game`) to it.
+///
+/// Returns the scores and how many of them came from `db`.
+pub fn db_move_scores<T: Game<Player = impl TwoPlayer + 'static> + NotatedGame + Eq + Hash>(
+    game: &T,
+    db: &mut SolveDb,
+) -> (CollectedMoves<T>, usize)
+where
+    GamePosition: From<T>,
+{
+    let mut table = HashMap::new();
+    let mut hits = 0;
+    let mut scores = Vec::new();
+
+    for m in game.possible_moves() {
+        let mut board = game.clone();
+        if let Err(err) = board.make_move(&m) {
+            scores.push(Err(GameSolveError::MoveError(err)));
+            continue;
+        }
+
+        let score = match db.get(&board) {
+            Some(score) => {
+                hits += 1;
+                Ok(score)
+            }
+            None => solve(&board, &mut table, None, &None),
+        };
+
+        scores.push(score.map(|score| {
+            db.insert(&board, score);
+            (m, -score)
+        }));
+    }
+
+    let best = scores
+        .iter()
+        .map(|score| score.as_ref().ok().map(|(_, score)| *score))
+        .collect::<Option<Vec<_>>>()
+        .and_then(|scores| scores.into_iter().max());
+    if let (GameState::Playable, Some(best)) = (game.state(), best) {
+        db.insert(game, best);
+    }
+
+    (scores, hits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chomp::Chomp;
+    use game_solver::move_scores;
+
+    fn temporary_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("games-db-{name}-{}", std::process::id()))
+    }
+
+    #[test]
+    fn scores_survive_reopening() {
+        let path = temporary_path("reopen");
+        let game = Chomp::new(4, 3);
+
+        let mut db = SolveDb::open(&path).unwrap();
+        let (first, hits) = db_move_scores(&game, &mut db);
+        assert_eq!(hits, 0);
+        db.save().unwrap();
+
+        let mut db = SolveDb::open(&path).unwrap();
+        let (second, hits) = db_move_scores(&game, &mut db);
+        fs::remove_file(&path).unwrap();
+
+        let expected = move_scores(&game, &mut HashMap::new(), None, &None)
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
+        assert_eq!(hits, expected.len());
+        for scores in [first, second] {
+            assert_eq!(
+                scores.into_iter().map(Result::unwrap).collect::<Vec<_>>(),
+                expected
+            );
+        }
+        assert!(db.get(&game).is_some());
+    }
+
+    #[test]
+    fn keys_name_the_game_as_the_cli_does() {
+        let game = Chomp::new(4, 3);
+        assert_eq!(key(&game), format!("chomp\t{}", game.to_notation()));
+    }
+
+    #[test]
+    fn corrupt_records_are_skipped() {
+        let path = temporary_path("corrupt");
+        let game = Chomp::new(4, 3);
+        let key = key(&game);
+        fs::write(
+            &path,
+            format!("{key}\t3\nno tabs here\n{key}\tnot a score\n\t\n{key}\t-"),
+        )
+        .unwrap();
+
+        let db = SolveDb::open(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(db.skipped(), 4);
+        assert_eq!(db.get(&game), Some(3));
+    }
+
+    #[test]
+    fn saving_keeps_records_saved_by_others() {
+        let path = temporary_path("merge");
+        let first = Chomp::new(3, 2);
+        let second = Chomp::new(2, 2);
+
+        let mut db = SolveDb::open(&path).unwrap();
+        let mut other = SolveDb::open(&path).unwrap();
+        db.insert(&first, 1);
+        other.insert(&second, 2);
+        db.save().unwrap();
+        other.save().unwrap();
+
+        let db = SolveDb::open(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(db.get(&first), Some(1));
+        assert_eq!(db.get(&second), Some(2));
+    }
+}
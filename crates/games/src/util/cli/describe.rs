@@ -0,0 +1,78 @@
+//! Lists the games, and describes one in full, from their [metadata](crate::util::metadata).
+
+use std::fmt::Write;
+
+use clap::ValueEnum;
+
+use crate::GameKind;
+
+/// The name the CLI knows a game by, e.g. `tic-tac-toe`.
+fn cli_name(kind: GameKind) -> String {
+    kind.to_possible_value()
+        .expect("every game has a name")
+        .get_name()
+        .to_string()
+}
+
+/// A table of every game: its CLI name, its name, and what it's about.
+pub fn list_games() -> String {
+    let rows = GameKind::value_variants()
+        .iter()
+        .map(|&kind| (cli_name(kind), kind.metadata()))
+        .collect::<Vec<_>>();
+
+    let command_width = rows
+        .iter()
+        .map(|(command, _)| command.len())
+        .max()
+        .unwrap_or(0);
+    let name_width = rows
+        .iter()
+        .map(|(_, metadata)| metadata.name.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut table = String::new();
+    for (command, metadata) in rows {
+        writeln!(
+            table,
+            "{command:command_width$}  {:name_width$}  {}",
+            metadata.name, metadata.summary
+        )
+        .unwrap();
+    }
+
+    table
+}
+
+/// Everything about a game: its rules, the parameters it's set up with,
+/// how its moves are written, and an example invocation.
+pub fn describe_game(kind: GameKind) -> String {
+    let metadata = kind.metadata();
+    let mut description = String::new();
+
+    writeln!(description, "{} ({})", metadata.name, cli_name(kind)).unwrap();
+    writeln!(description, "{}", metadata.summary).unwrap();
+    writeln!(description).unwrap();
+    writeln!(description, "{}", metadata.rules.trim_end()).unwrap();
+    writeln!(description).unwrap();
+
+    if metadata.parameters.is_empty() {
+        writeln!(description, "Parameters: none").unwrap();
+    } else {
+        writeln!(description, "Parameters:").unwrap();
+        for parameter in metadata.parameters {
+            writeln!(
+                description,
+                "  {}: {} (default {}; {})",
+                parameter.name, parameter.description, parameter.default, parameter.range
+            )
+            .unwrap();
+        }
+    }
+
+    writeln!(description, "Moves: {}", metadata.move_notation).unwrap();
+    writeln!(description, "Example: games-cli {}", metadata.example).unwrap();
+
+    description
+}
@@ -0,0 +1,118 @@
+use std::{fmt::Display, hash::Hash, io::Write};
+
+use anyhow::{anyhow, bail, Result};
+use clap::Args;
+use game_solver::{
+    endgame::{endgame_sweep, immediate_win},
+    player::TwoPlayer,
+    tablebase::EnumerableGame,
+    verify::checks,
+};
+
+use crate::{naive_nim::Nim, tic_tac_toe::TicTacToe, util::notation::NotatedGame, Games};
+
+use super::exit::{invalid, solver_failed};
+
+/// The most positions a sweep enumerates, as every one of them is kept in memory.
+pub const MAX_POSITIONS: usize = 1 << 22;
+
+/// Solves every position that ends within a few moves, checking the solver against retrograde analysis.
+#[derive(Args, Clone)]
+pub struct EndgameArgs {
+    /// Sweep the positions that end within this many moves, however they're played.
+    #[arg(long)]
+    pub plies: usize,
+    /// The game to sweep: naive-nim sweeps every position whose heaps are no larger than its own,
+    /// and tic-tac-toe every board of its dimensions and size.
+    #[command(subcommand)]
+    pub game: Games,
+}
+
+/// Sweeps the endgame of `args.game`, which only Nim and Tic-Tac-Toe can be, writing how many positions
+/// each player wins, the longest forced win, and every position a check disagreed about.
+/// Fails if there were any disagreements.
+pub fn run_endgame(args: &EndgameArgs, output: impl Write) -> Result<()> {
+    match args.game.clone() {
+        Games::NaiveNim(game) => {
+            let game: Nim = game.try_into().map_err(invalid)?;
+            sweep(game, args.plies, output)
+        }
+        Games::TicTacToe(game) => {
+            let game: TicTacToe = game.try_into().map_err(invalid)?;
+            sweep(game, args.plies, output)
+        }
+        game => Err(invalid(anyhow!(
+            "Endgame sweeps are only for naive-nim and tic-tac-toe, not {}.",
+            game.name()
+        ))),
+    }
+}
+
+fn sweep<T, P>(positions: T, plies: usize, mut output: impl Write) -> Result<()>
+where
+    P: TwoPlayer + 'static,
+    T: EnumerableGame<Player = P> + NotatedGame + Eq + Hash,
+    T::Move: Display,
+    T::MoveError: Display,
+{
+    if positions.position_count() > MAX_POSITIONS {
+        return Err(invalid(anyhow!(
+            "The game has {} positions to enumerate, more than the {MAX_POSITIONS} a sweep can keep.",
+            positions.position_count()
+        )));
+    }
+
+    let mut checks = checks();
+    checks.push(immediate_win());
+    let sweep = endgame_sweep(&positions, plies, &checks).map_err(solver_failed)?;
+
+    for (position, disagreement) in &sweep.disagreements {
+        writeln!(
+            output,
+            "{} disagrees with retrograde analysis about {}",
+            disagreement.check,
+            position.to_notation()
+        )?;
+        match &disagreement.played {
+            Some(m) => writeln!(
+                output,
+                "  move {m}: expected {}, found {}",
+                disagreement.expected, disagreement.found
+            )?,
+            None => writeln!(
+                output,
+                "  score: expected {}, found {}",
+                disagreement.expected, disagreement.found
+            )?,
+        }
+    }
+
+    writeln!(
+        output,
+        "Swept {} positions that end within {plies} moves: the player to move wins {}, loses {}, and ties {}.",
+        sweep.positions, sweep.wins, sweep.losses, sweep.ties
+    )?;
+    if let Some((moves, position)) = &sweep.longest_win {
+        writeln!(
+            output,
+            "The longest forced win takes {moves} move{}, from {}.",
+            if *moves == 1 { "" } else { "s" },
+            position.to_notation()
+        )?;
+    }
+    writeln!(
+        output,
+        "{} positions were disagreed about.",
+        sweep.disagreements.len()
+    )?;
+    output.flush()?;
+
+    if !sweep.disagreements.is_empty() {
+        bail!(
+            "The solver disagreed with retrograde analysis about {} positions.",
+            sweep.disagreements.len()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,172 @@
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+    hash::Hash,
+    io::Write,
+};
+
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use game_solver::{
+    game::Game,
+    mcts::Mcts,
+    player::TwoPlayer,
+    policy::Evaluator,
+    proof_number::ProofNumber,
+    strategy::{Evaluation, HeuristicSearch, Negamax, Outcome, Strategy, Value},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+use super::{
+    exit::{invalid, solver_failed},
+    interactive::{describe_score, SolverLimits},
+    report::scores::score_label,
+};
+
+/// An algorithm to evaluate a position with.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Alpha-beta search, which finds how many moves the outcome takes.
+    Negamax,
+    /// Proof-number search, which proves the outcome, often searching fewer positions.
+    Pn,
+    /// Monte Carlo tree search, which estimates the position from random games.
+    Mcts,
+}
+
+impl Algorithm {
+    /// Creates the strategy behind this algorithm.
+    ///
+    /// `iterations` and `seed` are only used by MCTS, which otherwise plays
+    /// its default amount of games, seeded from entropy.
+    pub fn strategy<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash + 'static>(
+        self,
+        iterations: Option<u64>,
+        seed: Option<u64>,
+    ) -> Box<dyn Strategy<T>> {
+        match self {
+            Self::Negamax => Box::new(Negamax::new(HashMap::new())),
+            Self::Pn => Box::new(ProofNumber::new()),
+            Self::Mcts => {
+                let rng = match seed {
+                    Some(seed) => StdRng::seed_from_u64(seed),
+                    None => StdRng::from_entropy(),
+                };
+
+                let mut mcts = Mcts::new(rng);
+                if let Some(iterations) = iterations {
+                    mcts.iterations = iterations;
+                }
+
+                Box::new(mcts)
+            }
+        }
+    }
+}
+
+/// A heuristic evaluator, for searching positions too far from the end to solve.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Eval {
+    /// The game's classic evaluation, such as weighted squares, mobility, and frontier discs in Reversi.
+    Classic,
+}
+
+/// A game with heuristic evaluators, which `--eval` searches with.
+pub trait Heuristics: Game + Sized {
+    /// The evaluator of the given kind, or None if the game doesn't have one.
+    fn evaluator(kind: Eval) -> Option<Box<dyn Evaluator<Self>>> {
+        let _ = kind;
+        None
+    }
+}
+
+/// Creates a search that looks `depth` moves ahead, scoring the positions beyond with the game's evaluator.
+pub fn heuristic_strategy<
+    T: Heuristics<Player = impl TwoPlayer + 'static> + Eq + Hash + 'static,
+>(
+    kind: Eval,
+    depth: usize,
+) -> Result<Box<dyn Strategy<T>>> {
+    let evaluator = T::evaluator(kind).ok_or_else(|| {
+        let name = kind.to_possible_value().expect("no evaluator is skipped");
+        invalid(anyhow!("This game has no {} evaluator.", name.get_name()))
+    })?;
+
+    Ok(Box::new(HeuristicSearch::new(
+        move |game: &T| evaluator.evaluate(game),
+        depth,
+        HashMap::new(),
+    )))
+}
+
+/// Describes an evaluation's value for the player to move, e.g. "can force a win".
+fn describe_value<T: Game>(game: &T, value: Value) -> String
+where
+    T::Player: TwoPlayer,
+{
+    match value {
+        Value::Exact(score) => describe_score(game, Some(score), SolverLimits::default()),
+        Value::Proven(Outcome::Win) => "can force a win".to_string(),
+        Value::Proven(Outcome::Tie) => "can force a tie".to_string(),
+        Value::Proven(Outcome::Loss) => "loses against perfect play".to_string(),
+        Value::Estimate(estimate) => {
+            format!(
+                "is estimated at {estimate:+.3}, from -1 (lost) to 1 (won) ({})",
+                score_label(game)
+            )
+        }
+        Value::Heuristic(score) => format!(
+            "is evaluated at {score:+} by the heuristic ({})",
+            score_label(game)
+        ),
+    }
+}
+
+/// Evaluates `game` with `strategy`, writing the board, the evaluation, the move it would play,
+/// and how many positions it took to `output`.
+pub fn evaluate_output<T: Game + Display>(
+    game: &T,
+    strategy: &mut dyn Strategy<T>,
+    mut output: impl Write,
+) -> Result<Evaluation<T::Move>>
+where
+    T::Move: Display,
+    T::Player: TwoPlayer,
+{
+    let evaluation = strategy.evaluate(game).map_err(solver_failed)?;
+
+    writeln!(output, "{game}")?;
+    writeln!(
+        output,
+        "The player to move {}.",
+        describe_value(game, evaluation.value)
+    )?;
+    match &evaluation.best_move {
+        Some(m) => writeln!(output, "Best move: {m}")?,
+        None => writeln!(output, "The game is over.")?,
+    }
+    writeln!(output, "Searched {} positions.", evaluation.nodes)?;
+
+    Ok(evaluation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::naive_nim::Nim;
+
+    #[test]
+    fn every_algorithm_finds_the_winning_nim_move() {
+        let game = Nim::new(vec![1, 2]);
+
+        for algorithm in Algorithm::value_variants() {
+            let mut strategy = algorithm.strategy(Some(2000), Some(7));
+            let mut output = Vec::new();
+            let evaluation = evaluate_output(&game, strategy.as_mut(), &mut output).unwrap();
+
+            let output = String::from_utf8(output).unwrap();
+            assert!(output.contains("Best move: 1-1"), "{algorithm:?}: {output}");
+            assert_ne!(evaluation.value.outcome(), Some(Outcome::Tie));
+        }
+    }
+}
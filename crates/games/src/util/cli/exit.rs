@@ -0,0 +1,180 @@
+//! How the command line interface exits, so that scripts can tell a lost position from a bad argument.
+//!
+//! | Code | Meaning |
+//! |------|---------|
+//! | 0    | Success, whatever the position is worth. |
+//! | 1    | Anything else went wrong, such as a file that couldn't be written, or `verify` finding a mismatch. |
+//! | 2    | The arguments, moves, or position given were invalid. |
+//! | 3    | A solve was stopped before it finished, such as by quitting the live analysis. |
+//! | 101  | A bug: the CLI panicked. Nothing else exits with it. |
+//!
+//! Errors are printed to stderr, as one line of JSON with `--format json`:
+//! `{"error":{"kind":"invalid","code":2,"message":"..."}}`.
+
+use std::{
+    error::Error as StdError,
+    fmt::{self, Display},
+    io::{self, Write},
+    process::ExitCode,
+};
+
+use anyhow::{anyhow, Error};
+use game_solver::{game::Game, GameSolveError};
+use serde::Serialize;
+
+use super::OutputFormat;
+
+/// What kind of failure an error is, which picks the code the CLI exits with.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorKind {
+    /// Anything that isn't one of the others.
+    Failed,
+    /// The arguments, moves, or position given were invalid.
+    Invalid,
+    /// A solve was stopped before it finished.
+    Aborted,
+}
+
+impl ErrorKind {
+    /// The code the CLI exits with for errors of this kind.
+    pub fn code(self) -> u8 {
+        match self {
+            Self::Failed => 1,
+            Self::Invalid => 2,
+            Self::Aborted => 3,
+        }
+    }
+}
+
+/// An error of a known kind, shown as the error it wraps.
+#[derive(Debug)]
+struct Classified {
+    kind: ErrorKind,
+    error: Error,
+}
+
+impl Display for Classified {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.error)
+    }
+}
+
+impl StdError for Classified {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.error.source()
+    }
+}
+
+fn classified(kind: ErrorKind, error: Error) -> Error {
+    Error::new(Classified { kind, error })
+}
+
+/// Marks `error` as caused by invalid arguments, moves, or positions.
+pub fn invalid(error: impl Into<Error>) -> Error {
+    classified(ErrorKind::Invalid, error.into())
+}
+
+/// Marks `error` as caused by a solve that was stopped before it finished.
+pub fn aborted(error: impl Into<Error>) -> Error {
+    classified(ErrorKind::Aborted, error.into())
+}
+
+/// The error for a solve that failed, which is [`ErrorKind::Aborted`] if it was cancelled.
+pub fn solver_failed<T: Game>(error: GameSolveError<T>) -> Error {
+    match error {
+        GameSolveError::CancellationTokenError => {
+            aborted(anyhow!("The solver was stopped before it finished."))
+        }
+        error => anyhow!("The solver failed: {error}"),
+    }
+}
+
+/// The kind of `error`, from the first error of its chain that was given one.
+pub fn kind(error: &Error) -> ErrorKind {
+    error
+        .chain()
+        .find_map(|error| error.downcast_ref::<Classified>())
+        .map_or(ErrorKind::Failed, |classified| classified.kind)
+}
+
+#[derive(Serialize)]
+struct ErrorReport<'a> {
+    error: ErrorDetails<'a>,
+}
+
+#[derive(Serialize)]
+struct ErrorDetails<'a> {
+    kind: ErrorKind,
+    code: u8,
+    message: &'a str,
+}
+
+/// Writes `error` to `output` as `format` prints it: as a line of JSON with [`OutputFormat::Json`],
+/// or as text, along with the errors that caused it.
+pub fn write_error(error: &Error, format: OutputFormat, mut output: impl Write) -> io::Result<()> {
+    match format {
+        OutputFormat::Json => {
+            let kind = kind(error);
+            let report = ErrorReport {
+                error: ErrorDetails {
+                    kind,
+                    code: kind.code(),
+                    message: &format!("{error:#}"),
+                },
+            };
+            serde_json::to_writer(&mut output, &report)?;
+            writeln!(output)
+        }
+        OutputFormat::Text | OutputFormat::Csv => writeln!(output, "Error: {error:?}"),
+    }
+}
+
+/// Prints `error` to stderr (see [`write_error`]), and returns the code to exit with.
+pub fn report(error: &Error, format: OutputFormat) -> ExitCode {
+    // there's nowhere left to report a failure to write to stderr
+    let _ = write_error(error, format, io::stderr().lock());
+
+    ExitCode::from(kind(error).code())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::naive_nim::Nim;
+
+    fn written(error: &Error, format: OutputFormat) -> String {
+        let mut output = Vec::new();
+        write_error(error, format, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn kinds_survive_context() {
+        assert_eq!(kind(&anyhow!("no such file")), ErrorKind::Failed);
+
+        let error = invalid(anyhow!("7 isn't a heap")).context("Could not play move 1 (7-1)");
+        assert_eq!(kind(&error), ErrorKind::Invalid);
+        assert_eq!(
+            format!("{error:#}"),
+            "Could not play move 1 (7-1): 7 isn't a heap"
+        );
+
+        let cancelled = solver_failed::<Nim>(GameSolveError::CancellationTokenError);
+        assert_eq!(kind(&cancelled), ErrorKind::Aborted);
+        let failed = solver_failed::<Nim>(GameSolveError::InvalidWindow { alpha: 1, beta: 0 });
+        assert_eq!(kind(&failed), ErrorKind::Failed);
+    }
+
+    #[test]
+    fn errors_are_written_in_the_format() {
+        let error = invalid(anyhow!("7 isn't a heap"));
+
+        assert_eq!(
+            written(&error, OutputFormat::Json),
+            "{\"error\":{\"kind\":\"invalid\",\"code\":2,\"message\":\"7 isn't a heap\"}}\n"
+        );
+        // (followed by a backtrace, with RUST_BACKTRACE set)
+        assert!(written(&error, OutputFormat::Text).starts_with("Error: 7 isn't a heap\n"));
+    }
+}
@@ -0,0 +1,136 @@
+use std::{collections::HashMap, fmt::Display, hash::Hash, io::Write};
+
+use anyhow::{anyhow, Result};
+use game_solver::{
+    game::{Game, GameState},
+    move_scores,
+    player::TwoPlayer,
+    refutation_line, solve,
+};
+
+use crate::util::parse::ParseMove;
+
+use super::{
+    exit::{invalid, solver_failed},
+    interactive::{describe_score, SolverLimits},
+    report::scores::score_label,
+};
+
+/// Scores every move of `game`, then writes the line that refutes `token`,
+/// with the evaluation of the position after each of its moves.
+///
+/// Returns the moves of the refutation.
+pub fn explain_output<
+    T: Game<Player = impl TwoPlayer + 'static> + ParseMove + Eq + Hash + Display,
+>(
+    game: &T,
+    token: &str,
+    mut output: impl Write,
+) -> Result<Vec<T::Move>>
+where
+    T::Move: Display + PartialEq,
+{
+    if game.state() != GameState::Playable {
+        return Err(invalid(anyhow!("The game is already over.")));
+    }
+
+    let explained = game.parse_move(token).map_err(invalid)?;
+
+    let mut table = HashMap::new();
+    let scores = move_scores(game, &mut table, None, &None)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(solver_failed)?;
+    let (_, score) = scores
+        .iter()
+        .find(|(m, _)| *m == explained)
+        .ok_or_else(|| invalid(anyhow!("{explained} isn't a possible move.")))?;
+    let best = scores
+        .iter()
+        .map(|(_, score)| *score)
+        .max()
+        .unwrap_or(*score);
+
+    writeln!(output, "{game}")?;
+    writeln!(
+        output,
+        "{explained} has a {} of {score} (the best move has {best}).",
+        score_label(game)
+    )?;
+
+    // the scoring pass left the exact score of every position on the line in the table
+    let line = refutation_line(game, &explained, &mut table, usize::MAX).map_err(solver_failed)?;
+
+    let mut position = game.clone();
+    position
+        .make_move(&explained)
+        .map_err(|_| anyhow!("{explained} isn't a possible move."))?;
+
+    for (ply, m) in line.iter().enumerate() {
+        position
+            .make_move(m)
+            .map_err(|_| anyhow!("{m} isn't a possible move."))?;
+
+        let description = match position.state() {
+            GameState::Playable => {
+                let score = solve(&position, &mut table, None, &None).map_err(solver_failed)?;
+                format!(
+                    "the player to move {}",
+                    describe_score(&position, Some(score), SolverLimits::default())
+                )
+            }
+            GameState::Tie => "the game is tied".to_string(),
+            GameState::Win(player) if player == position.player() => {
+                "the player to move wins".to_string()
+            }
+            GameState::Win(_) => "the player who just moved wins".to_string(),
+        };
+
+        writeln!(output, "{}. {m}: {description}", ply + 1)?;
+    }
+
+    Ok(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tic_tac_toe::TicTacToe, util::notation::NotatedGame};
+
+    #[test]
+    fn losing_openings_are_refuted_with_a_win() {
+        // X took a corner, so O loses unless it takes the center
+        let game = TicTacToe::from_notation("X../.../... O").unwrap();
+        let losing = move_scores(&game, &mut HashMap::new(), None, &None)
+            .map(Result::unwrap)
+            .filter(|(_, score)| *score < 0)
+            .collect::<Vec<_>>();
+        assert!(!losing.is_empty());
+
+        for (m, _) in losing {
+            let mut output = Vec::new();
+            let line = explain_output(&game, &m.to_string(), &mut output).unwrap();
+
+            let mut position = game.clone();
+            position.make_move(&m).unwrap();
+            let opponent = position.player();
+            for m in &line {
+                position.make_move(m).unwrap();
+            }
+
+            assert_eq!(
+                position.state(),
+                GameState::Win(opponent),
+                "{m}: {position}"
+            );
+            let output = String::from_utf8(output).unwrap();
+            assert!(output.contains(&format!("{}. ", line.len())), "{output}");
+        }
+    }
+
+    #[test]
+    fn impossible_moves_are_reported() {
+        let game = TicTacToe::from_notation("X../.../... O").unwrap();
+
+        assert!(explain_output(&game, "0-0", Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,159 @@
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+    hash::Hash,
+};
+
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use game_solver::{
+    game::Game,
+    player::TwoPlayer,
+    policy::{best_response_value, GreedyPolicy, Mobility, RandomPolicy},
+    solve,
+};
+
+use crate::Games;
+
+use super::{
+    exit::solver_failed,
+    interactive::{describe_score, SolverLimits},
+    report::scores::score_label,
+};
+
+/// A fixed opponent to measure.
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PolicyKind {
+    /// Plays a random move, which depends only on the position and --seed.
+    #[default]
+    Random,
+    /// Plays the move that leaves the fewest replies.
+    Greedy,
+}
+
+impl PolicyKind {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Random => "random",
+            Self::Greedy => "greedy",
+        }
+    }
+}
+
+/// Measures how much a fixed policy gives away against perfect play.
+#[derive(Args, Clone)]
+pub struct ExploitArgs {
+    /// The policy playing against the player to move.
+    #[arg(long, value_enum, default_value_t)]
+    pub policy: PolicyKind,
+    /// The seed of the random policy.
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+    #[command(subcommand)]
+    pub game: Games,
+}
+
+/// The score of perfect play from `game` against the policy, and against perfect play.
+pub fn exploit_scores<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    game: &T,
+    args: &ExploitArgs,
+) -> Result<(isize, isize)> {
+    let exploited = match args.policy {
+        PolicyKind::Random => {
+            best_response_value(game, &RandomPolicy::new(args.seed), &mut HashMap::new())
+        }
+        PolicyKind::Greedy => {
+            best_response_value(game, &GreedyPolicy(Mobility), &mut HashMap::new())
+        }
+    }
+    .map_err(solver_failed)?;
+
+    let perfect = solve(game, &mut HashMap::new(), None, &None).map_err(solver_failed)?;
+
+    Ok((exploited, perfect))
+}
+
+/// Prints how perfect play from `game` does against the policy, compared to against perfect play.
+pub fn run_exploit<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash + Display>(
+    game: T,
+    args: &ExploitArgs,
+) -> Result<()> {
+    let (exploited, perfect) = exploit_scores(&game, args)?;
+    let limits = SolverLimits::default();
+    let policy = args.policy.name();
+    let label = score_label(&game);
+
+    println!("{game}");
+    println!(
+        "Against the {policy} policy, the player to move {} ({label}: {exploited}).",
+        describe_score(&game, Some(exploited), limits)
+    );
+    println!(
+        "Against perfect play, the player to move {} ({label}: {perfect}).",
+        describe_score(&game, Some(perfect), limits)
+    );
+    println!(
+        "The {policy} policy gives away {} points.",
+        exploited - perfect
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        naive_nim::Nim,
+        reversi::{Reversi, ReversiArgs},
+        tic_tac_toe::TicTacToe,
+        Games,
+    };
+    use game_solver::{game::upper_bound, random::random_position};
+    use rand::{rngs::StdRng, SeedableRng};
+
+    fn args(policy: PolicyKind, seed: u64) -> ExploitArgs {
+        ExploitArgs {
+            policy,
+            seed,
+            game: Games::NaiveNim(Default::default()),
+        }
+    }
+
+    /// Checks that perfect play does at least as well against every policy as against perfect play,
+    /// and no better than winning with its next move.
+    fn assert_bounded<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash + Display>(game: &T) {
+        let max_score = upper_bound(game) - game.move_count() as isize;
+
+        for (policy, seed) in [
+            (PolicyKind::Random, 0),
+            (PolicyKind::Random, 1),
+            (PolicyKind::Greedy, 0),
+        ] {
+            let (exploited, perfect) = exploit_scores(game, &args(policy, seed)).unwrap();
+
+            assert!(perfect <= exploited, "{policy:?} {seed}: {game}");
+            assert!(exploited <= max_score, "{policy:?} {seed}: {game}");
+        }
+    }
+
+    #[test]
+    fn best_responses_are_bounded() {
+        assert_bounded(&TicTacToe::new(2, 3));
+        assert_bounded(&Nim::new(vec![2, 3, 4]));
+
+        // a Reversi endgame, with a few empty squares left
+        let start = Reversi::try_from(ReversiArgs::default()).unwrap();
+        let (endgame, _) = random_position(&start, 24, &mut StdRng::seed_from_u64(0)).unwrap();
+        assert_bounded(&endgame);
+    }
+
+    #[test]
+    fn random_play_gives_tic_tac_toe_away() {
+        let (exploited, perfect) =
+            exploit_scores(&TicTacToe::new(2, 3), &args(PolicyKind::Random, 0)).unwrap();
+
+        assert_eq!(perfect, 0);
+        assert!(exploited > 0);
+    }
+}
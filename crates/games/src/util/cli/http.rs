@@ -0,0 +1,435 @@
+//! A small HTTP server for analyzing positions, such as for the book's demos.
+//! This requires the `http` feature to be enabled.
+//!
+//! Both endpoints take a [position envelope](crate::GamePosition) as JSON,
+//! along with an optional budget of positions to search and milliseconds to search for:
+//!
+//! ```json
+//! {"game": "naive-nim", "position": {"heaps": [3, 5], "move_count": 0}, "nodes": 100000, "time_ms": 500}
+//! ```
+//!
+//! - `POST /solve` solves the position:
+//!   `{"player": "Next", "evaluation": {"score": 2, "outcome": {"result": "win", "moves": 6}}, "nodes": 41, "complete": true}`.
+//! - `POST /moves` scores every move, from best to worst:
+//!   `{"player": "Next", "moves": [{"move": "1-2", "score": 2, "outcome": {...}}, ...], "nodes": 97, "complete": true}`.
+//!
+//! A search that runs out of its budget answers with what it found, and `"complete": false`.
+//! Budgets can't go over the server's own limits, which are also the budgets of requests without one.
+//! Errors are answered with a status of 4xx or 5xx, and `{"error": "..."}`.
+//!
+//! Every game keeps its transposition table between requests, for the few games requested last.
+
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt::Debug,
+    hash::Hash,
+    io::{self, Read, Write},
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use anyhow::{anyhow, Result};
+use clap::Args;
+use game_solver::{
+    game::{score_to_outcome, Game},
+    move_scores,
+    player::TwoPlayer,
+    solve,
+    stats::Stats,
+    transposition::Score,
+    GameSolveError,
+};
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::{with_position, GameKind, GamePosition};
+
+use super::report::analysis::{Evaluation, MoveReport};
+
+/// The largest request body accepted, in bytes.
+const MAX_BODY: usize = 64 * 1024;
+
+/// Tables with more entries than this aren't kept between requests.
+const MAX_TABLE_ENTRIES: usize = 1 << 20;
+
+/// How often a search is checked against its budget.
+const BUDGET_POLL: Duration = Duration::from_millis(1);
+
+/// The options of the HTTP server.
+#[derive(Args, Debug, Clone)]
+pub struct HttpArgs {
+    /// The address to listen on. With a port of 0, a free port is picked (and printed).
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    pub address: String,
+    /// How many requests are analyzed at once. The others wait for their turn.
+    #[arg(long, default_value_t = NonZeroUsize::new(4).unwrap())]
+    pub workers: NonZeroUsize,
+    /// The most positions a request can search, which is also the budget of requests without one.
+    #[arg(long, default_value_t = 10_000_000)]
+    pub max_nodes: u64,
+    /// The most milliseconds a request can search for, which is also the budget of requests without one.
+    #[arg(long, default_value_t = 5_000)]
+    pub max_time_ms: u64,
+    /// The most moves a position can have left to play (by its game's limit), so oversized boards are rejected.
+    #[arg(long, default_value_t = 64)]
+    pub max_moves: usize,
+    /// How many games keep their transposition table between requests.
+    #[arg(long, default_value_t = NonZeroUsize::new(4).unwrap())]
+    pub tables: NonZeroUsize,
+}
+
+/// The body of a request: a position, and how much of a search it may use.
+#[derive(Deserialize)]
+struct AnalysisRequest {
+    #[serde(flatten)]
+    position: GamePosition,
+    nodes: Option<u64>,
+    time_ms: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct SolveResponse {
+    player: String,
+    /// None if the search ran out of its budget.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    evaluation: Option<Evaluation>,
+    nodes: u64,
+    complete: bool,
+}
+
+#[derive(Serialize)]
+struct MovesResponse<M> {
+    player: String,
+    /// The moves scored before the search ran out of its budget, from best to worst.
+    moves: Vec<MoveReport<M>>,
+    nodes: u64,
+    complete: bool,
+}
+
+/// An error, answered with its status code.
+#[derive(Debug)]
+struct HttpError {
+    status: u16,
+    message: String,
+}
+
+impl HttpError {
+    fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// How much a single search may do before it's cut short.
+#[derive(Debug, Clone, Copy)]
+struct Budget {
+    nodes: u64,
+    time: Duration,
+}
+
+/// The transposition tables of the games requested last.
+///
+/// A request takes its game's table out of the cache, and puts it back once it's done,
+/// so requests for the same game at the same time each get a table of their own.
+struct Tables(Mutex<LruCache<GameKind, Box<dyn Any + Send>>>);
+
+impl Tables {
+    fn take<T: Eq + Hash + 'static>(&self, kind: GameKind) -> HashMap<T, Score> {
+        let table = self
+            .0
+            .lock()
+            .expect("the table cache is never poisoned")
+            .pop(&kind);
+
+        table
+            .and_then(|table| table.downcast::<HashMap<T, Score>>().ok())
+            .map(|table| *table)
+            .unwrap_or_default()
+    }
+
+    fn put<T: Send + 'static>(&self, kind: GameKind, table: HashMap<T, Score>) {
+        if table.len() > MAX_TABLE_ENTRIES {
+            return;
+        }
+
+        self.0
+            .lock()
+            .expect("the table cache is never poisoned")
+            .put(kind, Box::new(table));
+    }
+}
+
+/// Everything the workers share.
+struct State<'a> {
+    args: &'a HttpArgs,
+    tables: Tables,
+}
+
+/// Runs `search` with a cancellation token that is set once `stats` counts more positions than the budget,
+/// or once the budget's time is up.
+fn with_budget<P: TwoPlayer + Sync, R>(
+    stats: &Stats<P>,
+    budget: Budget,
+    search: impl FnOnce(&Option<Arc<AtomicBool>>) -> R,
+) -> R {
+    let token = Arc::new(AtomicBool::new(false));
+    let done = AtomicBool::new(false);
+    let start = Instant::now();
+
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            while !done.load(Ordering::Relaxed) {
+                if stats.states_explored.load(Ordering::Relaxed) >= budget.nodes
+                    || start.elapsed() >= budget.time
+                {
+                    token.store(true, Ordering::Relaxed);
+                    return;
+                }
+
+                thread::sleep(BUDGET_POLL);
+            }
+        });
+
+        let result = search(&Some(token.clone()));
+        done.store(true, Ordering::Relaxed);
+        result
+    })
+}
+
+/// Rejects positions with more moves left to play than the server allows.
+fn check_size<T: Game>(game: &T, max_moves: usize) -> Result<(), HttpError> {
+    let left = game
+        .max_moves()
+        .map(|max| max.saturating_sub(game.move_count()));
+
+    match left {
+        Some(left) if left <= max_moves => Ok(()),
+        Some(left) => Err(HttpError::new(
+            413,
+            format!(
+                "the position has up to {left} moves left, but at most {max_moves} are allowed"
+            ),
+        )),
+        None => Err(HttpError::new(
+            413,
+            "the position has no limit on its moves, so its size can't be checked",
+        )),
+    }
+}
+
+fn solver_error<T: Game>(err: GameSolveError<T>) -> HttpError
+where
+    T::MoveError: Debug,
+{
+    HttpError::new(500, format!("the solver failed: {err}"))
+}
+
+fn solve_position<T, P>(
+    game: &T,
+    kind: GameKind,
+    budget: Budget,
+    state: &State,
+) -> Result<String, HttpError>
+where
+    T: Game<Player = P> + Eq + Hash + Send + 'static,
+    P: TwoPlayer + Sync + 'static,
+    T::MoveError: Debug,
+{
+    let mut table = state.tables.take::<T>(kind);
+    let stats = Stats::new(game.player(), game.move_count());
+
+    let result = with_budget(&stats, budget, |token| {
+        solve(game, &mut table, Some(&stats), token)
+    });
+    state.tables.put(kind, table);
+
+    let evaluation = match result {
+        Ok(score) => Some(Evaluation {
+            score,
+            outcome: score_to_outcome(game, score).into(),
+        }),
+        Err(GameSolveError::CancellationTokenError) => None,
+        Err(err) => return Err(solver_error(err)),
+    };
+
+    let response = SolveResponse {
+        player: game.player().to_string(),
+        complete: evaluation.is_some(),
+        evaluation,
+        nodes: stats.states_explored.into_inner(),
+    };
+
+    serde_json::to_string(&response).map_err(|err| HttpError::new(500, err.to_string()))
+}
+
+fn score_moves<T, P>(
+    game: &T,
+    kind: GameKind,
+    budget: Budget,
+    state: &State,
+) -> Result<String, HttpError>
+where
+    T: Game<Player = P> + Eq + Hash + Send + 'static,
+    P: TwoPlayer + Sync + 'static,
+    T::Move: Serialize,
+    T::MoveError: Debug,
+{
+    let mut table = state.tables.take::<T>(kind);
+    let stats = Stats::new(game.player(), game.move_count());
+
+    let result = with_budget(&stats, budget, |token| {
+        let mut scores = Vec::new();
+
+        for score in move_scores(game, &mut table, Some(&stats), token) {
+            match score {
+                Ok(score) => scores.push(score),
+                Err(GameSolveError::CancellationTokenError) => return Ok((scores, false)),
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok((scores, true))
+    });
+    state.tables.put(kind, table);
+
+    let (mut scores, complete) = result.map_err(solver_error)?;
+    scores.sort_by_key(|(_, score)| -score);
+
+    let response = MovesResponse {
+        player: game.player().to_string(),
+        moves: scores
+            .into_iter()
+            .map(|(game_move, score)| MoveReport {
+                game_move,
+                score,
+                outcome: score_to_outcome(game, score).into(),
+            })
+            .collect(),
+        nodes: stats.states_explored.into_inner(),
+        complete,
+    };
+
+    serde_json::to_string(&response).map_err(|err| HttpError::new(500, err.to_string()))
+}
+
+/// Reads the body of `request`, refusing bodies over [`MAX_BODY`] bytes.
+fn read_body(request: &mut Request) -> Result<String, HttpError> {
+    let too_large = || HttpError::new(413, format!("the body is over {MAX_BODY} bytes"));
+
+    if request
+        .body_length()
+        .is_some_and(|length| length > MAX_BODY)
+    {
+        return Err(too_large());
+    }
+
+    let mut body = String::new();
+    request
+        .as_reader()
+        .take(MAX_BODY as u64 + 1)
+        .read_to_string(&mut body)
+        .map_err(|err| HttpError::new(400, format!("the body can't be read: {err}")))?;
+
+    if body.len() > MAX_BODY {
+        return Err(too_large());
+    }
+
+    Ok(body)
+}
+
+/// Answers a request, returning the JSON of its response.
+fn respond(request: &mut Request, state: &State) -> Result<String, HttpError> {
+    let solve = match (request.method(), request.url()) {
+        (Method::Post, "/solve") => true,
+        (Method::Post, "/moves") => false,
+        (_, "/solve" | "/moves") => {
+            return Err(HttpError::new(405, "only POST requests are accepted"));
+        }
+        (_, url) => return Err(HttpError::new(404, format!("there is nothing at {url}"))),
+    };
+
+    let body = read_body(request)?;
+    let request = serde_json::from_str::<AnalysisRequest>(&body)
+        .map_err(|err| HttpError::new(400, format!("the position can't be read: {err}")))?;
+
+    let budget = Budget {
+        nodes: request.nodes.unwrap_or(u64::MAX).min(state.args.max_nodes),
+        time: Duration::from_millis(
+            request
+                .time_ms
+                .unwrap_or(u64::MAX)
+                .min(state.args.max_time_ms),
+        ),
+    };
+    let kind = request.position.kind();
+
+    with_position!(request.position, game => {
+        check_size(&game, state.args.max_moves)?;
+
+        if solve {
+            solve_position(&game, kind, budget, state)
+        } else {
+            score_moves(&game, kind, budget, state)
+        }
+    })
+}
+
+fn json_header(name: &str, value: &str) -> Header {
+    Header::from_bytes(name.as_bytes(), value.as_bytes()).expect("the header is valid")
+}
+
+/// Answers requests on one thread, until the server stops.
+fn work(server: &Server, state: &State) {
+    for mut request in server.incoming_requests() {
+        let (status, body) = match respond(&mut request, state) {
+            Ok(body) => (200, body),
+            Err(err) => (
+                err.status,
+                serde_json::json!({ "error": err.message }).to_string(),
+            ),
+        };
+
+        let response = Response::from_string(body)
+            .with_status_code(status)
+            .with_header(json_header("Content-Type", "application/json"))
+            // the book's demos are served from elsewhere
+            .with_header(json_header("Access-Control-Allow-Origin", "*"));
+
+        // the client hanging up isn't the server's problem
+        let _ = request.respond(response);
+    }
+}
+
+/// Serves the analysis of positions over HTTP until the process is stopped,
+/// answering up to [`HttpArgs::workers`] requests at once.
+///
+/// Prints the address it listens on once it's ready for requests.
+pub fn serve_http(args: &HttpArgs) -> Result<()> {
+    let server = Server::http(&args.address)
+        .map_err(|err| anyhow!("Could not listen on {}: {err}", args.address))?;
+
+    println!("Listening on http://{}", server.server_addr());
+    io::stdout().flush()?;
+
+    let state = State {
+        args,
+        tables: Tables(Mutex::new(LruCache::new(args.tables))),
+    };
+
+    thread::scope(|scope| {
+        for _ in 0..args.workers.get() {
+            scope.spawn(|| work(&server, &state));
+        }
+    });
+
+    Ok(())
+}
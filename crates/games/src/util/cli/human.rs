@@ -1,21 +1,21 @@
+//! The report of analyzing a position for people, with the search's statistics shown live
+//! in the terminal while it runs, with the `tui` feature.
+
+use std::{fmt::Display, sync::Arc};
+#[cfg(feature = "tui")]
 use std::{
-    fmt::Display,
-    sync::{
-        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
-        Arc,
-    },
+    panic,
+    sync::atomic::{AtomicBool, Ordering},
     thread,
     time::Duration,
 };
 
 use anyhow::Result;
 use core::hash::Hash;
-use game_solver::{
-    game::Game,
-    par_move_scores,
-    player::TwoPlayer,
-    stats::{Stats, TerminalEnds},
-};
+#[cfg(feature = "tui")]
+use game_solver::CollectedMoves;
+use game_solver::{game::Game, player::TwoPlayer, stats::Stats};
+#[cfg(feature = "tui")]
 use ratatui::{
     buffer::Buffer,
     crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind},
@@ -31,15 +31,28 @@ use ratatui::{
 };
 use std::fmt::Debug;
 
-use super::report::{scores::show_scores, stats::show_stats};
+use crate::util::notation::NotatedGame;
 
+use super::{
+    all_move_scores,
+    report::{
+        analysis::MoveFilter,
+        scores::show_scores,
+        stats::{show_ply_stats, show_stats},
+    },
+};
+
+#[cfg(feature = "tui")]
 #[derive(Debug)]
 struct App<G: Game> {
+    /// The name of the game being analyzed.
+    name: &'static str,
     exit: Arc<AtomicBool>,
     exit_ui: Arc<AtomicBool>,
     stats: Arc<Stats<G::Player>>,
 }
 
+#[cfg(feature = "tui")]
 impl<G: Game> App<G> {
     /// runs the application's main loop until the user quits
     pub fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
@@ -79,9 +92,10 @@ impl<G: Game> App<G> {
     }
 }
 
+#[cfg(feature = "tui")]
 impl<G: Game> Widget for &App<G> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let title = Title::from(" game-solver ".bold().green());
+        let title = Title::from(format!(" game-solver: {} ", self.name).bold().green());
         let instructions = Title::from(Line::from(vec![" Quit ".into(), "<Q> ".blue().bold()]));
         let block = Block::bordered()
             .title(title.alignment(Alignment::Center))
@@ -159,38 +173,74 @@ impl<G: Game> Widget for &App<G> {
     }
 }
 
+/// Scores the moves of `game` and prints them with the search's statistics,
+/// which are shown live while it searches with the `tui` feature.
 pub fn human_output<
-    T: Game<Player = impl TwoPlayer + Debug + Sync + Send + 'static>
+    T: Game<Player = impl TwoPlayer + Sync + Send + 'static>
         + Eq
         + Hash
         + Sync
         + Send
         + Display
         + Debug
+        + NotatedGame
         + 'static,
 >(
     game: T,
+    name: &'static str,
+    filter: MoveFilter,
+    stats_verbose: bool,
 ) -> Result<()>
 where
     T::Move: Sync + Send + Display,
     T::MoveError: Sync + Send + Debug,
 {
-    let mut terminal = ratatui::init();
-
-    let stats = Arc::new(Stats {
-        states_explored: AtomicU64::new(0),
-        max_depth: AtomicUsize::new(0),
-        cache_hits: AtomicU64::new(0),
-        pruning_cutoffs: AtomicU64::new(0),
-        terminal_ends: TerminalEnds::default(),
-        original_player: game.player(),
-        original_move_count: game.move_count(),
+    let stats = Arc::new(if stats_verbose {
+        Stats::per_ply(&game)
+    } else {
+        Stats::new(game.player(), game.move_count())
     });
 
+    #[cfg(feature = "tui")]
+    let move_scores = live_move_scores(&game, name, &stats)?;
+    #[cfg(not(feature = "tui"))]
+    let move_scores = {
+        // the name is only shown while searching
+        let _ = name;
+        all_move_scores(&game, Some(stats.as_ref()), &None)
+    };
+
+    println!("Position: {}", game.to_notation());
+    show_stats::<T>(&stats);
+    show_scores(&game, move_scores, filter)?;
+    if stats_verbose {
+        show_ply_stats(&stats);
+    }
+
+    Ok(())
+}
+
+/// Scores the moves of `game` on another thread, while the terminal shows how the search is going,
+/// until it's done or quit.
+#[cfg(feature = "tui")]
+fn live_move_scores<
+    T: Game<Player = impl TwoPlayer + Sync + Send + 'static> + Eq + Hash + Sync + Send + 'static,
+>(
+    game: &T,
+    name: &'static str,
+    stats: &Arc<Stats<T::Player>>,
+) -> Result<CollectedMoves<T>>
+where
+    T::Move: Sync + Send,
+    T::MoveError: Sync + Send,
+{
+    let mut terminal = ratatui::init();
+
     let exit = Arc::new(AtomicBool::new(false));
     let exit_ui = Arc::new(AtomicBool::new(false));
 
     let mut app: App<T> = App {
+        name,
         exit: exit.clone(),
         stats: stats.clone(),
         exit_ui: exit_ui.clone(),
@@ -199,7 +249,7 @@ where
     let internal_game = game.clone();
     let internal_stats = stats.clone();
     let game_thread = thread::spawn(move || {
-        let move_scores = par_move_scores(
+        let move_scores = all_move_scores(
             &internal_game,
             Some(internal_stats.as_ref()),
             &Some(exit.clone()),
@@ -212,10 +262,8 @@ where
 
     app.run(&mut terminal)?;
     ratatui::restore();
-    let move_scores = game_thread.join().unwrap();
-
-    show_stats::<T>(&stats);
-    show_scores(&game, move_scores);
-
-    Ok(())
+    // a panic of the solver is a bug, and the CLI exits as it does for any other
+    Ok(game_thread
+        .join()
+        .unwrap_or_else(|panic| panic::resume_unwind(panic)))
 }
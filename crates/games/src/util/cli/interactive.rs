@@ -0,0 +1,434 @@
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+    hash::Hash,
+    io::{BufRead, Write},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use anyhow::{anyhow, Result};
+use game_solver::{
+    game::{score_to_outcome, Game, GameScoreOutcome},
+    player::TwoPlayer,
+    session::{PonderUntil, Session},
+    solve, solve_depth_limited,
+    tie_break::{TieBreak, TieBreaker},
+    transposition::Score,
+    GameSolveError,
+};
+#[cfg(feature = "rayon")]
+use game_solver::{think::think, transposition::DepthCache};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "rayon")]
+use std::{hash::RandomState, time::Instant};
+
+use crate::{
+    util::{
+        notation::NotatedGame,
+        parse::ParseMove,
+        record::MoveNotation,
+        render::{Render, Style},
+    },
+    GamePosition,
+};
+
+use super::{ending, exit::solver_failed, saved::SavedSession, Endings};
+
+/// Bounds on how long the solver can think for a single move.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolverLimits {
+    /// How many moves ahead the solver can look.
+    /// If `None`, the solver searches until the end of the game.
+    pub depth: Option<usize>,
+    /// How long the solver can search for before it must play the best move it found.
+    pub timeout: Option<Duration>,
+}
+
+/// A game between a human and the solver, whose table keeps the scores of the positions the solver solved.
+pub type PlaySession<T> = Session<T, HashMap<T, Score>>;
+
+/// How the solver plays against a human, which is saved along with the game.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayOptions {
+    /// Bounds on how long the solver can think for a single move.
+    pub limits: SolverLimits,
+    /// The seed the solver chooses among its best moves with, at random,
+    /// or None to always choose the first of them.
+    pub seed: Option<u64>,
+    /// Whether the solver searches the human's replies while waiting for their move,
+    /// so it can answer them from its transposition table.
+    pub ponder: bool,
+}
+
+impl PlayOptions {
+    /// How the solver chooses among its best moves.
+    pub fn tie_break<M>(&self) -> TieBreak<M> {
+        self.seed.map_or(TieBreak::First, TieBreak::Random)
+    }
+}
+
+/// Cancels the returned token once `timeout` elapses,
+/// unless the returned sender is dropped first.
+pub fn start_timer(
+    timeout: Option<Duration>,
+) -> (Option<Arc<AtomicBool>>, Option<mpsc::Sender<()>>) {
+    let Some(timeout) = timeout else {
+        return (None, None);
+    };
+
+    let token = Arc::new(AtomicBool::new(false));
+    let (sender, receiver) = mpsc::channel::<()>();
+
+    let timer_token = token.clone();
+    thread::spawn(move || {
+        if let Err(mpsc::RecvTimeoutError::Timeout) = receiver.recv_timeout(timeout) {
+            timer_token.store(true, Ordering::Relaxed);
+        }
+    });
+
+    (Some(token), Some(sender))
+}
+
+/// A move chosen by the solver, with its score if the search finished evaluating it.
+pub type SolverMove<T> = (<T as Game>::Move, Option<isize>);
+
+/// How many positions the table of a timed search keeps.
+#[cfg(feature = "rayon")]
+const THINKING_TABLE_SIZE: u64 = 1 << 22;
+
+/// Picks the solver's move for the current position, along with its score.
+///
+/// The transposition table is only used (and kept) for full-depth searches,
+/// as depth-limited scores aren't exact.
+///
+/// With a timeout but no depth, the solver [thinks](game_solver::think::think) until the timeout,
+/// searching one move deeper at a time, and plays the best move of the deepest search it finished
+/// (or the first possible move, if it finished none). This requires the `rayon` feature.
+/// With both, or without the feature, the moves are searched one after the other,
+/// and the best move found so far is played.
+///
+/// Moves that score the same are chosen between with `tie_break`.
+pub fn solver_move<
+    T: Game<Player = impl TwoPlayer + Send + Sync + 'static> + Eq + Hash + Send + Sync + 'static,
+>(
+    game: &T,
+    transposition_table: &mut HashMap<T, Score>,
+    limits: SolverLimits,
+    tie_break: &mut TieBreaker<T::Move>,
+) -> Result<Option<SolverMove<T>>, GameSolveError<T>>
+where
+    T::Move: Send + Sync,
+    T::MoveError: Send,
+{
+    #[cfg(feature = "rayon")]
+    if let (Some(timeout), None) = (limits.timeout, limits.depth) {
+        let table = Arc::new(DepthCache::<T, RandomState>::with_capacity(
+            THINKING_TABLE_SIZE,
+        ));
+        let Some(thought) = think(game, &table, Instant::now() + timeout)? else {
+            return Ok(None);
+        };
+
+        // a tie the search couldn't see the end of may not be one
+        let known = |score: isize| thought.complete || score != 0;
+        return Ok(match tie_break.best(thought.scores) {
+            Some((m, score)) => Some((m, known(score).then_some(score))),
+            None => Some((thought.best_move, None)),
+        });
+    }
+
+    let (cancellation_token, _timer) = start_timer(limits.timeout);
+
+    let mut scores = Vec::new();
+    let mut first_move = None;
+
+    for m in game.possible_moves() {
+        if first_move.is_none() {
+            first_move = Some(m.clone());
+        }
+
+        let mut board = game.clone();
+        board.make_move(&m).map_err(GameSolveError::MoveError)?;
+
+        let score = match limits.depth {
+            Some(depth) => {
+                solve_depth_limited(&board, depth.saturating_sub(1), &cancellation_token)
+            }
+            None => solve(&board, transposition_table, None, &cancellation_token),
+        };
+
+        let score = match score {
+            Ok(score) => -score,
+            Err(GameSolveError::CancellationTokenError) => break,
+            Err(err) => return Err(err),
+        };
+
+        scores.push((m, score));
+    }
+
+    Ok(tie_break
+        .best(scores)
+        .map(|(m, score)| (m, Some(score)))
+        .or_else(|| first_move.map(|m| (m, None))))
+}
+
+pub(super) fn describe_score<T: Game>(
+    game: &T,
+    score: Option<isize>,
+    limits: SolverLimits,
+) -> String {
+    match score.map(|score| score_to_outcome(game, score)) {
+        Some(GameScoreOutcome::Win(moves)) => format!("expects to win in {moves}"),
+        Some(GameScoreOutcome::Loss(moves)) => format!("expects to lose in {moves}"),
+        // depth-limited searches can't tell apart ties from unknown outcomes
+        Some(GameScoreOutcome::Tie) if limits.depth.is_some() => {
+            "found no forced result".to_string()
+        }
+        Some(GameScoreOutcome::Tie) => "expects a tie".to_string(),
+        None => "ran out of time".to_string(),
+    }
+}
+
+/// Plays the game of `session` between a human (reading moves from `input`) and the solver,
+/// writing the board, drawn in `style` with the last move highlighted, and every move to `output` until the game ends.
+/// The moves played are kept in the session's history, even if the game doesn't finish.
+///
+/// Invalid moves are reported, and the human is asked again.
+/// Entering `undo` takes back the human's last move along with the solver's reply to it.
+/// Entering `save FILE` saves the session to `FILE` with `options` (and its transposition table, with `save FILE --table`),
+/// and `load FILE` replaces it, and the options, with the one saved there, which is the human's turn.
+///
+/// Returns true if the solver won, false if the human won, and None on a tie.
+pub fn play_interactive<
+    T: Game<Player = impl TwoPlayer + Send + Sync + 'static>
+        + MoveNotation
+        + NotatedGame
+        + Eq
+        + Hash
+        + Endings
+        + Render
+        + Send
+        + Sync
+        + 'static,
+>(
+    session: &mut PlaySession<T>,
+    solver_first: bool,
+    mut options: PlayOptions,
+    style: Style,
+    mut input: impl BufRead,
+    mut output: impl Write,
+) -> Result<Option<bool>>
+where
+    T::Move: Display + PartialEq + Send + Sync,
+    T::MoveError: Display + Send,
+    GamePosition: From<T>,
+{
+    let mut tie_break = TieBreaker::new(options.tie_break());
+    let mut solver_turn = solver_first;
+
+    loop {
+        let game = session.game().clone();
+        let last_move = session.history().moves().last();
+        writeln!(output, "{}", game.render(style, last_move))?;
+
+        // the winner is either the player to move, or the player that just moved.
+        let solver_won = |winner| (winner == game.player()) == solver_turn;
+        let name = |winner| {
+            if solver_won(winner) {
+                "The solver".to_string()
+            } else {
+                "You".to_string()
+            }
+        };
+
+        if let Some(ending) = ending(&game, name) {
+            writeln!(output, "{ending}")?;
+            return Ok(game.winner().map(solver_won));
+        }
+
+        let turn = if solver_turn {
+            let (m, score) = solver_move(&game, &mut session.table, options.limits, &mut tie_break)
+                .map_err(solver_failed)?
+                .ok_or_else(|| anyhow!("The solver has no moves in a playable position."))?;
+
+            writeln!(
+                output,
+                "The solver plays {m} ({}).",
+                describe_score(&game, score, options.limits)
+            )?;
+
+            Turn::Move(m)
+        } else if options.ponder {
+            let token = Arc::new(AtomicBool::new(false));
+
+            let (m, report) = thread::scope(|scope| {
+                let pondering =
+                    scope.spawn(|| session.ponder(PonderUntil::Cancelled(token.clone())));
+
+                let m = human_move(&game, &mut input, &mut output);
+                token.store(true, Ordering::Relaxed);
+
+                let report = pondering
+                    .join()
+                    .expect("the solver panicked while pondering");
+                (m, report)
+            });
+            let report = report.map_err(solver_failed)?;
+            let turn = m?;
+
+            writeln!(
+                output,
+                "The solver pondered {} of your replies while you thought.",
+                report.replies
+            )?;
+
+            turn
+        } else {
+            human_move(&game, &mut input, &mut output)?
+        };
+
+        let m = match turn {
+            Turn::Move(m) => m,
+            Turn::Undo => {
+                take_back(session, &mut output)?;
+                continue;
+            }
+            Turn::Save { path, table } => {
+                let saved = SavedSession::new(session, options, table);
+                match saved.write(&path) {
+                    Ok(()) => writeln!(output, "Saved the game to {}.", path.display())?,
+                    Err(err) => writeln!(output, "{err}")?,
+                }
+                continue;
+            }
+            Turn::Load(path) => {
+                match SavedSession::read(&path).and_then(|saved| saved.restore(&game)) {
+                    Ok((restored, restored_options)) => {
+                        *session = restored;
+                        options = restored_options;
+                        tie_break = TieBreaker::new(options.tie_break());
+                        solver_turn = false;
+                        writeln!(output, "{}", describe_resumed(session))?;
+                    }
+                    Err(err) => writeln!(output, "{err}")?,
+                }
+                continue;
+            }
+        };
+
+        session
+            .apply(&m)
+            .map_err(|err| anyhow!("Failed to move: {err}"))?;
+        solver_turn = !solver_turn;
+    }
+}
+
+/// What the human chose to do on their turn.
+enum Turn<M> {
+    Move(M),
+    /// Take back their last move, and the solver's reply to it.
+    Undo,
+    /// Save the session to a file, with its transposition table if `table` is set.
+    Save {
+        path: PathBuf,
+        table: bool,
+    },
+    /// Replace the session with the one saved in a file.
+    Load(PathBuf),
+}
+
+/// Says how far into the game a resumed session is, and how much of its analysis it kept.
+pub fn describe_resumed<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    session: &PlaySession<T>,
+) -> String {
+    format!(
+        "Resumed the game after {} moves, with {} solved positions.",
+        session.history().ply(),
+        session.table.len()
+    )
+}
+
+/// Takes back the human's last move and the solver's reply, so it's the human's turn again.
+fn take_back<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash>(
+    session: &mut PlaySession<T>,
+    output: &mut impl Write,
+) -> Result<()>
+where
+    T::Move: Display,
+{
+    // the human moves every other ply, so their last move is two plies back
+    if session.history().ply() < 2 {
+        writeln!(output, "You have no move to take back.")?;
+        return Ok(());
+    }
+
+    let reply = session.undo().map(ToString::to_string).unwrap_or_default();
+    let m = session.undo().map(ToString::to_string).unwrap_or_default();
+    writeln!(
+        output,
+        "You took back {m}, and the solver took back {reply}."
+    )?;
+
+    Ok(())
+}
+
+/// Asks the human for a legal move (or `undo`, `save`, or `load`), until they enter one.
+fn human_move<T: Game + ParseMove>(
+    game: &T,
+    input: &mut impl BufRead,
+    output: &mut impl Write,
+) -> Result<Turn<T::Move>>
+where
+    T::Move: Display + PartialEq,
+{
+    loop {
+        write!(output, "Your move: ")?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Err(anyhow!("Input ended before the game was finished."));
+        }
+
+        if line.trim() == "undo" {
+            return Ok(Turn::Undo);
+        }
+
+        if let Some(path) = line.trim().strip_prefix("save ") {
+            let (path, table) = match path.trim().strip_suffix("--table") {
+                Some(path) => (path.trim(), true),
+                None => (path.trim(), false),
+            };
+            return Ok(Turn::Save {
+                path: PathBuf::from(path),
+                table,
+            });
+        }
+
+        if let Some(path) = line.trim().strip_prefix("load ") {
+            return Ok(Turn::Load(PathBuf::from(path.trim())));
+        }
+
+        let m = match game.parse_move(line.trim()) {
+            Ok(m) => m,
+            Err(err) => {
+                writeln!(output, "Invalid move: {err}")?;
+                continue;
+            }
+        };
+
+        // some games panic on illegal moves, so we check them beforehand
+        if !game.possible_moves().any(|possible| possible == m) {
+            writeln!(output, "Illegal move: {m} can not be played here.")?;
+            continue;
+        }
+
+        return Ok(Turn::Move(m));
+    }
+}
@@ -0,0 +1,19 @@
+use std::io::Write;
+
+use anyhow::Result;
+use game_solver::game::Game;
+use serde::Serialize;
+
+use super::report::analysis::Report;
+
+/// Writes the analysis of a position to `output` as pretty-printed JSON.
+pub fn json_output<T: Game + Serialize>(report: &Report<T>, mut output: impl Write) -> Result<()>
+where
+    T::Move: Serialize,
+    T::Player: Serialize,
+{
+    serde_json::to_writer_pretty(&mut output, report)?;
+    writeln!(output)?;
+
+    Ok(())
+}
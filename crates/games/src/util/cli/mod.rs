@@ -1,67 +1,661 @@
+pub mod arena;
+mod batch;
+pub mod bench;
+pub mod census;
+mod csv;
+mod db;
+pub mod describe;
+pub mod endgame;
+pub mod evaluate;
+pub mod exit;
+mod explain;
+pub mod exploit;
+#[cfg(feature = "http")]
+pub mod http;
 mod human;
+pub mod interactive;
+mod json;
+mod multipv;
+mod only;
+pub mod openings;
+pub mod outcome;
+pub mod perft;
+pub mod precompute;
+mod probe;
+pub mod puzzle;
 mod report;
 mod robot;
+pub mod saved;
+mod self_play;
+pub mod serve;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod verify;
 
 use anyhow::{anyhow, Result};
+use batch::batch_output;
+use clap::{Args, ValueEnum};
+use csv::csv_output;
+use db::{db_move_scores, SolveDb};
+use evaluate::{evaluate_output, heuristic_strategy, Algorithm, Eval, Heuristics};
+use exit::invalid;
+use explain::explain_output;
+#[cfg(not(feature = "rayon"))]
+use game_solver::move_scores;
+#[cfg(feature = "rayon")]
+use game_solver::par_move_scores;
 use game_solver::{
+    dot::export_tree,
     game::{Game, GameState},
-    player::{ImpartialPlayer, TwoPlayer},
+    history::History,
+    player::TwoPlayer,
+    random::random_position,
+    session::Session,
+    stats::Stats,
+    tie_break::TieBreak,
+    CollectedMoves,
 };
 use human::human_output;
-use robot::robotic_output;
+use interactive::{describe_resumed, play_interactive, PlayOptions, SolverLimits};
+use json::json_output;
+use multipv::multi_pv_output;
+use only::only_output;
+use outcome::{outcome_class_output, OutcomeClasses};
+use probe::probe_output;
+use rand::{rngs::StdRng, SeedableRng};
+use report::{
+    analysis::{MoveFilter, Report},
+    scores::show_scores,
+};
+use robot::{print_position, robotic_output};
+use saved::SavedSession;
+use self_play::self_play;
+use serde::Serialize;
 use std::{
-    any::TypeId,
+    collections::HashMap,
     fmt::{Debug, Display},
+    fs::{self, File},
     hash::Hash,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::{atomic::AtomicBool, Arc},
+    time::Duration,
 };
 
+use crate::GamePosition;
+
+use super::{
+    notation::NotatedGame,
+    parse::ParseMove,
+    record::{save_record, GameRecord, MoveNotation, RecordError},
+    render::{Render, Style},
+};
+
+/// How the analysis of a position is printed.
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text.
+    #[default]
+    Text,
+    /// A JSON document with the position, the player to move,
+    /// the score of every move, and the overall evaluation.
+    Json,
+    /// One row per move, with its score, outcome, and rank.
+    Csv,
+}
+
+/// Options shared by every game's command line interface.
+#[derive(Args, Debug, Clone, Default)]
+pub struct CliOptions {
+    /// Print the analysis as plain text, instead of showing live progress,
+    /// and boards as the plain text scripts read, as they are whenever the output isn't a terminal.
+    #[arg(short, long)]
+    pub plain: bool,
+    /// The format to print the analysis in.
+    #[arg(long, value_enum, default_value_t, conflicts_with_all = ["play", "self_play"])]
+    pub format: OutputFormat,
+    /// Write the analysis (or the self-played game) to this file, instead of stdout.
+    /// Only supported by the JSON and CSV formats, self-play, and --batch.
+    #[arg(short, long, conflicts_with = "play")]
+    pub output: Option<PathBuf>,
+    /// Only show the best-scoring moves (every move tied for best is shown).
+    #[arg(long, conflicts_with_all = ["top", "play", "self_play"])]
+    pub best: bool,
+    /// Only show the N best moves.
+    #[arg(long, value_name = "N", conflicts_with_all = ["play", "self_play"], value_parser = clap::value_parser!(u64).range(1..))]
+    pub top: Option<u64>,
+    /// Play against the solver, entering moves through stdin, instead of analyzing the position.
+    #[arg(long, conflicts_with = "self_play")]
+    pub play: bool,
+    /// Let the solver make the first move when playing against it.
+    #[arg(long, requires = "play")]
+    pub solver_first: bool,
+    /// Only let the solver look this many moves ahead when playing against it,
+    /// only export this many moves of the game tree with --export-dot,
+    /// or search this many moves ahead with --eval.
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub depth: Option<u64>,
+    /// Give the solver this many seconds per move when playing against it.
+    /// It searches one move deeper at a time (without --depth), and once the time is up,
+    /// plays the best move of the deepest search it finished.
+    #[arg(long, requires = "play", value_parser = parse_seconds)]
+    pub timeout: Option<Duration>,
+    /// Let the solver search your possible replies while you think when playing against it,
+    /// so it can answer them faster.
+    #[arg(long, requires = "play", conflicts_with = "depth")]
+    pub ponder: bool,
+    /// Let the solver play both sides, printing every move until the game ends.
+    #[arg(long)]
+    pub self_play: bool,
+    /// The seed for anything random: choosing among the solver's best moves with --play and --self-play,
+    /// and the moves of --random-plies. The same seed always makes the same choices, on any platform.
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Play this many random moves after the given ones, to start from a random position.
+    /// Use --seed to get the same position every time.
+    #[arg(long, value_name = "N")]
+    pub random_plies: Option<usize>,
+    /// Analyze every line of this file instead, where each line is a list of moves
+    /// to play from the given position. Prints the best move of each line.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["play", "self_play", "export_dot", "random_plies", "best", "top"])]
+    pub batch: Option<PathBuf>,
+    /// How many threads to solve on, for scoring moves, the lines of --batch, and the games of `arena`.
+    /// Defaults to one per core. With 1, everything is solved in order, so runs are reproducible.
+    #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+    pub threads: Option<u64>,
+    /// Write the game tree, --depth moves deep, to this file as a Graphviz DOT graph,
+    /// instead of analyzing the position.
+    #[arg(long, value_name = "FILE", requires = "depth", conflicts_with_all = ["play", "self_play", "output"])]
+    pub export_dot: Option<PathBuf>,
+    /// Evaluate the position with this algorithm, printing its verdict and the move it would play,
+    /// instead of scoring every move.
+    #[arg(long, value_enum, conflicts_with_all = ["play", "self_play", "batch", "export_dot", "best", "top", "format"])]
+    pub algorithm: Option<Algorithm>,
+    /// Solve the position, then print what the transposition table holds about the position
+    /// after these space-separated moves, or about the position itself if there are none.
+    #[arg(long, value_name = "MOVES", conflicts_with_all = ["play", "self_play", "batch", "export_dot", "algorithm", "best", "top", "format"])]
+    pub probe: Option<String>,
+    /// Print the line that refutes this move: the opponent's best reply,
+    /// and the best moves of both sides after it, with the evaluation after each of them.
+    #[arg(long, value_name = "MOVE", conflicts_with_all = ["play", "self_play", "batch", "export_dot", "algorithm", "probe", "best", "top", "format"])]
+    pub explain: Option<String>,
+    /// Print the K best moves, each with its principal variation: the best moves of both sides after it.
+    #[arg(long, value_name = "K", value_parser = clap::value_parser!(u64).range(1..), conflicts_with_all = ["play", "self_play", "batch", "export_dot", "algorithm", "probe", "explain", "best", "top", "format"])]
+    pub multipv: Option<u64>,
+    /// Only score these comma-separated moves, in the order they're listed,
+    /// which is much faster than scoring every move when only a few matter.
+    #[arg(long, value_name = "MOVES", conflicts_with_all = ["play", "self_play", "batch", "export_dot", "algorithm", "probe", "explain", "multipv", "best", "top", "format"])]
+    pub only: Option<String>,
+    /// Take the scores of the moves from this file when it has them, and add those that had to be solved,
+    /// so that later runs with the same file don't solve them again. The text analysis is printed like --plain.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["play", "self_play", "batch", "export_dot", "algorithm", "probe", "explain", "multipv", "only"])]
+    pub db: Option<PathBuf>,
+    /// Search --depth moves ahead, scoring the positions beyond with this heuristic evaluator,
+    /// then print the evaluation and the move it would play. For positions too far from the end to solve.
+    #[arg(long, value_enum, requires = "depth", conflicts_with_all = ["play", "self_play", "batch", "export_dot", "algorithm", "probe", "explain", "multipv", "only", "db", "best", "top", "format"])]
+    pub eval: Option<Eval>,
+    /// Print the position's outcome class instead of analyzing it: whether Left or Right wins whoever moves first,
+    /// or whoever moves first or second wins. Only supported by Domineering and impartial games.
+    #[arg(long, conflicts_with_all = ["play", "self_play", "batch", "export_dot", "algorithm", "probe", "explain", "multipv", "only", "db", "eval", "best", "top", "format"])]
+    pub outcome_class: bool,
+    /// After the analysis, print a table of what the search did at each ply: how many positions it searched,
+    /// how many of them were cut off (on the first, second, or a later move), and its branching factor.
+    #[arg(long, conflicts_with_all = ["play", "self_play", "batch", "export_dot", "algorithm", "probe", "explain", "multipv", "only", "db", "eval", "outcome_class", "format"])]
+    pub stats_verbose: bool,
+    /// Start from the end of the game recorded in FILE (as --save-game writes it), instead of the position
+    /// given after the game's name. The game's arguments are still needed, but only pick the game.
+    #[arg(long, value_name = "FILE", conflicts_with = "random_plies")]
+    pub replay: Option<PathBuf>,
+    /// Pick up a game of --play saved to FILE with `save`, along with the options it was played with
+    /// (and the solver's analysis, if it was saved with `save FILE --table`).
+    /// The game's arguments are still needed, but only pick the game.
+    #[arg(long, value_name = "FILE", requires = "play", conflicts_with_all = ["replay", "random_plies", "solver_first", "depth", "timeout", "ponder"])]
+    pub resume: Option<PathBuf>,
+    /// Save the game played with --play or --self-play to FILE, as a record that --replay reads back.
+    /// A game replayed with --replay is saved from where its record started.
+    #[arg(long, value_name = "FILE")]
+    pub save_game: Option<PathBuf>,
+    /// How many random games --algorithm mcts plays.
+    #[arg(long, value_name = "N", requires = "algorithm", value_parser = clap::value_parser!(u64).range(1..))]
+    pub iterations: Option<u64>,
+}
+
+impl CliOptions {
+    pub fn solver_limits(&self) -> SolverLimits {
+        SolverLimits {
+            depth: self.depth.map(|depth| depth as usize),
+            timeout: self.timeout,
+        }
+    }
+
+    /// How the solver plays against a human with --play.
+    pub fn play_options(&self) -> PlayOptions {
+        PlayOptions {
+            limits: self.solver_limits(),
+            seed: self.seed,
+            ponder: self.ponder,
+        }
+    }
+
+    /// How the solver chooses among its best moves: at random from --seed, or the first of them.
+    pub fn tie_break<M>(&self) -> TieBreak<M> {
+        self.seed.map_or(TieBreak::First, TieBreak::Random)
+    }
+
+    pub fn move_filter(&self) -> MoveFilter {
+        match (self.best, self.top) {
+            (true, _) => MoveFilter::Best,
+            (false, Some(n)) => MoveFilter::Top(n as usize),
+            (false, None) => MoveFilter::All,
+        }
+    }
+
+    /// The style boards are drawn in: plain with `--plain` or `--output`, or if stdout isn't a terminal.
+    pub fn style(&self) -> Style {
+        if self.output.is_some() {
+            Style::Plain
+        } else {
+            Style::detect(self.plain)
+        }
+    }
+
+    /// Opens the file given by `--output`, or stdout if there is none.
+    pub fn output_writer(&self) -> Result<Box<dyn Write>> {
+        match &self.output {
+            Some(path) => Ok(Box::new(create_file(path)?)),
+            None => Ok(Box::new(io::stdout())),
+        }
+    }
+}
+
+fn create_file(path: &Path) -> Result<BufWriter<File>> {
+    let file =
+        File::create(path).map_err(|err| anyhow!("Could not create {}: {err}", path.display()))?;
+
+    Ok(BufWriter::new(file))
+}
+
+fn parse_seconds(s: &str) -> Result<Duration, String> {
+    s.parse::<f64>()
+        .map_err(|err| err.to_string())
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).map_err(|err| err.to_string()))
+}
+
 pub fn play<
-    T: Game<Player = impl TwoPlayer + Debug + Sync + Send + 'static>
+    T: Game<Player = impl TwoPlayer + Serialize + Sync + Send + 'static>
+        + ParseMove
         + Eq
         + Hash
         + Sync
         + Send
         + Display
         + Debug
+        + Serialize
+        + NotatedGame
+        + Heuristics
+        + OutcomeClasses
+        + Endings
+        + Render
         + 'static,
 >(
-    game: T,
-    plain: bool,
-) where
-    T::Move: Sync + Send + Display,
-    T::MoveError: Sync + Send + Debug,
+    mut game: T,
+    options: &CliOptions,
+) -> Result<()>
+where
+    T::Move: Sync + Send + Display + PartialEq + Serialize,
+    T::MoveError: Sync + Send + Debug + Display,
+    GamePosition: From<T>,
 {
+    if options.depth.is_some()
+        && !options.play
+        && options.export_dot.is_none()
+        && options.eval.is_none()
+    {
+        return Err(invalid(anyhow!(
+            "--depth is only supported by --play, --export-dot, and --eval."
+        )));
+    }
+
+    let mcts = options.algorithm == Some(Algorithm::Mcts);
+
+    if options.seed.is_some()
+        && !options.play
+        && !options.self_play
+        && options.random_plies.is_none()
+        && !mcts
+    {
+        return Err(invalid(anyhow!(
+            "--seed is only supported by --play, --self-play, --random-plies, and --algorithm mcts."
+        )));
+    }
+
+    if options.iterations.is_some() && !mcts {
+        return Err(invalid(anyhow!(
+            "--iterations is only supported by --algorithm mcts."
+        )));
+    }
+
+    if options.save_game.is_some() && !options.play && !options.self_play {
+        return Err(invalid(anyhow!(
+            "--save-game is only supported by --play and --self-play."
+        )));
+    }
+
+    if let Some(plies) = options.random_plies {
+        let mut rng = match options.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        let played;
+        (game, played) = random_position(&game, plies, &mut rng)
+            .map_err(|err| anyhow!("Failed to move: {err}"))?;
+
+        if played < plies {
+            eprintln!("The game ended after {played} random moves.");
+        }
+    }
+
+    let mut history = match &options.replay {
+        Some(path) => read_record(&game, path).map_err(invalid)?,
+        None => History::new(game.clone()),
+    };
+    let game = history.game().clone();
+
+    if let Some(algorithm) = options.algorithm {
+        let mut strategy = algorithm.strategy(options.iterations, options.seed);
+        let mut output = options.output_writer()?;
+        evaluate_output(&game, strategy.as_mut(), &mut output)?;
+
+        return Ok(output.flush()?);
+    }
+
+    if let Some(kind) = options.eval {
+        let depth = options.depth.expect("--eval requires --depth") as usize;
+        let mut strategy = heuristic_strategy(kind, depth)?;
+        let mut output = options.output_writer()?;
+        evaluate_output(&game, strategy.as_mut(), &mut output)?;
+
+        return Ok(output.flush()?);
+    }
+
+    if options.outcome_class {
+        let mut output = options.output_writer()?;
+        outcome_class_output(&game, &mut output)?;
+
+        return Ok(output.flush()?);
+    }
+
+    if let Some(moves) = &options.probe {
+        let mut output = options.output_writer()?;
+        probe_output(&game, moves, &mut output)?;
+
+        return Ok(output.flush()?);
+    }
+
+    if let Some(m) = &options.explain {
+        let mut output = options.output_writer()?;
+        explain_output(&game, m, &mut output)?;
+
+        return Ok(output.flush()?);
+    }
+
+    if let Some(k) = options.multipv {
+        let mut output = options.output_writer()?;
+        multi_pv_output(&game, k as usize, &mut output)?;
+
+        return Ok(output.flush()?);
+    }
+
+    if let Some(moves) = &options.only {
+        let mut output = options.output_writer()?;
+        only_output(&game, moves, &mut output)?;
+
+        return Ok(output.flush()?);
+    }
+
+    if let Some(path) = &options.export_dot {
+        let mut output = create_file(path)?;
+        let depth = options.depth.expect("--export-dot requires --depth") as usize;
+        export_tree(&game, depth, &mut output)
+            .map_err(|err| anyhow!("Could not export the game tree: {err}"))?;
+
+        return Ok(output.flush()?);
+    }
+
+    if let Some(path) = &options.batch {
+        let mut output = options.output_writer()?;
+        batch_output(&game, path, options.format, &mut output)?;
+
+        return Ok(output.flush()?);
+    }
+
+    if options.play {
+        let (mut session, play_options) = match &options.resume {
+            Some(path) => {
+                let resumed = SavedSession::read(path)
+                    .and_then(|saved| saved.restore(&game))
+                    .map_err(invalid)?;
+                println!("{}", describe_resumed(&resumed.0));
+                resumed
+            }
+            None => (
+                Session::from_history(history, HashMap::new()),
+                options.play_options(),
+            ),
+        };
+        let played = play_interactive(
+            &mut session,
+            options.solver_first,
+            play_options,
+            options.style(),
+            io::stdin().lock(),
+            io::stdout(),
+        );
+
+        // an unfinished game is saved too, so it can be picked up with --replay
+        save_game(options, session.history())?;
+        played?;
+
+        return Ok(());
+    }
+
+    if options.self_play {
+        let mut output = options.output_writer()?;
+        let played = self_play(
+            &mut history,
+            options.tie_break(),
+            options.style(),
+            &mut output,
+        );
+
+        save_game(options, &history)?;
+        played?;
+
+        return Ok(output.flush()?);
+    }
+
+    match options.format {
+        OutputFormat::Text => {
+            if options.output.is_some() {
+                return Err(invalid(anyhow!(
+                    "--output is only supported by --format json, --format csv, --self-play, and --batch."
+                )));
+            }
+        }
+        OutputFormat::Json | OutputFormat::Csv => {
+            let report = match &options.db {
+                Some(path) => {
+                    Report::with_scores(&game, solve_with_db(&game, path)?, options.move_filter())?
+                }
+                None => Report::new(&game, options.move_filter())?,
+            };
+
+            let mut output = options.output_writer()?;
+            match options.format {
+                OutputFormat::Json => json_output(&report, &mut output)?,
+                _ => csv_output(&report, &mut output)?,
+            }
+
+            return Ok(output.flush()?);
+        }
+    }
+
+    if let (Some(path), GameState::Playable) = (&options.db, game.state()) {
+        print_position(&game);
+        let move_scores = solve_with_db(&game, path)?;
+        return show_scores(&game, move_scores, options.move_filter());
+    }
+
     match game.state() {
         GameState::Playable => {
-            if plain {
-                robotic_output(game);
+            if options.plain {
+                robotic_output(game, options.move_filter(), options.stats_verbose)?;
             } else {
-                human_output(game).unwrap();
+                let name = GamePosition::from(game.clone()).kind().name();
+                human_output(game, name, options.move_filter(), options.stats_verbose)?;
             }
         }
-        GameState::Tie => println!("No moves left! Game tied!"),
-        GameState::Win(player) => {
-            if TypeId::of::<T::Player>() != TypeId::of::<ImpartialPlayer>() {
-                println!("The {player:?} player won!");
-            } else {
-                println!("Player {player:?} won!");
+        GameState::Tie | GameState::Win(_) => {
+            if let Some(ending) = ending(&game, |winner| format!("The {winner} player")) {
+                println!("{ending}");
             }
         }
     }
+
+    Ok(())
+}
+
+/// Reads the record at `path`, which has to be of the same game as `game`, and replays its moves.
+fn read_record<T>(game: &T, path: &Path) -> Result<History<T>>
+where
+    T: MoveNotation + NotatedGame,
+    T::Move: PartialEq,
+    GamePosition: From<T>,
+{
+    let record = fs::read_to_string(path)
+        .map_err(|err| anyhow!("Could not read {}: {err}", path.display()))?;
+
+    replay_record(game, &record).map_err(|err| anyhow!("{}: {err}", path.display()))
+}
+
+/// Reads `record`, which has to be of the same game as `game`, and replays its moves.
+fn replay_record<T>(game: &T, record: &str) -> Result<History<T>, RecordError>
+where
+    T: MoveNotation + NotatedGame,
+    T::Move: PartialEq,
+    GamePosition: From<T>,
+{
+    let record = record.parse::<GameRecord>()?;
+
+    // checked before the starting position, whose notation is only understood by its own game
+    let expected = GamePosition::from(game.clone()).kind();
+    if record.game != expected {
+        return Err(RecordError::WrongGame {
+            expected: expected.name(),
+            found: record.game.name(),
+        });
+    }
+
+    record.replay()
+}
+
+/// Writes the record of `history` to the file given by `--save-game`, if there is one.
+fn save_game<T>(options: &CliOptions, history: &History<T>) -> Result<()>
+where
+    T: MoveNotation + NotatedGame,
+    GamePosition: From<T>,
+{
+    let Some(path) = &options.save_game else {
+        return Ok(());
+    };
+
+    fs::write(path, save_record(history))
+        .map_err(|err| anyhow!("Could not write {}: {err}", path.display()))
+}
+
+/// A game that says how its finished positions were decided, which the line that ends them adds.
+pub trait Endings: Game {
+    /// How the game was decided, such as by a full board, or None (the default) if who won says it all.
+    fn decided_by(&self) -> Option<String> {
+        None
+    }
+}
+
+/// The line that ends a finished game: `Game tied!`, or `<winner> won!`,
+/// with the winner as `name` names them (such as `The solver`), followed by how it was decided if the game says,
+/// or None if the game isn't over.
+pub fn ending<T: Endings>(game: &T, name: impl FnOnce(T::Player) -> String) -> Option<String> {
+    let ending = if game.is_tie() {
+        "Game tied!".to_string()
+    } else {
+        format!("{} won!", name(game.winner()?))
+    };
+
+    Some(match game.decided_by() {
+        Some(decided_by) => format!("{ending} {decided_by}"),
+        None => ending,
+    })
+}
+
+/// Scores every move of `game`, taking what it can from the database at `path` and saving what it solved there.
+fn solve_with_db<T: Game<Player = impl TwoPlayer + 'static> + NotatedGame + Eq + Hash>(
+    game: &T,
+    path: &Path,
+) -> Result<CollectedMoves<T>>
+where
+    GamePosition: From<T>,
+{
+    let mut db = SolveDb::open(path)?;
+    if db.skipped() > 0 {
+        eprintln!(
+            "Skipped {} unreadable lines in {}.",
+            db.skipped(),
+            path.display()
+        );
+    }
+
+    let (move_scores, hits) = db_move_scores(game, &mut db);
+    db.save()?;
+    eprintln!(
+        "Found {hits} of {} moves in {}.",
+        move_scores.len(),
+        path.display()
+    );
+
+    Ok(move_scores)
+}
+
+/// Scores every move of `game`, on every thread with the `rayon` feature, and one after the other without it.
+fn all_move_scores<
+    T: Game<Player = impl TwoPlayer + Sync + 'static> + Eq + Hash + Sync + Send + 'static,
+>(
+    game: &T,
+    stats: Option<&Stats<T::Player>>,
+    cancellation_token: &Option<Arc<AtomicBool>>,
+) -> CollectedMoves<T>
+where
+    T::Move: Sync + Send,
+    T::MoveError: Sync + Send,
+{
+    #[cfg(feature = "rayon")]
+    return par_move_scores(game, stats, cancellation_token);
+    #[cfg(not(feature = "rayon"))]
+    move_scores(game, &mut HashMap::new(), stats, cancellation_token).collect()
 }
 
 pub fn move_failable<T>(game: &mut T, m: &T::Move) -> Result<()>
 where
     T: Game,
     T::MoveError: Display,
-    T::Player: Debug,
+    T::Player: TwoPlayer,
 {
     match game.state() {
         GameState::Playable => (),
         GameState::Tie => return Err(anyhow!("Can't continue - game is tied.")),
         GameState::Win(player) => {
             return Err(anyhow!(
-                "Can't continue game if player {player:?} already won."
+                "Can't continue game if player {player} already won."
             ))
         }
     };
@@ -69,3 +663,45 @@ where
     game.make_move(m)
         .map_err(|err| anyhow!("Failed to move: {}", err))
 }
+
+/// Plays the moves given on the command line from `game`, in order.
+///
+/// If one of them can't be played, the error names it and its place in the list.
+pub fn replay_moves<T>(game: &mut T, moves: &[T::Move]) -> Result<()>
+where
+    T: Game,
+    T::Move: Display,
+    T::MoveError: Display,
+    T::Player: TwoPlayer,
+{
+    replay(game, moves, |_, m| Ok(m.clone()))
+}
+
+/// Like [`replay_moves`], but for moves that are parsed in the position they're played in.
+pub fn replay_parsed_moves<T>(game: &mut T, moves: &[String]) -> Result<()>
+where
+    T: ParseMove,
+    T::MoveError: Display,
+    T::Player: TwoPlayer,
+{
+    replay(game, moves, |game, token| Ok(game.parse_move(token)?))
+}
+
+fn replay<T, M: Display>(
+    game: &mut T,
+    moves: &[M],
+    to_move: impl Fn(&T, &M) -> Result<T::Move>,
+) -> Result<()>
+where
+    T: Game,
+    T::MoveError: Display,
+    T::Player: TwoPlayer,
+{
+    for (index, arg) in moves.iter().enumerate() {
+        to_move(game, arg)
+            .and_then(|m| move_failable(game, &m))
+            .map_err(|err| invalid(anyhow!("Could not play move {} ({arg}): {err}", index + 1)))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+use std::{collections::HashMap, fmt::Display, hash::Hash, io::Write};
+
+use anyhow::{anyhow, Result};
+use game_solver::{game::Game, multi_pv, player::TwoPlayer};
+use itertools::Itertools;
+
+use super::{
+    exit::solver_failed,
+    interactive::{describe_score, SolverLimits},
+    report::scores::score_label,
+};
+
+/// Writes the `k` best moves of `game`, each with its score and principal variation.
+pub fn multi_pv_output<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash + Display>(
+    game: &T,
+    k: usize,
+    mut output: impl Write,
+) -> Result<()>
+where
+    T::Move: Display,
+{
+    let lines = multi_pv(game, &mut HashMap::new(), k).map_err(solver_failed)?;
+
+    writeln!(output, "{game}")?;
+    if lines.is_empty() {
+        writeln!(output, "The game is over.")?;
+    }
+
+    for (rank, (m, score, line)) in lines.iter().enumerate() {
+        let mut position = game.clone();
+        position
+            .make_move(m)
+            .map_err(|_| anyhow!("{m} isn't a possible move."))?;
+
+        writeln!(
+            output,
+            "{}. {m} ({}: {score}, the opponent {}): {}",
+            rank + 1,
+            score_label(game),
+            describe_score(&position, Some(-score), SolverLimits::default()),
+            line.iter().join(" ")
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tic_tac_toe::TicTacToe, util::notation::NotatedGame};
+    use game_solver::game::GameState;
+
+    #[test]
+    fn the_best_tic_tac_toe_lines_are_ties() {
+        let game = TicTacToe::new(2, 3);
+        let lines = multi_pv(&game, &mut HashMap::new(), 3).unwrap();
+
+        assert_eq!(lines.len(), 3);
+        for (m, score, line) in lines {
+            assert_eq!(score, 0, "{m}");
+
+            let mut position = game.clone();
+            position.make_move(&m).unwrap();
+            for m in &line {
+                assert_eq!(position.state(), GameState::Playable);
+                position.make_move(m).unwrap();
+            }
+            assert_eq!(position.state(), GameState::Tie, "{m}: {position}");
+        }
+
+        let mut output = Vec::new();
+        multi_pv_output(&game, 3, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("\n3. "), "{output}");
+        assert!(!output.contains("\n4. "), "{output}");
+    }
+
+    #[test]
+    fn ties_keep_the_move_order() {
+        let game = TicTacToe::from_notation("X../.../... O").unwrap();
+        let lines = multi_pv(&game, &mut HashMap::new(), 9).unwrap();
+
+        assert_eq!(lines.len(), 8);
+        assert!(lines.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+
+        let order = game.possible_moves().collect::<Vec<_>>();
+        let index = |m| order.iter().position(|other| other == m).unwrap();
+        assert!(lines
+            .windows(2)
+            .filter(|pair| pair[0].1 == pair[1].1)
+            .all(|pair| index(&pair[0].0) < index(&pair[1].0)));
+    }
+}
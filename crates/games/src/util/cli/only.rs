@@ -0,0 +1,121 @@
+use std::{collections::HashMap, fmt::Display, hash::Hash, io::Write};
+
+use anyhow::{anyhow, Result};
+use game_solver::{
+    game::{Game, GameState},
+    move_scores_for,
+    player::TwoPlayer,
+};
+
+use crate::util::parse::ParseMove;
+
+use super::{
+    exit::{invalid, solver_failed},
+    report::scores::describe_move,
+};
+
+/// Parses `moves`, a comma-separated list of moves of `game`,
+/// naming the entry that can't be parsed or isn't a possible move.
+fn parse_candidates<T: ParseMove>(game: &T, moves: &str) -> Result<Vec<T::Move>>
+where
+    T::Move: PartialEq,
+{
+    moves
+        .split(',')
+        .map(str::trim)
+        .enumerate()
+        .map(|(index, token)| {
+            let m = game
+                .parse_move(token)
+                .map_err(|err| anyhow!("Could not parse move {} ({token}): {err}", index + 1))?;
+
+            if !game.possible_moves().any(|possible| possible == m) {
+                return Err(anyhow!(
+                    "Move {} ({token}) isn't a possible move.",
+                    index + 1
+                ));
+            }
+
+            Ok(m)
+        })
+        .collect()
+}
+
+/// Scores only the moves of `game` listed in `moves`, separated by commas,
+/// and writes them in the order they were listed.
+///
+/// Returns the moves with their scores.
+pub fn only_output<T: Game<Player = impl TwoPlayer + 'static> + ParseMove + Eq + Hash + Display>(
+    game: &T,
+    moves: &str,
+    mut output: impl Write,
+) -> Result<Vec<(T::Move, isize)>>
+where
+    T::Move: Display + PartialEq,
+{
+    if game.state() != GameState::Playable {
+        return Err(invalid(anyhow!("The game is already over.")));
+    }
+
+    let candidates = parse_candidates(game, moves).map_err(invalid)?;
+
+    let scores = move_scores_for(game, &mut HashMap::new(), &candidates, None, &None)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(solver_failed)?;
+
+    writeln!(output, "{game}")?;
+    for move_score in &scores {
+        writeln!(output, "{}", describe_move(game, move_score))?;
+    }
+
+    Ok(scores)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tic_tac_toe::TicTacToe, util::notation::NotatedGame};
+    use game_solver::move_scores;
+
+    #[test]
+    fn subsets_agree_with_every_move() {
+        let game = TicTacToe::from_notation("X../.../... O").unwrap();
+        let all = move_scores(&game, &mut HashMap::new(), None, &None)
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
+
+        let mut output = Vec::new();
+        let scores = only_output(&game, "2-2, 1-1,0-1", &mut output).unwrap();
+
+        assert_eq!(
+            scores
+                .iter()
+                .map(|(m, _)| m.to_string())
+                .collect::<Vec<_>>(),
+            ["2-2", "1-1", "0-1"]
+        );
+        for (m, score) in &scores {
+            assert!(all.contains(&(m.clone(), *score)), "{m}: {score}");
+        }
+        let output = String::from_utf8(output).unwrap();
+        assert!(
+            output.contains("\n1-1, score for Right: 0, tie"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn bad_entries_are_named() {
+        let game = TicTacToe::from_notation("X../.../... O").unwrap();
+
+        let err = only_output(&game, "1-1,0-0", Vec::new()).unwrap_err();
+        assert!(err.to_string().starts_with("Move 2 (0-0)"), "{err}");
+
+        let err = only_output(&game, "1-1,nowhere", Vec::new()).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("Could not parse move 2 (nowhere)"),
+            "{err}"
+        );
+    }
+}
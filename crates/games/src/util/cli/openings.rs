@@ -0,0 +1,323 @@
+//! Reports the value of every opening of a game, up to a number of moves,
+//! as the book's tables of first moves and two-move openings show them.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    hash::Hash,
+    io::{self, Write},
+};
+
+use anyhow::{anyhow, Result};
+use clap::{Args, ValueEnum};
+use game_solver::{
+    game::{score_to_outcome, Game, GameState},
+    player::TwoPlayer,
+    solve,
+    transposition::TranspositionTable,
+};
+use serde::Serialize;
+
+use crate::{
+    util::{notation::NotatedGame, symmetry::Symmetries},
+    GamePosition, Games,
+};
+
+use super::{exit::solver_failed, report::analysis::Outcome};
+
+/// How the openings are printed.
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OpeningsFormat {
+    /// A JSON document, with the openings after each position nested in it.
+    #[default]
+    Json,
+    /// A Markdown list, with the openings after each position indented under it.
+    Md,
+}
+
+/// Solves every opening of a game up to a number of moves.
+#[derive(Args, Clone)]
+pub struct OpeningsArgs {
+    /// How many moves each opening is.
+    #[arg(long, default_value_t = 1)]
+    pub depth: usize,
+    /// The format to print the openings in.
+    #[arg(long, value_enum, default_value_t)]
+    pub format: OpeningsFormat,
+    #[command(subcommand)]
+    pub game: Games,
+}
+
+/// How many of the moves of a position win, tie, and lose for the player making them.
+#[derive(Serialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Continuations {
+    pub win: usize,
+    pub tie: usize,
+    pub loss: usize,
+}
+
+impl Continuations {
+    fn count(&mut self, score: isize) {
+        match score.signum() {
+            1 => self.win += 1,
+            0 => self.tie += 1,
+            _ => self.loss += 1,
+        }
+    }
+}
+
+/// An opening that has been solved, about to be printed.
+struct Opening<'a, M> {
+    /// The moves of the opening, or none for the position it starts from.
+    line: &'a [M],
+    /// The score of the last move for the player who made it,
+    /// or of the starting position for the player to move.
+    score: isize,
+    outcome: Outcome,
+    /// The continuations of the opening, or None if it's as long as it can be, or the game is over.
+    continuations: Option<Continuations>,
+}
+
+/// Prints openings as they're solved, so that a deep report doesn't have to be held in memory.
+trait OpeningsWriter<M> {
+    /// Starts an opening, whose continuations are printed after it, until it's finished.
+    fn start(&mut self, opening: &Opening<M>) -> io::Result<()>;
+
+    /// Finishes the opening started last that isn't finished yet.
+    fn finish(&mut self) -> io::Result<()>;
+}
+
+/// Writes a JSON object per opening, e.g.
+/// `{"line":["1-1"],"score":0,"outcome":{"result":"tie"},"continuations":{...},"openings":[...]}`.
+struct JsonOpenings<W> {
+    output: W,
+    /// Whether the opening being started is the first continuation of the one before it.
+    first: bool,
+}
+
+impl<M: Display, W: Write> OpeningsWriter<M> for JsonOpenings<W> {
+    fn start(&mut self, opening: &Opening<M>) -> io::Result<()> {
+        if !self.first {
+            write!(self.output, ",")?;
+        }
+
+        let line = opening.line.iter().map(M::to_string).collect::<Vec<_>>();
+        write!(
+            self.output,
+            "{{\"line\":{},\"score\":{},\"outcome\":{},\"continuations\":{},\"openings\":[",
+            serde_json::to_string(&line)?,
+            opening.score,
+            serde_json::to_string(&opening.outcome)?,
+            serde_json::to_string(&opening.continuations)?,
+        )?;
+
+        self.first = true;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        write!(self.output, "]}}")?;
+
+        self.first = false;
+        Ok(())
+    }
+}
+
+/// Writes a list item per opening, indented by its length, e.g.
+/// `- 1-1: tie (score 0), continuations: 0 win, 8 tie, 0 loss`.
+struct MarkdownOpenings<W> {
+    output: W,
+}
+
+impl<M: Display, W: Write> OpeningsWriter<M> for MarkdownOpenings<W> {
+    fn start(&mut self, opening: &Opening<M>) -> io::Result<()> {
+        let outcome = match opening.outcome {
+            Outcome::Win { moves } => format!("win in {moves}"),
+            Outcome::Loss { moves } => format!("lose in {moves}"),
+            Outcome::Tie => "tie".to_string(),
+        };
+
+        match opening.line.split_last() {
+            Some((last, line)) => write!(
+                self.output,
+                "{}- {last}: {outcome} (score {})",
+                "  ".repeat(line.len()),
+                opening.score
+            )?,
+            None => write!(
+                self.output,
+                "Starting position: {outcome} (score {})",
+                opening.score
+            )?,
+        }
+
+        if let Some(continuations) = opening.continuations {
+            write!(
+                self.output,
+                ", continuations: {} win, {} tie, {} loss",
+                continuations.win, continuations.tie, continuations.loss
+            )?;
+        }
+        writeln!(self.output)?;
+
+        // the list of openings has to be apart from the paragraph before it
+        if opening.line.is_empty() {
+            writeln!(self.output)?;
+        }
+
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The positions after each move of `game` that aren't symmetric to the position after an earlier move,
+/// scored for the player making the move.
+fn continuations<T: Game<Player = impl TwoPlayer + 'static> + Symmetries + Eq + Hash>(
+    game: &T,
+    table: &mut dyn TranspositionTable<T>,
+) -> Result<Vec<(T::Move, T, isize)>>
+where
+    T::MoveError: Display,
+{
+    if game.state() != GameState::Playable {
+        return Ok(Vec::new());
+    }
+
+    let mut seen = HashSet::new();
+    let mut continuations = Vec::new();
+    for m in game.possible_moves() {
+        let mut after = game.clone();
+        after
+            .make_move(&m)
+            .map_err(|err| anyhow!("Failed to move: {err}"))?;
+
+        if !seen.insert(after.canonical()) {
+            continue;
+        }
+
+        let score = -solve(&after, table, None, &None).map_err(solver_failed)?;
+        continuations.push((m, after, score));
+    }
+
+    Ok(continuations)
+}
+
+/// Prints the opening `line`, which left `game` and scored `score`, and every opening after it.
+/// `before` is the position the last move of the line was made in, which its outcome is counted from.
+fn write_opening<T: Game<Player = impl TwoPlayer + 'static> + Symmetries + Eq + Hash>(
+    game: &T,
+    before: &T,
+    line: &mut Vec<T::Move>,
+    score: isize,
+    depth: usize,
+    table: &mut dyn TranspositionTable<T>,
+    writer: &mut dyn OpeningsWriter<T::Move>,
+) -> Result<()>
+where
+    T::MoveError: Display,
+{
+    let continuations = if line.len() < depth {
+        continuations(game, table)?
+    } else {
+        Vec::new()
+    };
+
+    let counts = (!continuations.is_empty()).then(|| {
+        let mut counts = Continuations::default();
+        for (_, _, score) in &continuations {
+            counts.count(*score);
+        }
+        counts
+    });
+
+    writer.start(&Opening {
+        line,
+        score,
+        outcome: Outcome::from(score_to_outcome(before, score)),
+        continuations: counts,
+    })?;
+
+    for (m, after, score) in continuations {
+        line.push(m);
+        write_opening(&after, game, line, score, depth, table, writer)?;
+        line.pop();
+    }
+
+    writer.finish()?;
+    Ok(())
+}
+
+/// Solves every opening of `game` up to `args.depth` moves, printing each as it's solved,
+/// after a heading naming the game and its starting position.
+///
+/// Openings that leave a position symmetric to that of an earlier opening are left out,
+/// and every position is solved with the same transposition table.
+pub fn run_openings<
+    T: Game<Player = impl TwoPlayer + 'static> + Symmetries + NotatedGame + Eq + Hash,
+>(
+    game: T,
+    args: &OpeningsArgs,
+    mut output: impl Write,
+) -> Result<()>
+where
+    T::Move: Display,
+    T::MoveError: Display,
+    GamePosition: From<T>,
+{
+    let mut table = HashMap::new();
+    let score = solve(&game, &mut table, None, &None).map_err(solver_failed)?;
+
+    let name = GamePosition::from(game.clone()).kind().name();
+    match args.format {
+        OpeningsFormat::Json => {
+            let mut writer = JsonOpenings {
+                output: &mut output,
+                first: true,
+            };
+            write!(
+                writer.output,
+                "{{\"game\":{},\"position\":{},\"depth\":{},\"opening\":",
+                serde_json::to_string(name)?,
+                serde_json::to_string(&game.to_notation())?,
+                args.depth
+            )?;
+            write_opening(
+                &game,
+                &game,
+                &mut Vec::new(),
+                score,
+                args.depth,
+                &mut table,
+                &mut writer,
+            )?;
+            writeln!(output, "}}")?;
+        }
+        OpeningsFormat::Md => {
+            writeln!(
+                output,
+                "# Openings of {name}, up to {} move{}\n",
+                args.depth,
+                if args.depth == 1 { "" } else { "s" }
+            )?;
+            writeln!(output, "From `{}`.\n", game.to_notation())?;
+
+            let mut writer = MarkdownOpenings {
+                output: &mut output,
+            };
+            write_opening(
+                &game,
+                &game,
+                &mut Vec::new(),
+                score,
+                args.depth,
+                &mut table,
+                &mut writer,
+            )?;
+        }
+    }
+
+    Ok(output.flush()?)
+}
@@ -0,0 +1,72 @@
+use std::{fmt::Display, hash::Hash, io::Write};
+
+use anyhow::{anyhow, Result};
+use game_solver::{
+    cgt::{impartial_outcome_class, OutcomeClass},
+    game::{Game, Normal},
+    player::ImpartialPlayer,
+    GameSolveError,
+};
+
+use super::exit::{invalid, solver_failed};
+
+/// A game whose positions can be sorted into outcome classes, which `--outcome-class` prints.
+pub trait OutcomeClasses: Game + Sized {
+    /// The outcome class of the position, or None if the game can't find it
+    /// (a partizan game has to know the moves of the player who isn't to move).
+    fn outcome_class(&self) -> Option<Result<OutcomeClass, GameSolveError<Self>>> {
+        None
+    }
+
+    /// A move that theory says wins, without searching, written in the game's notation,
+    /// or None (the default) if the game has no such theory or the position is lost.
+    fn book_move(&self) -> Option<String> {
+        None
+    }
+}
+
+/// The outcome class of an impartial game under normal play, for [`OutcomeClasses::outcome_class`].
+pub fn impartial_class<T: Game<Player = ImpartialPlayer> + Normal + Eq + Hash>(
+    game: &T,
+) -> Option<Result<OutcomeClass, GameSolveError<T>>> {
+    Some(impartial_outcome_class(game))
+}
+
+/// Who wins a position of each class, e.g. "Left wins, whoever moves first".
+fn describe_class(class: OutcomeClass) -> &'static str {
+    match class {
+        OutcomeClass::Left => "Left wins, whoever moves first",
+        OutcomeClass::Right => "Right wins, whoever moves first",
+        OutcomeClass::Next => "whoever moves first wins",
+        OutcomeClass::Previous => "whoever moves second wins",
+    }
+}
+
+/// Writes the board and its outcome class to `output`, e.g. "This position is an L-position".
+pub fn outcome_class_output<T: OutcomeClasses + Display>(
+    game: &T,
+    mut output: impl Write,
+) -> Result<OutcomeClass> {
+    let class = game
+        .outcome_class()
+        .ok_or_else(|| invalid(anyhow!("This game has no outcome classes.")))?
+        .map_err(solver_failed)?;
+
+    let article = if class == OutcomeClass::Previous {
+        "a"
+    } else {
+        "an"
+    };
+
+    writeln!(output, "{game}")?;
+    writeln!(
+        output,
+        "This position is {article} {class}-position: {}.",
+        describe_class(class)
+    )?;
+    if let Some(m) = game.book_move() {
+        writeln!(output, "By the book, {m} wins.")?;
+    }
+
+    Ok(class)
+}
@@ -0,0 +1,44 @@
+use std::{fmt::Display, time::Instant};
+
+use clap::Args;
+use game_solver::{game::Game, perft, perft_divide};
+
+use crate::Games;
+
+/// Counts the move sequences of a given length from a position,
+/// to validate move generation.
+#[derive(Args, Clone)]
+pub struct PerftArgs {
+    /// The amount of moves in each sequence.
+    pub depth: usize,
+    /// Also print the count for each first move.
+    #[arg(long)]
+    pub divide: bool,
+    #[command(subcommand)]
+    pub game: Games,
+}
+
+/// Prints the perft count of `game` (split by first move with `--divide`),
+/// along with how long it took.
+pub fn run_perft<T: Game>(game: T, args: &PerftArgs)
+where
+    T::Move: Display,
+{
+    let start = Instant::now();
+
+    let total = if args.divide {
+        let counts = perft_divide(&game, args.depth);
+
+        for (m, count) in &counts {
+            println!("{m}: {count}");
+        }
+        println!();
+
+        counts.iter().map(|(_, count)| count).sum()
+    } else {
+        perft(&game, args.depth)
+    };
+
+    println!("perft({}) = {total}", args.depth);
+    println!("Took {:.3}s", start.elapsed().as_secs_f64());
+}
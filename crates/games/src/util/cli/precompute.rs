@@ -0,0 +1,451 @@
+//! Solving a fixed set of positions ahead of time, such as the examples the book shows,
+//! so that whatever shows them reads their analyses instead of solving them again.
+//!
+//! The manifest is a JSON list of the positions, each with a name, its game (as the CLI names it),
+//! and the position in the game's [notation](crate::util::notation). Optionally, `depth` limits how many moves
+//! ahead its moves are searched, and `timeout` how many seconds they're searched for:
+//!
+//! ```json
+//! [
+//!   { "name": "nim", "game": "naive-nim", "position": "3,5,7 m0" },
+//!   { "name": "corner", "game": "tic-tac-toe", "position": "X../.O./..X O", "depth": 4 }
+//! ]
+//! ```
+//!
+//! The results are written as JSON, in the order of the manifest: each entry with its analysis
+//! (as `--format json` prints it) or why it has none, how many positions solving it searched,
+//! and the key of its inputs. Entries whose key is the same as that of their results from an earlier run
+//! keep those results, without being solved again:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "entries": [
+//!     { "name": "nim", "game": "naive-nim", "position": "3,5,7 m0", "key": "…",
+//!       "states_explored": 4009, "exact": true, "analysis": { "notation": "3,5,7 m0", … } }
+//!   ]
+//! }
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    fs,
+    hash::Hash,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::atomic::Ordering,
+    time::Duration,
+};
+
+use anyhow::{anyhow, Result};
+use clap::{Args, ValueEnum};
+use game_solver::{
+    game::Game, player::TwoPlayer, solve, solve_depth_limited, stats::Stats, GameSolveError,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{util::notation::NotatedGame, with_game, GameKind, GamePosition};
+
+use super::{
+    create_file,
+    db::SolveDb,
+    exit::{aborted, invalid, solver_failed},
+    interactive::{start_timer, SolverLimits},
+    report::analysis::{MoveFilter, Report},
+    OutputFormat,
+};
+
+/// The version of the format results are written in. Results of another version are solved again.
+pub const VERSION: u32 = 1;
+
+/// Solves the positions of a manifest, skipping those solved by an earlier run.
+#[derive(Args, Clone)]
+pub struct PrecomputeArgs {
+    /// The manifest of the positions to solve.
+    pub manifest: PathBuf,
+    /// Write the results to this file, which the results of an earlier run are read back from.
+    #[arg(long, value_name = "FILE")]
+    pub results: PathBuf,
+    /// Take the scores of the moves from this file when it has them, and add those that had to be solved,
+    /// as `--db` does.
+    #[arg(long, value_name = "PATH")]
+    pub db: Option<PathBuf>,
+}
+
+/// A position of the manifest.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ManifestEntry {
+    /// What the position is called, which has to be unique in the manifest.
+    pub name: String,
+    /// The game, as the CLI names it, e.g. `tic-tac-toe`.
+    pub game: String,
+    /// The position, in the game's notation.
+    pub position: String,
+    /// How many moves ahead the moves are searched. Without it, they're solved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub depth: Option<usize>,
+    /// How many seconds the moves can be searched for, after which the position has no analysis.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<f64>,
+}
+
+impl ManifestEntry {
+    /// The key of the entry's inputs, which is the same on every run and platform:
+    /// a 64-bit [FNV-1a](https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function) hash
+    /// of its game, position, and limits, along with the version of the solver.
+    pub fn key(&self) -> String {
+        let inputs = serde_json::json!([
+            env!("CARGO_PKG_VERSION"),
+            self.game,
+            self.position,
+            self.depth,
+            self.timeout,
+        ]);
+
+        let hash = inputs
+            .to_string()
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+            });
+
+        format!("{hash:016x}")
+    }
+
+    fn limits(&self) -> Result<SolverLimits> {
+        let timeout = self
+            .timeout
+            .map(Duration::try_from_secs_f64)
+            .transpose()
+            .map_err(|err| invalid(anyhow!("{}: invalid timeout: {err}", self.name)))?;
+
+        Ok(SolverLimits {
+            depth: self.depth,
+            timeout,
+        })
+    }
+}
+
+/// The results of every position of a manifest.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PrecomputedResults {
+    /// The version of the format, [`VERSION`] for results written by this one.
+    pub version: u32,
+    pub entries: Vec<PrecomputedEntry>,
+}
+
+/// The results of a position of the manifest.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PrecomputedEntry {
+    #[serde(flatten)]
+    pub entry: ManifestEntry,
+    /// The [key](ManifestEntry::key) of the inputs the results are for.
+    pub key: String,
+    /// How many positions the solver searched for the results, when they were found.
+    /// Depth-limited searches, and scores taken from `--db`, don't count.
+    pub states_explored: u64,
+    /// Whether the scores are exact. Those of depth-limited searches aren't,
+    /// as a tie stands for anything the search couldn't see the end of.
+    pub exact: bool,
+    /// The analysis of the position, as `--format json` prints it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub analysis: Option<Value>,
+    /// Why the position has no analysis, such as running out of time. It's solved again on the next run.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// What a run did with a position of the manifest.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum Status {
+    Solved,
+    Unchanged,
+    Failed,
+}
+
+#[derive(Serialize)]
+struct EntrySummary<'a> {
+    name: &'a str,
+    status: Status,
+    /// How many positions this run searched for it.
+    states_explored: u64,
+}
+
+/// What a run did, as it's printed.
+#[derive(Serialize)]
+struct Summary<'a> {
+    solved: usize,
+    unchanged: usize,
+    failed: usize,
+    states_explored: u64,
+    entries: Vec<EntrySummary<'a>>,
+}
+
+fn read_manifest(path: &Path) -> Result<Vec<ManifestEntry>> {
+    let manifest = fs::read_to_string(path)
+        .map_err(|err| invalid(anyhow!("Could not read {}: {err}", path.display())))?;
+    let entries = serde_json::from_str::<Vec<ManifestEntry>>(&manifest)
+        .map_err(|err| invalid(anyhow!("Could not read {}: {err}", path.display())))?;
+
+    let mut names = HashSet::new();
+    if let Some(entry) = entries.iter().find(|entry| !names.insert(&entry.name)) {
+        return Err(invalid(anyhow!(
+            "{} names more than one position {:?}.",
+            path.display(),
+            entry.name
+        )));
+    }
+
+    Ok(entries)
+}
+
+/// The results written by an earlier run, by name. Results that are missing,
+/// can't be read, or are of another version are solved again.
+fn read_results(path: &Path) -> Result<HashMap<String, PrecomputedEntry>> {
+    let results = match fs::read_to_string(path) {
+        Ok(results) => results,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(HashMap::new()),
+        Err(err) => return Err(anyhow!("Could not read {}: {err}", path.display())),
+    };
+
+    Ok(serde_json::from_str::<PrecomputedResults>(&results)
+        .ok()
+        .filter(|results| results.version == VERSION)
+        .map(|results| {
+            results
+                .entries
+                .into_iter()
+                .map(|result| (result.entry.name.clone(), result))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Solves the position of `entry`, whose game is that of `_game`,
+/// taking the scores of its moves from `db` when it has them, and adding those it had to solve.
+fn precompute_entry<T, P>(
+    _game: T,
+    entry: &ManifestEntry,
+    db: Option<&mut SolveDb>,
+) -> Result<PrecomputedEntry>
+where
+    P: TwoPlayer + Serialize + Sync + 'static,
+    T: Game<Player = P> + NotatedGame + Serialize + Eq + Hash + Sync + Send + 'static,
+    T::Move: Serialize + Sync + Send,
+    T::MoveError: Display + Sync + Send,
+    GamePosition: From<T>,
+{
+    let position = T::from_notation(&entry.position)
+        .map_err(|err| invalid(anyhow!("{}: {err}", entry.name)))?;
+    let limits = entry.limits()?;
+    let mut precomputed = PrecomputedEntry {
+        entry: entry.clone(),
+        key: entry.key(),
+        states_explored: 0,
+        exact: limits.depth.is_none(),
+        analysis: None,
+        error: None,
+    };
+
+    let stats = Stats::new(position.player(), position.move_count());
+    let (cancellation_token, _timer) = start_timer(limits.timeout);
+    let mut table = HashMap::new();
+    let mut db = db;
+    let mut move_scores = Vec::new();
+
+    for m in position.possible_moves() {
+        let mut board = position.clone();
+        board
+            .make_move(&m)
+            .map_err(|err| anyhow!("{}: {err}", entry.name))?;
+
+        let known = db.as_deref().and_then(|db| db.get(&board));
+        let score = match (limits.depth, known) {
+            (Some(depth), _) => {
+                solve_depth_limited(&board, depth.saturating_sub(1), &cancellation_token)
+            }
+            (None, Some(score)) => Ok(score),
+            (None, None) => solve(&board, &mut table, Some(&stats), &cancellation_token),
+        };
+
+        match score {
+            Ok(score) => {
+                if let (None, Some(db)) = (limits.depth, db.as_deref_mut()) {
+                    db.insert(&board, score);
+                }
+                move_scores.push(Ok((m, -score)));
+            }
+            Err(GameSolveError::CancellationTokenError) => {
+                precomputed.states_explored = stats.states_explored.load(Ordering::Relaxed);
+                precomputed.error = Some("The solver ran out of time.".to_string());
+                return Ok(precomputed);
+            }
+            Err(err) => return Err(solver_failed(err).context(entry.name.clone())),
+        }
+    }
+
+    let report = Report::with_scores(&position, move_scores, MoveFilter::All)?;
+    if let (None, Some(db)) = (limits.depth, db) {
+        db.insert(&position, report.evaluation.score);
+    }
+
+    precomputed.states_explored = stats.states_explored.load(Ordering::Relaxed);
+    precomputed.analysis = Some(serde_json::to_value(&report)?);
+
+    Ok(precomputed)
+}
+
+/// Solves the positions of the manifest of `args` that the results of an earlier run don't have,
+/// writes the results of every position, and prints what was done to `output` in `format`.
+///
+/// Fails after writing the results if any position ran out of time.
+pub fn run_precompute(
+    args: &PrecomputeArgs,
+    format: OutputFormat,
+    mut output: impl Write,
+) -> Result<()> {
+    let manifest = read_manifest(&args.manifest)?;
+    let mut previous = read_results(&args.results)?;
+    let mut db = args.db.as_ref().map(SolveDb::open).transpose()?;
+
+    let mut entries = Vec::new();
+    let mut summaries = Vec::new();
+
+    for entry in &manifest {
+        let (result, status, states_explored) = match previous
+            .remove(&entry.name)
+            .filter(|result| result.key == entry.key() && result.error.is_none())
+        {
+            Some(result) => (result, Status::Unchanged, 0),
+            None => {
+                let kind = GameKind::from_str(&entry.game, false)
+                    .map_err(|err| invalid(anyhow!("{}: {err}", entry.name)))?;
+                let result = with_game!(
+                    kind.default_game(),
+                    game => precompute_entry(game, entry, db.as_mut())
+                )?;
+
+                let status = if result.error.is_some() {
+                    Status::Failed
+                } else {
+                    Status::Solved
+                };
+                let states_explored = result.states_explored;
+                (result, status, states_explored)
+            }
+        };
+
+        entries.push(result);
+        summaries.push(EntrySummary {
+            name: &entry.name,
+            status,
+            states_explored,
+        });
+    }
+
+    if let Some(db) = &mut db {
+        db.save()?;
+    }
+
+    let mut results = create_file(&args.results)?;
+    serde_json::to_writer_pretty(
+        &mut results,
+        &PrecomputedResults {
+            version: VERSION,
+            entries,
+        },
+    )?;
+    writeln!(results)?;
+    results
+        .flush()
+        .map_err(|err| anyhow!("Could not write {}: {err}", args.results.display()))?;
+
+    let count = |status| {
+        summaries
+            .iter()
+            .filter(|summary| summary.status == status)
+            .count()
+    };
+    let summary = Summary {
+        solved: count(Status::Solved),
+        unchanged: count(Status::Unchanged),
+        failed: count(Status::Failed),
+        states_explored: summaries
+            .iter()
+            .map(|summary| summary.states_explored)
+            .sum(),
+        entries: summaries,
+    };
+
+    match format {
+        OutputFormat::Json => {
+            serde_json::to_writer(&mut output, &summary)?;
+            writeln!(output)?;
+        }
+        OutputFormat::Text | OutputFormat::Csv => {
+            for entry in &summary.entries {
+                match entry.status {
+                    Status::Solved => writeln!(
+                        output,
+                        "{}: solved, searching {} positions",
+                        entry.name, entry.states_explored
+                    )?,
+                    Status::Unchanged => writeln!(output, "{}: unchanged", entry.name)?,
+                    Status::Failed => writeln!(output, "{}: ran out of time", entry.name)?,
+                }
+            }
+            writeln!(
+                output,
+                "Solved {}, unchanged {}, failed {}.",
+                summary.solved, summary.unchanged, summary.failed
+            )?;
+        }
+    }
+
+    if summary.failed > 0 {
+        return Err(aborted(anyhow!(
+            "{} of {} positions ran out of time.",
+            summary.failed,
+            manifest.len()
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(position: &str) -> ManifestEntry {
+        ManifestEntry {
+            name: "nim".to_string(),
+            game: "naive-nim".to_string(),
+            position: position.to_string(),
+            depth: None,
+            timeout: None,
+        }
+    }
+
+    #[test]
+    fn keys_only_depend_on_the_inputs() {
+        assert_eq!(entry("1,2 m0").key(), entry("1,2 m0").key());
+        assert_eq!(entry("1,2 m0").key().len(), 16);
+        assert_ne!(entry("1,2 m0").key(), entry("1,3 m0").key());
+
+        let renamed = ManifestEntry {
+            name: "other".to_string(),
+            ..entry("1,2 m0")
+        };
+        assert_eq!(renamed.key(), entry("1,2 m0").key());
+
+        let limited = ManifestEntry {
+            depth: Some(2),
+            ..entry("1,2 m0")
+        };
+        assert_ne!(limited.key(), entry("1,2 m0").key());
+    }
+}
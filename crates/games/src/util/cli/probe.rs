@@ -0,0 +1,112 @@
+use std::{collections::HashMap, fmt::Display, hash::Hash, io::Write};
+
+use anyhow::Result;
+use game_solver::{
+    game::Game,
+    move_scores,
+    player::TwoPlayer,
+    solve,
+    transposition::{Entry, Score, TranspositionTable},
+};
+
+use crate::util::parse::ParseMove;
+
+use super::{
+    exit::solver_failed,
+    interactive::{describe_score, SolverLimits},
+    replay_parsed_moves,
+    report::scores::score_label,
+};
+
+/// Solves `game`, then writes what the transposition table holds about the position
+/// after `moves`, a space-separated list of moves played from `game`.
+///
+/// Returns the entry of that position, if the table has one.
+pub fn probe_output<T: Game<Player = impl TwoPlayer + 'static> + ParseMove + Eq + Hash + Display>(
+    game: &T,
+    moves: &str,
+    mut output: impl Write,
+) -> Result<Option<Entry>>
+where
+    T::MoveError: Display,
+{
+    let moves = moves
+        .split_whitespace()
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    let mut position = game.clone();
+    replay_parsed_moves(&mut position, &moves)?;
+
+    let mut table = HashMap::new();
+    // scoring every move leaves their exact scores in the table, along with that of `game`
+    for result in move_scores(game, &mut table, None, &None) {
+        result.map_err(solver_failed)?;
+    }
+    solve(game, &mut table, None, &None).map_err(solver_failed)?;
+
+    let entry = table.entry(&position);
+
+    let label = score_label(&position);
+
+    writeln!(output, "{position}")?;
+    match entry.map(|entry| entry.score) {
+        Some(Score::Exact(score)) => writeln!(
+            output,
+            "The table holds the exact {label}: {score}, so the player to move {}.",
+            describe_score(&position, Some(score), SolverLimits::default())
+        )?,
+        Some(Score::LowerBound(score)) => writeln!(
+            output,
+            "The table holds a lower bound of {score} on the {label}."
+        )?,
+        Some(Score::UpperBound(score)) => writeln!(
+            output,
+            "The table holds an upper bound of {score} on the {label}."
+        )?,
+        None => writeln!(
+            output,
+            "The table holds nothing about this position: the solver didn't need to store it."
+        )?,
+    }
+
+    Ok(entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tic_tac_toe::TicTacToe;
+
+    #[test]
+    fn the_best_opening_is_solved_exactly() {
+        let game = TicTacToe::new(2, 3);
+        let (best, _) = move_scores(&game, &mut HashMap::new(), None, &None)
+            .map(Result::unwrap)
+            .max_by_key(|(_, score)| *score)
+            .unwrap();
+
+        let mut output = Vec::new();
+        let entry = probe_output(&game, &best.to_string(), &mut output).unwrap();
+
+        let mut position = game.clone();
+        position.make_move(&best).unwrap();
+        let score = solve(&position, &mut HashMap::new(), None, &None).unwrap();
+
+        assert_eq!(entry, Some(Entry::from(Score::Exact(score))));
+        let output = String::from_utf8(output).unwrap();
+        assert!(
+            output.contains(&format!(
+                "The table holds the exact score for {}: {score}, ",
+                position.player()
+            )),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn bad_moves_are_reported() {
+        let game = TicTacToe::new(2, 3);
+
+        assert!(probe_output(&game, "1-1 1-1", Vec::new()).is_err());
+    }
+}
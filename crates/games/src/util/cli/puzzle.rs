@@ -0,0 +1,97 @@
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+};
+
+use anyhow::Result;
+use clap::Args;
+use game_solver::{
+    game::Game,
+    player::TwoPlayer,
+    puzzle::{generate_puzzles, Difficulty, PuzzleFilter},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::GameKind;
+
+use super::{exit::solver_failed, report::scores::summarize};
+
+/// Generates positions where only one move wins.
+#[derive(Args, Debug, Clone)]
+pub struct PuzzleArgs {
+    /// The game to generate puzzles for, starting from its default position.
+    #[arg(long, value_enum)]
+    pub game: GameKind,
+    /// How many puzzles to generate.
+    #[arg(long, default_value_t = 5)]
+    pub count: usize,
+    /// The seed for the random positions. The same seed always generates the same puzzles.
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+    /// Instead of only keeping positions with a single winning move,
+    /// keep positions where the best move scores at least this much more than any other move.
+    #[arg(long, value_name = "SCORE", value_parser = clap::value_parser!(u64).range(1..))]
+    pub swing: Option<u64>,
+    /// Only keep positions whose difficulty rating is at least this: the share of the winner's moves
+    /// that throw the win away, added up over their turns on the winning line, plus the traps along it.
+    #[arg(long, value_name = "RATING")]
+    pub min_difficulty: Option<f64>,
+}
+
+/// Generates puzzles from `game`, printing each position along with its solution and score.
+pub fn run_puzzles<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash + Display>(
+    game: T,
+    args: &PuzzleArgs,
+) -> Result<()>
+where
+    T::Move: Display,
+    T::MoveError: Display,
+{
+    let filter = match args.swing {
+        Some(swing) => PuzzleFilter::Swing(swing as isize),
+        None => PuzzleFilter::UniqueWin,
+    };
+
+    let mut rng = StdRng::seed_from_u64(args.seed);
+    let puzzles = generate_puzzles(&game, &mut rng, args.count, filter, args.min_difficulty)
+        .map_err(solver_failed)?;
+
+    for (i, puzzle) in puzzles.iter().enumerate() {
+        println!("Puzzle {}:", i + 1);
+        println!("{}", puzzle.position);
+        println!(
+            "{}",
+            summarize(&puzzle.position, &(puzzle.solution.clone(), puzzle.score))
+        );
+        if let Some(difficulty) = puzzle.difficulty {
+            println!("{}", describe_difficulty(&difficulty));
+        }
+        println!();
+    }
+
+    if puzzles.len() < args.count {
+        eprintln!(
+            "Only found {} of {} puzzles for {}.",
+            puzzles.len(),
+            args.count,
+            args.game.name()
+        );
+    }
+
+    Ok(())
+}
+
+fn describe_difficulty(difficulty: &Difficulty) -> String {
+    let plural = |count: usize, noun: &str| match count {
+        1 => format!("1 {noun}"),
+        count => format!("{count} {noun}s"),
+    };
+
+    format!(
+        "difficulty: {:.2} (wins in {}, {:.0}% of moves keep the win, {})",
+        difficulty.rating(),
+        plural(difficulty.depth_to_win, "move"),
+        difficulty.forcing_ratio * 100.0,
+        plural(difficulty.traps, "trap")
+    )
+}
@@ -0,0 +1,164 @@
+use std::{collections::HashMap, fmt::Debug, hash::Hash};
+
+use anyhow::Result;
+use game_solver::{
+    game::{score_to_outcome, Game, GameScoreOutcome},
+    player::TwoPlayer,
+    solve, CollectedMoves,
+};
+use serde::Serialize;
+
+use crate::util::{
+    cli::{all_move_scores, exit::solver_failed},
+    move_score::normalize_move_scores,
+    notation::NotatedGame,
+};
+
+/// The outcome a score guarantees for the player to move,
+/// serialized as `{"result": "win", "moves": 3}`.
+#[derive(Serialize, Debug, PartialEq, Eq)]
+#[serde(tag = "result", rename_all = "lowercase")]
+pub enum Outcome {
+    Win { moves: usize },
+    Loss { moves: usize },
+    Tie,
+}
+
+impl From<GameScoreOutcome> for Outcome {
+    fn from(outcome: GameScoreOutcome) -> Self {
+        match outcome {
+            GameScoreOutcome::Win(moves) => Self::Win { moves },
+            GameScoreOutcome::Loss(moves) => Self::Loss { moves },
+            GameScoreOutcome::Tie => Self::Tie,
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct MoveReport<M> {
+    #[serde(rename = "move")]
+    pub game_move: M,
+    pub score: isize,
+    pub outcome: Outcome,
+}
+
+/// Which of the scored moves of a position are shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MoveFilter {
+    #[default]
+    All,
+    /// Only the best-scoring moves, including every move tied for best.
+    Best,
+    /// Only the first `n` moves, from best to worst.
+    Top(usize),
+}
+
+impl MoveFilter {
+    /// Filters a list of moves, sorted from best to worst.
+    pub fn apply<M>(self, mut move_scores: Vec<(M, isize)>) -> Vec<(M, isize)> {
+        match self {
+            Self::All => (),
+            Self::Best => {
+                let best_score = move_scores.first().map(|(_, score)| *score);
+                move_scores.retain(|(_, score)| Some(*score) == best_score);
+            }
+            Self::Top(n) => move_scores.truncate(n),
+        }
+
+        move_scores
+    }
+}
+
+#[derive(Serialize)]
+pub struct Evaluation {
+    pub score: isize,
+    pub outcome: Outcome,
+}
+
+/// A machine-readable analysis of a position.
+#[derive(Serialize)]
+#[serde(bound(serialize = "T: Serialize, T::Move: Serialize, T::Player: Serialize"))]
+pub struct Report<'a, T: Game> {
+    pub position: &'a T,
+    /// The position in its game's notation.
+    pub notation: String,
+    pub player: T::Player,
+    /// Every possible move (that passed the filter), from best to worst.
+    pub moves: Vec<MoveReport<T::Move>>,
+    pub evaluation: Evaluation,
+}
+
+impl<
+        'a,
+        T: Game<Player = impl TwoPlayer + Sync + 'static>
+            + NotatedGame
+            + Eq
+            + Hash
+            + Sync
+            + Send
+            + 'static,
+    > Report<'a, T>
+where
+    T::Move: Sync + Send,
+    T::MoveError: Sync + Send,
+{
+    /// Solves every move of `game`, keeping the moves that pass `filter`.
+    pub fn new(game: &'a T, filter: MoveFilter) -> Result<Self> {
+        Self::with_scores(game, all_move_scores(game, None, &None), filter)
+    }
+
+    /// Reports the already-solved moves of `game`, keeping the moves that pass `filter`.
+    pub fn with_scores(
+        game: &'a T,
+        move_scores: CollectedMoves<T>,
+        filter: MoveFilter,
+    ) -> Result<Self> {
+        let move_scores = normalize_move_scores::<T>(move_scores).map_err(solver_failed)?;
+
+        let score = match move_scores.first() {
+            Some((_, score)) => *score,
+            // terminal positions have no moves, but still have a score.
+            None => solve(game, &mut HashMap::new(), None, &None).map_err(solver_failed)?,
+        };
+
+        Ok(Self {
+            position: game,
+            notation: game.to_notation(),
+            player: game.player(),
+            moves: filter
+                .apply(move_scores)
+                .into_iter()
+                .map(|(game_move, score)| MoveReport {
+                    game_move,
+                    score,
+                    outcome: score_to_outcome(game, score).into(),
+                })
+                .collect(),
+            evaluation: Evaluation {
+                score,
+                outcome: score_to_outcome(game, score).into(),
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn moves() -> Vec<(char, isize)> {
+        vec![('a', 3), ('b', 3), ('c', 0), ('d', -2)]
+    }
+
+    #[test]
+    fn best_keeps_ties() {
+        assert_eq!(MoveFilter::Best.apply(moves()), vec![('a', 3), ('b', 3)]);
+    }
+
+    #[test]
+    fn top_truncates() {
+        assert_eq!(MoveFilter::Top(3).apply(moves()), moves()[..3]);
+        assert_eq!(MoveFilter::Top(10).apply(moves()), moves());
+        assert_eq!(MoveFilter::All.apply(moves()), moves());
+    }
+}
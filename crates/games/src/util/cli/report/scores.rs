@@ -1,39 +1,266 @@
-use std::fmt::{Debug, Display};
+use std::{
+    fmt::{Debug, Display},
+    io::{self, Write},
+};
 
 use game_solver::{
     game::{score_to_outcome, Game, GameScoreOutcome},
+    player::TwoPlayer,
     CollectedMoves,
 };
 
-use crate::util::move_score::normalize_move_scores;
+use crate::util::{cli::exit::solver_failed, move_score::normalize_move_scores};
+
+use super::analysis::MoveFilter;
+
+/// Names whose the scores of `game` are, e.g. `score for Left`: those of the player to move.
+pub fn score_label<T: Game>(game: &T) -> String
+where
+    T::Player: TwoPlayer,
+{
+    format!("score for {}", game.player())
+}
 
-pub fn show_scores<T: Game + Debug>(game: &T, move_scores: CollectedMoves<T>)
+/// Summarizes the best move in one line, e.g. `best: d3, score for Left: +6, win in 11`.
+pub fn summarize<T: Game>(game: &T, best: &(T::Move, isize)) -> String
 where
     T::Move: Display,
+    T::Player: TwoPlayer,
 {
-    let move_scores = normalize_move_scores::<T>(move_scores).unwrap();
-
-    let mut current_move_score = None;
-    for (game_move, score) in move_scores {
-        if current_move_score != Some(score) {
-            match score_to_outcome(game, score) {
-                GameScoreOutcome::Win(moves) => println!(
-                    "\n\nWin in {} move{} (score {}):",
-                    moves,
-                    if moves == 1 { "" } else { "s" },
-                    score
-                ),
-                GameScoreOutcome::Loss(moves) => println!(
-                    "\n\nLose in {} move{} (score {}):",
-                    moves,
-                    if moves == 1 { "" } else { "s" },
-                    score
-                ),
-                GameScoreOutcome::Tie => println!("\n\nTie with the following moves:"),
-            }
-            current_move_score = Some(score);
+    format!("best: {}", describe_move(game, best))
+}
+
+/// Describes a scored move, e.g. `d3, score for Left: +6, win in 11`.
+pub fn describe_move<T: Game>(game: &T, move_score: &(T::Move, isize)) -> String
+where
+    T::Move: Display,
+    T::Player: TwoPlayer,
+{
+    let (game_move, score) = move_score;
+
+    format!(
+        "{game_move}, {}: {}, {}",
+        score_label(game),
+        signed(*score),
+        describe_outcome(game, *score)
+    )
+}
+
+/// A score with its sign, e.g. `+6`, but for ties, which are scored 0 and have no sign to show.
+fn signed(score: isize) -> String {
+    if score == 0 {
+        score.to_string()
+    } else {
+        format!("{score:+}")
+    }
+}
+
+/// What a move's score says about it, next to those of the other moves of its position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Annotation {
+    /// `!`: the only move that doesn't lose.
+    OnlyMove,
+    /// `?`: a move that throws away a win, as a better one wins.
+    Blunder,
+}
+
+impl Annotation {
+    pub fn symbol(self) -> char {
+        match self {
+            Self::OnlyMove => '!',
+            Self::Blunder => '?',
         }
-        print!("{}, ", &game_move);
     }
-    println!();
+}
+
+/// Annotates each of the scored moves of a position, given every one of them.
+pub fn annotate<M>(move_scores: &[(M, isize)]) -> Vec<Option<Annotation>> {
+    let best = move_scores.iter().map(|(_, score)| *score).max();
+    let not_losing = move_scores.iter().filter(|(_, score)| *score >= 0).count();
+
+    move_scores
+        .iter()
+        .map(|(_, score)| {
+            if not_losing == 1 && *score >= 0 {
+                Some(Annotation::OnlyMove)
+            } else if best.is_some_and(|best| best > 0) && *score <= 0 {
+                Some(Annotation::Blunder)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Describes how a score ends the game for the player making the move, e.g. `win in 3`.
+fn describe_outcome<T: Game>(game: &T, score: isize) -> String {
+    match score_to_outcome(game, score) {
+        GameScoreOutcome::Win(moves) => format!("win in {moves}"),
+        GameScoreOutcome::Loss(moves) => format!("lose in {moves}"),
+        GameScoreOutcome::Tie => "tie".to_string(),
+    }
+}
+
+/// Writes the best move of `game`, then a line for each move that `filter` keeps,
+/// with its annotation, outcome, and score, e.g. `1-1 !  win in 3  +5`.
+/// `move_scores` are sorted from best to worst, and annotated next to every one of them.
+pub fn write_scores<T: Game>(
+    game: &T,
+    move_scores: Vec<(T::Move, isize)>,
+    filter: MoveFilter,
+    mut output: impl Write,
+) -> io::Result<()>
+where
+    T::Move: Display,
+    T::Player: TwoPlayer,
+{
+    let Some(best) = move_scores.first() else {
+        return Ok(());
+    };
+    writeln!(output, "{}", summarize(game, best))?;
+
+    let annotations = annotate(&move_scores);
+    let annotated = move_scores
+        .into_iter()
+        .zip(annotations)
+        .map(|((game_move, score), annotation)| ((game_move, annotation), score))
+        .collect();
+    let rows = filter
+        .apply(annotated)
+        .iter()
+        .map(|((game_move, annotation), score)| {
+            (
+                game_move.to_string(),
+                annotation.map_or(' ', Annotation::symbol),
+                describe_outcome(game, *score),
+                signed(*score),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let width = |column: fn(&(String, char, String, String)) -> usize| {
+        rows.iter().map(column).max().unwrap_or(0)
+    };
+    let move_width = width(|row| row.0.len());
+    let outcome_width = width(|row| row.2.len());
+    let score_width = width(|row| row.3.len());
+
+    writeln!(output)?;
+    writeln!(output, "Moves ({}):", score_label(game))?;
+    for (game_move, symbol, outcome, score) in &rows {
+        writeln!(
+            output,
+            "{game_move:<move_width$} {symbol}  {outcome:<outcome_width$}  {score:>score_width$}"
+        )?;
+    }
+
+    let legend = [Annotation::OnlyMove, Annotation::Blunder]
+        .into_iter()
+        .filter(|annotation| rows.iter().any(|row| row.1 == annotation.symbol()))
+        .map(|annotation| match annotation {
+            Annotation::OnlyMove => "! the only move that doesn't lose",
+            Annotation::Blunder => "? throws away the win",
+        })
+        .collect::<Vec<_>>();
+    if !legend.is_empty() {
+        writeln!(output)?;
+        writeln!(output, "{}", legend.join(", "))?;
+    }
+
+    Ok(())
+}
+
+/// Writes the best move of `game` and the scores of its moves to stdout, as [`write_scores`] does.
+///
+/// Fails if the solver failed to score any of the moves, such as when it was stopped.
+pub fn show_scores<T: Game + Debug>(
+    game: &T,
+    move_scores: CollectedMoves<T>,
+    filter: MoveFilter,
+) -> anyhow::Result<()>
+where
+    T::Move: Display,
+    T::Player: TwoPlayer,
+{
+    let move_scores = normalize_move_scores::<T>(move_scores).map_err(solver_failed)?;
+    write_scores(game, move_scores, filter, io::stdout().lock())?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use game_solver::move_scores;
+
+    use super::*;
+    use crate::{naive_nim::Nim, tic_tac_toe::TicTacToe, util::notation::NotatedGame};
+
+    fn scores_text<T: Game<Player = impl TwoPlayer + 'static> + Eq + std::hash::Hash + Debug>(
+        game: &T,
+        filter: MoveFilter,
+    ) -> String
+    where
+        T::Move: Display,
+    {
+        let scores = move_scores(game, &mut HashMap::new(), None, &None).collect();
+        let mut output = Vec::new();
+        write_scores(
+            game,
+            normalize_move_scores(scores).unwrap(),
+            filter,
+            &mut output,
+        )
+        .unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn a_fork_is_the_only_move() {
+        // O threatens the other diagonal, and blocking it makes two rows for X
+        let game = TicTacToe::from_notation("X.O/.O./..X X").unwrap();
+
+        assert_eq!(
+            scores_text(&game, MoveFilter::All),
+            "best: 2-0, score for Left: +3, win in 2\n\
+             \n\
+             Moves (score for Left):\n\
+             2-0 !  win in 2   +3\n\
+             2-1 ?  lose in 1  -4\n\
+             1-2 ?  lose in 1  -4\n\
+             1-0 ?  lose in 1  -4\n\
+             0-1 ?  lose in 1  -4\n\
+             \n\
+             ! the only move that doesn't lose, ? throws away the win\n"
+        );
+    }
+
+    #[test]
+    fn only_one_reply_evens_out_the_nim_sum() {
+        let game = Nim::new(vec![1, 2, 4]);
+
+        assert_eq!(
+            scores_text(&game, MoveFilter::Top(3)),
+            "best: 2-1, score for Next: +1, win in 6\n\
+             \n\
+             Moves (score for Next):\n\
+             2-1 !  win in 6   +1\n\
+             0-1 ?  lose in 5  -2\n\
+             2-2 ?  lose in 5  -2\n\
+             \n\
+             ! the only move that doesn't lose, ? throws away the win\n"
+        );
+    }
+
+    #[test]
+    fn ties_are_not_annotated_without_a_win() {
+        let game = TicTacToe::from_notation(".../.X./... O").unwrap();
+        let scores = move_scores(&game, &mut HashMap::new(), None, &None)
+            .map(Result::unwrap)
+            .collect::<Vec<_>>();
+
+        // the corners tie, and the edges lose, so no move is alone in not losing, and nothing wins
+        assert!(annotate(&scores).iter().all(Option::is_none));
+    }
 }
@@ -1,6 +1,6 @@
 use std::sync::atomic::Ordering;
 
-use game_solver::{game::Game, stats::Stats};
+use game_solver::{game::Game, player::Player, stats::Stats};
 
 pub fn show_stats<T: Game>(
     stats: &Stats<T::Player>,
@@ -39,3 +39,27 @@ pub fn show_stats<T: Game>(
     );
     println!();
 }
+
+/// Prints what the search did at each ply, for stats made with [`Stats::per_ply`].
+pub fn show_ply_stats<P: Player>(stats: &Stats<P>) {
+    println!("Per ply:");
+    println!();
+    println!(
+        "{:>4} {:>12} {:>10} {:>10} {:>10} {:>10} {:>9}",
+        "Ply", "Nodes", "Cutoffs", "1st move", "2nd move", "Later", "Branching"
+    );
+    for ply in stats.plies() {
+        println!(
+            "{:>4} {:>12} {:>10} {:>10} {:>10} {:>10} {:>9}",
+            ply.ply,
+            ply.nodes,
+            ply.cutoffs,
+            ply.cutoff_moves[0],
+            ply.cutoff_moves[1],
+            ply.cutoff_moves[2],
+            ply.branching_factor
+                .map_or_else(|| "-".to_string(), |factor| format!("{factor:.2}"))
+        );
+    }
+    println!();
+}
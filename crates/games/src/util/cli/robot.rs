@@ -1,7 +1,8 @@
+use anyhow::Result;
 use game_solver::{
     game::Game,
-    par_move_scores,
     player::{ImpartialPlayer, TwoPlayer},
+    stats::Stats,
 };
 use std::{
     any::TypeId,
@@ -9,34 +10,58 @@ use std::{
     hash::Hash,
 };
 
-use crate::util::cli::report::scores::show_scores;
+use crate::util::{
+    cli::{
+        all_move_scores,
+        report::{analysis::MoveFilter, scores::show_scores, stats::show_ply_stats},
+    },
+    notation::NotatedGame,
+};
 
 pub fn robotic_output<
-    T: Game<Player = impl TwoPlayer + Debug + Sync + 'static>
+    T: Game<Player = impl TwoPlayer + Sync + 'static>
         + Eq
         + Hash
         + Sync
         + Send
         + Display
         + Debug
+        + NotatedGame
         + 'static,
 >(
     game: T,
-) where
+    filter: MoveFilter,
+    stats_verbose: bool,
+) -> Result<()>
+where
     T::Move: Sync + Send + Display,
     T::MoveError: Sync + Send + Debug,
 {
+    print_position(&game);
+
+    let stats = stats_verbose.then(|| Stats::per_ply(&game));
+    let move_scores = all_move_scores(&game, stats.as_ref(), &None);
+
+    show_scores(&game, move_scores, filter)?;
+    if let Some(stats) = &stats {
+        show_ply_stats(stats);
+    }
+
+    Ok(())
+}
+
+/// Prints the board, its notation, and who is to move, as [`robotic_output`] does before solving.
+pub fn print_position<T: Game<Player = impl TwoPlayer + 'static> + Display + NotatedGame>(
+    game: &T,
+) {
     print!("{}", game);
     println!();
+    println!("Position: {}", game.to_notation());
 
     if TypeId::of::<T::Player>() != TypeId::of::<ImpartialPlayer>() {
-        println!("Player {:?} to move", game.player());
+        println!("Player {} to move", game.player());
     } else {
         // TODO: can we assert that game.player() is the next player?
         println!("Impartial game; Next player is moving.");
     }
-
-    let move_scores = par_move_scores(&game, None, &None);
-
-    show_scores(&game, move_scores);
 }
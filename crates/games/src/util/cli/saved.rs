@@ -0,0 +1,235 @@
+//! Saved games against the solver, for stopping a game of `--play` and picking it up later.
+//!
+//! A saved session is a JSON object with the version of its format, the [record](crate::util::record) of the game,
+//! the options the solver plays with, and optionally the solved positions of its transposition table,
+//! each as the notation of the position and its score:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "record": "naive-nim 3,5 m0\n1-2\n",
+//!   "options": { "limits": { "depth": null, "timeout": null }, "seed": null, "ponder": false },
+//!   "table": [["3,3 m1", { "Exact": -5 }]]
+//! }
+//! ```
+//!
+//! Files of another version of the format are rejected before anything else in them is read.
+
+use std::{collections::HashMap, fs, hash::Hash, path::Path};
+
+use anyhow::{anyhow, Result};
+use game_solver::{player::TwoPlayer, session::Session, transposition::Score};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    util::{
+        notation::NotatedGame,
+        record::{save_record, MoveNotation},
+    },
+    GamePosition,
+};
+
+use super::{
+    interactive::{PlayOptions, PlaySession},
+    replay_record,
+};
+
+/// The version of the format sessions are saved in, which is the only one they're read back from.
+pub const VERSION: u32 = 1;
+
+/// A game against the solver, as it's saved.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedSession {
+    /// The version of the format, [`VERSION`] for every session saved by this one.
+    pub version: u32,
+    /// The record of the game: its name, the position it started from, and the moves played from there.
+    pub record: String,
+    /// How the solver plays.
+    pub options: PlayOptions,
+    /// The positions of the transposition table, in their notation, with their scores, if it was saved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub table: Option<Vec<(String, Score)>>,
+}
+
+impl SavedSession {
+    /// The saved form of `session`, played with `options`, with its transposition table if `table` is set.
+    pub fn new<T>(session: &PlaySession<T>, options: PlayOptions, table: bool) -> Self
+    where
+        T: MoveNotation + NotatedGame + Eq + Hash,
+        T::Player: TwoPlayer + 'static,
+        GamePosition: From<T>,
+    {
+        Self {
+            version: VERSION,
+            record: save_record(session.history()),
+            options,
+            table: table.then(|| {
+                let mut positions = session
+                    .table
+                    .iter()
+                    .map(|(position, score)| (position.to_notation(), *score))
+                    .collect::<Vec<_>>();
+                // the table iterates in no particular order, so the same session is always saved the same way
+                positions.sort_by(|(a, _), (b, _)| a.cmp(b));
+                positions
+            }),
+        }
+    }
+
+    /// Reads a saved session, checking its version before the rest of it.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let value = serde_json::from_str::<serde_json::Value>(json)
+            .map_err(|err| anyhow!("it isn't a saved game: {err}"))?;
+
+        let version = value
+            .get("version")
+            .and_then(serde_json::Value::as_u64)
+            .ok_or_else(|| anyhow!("it isn't a saved game: it has no version"))?;
+        if version != u64::from(VERSION) {
+            return Err(anyhow!(
+                "it was saved in version {version} of the format, but only version {VERSION} can be read"
+            ));
+        }
+
+        serde_json::from_value(value).map_err(|err| anyhow!("it isn't a saved game: {err}"))
+    }
+
+    /// Reads the session saved at `path`.
+    pub fn read(path: &Path) -> Result<Self> {
+        let json = fs::read_to_string(path)
+            .map_err(|err| anyhow!("Could not read {}: {err}", path.display()))?;
+
+        Self::from_json(&json).map_err(|err| anyhow!("Could not load {}: {err}", path.display()))
+    }
+
+    /// Writes the session to `path`, replacing what was there.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string(self).expect("a saved session can always be written");
+
+        fs::write(path, json).map_err(|err| anyhow!("Could not write {}: {err}", path.display()))
+    }
+
+    /// Plays the saved game back, which has to be of the same game as `game`,
+    /// returning it as a session with the saved table (or an empty one), along with the options it was played with.
+    pub fn restore<T>(&self, game: &T) -> Result<(PlaySession<T>, PlayOptions)>
+    where
+        T: MoveNotation + NotatedGame + Eq + Hash,
+        T::Move: PartialEq,
+        T::Player: TwoPlayer + 'static,
+        GamePosition: From<T>,
+    {
+        let history =
+            replay_record(game, &self.record).map_err(|err| anyhow!("Could not load it: {err}"))?;
+
+        let table = self
+            .table
+            .iter()
+            .flatten()
+            .map(|(notation, score)| {
+                T::from_notation(notation)
+                    .map(|position| (position, *score))
+                    .map_err(|err| {
+                        anyhow!("Could not load the solved position `{notation}`: {err}")
+                    })
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        Ok((Session::from_history(history, table), self.options))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use game_solver::game::Game;
+
+    use super::*;
+    use crate::{
+        naive_nim::Nim,
+        tic_tac_toe::TicTacToe,
+        util::{cli::interactive::SolverLimits, parse::ParseMove},
+    };
+
+    fn session() -> Session<Nim, HashMap<Nim, Score>> {
+        let mut session = Session::new(Nim::new(vec![3, 5]), HashMap::new());
+        let m = session.game().parse_move("1-2").unwrap();
+        session.apply(&m).unwrap();
+        session.evaluate().unwrap();
+        session
+    }
+
+    fn options() -> PlayOptions {
+        PlayOptions {
+            limits: SolverLimits {
+                depth: Some(4),
+                timeout: None,
+            },
+            seed: Some(7),
+            ponder: false,
+        }
+    }
+
+    #[test]
+    fn sessions_are_restored_exactly() {
+        let session = session();
+        let saved = SavedSession::new(&session, options(), true);
+        let json = serde_json::to_string(&saved).unwrap();
+
+        let (restored, restored_options) = SavedSession::from_json(&json)
+            .unwrap()
+            .restore(&Nim::new(vec![1]))
+            .unwrap();
+
+        assert_eq!(restored.game(), session.game());
+        assert_eq!(restored.game().player(), session.game().player());
+        assert_eq!(restored.history().moves(), session.history().moves());
+        assert_eq!(restored.table, session.table);
+        assert!(!restored.table.is_empty());
+        assert_eq!(restored_options, options());
+    }
+
+    #[test]
+    fn the_table_is_only_saved_when_asked_for() {
+        let saved = SavedSession::new(&session(), options(), false);
+        let json = serde_json::to_string(&saved).unwrap();
+        assert!(!json.contains("table"));
+
+        let (restored, _) = SavedSession::from_json(&json)
+            .unwrap()
+            .restore(&Nim::new(vec![1]))
+            .unwrap();
+        assert!(restored.table.is_empty());
+    }
+
+    #[test]
+    fn other_versions_are_rejected() {
+        let mut saved = SavedSession::new(&session(), options(), false);
+        saved.version = VERSION + 1;
+        let json = serde_json::to_string(&saved).unwrap();
+
+        let err = SavedSession::from_json(&json).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "it was saved in version {} of the format, but only version {VERSION} can be read",
+                VERSION + 1
+            )
+        );
+
+        assert!(SavedSession::from_json(r#"{"record": ""}"#)
+            .unwrap_err()
+            .to_string()
+            .contains("it has no version"));
+    }
+
+    #[test]
+    fn other_games_are_rejected() {
+        let saved = SavedSession::new(&session(), options(), false);
+
+        let err = saved.restore(&TicTacToe::new(2, 3)).err().unwrap();
+        assert!(
+            err.to_string()
+                .contains("the record is of Nim (Naive), not Tic Tac Toe"),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use std::{collections::HashMap, fmt::Display, hash::Hash, io::Write};
+
+use anyhow::{anyhow, Result};
+use game_solver::{
+    game::Game,
+    history::History,
+    player::TwoPlayer,
+    solve,
+    tie_break::{TieBreak, TieBreaker},
+};
+
+use crate::util::render::{Render, Style};
+
+use super::{
+    ending,
+    exit::solver_failed,
+    interactive::{describe_score, SolverLimits},
+    move_failable, Endings,
+};
+
+/// Lets the solver play both sides from the current position of `history` until the game ends,
+/// writing every move, its evaluation, and the board after it, drawn in `style`, to `output`,
+/// and adding every move to `history`.
+///
+/// One transposition table is shared across the whole game,
+/// and ties between equally scored moves are broken with `tie_break`.
+///
+/// Returns true if the player to move at the start won, false if the other player won,
+/// and None on a tie.
+pub fn self_play<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash + Endings + Render>(
+    history: &mut History<T>,
+    tie_break: TieBreak<T::Move>,
+    style: Style,
+    mut output: impl Write,
+) -> Result<Option<bool>>
+where
+    T::Move: Display,
+    T::MoveError: Display,
+{
+    let mut transposition_table = HashMap::new();
+    let mut tie_break = TieBreaker::new(tie_break);
+    let mut move_count = 0;
+
+    writeln!(
+        output,
+        "{}",
+        history.game().render(style, history.moves().last())
+    )?;
+
+    loop {
+        let game = history.game().clone();
+
+        // the winner is either the player to move, or the player that just moved.
+        let first_player_won = |winner| (winner == game.player()) == (move_count % 2 == 0);
+        let name = |winner| {
+            if first_player_won(winner) {
+                "The first player".to_string()
+            } else {
+                "The second player".to_string()
+            }
+        };
+
+        if let Some(ending) = ending(&game, name) {
+            writeln!(output, "{ending}")?;
+            return Ok(game.winner().map(first_player_won));
+        }
+
+        let mut scores = Vec::new();
+
+        for m in game.possible_moves() {
+            let mut board = game.clone();
+            move_failable(&mut board, &m)?;
+
+            let score =
+                -solve(&board, &mut transposition_table, None, &None).map_err(solver_failed)?;
+
+            scores.push((m, score));
+        }
+
+        let (m, best_score) = tie_break
+            .best(scores)
+            .ok_or_else(|| anyhow!("No moves left in a playable position."))?;
+
+        move_count += 1;
+        writeln!(
+            output,
+            "{move_count}. {m} (the mover {}).",
+            describe_score(&game, Some(best_score), SolverLimits::default())
+        )?;
+
+        history
+            .push(m)
+            .map_err(|err| anyhow!("Failed to move: {err}"))?;
+        writeln!(
+            output,
+            "{}",
+            history.game().render(style, history.moves().last())
+        )?;
+    }
+}
@@ -0,0 +1,266 @@
+//! A line-based protocol for driving the solver from another program, such as a GUI.
+//!
+//! Every command is a single line, and every command but `quit` gets a single line in response,
+//! either starting with `error` or describing the result:
+//!
+//! - `games` lists the games that can be started (`games <game> ...`, by their CLI names).
+//! - `newgame <game> [args...]` starts a new game, taking the same arguments as the CLI (`ok`).
+//! - `move <move>` plays a move (`ok`, or `gameover tie` / `gameover win <player>` once the game ends).
+//! - `go [depth <n>] [time <ms>]` finds the solver's move without playing it
+//!   (`bestmove <move> score <score>`, without a score if the time ran out before it was known).
+//!   With only a time, the solver searches one move deeper at a time until it runs out.
+//! - `scores` scores every move, from best to worst (`scores <move>=<score> ...`).
+//! - `undo` takes back the last move (`ok`).
+//! - `redo` plays the last undone move again (`ok`, or `gameover ...` like `move`).
+//! - `quit` stops the server.
+//!
+//! The game and its transposition table are kept between commands.
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    hash::Hash,
+    io::{BufRead, Write},
+    iter,
+    time::Duration,
+};
+
+use anyhow::{anyhow, Result};
+use clap::{Parser, ValueEnum};
+use game_solver::{
+    game::{Game, GameState},
+    history::History,
+    move_scores,
+    player::TwoPlayer,
+    tie_break::TieBreaker,
+    transposition::Score,
+};
+
+use crate::{util::parse::ParseMove, with_game, GameKind, Games};
+
+use super::interactive::{solver_move, SolverLimits};
+
+/// The arguments of `newgame`, parsed like the CLI's.
+#[derive(Parser)]
+#[command(no_binary_name = true)]
+struct NewGame {
+    #[command(subcommand)]
+    game: Games,
+}
+
+/// A game being played over the protocol.
+trait Session {
+    fn play(&mut self, token: &str) -> Result<String>;
+    fn go(&mut self, limits: SolverLimits) -> Result<String>;
+    fn scores(&mut self) -> Result<String>;
+    fn undo(&mut self) -> Result<String>;
+    fn redo(&mut self) -> Result<String>;
+}
+
+struct GameSession<T: Game> {
+    /// The current position, and the moves played to reach it.
+    history: History<T>,
+    transposition_table: HashMap<T, Score>,
+}
+
+impl<T: Game> GameSession<T> {
+    fn new(game: T) -> Self {
+        Self {
+            history: History::new(game),
+            transposition_table: HashMap::new(),
+        }
+    }
+}
+
+impl<
+        T: Game<Player = impl TwoPlayer + Send + Sync + 'static>
+            + ParseMove
+            + Eq
+            + Hash
+            + Send
+            + Sync
+            + 'static,
+    > Session for GameSession<T>
+where
+    T::Move: Display + Send + Sync,
+    T::MoveError: Display + Send,
+{
+    fn play(&mut self, token: &str) -> Result<String> {
+        let m = self.history.game().parse_move(token)?;
+
+        if self.history.game().state() != GameState::Playable {
+            return Err(anyhow!("the game is over"));
+        }
+
+        self.history
+            .push(m)
+            .map_err(|err| anyhow!("failed to move: {err}"))?;
+
+        Ok(self.status())
+    }
+
+    fn go(&mut self, limits: SolverLimits) -> Result<String> {
+        if self.history.game().state() != GameState::Playable {
+            return Err(anyhow!("the game is over"));
+        }
+
+        match solver_move(
+            self.history.game(),
+            &mut self.transposition_table,
+            limits,
+            &mut TieBreaker::default(),
+        )
+        .map_err(|err| anyhow!("the solver failed: {err}"))?
+        {
+            Some((m, Some(score))) => Ok(format!("bestmove {m} score {score}")),
+            Some((m, None)) => Ok(format!("bestmove {m}")),
+            None => Err(anyhow!("there are no moves to play")),
+        }
+    }
+
+    fn scores(&mut self) -> Result<String> {
+        let mut scores = move_scores(
+            self.history.game(),
+            &mut self.transposition_table,
+            None,
+            &None,
+        )
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| anyhow!("the solver failed: {err}"))?;
+        scores.sort_by_key(|(_, score)| -score);
+
+        Ok(iter::once("scores".to_string())
+            .chain(scores.iter().map(|(m, score)| format!("{m}={score}")))
+            .collect::<Vec<_>>()
+            .join(" "))
+    }
+
+    fn undo(&mut self) -> Result<String> {
+        self.history
+            .undo()
+            .ok_or_else(|| anyhow!("there is no move to undo"))?;
+
+        Ok("ok".to_string())
+    }
+
+    fn redo(&mut self) -> Result<String> {
+        self.history
+            .redo()
+            .ok_or_else(|| anyhow!("there is no move to redo"))?;
+
+        Ok(self.status())
+    }
+}
+
+impl<T: Game> GameSession<T>
+where
+    T::Player: TwoPlayer,
+{
+    /// The response to reaching the current position.
+    fn status(&self) -> String {
+        match self.history.game().state() {
+            GameState::Playable => "ok".to_string(),
+            GameState::Tie => "gameover tie".to_string(),
+            GameState::Win(player) => format!("gameover win {player}"),
+        }
+    }
+}
+
+fn new_session(args: &[&str]) -> Result<Box<dyn Session>> {
+    let new_game = NewGame::try_parse_from(args).map_err(|err| {
+        // clap's errors span several lines, but responses are a single line
+        anyhow!("{}", err.to_string().lines().next().unwrap_or_default())
+    })?;
+
+    with_game!(new_game.game, game => Ok(Box::new(GameSession::new(game))))
+}
+
+fn parse_limits(args: &[&str]) -> Result<SolverLimits> {
+    let mut limits = SolverLimits::default();
+
+    for pair in args.chunks(2) {
+        match pair {
+            ["depth", depth] => {
+                limits.depth = Some(
+                    depth
+                        .parse()
+                        .map_err(|_| anyhow!("`{depth}` is not a depth"))?,
+                );
+            }
+            ["time", ms] => {
+                limits.timeout =
+                    Some(Duration::from_millis(ms.parse().map_err(|_| {
+                        anyhow!("`{ms}` is not an amount of milliseconds")
+                    })?));
+            }
+            _ => return Err(anyhow!("expected `go [depth <n>] [time <ms>]`")),
+        }
+    }
+
+    Ok(limits)
+}
+
+/// Runs one command, returning the response to it.
+fn respond(session: &mut Option<Box<dyn Session>>, command: &str, args: &[&str]) -> Result<String> {
+    if command == "games" {
+        let names = GameKind::value_variants()
+            .iter()
+            .map(|kind| kind.to_possible_value().expect("every game has a name"))
+            .map(|name| name.get_name().to_string());
+
+        return Ok(iter::once("games".to_string())
+            .chain(names)
+            .collect::<Vec<_>>()
+            .join(" "));
+    }
+
+    if command == "newgame" {
+        *session = Some(new_session(args)?);
+        return Ok("ok".to_string());
+    }
+
+    let session = session
+        .as_mut()
+        .ok_or_else(|| anyhow!("no game is being played; start one with `newgame`"))?;
+
+    match (command, args) {
+        ("move", [token]) => session.play(token),
+        ("move", _) => Err(anyhow!("expected `move <move>`")),
+        ("go", args) => session.go(parse_limits(args)?),
+        ("scores", []) => session.scores(),
+        ("undo", []) => session.undo(),
+        ("redo", []) => session.redo(),
+        ("scores" | "undo" | "redo", _) => Err(anyhow!("`{command}` takes no arguments")),
+        _ => Err(anyhow!("unknown command `{command}`")),
+    }
+}
+
+/// Reads commands from `input` until it ends (or `quit` is sent),
+/// writing a response to each to `output`.
+pub fn serve(input: impl BufRead, mut output: impl Write) -> Result<()> {
+    let mut session = None;
+
+    for line in input.lines() {
+        let line = line?;
+        let mut tokens = line.split_whitespace();
+
+        let Some(command) = tokens.next() else {
+            continue;
+        };
+
+        if command == "quit" {
+            break;
+        }
+
+        let args = tokens.collect::<Vec<_>>();
+        match respond(&mut session, command, &args) {
+            Ok(response) => writeln!(output, "{response}")?,
+            Err(err) => writeln!(output, "error {err}")?,
+        }
+
+        // the other end is waiting for the response
+        output.flush()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+//! Scores the moves of a position on a background thread, so they can be shown as they're found.
+
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+};
+
+use game_solver::{game::Game, move_scores, player::TwoPlayer, stats::Stats, transposition::Score};
+
+/// The moves scored so far.
+struct Progress<M> {
+    scores: Vec<(M, isize)>,
+    finished: bool,
+}
+
+/// A search of every move of a position, running until it's done or stopped.
+pub struct Analysis<T: Game> {
+    cancellation_token: Arc<AtomicBool>,
+    stats: Arc<Stats<T::Player>>,
+    progress: Arc<Mutex<Progress<T::Move>>>,
+    /// The amount of moves to score.
+    moves: usize,
+    /// The search, which hands back its table once it's done.
+    thread: Option<JoinHandle<HashMap<T, Score>>>,
+}
+
+impl<T> Analysis<T>
+where
+    T: Game + Eq + Hash + Send + 'static,
+    T::Player: TwoPlayer + Send + Sync + 'static,
+    T::Move: Send,
+{
+    /// Starts scoring the moves of `game`, with what `table` already knows.
+    pub fn start(game: T, mut table: HashMap<T, Score>) -> Self {
+        let cancellation_token = Arc::new(AtomicBool::new(false));
+        let stats = Arc::new(Stats::new(game.player(), game.move_count()));
+        let progress = Arc::new(Mutex::new(Progress {
+            scores: Vec::new(),
+            finished: false,
+        }));
+
+        let moves = game.possible_moves().count();
+
+        let thread = {
+            let cancellation_token = Some(cancellation_token.clone());
+            let stats = stats.clone();
+            let progress = progress.clone();
+
+            thread::spawn(move || {
+                let mut finished = true;
+
+                for score in move_scores(&game, &mut table, Some(&stats), &cancellation_token) {
+                    // the search was stopped, or failed, so the moves left aren't scored
+                    let Ok(score) = score else {
+                        finished = false;
+                        break;
+                    };
+
+                    progress.lock().unwrap().scores.push(score);
+                }
+
+                progress.lock().unwrap().finished = finished;
+                table
+            })
+        };
+
+        Self {
+            cancellation_token,
+            stats,
+            progress,
+            moves,
+            thread: Some(thread),
+        }
+    }
+}
+
+impl<T: Game> Analysis<T> {
+    /// The moves scored so far, from best to worst, for the player who would make them.
+    pub fn scores(&self) -> Vec<(T::Move, isize)> {
+        let mut scores = self.progress.lock().unwrap().scores.clone();
+        scores.sort_by_key(|(_, score)| -score);
+        scores
+    }
+
+    /// The amount of moves of the position, scored or not.
+    pub fn moves(&self) -> usize {
+        self.moves
+    }
+
+    /// The amount of positions searched so far.
+    pub fn nodes(&self) -> u64 {
+        self.stats.states_explored.load(Ordering::Relaxed)
+    }
+
+    /// Whether every move was scored.
+    pub fn is_finished(&self) -> bool {
+        self.progress.lock().unwrap().finished
+    }
+
+    /// Stops the search, returning its table for the next one.
+    pub fn stop(mut self) -> HashMap<T, Score> {
+        self.cancellation_token.store(true, Ordering::Relaxed);
+
+        self.thread
+            .take()
+            .expect("the search is only stopped once")
+            .join()
+            .unwrap_or_default()
+    }
+}
+
+impl<T: Game> Drop for Analysis<T> {
+    fn drop(&mut self) {
+        // a search that wasn't stopped is left to finish on its own, which it does quickly once cancelled
+        self.cancellation_token.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,287 @@
+//! What the TUI shows, and how it answers keys, without a terminal.
+
+use anyhow::{anyhow, Result};
+use ratatui::crossterm::event::KeyCode;
+
+use crate::{
+    domineering::Domineering, order_and_chaos::OrderAndChaos, reversi::Reversi,
+    tic_tac_toe::TicTacToe, GameKind, Games,
+};
+
+use super::board::{Board, BoardView};
+
+/// The games that can be picked from the menu: those played on a grid.
+pub const GRID_GAMES: [GameKind; 4] = [
+    GameKind::TicTacToe,
+    GameKind::OrderAndChaos,
+    GameKind::Reversi,
+    GameKind::Domineering,
+];
+
+/// Sets up a game of the menu, with its default arguments.
+fn open(kind: GameKind) -> Result<Box<dyn BoardView>> {
+    Ok(match kind.default_game() {
+        Games::TicTacToe(args) => Box::new(Board::new(TicTacToe::try_from(args)?)?),
+        Games::OrderAndChaos(args) => {
+            Box::new(Board::new(OrderAndChaos::<6, 6, 5, 6>::try_from(args)?)?)
+        }
+        Games::Reversi(args) => Box::new(Board::new(Reversi::try_from(args)?)?),
+        Games::Domineering(args) => Box::new(Board::new(Domineering::<5, 5>::try_from(args)?)?),
+        _ => return Err(anyhow!("{} isn't played on a grid", kind.name())),
+    })
+}
+
+/// The TUI: a menu of games, or a game being played and analyzed.
+pub struct App {
+    /// The game chosen in the menu.
+    selected: usize,
+    /// The game being played, or None in the menu.
+    board: Option<Box<dyn BoardView>>,
+    /// The result of the last action, such as the move it played or why it couldn't.
+    message: Option<String>,
+    quit: bool,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl App {
+    pub fn new() -> Self {
+        Self {
+            selected: 0,
+            board: None,
+            message: None,
+            quit: false,
+        }
+    }
+
+    pub fn selected(&self) -> GameKind {
+        GRID_GAMES[self.selected]
+    }
+
+    pub fn board(&self) -> Option<&dyn BoardView> {
+        self.board.as_deref()
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    pub fn should_quit(&self) -> bool {
+        self.quit
+    }
+
+    /// Answers a key press.
+    ///
+    /// In the menu, the arrows pick a game and Enter starts it. In a game, the arrows move the cursor,
+    /// Enter plays the chosen move at it, Tab chooses another, `b` plays the best move found so far,
+    /// `u` takes back the last move, and Esc goes back to the menu. `q` quits.
+    pub fn handle_key(&mut self, key: KeyCode) {
+        if key == KeyCode::Char('q') {
+            self.quit = true;
+            return;
+        }
+
+        let Some(board) = &mut self.board else {
+            match key {
+                KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+                KeyCode::Down => self.selected = (self.selected + 1).min(GRID_GAMES.len() - 1),
+                KeyCode::Enter => match open(self.selected()) {
+                    Ok(board) => {
+                        self.board = Some(board);
+                        self.message = None;
+                    }
+                    Err(err) => self.message = Some(err.to_string()),
+                },
+                _ => {}
+            }
+
+            return;
+        };
+
+        let played = |result: Result<String>| match result {
+            Ok(m) => format!("Played {m}."),
+            Err(err) => format!("{err}."),
+        };
+
+        match key {
+            KeyCode::Up => board.move_cursor(-1, 0),
+            KeyCode::Down => board.move_cursor(1, 0),
+            KeyCode::Left => board.move_cursor(0, -1),
+            KeyCode::Right => board.move_cursor(0, 1),
+            KeyCode::Tab => board.next_choice(),
+            KeyCode::Enter => self.message = Some(played(board.play())),
+            KeyCode::Char('b') => self.message = Some(played(board.play_best())),
+            KeyCode::Char('u') => {
+                self.message = Some(match board.undo() {
+                    Some(m) => format!("Took back {m}."),
+                    None => "There is no move to take back.".to_string(),
+                });
+            }
+            KeyCode::Esc => {
+                self.board = None;
+                self.message = None;
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+    use super::*;
+
+    fn press(app: &mut App, keys: &[KeyCode]) {
+        for &key in keys {
+            app.handle_key(key);
+        }
+    }
+
+    /// Waits for the analysis of the current position to score every move.
+    fn finished_evaluation(app: &App) -> super::super::board::Evaluation {
+        let start = Instant::now();
+
+        loop {
+            let evaluation = app.board().unwrap().evaluation();
+            if evaluation.finished {
+                return evaluation;
+            }
+
+            assert!(start.elapsed() < Duration::from_secs(60), "{evaluation:?}");
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    fn render(app: &App) -> String {
+        let area = Rect::new(0, 0, 100, 30);
+        let mut buffer = Buffer::empty(area);
+        app.render(area, &mut buffer);
+
+        buffer
+            .content()
+            .chunks(area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn the_menu_picks_a_game() {
+        let mut app = App::new();
+        assert_eq!(app.selected(), GameKind::TicTacToe);
+
+        press(&mut app, &[KeyCode::Up]);
+        assert_eq!(app.selected(), GameKind::TicTacToe);
+        press(
+            &mut app,
+            &[KeyCode::Down, KeyCode::Down, KeyCode::Down, KeyCode::Down],
+        );
+        assert_eq!(app.selected(), GameKind::Domineering);
+        assert!(render(&app).contains("Domineering"));
+
+        press(&mut app, &[KeyCode::Up, KeyCode::Enter]);
+        let board = app.board().unwrap();
+        assert_eq!(board.name(), "Reversi");
+        assert_eq!(board.size(), (6, 6));
+
+        press(&mut app, &[KeyCode::Esc]);
+        assert!(app.board().is_none());
+
+        press(&mut app, &[KeyCode::Char('q')]);
+        assert!(app.should_quit());
+    }
+
+    #[test]
+    fn moves_are_played_at_the_cursor_and_taken_back() {
+        let mut app = App::new();
+        press(&mut app, &[KeyCode::Enter]);
+
+        // the cursor stays on the board
+        press(&mut app, &[KeyCode::Up, KeyCode::Left]);
+        assert_eq!(app.board().unwrap().cursor(), (0, 0));
+
+        press(&mut app, &[KeyCode::Down, KeyCode::Right, KeyCode::Enter]);
+        assert_eq!(app.message(), Some("Played 1-1."));
+        let board = app.board().unwrap();
+        assert_eq!(board.square(1, 1), 'X');
+        assert!(!board.is_playable(1, 1));
+        assert_eq!(board.status(), "Right to move");
+
+        press(&mut app, &[KeyCode::Enter]);
+        assert_eq!(app.message(), Some("no move can be made here."));
+
+        press(&mut app, &[KeyCode::Char('u')]);
+        assert_eq!(app.message(), Some("Took back 1-1."));
+        assert_eq!(app.board().unwrap().square(1, 1), '.');
+
+        press(&mut app, &[KeyCode::Char('u')]);
+        assert_eq!(app.message(), Some("There is no move to take back."));
+    }
+
+    #[test]
+    fn several_moves_of_a_square_are_chosen_between() {
+        let mut app = App::new();
+        press(&mut app, &[KeyCode::Down, KeyCode::Enter]);
+
+        let board = app.board().unwrap();
+        assert_eq!(board.name(), "Order and Chaos");
+        assert_eq!(board.moves_at_cursor().len(), 2);
+
+        press(&mut app, &[KeyCode::Tab]);
+        assert_eq!(app.board().unwrap().choice(), 1);
+        press(&mut app, &[KeyCode::Tab]);
+        assert_eq!(app.board().unwrap().choice(), 0);
+
+        let chosen = app.board().unwrap().moves_at_cursor()[0].clone();
+        press(&mut app, &[KeyCode::Enter]);
+        assert_eq!(app.message(), Some(format!("Played {chosen}.").as_str()));
+    }
+
+    #[test]
+    fn the_analysis_scores_every_move_in_the_background() {
+        let mut app = App::new();
+        press(
+            &mut app,
+            &[
+                KeyCode::Enter,
+                KeyCode::Down,
+                KeyCode::Right,
+                KeyCode::Enter,
+            ],
+        );
+
+        // every reply to the center is scored, and only the corners don't lose
+        let evaluation = finished_evaluation(&app);
+        assert_eq!((evaluation.scored, evaluation.moves), (8, 8));
+        assert!(evaluation.nodes > 0);
+        assert!(evaluation.lines[..4]
+            .iter()
+            .all(|line| line.ends_with("tie")));
+        assert!(evaluation.lines[4..]
+            .iter()
+            .all(|line| line.contains("lose in")));
+
+        let screen = render(&app);
+        assert!(screen.contains("Tic Tac Toe"), "{screen}");
+        assert!(screen.contains("Scored 8 of 8 moves"), "{screen}");
+
+        // best play from here ties
+        for _ in 0..8 {
+            finished_evaluation(&app);
+            press(&mut app, &[KeyCode::Char('b')]);
+        }
+        let board = app.board().unwrap();
+        assert_eq!(board.status(), "Game tied!");
+        assert!(board.evaluation().lines.is_empty());
+    }
+}
@@ -0,0 +1,293 @@
+//! A game played on a grid, with a cursor for choosing its moves.
+
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+    hash::Hash,
+};
+
+use anyhow::{anyhow, Result};
+use game_solver::{
+    game::{Game, GameState},
+    history::History,
+    ordering::PlacementGame,
+    player::TwoPlayer,
+    transposition::Score,
+};
+
+use crate::{util::notation::NotatedGame, GamePosition};
+
+use super::{
+    super::{ending, report::scores::describe_move, Endings},
+    analysis::Analysis,
+};
+
+/// What the side panel shows of the search of the current position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Evaluation {
+    /// The moves scored so far, from best to worst, e.g. `1-1, score for Left: 0, tie`.
+    pub lines: Vec<String>,
+    pub scored: usize,
+    pub moves: usize,
+    pub nodes: u64,
+    pub finished: bool,
+}
+
+/// A game on a grid, as the TUI sees it, whatever game it is.
+pub trait BoardView {
+    /// The name of the game.
+    fn name(&self) -> &'static str;
+
+    /// The amount of rows and columns of the grid.
+    fn size(&self) -> (usize, usize);
+
+    /// The character of a square, as the game's notation writes it.
+    fn square(&self, row: usize, column: usize) -> char;
+
+    /// Whether a move can be made at a square.
+    fn is_playable(&self, row: usize, column: usize) -> bool;
+
+    /// The row and column of the cursor.
+    fn cursor(&self) -> (usize, usize);
+
+    /// Moves the cursor by some rows and columns, staying on the grid.
+    fn move_cursor(&mut self, rows: isize, columns: isize);
+
+    /// The moves that can be made at the cursor.
+    fn moves_at_cursor(&self) -> Vec<String>;
+
+    /// Which of the moves at the cursor is chosen.
+    fn choice(&self) -> usize;
+
+    /// Chooses the next move at the cursor, for games with several moves a square, such as Order and Chaos.
+    fn next_choice(&mut self);
+
+    /// Plays the chosen move at the cursor, returning it.
+    fn play(&mut self) -> Result<String>;
+
+    /// Plays the best move the analysis found so far, returning it.
+    fn play_best(&mut self) -> Result<String>;
+
+    /// Takes back the last move, returning it.
+    fn undo(&mut self) -> Option<String>;
+
+    /// Who is to move, or how the game ended.
+    fn status(&self) -> String;
+
+    fn evaluation(&self) -> Evaluation;
+}
+
+/// A game on a grid, along with the moves played on it and the search of its current position.
+pub struct Board<T: Game> {
+    name: &'static str,
+    history: History<T>,
+    /// The squares of the current position, by row.
+    squares: Vec<Vec<char>>,
+    cursor: (usize, usize),
+    choice: usize,
+    /// The search of the current position, which is None once the game is over.
+    analysis: Option<Analysis<T>>,
+    /// The table of the last search, once the game is over.
+    table: HashMap<T, Score>,
+}
+
+impl<T> Board<T>
+where
+    T: Game + PlacementGame + NotatedGame + Eq + Hash + Send + 'static,
+    T::Player: TwoPlayer + Debug + Send + Sync + 'static,
+    T::Move: Display + Send,
+    GamePosition: From<T>,
+{
+    /// Starts analyzing `game`, which has to be on a grid of two dimensions.
+    pub fn new(game: T) -> Result<Self> {
+        let dimensions = game.grid_size().len();
+        if dimensions != 2 {
+            return Err(anyhow!(
+                "only games on a grid of two dimensions can be shown, not {dimensions}"
+            ));
+        }
+
+        let mut board = Self {
+            name: GamePosition::from(game.clone()).kind().name(),
+            history: History::new(game),
+            squares: Vec::new(),
+            cursor: (0, 0),
+            choice: 0,
+            analysis: None,
+            table: HashMap::new(),
+        };
+        board.refresh();
+
+        Ok(board)
+    }
+
+    /// Reads the squares of the current position, and starts searching it.
+    fn refresh(&mut self) {
+        let game = self.history.game();
+
+        // the first field of the notation of a grid game is its squares, row by row
+        self.squares = game
+            .to_notation()
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .split('/')
+            .map(|row| row.chars().collect())
+            .collect();
+        self.choice = 0;
+
+        let table = match self.analysis.take() {
+            Some(analysis) => analysis.stop(),
+            None => std::mem::take(&mut self.table),
+        };
+
+        if game.state() == GameState::Playable {
+            self.analysis = Some(Analysis::start(game.clone(), table));
+        } else {
+            self.table = table;
+        }
+    }
+
+    fn moves_at(&self, (row, column): (usize, usize)) -> Vec<T::Move> {
+        let game = self.history.game();
+
+        game.possible_moves()
+            .filter(|m| game.cell(m) == [row, column])
+            .collect()
+    }
+
+    fn push(&mut self, m: T::Move) -> Result<String> {
+        let played = m.to_string();
+        self.history
+            .push(m)
+            .map_err(|err| anyhow!("{played} can't be played: {err}"))?;
+        self.refresh();
+
+        Ok(played)
+    }
+}
+
+impl<T> BoardView for Board<T>
+where
+    T: Game + PlacementGame + NotatedGame + Endings + Eq + Hash + Send + 'static,
+    T::Player: TwoPlayer + Debug + Send + Sync + 'static,
+    T::Move: Display + Send,
+    GamePosition: From<T>,
+{
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn size(&self) -> (usize, usize) {
+        let size = self.history.game().grid_size();
+        (size[0], size[1])
+    }
+
+    fn square(&self, row: usize, column: usize) -> char {
+        self.squares
+            .get(row)
+            .and_then(|row| row.get(column))
+            .copied()
+            .unwrap_or(' ')
+    }
+
+    fn is_playable(&self, row: usize, column: usize) -> bool {
+        !self.moves_at((row, column)).is_empty()
+    }
+
+    fn cursor(&self) -> (usize, usize) {
+        self.cursor
+    }
+
+    fn move_cursor(&mut self, rows: isize, columns: isize) {
+        let (height, width) = self.size();
+        let (row, column) = self.cursor;
+
+        self.cursor = (
+            row.saturating_add_signed(rows).min(height - 1),
+            column.saturating_add_signed(columns).min(width - 1),
+        );
+        self.choice = 0;
+    }
+
+    fn moves_at_cursor(&self) -> Vec<String> {
+        self.moves_at(self.cursor)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    fn choice(&self) -> usize {
+        self.choice
+    }
+
+    fn next_choice(&mut self) {
+        let moves = self.moves_at(self.cursor).len();
+        self.choice = if moves == 0 {
+            0
+        } else {
+            (self.choice + 1) % moves
+        };
+    }
+
+    fn play(&mut self) -> Result<String> {
+        let m = self
+            .moves_at(self.cursor)
+            .into_iter()
+            .nth(self.choice)
+            .ok_or_else(|| anyhow!("no move can be made here"))?;
+
+        self.push(m)
+    }
+
+    fn play_best(&mut self) -> Result<String> {
+        let m = self
+            .analysis
+            .as_ref()
+            .and_then(|analysis| analysis.scores().into_iter().next())
+            .map(|(m, _)| m)
+            .ok_or_else(|| anyhow!("no move has been scored yet"))?;
+
+        self.push(m)
+    }
+
+    fn undo(&mut self) -> Option<String> {
+        let m = self.history.undo()?.to_string();
+        self.refresh();
+
+        Some(m)
+    }
+
+    fn status(&self) -> String {
+        let game = self.history.game();
+
+        ending(game, |winner| format!("The {winner} player"))
+            .unwrap_or_else(|| format!("{} to move", game.player()))
+    }
+
+    fn evaluation(&self) -> Evaluation {
+        let Some(analysis) = &self.analysis else {
+            return Evaluation {
+                lines: Vec::new(),
+                scored: 0,
+                moves: 0,
+                nodes: 0,
+                finished: true,
+            };
+        };
+
+        let game = self.history.game();
+        let scores = analysis.scores();
+
+        Evaluation {
+            lines: scores
+                .iter()
+                .map(|move_score| describe_move(game, move_score))
+                .collect(),
+            scored: scores.len(),
+            moves: analysis.moves(),
+            nodes: analysis.nodes(),
+            finished: analysis.is_finished(),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! A terminal UI for playing and analyzing the games played on a grid.
+//! This requires the `tui` feature to be enabled.
+//!
+//! A game is picked from a menu, and its moves are chosen with a cursor on its board.
+//! Every position is searched on a background thread while it's shown,
+//! and the side panel shows the moves scored so far, which the search is cancelled from
+//! as soon as another position is shown.
+//!
+//! [`App`] is the whole state of the TUI, which can be driven without a terminal.
+
+mod analysis;
+pub mod app;
+pub mod board;
+mod ui;
+
+use std::time::Duration;
+
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{self, Event, KeyEventKind},
+    DefaultTerminal,
+};
+
+pub use app::App;
+
+/// Runs the TUI until it's quit.
+pub fn run_tui() -> Result<()> {
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &mut App::new());
+    ratatui::restore();
+
+    result
+}
+
+fn run(terminal: &mut DefaultTerminal, app: &mut App) -> Result<()> {
+    while !app.should_quit() {
+        terminal.draw(|frame| frame.render_widget(&*app, frame.area()))?;
+
+        // redraws at least every so often, to show how the search is going
+        if event::poll(Duration::from_millis(100))? {
+            // crossterm also sends key releases and repeats on Windows
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    app.handle_key(key.code);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
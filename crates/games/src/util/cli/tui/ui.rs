@@ -0,0 +1,173 @@
+//! Draws the TUI.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Style, Stylize},
+    symbols::border,
+    text::{Line, Span, Text},
+    widgets::{
+        block::{Position, Title},
+        Block, Paragraph, Widget, Wrap,
+    },
+};
+
+use super::{
+    app::{App, GRID_GAMES},
+    board::BoardView,
+};
+
+fn instructions(keys: &[(&str, &str)]) -> Title<'static> {
+    let spans = keys.iter().flat_map(|(action, key)| {
+        [
+            format!(" {action} ").into(),
+            format!("<{key}> ").blue().bold(),
+        ]
+    });
+
+    Title::from(Line::from(spans.collect::<Vec<_>>()))
+        .alignment(Alignment::Center)
+        .position(Position::Bottom)
+}
+
+fn render_menu(app: &App, area: Rect, buf: &mut Buffer) {
+    let block = Block::bordered()
+        .title(Title::from(" game-solver ".bold().green()).alignment(Alignment::Center))
+        .title(instructions(&[
+            ("Choose", "↑↓"),
+            ("Play", "Enter"),
+            ("Quit", "Q"),
+        ]))
+        .border_set(border::THICK);
+
+    let mut lines = vec![
+        Line::from("Pick a game to play and analyze:"),
+        Line::default(),
+    ];
+    for &kind in &GRID_GAMES {
+        let metadata = kind.metadata();
+        let name = format!("{:16}", metadata.name);
+
+        let line = if kind == app.selected() {
+            Line::from(vec![
+                "> ".yellow().bold(),
+                name.yellow().bold(),
+                metadata.summary.into(),
+            ])
+        } else {
+            Line::from(vec!["  ".into(), name.into(), metadata.summary.into()])
+        };
+        lines.push(line);
+    }
+
+    if let Some(message) = app.message() {
+        lines.push(Line::default());
+        lines.push(message.red().into());
+    }
+
+    Paragraph::new(Text::from(lines))
+        .wrap(Wrap { trim: false })
+        .block(block)
+        .render(area, buf);
+}
+
+fn render_board(board: &dyn BoardView, message: Option<&str>, area: Rect, buf: &mut Buffer) {
+    let block = Block::bordered()
+        .title(
+            Title::from(format!(" {} ", board.name()).bold().green()).alignment(Alignment::Center),
+        )
+        .title(instructions(&[
+            ("Move", "←↑↓→"),
+            ("Play", "Enter"),
+            ("Other move", "Tab"),
+            ("Best", "B"),
+            ("Undo", "U"),
+            ("Menu", "Esc"),
+            ("Quit", "Q"),
+        ]))
+        .border_set(border::THICK);
+
+    let (rows, columns) = board.size();
+    let cursor = board.cursor();
+
+    let mut lines = vec![Line::from(board.status().bold()), Line::default()];
+    for row in 0..rows {
+        let squares = (0..columns).map(|column| {
+            let square = format!(" {} ", board.square(row, column));
+
+            if (row, column) == cursor {
+                Span::styled(square, Style::new().reversed())
+            } else if board.is_playable(row, column) {
+                Span::styled(square, Style::new().cyan())
+            } else {
+                square.into()
+            }
+        });
+        lines.push(Line::from(squares.collect::<Vec<_>>()));
+    }
+
+    lines.push(Line::default());
+    let moves = board.moves_at_cursor();
+    lines.push(match moves.get(board.choice()) {
+        Some(m) if moves.len() > 1 => format!(
+            "Enter plays {m} ({} of {})",
+            board.choice() + 1,
+            moves.len()
+        )
+        .into(),
+        Some(m) => format!("Enter plays {m}").into(),
+        None => "No move can be made here".dim().into(),
+    });
+    if let Some(message) = message {
+        lines.push(message.yellow().into());
+    }
+
+    let inner = block.inner(area);
+    block.render(area, buf);
+
+    let [left, right] =
+        Layout::horizontal([Constraint::Min(3 * columns as u16 + 2), Constraint::Fill(1)])
+            .areas(inner);
+
+    Paragraph::new(Text::from(lines)).render(left, buf);
+    render_evaluation(board, right, buf);
+}
+
+fn render_evaluation(board: &dyn BoardView, area: Rect, buf: &mut Buffer) {
+    let evaluation = board.evaluation();
+
+    let progress = if evaluation.moves == 0 {
+        "No moves to score".to_string()
+    } else {
+        format!(
+            "Scored {} of {} moves{}",
+            evaluation.scored,
+            evaluation.moves,
+            if evaluation.finished { "" } else { "..." }
+        )
+    };
+
+    let mut lines = vec![
+        Line::from(progress),
+        Line::from(vec![
+            "Positions searched: ".into(),
+            evaluation.nodes.to_string().yellow(),
+        ]),
+        Line::default(),
+    ];
+    lines.extend(evaluation.lines.into_iter().map(Line::from));
+
+    Paragraph::new(Text::from(lines))
+        .block(Block::bordered().title(" Evaluation "))
+        .wrap(Wrap { trim: false })
+        .render(area, buf);
+}
+
+impl Widget for &App {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        match self.board() {
+            Some(board) => render_board(board, self.message(), area, buf),
+            None => render_menu(self, area, buf),
+        }
+    }
+}
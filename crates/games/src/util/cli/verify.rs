@@ -0,0 +1,122 @@
+use std::{fmt::Display, hash::Hash, io::Write};
+
+use anyhow::{anyhow, bail, Result};
+use clap::Args;
+#[cfg(not(feature = "rayon"))]
+use game_solver::verify::checks;
+#[cfg(feature = "rayon")]
+use game_solver::verify::parallel_checks;
+use game_solver::{
+    game::Game,
+    player::TwoPlayer,
+    verify::{verify_random, Mismatch},
+};
+use itertools::Itertools;
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{util::notation::NotatedGame, GameKind};
+
+use super::exit::solver_failed;
+
+/// Checks the solver against a brute force search on random positions.
+#[derive(Args, Debug, Clone)]
+pub struct VerifyArgs {
+    /// The game to verify, starting from its default position.
+    #[arg(value_enum)]
+    pub game: GameKind,
+    /// How many random positions to verify.
+    #[arg(long, default_value_t = 100)]
+    pub samples: usize,
+    /// The seed for the random positions. The same seed always verifies the same positions.
+    #[arg(long, default_value_t = 0)]
+    pub seed: u64,
+    /// Skip positions the brute force search can't finish in this many positions.
+    #[arg(long, value_name = "N", default_value_t = 200_000)]
+    pub node_limit: u64,
+}
+
+/// Verifies random positions of `game`, writing how many agreed, and every mismatch,
+/// each with the fewest moves that reproduce it and the notation of the position they reach.
+/// Fails if there were any mismatches.
+pub fn run_verify<T, P>(game: T, args: &VerifyArgs, mut output: impl Write) -> Result<()>
+where
+    P: TwoPlayer + Send + Sync + 'static,
+    T: Game<Player = P> + NotatedGame + Eq + Hash + Send + Sync + 'static,
+    T::Move: Display + Send + Sync,
+    T::MoveError: Display + Send + Sync,
+{
+    // `par_move_scores` is checked too with the `rayon` feature
+    #[cfg(feature = "rayon")]
+    let checks = parallel_checks();
+    #[cfg(not(feature = "rayon"))]
+    let checks = checks();
+
+    let mut rng = StdRng::seed_from_u64(args.seed);
+    let verification = verify_random(&game, &checks, args.samples, args.node_limit, &mut rng)
+        .map_err(solver_failed)?;
+
+    for mismatch in &verification.mismatches {
+        write_mismatch(&game, mismatch, &mut output)?;
+    }
+
+    writeln!(
+        output,
+        "Verified {} positions of {}: {} agreed, {} disagreed, {} were too large to search by brute force.",
+        args.samples,
+        args.game.name(),
+        verification.agreed,
+        verification.mismatches.len(),
+        verification.too_large
+    )?;
+    output.flush()?;
+
+    if !verification.mismatches.is_empty() {
+        bail!(
+            "The solver disagreed with the brute force search about {} positions.",
+            verification.mismatches.len()
+        );
+    }
+
+    Ok(())
+}
+
+fn write_mismatch<T: Game + NotatedGame>(
+    start: &T,
+    mismatch: &Mismatch<T::Move>,
+    mut output: impl Write,
+) -> Result<()>
+where
+    T::Move: Display,
+    T::MoveError: Display,
+{
+    let mut position = start.clone();
+    for m in &mismatch.moves {
+        position
+            .make_move(m)
+            .map_err(|err| anyhow!("{m} can't be replayed: {err}"))?;
+    }
+
+    let disagreement = &mismatch.disagreement;
+    writeln!(
+        output,
+        "{} disagrees after {} moves: {}",
+        disagreement.check,
+        mismatch.moves.len(),
+        mismatch.moves.iter().join(" ")
+    )?;
+    writeln!(output, "  position: {}", position.to_notation())?;
+    match &disagreement.played {
+        Some(m) => writeln!(
+            output,
+            "  move {m}: expected {}, found {}",
+            disagreement.expected, disagreement.found
+        )?,
+        None => writeln!(
+            output,
+            "  score: expected {}, found {}",
+            disagreement.expected, disagreement.found
+        )?,
+    }
+
+    Ok(())
+}
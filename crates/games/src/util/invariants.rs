@@ -0,0 +1,410 @@
+//! Checks of the invariants every game should uphold, callable from tests and fuzz targets alike.
+//!
+//! [`check_playthrough`] plays a game with moves chosen by a list of bytes, which lets a fuzzer
+//! explore reachable positions, and checks every position along the way with [`check_position`].
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+use anyhow::Result;
+use clap::ValueEnum;
+use game_solver::{
+    game::{win_score, Game, GameState},
+    player::{Player, TwoPlayer},
+    solve,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use thiserror::Error;
+
+use crate::{
+    util::{notation::NotatedGame, parse::ParseMove},
+    with_position, GameKind,
+};
+
+/// An invariant that a position doesn't uphold, described along with the position's notation.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum InvariantError {
+    #[error("{notation}: a clone isn't equal to the position, or hashes differently")]
+    Clone { notation: String },
+    #[error("{notation}: the possible move {m} can't be made: {error}")]
+    MoveFailed {
+        notation: String,
+        m: String,
+        error: String,
+    },
+    #[error("{notation}: making a move changed the move count from {before} to {after}")]
+    MoveCount {
+        notation: String,
+        before: usize,
+        after: usize,
+    },
+    #[error("{notation}: {move_count} moves were made, but the game has at most {max_moves}")]
+    MaxMoves {
+        notation: String,
+        move_count: usize,
+        max_moves: usize,
+    },
+    #[error("{notation}: there are {count} possible moves, but move_count_hint says {hint:?}")]
+    MoveCountHint {
+        notation: String,
+        count: usize,
+        hint: (usize, Option<usize>),
+    },
+    #[error("{notation}: has_moves says {has_moves}, but there are {count} possible moves")]
+    HasMoves {
+        notation: String,
+        count: usize,
+        has_moves: bool,
+    },
+    #[error("{notation}: the game isn't over, but there are no possible moves")]
+    NoMoves { notation: String },
+    #[error("{notation}: {error}")]
+    Ending {
+        notation: String,
+        error: &'static str,
+    },
+    #[error("{notation}: find_immediately_resolvable_game {error}")]
+    ResolvableGame { notation: String, error: String },
+    #[error("{notation}: the possible move {m} doesn't parse back to itself")]
+    MoveParse { notation: String, m: String },
+    #[error("{notation}: the notation doesn't describe the same position: {error}")]
+    Notation { notation: String, error: String },
+    #[error("{notation}: the serialized position doesn't deserialize to itself: {error}")]
+    Serde { notation: String, error: String },
+}
+
+fn hash<T: Hash>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Checks that `game`'s clones are equal and hash the same, that it stays within its move limit,
+/// that it reads back from its notation and serialized form, and that every possible move
+/// can be made, adds exactly one move, and parses back from how it's written.
+/// [`Game::move_count_hint`] and [`Game::has_moves`] have to agree with the possible moves.
+///
+/// Positions that aren't over have to have a possible move, and [`Game::find_immediately_resolvable_game`]
+/// has to return a position one move away that is over, which wins for the player to move if any can.
+/// [`Game::state`], [`Game::winner`] and [`Game::is_tie`] have to agree with [`Game::status`], and so does the solver
+/// on positions that are over, which can't be scored better than winning right away.
+pub fn check_position<T>(game: &T) -> Result<(), InvariantError>
+where
+    T: Game + ParseMove + NotatedGame + Serialize + DeserializeOwned + Eq + Hash,
+    T::Player: TwoPlayer + 'static,
+    T::Move: Display + PartialEq,
+{
+    let notation = game.to_notation();
+
+    let clone = game.clone();
+    if clone != *game || hash(&clone) != hash(game) {
+        return Err(InvariantError::Clone { notation });
+    }
+
+    if let Some(max_moves) = game.max_moves() {
+        if game.move_count() > max_moves {
+            return Err(InvariantError::MaxMoves {
+                notation,
+                move_count: game.move_count(),
+                max_moves,
+            });
+        }
+    }
+
+    match T::from_notation(&notation) {
+        Ok(read) if read == *game => (),
+        Ok(read) => {
+            return Err(InvariantError::Notation {
+                error: format!("it reads back as {}", read.to_notation()),
+                notation,
+            })
+        }
+        Err(err) => {
+            return Err(InvariantError::Notation {
+                error: err.message().to_string(),
+                notation,
+            })
+        }
+    }
+
+    let serde_error = match serde_json::to_string(game).map(|json| serde_json::from_str::<T>(&json))
+    {
+        Ok(Ok(read)) if read == *game => None,
+        Ok(Ok(_)) => Some("it deserializes to a different position".to_string()),
+        Ok(Err(err)) | Err(err) => Some(err.to_string()),
+    };
+    if let Some(error) = serde_error {
+        return Err(InvariantError::Serde { notation, error });
+    }
+
+    let mut children = Vec::new();
+    for m in game.possible_moves() {
+        if game.parse_move(&m.to_string()).ok().as_ref() != Some(&m) {
+            return Err(InvariantError::MoveParse {
+                notation,
+                m: m.to_string(),
+            });
+        }
+
+        let mut board = game.clone();
+        if let Err(err) = board.make_move(&m) {
+            return Err(InvariantError::MoveFailed {
+                notation,
+                m: m.to_string(),
+                error: err.to_string(),
+            });
+        }
+
+        if board.move_count() != game.move_count() + 1 {
+            return Err(InvariantError::MoveCount {
+                notation,
+                before: game.move_count(),
+                after: board.move_count(),
+            });
+        }
+
+        children.push(board);
+    }
+
+    let count = children.len();
+    let hint = game.move_count_hint();
+    if count < hint.0 || hint.1.is_some_and(|upper| count > upper) {
+        return Err(InvariantError::MoveCountHint {
+            notation,
+            count,
+            hint,
+        });
+    }
+
+    let has_moves = game.has_moves();
+    if has_moves != (count > 0) {
+        return Err(InvariantError::HasMoves {
+            notation,
+            count,
+            has_moves,
+        });
+    }
+
+    check_ending(game).map_err(|error| InvariantError::Ending {
+        notation: notation.clone(),
+        error,
+    })?;
+
+    if game.state() == GameState::Playable {
+        if children.is_empty() {
+            return Err(InvariantError::NoMoves { notation });
+        }
+
+        check_resolvable_game(game, &children).map_err(|error| InvariantError::ResolvableGame {
+            notation,
+            error: error.to_string(),
+        })?;
+    }
+
+    Ok(())
+}
+
+/// Checks that [`Game::state`], [`Game::winner`], [`Game::is_tie`], and the solver's score of a position
+/// that is over agree with its status, and that it isn't scored better than winning right away.
+fn check_ending<T>(game: &T) -> Result<(), &'static str>
+where
+    T: Game + Eq + Hash,
+    T::Player: TwoPlayer + 'static,
+{
+    // measured from the draw score, as the solver scores it
+    let status = game.status().score(game);
+    let state = game.state();
+
+    let agrees = match (status, state) {
+        (None, GameState::Playable) | (Some(0), GameState::Tie) => true,
+        // a positive score is the player to move's
+        (Some(score), GameState::Win(player)) if score != 0 => {
+            (score > 0) == (player == game.player())
+        }
+        _ => false,
+    };
+    if !agrees {
+        return Err("state disagrees with status");
+    }
+
+    let winner = match state {
+        GameState::Win(player) => Some(player),
+        GameState::Playable | GameState::Tie => None,
+    };
+    if game.winner() != winner {
+        return Err("winner disagrees with state");
+    }
+    if game.is_tie() != (state == GameState::Tie) {
+        return Err("is_tie disagrees with state");
+    }
+
+    let Some(expected) = status else {
+        return Ok(());
+    };
+
+    if expected.unsigned_abs() > win_score(game).unsigned_abs() {
+        return Err("the finished game is scored better than winning right away");
+    }
+
+    let score = solve(game, &mut HashMap::new(), None, &None)
+        .map_err(|_| "the solver failed when the game is over")?;
+
+    if score == expected {
+        Ok(())
+    } else {
+        Err("the solver's score of the finished game disagrees with its status")
+    }
+}
+
+/// Checks [`Game::find_immediately_resolvable_game`] against the positions one move away from `game`.
+fn check_resolvable_game<T: Game + Eq>(game: &T, children: &[T]) -> Result<(), &'static str> {
+    let wins = |board: &T| board.state() == GameState::Win(game.player().turn());
+    let resolvable = game
+        .find_immediately_resolvable_game()
+        .map_err(|_| "failed to make a move")?;
+
+    match resolvable {
+        Some(board) if !children.contains(&board) => {
+            Err("returned a position that isn't one move away")
+        }
+        Some(board) if board.state() == GameState::Playable => {
+            Err("returned a position that isn't over")
+        }
+        Some(board) if !wins(&board) && children.iter().any(wins) => {
+            Err("missed a win for the player to move")
+        }
+        None if children.iter().any(wins) => Err("missed a win for the player to move"),
+        _ => Ok(()),
+    }
+}
+
+/// Plays `game`, choosing each move by the next byte of `choices` (modulo the number of
+/// possible moves), until the game ends or the choices run out.
+///
+/// Every position along the way is checked with [`check_position`], and the last one is returned.
+pub fn check_playthrough<T>(mut game: T, choices: &[u8]) -> Result<T, InvariantError>
+where
+    T: Game + ParseMove + NotatedGame + Serialize + DeserializeOwned + Eq + Hash,
+    T::Player: TwoPlayer + 'static,
+    T::Move: Display + PartialEq,
+{
+    check_position(&game)?;
+
+    for &choice in choices {
+        if game.state() != GameState::Playable {
+            break;
+        }
+
+        let moves = game.possible_moves().collect::<Vec<_>>();
+        let Some(m) = moves.get(choice as usize % moves.len().max(1)) else {
+            break;
+        };
+
+        // `check_position` already made sure every possible move can be made
+        game.make_move(m)
+            .expect("a checked possible move can be made");
+        check_position(&game)?;
+    }
+
+    Ok(game)
+}
+
+/// Plays the game picked by the first byte of `bytes` from its [standard position](GameKind::standard_position),
+/// choosing moves by the rest (see [`check_playthrough`]).
+///
+/// Returns an error if an invariant doesn't hold.
+pub fn check_any_game(bytes: &[u8]) -> Result<()> {
+    let Some((&kind, choices)) = bytes.split_first() else {
+        return Ok(());
+    };
+
+    let kinds = GameKind::value_variants();
+    let kind = kinds[kind as usize % kinds.len()];
+
+    with_position!(kind.standard_position(), game => {
+        check_playthrough(game, choices)?;
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{naive_nim::Nim, tic_tac_toe::TicTacToe, util::move_natural::NaturalMove};
+
+    #[test]
+    fn every_game_upholds_its_invariants() {
+        for kind in 0..GameKind::value_variants().len() as u8 {
+            for choices in [
+                &[][..],
+                &[0; 64],
+                &[255; 64],
+                &[7, 3, 250, 1, 42, 9, 0, 128, 17, 5],
+            ] {
+                let bytes = [&[kind][..], choices].concat();
+                check_any_game(&bytes).unwrap_or_else(|err| panic!("{bytes:?}: {err}"));
+            }
+        }
+    }
+
+    #[test]
+    fn every_game_ends_consistently() -> Result<()> {
+        for &kind in GameKind::value_variants() {
+            for seed in 0..4u8 {
+                let choices = (0..=255u8)
+                    .map(|i| i.wrapping_mul(37).wrapping_add(seed))
+                    .collect::<Vec<_>>();
+
+                with_position!(kind.standard_position(), game => {
+                    let end = check_playthrough(game, &choices)
+                        .unwrap_or_else(|err| panic!("{kind:?} {seed}: {err}"));
+
+                    assert_ne!(end.state(), GameState::Playable, "{kind:?} {seed}");
+                    assert_eq!(end.winner().is_some(), !end.is_tie(), "{kind:?} {seed}");
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn endings_agree_with_the_solver() {
+        let lost = Nim::new(vec![0, 0]);
+        assert_eq!(lost.winner(), Some(lost.player().previous()));
+        assert_eq!(check_ending(&lost), Ok(()));
+
+        let tied = TicTacToe::from_notation("XOX/XOO/OXX O").unwrap();
+        assert!(tied.is_tie());
+        assert_eq!(check_ending(&tied), Ok(()));
+
+        let won = TicTacToe::from_notation("XXX/OO./... O").unwrap();
+        assert_eq!(won.winner(), Some(won.player().previous()));
+        assert_eq!(check_ending(&won), Ok(()));
+    }
+
+    #[test]
+    fn plays_until_the_game_ends() {
+        let game = check_playthrough(Nim::new(vec![1, 2]), &[0; 10]).unwrap();
+
+        assert_ne!(game.state(), GameState::Playable);
+        assert!(game.move_count() <= 3);
+    }
+
+    #[test]
+    fn resolvable_games_are_one_move_away() {
+        let game = Nim::new(vec![1]);
+        let mut taken = game.clone();
+        taken.make_move(&NaturalMove([0, 1])).unwrap();
+
+        assert_eq!(check_resolvable_game(&game, &[taken]), Ok(()));
+        assert_eq!(
+            check_resolvable_game(&game, &[]),
+            Err("returned a position that isn't one move away")
+        );
+    }
+}
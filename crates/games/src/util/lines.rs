@@ -0,0 +1,187 @@
+//! The lines of a grid, for games won by lining up pieces, so a move can be checked
+//! against the few lines through its cell instead of the whole board.
+
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+/// Every line of a grid, along its axes and diagonals, running from one edge of the grid to the other,
+/// along with the lines through each of its cells.
+///
+/// Cells are numbered in row-major order: the last axis varies fastest.
+///
+/// ```
+/// use games::util::lines::Lines;
+///
+/// let lines = Lines::new(&[3, 3]);
+/// // the middle of a 3x3 board is on its row, its column, and both diagonals
+/// assert_eq!(lines.through(4).count(), 4);
+/// // while a corner is on a diagonal that's just the corner
+/// assert!(lines.through(2).any(|(line, _)| line == [2]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lines {
+    /// The cells of each line, in order.
+    lines: Vec<Vec<usize>>,
+    /// For each cell, the lines through it, and where on each it is.
+    through: Vec<Vec<(usize, usize)>>,
+}
+
+impl Lines {
+    /// The lines of a grid that's `shape[i]` cells long along its `i`th axis.
+    pub fn new(shape: &[usize]) -> Self {
+        let cells = shape.iter().product::<usize>();
+        let mut lines = Vec::new();
+        let mut through = vec![Vec::new(); cells];
+
+        // a cell's place along every axis, or None if it's off the grid
+        let step = |cell: &[usize], direction: &[isize], sign: isize| -> Option<Vec<usize>> {
+            cell.iter()
+                .zip(direction)
+                .zip(shape)
+                .map(|((&place, &step), &length)| {
+                    place
+                        .checked_add_signed(step * sign)
+                        .filter(|&place| place < length)
+                })
+                .collect()
+        };
+        let index = |cell: &[usize]| {
+            cell.iter()
+                .zip(shape)
+                .fold(0, |index, (&place, &length)| index * length + place)
+        };
+        let place = |mut index: usize| {
+            let mut cell = vec![0; shape.len()];
+            for (place, &length) in cell.iter_mut().zip(shape).rev() {
+                *place = index % length;
+                index /= length;
+            }
+            cell
+        };
+
+        // every direction whose first step along any axis is forwards, so each line is only walked one way
+        let directions = (0..3usize.pow(shape.len() as u32))
+            .map(|direction| {
+                (0..shape.len())
+                    .rev()
+                    .map(|axis| (direction / 3usize.pow(axis as u32) % 3) as isize - 1)
+                    .collect::<Vec<_>>()
+            })
+            .filter(|direction| direction.iter().find(|&&step| step != 0) == Some(&1));
+
+        for direction in directions {
+            // every line starts at a cell that can't be stepped back from
+            for start in (0..cells).filter(|&start| step(&place(start), &direction, -1).is_none()) {
+                let mut line = Vec::new();
+                let mut cell = Some(place(start));
+                while let Some(current) = cell {
+                    through[index(&current)].push((lines.len(), line.len()));
+                    line.push(index(&current));
+                    cell = step(&current, &direction, 1);
+                }
+                lines.push(line);
+            }
+        }
+
+        Self { lines, through }
+    }
+
+    /// The lines of a grid of `shape`, which are only found the first time they're asked for on each thread,
+    /// as games that check every move against them can't afford to find them again at every position.
+    pub fn with<R>(shape: &[usize], f: impl FnOnce(&Lines) -> R) -> R {
+        thread_local! {
+            static LINES: RefCell<HashMap<Vec<usize>, Rc<Lines>>> = RefCell::new(HashMap::new());
+        }
+
+        let lines = LINES.with(|lines| {
+            let mut lines = lines.borrow_mut();
+            match lines.get(shape) {
+                Some(found) => Rc::clone(found),
+                None => Rc::clone(
+                    lines
+                        .entry(shape.to_vec())
+                        .or_insert_with(|| Rc::new(Lines::new(shape))),
+                ),
+            }
+        });
+
+        f(&lines)
+    }
+
+    /// The lines through `cell`, each with the place of `cell` on it.
+    pub fn through(&self, cell: usize) -> impl Iterator<Item = (&[usize], usize)> {
+        self.through[cell]
+            .iter()
+            .map(|&(line, at)| (self.lines[line].as_slice(), at))
+    }
+}
+
+/// The length of the run of cells on `line` through `line[at]` that `same` holds for,
+/// counting `line[at]` itself whether it holds for it or not, as a piece about to be placed there would.
+///
+/// ```
+/// use games::util::lines::run_through;
+///
+/// let pieces = ['X', 'X', '.', 'X', 'O'];
+/// assert_eq!(run_through(&[0, 1, 2, 3, 4], 2, |cell| pieces[cell] == 'X'), 4);
+/// ```
+pub fn run_through(line: &[usize], at: usize, same: impl Fn(usize) -> bool) -> usize {
+    let before = line[..at]
+        .iter()
+        .rev()
+        .take_while(|&&cell| same(cell))
+        .count();
+    let after = line[at + 1..]
+        .iter()
+        .take_while(|&&cell| same(cell))
+        .count();
+
+    before + 1 + after
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn count_full(lines: &Lines, length: usize) -> usize {
+        lines
+            .lines
+            .iter()
+            .filter(|line| line.len() == length)
+            .count()
+    }
+
+    #[test]
+    fn grids_have_every_line() {
+        // 3 rows, 3 columns, and 5 diagonals each way, 2 of which go corner to corner
+        let square = Lines::new(&[3, 3]);
+        assert_eq!(square.lines.len(), 16);
+        assert_eq!(count_full(&square, 3), 8);
+
+        // the 49 lines of 3x3x3 Tic-Tac-Toe
+        assert_eq!(count_full(&Lines::new(&[3, 3, 3]), 3), 49);
+        assert_eq!(
+            count_full(&Lines::new(&[4, 4, 4, 4]), 4),
+            (6usize.pow(4) - 4usize.pow(4)) / 2
+        );
+
+        // rows of 4 are the longest lines on a 2x4 board
+        let wide = Lines::new(&[2, 4]);
+        assert_eq!(count_full(&wide, 4), 2);
+        assert!(wide
+            .through(5)
+            .any(|(line, at)| line == [4, 5, 6, 7] && at == 1));
+        assert!(wide.through(5).any(|(line, at)| line == [0, 5] && at == 1));
+        assert!(wide.through(5).any(|(line, at)| line == [2, 5] && at == 1));
+    }
+
+    #[test]
+    fn every_cell_is_on_its_lines() {
+        let lines = Lines::new(&[3, 4, 2]);
+        for cell in 0..24 {
+            // one line along each of the 13 directions
+            assert_eq!(lines.through(cell).count(), 13);
+            assert!(lines.through(cell).all(|(line, at)| line[at] == cell));
+        }
+        assert_eq!(Lines::with(&[3, 4, 2], Lines::clone), lines);
+    }
+}
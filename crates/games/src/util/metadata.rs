@@ -0,0 +1,32 @@
+//! What each game is and how it's set up, for listing and describing the games.
+//!
+//! Every game module has a `METADATA` constant, which [`GameKind::metadata`](crate::GameKind::metadata) returns,
+//! so the CLI, the TUI, and the serve protocol describe the games the same way.
+
+/// A parameter a game is set up with on the CLI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parameter {
+    /// The parameter as it's written on the CLI, e.g. `<SIZE>` or `--width <WIDTH>`.
+    pub name: &'static str,
+    pub description: &'static str,
+    /// The value it takes when it isn't given.
+    pub default: &'static str,
+    /// The values it can take.
+    pub range: &'static str,
+}
+
+/// What a game is, and how to set it up and play it from the CLI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameMetadata {
+    /// The name of the game, e.g. `Tic Tac Toe`.
+    pub name: &'static str,
+    /// A one-line description of the game.
+    pub summary: &'static str,
+    /// The full rules, in Markdown.
+    pub rules: &'static str,
+    pub parameters: &'static [Parameter],
+    /// How moves are written, with an example.
+    pub move_notation: &'static str,
+    /// The arguments of an example invocation, starting with the game's CLI name.
+    pub example: &'static str,
+}
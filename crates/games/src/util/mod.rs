@@ -1,5 +1,18 @@
+pub mod bench;
+pub mod bitboard;
 pub mod cli;
 #[cfg(feature = "egui")]
 pub mod gui;
+pub mod invariants;
+pub mod lines;
+pub mod metadata;
 pub mod move_natural;
 pub mod move_score;
+pub mod notation;
+pub mod parse;
+pub mod position;
+pub mod record;
+pub mod render;
+pub mod resolve;
+pub mod scan;
+pub mod symmetry;
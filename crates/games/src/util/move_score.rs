@@ -31,3 +31,125 @@ pub fn best_move_score_testing<T: Game + std::fmt::Debug>(
 ) -> (T::Move, isize) {
     best_move_score(move_scores).unwrap().unwrap()
 }
+
+/// The optimal moves of `game` by brute force, from the scores of every move,
+/// in the order of `possible_moves`.
+#[cfg(test)]
+pub fn optimal_moves_testing<
+    T: Game<Player = impl game_solver::player::TwoPlayer + 'static>
+        + Eq
+        + std::hash::Hash
+        + std::fmt::Debug,
+>(
+    game: &T,
+) -> Vec<T::Move> {
+    let scores = game_solver::move_scores(game, &mut std::collections::HashMap::new(), None, &None)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    let best = scores.iter().map(|(_, score)| *score).max();
+
+    scores
+        .into_iter()
+        .filter(|(_, score)| Some(*score) == best)
+        .map(|(m, _)| m)
+        .collect()
+}
+
+/// Checks that [`solve_outcome`](game_solver::solve_outcome), which finds who wins an impartial game from its Grundy value,
+/// agrees with the score of negamax on `samples` random positions, up to `max_plies` moves after `start`.
+#[cfg(test)]
+pub fn impartial_outcomes_testing<
+    T: Game<Player = game_solver::player::ImpartialPlayer> + Eq + std::hash::Hash + std::fmt::Debug,
+>(
+    start: &T,
+    max_plies: usize,
+    samples: usize,
+) {
+    use game_solver::{random::random_position, solve, solve_outcome, strategy::Outcome};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    assert!(T::IMPARTIAL);
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut table = std::collections::HashMap::new();
+    for _ in 0..samples {
+        let plies = rng.gen_range(0..=max_plies);
+        let (position, _) = random_position(start, plies, &mut rng).unwrap();
+
+        let outcome = solve_outcome(&position, &mut table, None, &None).unwrap();
+        let score = solve(&position, &mut table, None, &None).unwrap();
+        assert_eq!(outcome, Outcome::from_score(score), "{position:?}");
+    }
+}
+
+/// Checks that [`evaluate_window`](game_solver::evaluate_window) agrees with the score of [`solve`](game_solver::solve)
+/// on `samples` random positions, up to `max_plies` moves after `start`, with windows around, at, and away from it,
+/// all searched with one table.
+#[cfg(test)]
+pub fn window_results_testing<
+    T: Game<Player = impl game_solver::player::TwoPlayer + 'static>
+        + Eq
+        + std::hash::Hash
+        + std::fmt::Debug,
+>(
+    start: &T,
+    max_plies: usize,
+    samples: usize,
+) {
+    use game_solver::{
+        evaluate_window, game::upper_bound, random::random_position, solve, WindowResult,
+    };
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut table = std::collections::HashMap::new();
+    for _ in 0..samples {
+        let plies = rng.gen_range(0..=max_plies);
+        let Ok((position, _)) = random_position(start, plies, &mut rng) else {
+            continue;
+        };
+
+        let score = solve(
+            &position,
+            &mut std::collections::HashMap::new(),
+            None,
+            &None,
+        )
+        .unwrap();
+        let bound = upper_bound(&position) + 1;
+        let random = rng.gen_range(-bound..bound);
+
+        for (alpha, beta) in [
+            (-1, 1),
+            (-bound, bound),
+            (score - 1, score + 1),
+            (score, score + 1),
+            (score - 1, score),
+            (random, random + 1),
+            (random.min(score - 2), score - 1),
+        ] {
+            if alpha < -bound || beta > bound || alpha >= beta {
+                continue;
+            }
+
+            match evaluate_window(&position, &mut table, alpha, beta, None, &None).unwrap() {
+                WindowResult::Exact(exact) => assert_eq!(exact, score, "{position:?}"),
+                WindowResult::AtMost(max) => {
+                    assert!(
+                        score <= max && max <= alpha,
+                        "{position:?} in {alpha}..{beta}"
+                    )
+                }
+                WindowResult::AtLeast(min) => {
+                    assert!(
+                        score >= min && min >= beta,
+                        "{position:?} in {alpha}..{beta}"
+                    )
+                }
+            }
+        }
+
+        assert!(evaluate_window(&position, &mut table, score, score, None, &None).is_err());
+        assert!(evaluate_window(&position, &mut table, -bound - 1, 0, None, &None).is_err());
+    }
+}
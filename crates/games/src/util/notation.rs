@@ -0,0 +1,439 @@
+//! Compact, single-line notations for positions, in the spirit of FEN in chess.
+//!
+//! A notation is made of fields separated by whitespace, such as a board and the player to move.
+//! Boards are written row by row, with rows separated by `/`, so Tic-Tac-Toe is written as
+//! `XOX/.O./..X O`: its three rows, followed by the player to move.
+//!
+//! Errors point at the part of the notation that's wrong, by its byte range.
+
+use std::{fmt, ops::Range, str::FromStr};
+
+use clap::ValueEnum;
+use game_solver::player::PartizanPlayer;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// A game whose positions can be written in, and read back from, a single-line notation.
+pub trait NotatedGame: Sized {
+    /// Writes the position in this game's notation.
+    fn to_notation(&self) -> String;
+
+    /// Reads a position in this game's notation, checking that it can be reached.
+    fn from_notation(notation: &str) -> Result<Self, NotationError>;
+}
+
+/// An error in a notation, pointing at the part of it that's wrong.
+///
+/// Its message is followed by the notation, with the offending part underlined:
+///
+/// ```txt
+/// `Z` isn't a square: expected one of `XO.`
+///   XOX/.Z./..X X
+///        ^
+/// ```
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub struct NotationError {
+    notation: String,
+    span: Range<usize>,
+    message: String,
+}
+
+impl NotationError {
+    /// Creates an error about the bytes of `notation` in `span`.
+    pub fn new(notation: &str, span: Range<usize>, message: impl Into<String>) -> Self {
+        Self {
+            notation: notation.to_string(),
+            span,
+            message: message.into(),
+        }
+    }
+
+    /// The byte range of the notation the error is about.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// What's wrong, without the notation.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // tabs and other whitespace would misalign the marker
+        let notation = self.notation.replace(char::is_whitespace, " ");
+        let offset = self.notation[..self.span.start].chars().count();
+        let width = self.notation[self.span.clone()].chars().count().max(1);
+
+        writeln!(f, "{}", self.message)?;
+        writeln!(f, "  {notation}")?;
+        write!(f, "  {}{}", " ".repeat(offset), "^".repeat(width))
+    }
+}
+
+/// A part of a notation, such as a field or a row of a board, which knows where it is
+/// so errors about it can point at it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field<'a> {
+    notation: &'a str,
+    span: Range<usize>,
+}
+
+impl<'a> Field<'a> {
+    /// The whole notation, for errors that aren't about any one part of it.
+    pub fn whole(notation: &'a str) -> Self {
+        Self {
+            notation,
+            span: 0..notation.len(),
+        }
+    }
+
+    pub fn text(&self) -> &'a str {
+        &self.notation[self.span.clone()]
+    }
+
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Creates an error pointing at this part of the notation.
+    pub fn error(&self, message: impl Into<String>) -> NotationError {
+        NotationError::new(self.notation, self.span(), message)
+    }
+
+    /// Splits this part on `separator`, keeping empty parts.
+    pub fn split(&self, separator: char) -> Vec<Field<'a>> {
+        let mut start = self.span.start;
+
+        self.text()
+            .split(separator)
+            .map(|part| {
+                let field = Self {
+                    notation: self.notation,
+                    span: start..start + part.len(),
+                };
+                start += part.len() + separator.len_utf8();
+                field
+            })
+            .collect()
+    }
+
+    /// Removes the `prefix` that marks what this field is, e.g. the `m` of a move count like `m2`.
+    pub fn strip_prefix(&self, prefix: char, name: &str) -> Result<Field<'a>, NotationError> {
+        if !self.text().starts_with(prefix) {
+            return Err(self.error(format!(
+                "expected the {name}, starting with `{prefix}`, but found `{}`",
+                self.text()
+            )));
+        }
+
+        Ok(Self {
+            notation: self.notation,
+            span: self.span.start + prefix.len_utf8()..self.span.end,
+        })
+    }
+
+    /// Parses this part as the number `name`.
+    pub fn number<N: FromStr>(&self, name: &str) -> Result<N, NotationError> {
+        // `FromStr` allows a leading `+`, which isn't part of any notation
+        if self.text().starts_with('+') {
+            return Err(self.error(format!("expected the {name}, but found `{}`", self.text())));
+        }
+
+        self.text().parse().map_err(|_| {
+            if self.text().is_empty() {
+                self.error(format!("expected the {name}"))
+            } else {
+                self.error(format!(
+                    "expected the {name}, but `{}` isn't a valid number",
+                    self.text()
+                ))
+            }
+        })
+    }
+
+    /// Reads this part as one of `choices`, each of which is written as its name, e.g. `X` or `O`.
+    pub fn choice<T: Copy>(&self, name: &str, choices: &[(&str, T)]) -> Result<T, NotationError> {
+        choices
+            .iter()
+            .find(|(text, _)| *text == self.text())
+            .map(|&(_, value)| value)
+            .ok_or_else(|| {
+                let names = choices
+                    .iter()
+                    .map(|(text, _)| format!("`{text}`"))
+                    .collect::<Vec<_>>();
+
+                self.error(format!(
+                    "expected the {name}, one of {}, but found `{}`",
+                    names.join(", "),
+                    self.text()
+                ))
+            })
+    }
+
+    /// Reads a board of rows separated by `/`, made of the characters in `squares`.
+    ///
+    /// If `size` (a width and height) is given, the board must be that size.
+    /// Otherwise, every row must be as long as the first.
+    pub fn board(
+        &self,
+        squares: &'static str,
+        size: Option<(usize, usize)>,
+    ) -> Result<Vec<Vec<char>>, NotationError> {
+        let rows = self.split('/');
+
+        if let Some((_, height)) = size {
+            if rows.len() != height {
+                return Err(self.error(format!(
+                    "expected {height} rows separated by `/`, but found {}",
+                    rows.len()
+                )));
+            }
+        }
+
+        let width = match size {
+            Some((width, _)) => width,
+            None if rows[0].text().is_empty() => {
+                return Err(rows[0].error("expected the board's first row"))
+            }
+            None => rows[0].text().chars().count(),
+        };
+
+        rows.iter()
+            .enumerate()
+            .map(|(index, row)| {
+                let mut offset = row.span.start;
+                let line = row
+                    .text()
+                    .chars()
+                    .map(|square| {
+                        let span = offset..offset + square.len_utf8();
+                        offset = span.end;
+
+                        if squares.contains(square) {
+                            Ok(square)
+                        } else {
+                            Err(NotationError::new(
+                                self.notation,
+                                span,
+                                format!("`{square}` isn't a square: expected one of `{squares}`"),
+                            ))
+                        }
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                if line.len() != width {
+                    return Err(row.error(format!(
+                        "expected row {} to have {width} squares, but it has {}",
+                        index + 1,
+                        line.len()
+                    )));
+                }
+
+                Ok(line)
+            })
+            .collect()
+    }
+}
+
+/// The player to move on a board given without the moves that led to it.
+#[derive(ValueEnum, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ToMove {
+    Left,
+    Right,
+}
+
+impl From<ToMove> for PartizanPlayer {
+    fn from(to_move: ToMove) -> Self {
+        match to_move {
+            ToMove::Left => PartizanPlayer::Left,
+            ToMove::Right => PartizanPlayer::Right,
+        }
+    }
+}
+
+/// Reads a position from its board alone (e.g. `XO./.X./..O`), with its rows separated by `/` or on lines of their own.
+///
+/// `read` is given the board with its rows separated by `/`, as the notation writes it,
+/// to read the position from it along with whatever else follows from the board, such as the player to move.
+/// Errors it gives about anything past the board point at the whole board.
+pub fn read_board<T>(
+    board: &str,
+    read: impl FnOnce(&str) -> Result<T, NotationError>,
+) -> Result<T, NotationError> {
+    let rows = board
+        .trim()
+        .lines()
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join("/");
+
+    read(&rows).map_err(|err| {
+        let span = if err.span().end <= rows.len() {
+            err.span()
+        } else {
+            0..rows.len()
+        };
+
+        NotationError::new(&rows, span, err.message())
+    })
+}
+
+/// Splits a notation into its whitespace-separated fields.
+pub fn split_fields(notation: &str) -> Vec<Field<'_>> {
+    let mut fields = Vec::new();
+    let mut start = None;
+
+    for (index, ch) in notation.char_indices().chain([(notation.len(), ' ')]) {
+        match (start, ch.is_whitespace()) {
+            (None, false) => start = Some(index),
+            (Some(from), true) => {
+                fields.push(Field {
+                    notation,
+                    span: from..index,
+                });
+                start = None;
+            }
+            _ => (),
+        }
+    }
+
+    fields
+}
+
+/// Splits a notation into the fields named in `required`, followed by up to
+/// as many fields as are named in `optional`.
+pub fn fields<'a>(
+    notation: &'a str,
+    required: &[&str],
+    optional: &[&str],
+) -> Result<Vec<Field<'a>>, NotationError> {
+    let fields = split_fields(notation);
+
+    if let Some(missing) = required.get(fields.len()) {
+        let end = notation.trim_end().len();
+        let message = match fields.len().checked_sub(1).map(|last| required[last]) {
+            Some(previous) => format!("expected the {missing} after the {previous}"),
+            None => format!("expected the {missing}"),
+        };
+
+        return Err(NotationError::new(notation, end..end, message));
+    }
+
+    let names = required.len() + optional.len();
+    if fields.len() > names {
+        let last = [required, optional].concat()[names - 1];
+        let extra = fields[names].span.start..fields[fields.len() - 1].span.end;
+
+        return Err(NotationError::new(
+            notation,
+            extra.clone(),
+            format!(
+                "unexpected `{}`: the notation ends with the {last}",
+                &notation[extra]
+            ),
+        ));
+    }
+
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_fields_with_their_spans() {
+        let spans = split_fields("  3,5,7 \t m2 ")
+            .iter()
+            .map(|field| (field.text(), field.span()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(spans, vec![("3,5,7", 2..7), ("m2", 10..12)]);
+    }
+
+    #[test]
+    fn rejects_missing_and_extra_fields() {
+        let missing = fields("XOX/.O./..X", &["board", "player to move"], &[]).unwrap_err();
+        assert_eq!(missing.span(), 11..11);
+        assert_eq!(
+            missing.message(),
+            "expected the player to move after the board"
+        );
+
+        let extra = fields("3,5,7 m0 s15 x y", &["heaps", "move count"], &["start"]).unwrap_err();
+        assert_eq!(extra.span(), 13..16);
+
+        assert_eq!(fields("  ", &["heaps"], &[]).unwrap_err().span(), 0..0);
+    }
+
+    #[test]
+    fn points_at_bad_squares() {
+        let field = Field::whole("XOX/.Z./..X");
+
+        let error = field.board("XO.", None).unwrap_err();
+        assert_eq!(error.span(), 5..6);
+        assert_eq!(
+            error.to_string(),
+            "`Z` isn't a square: expected one of `XO.`\n  XOX/.Z./..X\n       ^"
+        );
+
+        assert_eq!(
+            Field::whole("XOX/.O/..X")
+                .board("XO.", None)
+                .unwrap_err()
+                .span(),
+            4..6
+        );
+        assert_eq!(
+            Field::whole("XOX/.O./..X")
+                .board("XO.", Some((3, 4)))
+                .unwrap_err()
+                .span(),
+            0..11
+        );
+    }
+
+    #[test]
+    fn boards_are_read_from_rows_on_their_own_lines() {
+        let read = |board| {
+            read_board(board, |rows| {
+                Field::whole(rows).board("XO.", None)?;
+                Err::<(), _>(NotationError::new(
+                    rows,
+                    rows.len()..rows.len() + 2,
+                    "past the board",
+                ))
+            })
+            .unwrap_err()
+        };
+
+        let error = read("XO.\n.X.\r\n..O\n");
+        assert_eq!(error.message(), "past the board");
+        assert_eq!(error.span(), 0..11);
+        assert!(error.to_string().contains("  XO./.X./..O\n"), "{error}");
+
+        assert_eq!(read("XO./.Z./..O").span(), 5..6);
+    }
+
+    #[test]
+    fn parses_prefixed_numbers() {
+        let field = Field::whole("m12");
+        let count = field.strip_prefix('m', "move count").unwrap();
+
+        assert_eq!(count.number::<usize>("move count"), Ok(12));
+        assert_eq!(count.span(), 1..3);
+        assert!(field.strip_prefix('s', "start").is_err());
+        assert_eq!(
+            Field::whole("m1x")
+                .strip_prefix('m', "move count")
+                .unwrap()
+                .number::<usize>("move count")
+                .unwrap_err()
+                .span(),
+            1..3
+        );
+    }
+}
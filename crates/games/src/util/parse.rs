@@ -0,0 +1,286 @@
+//! Shared parsers for the move notation accepted by the CLIs.
+//!
+//! Coordinates can be written in two styles:
+//!
+//! - numeric, as `x-y` (zero-indexed), e.g. `2-3`
+//! - algebraic, as a column letter followed by a one-indexed row, e.g. `c4`
+//!
+//! Both styles describe the same square: `c4` and `2-3` are equivalent.
+
+use std::iter;
+
+use game_solver::game::Game;
+use itertools::Itertools;
+use thiserror::Error;
+
+/// The expected format of a coordinate, used in error messages.
+pub const COORD_FORMAT: &str = "a coordinate such as `c4` or `2-3`";
+
+/// An error that occurs when parsing user-provided notation.
+///
+/// Every variant names the offending token, as well as what was expected in its place.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    #[error("could not parse `{token}`: expected {expected}")]
+    Malformed { token: String, expected: String },
+    #[error("`{token}` is out of range: expected {expected}")]
+    OutOfRange { token: String, expected: String },
+}
+
+impl ParseError {
+    /// Creates an error for a token that doesn't match the `expected` format.
+    pub fn malformed(token: &str, expected: impl Into<String>) -> Self {
+        Self::Malformed {
+            token: token.to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Creates an error for a well-formed token whose value isn't within the `expected` range.
+    pub fn out_of_range(token: &str, expected: impl Into<String>) -> Self {
+        Self::OutOfRange {
+            token: token.to_string(),
+            expected: expected.into(),
+        }
+    }
+}
+
+/// A game whose moves can be read from user input, such as the interactive CLI.
+pub trait ParseMove: Game {
+    /// Parses a move in this game's notation, checking it against this position
+    /// (e.g. the size of the board), but not whether the move is legal.
+    fn parse_move(&self, token: &str) -> Result<Self::Move, ParseError>;
+}
+
+/// Parses a single non-negative number.
+pub fn parse_number(token: &str) -> Result<usize, ParseError> {
+    token
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| ParseError::malformed(token, "a non-negative number"))
+}
+
+/// Parses exactly `count` numbers separated by hyphens, e.g. `1-2-3` for a count of 3.
+pub fn parse_numbers(token: &str, count: usize) -> Result<Vec<usize>, ParseError> {
+    let expected = || {
+        format!(
+            "{} number{} separated by a hyphen ({})",
+            count,
+            if count == 1 { "" } else { "s" },
+            iter::repeat("x").take(count).join("-")
+        )
+    };
+
+    let parts = token.trim().split('-').collect::<Vec<_>>();
+
+    if parts.len() != count {
+        return Err(ParseError::malformed(token, expected()));
+    }
+
+    parts
+        .iter()
+        .map(|part| part.parse::<usize>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| ParseError::malformed(token, expected()))
+}
+
+/// Converts an algebraic column name to its zero-indexed column,
+/// where `a` is 0, `z` is 25, `aa` is 26, and so on.
+///
+/// Column letters are case-insensitive.
+pub fn parse_column(token: &str) -> Result<usize, ParseError> {
+    if token.is_empty() || !token.chars().all(|ch| ch.is_ascii_alphabetic()) {
+        return Err(ParseError::malformed(token, "a column letter such as `a`"));
+    }
+
+    token
+        .chars()
+        .try_fold(0usize, |acc, ch| {
+            let value = (ch.to_ascii_lowercase() as u8 - b'a') as usize + 1;
+            acc.checked_mul(26)?.checked_add(value)
+        })
+        .map(|column| column - 1)
+        .ok_or_else(|| ParseError::out_of_range(token, "a column that fits in memory"))
+}
+
+/// Converts a zero-indexed column to its algebraic name: the inverse of [`parse_column`].
+pub fn column_name(column: usize) -> String {
+    let mut name = Vec::new();
+    let mut remaining = column + 1;
+
+    while remaining > 0 {
+        remaining -= 1;
+        name.push((b'a' + (remaining % 26) as u8) as char);
+        remaining /= 26;
+    }
+
+    name.iter().rev().collect()
+}
+
+/// Parses a coordinate in either the numeric (`2-3`) or algebraic (`c4`) style,
+/// returning it as `(x, y)` without checking it against any board size.
+pub fn parse_coord_unbounded(token: &str) -> Result<(usize, usize), ParseError> {
+    let trimmed = token.trim();
+
+    if trimmed.starts_with(|ch: char| ch.is_ascii_alphabetic()) {
+        let split = trimmed
+            .find(|ch: char| !ch.is_ascii_alphabetic())
+            .ok_or_else(|| ParseError::malformed(token, COORD_FORMAT))?;
+        let (column, row) = trimmed.split_at(split);
+
+        let x = parse_column(column).map_err(|_| ParseError::malformed(token, COORD_FORMAT))?;
+        let y = row
+            .parse::<usize>()
+            .ok()
+            .and_then(|row| row.checked_sub(1))
+            .ok_or_else(|| ParseError::malformed(token, COORD_FORMAT))?;
+
+        Ok((x, y))
+    } else {
+        let numbers =
+            parse_numbers(trimmed, 2).map_err(|_| ParseError::malformed(token, COORD_FORMAT))?;
+
+        Ok((numbers[0], numbers[1]))
+    }
+}
+
+/// Parses a coordinate in either the numeric (`2-3`) or algebraic (`c4`) style,
+/// returning it as `(x, y)` if it lies on a board of the given dimensions.
+pub fn parse_coord(token: &str, width: usize, height: usize) -> Result<(usize, usize), ParseError> {
+    let (x, y) = parse_coord_unbounded(token)?;
+
+    if x >= width || y >= height {
+        return Err(ParseError::out_of_range(
+            token,
+            format!(
+                "a square on a {width}x{height} board (`a1` to `{}{height}`, or `0-0` to `{}-{}`)",
+                column_name(width.saturating_sub(1)),
+                width.saturating_sub(1),
+                height.saturating_sub(1)
+            ),
+        ));
+    }
+
+    Ok((x, y))
+}
+
+/// Parses a list of moves separated by whitespace and/or commas,
+/// using `parse` for every individual move.
+///
+/// The first invalid move is reported.
+pub fn parse_move_list<T>(
+    input: &str,
+    parse: impl Fn(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .split(|ch: char| ch == ',' || ch.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .map(parse)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_coord() {
+        assert_eq!(parse_coord("2-3", 6, 6), Ok((2, 3)));
+        assert_eq!(parse_coord("0-0", 6, 6), Ok((0, 0)));
+        assert_eq!(parse_coord(" 5-5 ", 6, 6), Ok((5, 5)));
+    }
+
+    #[test]
+    fn algebraic_coord() {
+        assert_eq!(parse_coord("c4", 6, 6), Ok((2, 3)));
+        assert_eq!(parse_coord("A1", 6, 6), Ok((0, 0)));
+        assert_eq!(parse_coord("f6", 6, 6), Ok((5, 5)));
+    }
+
+    #[test]
+    fn both_styles_agree() {
+        for x in 0..8 {
+            for y in 0..8 {
+                let algebraic = format!("{}{}", column_name(x), y + 1);
+                let numeric = format!("{x}-{y}");
+                assert_eq!(parse_coord(&algebraic, 8, 8), parse_coord(&numeric, 8, 8));
+            }
+        }
+    }
+
+    #[test]
+    fn out_of_range_coord() {
+        assert!(matches!(
+            parse_coord("6-0", 6, 6),
+            Err(ParseError::OutOfRange { token, .. }) if token == "6-0"
+        ));
+        assert!(matches!(
+            parse_coord("g1", 6, 6),
+            Err(ParseError::OutOfRange { token, .. }) if token == "g1"
+        ));
+        assert!(matches!(
+            parse_coord("a7", 6, 6),
+            Err(ParseError::OutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn malformed_coord() {
+        for token in [
+            "", "-", "2-", "-3", "2-3-4", "x-y", "c", "c0", "4c", "c4d", "2 3",
+        ] {
+            assert!(
+                matches!(
+                    parse_coord(token, 6, 6),
+                    Err(ParseError::Malformed { token: ref found, .. }) if found == token
+                ),
+                "{token:?} should be malformed"
+            );
+        }
+    }
+
+    #[test]
+    fn error_names_token_and_format() {
+        let message = parse_coord("zz", 6, 6).unwrap_err().to_string();
+        assert!(message.contains("`zz`"));
+        assert!(message.contains(COORD_FORMAT));
+    }
+
+    #[test]
+    fn columns() {
+        assert_eq!(parse_column("a"), Ok(0));
+        assert_eq!(parse_column("Z"), Ok(25));
+        assert_eq!(parse_column("aa"), Ok(26));
+        assert_eq!(parse_column("ab"), Ok(27));
+        assert!(parse_column("").is_err());
+        assert!(parse_column("a1").is_err());
+
+        for column in 0..1000 {
+            assert_eq!(parse_column(&column_name(column)), Ok(column));
+        }
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(parse_numbers("1-2-3", 3), Ok(vec![1, 2, 3]));
+        assert_eq!(parse_numbers("7", 1), Ok(vec![7]));
+        assert!(parse_numbers("1-2", 3).is_err());
+        assert!(parse_numbers("1-b-3", 3).is_err());
+        assert_eq!(parse_number("12"), Ok(12));
+        assert!(parse_number("-1").is_err());
+    }
+
+    #[test]
+    fn move_list() {
+        assert_eq!(
+            parse_move_list("c4 2-3,a1", |token| parse_coord(token, 6, 6)),
+            Ok(vec![(2, 3), (2, 3), (0, 0)])
+        );
+        assert_eq!(parse_move_list("3,5,7", parse_number), Ok(vec![3, 5, 7]));
+        assert_eq!(parse_move_list("", parse_number), Ok(vec![]));
+        assert!(matches!(
+            parse_move_list("3,x,7", parse_number),
+            Err(ParseError::Malformed { token, .. }) if token == "x"
+        ));
+    }
+}
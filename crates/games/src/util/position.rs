@@ -0,0 +1,120 @@
+//! Shared validation for positions read back from their serialized form.
+//!
+//! Boards are serialized as rows of characters, one character per square,
+//! so every game checks the shape and characters of its board the same way
+//! before checking its own invariants.
+
+use thiserror::Error;
+
+/// An error that occurs when a serialized position can't be reached in its game.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum PositionError {
+    #[error("expected the board to have {expected} rows, but it has {found}")]
+    RowCount { expected: usize, found: usize },
+    #[error("expected row {row} of the board to have {expected} squares, but it has {found}")]
+    RowLength {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("`{square}` in row {row} of the board isn't a square: expected one of `{expected}`")]
+    UnknownSquare {
+        row: usize,
+        square: char,
+        expected: &'static str,
+    },
+    #[error("impossible position: {0}")]
+    Impossible(String),
+}
+
+impl PositionError {
+    /// Creates an error for a position that breaks one of its game's rules.
+    pub fn impossible(reason: impl Into<String>) -> Self {
+        Self::Impossible(reason.into())
+    }
+}
+
+/// Checks that `rows` form a `width` by `height` board made of the characters in `squares`,
+/// returning its squares, indexed by row and then column.
+pub fn parse_board(
+    rows: &[String],
+    width: usize,
+    height: usize,
+    squares: &'static str,
+) -> Result<Vec<Vec<char>>, PositionError> {
+    if rows.len() != height {
+        return Err(PositionError::RowCount {
+            expected: height,
+            found: rows.len(),
+        });
+    }
+
+    rows.iter()
+        .enumerate()
+        .map(|(row, line)| {
+            let line = line.chars().collect::<Vec<_>>();
+
+            if line.len() != width {
+                return Err(PositionError::RowLength {
+                    row,
+                    expected: width,
+                    found: line.len(),
+                });
+            }
+
+            if let Some(&square) = line.iter().find(|square| !squares.contains(**square)) {
+                return Err(PositionError::UnknownSquare {
+                    row,
+                    square,
+                    expected: squares,
+                });
+            }
+
+            Ok(line)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(rows: &[&str]) -> Vec<String> {
+        rows.iter().map(|row| row.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_a_board() {
+        assert_eq!(
+            parse_board(&rows(&["X-", "-O"]), 2, 2, "XO-"),
+            Ok(vec![vec!['X', '-'], vec!['-', 'O']])
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_boards() {
+        assert_eq!(
+            parse_board(&rows(&["X-"]), 2, 2, "XO-"),
+            Err(PositionError::RowCount {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            parse_board(&rows(&["X-", "-"]), 2, 2, "XO-"),
+            Err(PositionError::RowLength {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            parse_board(&rows(&["X-", "-Z"]), 2, 2, "XO-"),
+            Err(PositionError::UnknownSquare {
+                row: 1,
+                square: 'Z',
+                expected: "XO-"
+            })
+        );
+    }
+}
@@ -0,0 +1,344 @@
+//! Game records, for saving a game and loading it back.
+//!
+//! A record is a header line naming the game (as the CLI does) and the position it started from
+//! (in its [notation](super::notation)), followed by the moves played from there, separated by whitespace:
+//!
+//! ```txt
+//! naive-nim 3,5 m0
+//! 1-2 0-3 1-3
+//! ```
+//!
+//! Moves are written in the notation the CLI reads them in, as [`MoveNotation`] does.
+//! A move written with whitespace (or a `"`) in it is put in quotes, with `\"` and `\\` standing for `"` and `\`.
+
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use clap::ValueEnum;
+use game_solver::history::History;
+use thiserror::Error;
+
+use crate::{GameKind, GamePosition};
+
+use super::{
+    notation::{NotatedGame, NotationError},
+    parse::{ParseError, ParseMove},
+};
+
+/// A game whose moves can be written down, and read back from what was written,
+/// in the position they're played in.
+///
+/// Every game that reads its moves ([`ParseMove`]) in the notation it [displays](Display) them in has one.
+pub trait MoveNotation: ParseMove {
+    /// Writes a move of this position.
+    fn write_move(&self, m: &Self::Move) -> String;
+
+    /// Reads a move of this position, as [`MoveNotation::write_move`] writes it.
+    fn read_move(&self, token: &str) -> Result<Self::Move, ParseError> {
+        self.parse_move(token)
+    }
+}
+
+impl<T: ParseMove> MoveNotation for T
+where
+    T::Move: Display,
+{
+    fn write_move(&self, m: &Self::Move) -> String {
+        m.to_string()
+    }
+}
+
+/// An error in a record, or in replaying it.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum RecordError {
+    #[error("the record is empty: expected a header naming the game and its starting position")]
+    Empty,
+    #[error("`{0}` isn't a game")]
+    UnknownGame(String),
+    #[error("the record is of {found}, not {expected}")]
+    WrongGame {
+        expected: &'static str,
+        found: &'static str,
+    },
+    #[error("the starting position can't be read: {0}")]
+    Position(NotationError),
+    #[error("a quoted move is never closed")]
+    UnclosedQuote,
+    #[error("move {index} (`{token}`) can't be played: {error}")]
+    Move {
+        /// The place of the move in the record, starting from 1.
+        index: usize,
+        token: String,
+        error: String,
+    },
+}
+
+/// A game as it's recorded: the game, the position it started from, and the moves played from there.
+///
+/// Reading a record only splits it into its parts: [`GameRecord::replay`] plays its moves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameRecord {
+    pub game: GameKind,
+    /// The starting position, in the game's notation.
+    pub position: String,
+    /// The moves played from the starting position, in order.
+    pub moves: Vec<String>,
+}
+
+impl GameRecord {
+    /// The record of the moves played in `history`, from where it started.
+    pub fn new<T>(history: &History<T>) -> Self
+    where
+        T: MoveNotation + NotatedGame,
+        GamePosition: From<T>,
+    {
+        let moves = history
+            .moves()
+            .iter()
+            .enumerate()
+            .map(|(ply, m)| {
+                history
+                    .position_at(ply)
+                    .expect("every move is played from a position of the history")
+                    .write_move(m)
+            })
+            .collect();
+
+        Self {
+            game: GamePosition::from(history.start().clone()).kind(),
+            position: history.start().to_notation(),
+            moves,
+        }
+    }
+
+    /// Plays the moves of the record from its starting position.
+    ///
+    /// Every move is checked to be possible before it's made, so it fails instead of panicking on illegal moves.
+    pub fn replay<T>(&self) -> Result<History<T>, RecordError>
+    where
+        T: MoveNotation + NotatedGame,
+        T::Move: PartialEq,
+        GamePosition: From<T>,
+    {
+        let start = T::from_notation(&self.position).map_err(RecordError::Position)?;
+
+        let expected = GamePosition::from(start.clone()).kind();
+        if expected != self.game {
+            return Err(RecordError::WrongGame {
+                expected: expected.name(),
+                found: self.game.name(),
+            });
+        }
+
+        let mut history = History::new(start);
+
+        for (index, token) in self.moves.iter().enumerate() {
+            let error = |error: String| RecordError::Move {
+                index: index + 1,
+                token: token.clone(),
+                error,
+            };
+
+            let game = history.game();
+            let m = game
+                .read_move(token)
+                .map_err(|err| error(err.to_string()))?;
+
+            if !game.possible_moves().any(|possible| possible == m) {
+                return Err(error("it isn't a possible move".to_string()));
+            }
+
+            history.push(m).map_err(|err| error(err.to_string()))?;
+        }
+
+        Ok(history)
+    }
+}
+
+impl Display for GameRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self
+            .game
+            .to_possible_value()
+            .expect("every game has a name");
+
+        writeln!(f, "{} {}", name.get_name(), self.position)?;
+        writeln!(
+            f,
+            "{}",
+            self.moves
+                .iter()
+                .map(|m| quote(m))
+                .collect::<Vec<_>>()
+                .join(" ")
+        )
+    }
+}
+
+impl FromStr for GameRecord {
+    type Err = RecordError;
+
+    fn from_str(record: &str) -> Result<Self, Self::Err> {
+        let record = record.trim_start();
+        let (header, moves) = record.split_once('\n').unwrap_or((record, ""));
+
+        let header = header.trim();
+        if header.is_empty() {
+            return Err(RecordError::Empty);
+        }
+
+        let (name, position) = header
+            .split_once(char::is_whitespace)
+            .unwrap_or((header, ""));
+        let game = GameKind::from_str(name, true)
+            .map_err(|_| RecordError::UnknownGame(name.to_string()))?;
+
+        Ok(Self {
+            game,
+            position: position.trim().to_string(),
+            moves: split_moves(moves)?,
+        })
+    }
+}
+
+/// Writes a move as a single token, quoting it if it has to be.
+fn quote(m: &str) -> String {
+    if !m.is_empty() && !m.contains(|ch: char| ch.is_whitespace() || ch == '"') {
+        return m.to_string();
+    }
+
+    format!("\"{}\"", m.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Splits the moves of a record, unquoting those in quotes.
+fn split_moves(moves: &str) -> Result<Vec<String>, RecordError> {
+    let mut tokens = Vec::new();
+    let mut chars = moves.chars().peekable();
+
+    while let Some(&ch) = chars.peek() {
+        if ch.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        let mut token = String::new();
+
+        if ch == '"' {
+            chars.next();
+
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => token.push(chars.next().ok_or(RecordError::UnclosedQuote)?),
+                    Some(ch) => token.push(ch),
+                    None => return Err(RecordError::UnclosedQuote),
+                }
+            }
+        } else {
+            while let Some(ch) = chars.next_if(|ch| !ch.is_whitespace()) {
+                token.push(ch);
+            }
+        }
+
+        tokens.push(token);
+    }
+
+    Ok(tokens)
+}
+
+/// Writes the record of the moves played in `history`.
+pub fn save_record<T>(history: &History<T>) -> String
+where
+    T: MoveNotation + NotatedGame,
+    GamePosition: From<T>,
+{
+    GameRecord::new(history).to_string()
+}
+
+/// Reads a record, and plays its moves from its starting position.
+pub fn load_record<T>(record: &str) -> Result<History<T>, RecordError>
+where
+    T: MoveNotation + NotatedGame,
+    T::Move: PartialEq,
+    GamePosition: From<T>,
+{
+    record.parse::<GameRecord>()?.replay()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{naive_nim::Nim, util::move_natural::NaturalMove};
+
+    #[test]
+    fn moves_with_whitespace_and_quotes_are_quoted() {
+        let moves = [
+            "1-2",
+            "a move",
+            "\"quoted\"",
+            "back\\slash \"",
+            "",
+            "tab\there",
+            "x",
+        ]
+        .map(String::from)
+        .to_vec();
+
+        let written = moves.iter().map(|m| quote(m)).collect::<Vec<_>>().join(" ");
+        assert_eq!(
+            written,
+            r#"1-2 "a move" "\"quoted\"" "back\\slash \"" "" "tab	here" x"#
+        );
+        assert_eq!(split_moves(&written).unwrap(), moves);
+
+        assert_eq!(split_moves("1-2 \"open"), Err(RecordError::UnclosedQuote));
+        assert_eq!(split_moves("\"open\\"), Err(RecordError::UnclosedQuote));
+    }
+
+    #[test]
+    fn records_are_a_header_and_moves() {
+        let mut history = History::new(Nim::new(vec![3, 5]));
+        history.push(NaturalMove([1, 2])).unwrap();
+        history.push(NaturalMove([0, 3])).unwrap();
+
+        let record = save_record(&history);
+        assert_eq!(record, "naive-nim 3,5 m0\n1-2 0-3\n");
+
+        let loaded = load_record::<Nim>(&record).unwrap();
+        assert_eq!(loaded.start(), history.start());
+        assert_eq!(loaded.moves(), history.moves());
+
+        // moves can span lines
+        let loaded = load_record::<Nim>("naive-nim 3,5 m0\n1-2\n  0-3\n\n").unwrap();
+        assert_eq!(loaded.game(), history.game());
+    }
+
+    #[test]
+    fn bad_records_are_reported() {
+        let error = |record: &str| load_record::<Nim>(record).unwrap_err();
+
+        assert_eq!(error(" \n"), RecordError::Empty);
+        assert_eq!(
+            error("chess startpos\ne4"),
+            RecordError::UnknownGame("chess".to_string())
+        );
+        assert!(matches!(
+            error("naive-nim 3,x m0\n"),
+            RecordError::Position(_)
+        ));
+        assert_eq!(
+            error("tic-tac-toe 3,5 m0\n"),
+            RecordError::WrongGame {
+                expected: "Nim (Naive)",
+                found: "Tic Tac Toe",
+            }
+        );
+
+        let RecordError::Move { index, token, .. } = error("naive-nim 3,5 m0\n1-2 0-4") else {
+            panic!("an impossible move was replayed");
+        };
+        assert_eq!((index, token.as_str()), (2, "0-4"));
+    }
+}
@@ -0,0 +1,341 @@
+//! Drawing boards for people, with box-drawing borders, coordinates, and colors,
+//! or as the plain text every game's [`Display`] writes, which scripts read.
+
+use std::{
+    fmt::{self, Display, Write},
+    io::{self, IsTerminal},
+};
+
+use game_solver::{game::Game, player::PartizanPlayer};
+#[cfg(feature = "color")]
+use owo_colors::OwoColorize;
+
+/// How boards are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Style {
+    /// The board as the game's [`Display`] writes it.
+    #[default]
+    Plain,
+    /// The board in a box-drawing grid with its coordinates along the edges,
+    /// with the last move in brackets and the cells a move can be made on dotted.
+    Unicode,
+    /// [`Style::Unicode`], with the pieces of each player in their own color,
+    /// the last move in bold, and the dots of the cells a move can be made on dimmed.
+    #[cfg(feature = "color")]
+    Color,
+}
+
+impl Style {
+    /// The style to draw boards on standard output in:
+    /// plain if `plain` is set or standard output isn't a terminal, so that piped output stays the same,
+    /// and otherwise colored, unless the `NO_COLOR` environment variable is set.
+    pub fn detect(plain: bool) -> Self {
+        if plain || !io::stdout().is_terminal() {
+            return Self::Plain;
+        }
+
+        #[cfg(feature = "color")]
+        if std::env::var_os("NO_COLOR").is_none() {
+            return Self::Color;
+        }
+
+        Self::Unicode
+    }
+}
+
+/// A cell of a [`Grid`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    /// The character the plain board writes for the cell.
+    pub plain: char,
+    /// The character the drawn board writes for the cell, where a space leaves it empty.
+    pub drawn: char,
+    /// The player whose color the cell is drawn in, if any.
+    pub side: Option<PartizanPlayer>,
+    /// Whether a move can be made on the cell, which the drawn board dots if it's empty.
+    pub legal: bool,
+    /// Whether the last move was made on the cell.
+    pub last_move: bool,
+}
+
+impl Cell {
+    /// A cell written as `plain` on the plain board, and left empty on the drawn one.
+    pub fn empty(plain: char) -> Self {
+        Self::new(plain, ' ')
+    }
+
+    /// A cell written as `plain` on the plain board, and as `drawn` on the drawn one.
+    pub fn new(plain: char, drawn: char) -> Self {
+        Self {
+            plain,
+            drawn,
+            side: None,
+            legal: false,
+            last_move: false,
+        }
+    }
+
+    /// A piece of `side`, written as `symbol` on both boards.
+    pub fn piece(symbol: char, side: PartizanPlayer) -> Self {
+        Self {
+            side: Some(side),
+            ..Self::new(symbol, symbol)
+        }
+    }
+
+    /// The same cell, marked as one a move can be made on.
+    pub fn legal(self) -> Self {
+        Self {
+            legal: true,
+            ..self
+        }
+    }
+}
+
+/// A board laid out as rows of cells, with lines written above it.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Grid {
+    /// Lines written above the board, such as whose turn it is.
+    pub notes: Vec<String>,
+    /// The cells of the board, from the top row down.
+    pub rows: Vec<Vec<Cell>>,
+}
+
+impl Grid {
+    /// A grid of `rows`, with no lines above it.
+    pub fn new(rows: Vec<Vec<Cell>>) -> Self {
+        Self {
+            notes: Vec::new(),
+            rows,
+        }
+    }
+
+    /// The same grid, with `note` written above it, below the notes it already has.
+    pub fn note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// Marks the cells at `cells`, as (row, column), as those the last move was made on.
+    /// Cells outside the grid are ignored.
+    pub fn mark_last_move(&mut self, cells: &[(usize, usize)]) {
+        for &(row, column) in cells {
+            if let Some(cell) = self
+                .rows
+                .get_mut(row)
+                .and_then(|cells| cells.get_mut(column))
+            {
+                cell.last_move = true;
+            }
+        }
+    }
+
+    /// Writes the notes, then every row as the plain characters of its cells.
+    pub fn write_plain(&self, f: &mut impl Write) -> fmt::Result {
+        for note in &self.notes {
+            writeln!(f, "{note}")?;
+        }
+
+        for row in &self.rows {
+            for cell in row {
+                f.write_char(cell.plain)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+
+    /// The grid drawn in `style`, with the column numbers above it and the row numbers to its left.
+    pub fn draw(&self, style: Style) -> String {
+        if style == Style::Plain {
+            let mut plain = String::new();
+            self.write_plain(&mut plain)
+                .expect("writing to a string can't fail");
+            return plain;
+        }
+
+        let columns = self.rows.iter().map(Vec::len).max().unwrap_or(0);
+        let label_width = self.rows.len().saturating_sub(1).to_string().len();
+        let margin = " ".repeat(label_width + 1);
+        let border = |left: &str, middle: &str, right: &str| {
+            format!(
+                "{margin}{left}{}{right}\n",
+                vec!["───"; columns].join(middle)
+            )
+        };
+
+        let mut drawn = String::new();
+        for note in &self.notes {
+            drawn.push_str(note);
+            drawn.push('\n');
+        }
+
+        // the labels are centered over the columns, one space in from their borders
+        drawn.push_str(&margin);
+        drawn.push(' ');
+        for column in 0..columns {
+            drawn.push_str(&format!("{column:^3} "));
+        }
+        drawn.truncate(drawn.trim_end_matches(' ').len());
+        drawn.push('\n');
+
+        drawn.push_str(&border("┌", "┬", "┐"));
+        for (index, row) in self.rows.iter().enumerate() {
+            if index > 0 {
+                drawn.push_str(&border("├", "┼", "┤"));
+            }
+
+            drawn.push_str(&format!("{index:>label_width$} │"));
+            for column in 0..columns {
+                let cell = row.get(column).copied().unwrap_or(Cell::empty(' '));
+                drawn.push_str(&draw_cell(cell, style));
+                drawn.push('│');
+            }
+            drawn.push('\n');
+        }
+        drawn.push_str(&border("└", "┴", "┘"));
+
+        drawn
+    }
+}
+
+/// The three characters `cell` is drawn as in `style`, between the borders of its column.
+fn draw_cell(cell: Cell, style: Style) -> String {
+    let (open, close) = if cell.last_move {
+        ('[', ']')
+    } else {
+        (' ', ' ')
+    };
+    let symbol = if cell.legal && cell.drawn == ' ' {
+        '·'
+    } else {
+        cell.drawn
+    };
+
+    match style {
+        #[cfg(feature = "color")]
+        Style::Color => {
+            let symbol = match cell.side {
+                Some(PartizanPlayer::Left) => symbol.blue().to_string(),
+                Some(PartizanPlayer::Right) => symbol.red().to_string(),
+                None if cell.legal => symbol.dimmed().to_string(),
+                None => symbol.to_string(),
+            };
+
+            if cell.last_move {
+                format!("{}", format!("{open}{symbol}{close}").bold())
+            } else {
+                format!("{open}{symbol}{close}")
+            }
+        }
+        _ => format!("{open}{symbol}{close}"),
+    }
+}
+
+/// A game whose positions can be drawn for people, instead of written as their [`Display`].
+pub trait Render: Game + Display {
+    /// The position as a grid of cells, or None (the default) if it isn't laid out as one,
+    /// in which case it's drawn as its [`Display`] in every style.
+    fn grid(&self) -> Option<Grid> {
+        None
+    }
+
+    /// The cells of the grid that `m` was made on, as (row, column), which are highlighted after it's made.
+    /// By default, none are.
+    fn move_cells(&self, _m: &Self::Move) -> Vec<(usize, usize)> {
+        Vec::new()
+    }
+
+    /// The position drawn in `style`, highlighting the cells of `last_move`, the move that reached it.
+    ///
+    /// ```
+    /// use games::{
+    ///     tic_tac_toe::TicTacToe,
+    ///     util::{
+    ///         notation::NotatedGame,
+    ///         parse::ParseMove,
+    ///         render::{Render, Style},
+    ///     },
+    /// };
+    ///
+    /// let game = TicTacToe::from_notation("XO./.../... X").unwrap();
+    /// assert_eq!(game.render(Style::Plain, None), game.to_string());
+    ///
+    /// let last_move = game.parse_move("0-1").unwrap();
+    /// assert_eq!(
+    ///     game.render(Style::Unicode, Some(&last_move)),
+    ///     "    0   1   2
+    ///   ┌───┬───┬───┐
+    /// 0 │ X │[O]│ · │
+    ///   ├───┼───┼───┤
+    /// 1 │ · │ · │ · │
+    ///   ├───┼───┼───┤
+    /// 2 │ · │ · │ · │
+    ///   └───┴───┴───┘
+    /// "
+    /// );
+    /// ```
+    fn render(&self, style: Style, last_move: Option<&Self::Move>) -> String {
+        match self.grid() {
+            Some(mut grid) if style != Style::Plain => {
+                if let Some(m) = last_move {
+                    grid.mark_last_move(&self.move_cells(m));
+                }
+                grid.draw(style)
+            }
+            _ => self.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid {
+        Grid::new(vec![
+            vec![
+                Cell::piece('X', PartizanPlayer::Left),
+                Cell::empty('*').legal(),
+            ],
+            vec![Cell::empty('-'), Cell::piece('O', PartizanPlayer::Right)],
+        ])
+        .note("Current player: X")
+    }
+
+    #[test]
+    fn plain_writes_the_plain_characters() {
+        assert_eq!(grid().draw(Style::Plain), "Current player: X\nX*\n-O\n");
+    }
+
+    #[test]
+    fn unicode_draws_a_grid_with_coordinates() {
+        let mut grid = grid();
+        grid.mark_last_move(&[(1, 1), (5, 5)]);
+
+        assert_eq!(
+            grid.draw(Style::Unicode),
+            "Current player: X
+    0   1
+  ┌───┬───┐
+0 │ X │ · │
+  ├───┼───┤
+1 │   │[O]│
+  └───┴───┘
+"
+        );
+    }
+
+    #[test]
+    fn row_labels_are_right_aligned() {
+        let grid = Grid::new(vec![vec![Cell::empty('.')]; 11]);
+        let drawn = grid.draw(Style::Unicode);
+        let lines = drawn.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines[0], "     0");
+        assert_eq!(lines[1], "   ┌───┐");
+        assert_eq!(lines[2], " 0 │   │");
+        assert_eq!(lines[22], "10 │   │");
+    }
+}
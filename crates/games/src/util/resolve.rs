@@ -0,0 +1,85 @@
+//! [`Game::find_immediately_resolvable_game`] for games that can tell how a move ends the game
+//! without making it, which only copies the board to make the move it picks.
+
+use game_solver::{
+    game::{Game, GameState},
+    player::Player,
+};
+
+/// The position [`Game::find_immediately_resolvable_game`] returns by default, where `ending`
+/// gives the state of `game` after each of its moves without making it: the first move that wins,
+/// or else the last that ties, or else the first that loses.
+///
+/// Only the move picked is made.
+pub fn find_resolvable<T: Game>(
+    game: &T,
+    mut ending: impl FnMut(&T::Move) -> GameState<T::Player>,
+) -> Result<Option<T>, T::MoveError> {
+    let mut best_non_winning_move = None;
+
+    for m in game.possible_moves() {
+        match ending(&m) {
+            GameState::Playable => continue,
+            GameState::Tie => best_non_winning_move = Some(m),
+            GameState::Win(winning_player) => {
+                if winning_player == game.player().turn() {
+                    return played(game, &m).map(Some);
+                } else if best_non_winning_move.is_none() {
+                    best_non_winning_move = Some(m);
+                }
+            }
+        }
+    }
+
+    best_non_winning_move.map(|m| played(game, &m)).transpose()
+}
+
+fn played<T: Game>(game: &T, m: &T::Move) -> Result<T, T::MoveError> {
+    let mut game = game.clone();
+    game.make_move(m)?;
+    Ok(game)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{collections::HashSet, fmt::Debug, hash::Hash};
+
+    use super::*;
+
+    /// [`Game::find_immediately_resolvable_game`]'s default, which makes every move to see how it ends.
+    fn by_making_every_move<T: Game>(game: &T) -> Result<Option<T>, T::MoveError> {
+        find_resolvable(game, |m| {
+            played(game, m).map_or(GameState::Playable, |game| game.state())
+        })
+    }
+
+    /// Checks that [`Game::find_immediately_resolvable_game`] picks the position making every move would
+    /// in every position reachable from `game`, returning how many positions were checked.
+    pub(crate) fn agrees_with_making_every_move<T>(game: T) -> usize
+    where
+        T: Game + Eq + Hash + Debug,
+        T::MoveError: Debug,
+    {
+        let mut seen = HashSet::new();
+        let mut unchecked = vec![game];
+
+        while let Some(game) = unchecked.pop() {
+            if game.state() != GameState::Playable || seen.contains(&game) {
+                continue;
+            }
+
+            assert_eq!(
+                game.find_immediately_resolvable_game().unwrap(),
+                by_making_every_move(&game).unwrap(),
+                "{game:?}"
+            );
+
+            for m in game.possible_moves() {
+                unchecked.push(played(&game, &m).unwrap());
+            }
+            seen.insert(game);
+        }
+
+        seen.len()
+    }
+}
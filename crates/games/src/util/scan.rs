@@ -0,0 +1,22 @@
+//! Lazy move generation for games whose moves are the candidates of a fixed list that a position allows.
+
+use std::{
+    iter::{repeat, FilterMap, Repeat, Zip},
+    ops::Range,
+};
+
+/// The moves of `game` out of the candidates `0..count`, made as they're asked for,
+/// so a search that prunes after the first few moves doesn't make the rest.
+///
+/// It's a named type, so games can use it as their [`Game::Iter`](game_solver::game::Game::Iter).
+pub type Scan<'a, T, M> =
+    FilterMap<Zip<Repeat<&'a T>, Range<usize>>, fn((&'a T, usize)) -> Option<M>>;
+
+/// Scans the candidates `0..count` of `game` in order, keeping the moves `allowed` returns.
+pub fn scan<'a, T, M>(
+    game: &'a T,
+    count: usize,
+    allowed: fn((&'a T, usize)) -> Option<M>,
+) -> Scan<'a, T, M> {
+    repeat(game).zip(0..count).filter_map(allowed)
+}
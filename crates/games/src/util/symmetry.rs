@@ -0,0 +1,13 @@
+//! Symmetric positions, such as a board and its mirror image, which play out the same way.
+
+use game_solver::game::Game;
+
+/// A game whose positions can be mapped to one of the positions symmetric to them,
+/// so that `openings` only solves and reports each of them once.
+pub trait Symmetries: Game + Sized {
+    /// The same position for every position symmetric to this one, such as the smallest of them.
+    /// By default, a position is only symmetric to itself.
+    fn canonical(&self) -> Self {
+        self.clone()
+    }
+}
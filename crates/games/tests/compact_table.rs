@@ -0,0 +1,195 @@
+use std::{
+    collections::HashMap,
+    hash::{BuildHasher, DefaultHasher, Hash, Hasher},
+    mem::size_of,
+    sync::atomic::Ordering,
+};
+
+use game_solver::{
+    game::Game,
+    move_scores,
+    player::TwoPlayer,
+    solve,
+    stats::Stats,
+    transposition::{CompactTable, Score, ScoreRepr, TranspositionTable, VerifyHash},
+};
+use games::{chomp::Chomp, domineering::Domineering, naive_nim::Nim, util::notation::NotatedGame};
+
+#[test]
+fn compact_tables_take_a_tenth_of_the_memory() {
+    // a 7x5 corner of a 10x10 board, which the map stores whole with every position
+    let game = Chomp::from_notation(&format!(
+        "{}{}.XXXXXX... m2",
+        "........../".repeat(5),
+        "XXXXXXX.../".repeat(4)
+    ))
+    .unwrap();
+    let (width, height) = (10, 10);
+
+    let mut table = HashMap::<Chomp, Score>::new();
+    let score = solve(&game, &mut table, None, &None).unwrap();
+
+    let mut compact = CompactTable::<Chomp, i8>::new();
+    assert_eq!(solve(&game, &mut compact, None, &None).unwrap(), score);
+    assert_eq!(compact.len(), table.len());
+
+    // every position in the map also holds its board on the heap
+    let entry_size = size_of::<(Chomp, Score)>() + width * height;
+    let memory = table.len() * entry_size;
+    let compact_memory = compact.len() * CompactTable::<Chomp, i8>::ENTRY_SIZE;
+
+    assert!(
+        compact_memory * 10 <= memory,
+        "{} positions take {compact_memory} bytes compacted, and {memory} bytes otherwise",
+        table.len()
+    );
+}
+
+#[test]
+fn scores_that_fit_are_stored_as_they_are() {
+    for score in [i8::MIN as isize, -5, 0, 35, i8::MAX as isize] {
+        assert_eq!(i8::from_score(score).map(i8::to_score), Some(score));
+    }
+
+    assert_eq!(i8::from_score(200), None);
+    assert_eq!(i8::from_score(-129), None);
+}
+
+#[test]
+fn scores_that_dont_fit_are_widened_or_left_out() {
+    let game = Nim::new(vec![3]);
+    let mut table = CompactTable::<Nim, i8>::new();
+
+    // bounds that don't fit would be wrong if they were saturated, so they're read back as no bound at all
+    table.insert(game.clone(), Score::UpperBound(200));
+    assert_eq!(table.get(&game), None);
+    table.insert(game.clone(), Score::LowerBound(-200));
+    assert_eq!(table.get(&game), None);
+
+    // past the other end, the bound as tight as an i8 makes it is still true
+    table.insert(game.clone(), Score::UpperBound(-200));
+    assert_eq!(table.get(&game), Some(Score::UpperBound(-128)));
+    table.insert(game.clone(), Score::LowerBound(200));
+    assert_eq!(table.get(&game), Some(Score::LowerBound(127)));
+
+    // an exact score that doesn't fit leaves what was known before
+    table.insert(game.clone(), Score::Exact(200));
+    assert_eq!(table.get(&game), Some(Score::LowerBound(127)));
+    table.insert(game.clone(), Score::Exact(100));
+    assert_eq!(table.get(&game), Some(Score::Exact(100)));
+}
+
+/// Runs in release builds as well, where scores that don't fit used to be saturated into wrong ones.
+#[test]
+fn tables_too_narrow_for_the_scores_solve_right() {
+    // the first player wins in about 7 of the 153 moves the heaps allow, for a score far past 127
+    let game = Nim::new(vec![150, 3]);
+    let expected = solve(&game, &mut HashMap::new(), None, &None).unwrap();
+    assert!(expected > i8::MAX as isize);
+
+    let mut table = CompactTable::<Nim, i8>::new();
+    assert_eq!(solve(&game, &mut table, None, &None).unwrap(), expected);
+    assert_eq!(
+        scores_with(&game, &mut table).0,
+        scores_with(&game, &mut HashMap::new()).0
+    );
+}
+
+/// Hashes every position to one of 4 keys, so that nearly every lookup finds the entry of another position.
+#[derive(Default)]
+struct FourKeys;
+
+impl BuildHasher for FourKeys {
+    type Hasher = FourKeysHasher;
+
+    fn build_hasher(&self) -> FourKeysHasher {
+        FourKeysHasher(DefaultHasher::new())
+    }
+}
+
+struct FourKeysHasher(DefaultHasher);
+
+impl Hasher for FourKeysHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.0.write(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        self.0.finish() % 4
+    }
+}
+
+/// The score of every move of `game` with `table`, and the collisions the solver counted.
+fn scores_with<T, P>(game: &T, table: &mut dyn TranspositionTable<T>) -> (Vec<isize>, u64)
+where
+    T: Game<Player = P> + Eq + Hash,
+    P: TwoPlayer + 'static,
+{
+    let stats = Stats::new(game.player(), game.move_count());
+    let scores = move_scores(game, table, Some(&stats), &None)
+        .map(|result| match result {
+            Ok((_, score)) => score,
+            Err(_) => panic!("the solver failed"),
+        })
+        .collect();
+
+    (
+        scores,
+        stats.transposition_collisions.load(Ordering::Relaxed),
+    )
+}
+
+fn chomp() -> Chomp {
+    Chomp::from_notation("....../XXXX../XXXXX./.XXXXX m1").unwrap()
+}
+
+#[test]
+fn verified_tables_are_right_despite_collisions() {
+    let game = chomp();
+    let (expected, _) = scores_with(&game, &mut HashMap::new());
+
+    let mut table = CompactTable::<Chomp, i8, FourKeys>::verified();
+    let (scores, collisions) = scores_with(&game, &mut table);
+
+    assert_eq!(scores, expected);
+    assert!(collisions > 0);
+    assert_eq!(table.collisions(), collisions);
+    assert!(table.len() <= 4);
+}
+
+#[test]
+fn tables_of_whole_positions_are_right_despite_collisions() {
+    // Domineering only hashes its Zobrist hash, so a second hash would collide along with the first
+    let game = Domineering::<4, 4>::new();
+    let (expected, _) = scores_with(&game, &mut HashMap::new());
+
+    let mut table = CompactTable::<Domineering<4, 4>, i8, FourKeys>::with_positions();
+    let (scores, collisions) = scores_with(&game, &mut table);
+
+    assert_eq!(scores, expected);
+    assert!(collisions > 0);
+
+    let mut table = CompactTable::<Chomp, i8, FourKeys>::with_positions();
+    assert_eq!(
+        scores_with(&chomp(), &mut table).0,
+        scores_with(&chomp(), &mut HashMap::new()).0
+    );
+}
+
+#[test]
+fn unverified_tables_count_no_collisions() {
+    let mut table = CompactTable::<Chomp, i8, FourKeys>::new();
+    let (_, collisions) = scores_with(&chomp(), &mut table);
+
+    assert_eq!(collisions, 0);
+    assert!(table.len() <= 4);
+}
+
+#[test]
+fn verification_takes_more_memory() {
+    assert_eq!(CompactTable::<Chomp, i8>::ENTRY_SIZE, 16);
+    assert_eq!(
+        CompactTable::<Chomp, i8, FourKeys, VerifyHash>::ENTRY_SIZE,
+        24
+    );
+}
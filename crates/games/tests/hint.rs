@@ -0,0 +1,119 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+use game_solver::{
+    game::Game,
+    hint::{hint, Effort, HintSource, Hints, Honesty},
+    move_scores,
+    player::TwoPlayer,
+    solve,
+    strategy::{Outcome, Value},
+};
+use games::{
+    chomp::Chomp, naive_nim::Nim, reversi::Reversi, tic_tac_toe::TicTacToe,
+    util::notation::NotatedGame,
+};
+
+/// Checks that `hint` with [`Effort::Exact`] scores `game` like `solve`, suggesting one of its best moves.
+fn exact_matches_solve<T, P>(game: &T)
+where
+    P: TwoPlayer + 'static,
+    T: Hints<Player = P> + Eq + Hash + Debug,
+    T::Move: PartialEq + Debug,
+{
+    let suggestion = hint(game, Effort::Exact).unwrap().unwrap();
+    let score = solve(game, &mut HashMap::new(), None, &None).unwrap();
+
+    assert_eq!(suggestion.estimate, Value::Exact(score));
+    assert_eq!(suggestion.honesty, Honesty::Exact);
+    assert_eq!(suggestion.source, HintSource::Solve);
+
+    let best_moves = move_scores(game, &mut HashMap::new(), None, &None)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap()
+        .into_iter()
+        .filter(|(_, move_score)| *move_score == score)
+        .map(|(m, _)| m)
+        .collect::<Vec<_>>();
+    assert!(
+        best_moves.contains(&suggestion.best_move),
+        "{:?}",
+        suggestion.best_move
+    );
+}
+
+#[test]
+fn exact_hints_match_solve() {
+    exact_matches_solve(&Nim::new(vec![3, 5, 7]));
+    exact_matches_solve(&Nim::new(vec![1, 2, 3]));
+    exact_matches_solve(&Chomp::new(4, 3));
+    exact_matches_solve(&TicTacToe::from_notation("X../.O./... X").unwrap());
+}
+
+#[test]
+fn book_moves_are_exact() {
+    let game = Nim::new(vec![3, 5, 7]);
+    let suggestion = hint(&game, Effort::Instant).unwrap().unwrap();
+
+    assert_eq!(suggestion.source, HintSource::Book);
+    assert_eq!(suggestion.honesty, Honesty::Exact);
+    assert_eq!(suggestion.estimate, Value::Proven(Outcome::Win));
+    assert_eq!(Some(suggestion.best_move), game.optimal_move());
+}
+
+#[test]
+fn impartial_games_without_a_book_move_use_grundy_values() {
+    // Chomp has no book, and the player to move always wins a rectangle
+    let suggestion = hint(&Chomp::new(4, 3), Effort::Quick).unwrap().unwrap();
+    assert_eq!(suggestion.source, HintSource::Grundy);
+    assert_eq!(suggestion.honesty, Honesty::Exact);
+    assert_eq!(suggestion.estimate, Value::Proven(Outcome::Win));
+
+    // the heaps' nim-sum is 0, so the book has no winning move, and every move loses
+    let suggestion = hint(&Nim::new(vec![1, 2, 3]), Effort::Quick)
+        .unwrap()
+        .unwrap();
+    assert_eq!(suggestion.source, HintSource::Grundy);
+    assert_eq!(suggestion.estimate, Value::Proven(Outcome::Loss));
+}
+
+#[test]
+fn games_without_an_evaluator_are_solved_within_the_budget() {
+    // O threatens the middle column, with 5 moves left to see to the end
+    let game = TicTacToe::from_notation("XO./.OX/... X").unwrap();
+    let suggestion = hint(&game, Effort::Quick).unwrap().unwrap();
+    let score = solve(&game, &mut HashMap::new(), None, &None).unwrap();
+
+    assert_eq!(suggestion.source, HintSource::Solve);
+    assert_eq!(suggestion.honesty, Honesty::Exact);
+    assert_eq!(suggestion.estimate, Value::Exact(score));
+}
+
+#[test]
+fn instant_reversi_hints_are_heuristic_and_quick() {
+    let game = Reversi::new();
+
+    let start = Instant::now();
+    let suggestion = hint(&game, Effort::Instant).unwrap().unwrap();
+    let elapsed = start.elapsed();
+
+    // the budget is a few milliseconds, and the search stops at the first position after it
+    assert!(elapsed < Duration::from_millis(250), "{elapsed:?}");
+    assert_eq!(suggestion.source, HintSource::Search);
+    assert_eq!(suggestion.honesty, Honesty::Heuristic);
+    assert!(matches!(suggestion.estimate, Value::Heuristic(_)));
+    assert!(game.possible_moves().any(|m| m == suggestion.best_move));
+}
+
+#[test]
+fn finished_games_have_no_hint() {
+    let game = Nim::new(vec![0, 0]);
+
+    for effort in [Effort::Instant, Effort::Quick, Effort::Exact] {
+        assert_eq!(hint(&game, effort).unwrap(), None);
+    }
+}
@@ -0,0 +1,114 @@
+use std::{
+    fmt::Debug,
+    hash::{BuildHasher, Hash, RandomState},
+};
+
+use game_solver::{
+    game::{Game, GameState},
+    hash::IncrementalHash,
+    history::History,
+};
+use games::{
+    domineering::Domineering, naive_nim::Nim, tic_tac_toe::TicTacToe,
+    util::move_natural::NaturalMove,
+};
+use rand::{rngs::StdRng, seq::IteratorRandom, SeedableRng};
+
+/// Plays random games from `start`, then undoes and redoes all of their moves,
+/// checking that every position comes back as it was, down to its hash.
+fn check_random_games<T>(start: &T, games: usize)
+where
+    T: Game + Eq + Hash + Debug,
+    T::Move: PartialEq + Debug,
+    T::MoveError: Debug,
+{
+    let mut rng = StdRng::seed_from_u64(0);
+    let hasher = RandomState::new();
+
+    for _ in 0..games {
+        let mut history = History::new(start.clone());
+        let mut positions = vec![start.clone()];
+
+        while history.game().state() == GameState::Playable {
+            let m = history.game().possible_moves().choose(&mut rng).unwrap();
+            history.push(m).unwrap();
+            positions.push(history.game().clone());
+        }
+
+        let moves = history.moves().to_vec();
+        assert_eq!(moves.len(), positions.len() - 1);
+
+        for (ply, position) in positions.iter().enumerate() {
+            assert_eq!(history.position_at(ply), Some(position));
+        }
+        assert_eq!(history.position_at(positions.len()), None);
+
+        for m in moves.iter().rev() {
+            assert_eq!(history.undo(), Some(m));
+
+            let position = &positions[history.ply()];
+            assert_eq!(history.game(), position);
+            assert_eq!(hasher.hash_one(history.game()), hasher.hash_one(position));
+        }
+        assert_eq!(history.undo(), None);
+        assert_eq!(history.game(), start);
+        assert_eq!(hasher.hash_one(history.game()), hasher.hash_one(start));
+        assert!(history.moves().is_empty());
+
+        for m in &moves {
+            assert_eq!(history.redo(), Some(m));
+        }
+        assert_eq!(history.redo(), None);
+        assert_eq!(history.game(), positions.last().unwrap());
+        assert_eq!(history.moves(), moves);
+    }
+}
+
+#[test]
+fn undoing_every_move_restores_the_start() {
+    check_random_games(&TicTacToe::new(2, 3), 100);
+    check_random_games(&TicTacToe::new(3, 3), 20);
+    check_random_games(&Domineering::<5, 5>::new(), 100);
+    check_random_games(&Nim::new(vec![3, 4, 5]), 100);
+}
+
+#[test]
+fn undoing_restores_incremental_hashes() {
+    let start = TicTacToe::new(2, 4);
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut history = History::new(start.clone());
+
+    for _ in 0..8 {
+        let m = history.game().possible_moves().choose(&mut rng).unwrap();
+        history.push(m).unwrap();
+    }
+
+    while history.undo().is_some() {
+        assert_eq!(
+            history.game().incremental_hash(),
+            history.game().full_hash()
+        );
+    }
+    assert_eq!(history.game().incremental_hash(), start.incremental_hash());
+}
+
+#[test]
+fn pushing_after_an_undo_forgets_the_undone_moves() {
+    let start = Nim::new(vec![2, 3]);
+    let mut history = History::new(start.clone());
+
+    let moves = start.possible_moves().collect::<Vec<_>>();
+    history.push(moves[0]).unwrap();
+    history.undo().unwrap();
+    history.push(moves[1]).unwrap();
+
+    assert_eq!(history.moves(), [moves[1]]);
+    assert_eq!(history.redo(), None);
+
+    // a failed move leaves everything as it was
+    let game = history.game().clone();
+    assert!(history.push(NaturalMove([5, 1])).is_err());
+    assert_eq!(history.game(), &game);
+    assert_eq!(history.moves(), [moves[1]]);
+    assert_eq!(history.start(), &start);
+}
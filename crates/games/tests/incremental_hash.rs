@@ -0,0 +1,85 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    hash::{BuildHasher, Hash, RandomState},
+};
+
+use game_solver::{
+    game::{Game, GameState},
+    hash::{BuildIncrementalHasher, IncrementalHash},
+    player::TwoPlayer,
+    solve,
+};
+use games::{domineering::Domineering, tic_tac_toe::TicTacToe, util::notation::NotatedGame};
+use rand::{rngs::StdRng, seq::IteratorRandom, SeedableRng};
+
+/// Plays random games from `start`, checking after every move that the hash kept up to date
+/// by the moves made is the one computed from scratch.
+fn check_random_games<T: IncrementalHash>(start: &T, games: usize)
+where
+    T::MoveError: Debug,
+{
+    let mut rng = StdRng::seed_from_u64(0);
+    assert_eq!(start.incremental_hash(), start.full_hash());
+
+    for _ in 0..games {
+        let mut game = start.clone();
+
+        while game.state() == GameState::Playable {
+            let m = game.possible_moves().choose(&mut rng).unwrap();
+            game.make_move(&m).unwrap();
+
+            assert_eq!(game.incremental_hash(), game.full_hash());
+        }
+    }
+}
+
+/// The score of `game`, which has to be the same however its table is hashed.
+fn score<T, P, S>(game: &T) -> isize
+where
+    T: Game<Player = P> + Eq + Hash + Debug,
+    P: TwoPlayer + 'static,
+    S: BuildHasher + Default,
+{
+    solve(game, &mut HashMap::<T, _, S>::default(), None, &None).unwrap()
+}
+
+#[test]
+fn domineering_hashes_incrementally() {
+    check_random_games(&Domineering::<5, 5>::new(), 200);
+    check_random_games(&Domineering::<13, 2>::new(), 200);
+    check_random_games(
+        &Domineering::<5, 5>::from_notation("#..../#..../...../...../..... h").unwrap(),
+        200,
+    );
+
+    for region in Domineering::<5, 5>::from_notation("..#../..#../####./..#../..#.. v")
+        .unwrap()
+        .regions()
+    {
+        check_random_games(&region, 20);
+    }
+}
+
+#[test]
+fn tic_tac_toe_hashes_incrementally() {
+    check_random_games(&TicTacToe::new(2, 3), 200);
+    check_random_games(&TicTacToe::new(2, 4), 200);
+    check_random_games(&TicTacToe::new(3, 3), 50);
+    check_random_games(&TicTacToe::from_notation("X.O/.X./..O X").unwrap(), 50);
+}
+
+#[test]
+fn tables_keyed_by_the_incremental_hash_agree() {
+    let domineering = Domineering::<4, 4>::new();
+    assert_eq!(
+        score::<_, _, BuildIncrementalHasher>(&domineering),
+        score::<_, _, RandomState>(&domineering)
+    );
+
+    let tic_tac_toe = TicTacToe::new(2, 3);
+    assert_eq!(
+        score::<_, _, BuildIncrementalHasher>(&tic_tac_toe),
+        score::<_, _, RandomState>(&tic_tac_toe)
+    );
+}
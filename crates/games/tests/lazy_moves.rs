@@ -0,0 +1,144 @@
+use std::{collections::HashMap, fmt::Debug, hash::Hash, sync::atomic::Ordering};
+
+use counting_allocator::{allocations, CountingAllocator};
+use game_solver::{
+    game::{Game, GameState},
+    player::TwoPlayer,
+    solve,
+    stats::Stats,
+};
+use games::{
+    chomp::Chomp, domineering::Domineering, naive_nim::Nim, order_and_chaos::OrderAndChaos,
+    reversi::Reversi, sprouts::Sprouts, util::notation::NotatedGame,
+};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// The allocations made to find the first move of `game`.
+fn first_move_allocations<T: Game>(game: &T) -> usize {
+    let (first, count) = allocations(|| game.possible_moves().next());
+    assert!(first.is_some());
+    count
+}
+
+/// The score of `game`, and how many positions solving it searches.
+fn searched<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash + Debug>(
+    game: &T,
+) -> (isize, u64) {
+    let stats = Stats::new(game.player(), game.move_count());
+    let score = solve(game, &mut HashMap::new(), Some(&stats), &None).unwrap();
+    (score, stats.states_explored.load(Ordering::Relaxed))
+}
+
+/// Plays `plies` moves into `game`, spread out over its possible moves.
+fn played<T: Game>(mut game: T, plies: usize) -> T
+where
+    T::MoveError: Debug,
+{
+    for ply in 0..plies {
+        let moves = game.possible_moves().collect::<Vec<_>>();
+        assert!(game.state() == GameState::Playable);
+        game.make_move(&moves[ply * 7 % moves.len()]).unwrap();
+    }
+
+    game
+}
+
+/// The allocations made while solving `game`, with a table big enough that it never grows,
+/// along with how many positions were searched, and how many times the table was looked in.
+fn solve_allocations<T: Game<Player = impl TwoPlayer + 'static> + Eq + Hash + Debug>(
+    game: &T,
+) -> (usize, u64, u64) {
+    let mut table = HashMap::with_capacity(1 << 16);
+    let stats = Stats::new(game.player(), game.move_count());
+    let (_, count) = allocations(|| solve(game, &mut table, Some(&stats), &None).unwrap());
+    (
+        count,
+        stats.states_explored.load(Ordering::Relaxed),
+        stats.transposition_probes.load(Ordering::Relaxed),
+    )
+}
+
+/// Order and Chaos with 12 squares left, from the moves of its `bench` positions.
+fn order_and_chaos() -> OrderAndChaos<6, 6, 5, 6> {
+    let moves = "0-1-o 1-0-x 1-4-o 2-3-x 3-1-o 4-0-x 4-4-o 5-3-x 0-2-o 1-3-x 2-4-o 3-5-x \
+                 5-0-o 0-3-x 2-0-o 3-4-x 5-2-o 1-2-x 3-3-o 0-0-x 3-0-o 0-4-x 4-2-o 2-2-x";
+
+    let mut game = OrderAndChaos::new();
+    for m in moves.split_whitespace() {
+        game.make_move(&m.parse().unwrap()).unwrap();
+    }
+    game
+}
+
+fn reversi() -> Reversi {
+    Reversi::from_notation("....../....../..XO../..OX../....../...... X").unwrap()
+}
+
+#[test]
+fn finding_a_move_makes_no_others() {
+    assert_eq!(first_move_allocations(&Nim::new(vec![3, 4, 5])), 0);
+    assert_eq!(first_move_allocations(&Domineering::<5, 5>::new()), 0);
+    assert_eq!(first_move_allocations(&Chomp::new(5, 4)), 0);
+    assert_eq!(
+        first_move_allocations(&OrderAndChaos::<6, 6, 5, 6>::new()),
+        0
+    );
+    assert_eq!(first_move_allocations(&Sprouts::new(4)), 0);
+}
+
+#[test]
+fn moves_are_only_made_when_asked_for() {
+    // a Reversi move is only possible once its flips are found, so the first costs one allocation,
+    // but the others aren't searched for until they're asked for
+    let game = reversi();
+    let first = first_move_allocations(&game);
+    let (moves, all) = allocations(|| game.possible_moves().count());
+    assert!(
+        first < all,
+        "{first} allocations for the first move, {all} for all {moves}"
+    );
+
+    let nim = Nim::new(vec![3, 4, 5]);
+    assert_eq!(nim.possible_moves().len(), 12);
+    let mut moves = nim.possible_moves();
+    moves.nth(4);
+    assert_eq!(moves.len(), 7);
+}
+
+#[test]
+fn searches_are_unchanged() {
+    // the number of positions each search goes through, which doesn't depend on how the moves are made
+    assert_eq!(searched(&Nim::new(vec![3, 4, 5])), (2, 887));
+    // (Domineering positions that only differ in their dead squares share their entries)
+    assert_eq!(searched(&Domineering::<5, 4>::new()), (12, 10158));
+    assert_eq!(searched(&Chomp::new(5, 4)), (10, 2882));
+    assert_eq!(searched(&Sprouts::new(3)), (-3, 236));
+    assert_eq!(searched(&played(reversi(), 20)), (8, 8316));
+    assert_eq!(searched(&played(order_and_chaos(), 2)), (-1, 31817));
+}
+
+#[test]
+#[cfg_attr(
+    game_solver_check_dominance,
+    ignore = "every solve also searches the dominated moves, twice"
+)]
+fn boards_are_reused_by_the_search() {
+    // a Domineering board is a bitboard, which copying never allocates,
+    // so only the search's own boards for each ply are allocated
+    let (allocations, positions, _) = solve_allocations(&Domineering::<5, 4>::new());
+    assert!(
+        (allocations as f64) < 0.01 * positions as f64,
+        "{allocations} allocations for {positions} positions"
+    );
+
+    // Nim's heaps are copied into the ones already allocated,
+    // so only the positions stored in the table (as its keys) allocate,
+    // each of which was looked for in it first
+    let (allocations, positions, probes) = solve_allocations(&Nim::new(vec![3, 4, 5]));
+    assert!(
+        allocations < probes as usize && allocations < positions as usize,
+        "{allocations} allocations for {positions} positions, and {probes} table probes"
+    );
+}
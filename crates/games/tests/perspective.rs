@@ -0,0 +1,111 @@
+use std::{collections::HashMap, fmt::Debug, hash::Hash};
+
+use game_solver::{
+    evaluate_for, evaluate_for_mover,
+    game::{Game, GameState},
+    move_scores,
+    player::TwoPlayer,
+    random::random_position,
+    solve,
+};
+use games::{
+    domineering::Domineering, naive_nim::Nim, tic_tac_toe::TicTacToe, util::notation::NotatedGame,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Checks the perspectives of the scores of `game` and of its moves.
+fn check_perspectives<T, P>(game: &T)
+where
+    T: Game<Player = P> + Eq + Hash + Debug,
+    P: TwoPlayer + Debug + 'static,
+    T::MoveError: Debug,
+{
+    let mut table = HashMap::new();
+    let mover = game.player();
+
+    let for_mover = evaluate_for_mover(game, &mut table, None, &None).unwrap();
+    let for_player = evaluate_for(game, mover, &mut table, None, &None).unwrap();
+    let for_other = evaluate_for(game, mover.other(), &mut table, None, &None).unwrap();
+
+    assert_eq!(for_mover, for_player, "{game:?}");
+    assert_eq!(for_player, -for_other, "{game:?}");
+
+    if game.state() != GameState::Playable {
+        return;
+    }
+
+    // the score of every move is for the player making it, so the best one is the score of the position
+    let scores = move_scores(game, &mut table, None, &None)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    for (m, score) in &scores {
+        let mut board = game.clone();
+        board.make_move(m).unwrap();
+
+        assert_eq!(
+            *score,
+            // impartial players are named relative to the position, which `turn` keeps up with
+            evaluate_for(&board, mover.turn(), &mut table, None, &None).unwrap(),
+            "{game:?}"
+        );
+    }
+    assert_eq!(
+        scores.iter().map(|(_, score)| *score).max(),
+        Some(for_mover),
+        "{game:?}"
+    );
+}
+
+/// Checks `start`, and positions reached from it by random play.
+fn check_random_positions<T, P>(start: &T)
+where
+    T: Game<Player = P> + Eq + Hash + Debug,
+    P: TwoPlayer + Debug + 'static,
+    T::MoveError: Debug,
+{
+    let mut rng = StdRng::seed_from_u64(1);
+
+    for plies in 0..6 {
+        let (game, _) = random_position(start, plies, &mut rng).unwrap();
+        check_perspectives(&game);
+    }
+}
+
+#[test]
+fn both_perspectives_negate() {
+    check_random_positions(&TicTacToe::new(2, 3));
+    check_random_positions(&Domineering::<4, 4>::new());
+    check_random_positions(&Nim::new(vec![2, 3, 4]));
+}
+
+#[test]
+fn an_odd_number_of_moves_scores_for_the_other_player() {
+    // a corner opening keeps the tie, and leaves O to move
+    let game = TicTacToe::from_notation("X../.../... O").unwrap();
+    let o = game.player();
+    let x = o.other();
+
+    let mut table = HashMap::new();
+    assert_eq!(evaluate_for(&game, x, &mut table, None, &None).unwrap(), 0);
+
+    // O's moves are scored for O, and X is to move after each of them, so solving them scores them for X
+    let scores = move_scores(&game, &mut table, None, &None)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    for (m, score) in scores {
+        let mut board = game.clone();
+        board.make_move(&m).unwrap();
+
+        assert_eq!(board.player(), x);
+        assert_eq!(
+            solve(&board, &mut table, None, &None).unwrap(),
+            -score,
+            "{m}"
+        );
+        assert_eq!(
+            evaluate_for(&board, o, &mut table, None, &None).unwrap(),
+            score,
+            "{m}"
+        );
+    }
+}
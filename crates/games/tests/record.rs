@@ -0,0 +1,64 @@
+use std::fmt::Debug;
+
+use clap::ValueEnum;
+use game_solver::{game::GameState, history::History};
+use games::{
+    chomp::Chomp,
+    naive_nim::Nim,
+    util::{
+        notation::NotatedGame,
+        record::{load_record, save_record, GameRecord, MoveNotation},
+    },
+    with_game, GameKind, GamePosition,
+};
+use rand::{rngs::StdRng, seq::IteratorRandom, SeedableRng};
+
+/// Plays random games from `start`, checking that each one's record reads back as the same game.
+fn check_records<T>(start: T, games: u64)
+where
+    T: MoveNotation + NotatedGame + Eq + Debug,
+    T::Move: PartialEq + Debug,
+    T::MoveError: Debug,
+    GamePosition: From<T>,
+{
+    for seed in 0..games {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut history = History::new(start.clone());
+
+        // stopping early records unfinished games too
+        let plies = (seed as usize * 7) % 40;
+        while history.game().state() == GameState::Playable && history.ply() < plies {
+            let m = history.game().possible_moves().choose(&mut rng).unwrap();
+            history.push(m).unwrap();
+        }
+
+        let record = save_record(&history);
+        let loaded = load_record::<T>(&record).unwrap_or_else(|err| panic!("{record}: {err}"));
+
+        assert_eq!(loaded.start(), history.start(), "{record}");
+        assert_eq!(loaded.moves(), history.moves(), "{record}");
+        assert_eq!(loaded.game(), history.game(), "{record}");
+        assert_eq!(save_record(&loaded), record);
+
+        let parsed = record.parse::<GameRecord>().unwrap();
+        assert_eq!(parsed, GameRecord::new(&history));
+        assert_eq!(parsed.moves.len(), history.ply());
+    }
+}
+
+#[test]
+fn every_game_round_trips_through_its_record() -> anyhow::Result<()> {
+    for &kind in GameKind::value_variants() {
+        with_game!(kind.default_game(), game => check_records(game, 8));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn records_of_one_game_are_not_read_as_another() {
+    let record = save_record(&History::new(Nim::new(vec![3, 5])));
+
+    assert!(load_record::<Nim>(&record).is_ok());
+    assert!(load_record::<Chomp>(&record).is_err());
+}
@@ -0,0 +1,82 @@
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    sync::{atomic::Ordering, Arc},
+    time::{Duration, Instant},
+};
+
+use game_solver::{
+    batch::{par_solve_many, BatchScore},
+    random::random_position,
+    solve,
+    transposition::TranspositionCache,
+};
+use games::tic_tac_toe::TicTacToe;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rayon::ThreadPoolBuilder;
+
+/// `count` positions reached by random play on a `size`x`size` board.
+fn random_positions(count: usize, size: usize) -> Vec<TicTacToe> {
+    let mut rng = StdRng::seed_from_u64(5);
+    let start = TicTacToe::new(2, size);
+
+    (0..count)
+        .map(|_| {
+            let plies = rng.gen_range(2..=size * size);
+            random_position(&start, plies, &mut rng).unwrap().0
+        })
+        .collect()
+}
+
+fn table() -> Arc<TranspositionCache<TicTacToe, RandomState>> {
+    Arc::new(TranspositionCache::with_capacity(1 << 20))
+}
+
+#[test]
+fn many_positions_are_solved_as_they_are_one_by_one() {
+    let positions = random_positions(200, 3);
+    let results = par_solve_many(&positions, &table(), None);
+
+    assert_eq!(results.len(), positions.len());
+    for (game, result) in positions.iter().zip(&results) {
+        let score = solve(game, &mut HashMap::new(), None, &None).unwrap();
+
+        match result.score {
+            BatchScore::Solved(solved) => assert_eq!(solved, score, "{game:?}"),
+            ref other => panic!("{game:?}: {other:?}"),
+        }
+    }
+
+    // every position was searched, and counted on its own
+    assert!(results
+        .iter()
+        .all(|result| result.stats.states_explored.load(Ordering::Relaxed) > 0));
+}
+
+#[test]
+fn a_past_deadline_leaves_every_position_unfinished() {
+    let positions = random_positions(20, 3);
+    let results = par_solve_many(&positions, &table(), Some(Instant::now()));
+
+    assert!(results
+        .iter()
+        .all(|result| matches!(result.score, BatchScore::Unfinished)));
+}
+
+#[test]
+fn a_hard_position_does_not_hold_up_the_rest() {
+    // an empty 5x5 board takes far longer to solve than the deadline allows
+    let mut positions = vec![TicTacToe::new(2, 5)];
+    positions.extend(random_positions(20, 3));
+
+    let pool = ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+    let started = Instant::now();
+    let results = pool
+        .install(|| par_solve_many(&positions, &table(), Some(started + Duration::from_secs(5))));
+
+    assert!(started.elapsed() < Duration::from_secs(30));
+    assert!(matches!(results[0].score, BatchScore::Unfinished));
+    assert!(results[0].stats.states_explored.load(Ordering::Relaxed) > 0);
+    for result in &results[1..] {
+        assert!(matches!(result.score, BatchScore::Solved(_)), "{result:?}");
+    }
+}
@@ -0,0 +1,158 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::{Debug, Display},
+    hash::{Hash, Hasher},
+    sync::{Arc, Mutex},
+};
+
+use game_solver::{
+    arena::{Arena, PolicyAgent},
+    game::{Game, GameStatus},
+    move_scores, par_move_scores_in,
+    player::TwoPlayer,
+    policy::RandomPolicy,
+};
+use games::{
+    chomp::Chomp, domineering::Domineering, naive_nim::Nim, tic_tac_toe::TicTacToe,
+    util::notation::NotatedGame,
+};
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+fn pool(threads: usize) -> ThreadPool {
+    ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .unwrap()
+}
+
+/// A game that records the size of every thread pool its moves are made on.
+#[derive(Clone)]
+struct Observed<T> {
+    game: T,
+    pools: Arc<Mutex<BTreeSet<usize>>>,
+}
+
+impl<T: PartialEq> PartialEq for Observed<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.game == other.game
+    }
+}
+
+impl<T: Eq> Eq for Observed<T> {}
+
+impl<T: Hash> Hash for Observed<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.game.hash(state);
+    }
+}
+
+impl<T: Game> Game for Observed<T> {
+    type Move = T::Move;
+    type Iter<'a>
+        = T::Iter<'a>
+    where
+        T: 'a;
+    type MoveError = T::MoveError;
+    type Player = T::Player;
+
+    fn move_count(&self) -> usize {
+        self.game.move_count()
+    }
+
+    fn max_moves(&self) -> Option<usize> {
+        self.game.max_moves()
+    }
+
+    fn make_move(&mut self, m: &Self::Move) -> Result<(), Self::MoveError> {
+        self.pools
+            .lock()
+            .unwrap()
+            .insert(rayon::current_num_threads());
+        self.game.make_move(m)
+    }
+
+    fn possible_moves(&self) -> Self::Iter<'_> {
+        self.game.possible_moves()
+    }
+
+    fn status(&self) -> GameStatus {
+        self.game.status()
+    }
+
+    fn player(&self) -> Self::Player {
+        self.game.player()
+    }
+}
+
+/// The score of every move of `game`, scored on a pool of `threads` threads.
+fn scores<T, P>(game: &T, threads: usize) -> Vec<(String, isize)>
+where
+    T: Game<Player = P> + Eq + Hash + Sync + Send + 'static,
+    P: TwoPlayer + Sync + 'static,
+    T::Move: Display + Sync + Send,
+    T::MoveError: Debug + Sync + Send,
+{
+    par_move_scores_in(&pool(threads), game, None, &None)
+        .into_iter()
+        .map(|score| {
+            let (m, score) = score.ok().unwrap();
+            (m.to_string(), score)
+        })
+        .collect()
+}
+
+/// Checks that `game` is scored the same on any thread pool, and as `move_scores` scores it.
+fn check_thread_counts<T, P>(game: &T)
+where
+    T: Game<Player = P> + Eq + Hash + Sync + Send + 'static,
+    P: TwoPlayer + Sync + 'static,
+    T::Move: Display + Sync + Send,
+    T::MoveError: Debug + Sync + Send,
+{
+    let sequential = move_scores(game, &mut HashMap::new(), None, &None)
+        .map(|score| {
+            let (m, score) = score.ok().unwrap();
+            (m.to_string(), score)
+        })
+        .collect::<Vec<_>>();
+
+    for threads in [1, 2, 8] {
+        assert_eq!(scores(game, threads), sequential, "{threads} threads");
+    }
+}
+
+#[test]
+fn scores_are_the_same_on_any_thread_count() {
+    check_thread_counts(&Nim::new(vec![3, 4, 5]));
+    check_thread_counts(&Chomp::new(5, 4));
+    check_thread_counts(&Domineering::<5, 4>::new());
+    check_thread_counts(&TicTacToe::from_notation("X../.O./... X").unwrap());
+}
+
+#[test]
+fn moves_are_scored_on_the_given_pool() {
+    for threads in [1, 3] {
+        let game = Observed {
+            game: Chomp::new(4, 3),
+            pools: Arc::default(),
+        };
+        scores(&game, threads);
+
+        assert_eq!(*game.pools.lock().unwrap(), BTreeSet::from([threads]));
+    }
+}
+
+#[test]
+fn arena_results_are_the_same_on_any_thread_count() {
+    let arena = Arena::new(30, 7);
+    let game = TicTacToe::new(2, 3);
+    let random = |seed| PolicyAgent(RandomPolicy::new(seed));
+
+    let results = arena.run(&game, random, random).unwrap();
+    for threads in [1, 2, 8] {
+        let pooled = pool(threads)
+            .install(|| arena.par_run(&game, random, random))
+            .unwrap();
+        assert_eq!(pooled, results, "{threads} threads");
+    }
+}
@@ -0,0 +1,80 @@
+use std::{collections::HashMap, hash::Hash};
+
+use game_solver::{
+    game::{Game, GameState},
+    optimal_moves,
+    player::TwoPlayer,
+    random::random_position,
+    solve, solve_full_window,
+    transposition::Score,
+};
+use games::{chomp::Chomp, domineering::Domineering};
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Positions reached by random play from `start`, from its first move to as deep as `max_plies`.
+fn positions<T: Game>(start: &T, max_plies: usize) -> Vec<T> {
+    let mut rng = StdRng::seed_from_u64(187);
+
+    (1..=max_plies)
+        .filter_map(|plies| Some(random_position(start, plies, &mut rng).ok()?.0))
+        .filter(|game| game.state() == GameState::Playable)
+        .collect()
+}
+
+/// Solves `positions` with one table, alternating between full and null window searches,
+/// and checks every score against that of a search with a table of its own.
+fn interleaved_searches_agree<T, P>(start: &T, positions: &[T])
+where
+    T: Game<Player = P> + Eq + Hash,
+    P: TwoPlayer + 'static,
+{
+    let score = |game: &T| match solve(game, &mut HashMap::new(), None, &None) {
+        Ok(score) => score,
+        Err(_) => panic!("the solver failed"),
+    };
+
+    let mut table = HashMap::<T, Score>::new();
+
+    for (index, game) in positions.iter().chain([start]).enumerate() {
+        let shared = if index % 2 == 0 {
+            solve_full_window(game, &mut table, None, &None)
+        } else {
+            solve(game, &mut table, None, &None)
+        };
+        let Ok(shared) = shared else {
+            panic!("the solver failed");
+        };
+        assert_eq!(shared, score(game), "position {index}");
+
+        // whatever the windows its entries were stored with, the table scores every move right
+        let Ok(optimal) = optimal_moves(game, &mut table) else {
+            panic!("the solver failed");
+        };
+        let Ok(expected) = optimal_moves(game, &mut HashMap::new()) else {
+            panic!("the solver failed");
+        };
+        assert_eq!(optimal.len(), expected.len(), "position {index}");
+    }
+
+    // the bounds stored by every search hold for the exact scores
+    for (game, entry) in &table {
+        let exact = score(game);
+        match *entry {
+            Score::Exact(score) => assert_eq!(score, exact),
+            Score::LowerBound(min) => assert!(exact >= min),
+            Score::UpperBound(max) => assert!(exact <= max),
+        }
+    }
+}
+
+#[test]
+fn chomp_searches_share_a_table() {
+    let start = Chomp::new(4, 4);
+    interleaved_searches_agree(&start, &positions(&start, 8));
+}
+
+#[test]
+fn domineering_searches_share_a_table() {
+    let start = Domineering::<4, 4>::new();
+    interleaved_searches_agree(&start, &positions(&start, 5));
+}
@@ -24,10 +24,8 @@ pub fn mex(list: &[Nimber]) -> Option<Nimber> {
     let mut set: HashSet<Nimber> = HashSet::with_capacity(list.len());
     
     for item in list {
-        if set.insert(*item) {
-            if item > &mex.unwrap_or(Nimber(0)) {
-                mex = Some(*item)
-            }
+        if set.insert(*item) && item > &mex.unwrap_or(Nimber(0)) {
+            mex = Some(*item)
         }
     }
 
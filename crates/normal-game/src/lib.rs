@@ -30,23 +30,23 @@ pub trait Game {
 
     /// Gets the ordering of a game to another game
     /// Games define a partial ordering, and not a total ordering.
-    fn partial_cmp(&self, other: &Box<dyn Game>) -> Option<Ordering>;
+    fn partial_cmp(&self, other: &dyn Game) -> Option<Ordering>;
 
     /// Checks if two games are equal.
-    fn eq(&self, other: &Box<dyn Game>) -> bool {
+    fn eq(&self, other: &dyn Game) -> bool {
         self.partial_cmp(other) == Some(Ordering::Equal)
     }
 }
 
 impl PartialEq for Box<dyn Game> {
     fn eq(&self, other: &Self) -> bool {
-        Game::eq(self.as_ref(), &Box::new(other))
+        Game::eq(self.as_ref(), other.as_ref())
     }
 }
 
 impl PartialOrd for Box<dyn Game> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Game::partial_cmp(self.as_ref(), &Box::new(other))
+        Game::partial_cmp(self.as_ref(), other.as_ref())
     }
 }
 
@@ -113,7 +113,7 @@ impl Game for VecGame {
         unimplemented!()
     }
 
-    fn disjinctive_sum(&self, g: Box<dyn Game>) -> Box<dyn Game> {
+    fn disjinctive_sum(&self, _g: Box<dyn Game>) -> Box<dyn Game> {
         unimplemented!()
     }
 
@@ -128,7 +128,7 @@ impl Game for VecGame {
         ))
     }
 
-    fn partial_cmp(&self, other: &Box<dyn Game>) -> Option<Ordering> {
+    fn partial_cmp(&self, _other: &dyn Game) -> Option<Ordering> {
         unimplemented!()
     }
 }
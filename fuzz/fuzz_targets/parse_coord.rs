@@ -0,0 +1,27 @@
+#![no_main]
+
+use games::util::parse::{column_name, parse_column, parse_coord, parse_move_list};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (u8, u8, &str)| {
+    let (width, height, token) = (input.0 as usize, input.1 as usize, input.2);
+
+    if let Ok((x, y)) = parse_coord(token, width, height) {
+        assert!(x < width && y < height, "{token:?} is off the board");
+
+        // both styles describe the same square
+        assert_eq!(parse_coord(&format!("{x}-{y}"), width, height), Ok((x, y)));
+        assert_eq!(
+            parse_coord(&format!("{}{}", column_name(x), y + 1), width, height),
+            Ok((x, y))
+        );
+    }
+
+    if let Ok(column) = parse_column(token) {
+        assert_eq!(parse_column(&column_name(column)), Ok(column));
+    }
+
+    if let Ok(moves) = parse_move_list(token, |m| parse_coord(m, width, height)) {
+        assert!(moves.iter().all(|&(x, y)| x < width && y < height));
+    }
+});
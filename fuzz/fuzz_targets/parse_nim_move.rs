@@ -0,0 +1,13 @@
+#![no_main]
+
+use games::{naive_nim::Nim, util::parse::ParseMove};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|token: &str| {
+    let game = Nim::new(vec![3, 5, 7]);
+
+    if let Ok(m) = game.parse_move(token) {
+        // accepted moves read back from how they're written
+        assert_eq!(game.parse_move(&m.to_string()), Ok(m));
+    }
+});
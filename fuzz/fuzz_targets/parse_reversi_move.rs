@@ -0,0 +1,12 @@
+#![no_main]
+
+use games::reversi::{parse_reversi_move, HEIGHT, WIDTH};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|token: &str| {
+    if let Ok(m) = parse_reversi_move(token) {
+        // accepted moves are on the board, and read back from how they're written
+        assert!(m.0[0] < WIDTH && m.0[1] < HEIGHT, "{token:?} is off the board");
+        assert_eq!(parse_reversi_move(&m.to_string()), Ok(m));
+    }
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+use games::util::invariants::check_any_game;
+use libfuzzer_sys::fuzz_target;
+
+// the first byte picks the game, and every other byte picks a move
+fuzz_target!(|bytes: &[u8]| {
+    if let Err(err) = check_any_game(bytes) {
+        panic!("{err}");
+    }
+});